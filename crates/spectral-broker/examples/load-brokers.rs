@@ -3,17 +3,10 @@
 use spectral_broker::{BrokerLoader, BrokerRegistry};
 
 fn main() -> Result<(), Box<dyn std::error::Error>> {
-    // Try to load from the default directory (broker-definitions/)
-    println!("Loading broker definitions from broker-definitions/...\n");
+    // Load embedded definitions, overridden by broker-definitions/ when present
+    println!("Loading broker definitions...\n");
 
-    let loader = match BrokerLoader::with_default_dir() {
-        Ok(loader) => loader,
-        Err(e) => {
-            eprintln!("Error: {}", e);
-            eprintln!("\nMake sure you're running this from the workspace root!");
-            return Err(Box::new(e));
-        }
-    };
+    let loader = BrokerLoader::with_default_dir()?;
 
     // Load all brokers
     let definitions = loader.load_all()?;
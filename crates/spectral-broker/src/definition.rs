@@ -3,10 +3,13 @@
 //! This module defines the data structures for broker definitions loaded from TOML files.
 
 use crate::error::{BrokerError, Result};
+use crate::removal::api::render_template;
+use crate::removal::email;
 use chrono::NaiveDate;
 use serde::{Deserialize, Serialize};
-use spectral_core::{BrokerId, PiiField};
-use std::collections::HashMap;
+use spectral_core::{normalize_country, BrokerId, PiiField};
+use std::collections::{BTreeMap, HashMap};
+use std::path::PathBuf;
 
 /// Complete broker definition loaded from TOML.
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -19,6 +22,14 @@ pub struct BrokerDefinition {
 
     /// Removal/opt-out configuration
     pub removal: RemovalMethod,
+
+    /// Bundled HTML fixture for checking result selectors offline
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fixture: Option<SelectorFixture>,
+
+    /// How to check afterwards that a removal took effect
+    #[serde(default)]
+    pub confirmation: RemovalConfirmation,
 }
 
 impl BrokerDefinition {
@@ -40,6 +51,74 @@ impl BrokerDefinition {
         self.broker.category
     }
 
+    /// Get the IDs of sibling brokers declared by this definition.
+    #[must_use]
+    pub fn related_brokers(&self) -> &[BrokerId] {
+        &self.broker.related_brokers
+    }
+
+    /// Check whether this broker lists residents of the given country.
+    ///
+    /// Country names and aliases are normalized before comparison.
+    #[must_use]
+    pub fn covers_country(&self, country: &str) -> bool {
+        if self.broker.countries.is_empty() {
+            return true;
+        }
+        let country = normalize_country(country);
+        self.broker
+            .countries
+            .iter()
+            .any(|covered| normalize_country(covered) == country)
+    }
+
+    /// Where the user completes an ID-verified removal, if this broker
+    /// requires one.
+    ///
+    /// This is the opt-out form for form-based removals and the broker's
+    /// website otherwise.
+    #[must_use]
+    pub fn id_verification_url(&self) -> Option<&str> {
+        self.broker
+            .requires_id_verification
+            .then(|| self.opt_out_url())
+    }
+
+    /// Where the user signs in to finish a removal, if this broker only
+    /// accepts removals from account holders.
+    ///
+    /// Chosen like [`Self::id_verification_url`].
+    #[must_use]
+    pub fn account_required_url(&self) -> Option<&str> {
+        self.broker.requires_account.then(|| self.opt_out_url())
+    }
+
+    /// Extra HTTP headers sent with every page fetched from this broker.
+    #[must_use]
+    pub fn request_headers(&self) -> &HashMap<String, String> {
+        &self.broker.request_headers
+    }
+
+    /// Where to send a formal legal deletion request: the broker's
+    /// `legal_contact`, falling back to the address of an email removal.
+    #[must_use]
+    pub fn legal_contact(&self) -> Option<&str> {
+        match (&self.broker.legal_contact, &self.removal) {
+            (Some(contact), _) | (None, RemovalMethod::Email { email: contact, .. }) => {
+                Some(contact)
+            }
+            (None, _) => None,
+        }
+    }
+
+    /// The opt-out form for form-based removals, the broker's website otherwise.
+    fn opt_out_url(&self) -> &str {
+        match &self.removal {
+            RemovalMethod::WebForm { url, .. } | RemovalMethod::BrowserForm { url, .. } => url,
+            _ => &self.broker.url,
+        }
+    }
+
     /// Validate the broker definition for completeness and correctness.
     pub fn validate(&self) -> Result<()> {
         // Validate broker metadata
@@ -79,12 +158,62 @@ impl BrokerDefinition {
             });
         }
 
+        // Validate related brokers; cross-references are checked by the loader
+        for (i, related) in self.broker.related_brokers.iter().enumerate() {
+            if related == &self.broker.id {
+                return Err(BrokerError::ValidationError {
+                    broker_id: self.broker.id.to_string(),
+                    reason: "related_brokers cannot include the broker itself".to_string(),
+                });
+            }
+            if self.broker.related_brokers[..i].contains(related) {
+                return Err(BrokerError::ValidationError {
+                    broker_id: self.broker.id.to_string(),
+                    reason: format!("related_brokers lists {related} more than once"),
+                });
+            }
+        }
+
+        // Validate request headers
+        for (name, value) in &self.broker.request_headers {
+            if !is_header_name(name) {
+                return Err(BrokerError::ValidationError {
+                    broker_id: self.broker.id.to_string(),
+                    reason: format!("request_headers has an invalid header name {name:?}"),
+                });
+            }
+            if value.chars().any(char::is_control) {
+                return Err(BrokerError::ValidationError {
+                    broker_id: self.broker.id.to_string(),
+                    reason: format!("request_headers value for {name} has control characters"),
+                });
+            }
+        }
+
+        if let Some(contact) = &self.broker.legal_contact {
+            if !is_email_address(contact) {
+                return Err(BrokerError::ValidationError {
+                    broker_id: self.broker.id.to_string(),
+                    reason: format!("legal_contact {contact:?} is not an email address"),
+                });
+            }
+        }
+
         // Validate search method
         self.search.validate(&self.broker.id)?;
 
         // Validate removal method
         self.removal.validate(&self.broker.id)?;
 
+        if let RemovalConfirmation::OnSite { url, .. } = &self.confirmation {
+            if url.trim().is_empty() {
+                return Err(BrokerError::ValidationError {
+                    broker_id: self.broker.id.to_string(),
+                    reason: "on-site confirmation URL cannot be empty".to_string(),
+                });
+            }
+        }
+
         Ok(())
     }
 }
@@ -126,12 +255,64 @@ pub struct BrokerMetadata {
     /// Geographic regions where this broker is relevant
     #[serde(default = "default_region_relevance")]
     pub region_relevance: Vec<String>,
+
+    /// ISO 3166-1 alpha-2 codes of countries whose residents this broker
+    /// lists. Empty means the broker is not restricted to particular countries.
+    #[serde(default)]
+    pub countries: Vec<String>,
+
+    /// Whether the broker only processes removals after the user uploads a
+    /// government ID. These removals are left to the user, never automated.
+    #[serde(default)]
+    pub requires_id_verification: bool,
+
+    /// Whether the broker only processes removals for signed-in account
+    /// holders. These removals are left to the user, never automated.
+    #[serde(default)]
+    pub requires_account: bool,
+
+    /// Brokers run by the same operator or sharing its data, such that a
+    /// listing here usually means a listing there too
+    #[serde(default)]
+    pub related_brokers: Vec<BrokerId>,
+
+    /// Extra HTTP headers, such as a `Referer`, that this broker's pages
+    /// expect. Sent on top of the default headers when fetching its pages.
+    #[serde(default)]
+    pub request_headers: HashMap<String, String>,
+
+    /// Address for formal privacy or legal requests, such as the broker's
+    /// privacy officer. Used when routine opt-outs keep failing.
+    #[serde(default)]
+    pub legal_contact: Option<String>,
 }
 
 fn default_region_relevance() -> Vec<String> {
     vec!["Global".to_string()]
 }
 
+/// Whether `name` is a valid HTTP header name (an RFC 9110 token).
+fn is_header_name(name: &str) -> bool {
+    !name.is_empty()
+        && name
+            .bytes()
+            .all(|b| b.is_ascii_alphanumeric() || b"!#$%&'*+-.^_`|~".contains(&b))
+}
+
+/// Whether `address` looks like a single email address: one `@` with text on
+/// both sides and a dot in the domain, without spaces or control characters.
+fn is_email_address(address: &str) -> bool {
+    let Some((local, domain)) = address.split_once('@') else {
+        return false;
+    };
+    !local.is_empty()
+        && domain.contains('.')
+        && !domain.starts_with('.')
+        && !domain.ends_with('.')
+        && !domain.contains('@')
+        && !address.chars().any(|c| c.is_whitespace() || c.is_control())
+}
+
 /// Categories of data brokers.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 #[serde(rename_all = "kebab-case")]
@@ -184,6 +365,23 @@ impl BrokerCategory {
             Self::Other => "Other",
         }
     }
+
+    /// Relative weight of a listing in this category when breaking down
+    /// exposure, with people search as the 1.0 baseline.
+    ///
+    /// Background check and financial listings are weighted higher because
+    /// they are used for screening and credit decisions; marketing lists and
+    /// public records carry less additional risk.
+    #[must_use]
+    pub fn risk_weight(&self) -> f64 {
+        match self {
+            Self::BackgroundCheck | Self::Financial => 1.5,
+            Self::DataAggregator => 1.25,
+            Self::PeopleSearch | Self::Other => 1.0,
+            Self::GovernmentRecords | Self::SocialMedia => 0.75,
+            Self::Marketing => 0.5,
+        }
+    }
 }
 
 /// Difficulty level for removal from a broker.
@@ -262,12 +460,84 @@ pub struct ResultSelectors {
     /// Email addresses field selector
     #[serde(skip_serializing_if = "Option::is_none")]
     pub emails: Option<String>,
+    /// The broker's own match-strength label, e.g. "Likely match" or "92% match"
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub match_indicator: Option<String>,
     /// Indicator that no results were found
     #[serde(skip_serializing_if = "Option::is_none")]
     pub no_results_indicator: Option<String>,
     /// CAPTCHA detection
     #[serde(skip_serializing_if = "Option::is_none")]
     pub captcha_required: Option<String>,
+    /// Link to the next page of results, for brokers that paginate
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub next_page: Option<String>,
+    /// Maximum number of result pages to follow (defaults to [`DEFAULT_MAX_RESULT_PAGES`])
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_pages: Option<u32>,
+}
+
+/// Number of result pages followed when a definition sets `next_page` but no `max_pages`.
+pub const DEFAULT_MAX_RESULT_PAGES: u32 = 5;
+
+impl ResultSelectors {
+    /// Maximum number of result pages to parse, including the first.
+    ///
+    /// Always at least one. Without a `next_page` selector only the first
+    /// page is ever parsed.
+    #[must_use]
+    pub fn page_limit(&self) -> u32 {
+        if self.next_page.is_none() {
+            return 1;
+        }
+        self.max_pages.unwrap_or(DEFAULT_MAX_RESULT_PAGES).max(1)
+    }
+}
+
+/// How a broker shows that a removal has gone through.
+///
+/// Unlike [`ConfirmationType`], which describes the broker's response to the
+/// submission, this describes how to check later that the listing is
+/// actually gone.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(tag = "method", rename_all = "kebab-case")]
+pub enum RemovalConfirmation {
+    /// Nothing to check automatically
+    #[default]
+    None,
+
+    /// The broker emails the user once the listing is removed
+    Email,
+
+    /// A page on the broker's site shows whether the listing is still up
+    #[serde(rename = "on-site")]
+    OnSite {
+        /// Page to fetch, as a `{field}` template (usually the listing URL)
+        #[serde(default = "default_confirmation_url")]
+        url: String,
+        /// Text the page shows once the listing is removed, for brokers that
+        /// keep serving the page instead of returning 404
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        removed_text: Option<String>,
+    },
+}
+
+fn default_confirmation_url() -> String {
+    "{found_listing_url}".to_string()
+}
+
+/// Bundled search-results page used to check that result selectors still work.
+///
+/// Fixtures must be small and contain only made-up, PII-free data.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SelectorFixture {
+    /// Path to the HTML fixture, relative to the definition file
+    pub path: PathBuf,
+    /// Number of listings the selectors should extract from the fixture
+    pub expected_results: usize,
+    /// Field selectors that must match in every listing (e.g. `name`, `age`)
+    #[serde(default)]
+    pub expected_fields: Vec<String>,
 }
 
 impl SearchMethod {
@@ -285,6 +555,15 @@ impl SearchMethod {
         }
     }
 
+    /// Whether searching needs a browser rather than a plain HTTP fetch.
+    ///
+    /// URL templates produce a results page that can be fetched directly;
+    /// web forms have to be filled in and submitted.
+    #[must_use]
+    pub fn requires_browser(&self) -> bool {
+        matches!(self, Self::WebForm { .. })
+    }
+
     /// Validate the search method configuration.
     fn validate(&self, broker_id: &BrokerId) -> Result<()> {
         match self {
@@ -407,6 +686,10 @@ pub struct FormSelectors {
     /// Selector for error message indicator
     #[serde(skip_serializing_if = "Option::is_none")]
     pub error_indicator: Option<String>,
+
+    /// Selector for a login or signup wall shown instead of the opt-out form
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub account_wall: Option<String>,
 }
 
 /// Methods for removal/opt-out from a broker.
@@ -471,6 +754,30 @@ pub enum RemovalMethod {
         notes: String,
     },
 
+    /// Official opt-out API provided by the broker
+    #[serde(rename = "api")]
+    Api {
+        /// URL of the deletion endpoint
+        endpoint: String,
+        /// HTTP method used for the request (`method` is the removal tag)
+        #[serde(default)]
+        http_method: ApiMethod,
+        /// Authentication sent with the request
+        #[serde(default)]
+        auth: ApiAuth,
+        /// Request body fields (e.g., "`email_address`" -> "`{email}`")
+        field_mapping: HashMap<String, String>,
+        /// Encoding of the request body
+        #[serde(default)]
+        body_format: ApiBodyFormat,
+        /// Confirmation method
+        #[serde(default)]
+        confirmation: ConfirmationType,
+        /// Additional notes or instructions
+        #[serde(default)]
+        notes: String,
+    },
+
     /// Manual process with instructions
     Manual {
         /// Instructions for manual removal
@@ -478,7 +785,155 @@ pub enum RemovalMethod {
     },
 }
 
+/// HTTP method for an opt-out API request.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "UPPERCASE")]
+pub enum ApiMethod {
+    /// POST request
+    #[default]
+    Post,
+    /// PUT request
+    Put,
+    /// DELETE request
+    Delete,
+}
+
+/// Authentication for an opt-out API request.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(tag = "type", rename_all = "kebab-case")]
+pub enum ApiAuth {
+    /// No authentication
+    #[default]
+    None,
+    /// `Authorization: Bearer <token>` header
+    Bearer {
+        /// Token issued by the broker for opt-out requests
+        token: String,
+    },
+    /// Custom header carrying an API key
+    Header {
+        /// Header name (e.g., "X-Api-Key")
+        name: String,
+        /// Header value
+        value: String,
+    },
+}
+
+/// Encoding of an opt-out API request body.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "kebab-case")]
+pub enum ApiBodyFormat {
+    /// `application/json`
+    #[default]
+    Json,
+    /// `application/x-www-form-urlencoded`
+    Form,
+}
+
+/// A removal request rendered with sample field values.
+///
+/// Lets the broker explorer show what an opt-out will send without a real
+/// profile.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "kind", rename_all = "kebab-case")]
+pub enum RemovalPreview {
+    /// Email that would be sent to the broker
+    Email {
+        /// Broker's removal address
+        to: String,
+        /// Rendered subject
+        subject: String,
+        /// Rendered body
+        body: String,
+    },
+
+    /// Form or API request with its fields filled in
+    Form {
+        /// Form URL or API endpoint
+        url: String,
+        /// Rendered field values, by field name
+        fields: BTreeMap<String, String>,
+    },
+
+    /// Phone or manual removal, which has nothing to render
+    Instructions {
+        /// Instructions shown to the user
+        text: String,
+    },
+}
+
 impl RemovalMethod {
+    /// Render this removal method's templates with `sample_fields`.
+    ///
+    /// Renders with the same engine as a real submission: emails with the
+    /// `{{field}}` renderer that sends them, and forms and API requests with
+    /// `{field}` substitution. A preview fails exactly where sending with a
+    /// profile lacking those fields would.
+    ///
+    /// # Errors
+    /// Returns a description of the problem if a template names a field
+    /// missing from `sample_fields`, a form template has an unterminated
+    /// placeholder, or an email preview has no `email` sample field.
+    pub fn render_preview(
+        &self,
+        sample_fields: &HashMap<String, String>,
+    ) -> std::result::Result<RemovalPreview, String> {
+        let render = |template: &str| render_template(template, sample_fields);
+        let render_fields = |fields: &HashMap<String, String>| {
+            fields
+                .iter()
+                .map(|(name, template)| Ok((name.clone(), render(template)?)))
+                .collect::<std::result::Result<BTreeMap<_, _>, String>>()
+        };
+
+        Ok(match self {
+            Self::Email {
+                email: to, body, ..
+            } => {
+                let available: Vec<&str> = sample_fields.keys().map(String::as_str).collect();
+                email::validate_template(body, &available).map_err(|unresolved| {
+                    format!(
+                        "Email template has unresolved placeholders: {}",
+                        unresolved.join(", ")
+                    )
+                })?;
+                let user_email = sample_fields
+                    .get("email")
+                    .ok_or("Missing required field: email")?;
+
+                let rendered = email::render_template(body, user_email, to, sample_fields);
+                RemovalPreview::Email {
+                    to: rendered.to,
+                    subject: rendered.subject,
+                    body: rendered.body,
+                }
+            }
+            Self::WebForm { url, fields, .. } | Self::BrowserForm { url, fields, .. } => {
+                RemovalPreview::Form {
+                    url: url.clone(),
+                    fields: render_fields(fields)?,
+                }
+            }
+            Self::Api {
+                endpoint,
+                field_mapping,
+                ..
+            } => RemovalPreview::Form {
+                url: endpoint.clone(),
+                fields: render_fields(field_mapping)?,
+            },
+            Self::Phone {
+                phone,
+                instructions,
+            } => RemovalPreview::Instructions {
+                text: format!("Call {phone}. {instructions}"),
+            },
+            Self::Manual { instructions } => RemovalPreview::Instructions {
+                text: instructions.clone(),
+            },
+        })
+    }
+
     /// Validate the removal method configuration.
     fn validate(&self, broker_id: &BrokerId) -> Result<()> {
         match self {
@@ -500,6 +955,11 @@ impl RemovalMethod {
                 instructions,
             } => Self::validate_phone(broker_id, phone, instructions),
             Self::BrowserForm { url, .. } => Self::validate_browser_form(broker_id, url),
+            Self::Api {
+                endpoint,
+                field_mapping,
+                ..
+            } => Self::validate_api(broker_id, endpoint, field_mapping),
             Self::Manual { instructions } => Self::validate_manual(broker_id, instructions),
         }
     }
@@ -594,6 +1054,27 @@ impl RemovalMethod {
         Ok(())
     }
 
+    fn validate_api(
+        broker_id: &BrokerId,
+        endpoint: &str,
+        field_mapping: &HashMap<String, String>,
+    ) -> Result<()> {
+        // The request body carries decrypted profile fields
+        if !endpoint.starts_with("https://") {
+            return Err(BrokerError::ValidationError {
+                broker_id: broker_id.to_string(),
+                reason: "API removal requires an https:// endpoint".to_string(),
+            });
+        }
+        if field_mapping.is_empty() {
+            return Err(BrokerError::ValidationError {
+                broker_id: broker_id.to_string(),
+                reason: "API removal requires field_mapping".to_string(),
+            });
+        }
+        Ok(())
+    }
+
     fn validate_manual(broker_id: &BrokerId, instructions: &str) -> Result<()> {
         if instructions.is_empty() {
             return Err(BrokerError::ValidationError {
@@ -622,6 +1103,18 @@ pub enum ConfirmationType {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_broker_category_risk_weight() {
+        assert!((BrokerCategory::PeopleSearch.risk_weight() - 1.0).abs() < f64::EPSILON);
+        assert!(
+            BrokerCategory::BackgroundCheck.risk_weight()
+                > BrokerCategory::PeopleSearch.risk_weight()
+        );
+        assert!(
+            BrokerCategory::Marketing.risk_weight() < BrokerCategory::PeopleSearch.risk_weight()
+        );
+    }
+
     #[test]
     fn test_broker_category_display() {
         assert_eq!(BrokerCategory::PeopleSearch.display_name(), "People Search");
@@ -666,6 +1159,30 @@ mod tests {
         assert!(method.validate(&broker_id).is_err());
     }
 
+    #[test]
+    fn test_only_web_form_search_requires_browser() {
+        let template = SearchMethod::UrlTemplate {
+            template: "https://example.com/{first}-{last}".to_string(),
+            requires_fields: vec![PiiField::FirstName, PiiField::LastName],
+            result_selectors: None,
+        };
+        assert!(!template.requires_browser());
+
+        let form = SearchMethod::WebForm {
+            url: "https://example.com/search".to_string(),
+            fields: HashMap::from([("first".to_string(), "{first_name}".to_string())]),
+            requires_fields: vec![PiiField::FirstName],
+            result_selectors: None,
+        };
+        assert!(form.requires_browser());
+
+        let manual = SearchMethod::Manual {
+            url: "https://example.com/search".to_string(),
+            instructions: "Search by name".to_string(),
+        };
+        assert!(!manual.requires_browser());
+    }
+
     #[test]
     fn test_removal_method_validation() {
         let broker_id = BrokerId::new("test-broker").expect("valid broker ID");
@@ -683,6 +1200,7 @@ mod tests {
             captcha_frame: None,
             success_indicator: Some(".success".to_string()),
             error_indicator: None,
+            account_wall: None,
         };
         let method = RemovalMethod::WebForm {
             url: "https://example.com/optout".to_string(),
@@ -706,6 +1224,7 @@ mod tests {
             captcha_frame: None,
             success_indicator: Some(".success".to_string()),
             error_indicator: None,
+            account_wall: None,
         };
         let method = RemovalMethod::WebForm {
             url: String::new(),
@@ -737,6 +1256,129 @@ mod tests {
         assert!(method.validate(&broker_id).is_err());
     }
 
+    fn sample_fields() -> HashMap<String, String> {
+        [
+            ("full_name", "Jane Sample"),
+            ("first_name", "Jane"),
+            ("last_name", "Sample"),
+            ("email", "jane@example.com"),
+            ("found_listing_url", "https://broker.example.com/p/123"),
+        ]
+        .into_iter()
+        .map(|(name, value)| (name.to_string(), value.to_string()))
+        .collect()
+    }
+
+    #[test]
+    fn test_email_preview_substitutes_sample_fields() {
+        let method = RemovalMethod::Email {
+            email: "privacy@example.com".to_string(),
+            subject: "Removal Request".to_string(),
+            body: "Please remove {{full_name}}, reachable at {{email}}.".to_string(),
+            response_days: 7,
+            notes: String::new(),
+        };
+
+        // The subject is the one sent emails use, not the definition's
+        assert_eq!(
+            method.render_preview(&sample_fields()),
+            Ok(RemovalPreview::Email {
+                to: "privacy@example.com".to_string(),
+                subject: "Opt-Out Request — Jane Sample".to_string(),
+                body: "Please remove Jane Sample, reachable at jane@example.com.".to_string(),
+            })
+        );
+    }
+
+    #[test]
+    fn test_form_preview_substitutes_sample_fields() {
+        let method = RemovalMethod::WebForm {
+            url: "https://broker.example.com/optout".to_string(),
+            fields: HashMap::from([
+                ("listing_url".to_string(), "{found_listing_url}".to_string()),
+                ("name".to_string(), "{first_name} {last_name}".to_string()),
+            ]),
+            form_selectors: FormSelectors::default(),
+            confirmation: ConfirmationType::Automatic,
+            notes: String::new(),
+        };
+
+        let Ok(RemovalPreview::Form { url, fields }) = method.render_preview(&sample_fields())
+        else {
+            panic!("expected a form preview");
+        };
+        assert_eq!(url, "https://broker.example.com/optout");
+        assert_eq!(fields["listing_url"], "https://broker.example.com/p/123");
+        assert_eq!(fields["name"], "Jane Sample");
+    }
+
+    #[test]
+    fn test_preview_reports_fields_missing_from_sample() {
+        let method = RemovalMethod::Email {
+            email: "privacy@example.com".to_string(),
+            subject: "Removal Request".to_string(),
+            body: "Born {{date_of_birth}}".to_string(),
+            response_days: 7,
+            notes: String::new(),
+        };
+
+        let err = method
+            .render_preview(&sample_fields())
+            .expect_err("date_of_birth is not in the sample");
+        assert!(err.contains("date_of_birth"));
+    }
+
+    #[test]
+    fn test_api_removal_method_parses_and_validates() {
+        let broker_id = BrokerId::new("test-broker").expect("valid broker ID");
+        let toml_str = r#"
+            method = "api"
+            endpoint = "https://api.example.com/v1/optout"
+            body_format = "form"
+
+            [auth]
+            type = "header"
+            name = "X-Api-Key"
+            value = "public-optout-key"
+
+            [field_mapping]
+            email_address = "{email}"
+            profile_url = "{listing_url}"
+        "#;
+
+        let method: RemovalMethod = toml::from_str(toml_str).expect("parse api removal");
+        let RemovalMethod::Api {
+            http_method,
+            auth,
+            field_mapping,
+            body_format,
+            confirmation,
+            ..
+        } = &method
+        else {
+            panic!("expected Api removal method");
+        };
+        assert_eq!(*http_method, ApiMethod::Post);
+        assert!(matches!(auth, ApiAuth::Header { name, .. } if name == "X-Api-Key"));
+        assert_eq!(field_mapping.len(), 2);
+        assert_eq!(*body_format, ApiBodyFormat::Form);
+        assert_eq!(*confirmation, ConfirmationType::Automatic);
+        assert!(method.validate(&broker_id).is_ok());
+
+        for endpoint in ["ftp://example.com", "http://api.example.com/v1/optout"] {
+            let method = RemovalMethod::Api {
+                endpoint: endpoint.to_string(),
+                http_method: ApiMethod::Post,
+                auth: ApiAuth::None,
+                field_mapping: HashMap::from([("email".to_string(), "{email}".to_string())]),
+                body_format: ApiBodyFormat::Json,
+                confirmation: ConfirmationType::Automatic,
+                notes: String::new(),
+            };
+            assert!(method.validate(&broker_id).is_err(), "{endpoint}");
+        }
+    }
+
     #[test]
     fn test_broker_definition_validation() {
         let broker_id = BrokerId::new("test-broker").expect("valid broker ID");
@@ -753,6 +1395,7 @@ mod tests {
             captcha_frame: None,
             success_indicator: Some(".success".to_string()),
             error_indicator: None,
+            account_wall: None,
         };
 
         let definition = BrokerDefinition {
@@ -768,6 +1411,12 @@ mod tests {
                 last_verified: NaiveDate::from_ymd_opt(2025, 5, 1).expect("valid date"),
                 scan_priority: ScanPriority::OnRequest,
                 region_relevance: vec!["Global".to_string()],
+                countries: vec![],
+                requires_id_verification: false,
+                requires_account: false,
+                related_brokers: vec![],
+                request_headers: HashMap::new(),
+                legal_contact: None,
             },
             search: SearchMethod::UrlTemplate {
                 template: "https://test.com/{first}-{last}".to_string(),
@@ -781,6 +1430,8 @@ mod tests {
                 confirmation: ConfirmationType::EmailVerification,
                 notes: String::new(),
             },
+            fixture: None,
+            confirmation: RemovalConfirmation::None,
         };
 
         assert!(definition.validate().is_ok());
@@ -795,12 +1446,136 @@ mod tests {
         invalid_def.broker.recheck_interval_days = 500;
         assert!(invalid_def.validate().is_err());
 
+        // Test related_brokers listing the broker itself
+        let mut invalid_def = definition.clone();
+        invalid_def.broker.related_brokers = vec![broker_id.clone()];
+        assert!(invalid_def.validate().is_err());
+
+        // Test related_brokers listing a sibling twice
+        let mut invalid_def = definition.clone();
+        let sibling = BrokerId::new("sibling").expect("valid broker ID");
+        invalid_def.broker.related_brokers = vec![sibling.clone(), sibling];
+        assert!(invalid_def.validate().is_err());
+
+        // Test a header name that is not a token
+        let mut invalid_def = definition.clone();
+        invalid_def.broker.request_headers =
+            HashMap::from([("Bad Header".to_string(), "x".to_string())]);
+        assert!(invalid_def.validate().is_err());
+
+        // Test a header value that would split the request
+        let mut invalid_def = definition.clone();
+        invalid_def.broker.request_headers = HashMap::from([(
+            "Referer".to_string(),
+            "https://test.com/\r\nX-Injected: 1".to_string(),
+        )]);
+        assert!(invalid_def.validate().is_err());
+
         // Test empty name
         let mut invalid_def = definition;
         invalid_def.broker.name = String::new();
         assert!(invalid_def.validate().is_err());
     }
 
+    #[test]
+    fn test_request_headers_parse() {
+        let toml = r#"
+            [broker]
+            id = "test-broker"
+            name = "Test Broker"
+            url = "https://example.com"
+            domain = "example.com"
+            category = "people-search"
+            difficulty = "Easy"
+            typical_removal_days = 7
+            recheck_interval_days = 30
+            last_verified = "2025-01-01"
+
+            [broker.request_headers]
+            Referer = "https://example.com/search"
+            X-Requested-With = "XMLHttpRequest"
+
+            [search]
+            method = "url-template"
+            template = "https://example.com/{first}-{last}"
+            requires_fields = ["first_name", "last_name"]
+
+            [removal]
+            method = "manual"
+            instructions = "Manual removal"
+        "#;
+
+        let def: BrokerDefinition = toml::from_str(toml).expect("parse broker definition");
+        assert!(def.validate().is_ok());
+        assert_eq!(def.request_headers().len(), 2);
+        assert_eq!(
+            def.request_headers().get("Referer").map(String::as_str),
+            Some("https://example.com/search")
+        );
+
+        let without: BrokerDefinition = toml::from_str(
+            &toml.replace(
+                "[broker.request_headers]\n            Referer = \"https://example.com/search\"\n            X-Requested-With = \"XMLHttpRequest\"\n",
+                "",
+            ),
+        )
+        .expect("parse broker definition");
+        assert!(without.request_headers().is_empty());
+    }
+
+    #[test]
+    fn test_legal_contact() {
+        let toml = r#"
+            [broker]
+            id = "test-broker"
+            name = "Test Broker"
+            url = "https://example.com"
+            domain = "example.com"
+            category = "people-search"
+            difficulty = "Easy"
+            typical_removal_days = 7
+            recheck_interval_days = 30
+            last_verified = "2025-01-01"
+            legal_contact = "dpo@example.com"
+
+            [search]
+            method = "url-template"
+            template = "https://example.com/{first}-{last}"
+            requires_fields = ["first_name", "last_name"]
+
+            [removal]
+            method = "manual"
+            instructions = "Manual removal"
+        "#;
+
+        let mut def: BrokerDefinition = toml::from_str(toml).expect("parse broker definition");
+        assert!(def.validate().is_ok());
+        assert_eq!(def.legal_contact(), Some("dpo@example.com"));
+
+        // Email removals fall back to the removal address
+        def.broker.legal_contact = None;
+        assert_eq!(def.legal_contact(), None);
+        def.removal = RemovalMethod::Email {
+            email: "optout@example.com".to_string(),
+            subject: "Removal".to_string(),
+            body: "Remove {{full_name}}".to_string(),
+            response_days: 30,
+            notes: String::new(),
+        };
+        assert_eq!(def.legal_contact(), Some("optout@example.com"));
+
+        for invalid in [
+            "privacy",
+            "privacy@",
+            "@example.com",
+            "dpo@example",
+            "a b@example.com",
+        ] {
+            def.broker.legal_contact = Some(invalid.to_string());
+            assert!(def.validate().is_err(), "{invalid} accepted");
+        }
+    }
+
     #[test]
     fn test_search_result_selectors_parsing() {
         let toml = r#"
@@ -899,6 +1674,123 @@ mod tests {
         assert_eq!(def.broker.region_relevance, vec!["Global".to_string()]);
     }
 
+    #[test]
+    fn test_covers_country() {
+        let toml = r#"
+            [broker]
+            id = "test-broker"
+            name = "Test Broker"
+            url = "https://example.com"
+            domain = "example.com"
+            category = "people-search"
+            difficulty = "Easy"
+            typical_removal_days = 7
+            recheck_interval_days = 30
+            last_verified = "2025-01-01"
+            countries = ["US"]
+
+            [search]
+            method = "url-template"
+            template = "https://example.com/{first}-{last}"
+            requires_fields = ["first_name", "last_name"]
+
+            [removal]
+            method = "manual"
+            instructions = "Manual removal"
+        "#;
+
+        let mut def: BrokerDefinition =
+            toml::from_str(toml).expect("should parse broker definition with countries");
+        assert!(def.covers_country("US"));
+        assert!(def.covers_country("usa"));
+        assert!(!def.covers_country("GB"));
+
+        def.broker.countries.clear();
+        assert!(def.covers_country("GB"));
+    }
+
+    #[test]
+    fn test_id_verification_url() {
+        let toml = r#"
+            [broker]
+            id = "test-broker"
+            name = "Test Broker"
+            url = "https://example.com"
+            domain = "example.com"
+            category = "people-search"
+            difficulty = "Hard"
+            typical_removal_days = 30
+            recheck_interval_days = 30
+            last_verified = "2025-01-01"
+            requires_id_verification = true
+
+            [search]
+            method = "url-template"
+            template = "https://example.com/{first}-{last}"
+            requires_fields = ["first_name", "last_name"]
+
+            [removal]
+            method = "manual"
+            instructions = "Upload a photo of your ID"
+        "#;
+
+        let mut def: BrokerDefinition =
+            toml::from_str(toml).expect("should parse broker definition requiring ID");
+        assert_eq!(def.id_verification_url(), Some("https://example.com"));
+
+        def.removal = RemovalMethod::BrowserForm {
+            url: "https://example.com/opt-out".to_string(),
+            fields: HashMap::new(),
+            form_selectors: FormSelectors::default(),
+            confirmation: ConfirmationType::default(),
+            notes: String::new(),
+        };
+        assert_eq!(
+            def.id_verification_url(),
+            Some("https://example.com/opt-out")
+        );
+
+        def.broker.requires_id_verification = false;
+        assert_eq!(def.id_verification_url(), None);
+    }
+
+    #[test]
+    fn test_account_required_url() {
+        let toml = r#"
+            [broker]
+            id = "test-broker"
+            name = "Test Broker"
+            url = "https://example.com"
+            domain = "example.com"
+            category = "people-search"
+            difficulty = "Hard"
+            typical_removal_days = 30
+            recheck_interval_days = 30
+            last_verified = "2025-01-01"
+            requires_account = true
+
+            [search]
+            method = "url-template"
+            template = "https://example.com/{first}-{last}"
+            requires_fields = ["first_name", "last_name"]
+
+            [removal]
+            method = "browser-form"
+            url = "https://example.com/opt-out"
+        "#;
+
+        let mut def: BrokerDefinition =
+            toml::from_str(toml).expect("should parse broker definition requiring an account");
+        assert_eq!(
+            def.account_required_url(),
+            Some("https://example.com/opt-out")
+        );
+        assert_eq!(def.id_verification_url(), None);
+
+        def.broker.requires_account = false;
+        assert_eq!(def.account_required_url(), None);
+    }
+
     #[test]
     fn test_scan_priority_can_be_set() {
         let toml = r#"
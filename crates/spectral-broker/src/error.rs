@@ -63,6 +63,10 @@ pub enum BrokerError {
         /// Reason for removal failure
         reason: String,
     },
+
+    /// CAPTCHA solving service failed or gave up
+    #[error("CAPTCHA solving failed: {0}")]
+    CaptchaSolving(String),
 }
 
 /// Result type for broker operations.
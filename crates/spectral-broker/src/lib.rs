@@ -7,8 +7,9 @@
 //! # Architecture
 //!
 //! - **Definition Types** ([`definition`]): Strongly-typed broker metadata and configuration
-//! - **Loader** ([`loader`]): TOML file loading from `broker-definitions/` directory
+//! - **Loader** ([`loader`]): TOML file loading from `broker-definitions/`, embedded or on disk
 //! - **Registry** ([`registry`]): In-memory cache with query support
+//! - **Self-test** ([`selftest`]): Offline selector checks against bundled fixtures
 //! - **Errors** ([`error`]): Broker-specific error types
 //!
 //! # Example
@@ -18,7 +19,7 @@
 //! use spectral_core::BrokerId;
 //!
 //! # fn main() -> Result<(), Box<dyn std::error::Error>> {
-//! // Load broker definitions from the default directory
+//! // Load embedded broker definitions, overridden by `broker-definitions/` if present
 //! let loader = BrokerLoader::with_default_dir()?;
 //! let registry = BrokerRegistry::load_from(&loader)?;
 //!
@@ -44,12 +45,15 @@ pub mod error;
 pub mod loader;
 pub mod registry;
 pub mod removal;
+pub mod selftest;
 
 // Re-export commonly used types
 pub use definition::{
-    BrokerCategory, BrokerDefinition, BrokerMetadata, ConfirmationType, RemovalDifficulty,
-    RemovalMethod, ScanPriority, SearchMethod,
+    ApiAuth, ApiBodyFormat, ApiMethod, BrokerCategory, BrokerDefinition, BrokerMetadata,
+    ConfirmationType, RemovalConfirmation, RemovalDifficulty, RemovalMethod, RemovalPreview,
+    ScanPriority, SearchMethod, SelectorFixture,
 };
 pub use error::{BrokerError, Result};
-pub use loader::BrokerLoader;
-pub use registry::BrokerRegistry;
+pub use loader::{BrokerLoader, LoadError};
+pub use registry::{BrokerRegistry, CategoryExposure, StaleDefinition, DEFAULT_STALE_AFTER_DAYS};
+pub use selftest::{SelectorTestResult, SelectorTestStatus};
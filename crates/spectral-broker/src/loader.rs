@@ -1,53 +1,102 @@
 //! Broker definition loading from TOML files.
 //!
 //! This module handles loading broker definitions from the `broker-definitions/` directory.
+//! The same definitions are embedded in the binary at compile time, so a packaged
+//! app can scan even when the directory is missing or has been moved.
 
 use crate::{
     definition::BrokerDefinition,
     error::{BrokerError, Result},
 };
+use include_dir::{include_dir, Dir};
 use spectral_core::BrokerId;
-use std::path::{Path, PathBuf};
+use std::collections::HashSet;
+use std::path::{Component, Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
 use tracing::{debug, info, warn};
 
+/// Broker definitions embedded at compile time.
+static EMBEDDED_DEFINITIONS: Dir<'_> = include_dir!("$CARGO_MANIFEST_DIR/../../broker-definitions");
+
+/// Most definition files parsed at once by [`BrokerLoader::load_all_with_errors`].
+const MAX_PARALLEL_LOADS: usize = 8;
+
 /// Loader for broker definitions from TOML files.
 pub struct BrokerLoader {
-    /// Base directory containing broker definitions
-    definitions_dir: PathBuf,
+    /// On-disk directory containing broker definitions, if any
+    definitions_dir: Option<PathBuf>,
+    /// Whether embedded definitions are loaded beneath the on-disk ones
+    use_embedded: bool,
 }
 
 impl BrokerLoader {
     /// Create a new loader with the given definitions directory.
     ///
+    /// Only definitions in the directory are loaded.
+    ///
     /// # Errors
     /// Returns error if the directory doesn't exist.
     pub fn new(definitions_dir: impl Into<PathBuf>) -> Result<Self> {
-        let definitions_dir = definitions_dir.into();
+        let definitions_dir = Self::existing_dir(definitions_dir.into())?;
 
-        if !definitions_dir.exists() {
-            return Err(BrokerError::DirectoryNotFound {
-                path: definitions_dir.display().to_string(),
-            });
-        }
+        Ok(Self {
+            definitions_dir: Some(definitions_dir),
+            use_embedded: false,
+        })
+    }
 
-        if !definitions_dir.is_dir() {
-            return Err(BrokerError::DirectoryNotFound {
-                path: definitions_dir.display().to_string(),
-            });
+    /// Create a loader for the definitions embedded in the binary.
+    #[must_use]
+    pub fn from_embedded() -> Self {
+        Self {
+            definitions_dir: None,
+            use_embedded: true,
         }
+    }
+
+    /// Create a loader that layers an on-disk directory over the embedded
+    /// definitions.
+    ///
+    /// Definitions in the directory replace embedded definitions with the same
+    /// broker ID; brokers only present in the directory are added.
+    ///
+    /// # Errors
+    /// Returns error if the directory doesn't exist.
+    pub fn with_override_dir(definitions_dir: impl Into<PathBuf>) -> Result<Self> {
+        let definitions_dir = Self::existing_dir(definitions_dir.into())?;
 
-        Ok(Self { definitions_dir })
+        Ok(Self {
+            definitions_dir: Some(definitions_dir),
+            use_embedded: true,
+        })
     }
 
     /// Create a loader using the default definitions directory.
     ///
-    /// Looks for `broker-definitions/` relative to the workspace root.
+    /// Looks for `broker-definitions/` relative to the workspace root and
+    /// layers it over the embedded definitions. If no directory is found, only
+    /// the embedded definitions are used.
     ///
     /// # Errors
-    /// Returns error if the default directory doesn't exist.
+    /// Currently always succeeds, since the embedded definitions are used
+    /// when no directory is found.
     pub fn with_default_dir() -> Result<Self> {
+        let Some(definitions_dir) = Self::find_default_dir() else {
+            debug!("no broker-definitions directory found, using embedded definitions");
+            return Ok(Self::from_embedded());
+        };
+
+        debug!(dir = %definitions_dir.display(), "using on-disk broker definitions");
+        Ok(Self {
+            definitions_dir: Some(definitions_dir),
+            use_embedded: true,
+        })
+    }
+
+    /// Locate `broker-definitions/` on disk.
+    fn find_default_dir() -> Option<PathBuf> {
         // Find workspace root by looking for Cargo.toml with [workspace]
-        let mut current_dir = std::env::current_dir()?;
+        let mut current_dir = std::env::current_dir().ok()?;
 
         loop {
             let cargo_toml = current_dir.join("Cargo.toml");
@@ -56,7 +105,7 @@ impl BrokerLoader {
                 if let Ok(contents) = std::fs::read_to_string(&cargo_toml) {
                     if contents.contains("[workspace]") {
                         let definitions_dir = current_dir.join("broker-definitions");
-                        return Self::new(definitions_dir);
+                        return definitions_dir.is_dir().then_some(definitions_dir);
                     }
                 }
             }
@@ -71,11 +120,24 @@ impl BrokerLoader {
 
         // Fallback: try relative path
         let definitions_dir = PathBuf::from("broker-definitions");
-        Self::new(definitions_dir)
+        definitions_dir.is_dir().then_some(definitions_dir)
+    }
+
+    /// Check that a definitions directory exists.
+    fn existing_dir(definitions_dir: PathBuf) -> Result<PathBuf> {
+        if !definitions_dir.is_dir() {
+            return Err(BrokerError::DirectoryNotFound {
+                path: definitions_dir.display().to_string(),
+            });
+        }
+
+        Ok(definitions_dir)
     }
 
     /// Load a single broker definition by ID.
     ///
+    /// The on-disk directory is searched first, then the embedded definitions.
+    ///
     /// # Errors
     /// Returns error if the definition file doesn't exist, can't be read, or is invalid.
     pub fn load(&self, broker_id: &BrokerId) -> Result<BrokerDefinition> {
@@ -93,63 +155,167 @@ impl BrokerLoader {
         Ok(definition)
     }
 
-    /// Load all broker definitions from the definitions directory.
+    /// Load all broker definitions.
     ///
-    /// Invalid definitions are logged as warnings and skipped.
+    /// Invalid definitions are logged as warnings and skipped. On-disk
+    /// definitions take precedence over embedded ones with the same ID.
     ///
     /// # Errors
     /// Returns error if the directory can't be read.
     pub fn load_all(&self) -> Result<Vec<BrokerDefinition>> {
-        let mut definitions = Vec::new();
+        let (definitions, errors) = self.load_all_with_errors()?;
+        for error in &errors {
+            warn!(
+                path = %error.path.display(),
+                error = %error.error,
+                "skipping broker definition"
+            );
+        }
+        Ok(definitions)
+    }
+
+    /// Load all broker definitions, returning the files that failed
+    /// alongside the ones that loaded.
+    ///
+    /// Files are parsed and validated on a few threads at once, and a bad
+    /// file does not stop the others from loading.
+    /// Definitions and errors are ordered by path, whatever order the files
+    /// finish in. On-disk definitions take precedence over embedded ones with
+    /// the same ID.
+    ///
+    /// # Errors
+    /// Returns error if the directory can't be read.
+    pub fn load_all_with_errors(&self) -> Result<(Vec<BrokerDefinition>, Vec<LoadError>)> {
+        let mut loaded: Vec<(PathBuf, BrokerDefinition)> = Vec::new();
+        let mut errors = Vec::new();
+
+        if self.use_embedded {
+            let mut files = Vec::new();
+            Self::collect_embedded_files(&EMBEDDED_DEFINITIONS, &mut files);
+            files.sort_by_key(|file| file.path());
+
+            let results = load_in_parallel(&files, |file| {
+                Self::load_embedded_file(file.path(), file.contents_utf8()).and_then(validated)
+            });
+            let paths = files.iter().map(|file| file.path().to_path_buf());
+            Self::merge(paths.zip(results), &mut loaded, &mut errors);
+        }
+
+        if let Some(definitions_dir) = &self.definitions_dir {
+            let mut paths = Vec::new();
+            Self::collect_definition_files(definitions_dir, &mut paths)?;
+            paths.sort();
+
+            let results = load_in_parallel(&paths, |path| {
+                Self::load_from_path(path).and_then(validated)
+            });
+            Self::merge(paths.into_iter().zip(results), &mut loaded, &mut errors);
+        }
 
-        Self::walk_and_load_recursive(&self.definitions_dir, &mut definitions)?;
+        errors.extend(Self::drop_dangling_related(&mut loaded));
 
         info!(
-            count = definitions.len(),
-            dir = %self.definitions_dir.display(),
+            count = loaded.len(),
+            errors = errors.len(),
+            dir = %self.definitions_dir.as_deref().map_or_else(String::new, |d| d.display().to_string()),
+            embedded = self.use_embedded,
             "loaded broker definitions"
         );
 
-        Ok(definitions)
+        let definitions = loaded
+            .into_iter()
+            .map(|(_, definition)| definition)
+            .collect();
+        Ok((definitions, errors))
     }
 
-    /// Recursively walk directory and load all TOML files.
-    fn walk_and_load_recursive(dir: &Path, definitions: &mut Vec<BrokerDefinition>) -> Result<()> {
+    /// Add loaded definitions, replacing any earlier one with the same ID,
+    /// and collect the failures.
+    fn merge(
+        results: impl Iterator<Item = (PathBuf, Result<BrokerDefinition>)>,
+        loaded: &mut Vec<(PathBuf, BrokerDefinition)>,
+        errors: &mut Vec<LoadError>,
+    ) {
+        for (path, result) in results {
+            match result {
+                Ok(definition) => {
+                    match loaded.iter_mut().find(|(_, d)| d.id() == definition.id()) {
+                        Some(existing) => *existing = (path, definition),
+                        None => loaded.push((path, definition)),
+                    }
+                }
+                Err(error) => errors.push(LoadError { path, error }),
+            }
+        }
+    }
+
+    /// Drop definitions whose `related_brokers` name a broker that was not
+    /// loaded, returning an error for each.
+    ///
+    /// Repeats until stable, since dropping one definition can leave another
+    /// pointing at it.
+    fn drop_dangling_related(loaded: &mut Vec<(PathBuf, BrokerDefinition)>) -> Vec<LoadError> {
+        let mut errors = Vec::new();
+        loop {
+            let ids: HashSet<BrokerId> = loaded.iter().map(|(_, d)| d.id().clone()).collect();
+            let before = loaded.len();
+
+            loaded.retain(|(path, definition)| {
+                let Some(missing) = definition
+                    .related_brokers()
+                    .iter()
+                    .find(|related| !ids.contains(*related))
+                else {
+                    return true;
+                };
+                errors.push(LoadError {
+                    path: path.clone(),
+                    error: BrokerError::ValidationError {
+                        broker_id: definition.id().to_string(),
+                        reason: format!("unknown related broker {missing}"),
+                    },
+                });
+                false
+            });
+
+            if loaded.len() == before {
+                break;
+            }
+        }
+        errors
+    }
+
+    /// Recursively collect the definition files embedded in the binary.
+    fn collect_embedded_files<'a>(dir: &'a Dir<'a>, files: &mut Vec<&'a include_dir::File<'a>>) {
+        for subdir in dir.dirs() {
+            Self::collect_embedded_files(subdir, files);
+        }
+        files.extend(
+            dir.files()
+                .filter(|file| Self::is_definition_file(file.path())),
+        );
+    }
+
+    /// Check whether a path looks like a broker definition file.
+    ///
+    /// `schema.toml` documents the format and is not a definition.
+    fn is_definition_file(path: &Path) -> bool {
+        path.extension().and_then(|s| s.to_str()) == Some("toml")
+            && !matches!(
+                path.file_name().and_then(|s| s.to_str()),
+                Some("README.toml" | "schema.toml")
+            )
+    }
+
+    /// Recursively collect the definition files in a directory.
+    fn collect_definition_files(dir: &Path, paths: &mut Vec<PathBuf>) -> Result<()> {
         for entry in std::fs::read_dir(dir)? {
-            let entry = entry?;
-            let path = entry.path();
+            let path = entry?.path();
 
             if path.is_dir() {
-                // Recursively process subdirectories
-                Self::walk_and_load_recursive(&path, definitions)?;
-            } else if path.extension().and_then(|s| s.to_str()) == Some("toml") {
-                // Skip README files
-                if path.file_name().and_then(|s| s.to_str()) == Some("README.toml") {
-                    continue;
-                }
-
-                // Load and parse TOML
-                match Self::load_from_path(&path) {
-                    Ok(definition) => {
-                        // Validate before adding
-                        if let Err(e) = definition.validate() {
-                            warn!(
-                                path = %path.display(),
-                                error = %e,
-                                "skipping invalid broker definition"
-                            );
-                            continue;
-                        }
-                        definitions.push(definition);
-                    }
-                    Err(e) => {
-                        warn!(
-                            path = %path.display(),
-                            error = %e,
-                            "failed to load broker definition"
-                        );
-                    }
-                }
+                Self::collect_definition_files(&path, paths)?;
+            } else if Self::is_definition_file(&path) {
+                paths.push(path);
             }
         }
 
@@ -161,13 +327,34 @@ impl BrokerLoader {
         // Try to find the TOML file in any subdirectory
         let filename = format!("{}.toml", broker_id.as_str());
 
-        if let Some(path) = Self::find_file(&self.definitions_dir, &filename)? {
-            Self::load_from_path(&path)
-        } else {
-            Err(BrokerError::NotFound {
-                broker_id: broker_id.to_string(),
-            })
+        if let Some(definitions_dir) = &self.definitions_dir {
+            if let Some(path) = Self::find_file(definitions_dir, &filename)? {
+                return Self::load_from_path(&path);
+            }
         }
+
+        if self.use_embedded {
+            if let Some(file) = Self::find_embedded_file(&EMBEDDED_DEFINITIONS, &filename) {
+                return Self::load_embedded_file(file.path(), file.contents_utf8());
+            }
+        }
+
+        Err(BrokerError::NotFound {
+            broker_id: broker_id.to_string(),
+        })
+    }
+
+    /// Recursively search the embedded definitions for a file by name.
+    fn find_embedded_file<'a>(
+        dir: &'a Dir<'a>,
+        filename: &str,
+    ) -> Option<&'a include_dir::File<'a>> {
+        dir.files()
+            .find(|file| file.path().file_name().and_then(|s| s.to_str()) == Some(filename))
+            .or_else(|| {
+                dir.dirs()
+                    .find_map(|subdir| Self::find_embedded_file(subdir, filename))
+            })
     }
 
     /// Recursively search for a file by name.
@@ -193,6 +380,31 @@ impl BrokerLoader {
         Ok(None)
     }
 
+    /// Parse an embedded broker definition.
+    ///
+    /// Relative fixture paths are resolved within the embedded directory and
+    /// can be read with [`embedded_file_contents`].
+    fn load_embedded_file(path: &Path, contents: Option<&str>) -> Result<BrokerDefinition> {
+        let contents = contents.ok_or_else(|| BrokerError::LoadError {
+            path: path.display().to_string(),
+            source: "embedded definition is not valid UTF-8".into(),
+        })?;
+
+        let mut definition: BrokerDefinition =
+            toml::from_str(contents).map_err(|e| BrokerError::ParseError {
+                path: path.display().to_string(),
+                source: e,
+            })?;
+
+        if let (Some(fixture), Some(dir)) = (definition.fixture.as_mut(), path.parent()) {
+            if fixture.path.is_relative() {
+                fixture.path = normalize_relative(&dir.join(&fixture.path));
+            }
+        }
+
+        Ok(definition)
+    }
+
     /// Load a broker definition from a specific file path.
     fn load_from_path(path: &Path) -> Result<BrokerDefinition> {
         let contents = std::fs::read_to_string(path).map_err(|e| BrokerError::LoadError {
@@ -200,13 +412,118 @@ impl BrokerLoader {
             source: Box::new(e),
         })?;
 
-        toml::from_str(&contents).map_err(|e| BrokerError::ParseError {
-            path: path.display().to_string(),
-            source: e,
-        })
+        let mut definition: BrokerDefinition =
+            toml::from_str(&contents).map_err(|e| BrokerError::ParseError {
+                path: path.display().to_string(),
+                source: e,
+            })?;
+
+        // Fixture paths are relative to the definition file
+        if let (Some(fixture), Some(dir)) = (definition.fixture.as_mut(), path.parent()) {
+            if fixture.path.is_relative() {
+                fixture.path = dir.join(&fixture.path);
+            }
+        }
+
+        Ok(definition)
+    }
+}
+
+/// A definition file that could not be loaded.
+#[derive(Debug)]
+pub struct LoadError {
+    /// Path of the file; relative to `broker-definitions/` for embedded files
+    pub path: PathBuf,
+    /// Why the file was skipped
+    pub error: BrokerError,
+}
+
+impl std::fmt::Display for LoadError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: {}", self.path.display(), self.error)
     }
 }
 
+impl std::error::Error for LoadError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(&self.error)
+    }
+}
+
+/// Check a freshly parsed definition.
+fn validated(definition: BrokerDefinition) -> Result<BrokerDefinition> {
+    definition.validate()?;
+    Ok(definition)
+}
+
+/// Apply `load` to every item on up to [`MAX_PARALLEL_LOADS`] threads,
+/// returning the results in the order of `items`.
+fn load_in_parallel<T: Sync, R: Send>(items: &[T], load: impl Fn(&T) -> R + Sync) -> Vec<R> {
+    let workers = std::thread::available_parallelism()
+        .map_or(1, std::num::NonZeroUsize::get)
+        .min(MAX_PARALLEL_LOADS)
+        .min(items.len());
+    if workers <= 1 {
+        return items.iter().map(load).collect();
+    }
+
+    let next = AtomicUsize::new(0);
+    let mut results: Vec<Option<R>> = std::iter::repeat_with(|| None).take(items.len()).collect();
+    std::thread::scope(|scope| {
+        let workers: Vec<_> = (0..workers)
+            .map(|_| {
+                scope.spawn(|| {
+                    let mut done = Vec::new();
+                    loop {
+                        let index = next.fetch_add(1, Ordering::Relaxed);
+                        let Some(item) = items.get(index) else {
+                            return done;
+                        };
+                        done.push((index, load(item)));
+                    }
+                })
+            })
+            .collect();
+
+        for worker in workers {
+            let done = worker
+                .join()
+                .unwrap_or_else(|panic| std::panic::resume_unwind(panic));
+            for (index, result) in done {
+                results[index] = Some(result);
+            }
+        }
+    });
+    results
+        .into_iter()
+        .map(|r| r.expect("every item loaded"))
+        .collect()
+}
+
+/// Read an embedded file by its path relative to `broker-definitions/`.
+///
+/// Used for fixtures of embedded definitions, which have no on-disk location.
+pub(crate) fn embedded_file_contents(path: &Path) -> Option<&'static str> {
+    EMBEDDED_DEFINITIONS
+        .get_file(normalize_relative(path))
+        .and_then(include_dir::File::contents_utf8)
+}
+
+/// Resolve `.` and `..` components of a relative path without touching the filesystem.
+fn normalize_relative(path: &Path) -> PathBuf {
+    let mut normalized = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::ParentDir => {
+                normalized.pop();
+            }
+            Component::CurDir => {}
+            other => normalized.push(other),
+        }
+    }
+    normalized
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -333,6 +650,124 @@ success_indicator = ".success"
         assert_eq!(definitions.len(), 1);
     }
 
+    #[test]
+    fn test_load_all_with_errors_reports_bad_file() {
+        let temp_dir = TempDir::new().expect("create temp dir");
+        for broker_id in ["alpha-broker", "beta-broker", "gamma-broker"] {
+            create_test_definition_file(temp_dir.path(), broker_id, "people-search");
+        }
+        let invalid_path = temp_dir.path().join("people-search").join("broken.toml");
+        std::fs::write(&invalid_path, "invalid toml content [[[").expect("write invalid file");
+
+        let loader = BrokerLoader::new(temp_dir.path()).expect("create loader");
+        let (definitions, errors) = loader.load_all_with_errors().expect("load all definitions");
+
+        assert_eq!(definitions.len(), 3);
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].path, invalid_path);
+        assert!(matches!(errors[0].error, BrokerError::ParseError { .. }));
+        assert!(errors[0].to_string().contains("broken.toml"));
+    }
+
+    #[test]
+    fn test_load_all_order_is_stable() {
+        let temp_dir = TempDir::new().expect("create temp dir");
+        let mut expected = Vec::new();
+        for category in ["people-search", "background-check", "phone-lookup"] {
+            for i in 0..8 {
+                let broker_id = format!("{category}-{i:02}");
+                create_test_definition_file(temp_dir.path(), &broker_id, category);
+                expected.push(broker_id);
+            }
+        }
+        // Definitions are ordered by path, so by category directory first
+        expected.sort();
+
+        let loader = BrokerLoader::new(temp_dir.path()).expect("create loader");
+        for _ in 0..5 {
+            let definitions = loader.load_all().expect("load all definitions");
+            let ids: Vec<&str> = definitions.iter().map(|d| d.id().as_str()).collect();
+            assert_eq!(ids, expected);
+        }
+
+        let (registry, errors) =
+            crate::BrokerRegistry::load_with_errors(&loader).expect("load registry");
+        assert!(errors.is_empty());
+        let ids: Vec<String> = registry
+            .get_all_ids()
+            .iter()
+            .map(ToString::to_string)
+            .collect();
+        assert_eq!(ids, expected);
+    }
+
+    #[test]
+    fn test_load_in_parallel_keeps_input_order() {
+        let items: Vec<u64> = (0..100).collect();
+        let results = load_in_parallel(&items, |&i| {
+            // Finish out of order
+            std::thread::sleep(std::time::Duration::from_micros((100 - i) * 10));
+            i * 2
+        });
+        assert_eq!(results, items.iter().map(|i| i * 2).collect::<Vec<_>>());
+    }
+
+    /// Declare `related` as siblings in an existing test definition file.
+    fn set_related_brokers(path: &Path, related: &[&str]) {
+        let content = std::fs::read_to_string(path).expect("read test file");
+        let related = related
+            .iter()
+            .map(|id| format!("\"{id}\""))
+            .collect::<Vec<_>>()
+            .join(", ");
+        let content = content.replacen(
+            "last_verified = \"2025-05-01\"",
+            &format!("last_verified = \"2025-05-01\"\nrelated_brokers = [{related}]"),
+            1,
+        );
+        std::fs::write(path, content).expect("write test file");
+    }
+
+    #[test]
+    fn test_load_all_keeps_resolvable_related_brokers() {
+        let temp_dir = TempDir::new().expect("create temp dir");
+        let path = create_test_definition_file(temp_dir.path(), "broker-1", "people-search");
+        set_related_brokers(&path, &["broker-2"]);
+        create_test_definition_file(temp_dir.path(), "broker-2", "people-search");
+
+        let loader = BrokerLoader::new(temp_dir.path()).expect("create loader");
+        let definitions = loader.load_all().expect("load all definitions");
+
+        assert_eq!(definitions.len(), 2);
+        let broker_1 = definitions
+            .iter()
+            .find(|d| d.id().as_str() == "broker-1")
+            .expect("broker-1 loaded");
+        assert_eq!(
+            broker_1.related_brokers(),
+            [BrokerId::new("broker-2").expect("valid broker ID")]
+        );
+    }
+
+    #[test]
+    fn test_load_all_skips_dangling_related_brokers() {
+        let temp_dir = TempDir::new().expect("create temp dir");
+        create_test_definition_file(temp_dir.path(), "valid-broker", "people-search");
+        let dangling =
+            create_test_definition_file(temp_dir.path(), "dangling-broker", "people-search");
+        set_related_brokers(&dangling, &["missing-broker"]);
+        // Only points at the dangling definition, so it goes too
+        let chained =
+            create_test_definition_file(temp_dir.path(), "chained-broker", "people-search");
+        set_related_brokers(&chained, &["dangling-broker"]);
+
+        let loader = BrokerLoader::new(temp_dir.path()).expect("create loader");
+        let definitions = loader.load_all().expect("load all definitions");
+
+        let ids: Vec<_> = definitions.iter().map(|d| d.id().as_str()).collect();
+        assert_eq!(ids, ["valid-broker"]);
+    }
+
     #[test]
     fn test_find_file_in_nested_directories() {
         let temp_dir = TempDir::new().expect("create temp dir");
@@ -349,4 +784,68 @@ success_indicator = ".success"
         assert!(found.is_some());
         assert_eq!(found.unwrap(), file_path);
     }
+
+    /// Number of broker definition files embedded from `broker-definitions/`.
+    fn bundled_broker_count() -> usize {
+        let mut files = Vec::new();
+        BrokerLoader::collect_embedded_files(&EMBEDDED_DEFINITIONS, &mut files);
+        files.len()
+    }
+
+    #[test]
+    fn test_embedded_loader_yields_bundled_brokers() {
+        let definitions = BrokerLoader::from_embedded()
+            .load_all()
+            .expect("load embedded definitions");
+
+        assert_eq!(definitions.len(), bundled_broker_count());
+
+        let spokeo = BrokerLoader::from_embedded()
+            .load(&BrokerId::new("spokeo").expect("valid broker ID"))
+            .expect("load embedded spokeo");
+        assert_eq!(spokeo.name(), "Spokeo");
+    }
+
+    #[test]
+    fn test_embedded_fixture_is_readable() {
+        let spokeo = BrokerLoader::from_embedded()
+            .load(&BrokerId::new("spokeo").expect("valid broker ID"))
+            .expect("load embedded spokeo");
+        let fixture = spokeo.fixture.expect("spokeo has a fixture");
+
+        assert_eq!(fixture.path, PathBuf::from("fixtures/spokeo.html"));
+        assert!(embedded_file_contents(&fixture.path).is_some());
+    }
+
+    #[test]
+    fn test_on_disk_override_takes_precedence() {
+        let temp_dir = TempDir::new().expect("create temp dir");
+        let path = create_test_definition_file(temp_dir.path(), "spokeo", "people-search");
+        let content = std::fs::read_to_string(&path).expect("read test file");
+        std::fs::write(&path, content.replace("Test Broker", "Spokeo Override"))
+            .expect("write override");
+        create_test_definition_file(temp_dir.path(), "local-only", "people-search");
+
+        let loader = BrokerLoader::with_override_dir(temp_dir.path()).expect("create loader");
+        let definitions = loader.load_all().expect("load all definitions");
+
+        // One broker replaced, one added
+        assert_eq!(definitions.len(), bundled_broker_count() + 1);
+        let spokeo_id = BrokerId::new("spokeo").expect("valid broker ID");
+        let spokeo = definitions
+            .iter()
+            .find(|d| d.id() == &spokeo_id)
+            .expect("spokeo present");
+        assert_eq!(spokeo.name(), "Spokeo Override");
+        assert_eq!(
+            loader.load(&spokeo_id).expect("load spokeo").name(),
+            "Spokeo Override"
+        );
+
+        // Brokers missing from the override directory still load from the binary
+        let whitepages = loader
+            .load(&BrokerId::new("whitepages").expect("valid broker ID"))
+            .expect("load embedded whitepages");
+        assert_eq!(whitepages.name(), "Whitepages");
+    }
 }
@@ -3,12 +3,44 @@
 use crate::{
     definition::{BrokerCategory, BrokerDefinition, RemovalDifficulty},
     error::{BrokerError, Result},
-    loader::BrokerLoader,
+    loader::{BrokerLoader, LoadError},
+    selftest::{self, SelectorTestResult},
 };
+use chrono::NaiveDate;
+use serde::Serialize;
 use spectral_core::BrokerId;
 use std::collections::HashMap;
 use std::sync::{Arc, RwLock};
-use tracing::{debug, info};
+use tracing::{debug, info, warn};
+
+/// Days after `last_verified` at which a loaded definition is logged as stale.
+pub const DEFAULT_STALE_AFTER_DAYS: u32 = 180;
+
+/// Findings in one broker category and their share of overall exposure.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct CategoryExposure {
+    /// Broker category
+    pub category: BrokerCategory,
+    /// Number of findings on brokers in this category
+    pub finding_count: i64,
+    /// Risk weight of the category (see [`BrokerCategory::risk_weight`])
+    pub risk_weight: f64,
+    /// `finding_count` multiplied by `risk_weight`
+    pub weighted_exposure: f64,
+    /// Fraction of the total weighted exposure, between 0 and 1
+    pub share: f64,
+}
+
+/// A broker definition that has not been verified recently.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct StaleDefinition {
+    /// Broker the definition belongs to
+    pub broker_id: BrokerId,
+    /// Date the definition was last checked against the live site
+    pub last_verified: NaiveDate,
+    /// Days between `last_verified` and today
+    pub days_since_verified: i64,
+}
 
 /// In-memory cache of broker definitions with query capabilities.
 ///
@@ -47,7 +79,24 @@ impl BrokerRegistry {
     /// Returns error if loading fails.
     pub fn reload(&self, loader: &BrokerLoader) -> Result<()> {
         let definitions = loader.load_all()?;
+        self.replace_all(definitions);
+        Ok(())
+    }
 
+    /// Create a registry from the given loader, returning the definition
+    /// files that could not be loaded alongside it.
+    ///
+    /// # Errors
+    /// Returns error if the definitions directory can't be read.
+    pub fn load_with_errors(loader: &BrokerLoader) -> Result<(Self, Vec<LoadError>)> {
+        let (definitions, errors) = loader.load_all_with_errors()?;
+        let registry = Self::new();
+        registry.replace_all(definitions);
+        Ok((registry, errors))
+    }
+
+    /// Replace the cached definitions and warn about stale ones.
+    fn replace_all(&self, definitions: Vec<BrokerDefinition>) {
         let mut cache = self
             .definitions
             .write()
@@ -61,8 +110,52 @@ impl BrokerRegistry {
         }
 
         info!(count = cache.len(), "reloaded broker definitions");
+        drop(cache);
+
+        for stale in self.stale_definitions(DEFAULT_STALE_AFTER_DAYS) {
+            warn!(
+                broker_id = %stale.broker_id,
+                last_verified = %stale.last_verified,
+                days = stale.days_since_verified,
+                "broker definition has not been verified recently and may be stale"
+            );
+        }
+    }
 
-        Ok(())
+    /// Get the definitions last verified more than `threshold_days` ago.
+    ///
+    /// Old definitions are likely to have outdated selectors or URLs. Results
+    /// are sorted with the longest unverified first.
+    #[must_use]
+    pub fn stale_definitions(&self, threshold_days: u32) -> Vec<StaleDefinition> {
+        self.stale_definitions_as_of(threshold_days, chrono::Utc::now().date_naive())
+    }
+
+    fn stale_definitions_as_of(
+        &self,
+        threshold_days: u32,
+        today: NaiveDate,
+    ) -> Vec<StaleDefinition> {
+        let cache = self
+            .definitions
+            .read()
+            .expect("acquire read lock on definitions");
+
+        let mut stale: Vec<StaleDefinition> = cache
+            .values()
+            .map(|definition| StaleDefinition {
+                broker_id: definition.id().clone(),
+                last_verified: definition.broker.last_verified,
+                days_since_verified: (today - definition.broker.last_verified).num_days(),
+            })
+            .filter(|entry| entry.days_since_verified > i64::from(threshold_days))
+            .collect();
+        stale.sort_by(|a, b| {
+            b.days_since_verified
+                .cmp(&a.days_since_verified)
+                .then_with(|| a.broker_id.as_str().cmp(b.broker_id.as_str()))
+        });
+        stale
     }
 
     /// Get a broker definition by ID.
@@ -83,7 +176,7 @@ impl BrokerRegistry {
             })
     }
 
-    /// Get all broker definitions.
+    /// Get all broker definitions, sorted by broker ID.
     #[must_use]
     pub fn get_all(&self) -> Vec<BrokerDefinition> {
         let cache = self
@@ -91,7 +184,35 @@ impl BrokerRegistry {
             .read()
             .expect("acquire read lock on definitions");
 
-        cache.values().cloned().collect()
+        let mut definitions: Vec<_> = cache.values().cloned().collect();
+        definitions.sort_by(|a, b| a.id().as_str().cmp(b.id().as_str()));
+        definitions
+    }
+
+    /// Get the brokers related to the given one.
+    ///
+    /// Relations are symmetric: this includes brokers the definition lists in
+    /// `related_brokers` and brokers that list it. Results are sorted by
+    /// broker ID; unknown IDs yield an empty list.
+    #[must_use]
+    pub fn related(&self, broker_id: &BrokerId) -> Vec<BrokerDefinition> {
+        let cache = self
+            .definitions
+            .read()
+            .expect("acquire read lock on definitions");
+
+        let declared = cache
+            .get(broker_id)
+            .map_or(&[][..], BrokerDefinition::related_brokers);
+
+        let mut related: Vec<_> = cache
+            .values()
+            .filter(|def| def.id() != broker_id)
+            .filter(|def| declared.contains(def.id()) || def.related_brokers().contains(broker_id))
+            .cloned()
+            .collect();
+        related.sort_by(|a, b| a.id().as_str().cmp(b.id().as_str()));
+        related
     }
 
     /// Query brokers by category.
@@ -165,7 +286,7 @@ impl BrokerRegistry {
         cache.contains_key(broker_id)
     }
 
-    /// Get all broker IDs in the registry.
+    /// Get all broker IDs in the registry, sorted.
     #[must_use]
     pub fn get_all_ids(&self) -> Vec<BrokerId> {
         let cache = self
@@ -173,7 +294,9 @@ impl BrokerRegistry {
             .read()
             .expect("acquire read lock on definitions");
 
-        cache.keys().cloned().collect()
+        let mut ids: Vec<_> = cache.keys().cloned().collect();
+        ids.sort_by(|a, b| a.as_str().cmp(b.as_str()));
+        ids
     }
 
     /// Get broker count by category.
@@ -193,6 +316,62 @@ impl BrokerRegistry {
         counts
     }
 
+    /// Group per-broker finding counts by broker category.
+    ///
+    /// `findings_per_broker` holds `(broker_id, finding_count)` pairs. Brokers
+    /// that are not in the registry are counted as [`BrokerCategory::Other`].
+    /// Categories without findings are omitted; the rest are sorted by
+    /// weighted exposure, highest first.
+    #[must_use]
+    pub fn exposure_breakdown(
+        &self,
+        findings_per_broker: &[(String, i64)],
+    ) -> Vec<CategoryExposure> {
+        let cache = self
+            .definitions
+            .read()
+            .expect("acquire read lock on definitions");
+
+        let mut counts: HashMap<BrokerCategory, i64> = HashMap::new();
+        for (broker_id, count) in findings_per_broker {
+            let category = BrokerId::new(broker_id.as_str())
+                .ok()
+                .and_then(|id| cache.get(&id))
+                .map_or(BrokerCategory::Other, BrokerDefinition::category);
+            *counts.entry(category).or_insert(0) += count;
+        }
+        drop(cache);
+
+        let mut breakdown: Vec<CategoryExposure> = counts
+            .into_iter()
+            .filter(|(_, count)| *count > 0)
+            .map(|(category, finding_count)| {
+                let risk_weight = category.risk_weight();
+                #[allow(clippy::cast_precision_loss)]
+                let weighted_exposure = finding_count as f64 * risk_weight;
+                CategoryExposure {
+                    category,
+                    finding_count,
+                    risk_weight,
+                    weighted_exposure,
+                    share: 0.0,
+                }
+            })
+            .collect();
+
+        let total: f64 = breakdown.iter().map(|entry| entry.weighted_exposure).sum();
+        for entry in &mut breakdown {
+            entry.share = entry.weighted_exposure / total;
+        }
+
+        breakdown.sort_by(|a, b| {
+            b.weighted_exposure
+                .total_cmp(&a.weighted_exposure)
+                .then_with(|| a.category.display_name().cmp(b.category.display_name()))
+        });
+        breakdown
+    }
+
     /// Add or update a broker definition in the registry.
     ///
     /// This is useful for testing or dynamic updates.
@@ -230,6 +409,34 @@ impl BrokerRegistry {
 
         removed
     }
+
+    /// Run every broker's result selectors against its bundled HTML fixture.
+    ///
+    /// Brokers without result selectors or without a fixture are skipped.
+    /// Results are sorted by broker ID.
+    #[must_use]
+    pub fn self_test(&self) -> Vec<SelectorTestResult> {
+        let cache = self
+            .definitions
+            .read()
+            .expect("acquire read lock on definitions");
+
+        let mut results: Vec<_> = cache
+            .values()
+            .filter_map(selftest::test_definition)
+            .collect();
+        results.sort_by(|a, b| a.broker_id.as_str().cmp(b.broker_id.as_str()));
+
+        for result in results.iter().filter(|r| !r.passed()) {
+            tracing::warn!(
+                broker_id = %result.broker_id,
+                status = ?result.status,
+                "broker selector self-test failed"
+            );
+        }
+
+        results
+    }
 }
 
 impl Default for BrokerRegistry {
@@ -242,7 +449,8 @@ impl Default for BrokerRegistry {
 mod tests {
     use super::*;
     use crate::definition::{
-        BrokerMetadata, ConfirmationType, FormSelectors, RemovalMethod, SearchMethod,
+        BrokerMetadata, ConfirmationType, FormSelectors, RemovalConfirmation, RemovalMethod,
+        SearchMethod,
     };
     use chrono::NaiveDate;
     use spectral_core::PiiField;
@@ -269,6 +477,12 @@ mod tests {
                 last_verified: NaiveDate::from_ymd_opt(2025, 5, 1).expect("valid date"),
                 scan_priority: crate::definition::ScanPriority::OnRequest,
                 region_relevance: vec!["Global".to_string()],
+                countries: vec![],
+                requires_id_verification: false,
+                requires_account: false,
+                related_brokers: vec![],
+                request_headers: HashMap::new(),
+                legal_contact: None,
             },
             search: SearchMethod::UrlTemplate {
                 template: "https://test.com/{first}-{last}".to_string(),
@@ -288,10 +502,13 @@ mod tests {
                     captcha_frame: None,
                     success_indicator: Some(".success".to_string()),
                     error_indicator: None,
+                    account_wall: None,
                 },
                 confirmation: ConfirmationType::EmailVerification,
                 notes: String::new(),
             },
+            fixture: None,
+            confirmation: RemovalConfirmation::None,
         }
     }
 
@@ -399,6 +616,44 @@ mod tests {
         assert_eq!(all.len(), 3);
     }
 
+    #[test]
+    fn test_registry_related_resolves_siblings() {
+        let registry = BrokerRegistry::new();
+        let mut whitepages = create_test_definition(
+            "whitepages",
+            BrokerCategory::PeopleSearch,
+            RemovalDifficulty::Easy,
+        );
+        whitepages.broker.related_brokers = vec![
+            BrokerId::new("411").expect("valid broker ID"),
+            BrokerId::new("anywho").expect("valid broker ID"),
+        ];
+        registry.insert(whitepages).expect("insert definition");
+        for id in ["411", "anywho", "spokeo"] {
+            let definition =
+                create_test_definition(id, BrokerCategory::PeopleSearch, RemovalDifficulty::Easy);
+            registry.insert(definition).expect("insert definition");
+        }
+
+        let related: Vec<_> = registry
+            .related(&BrokerId::new("whitepages").expect("valid broker ID"))
+            .iter()
+            .map(|d| d.id().to_string())
+            .collect();
+        assert_eq!(related, ["411", "anywho"]);
+
+        // Siblings see the broker that declared them
+        let related: Vec<_> = registry
+            .related(&BrokerId::new("anywho").expect("valid broker ID"))
+            .iter()
+            .map(|d| d.id().to_string())
+            .collect();
+        assert_eq!(related, ["whitepages"]);
+
+        let spokeo = BrokerId::new("spokeo").expect("valid broker ID");
+        assert!(registry.related(&spokeo).is_empty());
+    }
+
     #[test]
     fn test_registry_get_by_category() {
         let registry = BrokerRegistry::new();
@@ -567,4 +822,196 @@ mod tests {
         assert!(id_strings.contains(&"broker-1".to_string()));
         assert!(id_strings.contains(&"broker-2".to_string()));
     }
+
+    fn write_fixture_backed_definition(dir: &std::path::Path, id: &str, result_item: &str) {
+        std::fs::write(
+            dir.join(format!("{id}.html")),
+            r#"<div class="results">
+                <div class="card"><a class="link" href="/p/1">View</a><b class="name">Alex Example</b></div>
+                <div class="card"><a class="link" href="/p/2">View</a><b class="name">Sam Sample</b></div>
+            </div>"#,
+        )
+        .expect("write fixture");
+
+        std::fs::write(
+            dir.join(format!("{id}.toml")),
+            format!(
+                r#"
+[broker]
+id = "{id}"
+name = "Fixture Broker"
+url = "https://test.com"
+domain = "test.com"
+category = "people-search"
+difficulty = "Easy"
+typical_removal_days = 7
+recheck_interval_days = 30
+last_verified = "2025-05-01"
+
+[search]
+method = "url-template"
+template = "https://test.com/{{first}}-{{last}}"
+requires_fields = ["first_name", "last_name"]
+
+[search.result_selectors]
+results_container = ".results"
+result_item = "{result_item}"
+listing_url = "a.link"
+name = ".name"
+
+[removal]
+method = "manual"
+instructions = "Email support"
+
+[fixture]
+path = "{id}.html"
+expected_results = 2
+expected_fields = ["name"]
+"#
+            ),
+        )
+        .expect("write definition");
+    }
+
+    #[test]
+    fn test_registry_self_test_detects_broken_selectors() {
+        let temp_dir = tempfile::TempDir::new().expect("create temp dir");
+        write_fixture_backed_definition(temp_dir.path(), "good-broker", ".card");
+        write_fixture_backed_definition(temp_dir.path(), "stale-broker", ".result-card");
+
+        let loader = BrokerLoader::new(temp_dir.path()).expect("create loader");
+        let registry = BrokerRegistry::load_from(&loader).expect("load registry");
+
+        // Brokers without fixtures are skipped
+        registry
+            .insert(create_test_definition(
+                "no-fixture",
+                BrokerCategory::PeopleSearch,
+                RemovalDifficulty::Easy,
+            ))
+            .expect("insert definition");
+
+        let results = registry.self_test();
+        assert_eq!(results.len(), 2);
+
+        assert_eq!(results[0].broker_id.as_str(), "good-broker");
+        assert!(results[0].passed());
+        assert_eq!(results[0].found_results, 2);
+
+        assert_eq!(results[1].broker_id.as_str(), "stale-broker");
+        assert!(!results[1].passed());
+        assert_eq!(results[1].found_results, 0);
+    }
+
+    #[test]
+    fn test_exposure_breakdown_groups_by_category() {
+        let registry = BrokerRegistry::new();
+        for (id, category) in [
+            ("spokeo", BrokerCategory::PeopleSearch),
+            ("whitepages", BrokerCategory::PeopleSearch),
+            ("checkr", BrokerCategory::BackgroundCheck),
+            ("acxiom", BrokerCategory::Marketing),
+        ] {
+            registry
+                .insert(create_test_definition(
+                    id,
+                    category,
+                    RemovalDifficulty::Easy,
+                ))
+                .expect("insert definition");
+        }
+
+        let breakdown = registry.exposure_breakdown(&[
+            ("spokeo".to_string(), 2),
+            ("whitepages".to_string(), 1),
+            ("checkr".to_string(), 4),
+            ("acxiom".to_string(), 2),
+            ("retired-broker".to_string(), 1),
+        ]);
+
+        let summary: Vec<_> = breakdown
+            .iter()
+            .map(|entry| (entry.category, entry.finding_count, entry.weighted_exposure))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                (BrokerCategory::BackgroundCheck, 4, 6.0),
+                (BrokerCategory::PeopleSearch, 3, 3.0),
+                (BrokerCategory::Marketing, 2, 1.0),
+                (BrokerCategory::Other, 1, 1.0),
+            ]
+        );
+
+        assert!((breakdown[0].share - 6.0 / 11.0).abs() < f64::EPSILON);
+        let total_share: f64 = breakdown.iter().map(|entry| entry.share).sum();
+        assert!((total_share - 1.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_exposure_breakdown_empty() {
+        let registry = BrokerRegistry::new();
+        assert!(registry.exposure_breakdown(&[]).is_empty());
+        assert!(registry
+            .exposure_breakdown(&[("spokeo".to_string(), 0)])
+            .is_empty());
+    }
+
+    fn verified_on(id: &str, last_verified: NaiveDate) -> BrokerDefinition {
+        let mut definition =
+            create_test_definition(id, BrokerCategory::PeopleSearch, RemovalDifficulty::Easy);
+        definition.broker.last_verified = last_verified;
+        definition
+    }
+
+    #[test]
+    fn test_stale_definitions_reports_old_definitions() {
+        let registry = BrokerRegistry::new();
+        let today = chrono::Utc::now().date_naive();
+        registry
+            .insert(verified_on(
+                "ancient",
+                NaiveDate::from_ymd_opt(2015, 1, 1).expect("valid date"),
+            ))
+            .expect("insert");
+        registry
+            .insert(verified_on("old", today - chrono::Duration::days(400)))
+            .expect("insert");
+        registry
+            .insert(verified_on("fresh", today - chrono::Duration::days(10)))
+            .expect("insert");
+
+        let stale = registry.stale_definitions(DEFAULT_STALE_AFTER_DAYS);
+
+        let ids: Vec<&str> = stale.iter().map(|s| s.broker_id.as_str()).collect();
+        assert_eq!(ids, vec!["ancient", "old"]);
+        assert_eq!(stale[1].days_since_verified, 400);
+        assert!(registry
+            .stale_definitions(5)
+            .iter()
+            .any(|s| s.broker_id.as_str() == "fresh"));
+    }
+
+    #[test]
+    fn test_stale_definitions_as_of() {
+        let registry = BrokerRegistry::new();
+        let verified = NaiveDate::from_ymd_opt(2025, 1, 1).expect("valid date");
+        registry
+            .insert(verified_on("broker", verified))
+            .expect("insert");
+
+        let day = |d| verified + chrono::Duration::days(d);
+        // Exactly at the threshold is not yet stale
+        assert!(registry.stale_definitions_as_of(30, day(30)).is_empty());
+        assert_eq!(
+            registry.stale_definitions_as_of(30, day(31)),
+            vec![StaleDefinition {
+                broker_id: BrokerId::new("broker").expect("valid broker ID"),
+                last_verified: verified,
+                days_since_verified: 31,
+            }]
+        );
+        // A definition dated in the future is never stale
+        assert!(registry.stale_definitions_as_of(0, day(-5)).is_empty());
+    }
 }
@@ -0,0 +1,404 @@
+//! Official opt-out API removal submission.
+//!
+//! Some brokers expose a deletion endpoint. Calling it directly is faster and
+//! more reliable than driving a browser through their opt-out form.
+
+use crate::definition::{
+    ApiAuth, ApiBodyFormat, ApiMethod, BrokerDefinition, ConfirmationType, RemovalMethod,
+};
+use crate::error::{BrokerError, Result};
+use crate::removal::RemovalOutcome;
+use std::collections::{BTreeMap, HashMap};
+use std::time::Duration;
+
+/// Request timeout for opt-out API calls.
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Maximum length of a response body kept in failure details.
+const MAX_ERROR_DETAILS_LEN: usize = 500;
+
+/// Submitter for brokers with an official opt-out API.
+#[derive(Debug, Clone)]
+pub struct ApiRemovalSubmitter {
+    client: reqwest::Client,
+}
+
+impl ApiRemovalSubmitter {
+    /// Create a new API submitter.
+    pub fn new() -> Result<Self> {
+        let client = reqwest::Client::builder()
+            .timeout(REQUEST_TIMEOUT)
+            .build()
+            .map_err(|e| BrokerError::RemovalError {
+                broker_id: "unknown".to_string(),
+                reason: format!("Failed to create HTTP client: {e}"),
+            })?;
+
+        Ok(Self { client })
+    }
+
+    /// Create a submitter that uses an existing HTTP client.
+    #[must_use]
+    pub fn with_client(client: reqwest::Client) -> Self {
+        Self { client }
+    }
+
+    /// Submit a removal request for a broker.
+    ///
+    /// `field_values` holds the decrypted profile fields (e.g. `email`,
+    /// `first_name`, `listing_url`) that the definition's `field_mapping`
+    /// templates refer to.
+    ///
+    /// Client errors (4xx) are returned as [`RemovalOutcome::Failed`]. Network
+    /// errors and server errors (5xx) are returned as `Err` so callers can retry.
+    pub async fn submit(
+        &self,
+        broker_def: &BrokerDefinition,
+        field_values: &HashMap<String, String>,
+    ) -> Result<RemovalOutcome> {
+        let RemovalMethod::Api {
+            endpoint,
+            http_method,
+            auth,
+            field_mapping,
+            body_format,
+            confirmation,
+            ..
+        } = &broker_def.removal
+        else {
+            return Err(BrokerError::RemovalError {
+                broker_id: broker_def.id().to_string(),
+                reason: "Not an api removal method".to_string(),
+            });
+        };
+
+        let body = build_body(field_mapping, field_values).map_err(|reason| {
+            BrokerError::RemovalError {
+                broker_id: broker_def.id().to_string(),
+                reason,
+            }
+        })?;
+
+        let request = match http_method {
+            ApiMethod::Post => self.client.post(endpoint),
+            ApiMethod::Put => self.client.put(endpoint),
+            ApiMethod::Delete => self.client.delete(endpoint),
+        };
+        let request = match auth {
+            ApiAuth::None => request,
+            ApiAuth::Bearer { token } => request.bearer_auth(token),
+            ApiAuth::Header { name, value } => request.header(name.as_str(), value.as_str()),
+        };
+        let request = match body_format {
+            ApiBodyFormat::Json => request.json(&body),
+            ApiBodyFormat::Form => request.form(&body),
+        };
+
+        let response = request
+            .send()
+            .await
+            .map_err(|e| BrokerError::RemovalError {
+                broker_id: broker_def.id().to_string(),
+                reason: format!("Request to opt-out API failed: {e}"),
+            })?;
+
+        let status = response.status().as_u16();
+        let text = response.text().await.unwrap_or_default();
+
+        outcome_for_response(status, &text, *confirmation, field_values).ok_or_else(|| {
+            BrokerError::RemovalError {
+                broker_id: broker_def.id().to_string(),
+                reason: format!("Opt-out API returned server error {status}"),
+            }
+        })
+    }
+}
+
+/// Build the request body by filling each mapping template with field values.
+///
+/// Returns an error naming the first placeholder with no value.
+fn build_body(
+    field_mapping: &HashMap<String, String>,
+    field_values: &HashMap<String, String>,
+) -> std::result::Result<BTreeMap<String, String>, String> {
+    field_mapping
+        .iter()
+        .map(|(key, template)| Ok((key.clone(), render_template(template, field_values)?)))
+        .collect()
+}
+
+/// Replace `{name}` placeholders in `template` with values from `field_values`.
+pub(crate) fn render_template(
+    template: &str,
+    field_values: &HashMap<String, String>,
+) -> std::result::Result<String, String> {
+    let mut rendered = String::with_capacity(template.len());
+    let mut rest = template;
+
+    while let Some(start) = rest.find('{') {
+        rendered.push_str(&rest[..start]);
+        let Some(len) = rest[start..].find('}') else {
+            return Err(format!("unterminated placeholder in template '{template}'"));
+        };
+        let name = &rest[start + 1..start + len];
+        let value = field_values
+            .get(name)
+            .ok_or_else(|| format!("no value for field '{name}'"))?;
+        rendered.push_str(value);
+        rest = &rest[start + len + 1..];
+    }
+    rendered.push_str(rest);
+
+    Ok(rendered)
+}
+
+/// Map an API response to a removal outcome.
+///
+/// Returns `None` for server errors, which are worth retrying.
+fn outcome_for_response(
+    status: u16,
+    body: &str,
+    confirmation: ConfirmationType,
+    field_values: &HashMap<String, String>,
+) -> Option<RemovalOutcome> {
+    let failed = |reason: &str| RemovalOutcome::Failed {
+        reason: reason.to_string(),
+        error_details: (!body.is_empty())
+            .then(|| body.chars().take(MAX_ERROR_DETAILS_LEN).collect()),
+    };
+
+    match status {
+        200..=299 => Some(match confirmation {
+            ConfirmationType::EmailVerification => {
+                let email = field_values.get("email").cloned().unwrap_or_default();
+                RemovalOutcome::RequiresEmailVerification {
+                    email: email.clone(),
+                    sent_to: email,
+                }
+            }
+            ConfirmationType::Automatic | ConfirmationType::Manual => RemovalOutcome::Submitted,
+        }),
+        400 | 422 => Some(failed("Opt-out API rejected the request")),
+        401 | 403 => Some(failed("Opt-out API rejected the credentials")),
+        404 => Some(failed("Opt-out API found no matching record")),
+        429 => Some(failed("Opt-out API rate limited the request")),
+        500..=599 => None,
+        _ => Some(failed(&format!("Opt-out API returned status {status}"))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::definition::{
+        BrokerCategory, BrokerMetadata, RemovalConfirmation, RemovalDifficulty, ScanPriority,
+        SearchMethod,
+    };
+    use spectral_core::BrokerId;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::TcpListener;
+
+    fn field_values() -> HashMap<String, String> {
+        HashMap::from([
+            ("email".to_string(), "jane@example.com".to_string()),
+            ("first_name".to_string(), "Jane".to_string()),
+            (
+                "listing_url".to_string(),
+                "https://broker.example/p/123".to_string(),
+            ),
+        ])
+    }
+
+    /// An API broker posting to `endpoint`.
+    ///
+    /// Built directly rather than loaded, since the local mock listener is
+    /// plain `http://` and would not pass definition validation.
+    fn api_broker(endpoint: String, body_format: ApiBodyFormat) -> BrokerDefinition {
+        BrokerDefinition {
+            broker: BrokerMetadata {
+                id: BrokerId::new("api-broker").expect("valid broker ID"),
+                name: "API Broker".to_string(),
+                url: "https://broker.example".to_string(),
+                domain: "broker.example".to_string(),
+                category: BrokerCategory::PeopleSearch,
+                difficulty: RemovalDifficulty::Easy,
+                typical_removal_days: 7,
+                recheck_interval_days: 30,
+                last_verified: chrono::NaiveDate::from_ymd_opt(2026, 1, 1).expect("valid date"),
+                scan_priority: ScanPriority::OnRequest,
+                region_relevance: vec!["US".to_string()],
+                countries: vec![],
+                requires_id_verification: false,
+                requires_account: false,
+                related_brokers: vec![],
+                request_headers: HashMap::new(),
+                legal_contact: None,
+            },
+            search: SearchMethod::Manual {
+                url: "https://broker.example/search".to_string(),
+                instructions: "Search by name".to_string(),
+            },
+            removal: RemovalMethod::Api {
+                endpoint,
+                http_method: ApiMethod::Post,
+                auth: ApiAuth::Bearer {
+                    token: "optout-token".to_string(),
+                },
+                field_mapping: HashMap::from([
+                    ("email_address".to_string(), "{email}".to_string()),
+                    ("profile".to_string(), "{listing_url}".to_string()),
+                ]),
+                body_format,
+                confirmation: ConfirmationType::Automatic,
+                notes: String::new(),
+            },
+            fixture: None,
+            confirmation: RemovalConfirmation::None,
+        }
+    }
+
+    /// Serve a single HTTP response and return the raw request received.
+    async fn mock_api(status_line: &'static str) -> (String, tokio::task::JoinHandle<String>) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.expect("bind");
+        let endpoint = format!("http://{}/v1/optout", listener.local_addr().expect("addr"));
+
+        let handle = tokio::spawn(async move {
+            let (mut socket, _) = listener.accept().await.expect("accept");
+            let mut request = Vec::new();
+            let mut buf = [0u8; 4096];
+            loop {
+                let n = socket.read(&mut buf).await.expect("read");
+                request.extend_from_slice(&buf[..n]);
+                let text = String::from_utf8_lossy(&request);
+                if let Some(header_end) = text.find("\r\n\r\n") {
+                    let content_length = text[..header_end]
+                        .lines()
+                        .find_map(|l| {
+                            l.to_ascii_lowercase()
+                                .strip_prefix("content-length:")
+                                .map(|v| v.trim().parse::<usize>().unwrap_or(0))
+                        })
+                        .unwrap_or(0);
+                    if request.len() >= header_end + 4 + content_length {
+                        break;
+                    }
+                }
+                if n == 0 {
+                    break;
+                }
+            }
+
+            let response =
+                format!("HTTP/1.1 {status_line}\r\ncontent-length: 0\r\nconnection: close\r\n\r\n");
+            socket.write_all(response.as_bytes()).await.expect("write");
+            String::from_utf8_lossy(&request).into_owned()
+        });
+
+        (endpoint, handle)
+    }
+
+    #[tokio::test]
+    async fn test_submit_sends_mapped_json_body() {
+        let (endpoint, server) = mock_api("200 OK").await;
+        let broker = api_broker(endpoint, ApiBodyFormat::Json);
+
+        let outcome = ApiRemovalSubmitter::new()
+            .expect("create submitter")
+            .submit(&broker, &field_values())
+            .await
+            .expect("submit");
+        assert_eq!(outcome, RemovalOutcome::Submitted);
+
+        let request = server.await.expect("server task");
+        assert!(request.starts_with("POST /v1/optout "));
+        assert!(request
+            .to_ascii_lowercase()
+            .contains("authorization: bearer optout-token"));
+        let body = &request[request.find("\r\n\r\n").expect("body") + 4..];
+        let json: serde_json::Value = serde_json::from_str(body).expect("json body");
+        assert_eq!(
+            json,
+            serde_json::json!({
+                "email_address": "jane@example.com",
+                "profile": "https://broker.example/p/123"
+            })
+        );
+    }
+
+    #[tokio::test]
+    async fn test_submit_sends_form_body() {
+        let (endpoint, server) = mock_api("202 Accepted").await;
+        let broker = api_broker(endpoint, ApiBodyFormat::Form);
+
+        let outcome = ApiRemovalSubmitter::new()
+            .expect("create submitter")
+            .submit(&broker, &field_values())
+            .await
+            .expect("submit");
+        assert_eq!(outcome, RemovalOutcome::Submitted);
+
+        let request = server.await.expect("server task");
+        assert!(request
+            .to_ascii_lowercase()
+            .contains("content-type: application/x-www-form-urlencoded"));
+        assert!(request.contains("email_address=jane%40example.com"));
+    }
+
+    #[tokio::test]
+    async fn test_submit_maps_error_statuses() {
+        let (endpoint, server) = mock_api("404 Not Found").await;
+        let broker = api_broker(endpoint, ApiBodyFormat::Json);
+        let outcome = ApiRemovalSubmitter::new()
+            .expect("create submitter")
+            .submit(&broker, &field_values())
+            .await
+            .expect("submit");
+        assert!(outcome.is_failure());
+        server.await.expect("server task");
+
+        let (endpoint, server) = mock_api("503 Service Unavailable").await;
+        let broker = api_broker(endpoint, ApiBodyFormat::Json);
+        let result = ApiRemovalSubmitter::new()
+            .expect("create submitter")
+            .submit(&broker, &field_values())
+            .await;
+        assert!(matches!(result, Err(BrokerError::RemovalError { .. })));
+        server.await.expect("server task");
+    }
+
+    #[test]
+    fn test_outcome_for_response() {
+        let values = field_values();
+        assert_eq!(
+            outcome_for_response(200, "", ConfirmationType::Automatic, &values),
+            Some(RemovalOutcome::Submitted)
+        );
+        assert_eq!(
+            outcome_for_response(201, "", ConfirmationType::EmailVerification, &values),
+            Some(RemovalOutcome::RequiresEmailVerification {
+                email: "jane@example.com".to_string(),
+                sent_to: "jane@example.com".to_string(),
+            })
+        );
+        assert!(matches!(
+            outcome_for_response(401, "bad key", ConfirmationType::Automatic, &values),
+            Some(RemovalOutcome::Failed { error_details: Some(ref d), .. }) if d == "bad key"
+        ));
+        assert_eq!(
+            outcome_for_response(502, "", ConfirmationType::Automatic, &values),
+            None
+        );
+    }
+
+    #[test]
+    fn test_render_template() {
+        let values = field_values();
+        assert_eq!(
+            render_template("{first_name} <{email}>", &values),
+            Ok("Jane <jane@example.com>".to_string())
+        );
+        assert_eq!(render_template("static", &values), Ok("static".to_string()));
+        assert!(render_template("{phone}", &values).is_err());
+        assert!(render_template("{email", &values).is_err());
+    }
+}
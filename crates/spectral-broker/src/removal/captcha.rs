@@ -1,8 +1,64 @@
 //! CAPTCHA detection and solving.
+//!
+//! By default a CAPTCHA is left to the user: [`ManualSolver`] never solves
+//! anything, so the removal is queued for manual action. Users who opt into
+//! a paid solving service get [`ExternalCaptchaSolver`] instead, which sends
+//! the challenge's site key and page URL to a service with a
+//! 2captcha-compatible API, polls for the response token and injects it into
+//! the page so the form can be submitted.
 
-use crate::error::Result;
+use crate::error::{BrokerError, Result};
 use async_trait::async_trait;
+use scraper::{Html, Selector};
+use serde::Deserialize;
 use spectral_browser::{BrowserActions, BrowserEngine};
+use std::time::{Duration, Instant};
+
+/// How long to wait between polls for a solution by default.
+const DEFAULT_POLL_INTERVAL: Duration = Duration::from_secs(5);
+
+/// How long to wait for a solution by default.
+const DEFAULT_SOLVE_TIMEOUT: Duration = Duration::from_secs(180);
+
+/// Request timeout for calls to the solving service.
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Poll response meaning the service is still working on the challenge.
+const NOT_READY: &str = "CAPCHA_NOT_READY";
+
+/// The page a CAPTCHA solver works on.
+#[async_trait]
+pub trait CaptchaPage: Send + Sync {
+    /// URL of the current page.
+    async fn url(&self) -> Result<String>;
+
+    /// HTML of the current page.
+    async fn content(&self) -> Result<String>;
+
+    /// Run a script in the current page.
+    async fn run_script(&self, script: &str) -> Result<()>;
+}
+
+#[async_trait]
+impl CaptchaPage for BrowserEngine {
+    async fn url(&self) -> Result<String> {
+        self.current_url().await.map_err(|e| page_error(&e))
+    }
+
+    async fn content(&self) -> Result<String> {
+        self.page_content().await.map_err(|e| page_error(&e))
+    }
+
+    async fn run_script(&self, script: &str) -> Result<()> {
+        BrowserEngine::run_script(self, script)
+            .await
+            .map_err(|e| page_error(&e))
+    }
+}
+
+fn page_error(e: &spectral_browser::BrowserError) -> BrokerError {
+    BrokerError::CaptchaSolving(format!("browser error: {e}"))
+}
 
 /// CAPTCHA solver trait for pluggable implementations.
 #[async_trait]
@@ -10,7 +66,7 @@ pub trait CaptchaSolver: Send + Sync {
     /// Attempt to solve a CAPTCHA.
     ///
     /// Returns Ok(true) if solved, Ok(false) if manual intervention needed.
-    async fn solve(&self, engine: &BrowserEngine, captcha_selector: &str) -> Result<bool>;
+    async fn solve(&self, page: &dyn CaptchaPage, captcha_selector: &str) -> Result<bool>;
 }
 
 /// Manual CAPTCHA solver - pauses and returns false to signal user intervention needed.
@@ -18,12 +74,299 @@ pub struct ManualSolver;
 
 #[async_trait]
 impl CaptchaSolver for ManualSolver {
-    async fn solve(&self, _engine: &BrowserEngine, _captcha_selector: &str) -> Result<bool> {
+    async fn solve(&self, _page: &dyn CaptchaPage, _captcha_selector: &str) -> Result<bool> {
         // Manual solver doesn't attempt to solve - just signals pause needed
         Ok(false)
     }
 }
 
+/// Kind of CAPTCHA widget.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CaptchaKind {
+    /// Google reCAPTCHA v2
+    ReCaptchaV2,
+    /// hCaptcha
+    HCaptcha,
+}
+
+impl CaptchaKind {
+    /// Name of the form field the widget puts its response token in.
+    #[must_use]
+    pub fn response_field(self) -> &'static str {
+        match self {
+            Self::ReCaptchaV2 => "g-recaptcha-response",
+            Self::HCaptcha => "h-captcha-response",
+        }
+    }
+}
+
+/// A CAPTCHA found on a page, as sent to a solving service.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CaptchaChallenge {
+    /// Kind of widget
+    pub kind: CaptchaKind,
+    /// Public site key the widget was rendered with
+    pub site_key: String,
+    /// URL of the page showing the widget
+    pub page_url: String,
+}
+
+impl CaptchaChallenge {
+    /// Find a reCAPTCHA or hCaptcha widget in `html`.
+    ///
+    /// Looks for a `data-sitekey` attribute first, then for the widget's
+    /// iframe. Returns `None` for other kinds of CAPTCHA.
+    #[must_use]
+    pub fn find(html: &str, page_url: &str) -> Option<Self> {
+        let document = Html::parse_document(html);
+        let challenge = |kind, site_key: &str| Self {
+            kind,
+            site_key: site_key.to_string(),
+            page_url: page_url.to_string(),
+        };
+
+        let widgets = Selector::parse("[data-sitekey]").expect("valid selector");
+        if let Some(widget) = document.select(&widgets).next() {
+            let site_key = widget.value().attr("data-sitekey").unwrap_or_default();
+            let kind = if widget.value().classes().any(|class| class == "h-captcha") {
+                CaptchaKind::HCaptcha
+            } else {
+                CaptchaKind::ReCaptchaV2
+            };
+            if !site_key.is_empty() {
+                return Some(challenge(kind, site_key));
+            }
+        }
+
+        let iframes = Selector::parse("iframe[src]").expect("valid selector");
+        document.select(&iframes).find_map(|iframe| {
+            let src = url::Url::parse(iframe.value().attr("src")?).ok()?;
+            let host = src.host_str()?;
+            let (kind, key_param) = if host.ends_with("hcaptcha.com") {
+                (CaptchaKind::HCaptcha, "sitekey")
+            } else if src.path().contains("/recaptcha/") {
+                (CaptchaKind::ReCaptchaV2, "k")
+            } else {
+                return None;
+            };
+            // hCaptcha puts its parameters in the fragment
+            let params = src
+                .fragment()
+                .map(|fragment| url::form_urlencoded::parse(fragment.as_bytes()))
+                .into_iter()
+                .flatten()
+                .chain(src.query_pairs());
+            params
+                .filter(|(name, value)| name == key_param && !value.is_empty())
+                .map(|(_, value)| challenge(kind, &value))
+                .next()
+        })
+    }
+}
+
+/// Script that puts `token` into the widget's response field and calls the
+/// widget's callback, if it declares one.
+#[must_use]
+pub fn injection_script(kind: CaptchaKind, token: &str) -> String {
+    let token = serde_json::to_string(token).expect("strings serialize");
+    let field = kind.response_field();
+    format!(
+        r#"(function (token) {{
+    var fields = document.querySelectorAll('[name="{field}"]');
+    if (fields.length === 0) {{
+        var widget = document.querySelector('[data-sitekey]');
+        var form = widget ? widget.closest('form') : document.querySelector('form');
+        if (form) {{
+            var field = document.createElement('textarea');
+            field.name = '{field}';
+            field.style.display = 'none';
+            form.appendChild(field);
+            fields = [field];
+        }}
+    }}
+    fields.forEach(function (field) {{
+        field.value = token;
+        field.innerHTML = token;
+    }});
+    var withCallback = document.querySelector('[data-sitekey][data-callback]');
+    if (withCallback) {{
+        var callback = window[withCallback.getAttribute('data-callback')];
+        if (typeof callback === 'function') {{
+            callback(token);
+        }}
+    }}
+}})({token});"#
+    )
+}
+
+/// Response body of the 2captcha-style `in.php` and `res.php` endpoints.
+#[derive(Debug, Deserialize)]
+struct ServiceResponse {
+    status: u8,
+    request: String,
+}
+
+/// Solver backed by a paid CAPTCHA solving service.
+///
+/// Talks to the 2captcha API (`in.php` to submit, `res.php` to poll), which
+/// other services such as anti-captcha also offer. This sends the page URL
+/// and site key to a third party, so it must only be used when the user has
+/// opted in.
+pub struct ExternalCaptchaSolver {
+    client: reqwest::Client,
+    service_url: String,
+    api_key: String,
+    poll_interval: Duration,
+    timeout: Duration,
+}
+
+impl std::fmt::Debug for ExternalCaptchaSolver {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        // Leave out the API key
+        f.debug_struct("ExternalCaptchaSolver")
+            .field("service_url", &self.service_url)
+            .field("poll_interval", &self.poll_interval)
+            .field("timeout", &self.timeout)
+            .finish_non_exhaustive()
+    }
+}
+
+impl ExternalCaptchaSolver {
+    /// Create a solver for the service at `service_url`, e.g.
+    /// `https://2captcha.com`.
+    pub fn new(service_url: impl Into<String>, api_key: impl Into<String>) -> Result<Self> {
+        let client = reqwest::Client::builder()
+            .timeout(REQUEST_TIMEOUT)
+            .build()
+            .map_err(|e| {
+                BrokerError::CaptchaSolving(format!("failed to create HTTP client: {e}"))
+            })?;
+
+        Ok(Self {
+            client,
+            service_url: service_url.into().trim_end_matches('/').to_string(),
+            api_key: api_key.into(),
+            poll_interval: DEFAULT_POLL_INTERVAL,
+            timeout: DEFAULT_SOLVE_TIMEOUT,
+        })
+    }
+
+    /// Set how long to wait between polls for a solution.
+    #[must_use]
+    pub fn with_poll_interval(mut self, poll_interval: Duration) -> Self {
+        self.poll_interval = poll_interval;
+        self
+    }
+
+    /// Set how long to wait for a solution before giving up.
+    #[must_use]
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Submit `challenge` to the service and wait for its response token.
+    ///
+    /// # Errors
+    /// Returns `BrokerError::CaptchaSolving` if the service rejects the
+    /// challenge, reports an error, or has no solution within the timeout.
+    pub async fn request_token(&self, challenge: &CaptchaChallenge) -> Result<String> {
+        let (method, key_param) = match challenge.kind {
+            CaptchaKind::ReCaptchaV2 => ("userrecaptcha", "googlekey"),
+            CaptchaKind::HCaptcha => ("hcaptcha", "sitekey"),
+        };
+        let submitted = self
+            .call(
+                self.client
+                    .post(format!("{}/in.php", self.service_url))
+                    .form(&[
+                        ("key", self.api_key.as_str()),
+                        ("method", method),
+                        (key_param, challenge.site_key.as_str()),
+                        ("pageurl", challenge.page_url.as_str()),
+                        ("json", "1"),
+                    ]),
+            )
+            .await?;
+        if submitted.status != 1 {
+            return Err(BrokerError::CaptchaSolving(format!(
+                "service rejected the challenge: {}",
+                submitted.request
+            )));
+        }
+        let task_id = submitted.request;
+
+        let deadline = Instant::now() + self.timeout;
+        loop {
+            tokio::time::sleep(self.poll_interval).await;
+
+            let polled = self
+                .call(
+                    self.client
+                        .get(format!("{}/res.php", self.service_url))
+                        .query(&[
+                            ("key", self.api_key.as_str()),
+                            ("action", "get"),
+                            ("id", task_id.as_str()),
+                            ("json", "1"),
+                        ]),
+                )
+                .await?;
+            match (polled.status, polled.request.as_str()) {
+                (1, _) => return Ok(polled.request),
+                (_, NOT_READY) if Instant::now() < deadline => {}
+                (_, NOT_READY) => {
+                    return Err(BrokerError::CaptchaSolving(format!(
+                        "no solution within {:?}",
+                        self.timeout
+                    )))
+                }
+                (_, error) => {
+                    return Err(BrokerError::CaptchaSolving(format!(
+                        "service could not solve the challenge: {error}"
+                    )))
+                }
+            }
+        }
+    }
+
+    async fn call(&self, request: reqwest::RequestBuilder) -> Result<ServiceResponse> {
+        request
+            .send()
+            .await
+            .and_then(reqwest::Response::error_for_status)
+            .map_err(|e| BrokerError::CaptchaSolving(format!("service request failed: {e}")))?
+            .json()
+            .await
+            .map_err(|e| BrokerError::CaptchaSolving(format!("unexpected service response: {e}")))
+    }
+}
+
+#[async_trait]
+impl CaptchaSolver for ExternalCaptchaSolver {
+    async fn solve(&self, page: &dyn CaptchaPage, _captcha_selector: &str) -> Result<bool> {
+        let page_url = page.url().await?;
+        let Some(challenge) = CaptchaChallenge::find(&page.content().await?, &page_url) else {
+            tracing::warn!(
+                "CAPTCHA on {} is not a kind the solving service supports",
+                page_url
+            );
+            return Ok(false);
+        };
+
+        tracing::info!(
+            "Sending {:?} on {} to solving service",
+            challenge.kind,
+            page_url
+        );
+        let token = self.request_token(&challenge).await?;
+        page.run_script(&injection_script(challenge.kind, &token))
+            .await?;
+
+        Ok(true)
+    }
+}
+
 /// Detect if a CAPTCHA is present on the page.
 pub async fn detect_captcha(
     engine: &BrowserEngine,
@@ -44,11 +387,206 @@ pub async fn detect_captcha(
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::sync::{Arc, Mutex};
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::TcpListener;
+
+    const PAGE_URL: &str = "https://broker.example/optout";
 
     #[tokio::test]
     async fn test_manual_solver_returns_false() {
-        // Note: This test can't actually create a browser without Chrome installed
-        // Just testing the interface - verify the struct exists and implements the trait
-        assert_eq!(std::mem::size_of::<ManualSolver>(), 0);
+        let page = MockPage::new("<div class=\"g-recaptcha\" data-sitekey=\"key\"></div>");
+        assert!(!ManualSolver.solve(&page, "#captcha").await.unwrap());
+        assert!(page.scripts().is_empty());
+    }
+
+    /// Page with fixed HTML that records the scripts run in it.
+    struct MockPage {
+        html: String,
+        scripts: Mutex<Vec<String>>,
+    }
+
+    impl MockPage {
+        fn new(html: &str) -> Self {
+            Self {
+                html: html.to_string(),
+                scripts: Mutex::new(Vec::new()),
+            }
+        }
+
+        fn scripts(&self) -> Vec<String> {
+            self.scripts.lock().expect("scripts lock").clone()
+        }
+    }
+
+    #[async_trait]
+    impl CaptchaPage for MockPage {
+        async fn url(&self) -> Result<String> {
+            Ok(PAGE_URL.to_string())
+        }
+
+        async fn content(&self) -> Result<String> {
+            Ok(self.html.clone())
+        }
+
+        async fn run_script(&self, script: &str) -> Result<()> {
+            self.scripts
+                .lock()
+                .expect("scripts lock")
+                .push(script.to_string());
+            Ok(())
+        }
+    }
+
+    /// Serve a 2captcha-style API that accepts every challenge as `task-1`
+    /// and answers `polls` in order, repeating the last one.
+    ///
+    /// Returns the service URL and the raw requests received.
+    async fn mock_service(polls: &'static [&'static str]) -> (String, Arc<Mutex<Vec<String>>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.expect("bind");
+        let url = format!("http://{}", listener.local_addr().expect("addr"));
+        let requests = Arc::new(Mutex::new(Vec::new()));
+        let log = Arc::clone(&requests);
+
+        tokio::spawn(async move {
+            let mut poll = 0;
+            while let Ok((mut socket, _)) = listener.accept().await {
+                let mut buf = [0u8; 4096];
+                let n = socket.read(&mut buf).await.unwrap_or(0);
+                let request = String::from_utf8_lossy(&buf[..n]).into_owned();
+                let body = if request.starts_with("POST /in.php") {
+                    r#"{"status":1,"request":"task-1"}"#
+                } else {
+                    let answer = polls[poll.min(polls.len() - 1)];
+                    poll += 1;
+                    answer
+                };
+                log.lock().expect("request log").push(request);
+                let response = format!(
+                    "HTTP/1.1 200 OK\r\ncontent-type: application/json\r\ncontent-length: {}\r\nconnection: close\r\n\r\n{}",
+                    body.len(),
+                    body
+                );
+                let _ = socket.write_all(response.as_bytes()).await;
+            }
+        });
+
+        (url, requests)
+    }
+
+    fn solver(service_url: &str) -> ExternalCaptchaSolver {
+        ExternalCaptchaSolver::new(service_url, "api-key-123")
+            .expect("create solver")
+            .with_poll_interval(Duration::from_millis(10))
+            .with_timeout(Duration::from_secs(5))
+    }
+
+    #[tokio::test]
+    async fn test_external_solver_retrieves_and_injects_token() {
+        let (service_url, requests) = mock_service(&[
+            r#"{"status":0,"request":"CAPCHA_NOT_READY"}"#,
+            r#"{"status":1,"request":"solved-token-xyz"}"#,
+        ])
+        .await;
+        let page = MockPage::new(
+            r#"<form><div class="g-recaptcha" data-sitekey="site-key-abc"></div></form>"#,
+        );
+
+        let solved = solver(&service_url)
+            .solve(&page, "#captcha")
+            .await
+            .expect("solve");
+        assert!(solved);
+
+        let requests = requests.lock().expect("request log").clone();
+        assert_eq!(requests.len(), 3, "one submission and two polls");
+        let submission = &requests[0];
+        assert!(submission.starts_with("POST /in.php"));
+        for param in [
+            "key=api-key-123",
+            "method=userrecaptcha",
+            "googlekey=site-key-abc",
+            "pageurl=https%3A%2F%2Fbroker.example%2Foptout",
+        ] {
+            assert!(submission.contains(param), "missing {param}");
+        }
+        assert!(requests[1].starts_with("GET /res.php?key=api-key-123&action=get&id=task-1"));
+
+        let scripts = page.scripts();
+        assert_eq!(scripts.len(), 1);
+        assert!(scripts[0].contains("[name=\"g-recaptcha-response\"]"));
+        assert!(scripts[0].contains("})(\"solved-token-xyz\");"));
+    }
+
+    #[tokio::test]
+    async fn test_external_solver_reports_service_errors() {
+        let (service_url, _) =
+            mock_service(&[r#"{"status":0,"request":"ERROR_CAPTCHA_UNSOLVABLE"}"#]).await;
+        let page = MockPage::new(r#"<div class="h-captcha" data-sitekey="site-key-abc"></div>"#);
+
+        let result = solver(&service_url).solve(&page, "#captcha").await;
+
+        assert!(matches!(
+            result,
+            Err(BrokerError::CaptchaSolving(reason)) if reason.contains("ERROR_CAPTCHA_UNSOLVABLE")
+        ));
+        assert!(page.scripts().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_external_solver_skips_unsupported_captcha() {
+        let (service_url, requests) = mock_service(&[]).await;
+        let page = MockPage::new(r#"<img id="captcha" src="/captcha.png">"#);
+
+        let solved = solver(&service_url)
+            .solve(&page, "#captcha")
+            .await
+            .expect("solve");
+
+        assert!(!solved);
+        assert!(requests.lock().expect("request log").is_empty());
+    }
+
+    #[test]
+    fn test_find_challenge() {
+        let hcaptcha = CaptchaChallenge::find(
+            r#"<div class="h-captcha" data-sitekey="h-key"></div>"#,
+            PAGE_URL,
+        )
+        .expect("hcaptcha widget");
+        assert_eq!(hcaptcha.kind, CaptchaKind::HCaptcha);
+        assert_eq!(hcaptcha.site_key, "h-key");
+        assert_eq!(hcaptcha.page_url, PAGE_URL);
+
+        let recaptcha_frame = CaptchaChallenge::find(
+            r#"<iframe src="https://www.google.com/recaptcha/api2/anchor?ar=1&k=g-key&co=abc"></iframe>"#,
+            PAGE_URL,
+        )
+        .expect("recaptcha iframe");
+        assert_eq!(recaptcha_frame.kind, CaptchaKind::ReCaptchaV2);
+        assert_eq!(recaptcha_frame.site_key, "g-key");
+
+        let hcaptcha_frame = CaptchaChallenge::find(
+            r#"<iframe src="https://newassets.hcaptcha.com/captcha/v1/abc/static/hcaptcha.html#frame=checkbox&sitekey=h-key"></iframe>"#,
+            PAGE_URL,
+        )
+        .expect("hcaptcha iframe");
+        assert_eq!(hcaptcha_frame.kind, CaptchaKind::HCaptcha);
+        assert_eq!(hcaptcha_frame.site_key, "h-key");
+
+        assert_eq!(
+            CaptchaChallenge::find(
+                r#"<iframe src="https://example.com/embed"></iframe>"#,
+                PAGE_URL
+            ),
+            None
+        );
+    }
+
+    #[test]
+    fn test_injection_script_escapes_token() {
+        let script = injection_script(CaptchaKind::HCaptcha, "a\"b'</script>");
+        assert!(script.contains("[name=\"h-captcha-response\"]"));
+        assert!(script.ends_with("})(\"a\\\"b'</script>\");"));
     }
 }
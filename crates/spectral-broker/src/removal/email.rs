@@ -0,0 +1,144 @@
+//! Email removal templates.
+//!
+//! Email removal bodies name profile fields as `{{field_name}}`
+//! placeholders. The same renderer fills them when a removal email is sent
+//! and when a broker's removal method is previewed, so a preview shows the
+//! email exactly as it would go out.
+
+use std::collections::HashMap;
+
+/// A rendered removal email.
+pub struct EmailTemplate {
+    /// Broker's removal address
+    pub to: String,
+    /// Subject line
+    pub subject: String,
+    /// Body with its placeholders filled in
+    pub body: String,
+}
+
+/// Profile fields that can fill a `{{field_name}}` placeholder.
+pub const TEMPLATE_FIELDS: &[&str] = &[
+    "full_name",
+    "first_name",
+    "middle_name",
+    "last_name",
+    "address",
+    "city",
+    "state",
+    "zip_code",
+    "date_of_birth",
+    "email",
+    "phone",
+];
+
+/// Names of the `{{field_name}}` placeholders in template, in order of first use.
+#[must_use]
+pub fn placeholders(template: &str) -> Vec<String> {
+    let mut names: Vec<String> = Vec::new();
+    let mut rest = template;
+    while let Some(start) = rest.find("{{") {
+        let after = &rest[start + 2..];
+        let Some(len) = after.find("}}") else {
+            break;
+        };
+        let name = after[..len].trim();
+        if !name.is_empty() && !names.iter().any(|n| n == name) {
+            names.push(name.to_string());
+        }
+        rest = &after[len + 2..];
+    }
+    names
+}
+
+/// Checks that every placeholder in template names one of `available_fields`.
+///
+/// # Errors
+/// Returns the placeholders that would be left in the rendered email.
+pub fn validate_template(template: &str, available_fields: &[&str]) -> Result<(), Vec<String>> {
+    let unresolved: Vec<String> = placeholders(template)
+        .into_iter()
+        .filter(|name| !available_fields.contains(&name.as_str()))
+        .collect();
+    if unresolved.is_empty() {
+        Ok(())
+    } else {
+        Err(unresolved)
+    }
+}
+
+/// Substitutes `{{field_name}}` placeholders in template with profile values.
+#[must_use]
+#[allow(clippy::implicit_hasher)]
+pub fn render_template(
+    template: &str,
+    email: &str,
+    to: &str,
+    profile_fields: &HashMap<String, String>,
+) -> EmailTemplate {
+    let subject = format!(
+        "Opt-Out Request — {}",
+        profile_fields.get("full_name").cloned().unwrap_or_default()
+    );
+    let mut body = template.to_string();
+    for (key, value) in profile_fields {
+        body = body.replace(&format!("{{{{{key}}}}}"), value);
+    }
+    // Replace remaining known placeholders
+    body = body.replace("{{email}}", email);
+    EmailTemplate {
+        to: to.to_string(),
+        subject,
+        body,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_template_substitutes_fields() {
+        let mut fields = HashMap::new();
+        fields.insert("full_name".to_string(), "Alice Smith".to_string());
+        fields.insert("address".to_string(), "123 Main St".to_string());
+        let template = "Name: {{full_name}}\nAddress: {{address}}\nEmail: {{email}}";
+        let result = render_template(template, "alice@example.com", "optout@broker.com", &fields);
+        assert_eq!(result.to, "optout@broker.com");
+        assert!(result.subject.contains("Alice Smith"));
+        assert!(result.body.contains("Alice Smith"));
+        assert!(result.body.contains("123 Main St"));
+        assert!(result.body.contains("alice@example.com"));
+    }
+
+    #[test]
+    fn test_placeholders_are_listed_once_in_order() {
+        let template = "Dear {{first_name}}, re {{ full_name }} and {{first_name}} {{unclosed";
+        assert_eq!(placeholders(template), ["first_name", "full_name"]);
+    }
+
+    #[test]
+    fn test_validate_template_reports_missing_fields() {
+        let template = "Dear {{first_name}},\nPlease remove {{full_name}} ({{case_number}}).";
+        assert_eq!(
+            validate_template(template, &["full_name", "email"]),
+            Err(vec!["first_name".to_string(), "case_number".to_string()])
+        );
+        assert_eq!(
+            validate_template(template, &["first_name", "full_name", "case_number"]),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn test_validate_template_against_profile_fields() {
+        assert!(
+            validate_template("Name: {{full_name}}\nEmail: {{email}}", TEMPLATE_FIELDS).is_ok()
+        );
+        assert!(validate_template("Plain text", TEMPLATE_FIELDS).is_ok());
+        assert_eq!(
+            validate_template("Ref: {{listing_id}}", TEMPLATE_FIELDS),
+            Err(vec!["listing_id".to_string()])
+        );
+    }
+}
@@ -1,9 +1,17 @@
 //! Removal execution and result tracking.
 
+pub mod api;
 pub mod captcha;
+pub mod email;
 pub mod result;
+pub mod verify;
 pub mod web_form;
 
-pub use captcha::{detect_captcha, CaptchaSolver, ManualSolver};
+pub use api::ApiRemovalSubmitter;
+pub use captcha::{
+    detect_captcha, CaptchaChallenge, CaptchaKind, CaptchaPage, CaptchaSolver,
+    ExternalCaptchaSolver, ManualSolver,
+};
 pub use result::RemovalOutcome;
-pub use web_form::WebFormSubmitter;
+pub use verify::{RemovalVerification, RemovalVerifier};
+pub use web_form::{classify_result_page, detect_account_wall, WebFormSubmitter};
@@ -22,8 +22,18 @@ pub enum RemovalOutcome {
         captcha_url: String,
     },
 
-    /// Broker requires account creation first
-    RequiresAccountCreation,
+    /// Broker only accepts removals from signed-in users, which is left to
+    /// the user
+    RequiresAccountCreation {
+        /// Where the user signs in or creates the account
+        signup_url: String,
+    },
+
+    /// Broker requires a government ID upload, which is left to the user
+    RequiresIdVerification {
+        /// Where the user finds the broker's instructions
+        instructions_url: String,
+    },
 
     /// Submission failed with reason
     Failed {
@@ -42,7 +52,8 @@ impl RemovalOutcome {
             self,
             Self::RequiresEmailVerification { .. }
                 | Self::RequiresCaptcha { .. }
-                | Self::RequiresAccountCreation
+                | Self::RequiresAccountCreation { .. }
+                | Self::RequiresIdVerification { .. }
         )
     }
 
@@ -70,6 +81,17 @@ mod tests {
         };
         assert!(outcome.requires_user_action());
 
+        let outcome = RemovalOutcome::RequiresIdVerification {
+            instructions_url: "https://example.com/id".to_string(),
+        };
+        assert!(outcome.requires_user_action());
+        assert!(!outcome.is_failure());
+
+        let outcome = RemovalOutcome::RequiresAccountCreation {
+            signup_url: "https://example.com/signup".to_string(),
+        };
+        assert!(outcome.requires_user_action());
+
         let outcome = RemovalOutcome::Submitted;
         assert!(!outcome.requires_user_action());
     }
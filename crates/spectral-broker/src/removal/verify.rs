@@ -0,0 +1,267 @@
+//! Follow-up checks that a submitted removal took effect.
+//!
+//! A broker accepting an opt-out does not mean the listing is gone. For
+//! brokers whose definition has on-site confirmation, the listing page is
+//! fetched again: a 404 or 410, or the broker's "removed" text, confirms the
+//! removal.
+
+use crate::definition::{BrokerDefinition, RemovalConfirmation};
+use crate::error::{BrokerError, Result};
+use crate::removal::api::render_template;
+use std::collections::HashMap;
+use std::time::Duration;
+
+/// Request timeout for confirmation page fetches.
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Result of checking whether a removal took effect.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RemovalVerification {
+    /// The listing is gone
+    Removed,
+    /// The listing is still published
+    StillListed,
+    /// The broker has no on-site confirmation; wait for its email or check
+    /// by hand
+    NotCheckable,
+}
+
+/// Checks brokers' sites to confirm that removals went through.
+#[derive(Debug, Clone)]
+pub struct RemovalVerifier {
+    client: reqwest::Client,
+}
+
+impl RemovalVerifier {
+    /// Create a new verifier.
+    pub fn new() -> Result<Self> {
+        let client = reqwest::Client::builder()
+            .timeout(REQUEST_TIMEOUT)
+            .build()
+            .map_err(|e| BrokerError::RemovalError {
+                broker_id: "unknown".to_string(),
+                reason: format!("Failed to create HTTP client: {e}"),
+            })?;
+
+        Ok(Self { client })
+    }
+
+    /// Create a verifier that uses an existing HTTP client.
+    #[must_use]
+    pub fn with_client(client: reqwest::Client) -> Self {
+        Self { client }
+    }
+
+    /// Check whether the removal for a broker has taken effect.
+    ///
+    /// `field_values` holds the profile fields and the `found_listing_url`
+    /// that the definition's confirmation URL template refers to.
+    ///
+    /// # Errors
+    /// Returns `BrokerError::RemovalError` if the URL template cannot be
+    /// filled, the page cannot be fetched, or the broker answers with a
+    /// status that says nothing about the listing (e.g. a server error or
+    /// rate limit).
+    pub async fn verify_removal(
+        &self,
+        broker_def: &BrokerDefinition,
+        field_values: &HashMap<String, String>,
+    ) -> Result<RemovalVerification> {
+        let RemovalConfirmation::OnSite { url, removed_text } = &broker_def.confirmation else {
+            return Ok(RemovalVerification::NotCheckable);
+        };
+        let error = |reason: String| BrokerError::RemovalError {
+            broker_id: broker_def.id().to_string(),
+            reason,
+        };
+
+        let url = render_template(url, field_values).map_err(error)?;
+        let mut request = self.client.get(&url);
+        for (name, value) in broker_def.request_headers() {
+            request = request.header(name.as_str(), value.as_str());
+        }
+        let response = request
+            .send()
+            .await
+            .map_err(|e| error(format!("Failed to fetch confirmation page: {e}")))?;
+
+        let status = response.status().as_u16();
+        let body = response.text().await.unwrap_or_default();
+        verification_for_response(status, &body, removed_text.as_deref())
+            .ok_or_else(|| error(format!("Confirmation page returned status {status}")))
+    }
+}
+
+/// Map a confirmation page response to a verification result.
+///
+/// Returns `None` when the status does not tell whether the listing exists.
+fn verification_for_response(
+    status: u16,
+    body: &str,
+    removed_text: Option<&str>,
+) -> Option<RemovalVerification> {
+    match status {
+        404 | 410 => Some(RemovalVerification::Removed),
+        200..=299 => Some(match removed_text {
+            Some(text) if body.contains(text) => RemovalVerification::Removed,
+            _ => RemovalVerification::StillListed,
+        }),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::definition::{
+        BrokerCategory, BrokerMetadata, RemovalDifficulty, RemovalMethod, ScanPriority,
+        SearchMethod,
+    };
+    use spectral_core::BrokerId;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::TcpListener;
+
+    fn broker(confirmation: RemovalConfirmation) -> BrokerDefinition {
+        BrokerDefinition {
+            broker: BrokerMetadata {
+                id: BrokerId::new("listing-broker").expect("valid broker ID"),
+                name: "Listing Broker".to_string(),
+                url: "https://broker.example".to_string(),
+                domain: "broker.example".to_string(),
+                category: BrokerCategory::PeopleSearch,
+                difficulty: RemovalDifficulty::Easy,
+                typical_removal_days: 7,
+                recheck_interval_days: 30,
+                last_verified: chrono::NaiveDate::from_ymd_opt(2026, 1, 1).expect("valid date"),
+                scan_priority: ScanPriority::OnRequest,
+                region_relevance: vec!["US".to_string()],
+                countries: vec![],
+                requires_id_verification: false,
+                requires_account: false,
+                related_brokers: vec![],
+                request_headers: HashMap::new(),
+                legal_contact: None,
+            },
+            search: SearchMethod::Manual {
+                url: "https://broker.example/search".to_string(),
+                instructions: "Search by name".to_string(),
+            },
+            removal: RemovalMethod::Manual {
+                instructions: "Use the opt-out page".to_string(),
+            },
+            fixture: None,
+            confirmation,
+        }
+    }
+
+    fn on_site() -> RemovalConfirmation {
+        RemovalConfirmation::OnSite {
+            url: "{found_listing_url}".to_string(),
+            removed_text: None,
+        }
+    }
+
+    /// Serve a single HTTP response and return the listing URL it answers.
+    async fn mock_listing(status_line: &'static str, body: &'static str) -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").await.expect("bind");
+        let url = format!("http://{}/p/123", listener.local_addr().expect("addr"));
+
+        tokio::spawn(async move {
+            let (mut socket, _) = listener.accept().await.expect("accept");
+            let mut buf = [0u8; 4096];
+            let mut request = Vec::new();
+            while !String::from_utf8_lossy(&request).contains("\r\n\r\n") {
+                let n = socket.read(&mut buf).await.expect("read");
+                if n == 0 {
+                    break;
+                }
+                request.extend_from_slice(&buf[..n]);
+            }
+            let response = format!(
+                "HTTP/1.1 {status_line}\r\ncontent-length: {}\r\nconnection: close\r\n\r\n{body}",
+                body.len()
+            );
+            socket.write_all(response.as_bytes()).await.expect("write");
+        });
+
+        url
+    }
+
+    async fn verify(broker_def: &BrokerDefinition, listing_url: String) -> RemovalVerification {
+        let fields = HashMap::from([("found_listing_url".to_string(), listing_url)]);
+        RemovalVerifier::new()
+            .expect("create verifier")
+            .verify_removal(broker_def, &fields)
+            .await
+            .expect("verify")
+    }
+
+    #[tokio::test]
+    async fn test_missing_listing_is_confirmed_removed() {
+        let url = mock_listing("404 Not Found", "").await;
+        assert_eq!(
+            verify(&broker(on_site()), url).await,
+            RemovalVerification::Removed
+        );
+    }
+
+    #[tokio::test]
+    async fn test_listing_still_present_is_not_removed() {
+        let url = mock_listing("200 OK", "<h1>Jane Sample, 42</h1>").await;
+        assert_eq!(
+            verify(&broker(on_site()), url).await,
+            RemovalVerification::StillListed
+        );
+    }
+
+    #[tokio::test]
+    async fn test_removed_text_confirms_removal() {
+        let confirmation = RemovalConfirmation::OnSite {
+            url: "{found_listing_url}".to_string(),
+            removed_text: Some("This profile has been removed".to_string()),
+        };
+        let url = mock_listing("200 OK", "<p>This profile has been removed</p>").await;
+        assert_eq!(
+            verify(&broker(confirmation), url).await,
+            RemovalVerification::Removed
+        );
+    }
+
+    #[tokio::test]
+    async fn test_brokers_without_on_site_confirmation_are_not_checked() {
+        for confirmation in [RemovalConfirmation::None, RemovalConfirmation::Email] {
+            assert_eq!(
+                verify(&broker(confirmation), "http://127.0.0.1:9/".to_string()).await,
+                RemovalVerification::NotCheckable
+            );
+        }
+    }
+
+    #[test]
+    fn test_inconclusive_statuses() {
+        assert_eq!(verification_for_response(503, "", None), None);
+        assert_eq!(verification_for_response(429, "", None), None);
+        assert_eq!(
+            verification_for_response(410, "", None),
+            Some(RemovalVerification::Removed)
+        );
+    }
+
+    #[test]
+    fn test_on_site_confirmation_parses_from_toml() {
+        let confirmation: RemovalConfirmation =
+            toml::from_str("method = \"on-site\"\nremoved_text = \"No longer available\"\n")
+                .expect("parse confirmation");
+        assert_eq!(
+            confirmation,
+            RemovalConfirmation::OnSite {
+                url: "{found_listing_url}".to_string(),
+                removed_text: Some("No longer available".to_string()),
+            }
+        );
+        assert_eq!(
+            toml::from_str::<RemovalConfirmation>("method = \"email\"\n").expect("parse"),
+            RemovalConfirmation::Email
+        );
+    }
+}
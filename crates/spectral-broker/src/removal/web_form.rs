@@ -1,15 +1,15 @@
 //! Web form removal submission.
 
-use crate::definition::{BrokerDefinition, RemovalMethod};
+use crate::definition::{BrokerDefinition, FormSelectors, RemovalMethod};
 use crate::error::{BrokerError, Result};
 use crate::removal::{detect_captcha, CaptchaSolver, ManualSolver, RemovalOutcome};
+use scraper::{ElementRef, Html, Selector};
 use spectral_browser::{BrowserActions, BrowserEngine};
 use std::collections::HashMap;
 
 /// Web form submitter for automated opt-out requests.
 pub struct WebFormSubmitter {
     engine: BrowserEngine,
-    #[allow(dead_code)]
     captcha_solver: Box<dyn CaptchaSolver>,
 }
 
@@ -29,6 +29,13 @@ impl WebFormSubmitter {
         })
     }
 
+    /// Use `solver` for CAPTCHAs instead of handing them to the user.
+    #[must_use]
+    pub fn with_captcha_solver(mut self, solver: Box<dyn CaptchaSolver>) -> Self {
+        self.captcha_solver = solver;
+        self
+    }
+
     /// Submit a removal request for a broker.
     pub async fn submit(
         &self,
@@ -57,14 +64,31 @@ impl WebFormSubmitter {
                 reason: format!("Navigation failed: {e}"),
             })?;
 
+        // Bail before filling anything if the form sits behind a login
+        if let Some(outcome) =
+            detect_account_wall(&self.page_content(broker_def).await?, form_selectors, url)
+        {
+            return Ok(outcome);
+        }
+
         // Check for CAPTCHA
         let captcha_detected =
             detect_captcha(&self.engine, form_selectors.captcha_frame.as_deref()).await?;
 
         if captcha_detected {
-            return Ok(RemovalOutcome::RequiresCaptcha {
-                captcha_url: url.clone(),
-            });
+            let selector = form_selectors.captcha_frame.as_deref().unwrap_or_default();
+            let solved = match self.captcha_solver.solve(&self.engine, selector).await {
+                Ok(solved) => solved,
+                Err(e) => {
+                    tracing::warn!("CAPTCHA solving failed for {}: {}", broker_def.id(), e);
+                    false
+                }
+            };
+            if !solved {
+                return Ok(RemovalOutcome::RequiresCaptcha {
+                    captcha_url: url.clone(),
+                });
+            }
         }
 
         // Fill form fields
@@ -99,33 +123,188 @@ impl WebFormSubmitter {
         // Wait a moment for submission to process
         tokio::time::sleep(tokio::time::Duration::from_secs(2)).await;
 
-        // Check for success indicator
-        if let Some(success_sel) = &form_selectors.success_indicator {
-            match self.engine.wait_for_selector(success_sel, 5000).await {
-                Ok(()) => {
-                    // Success! Get email from field_values if present
-                    let email = field_values.get("email").cloned().unwrap_or_default();
-
-                    return Ok(RemovalOutcome::RequiresEmailVerification {
-                        email: email.clone(),
-                        sent_to: email,
-                    });
-                }
-                Err(_) => {
-                    // Success indicator not found - might have failed
-                    return Ok(RemovalOutcome::Failed {
-                        reason: "Success confirmation not detected".to_string(),
-                        error_details: None,
-                    });
-                }
+        // A 200 response can still be a rejection, so inspect the result page
+        let email = field_values.get("email").map(String::as_str);
+        let mut outcome = classify_result_page(
+            &self.page_content(broker_def).await?,
+            form_selectors,
+            url,
+            email,
+        );
+
+        // The confirmation may render late; give it a few more seconds
+        if let (Some(success_sel), RemovalOutcome::Failed { .. }) =
+            (&form_selectors.success_indicator, &outcome)
+        {
+            if self
+                .engine
+                .wait_for_selector(success_sel, 5000)
+                .await
+                .is_ok()
+            {
+                outcome = classify_result_page(
+                    &self.page_content(broker_def).await?,
+                    form_selectors,
+                    url,
+                    email,
+                );
             }
         }
 
-        // No success indicator configured, assume submitted
-        Ok(RemovalOutcome::Submitted)
+        Ok(outcome)
+    }
+
+    /// HTML of the page the browser is currently on.
+    async fn page_content(&self, broker_def: &BrokerDefinition) -> Result<String> {
+        self.engine
+            .page_content()
+            .await
+            .map_err(|e| BrokerError::RemovalError {
+                broker_id: broker_def.id().to_string(),
+                reason: format!("Failed to read result page: {e}"),
+            })
     }
 }
 
+/// Selectors for common login and signup forms, checked in addition to the
+/// broker's own `account_wall` selector.
+const ACCOUNT_WALL_SELECTORS: &[&str] = &[
+    "input[type='password']",
+    "form[action*='login']",
+    "form[action*='signin']",
+    "form[action*='signup']",
+    "form[action*='register']",
+];
+
+/// Check whether the opt-out page is hidden behind a login or signup wall,
+/// returning [`RemovalOutcome::RequiresAccountCreation`] pointing at
+/// `signup_url` if so.
+///
+/// The broker's `account_wall` selector is trusted on its own. The common
+/// login-form selectors only count when none of the broker's form inputs are
+/// on the page, so a sign-in box in the site header next to the opt-out form
+/// is not mistaken for a wall.
+#[must_use]
+pub fn detect_account_wall(
+    html: &str,
+    form_selectors: &FormSelectors,
+    signup_url: &str,
+) -> Option<RemovalOutcome> {
+    let document = Html::parse_document(html);
+
+    let declared_wall = form_selectors
+        .account_wall
+        .as_deref()
+        .is_some_and(|css| select_first(&document, css).is_some());
+
+    let form_present = [
+        &form_selectors.listing_url_input,
+        &form_selectors.email_input,
+        &form_selectors.first_name_input,
+        &form_selectors.last_name_input,
+        &form_selectors.full_name_input,
+    ]
+    .into_iter()
+    .flatten()
+    .any(|css| select_first(&document, css).is_some());
+
+    let login_form = !form_present
+        && ACCOUNT_WALL_SELECTORS
+            .iter()
+            .any(|css| select_first(&document, css).is_some());
+
+    (declared_wall || login_form).then(|| RemovalOutcome::RequiresAccountCreation {
+        signup_url: signup_url.to_string(),
+    })
+}
+
+/// Selectors for common CAPTCHA widgets, checked in addition to the broker's
+/// own `captcha_frame` selector.
+const CAPTCHA_WIDGET_SELECTORS: &[&str] = &[
+    ".g-recaptcha",
+    "iframe[src*='recaptcha']",
+    ".h-captcha",
+    "iframe[src*='hcaptcha']",
+    ".cf-turnstile",
+];
+
+/// Decide the outcome of a web-form submission from the page shown after it.
+///
+/// Checks, in order:
+/// 1. A CAPTCHA on the result page means the request was not accepted yet.
+/// 2. The broker's `error_indicator` means it was rejected; the indicator's
+///    text becomes the failure reason.
+/// 3. If the broker defines a `success_indicator`, it must be present.
+///    Brokers that confirm by email then need the user to click the link.
+/// 4. Without any indicators the submission is assumed to have gone through.
+#[must_use]
+pub fn classify_result_page(
+    html: &str,
+    form_selectors: &FormSelectors,
+    captcha_url: &str,
+    email: Option<&str>,
+) -> RemovalOutcome {
+    let document = Html::parse_document(html);
+
+    let captcha_present = form_selectors
+        .captcha_frame
+        .iter()
+        .map(String::as_str)
+        .chain(CAPTCHA_WIDGET_SELECTORS.iter().copied())
+        .any(|css| select_first(&document, css).is_some());
+    if captcha_present {
+        return RemovalOutcome::RequiresCaptcha {
+            captcha_url: captcha_url.to_string(),
+        };
+    }
+
+    if let Some(error_element) = form_selectors
+        .error_indicator
+        .as_deref()
+        .and_then(|css| select_first(&document, css))
+    {
+        let text = error_element
+            .text()
+            .collect::<Vec<_>>()
+            .join(" ")
+            .split_whitespace()
+            .collect::<Vec<_>>()
+            .join(" ");
+        let reason = if text.is_empty() {
+            "Unknown error".to_string()
+        } else {
+            text
+        };
+        return RemovalOutcome::Failed {
+            reason: format!("Form error: {reason}"),
+            error_details: None,
+        };
+    }
+
+    if let Some(success_sel) = &form_selectors.success_indicator {
+        if select_first(&document, success_sel).is_none() {
+            return RemovalOutcome::Failed {
+                reason: "Success confirmation not detected".to_string(),
+                error_details: None,
+            };
+        }
+        let email = email.unwrap_or_default().to_string();
+        return RemovalOutcome::RequiresEmailVerification {
+            email: email.clone(),
+            sent_to: email,
+        };
+    }
+
+    RemovalOutcome::Submitted
+}
+
+/// First element matching `css`, or `None` if nothing matches or the
+/// selector is invalid.
+fn select_first<'a>(document: &'a Html, css: &str) -> Option<ElementRef<'a>> {
+    let selector = Selector::parse(css).ok()?;
+    document.select(&selector).next()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -135,4 +314,141 @@ mod tests {
         // Just verify the struct compiles
         assert_eq!(std::mem::size_of::<Box<dyn CaptchaSolver>>(), 16);
     }
+
+    fn selectors() -> FormSelectors {
+        FormSelectors {
+            listing_url_input: None,
+            email_input: Some("#email".to_string()),
+            first_name_input: None,
+            last_name_input: None,
+            full_name_input: None,
+            submit_button: "button[type='submit']".to_string(),
+            captcha_frame: Some("#captcha".to_string()),
+            success_indicator: Some(".optout-success".to_string()),
+            error_indicator: Some(".alert-error".to_string()),
+            account_wall: None,
+        }
+    }
+
+    const FORM_URL: &str = "https://broker.example/optout";
+
+    #[test]
+    fn test_success_page() {
+        let html = r#"<html><body>
+            <div class="optout-success">Your request has been received.</div>
+        </body></html>"#;
+
+        assert_eq!(
+            classify_result_page(html, &selectors(), FORM_URL, Some("user@example.com")),
+            RemovalOutcome::RequiresEmailVerification {
+                email: "user@example.com".to_string(),
+                sent_to: "user@example.com".to_string(),
+            }
+        );
+    }
+
+    #[test]
+    fn test_error_page_returned_with_200() {
+        let html = r#"<html><body>
+            <form id="optout"><input id="email"></form>
+            <p class="alert-error">
+                We could not find a   listing
+                matching that URL.
+            </p>
+        </body></html>"#;
+
+        assert_eq!(
+            classify_result_page(html, &selectors(), FORM_URL, Some("user@example.com")),
+            RemovalOutcome::Failed {
+                reason: "Form error: We could not find a listing matching that URL.".to_string(),
+                error_details: None,
+            }
+        );
+    }
+
+    #[test]
+    fn test_captcha_page() {
+        let html = r#"<html><body>
+            <p>Please verify you are human.</p>
+            <div class="g-recaptcha" data-sitekey="abc"></div>
+        </body></html>"#;
+
+        assert_eq!(
+            classify_result_page(html, &selectors(), FORM_URL, None),
+            RemovalOutcome::RequiresCaptcha {
+                captcha_url: FORM_URL.to_string(),
+            }
+        );
+    }
+
+    #[test]
+    fn test_missing_success_indicator_fails() {
+        let html = "<html><body><p>Thanks!</p></body></html>";
+
+        let outcome = classify_result_page(html, &selectors(), FORM_URL, None);
+        assert!(outcome.is_failure());
+    }
+
+    #[test]
+    fn test_login_wall_detected() {
+        let html = r#"<html><body>
+            <form action="/login" method="post">
+                <input name="username"><input type="password" name="password">
+            </form>
+        </body></html>"#;
+
+        assert_eq!(
+            detect_account_wall(html, &selectors(), FORM_URL),
+            Some(RemovalOutcome::RequiresAccountCreation {
+                signup_url: FORM_URL.to_string(),
+            })
+        );
+    }
+
+    #[test]
+    fn test_header_login_next_to_form_is_not_a_wall() {
+        let html = r#"<html><body>
+            <header><form action="/login"><input type="password"></form></header>
+            <form id="optout"><input id="email"></form>
+        </body></html>"#;
+
+        assert_eq!(detect_account_wall(html, &selectors(), FORM_URL), None);
+    }
+
+    #[test]
+    fn test_broker_account_wall_selector() {
+        let selectors = FormSelectors {
+            account_wall: Some(".members-only".to_string()),
+            ..selectors()
+        };
+        let html = r#"<html><body>
+            <div class="members-only">Sign in to manage your listing</div>
+            <form id="optout"><input id="email"></form>
+        </body></html>"#;
+
+        assert!(detect_account_wall(html, &selectors, FORM_URL).is_some());
+        assert_eq!(
+            detect_account_wall(
+                "<html><body><form><input id=\"email\"></form></body></html>",
+                &selectors,
+                FORM_URL
+            ),
+            None
+        );
+    }
+
+    #[test]
+    fn test_no_indicators_assumes_submitted() {
+        let selectors = FormSelectors {
+            captcha_frame: None,
+            success_indicator: None,
+            error_indicator: None,
+            ..selectors()
+        };
+
+        assert_eq!(
+            classify_result_page("<html><body>OK</body></html>", &selectors, FORM_URL, None),
+            RemovalOutcome::Submitted
+        );
+    }
 }
@@ -0,0 +1,289 @@
+//! Offline self-test of broker result selectors against bundled HTML fixtures.
+//!
+//! Broker sites change their markup without notice, which silently breaks the
+//! CSS selectors in a definition. Each definition may reference a small,
+//! PII-free fixture page; running the selectors against it catches a broken
+//! selector before a user's scan does.
+
+use crate::definition::{BrokerDefinition, ResultSelectors, SelectorFixture};
+use crate::loader::embedded_file_contents;
+use scraper::{ElementRef, Html, Selector};
+use serde::{Deserialize, Serialize};
+use spectral_core::BrokerId;
+
+/// Outcome of testing one broker's selectors against its fixture.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "status", rename_all = "snake_case")]
+pub enum SelectorTestStatus {
+    /// Selectors extracted the expected listings and fields
+    Passed,
+    /// Selectors did not match the fixture as expected
+    Failed {
+        /// What went wrong
+        reason: String,
+    },
+}
+
+/// Result of running a broker's result selectors against its fixture.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SelectorTestResult {
+    /// Broker that was tested
+    pub broker_id: BrokerId,
+    /// Pass/fail status
+    pub status: SelectorTestStatus,
+    /// Number of listings extracted from the fixture
+    pub found_results: usize,
+    /// Number of listings the fixture expects
+    pub expected_results: usize,
+    /// Expected fields that were missing from at least one listing
+    pub missing_fields: Vec<String>,
+}
+
+impl SelectorTestResult {
+    /// Check if the selectors passed.
+    #[must_use]
+    pub fn passed(&self) -> bool {
+        self.status == SelectorTestStatus::Passed
+    }
+
+    fn failed(broker_id: &BrokerId, fixture: &SelectorFixture, reason: String) -> Self {
+        Self {
+            broker_id: broker_id.clone(),
+            status: SelectorTestStatus::Failed { reason },
+            found_results: 0,
+            expected_results: fixture.expected_results,
+            missing_fields: Vec::new(),
+        }
+    }
+}
+
+/// Run a broker's selectors against its fixture.
+///
+/// Returns `None` if the broker has no result selectors or no fixture.
+#[must_use]
+pub fn test_definition(definition: &BrokerDefinition) -> Option<SelectorTestResult> {
+    let fixture = definition.fixture.as_ref()?;
+    let selectors = definition.search.result_selectors()?;
+
+    let html = match std::fs::read_to_string(&fixture.path) {
+        Ok(html) => html,
+        // Fixtures of embedded definitions live in the binary, not on disk
+        Err(_) if embedded_file_contents(&fixture.path).is_some() => {
+            embedded_file_contents(&fixture.path)
+                .unwrap_or_default()
+                .to_string()
+        }
+        Err(e) => {
+            return Some(SelectorTestResult::failed(
+                definition.id(),
+                fixture,
+                format!("failed to read fixture {}: {e}", fixture.path.display()),
+            ))
+        }
+    };
+
+    Some(test_selectors(definition.id(), selectors, fixture, &html))
+}
+
+/// Run result selectors against fixture HTML.
+#[must_use]
+pub fn test_selectors(
+    broker_id: &BrokerId,
+    selectors: &ResultSelectors,
+    fixture: &SelectorFixture,
+    html: &str,
+) -> SelectorTestResult {
+    let document = Html::parse_document(html);
+
+    let parse = |name: &str, css: &str| {
+        Selector::parse(css).map_err(|e| format!("invalid {name} selector '{css}': {e}"))
+    };
+
+    if let Some(captcha) = &selectors.captcha_required {
+        match parse("captcha_required", captcha) {
+            Ok(sel) if document.select(&sel).next().is_some() => {
+                return SelectorTestResult::failed(
+                    broker_id,
+                    fixture,
+                    "captcha_required selector matched the fixture".to_string(),
+                );
+            }
+            Ok(_) => {}
+            Err(reason) => return SelectorTestResult::failed(broker_id, fixture, reason),
+        }
+    }
+
+    let (item_sel, url_sel) = match (
+        parse("result_item", &selectors.result_item),
+        parse("listing_url", &selectors.listing_url),
+    ) {
+        (Ok(item), Ok(url)) => (item, url),
+        (Err(reason), _) | (_, Err(reason)) => {
+            return SelectorTestResult::failed(broker_id, fixture, reason)
+        }
+    };
+
+    let listings: Vec<ElementRef> = document
+        .select(&item_sel)
+        .filter(|item| {
+            item.select(&url_sel)
+                .next()
+                .and_then(|el| el.value().attr("href"))
+                .is_some()
+        })
+        .collect();
+
+    let mut missing_fields = Vec::new();
+    for field in &fixture.expected_fields {
+        let Some(css) = field_selector(selectors, field) else {
+            missing_fields.push(field.clone());
+            continue;
+        };
+        let sel = match parse(field, css) {
+            Ok(sel) => sel,
+            Err(reason) => return SelectorTestResult::failed(broker_id, fixture, reason),
+        };
+        let all_present = listings.iter().all(|item| {
+            item.select(&sel)
+                .next()
+                .is_some_and(|el| !el.text().collect::<String>().trim().is_empty())
+        });
+        if !all_present {
+            missing_fields.push(field.clone());
+        }
+    }
+
+    let status = if listings.len() != fixture.expected_results {
+        SelectorTestStatus::Failed {
+            reason: format!(
+                "expected {} results, selectors extracted {}",
+                fixture.expected_results,
+                listings.len()
+            ),
+        }
+    } else if !missing_fields.is_empty() {
+        SelectorTestStatus::Failed {
+            reason: format!("fields missing from results: {}", missing_fields.join(", ")),
+        }
+    } else {
+        SelectorTestStatus::Passed
+    };
+
+    SelectorTestResult {
+        broker_id: broker_id.clone(),
+        status,
+        found_results: listings.len(),
+        expected_results: fixture.expected_results,
+        missing_fields,
+    }
+}
+
+/// Look up the optional field selector by its TOML key.
+fn field_selector<'a>(selectors: &'a ResultSelectors, field: &str) -> Option<&'a str> {
+    match field {
+        "name" => selectors.name.as_deref(),
+        "age" => selectors.age.as_deref(),
+        "location" => selectors.location.as_deref(),
+        "relatives" => selectors.relatives.as_deref(),
+        "phones" => selectors.phones.as_deref(),
+        "emails" => selectors.emails.as_deref(),
+        "match_indicator" => selectors.match_indicator.as_deref(),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FIXTURE_HTML: &str = r#"
+        <div class="search-results">
+            <div class="result-card">
+                <a class="profile-link" href="/p/1">View</a>
+                <span class="name">Alex Example</span>
+                <span class="age">40</span>
+            </div>
+            <div class="result-card">
+                <a class="profile-link" href="/p/2">View</a>
+                <span class="name">Sam Sample</span>
+            </div>
+        </div>
+    "#;
+
+    fn selectors() -> ResultSelectors {
+        ResultSelectors {
+            results_container: ".search-results".to_string(),
+            result_item: ".result-card".to_string(),
+            listing_url: "a.profile-link".to_string(),
+            name: Some(".name".to_string()),
+            age: Some(".age".to_string()),
+            location: None,
+            relatives: None,
+            phones: None,
+            emails: None,
+            match_indicator: None,
+            no_results_indicator: None,
+            captcha_required: None,
+            next_page: None,
+            max_pages: None,
+        }
+    }
+
+    fn fixture(expected_results: usize, fields: &[&str]) -> SelectorFixture {
+        SelectorFixture {
+            path: "unused.html".into(),
+            expected_results,
+            expected_fields: fields.iter().map(ToString::to_string).collect(),
+        }
+    }
+
+    fn broker_id() -> BrokerId {
+        BrokerId::new("test-broker").expect("valid broker ID")
+    }
+
+    #[test]
+    fn test_matching_selectors_pass() {
+        let result = test_selectors(
+            &broker_id(),
+            &selectors(),
+            &fixture(2, &["name"]),
+            FIXTURE_HTML,
+        );
+        assert!(result.passed());
+        assert_eq!(result.found_results, 2);
+    }
+
+    #[test]
+    fn test_wrong_result_count_fails() {
+        let mut sels = selectors();
+        sels.result_item = ".result-row".to_string();
+
+        let result = test_selectors(&broker_id(), &sels, &fixture(2, &[]), FIXTURE_HTML);
+        assert!(!result.passed());
+        assert_eq!(result.found_results, 0);
+    }
+
+    #[test]
+    fn test_field_missing_from_a_listing_fails() {
+        let result = test_selectors(
+            &broker_id(),
+            &selectors(),
+            &fixture(2, &["age"]),
+            FIXTURE_HTML,
+        );
+        assert!(!result.passed());
+        assert_eq!(result.missing_fields, vec!["age".to_string()]);
+    }
+
+    #[test]
+    fn test_invalid_selector_fails() {
+        let mut sels = selectors();
+        sels.listing_url = "a[".to_string();
+
+        let result = test_selectors(&broker_id(), &sels, &fixture(2, &[]), FIXTURE_HTML);
+        assert!(matches!(
+            result.status,
+            SelectorTestStatus::Failed { ref reason } if reason.contains("listing_url")
+        ));
+    }
+}
@@ -1,13 +1,26 @@
 use crate::actions::{extract_domain, BrowserActions};
 use crate::error::{BrowserError, Result};
 use crate::fingerprint::FingerprintConfig;
+use crate::network::NetworkMonitor;
 use chromiumoxide::browser::{Browser, BrowserConfig};
+use chromiumoxide::cdp::browser_protocol::network::{
+    EventLoadingFailed, EventLoadingFinished, EventRequestWillBeSent, Headers,
+    SetExtraHttpHeadersParams,
+};
+use chromiumoxide::detection::{default_executable, DetectionOptions};
 use chromiumoxide::page::{Page, ScreenshotParams};
-use futures_util::stream::StreamExt;
+use futures_util::stream::{self, StreamExt};
 use std::collections::HashMap;
+use std::path::PathBuf;
 use std::sync::Arc;
 use std::time::{Duration, Instant};
-use tokio::sync::RwLock;
+use tokio::sync::{Mutex, RwLock};
+
+/// How long the network must be quiet before page content is read
+const CONTENT_QUIET_MS: u64 = 500;
+
+/// Longest wait for the network to go quiet before page content is read anyway
+const CONTENT_IDLE_TIMEOUT_MS: u64 = 10_000;
 
 /// Rate limiter per domain
 #[derive(Debug)]
@@ -44,9 +57,22 @@ pub struct BrowserEngine {
     fingerprint: FingerprintConfig,
     rate_limiter: Arc<RwLock<RateLimiter>>,
     current_page: Arc<RwLock<Option<Page>>>,
+    network: Arc<NetworkMonitor>,
+    /// Held for a whole page fetch, so fetches on the one page take turns
+    /// and one fetch's request headers are never sent with another's
+    fetching: Mutex<()>,
 }
 
 impl BrowserEngine {
+    /// Locate the Chrome/Chromium binary a new engine would launch, without
+    /// starting it.
+    ///
+    /// # Errors
+    /// Returns `BrowserError::BrowserNotFound` if no browser is installed.
+    pub fn find_executable() -> Result<PathBuf> {
+        default_executable(DetectionOptions::default()).map_err(BrowserError::BrowserNotFound)
+    }
+
     /// Create a new browser engine with default configuration
     pub async fn new() -> Result<Self> {
         Self::with_fingerprint(FingerprintConfig::randomized()).await
@@ -71,7 +97,7 @@ impl BrowserEngine {
         let (browser, mut handler) = Browser::launch(config).await.map_err(|e| {
             let msg = e.to_string();
             if msg.contains("Could not auto detect") || msg.contains("chrome executable") {
-                BrowserError::ChromiumError(format!(
+                BrowserError::BrowserNotFound(format!(
                     "Chrome/Chromium not found. Please install:\n\
                         Ubuntu/Debian: sudo apt-get install chromium-browser\n\
                         Fedora: sudo dnf install chromium\n\
@@ -98,6 +124,8 @@ impl BrowserEngine {
             fingerprint,
             rate_limiter: Arc::new(RwLock::new(RateLimiter::new(1000))), // 1 second default
             current_page: Arc::new(RwLock::new(None)),
+            network: Arc::new(NetworkMonitor::new()),
+            fetching: Mutex::new(()),
         })
     }
 
@@ -111,6 +139,7 @@ impl BrowserEngine {
                 .new_page("about:blank")
                 .await
                 .map_err(|e| BrowserError::ChromiumError(e.to_string()))?;
+            self.watch_network(&page).await?;
             *page_lock = Some(page);
         }
 
@@ -121,12 +150,141 @@ impl BrowserEngine {
             .clone())
     }
 
+    /// Feed the page's request events into the network monitor
+    async fn watch_network(&self, page: &Page) -> Result<()> {
+        let listen_err =
+            |e: chromiumoxide::error::CdpError| BrowserError::ChromiumError(e.to_string());
+        let started = page
+            .event_listener::<EventRequestWillBeSent>()
+            .await
+            .map_err(listen_err)?
+            .map(|event| (true, event.request_id.inner().clone()));
+        let finished = page
+            .event_listener::<EventLoadingFinished>()
+            .await
+            .map_err(listen_err)?
+            .map(|event| (false, event.request_id.inner().clone()));
+        let failed = page
+            .event_listener::<EventLoadingFailed>()
+            .await
+            .map_err(listen_err)?
+            .map(|event| (false, event.request_id.inner().clone()));
+
+        let network = Arc::clone(&self.network);
+        let mut events = stream::select(started, stream::select(finished, failed));
+        tokio::spawn(async move {
+            while let Some((is_start, request_id)) = events.next().await {
+                if is_start {
+                    network.request_started(&request_id);
+                } else {
+                    network.request_finished(&request_id);
+                }
+            }
+        });
+
+        Ok(())
+    }
+
+    /// Wait until the current page has had no requests in flight for `quiet_ms`
+    ///
+    /// Use before reading a page whose results load asynchronously. Returns
+    /// [`BrowserError::Timeout`] if the network is still busy after
+    /// `timeout_ms`, e.g. on a page that polls.
+    pub async fn wait_for_network_idle(&self, quiet_ms: u64, timeout_ms: u64) -> Result<()> {
+        // Make sure the page exists so its requests are being watched
+        self.get_page().await?;
+        self.network
+            .wait_for_idle(
+                Duration::from_millis(quiet_ms),
+                Duration::from_millis(timeout_ms),
+            )
+            .await
+    }
+
     /// Fetch a page and return its HTML content
+    ///
+    /// Waits for the network to go quiet first so results loaded by XHR are
+    /// in the HTML. A page that never goes quiet is read when the wait times out.
     pub async fn fetch_page_content(&self, url: &str) -> Result<String> {
+        let _fetching = self.fetching.lock().await;
+        self.load_page_content(url).await
+    }
+
+    /// Fetch a page like [`fetch_page_content`](Self::fetch_page_content),
+    /// sending `headers` with its requests
+    ///
+    /// The headers apply to this fetch only: they are cleared once the page
+    /// has been read, even if the fetch failed.
+    pub async fn fetch_page_content_with_headers(
+        &self,
+        url: &str,
+        headers: &HashMap<String, String>,
+    ) -> Result<String> {
+        let _fetching = self.fetching.lock().await;
+        if headers.is_empty() {
+            return self.load_page_content(url).await;
+        }
+
+        self.set_extra_headers(headers).await?;
+        let content = self.load_page_content(url).await;
+        let cleared = self.set_extra_headers(&HashMap::new()).await;
+        let content = content?;
+        cleared?;
+        Ok(content)
+    }
+
+    /// Navigate to `url` and read the page once the network is quiet
+    async fn load_page_content(&self, url: &str) -> Result<String> {
         // Navigate to the URL
         self.navigate(url).await?;
 
+        if let Err(e) = self
+            .wait_for_network_idle(CONTENT_QUIET_MS, CONTENT_IDLE_TIMEOUT_MS)
+            .await
+        {
+            tracing::warn!("Reading {} before the network went idle: {}", url, e);
+        }
+
         // Get the page HTML
+        self.page_content().await
+    }
+
+    /// Send `headers` with every request the page makes until they are
+    /// replaced; an empty map clears them
+    async fn set_extra_headers(&self, headers: &HashMap<String, String>) -> Result<()> {
+        let page = self.get_page().await?;
+        let headers = serde_json::to_value(headers)
+            .map_err(|e| BrowserError::ChromiumError(e.to_string()))?;
+        page.execute(SetExtraHttpHeadersParams::new(Headers::new(headers)))
+            .await
+            .map_err(|e| BrowserError::ChromiumError(e.to_string()))?;
+
+        Ok(())
+    }
+
+    /// Return the URL of the current page
+    pub async fn current_url(&self) -> Result<String> {
+        let page = self.get_page().await?;
+        let url = page
+            .url()
+            .await
+            .map_err(|e| BrowserError::ChromiumError(e.to_string()))?;
+
+        Ok(url.unwrap_or_default())
+    }
+
+    /// Run a script in the current page, discarding its result
+    pub async fn run_script(&self, script: &str) -> Result<()> {
+        let page = self.get_page().await?;
+        page.evaluate(script)
+            .await
+            .map_err(|e| BrowserError::ChromiumError(e.to_string()))?;
+
+        Ok(())
+    }
+
+    /// Return the HTML content of the current page without navigating
+    pub async fn page_content(&self) -> Result<String> {
         let page = self.get_page().await?;
         let html = page
             .content()
@@ -150,6 +308,8 @@ impl BrowserActions for BrowserEngine {
 
         let page = self.get_page().await?;
 
+        // Only the new document's requests count towards network idle
+        self.network.reset();
         page.goto(url)
             .await
             .map_err(|e| BrowserError::NavigationError(e.to_string()))?;
@@ -7,6 +7,9 @@ pub enum BrowserError {
     #[error("chromium error: {0}")]
     ChromiumError(String),
 
+    #[error("browser not found: {0}")]
+    BrowserNotFound(String),
+
     #[error("navigation failed: {0}")]
     NavigationError(String),
 
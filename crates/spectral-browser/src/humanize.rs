@@ -0,0 +1,326 @@
+use crate::actions::BrowserActions;
+use crate::error::Result;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use std::sync::Mutex;
+use std::time::Duration;
+
+/// Timing settings for human-like form interaction
+///
+/// Filling a form instantly is a common bot signature, so with humanization
+/// enabled text is typed one character at a time and clicks are preceded by
+/// a short pause. All delays are bounded so a submission never stalls.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HumanizeConfig {
+    /// Type and click with human-like timing
+    pub enabled: bool,
+    /// Shortest delay between keystrokes in milliseconds
+    pub min_keystroke_delay_ms: u64,
+    /// Longest delay between keystrokes in milliseconds
+    pub max_keystroke_delay_ms: u64,
+    /// Upper bound on the total typing time for one field in milliseconds
+    pub max_field_typing_ms: u64,
+    /// Shortest pause before a click in milliseconds
+    pub min_click_pause_ms: u64,
+    /// Longest pause before a click in milliseconds
+    pub max_click_pause_ms: u64,
+    /// Seed for the delay RNG, for reproducible timing in tests
+    pub seed: Option<u64>,
+}
+
+impl HumanizeConfig {
+    /// Configuration that fills fields in one shot and clicks immediately
+    pub fn disabled() -> Self {
+        Self {
+            enabled: false,
+            ..Self::default()
+        }
+    }
+}
+
+impl Default for HumanizeConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            min_keystroke_delay_ms: 40,
+            max_keystroke_delay_ms: 140,
+            max_field_typing_ms: 4000,
+            min_click_pause_ms: 150,
+            max_click_pause_ms: 600,
+            seed: None,
+        }
+    }
+}
+
+/// Source of randomized interaction delays
+#[derive(Debug)]
+pub struct Humanizer {
+    config: HumanizeConfig,
+    rng: Mutex<StdRng>,
+}
+
+impl Humanizer {
+    /// Create a humanizer, seeding the RNG from `config.seed` when set
+    pub fn new(config: HumanizeConfig) -> Self {
+        let rng = match config.seed {
+            Some(seed) => StdRng::seed_from_u64(seed),
+            None => StdRng::from_entropy(),
+        };
+        Self {
+            config,
+            rng: Mutex::new(rng),
+        }
+    }
+
+    /// Whether interactions are humanized
+    pub fn is_enabled(&self) -> bool {
+        self.config.enabled
+    }
+
+    /// Delays to wait before each of `count` keystrokes
+    ///
+    /// The first keystroke is immediate. Delays are scaled down when their
+    /// sum would exceed `max_field_typing_ms`.
+    pub fn keystroke_delays(&self, count: usize) -> Vec<Duration> {
+        let mut delays: Vec<u64> = {
+            let mut rng = self.rng.lock().expect("humanizer rng lock");
+            (0..count)
+                .map(|i| {
+                    if i == 0 {
+                        0
+                    } else {
+                        sample(
+                            &mut rng,
+                            self.config.min_keystroke_delay_ms,
+                            self.config.max_keystroke_delay_ms,
+                        )
+                    }
+                })
+                .collect()
+        };
+
+        let total: u64 = delays.iter().sum();
+        if total > self.config.max_field_typing_ms {
+            for delay in &mut delays {
+                *delay = *delay * self.config.max_field_typing_ms / total;
+            }
+        }
+
+        delays.into_iter().map(Duration::from_millis).collect()
+    }
+
+    /// Pause to wait before a click
+    pub fn click_pause(&self) -> Duration {
+        let mut rng = self.rng.lock().expect("humanizer rng lock");
+        Duration::from_millis(sample(
+            &mut rng,
+            self.config.min_click_pause_ms,
+            self.config.max_click_pause_ms,
+        ))
+    }
+}
+
+/// Uniform sample from `min..=max`, tolerating a reversed range
+fn sample(rng: &mut StdRng, min: u64, max: u64) -> u64 {
+    rng.gen_range(min.min(max)..=max.max(min))
+}
+
+/// Browser actions with human-like typing and click timing
+///
+/// Wraps another [`BrowserActions`] implementation. When humanization is
+/// enabled, [`fill_field`](BrowserActions::fill_field) types the value one
+/// character at a time with jittered spacing and
+/// [`click`](BrowserActions::click) waits briefly first; otherwise every call
+/// is passed straight through.
+pub struct HumanizedActions<'a, A: ?Sized> {
+    inner: &'a A,
+    humanizer: Humanizer,
+}
+
+impl<'a, A: BrowserActions + Sync + ?Sized> HumanizedActions<'a, A> {
+    /// Wrap `inner` with the given timing configuration
+    pub fn new(inner: &'a A, config: HumanizeConfig) -> Self {
+        Self {
+            inner,
+            humanizer: Humanizer::new(config),
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl<A: BrowserActions + Sync + ?Sized> BrowserActions for HumanizedActions<'_, A> {
+    async fn navigate(&self, url: &str) -> Result<()> {
+        self.inner.navigate(url).await
+    }
+
+    async fn fill_field(&self, selector: &str, value: &str) -> Result<()> {
+        if !self.humanizer.is_enabled() {
+            return self.inner.fill_field(selector, value).await;
+        }
+
+        let chars: Vec<char> = value.chars().collect();
+        let delays = self.humanizer.keystroke_delays(chars.len());
+        let mut buf = [0u8; 4];
+        for (ch, delay) in chars.into_iter().zip(delays) {
+            if !delay.is_zero() {
+                tokio::time::sleep(delay).await;
+            }
+            self.inner
+                .fill_field(selector, ch.encode_utf8(&mut buf))
+                .await?;
+        }
+        Ok(())
+    }
+
+    async fn click(&self, selector: &str) -> Result<()> {
+        if self.humanizer.is_enabled() {
+            tokio::time::sleep(self.humanizer.click_pause()).await;
+        }
+        self.inner.click(selector).await
+    }
+
+    async fn wait_for_selector(&self, selector: &str, timeout_ms: u64) -> Result<()> {
+        self.inner.wait_for_selector(selector, timeout_ms).await
+    }
+
+    async fn extract_text(&self, selector: &str) -> Result<String> {
+        self.inner.extract_text(selector).await
+    }
+
+    async fn screenshot(&self) -> Result<Vec<u8>> {
+        self.inner.screenshot().await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Instant;
+
+    /// Records `fill_field` and `click` calls with the time they were made
+    #[derive(Default)]
+    struct RecordingActions {
+        calls: Mutex<Vec<(String, Instant)>>,
+    }
+
+    impl RecordingActions {
+        fn record(&self, call: String) {
+            self.calls
+                .lock()
+                .expect("calls lock")
+                .push((call, Instant::now()));
+        }
+
+        fn calls(&self) -> Vec<(String, Instant)> {
+            self.calls.lock().expect("calls lock").clone()
+        }
+    }
+
+    #[async_trait::async_trait]
+    impl BrowserActions for RecordingActions {
+        async fn navigate(&self, _url: &str) -> Result<()> {
+            Ok(())
+        }
+
+        async fn fill_field(&self, selector: &str, value: &str) -> Result<()> {
+            self.record(format!("fill {selector} {value}"));
+            Ok(())
+        }
+
+        async fn click(&self, selector: &str) -> Result<()> {
+            self.record(format!("click {selector}"));
+            Ok(())
+        }
+
+        async fn wait_for_selector(&self, _selector: &str, _timeout_ms: u64) -> Result<()> {
+            Ok(())
+        }
+
+        async fn extract_text(&self, _selector: &str) -> Result<String> {
+            Ok(String::new())
+        }
+
+        async fn screenshot(&self) -> Result<Vec<u8>> {
+            Ok(vec![])
+        }
+    }
+
+    fn fast_config() -> HumanizeConfig {
+        HumanizeConfig {
+            enabled: true,
+            min_keystroke_delay_ms: 5,
+            max_keystroke_delay_ms: 15,
+            max_field_typing_ms: 1000,
+            min_click_pause_ms: 5,
+            max_click_pause_ms: 10,
+            seed: Some(7),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_humanized_fill_types_each_character_with_spacing() {
+        let recorder = RecordingActions::default();
+        let actions = HumanizedActions::new(&recorder, fast_config());
+
+        actions.fill_field("#name", "Jane").await.unwrap();
+
+        let calls = recorder.calls();
+        let typed: Vec<&str> = calls.iter().map(|(call, _)| call.as_str()).collect();
+        assert_eq!(
+            typed,
+            [
+                "fill #name J",
+                "fill #name a",
+                "fill #name n",
+                "fill #name e"
+            ]
+        );
+        for pair in calls.windows(2) {
+            assert!(pair[1].1 - pair[0].1 >= Duration::from_millis(5));
+        }
+    }
+
+    #[tokio::test]
+    async fn test_disabled_fill_is_one_shot() {
+        let recorder = RecordingActions::default();
+        let actions = HumanizedActions::new(&recorder, HumanizeConfig::disabled());
+
+        actions.fill_field("#name", "Jane").await.unwrap();
+        actions.click("#submit").await.unwrap();
+
+        let calls: Vec<String> = recorder.calls().into_iter().map(|(c, _)| c).collect();
+        assert_eq!(calls, ["fill #name Jane", "click #submit"]);
+    }
+
+    #[test]
+    fn test_keystroke_delays_are_bounded() {
+        let humanizer = Humanizer::new(HumanizeConfig {
+            max_field_typing_ms: 500,
+            seed: Some(1),
+            ..HumanizeConfig::default()
+        });
+
+        let delays = humanizer.keystroke_delays(200);
+        assert_eq!(delays.len(), 200);
+        assert_eq!(delays[0], Duration::ZERO);
+        assert!(delays.iter().sum::<Duration>() <= Duration::from_millis(500));
+        assert!(
+            delays
+                .iter()
+                .all(|d| *d
+                    <= Duration::from_millis(HumanizeConfig::default().max_keystroke_delay_ms))
+        );
+    }
+
+    #[test]
+    fn test_seeded_delays_are_deterministic() {
+        let a = Humanizer::new(fast_config());
+        let b = Humanizer::new(fast_config());
+
+        assert_eq!(a.keystroke_delays(10), b.keystroke_delays(10));
+        assert_eq!(a.click_pause(), b.click_pause());
+
+        let pause = a.click_pause();
+        assert!(pause >= Duration::from_millis(5) && pause <= Duration::from_millis(10));
+    }
+}
@@ -7,7 +7,11 @@ pub mod actions;
 pub mod engine;
 pub mod error;
 pub mod fingerprint;
+pub mod humanize;
+pub mod network;
 
 pub use actions::BrowserActions;
 pub use engine::BrowserEngine;
 pub use error::{BrowserError, Result};
+pub use humanize::{HumanizeConfig, HumanizedActions, Humanizer};
+pub use network::NetworkMonitor;
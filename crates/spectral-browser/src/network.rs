@@ -0,0 +1,254 @@
+//! Network activity tracking for pages that load results asynchronously.
+//!
+//! Broker sites built as single-page apps fetch their results with XHRs
+//! after the load event, so a page can be read half-rendered. The
+//! [`NetworkMonitor`] counts the requests a page has in flight and lets a
+//! caller wait until the page has been quiet for a while.
+
+use crate::error::{BrowserError, Result};
+use std::collections::HashSet;
+use std::sync::Mutex;
+use std::time::Duration;
+use tokio::sync::Notify;
+use tokio::time::Instant;
+
+#[derive(Debug)]
+struct Activity {
+    in_flight: HashSet<String>,
+    last_activity: Instant,
+}
+
+/// Tracks the requests a page has in flight.
+#[derive(Debug)]
+pub struct NetworkMonitor {
+    activity: Mutex<Activity>,
+    changed: Notify,
+}
+
+impl NetworkMonitor {
+    /// Create a monitor with no requests in flight.
+    pub fn new() -> Self {
+        Self {
+            activity: Mutex::new(Activity {
+                in_flight: HashSet::new(),
+                last_activity: Instant::now(),
+            }),
+            changed: Notify::new(),
+        }
+    }
+
+    /// Record that request `id` was sent.
+    ///
+    /// A redirect reuses the request ID, so it is not counted twice.
+    pub fn request_started(&self, id: &str) {
+        self.update(|activity| {
+            activity.in_flight.insert(id.to_string());
+        });
+    }
+
+    /// Record that request `id` finished or failed.
+    pub fn request_finished(&self, id: &str) {
+        self.update(|activity| {
+            activity.in_flight.remove(id);
+        });
+    }
+
+    /// Forget the requests in flight, as when the page navigates away.
+    ///
+    /// Requests of the previous document, such as a long poll, may never
+    /// report finishing, and would otherwise keep every later page busy.
+    pub fn reset(&self) {
+        self.update(|activity| activity.in_flight.clear());
+    }
+
+    /// Number of requests currently in flight.
+    pub fn in_flight(&self) -> usize {
+        self.lock().in_flight.len()
+    }
+
+    /// Wait until no request has been in flight for `quiet`.
+    ///
+    /// Returns [`BrowserError::Timeout`] if the network has not been quiet
+    /// for that long within `timeout`, e.g. on a page that polls.
+    pub async fn wait_for_idle(&self, quiet: Duration, timeout: Duration) -> Result<()> {
+        tokio::time::timeout(timeout, self.idle(quiet))
+            .await
+            .map_err(|_| {
+                BrowserError::Timeout(format!(
+                    "network not idle for {}ms after {}ms ({} requests in flight)",
+                    quiet.as_millis(),
+                    timeout.as_millis(),
+                    self.in_flight()
+                ))
+            })
+    }
+
+    async fn idle(&self, quiet: Duration) {
+        loop {
+            // Register for the next change before reading the state, so a
+            // request that starts in between still wakes us
+            let changed = self.changed.notified();
+            tokio::pin!(changed);
+            changed.as_mut().enable();
+
+            let quiet_until = {
+                let activity = self.lock();
+                activity
+                    .in_flight
+                    .is_empty()
+                    .then(|| activity.last_activity + quiet)
+            };
+
+            match quiet_until {
+                Some(deadline) if deadline <= Instant::now() => return,
+                Some(deadline) => {
+                    tokio::select! {
+                        () = changed => {}
+                        () = tokio::time::sleep_until(deadline) => {}
+                    }
+                }
+                None => changed.await,
+            }
+        }
+    }
+
+    fn update(&self, apply: impl FnOnce(&mut Activity)) {
+        {
+            let mut activity = self.lock();
+            apply(&mut activity);
+            activity.last_activity = Instant::now();
+        }
+        self.changed.notify_waiters();
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, Activity> {
+        // The state stays consistent even if a holder panicked
+        self.activity
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+    }
+}
+
+impl Default for NetworkMonitor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+
+    /// Simulate a page firing XHRs: each `(start_ms, end_ms)` is one request.
+    fn fire_requests(monitor: &Arc<NetworkMonitor>, requests: &[(u64, u64)]) {
+        for (i, &(start_ms, end_ms)) in requests.iter().enumerate() {
+            let monitor = Arc::clone(monitor);
+            let id = format!("xhr-{i}");
+            tokio::spawn(async move {
+                tokio::time::sleep(Duration::from_millis(start_ms)).await;
+                monitor.request_started(&id);
+                tokio::time::sleep(Duration::from_millis(end_ms - start_ms)).await;
+                monitor.request_finished(&id);
+            });
+        }
+    }
+
+    #[tokio::test]
+    async fn test_waits_for_delayed_requests_to_settle() {
+        let monitor = Arc::new(NetworkMonitor::new());
+        // The second XHR starts while the first is in flight and settles at 300ms
+        fire_requests(&monitor, &[(20, 150), (100, 300)]);
+
+        let started = Instant::now();
+        monitor
+            .wait_for_idle(Duration::from_millis(100), Duration::from_secs(5))
+            .await
+            .expect("network settles");
+
+        assert!(started.elapsed() >= Duration::from_millis(400));
+        assert_eq!(monitor.in_flight(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_quiet_gap_shorter_than_window_keeps_waiting() {
+        let monitor = Arc::new(NetworkMonitor::new());
+        // 50ms gap between the XHRs is shorter than the 150ms quiet window
+        fire_requests(&monitor, &[(10, 60), (110, 160)]);
+
+        let started = Instant::now();
+        monitor
+            .wait_for_idle(Duration::from_millis(150), Duration::from_secs(5))
+            .await
+            .expect("network settles");
+
+        assert!(started.elapsed() >= Duration::from_millis(310));
+    }
+
+    #[tokio::test]
+    async fn test_times_out_if_activity_never_quiets() {
+        let monitor = Arc::new(NetworkMonitor::new());
+        let poller = {
+            let monitor = Arc::clone(&monitor);
+            tokio::spawn(async move {
+                for i in 0.. {
+                    let id = format!("poll-{i}");
+                    monitor.request_started(&id);
+                    tokio::time::sleep(Duration::from_millis(20)).await;
+                    monitor.request_finished(&id);
+                    tokio::time::sleep(Duration::from_millis(20)).await;
+                }
+            })
+        };
+
+        let started = Instant::now();
+        let result = monitor
+            .wait_for_idle(Duration::from_millis(100), Duration::from_millis(300))
+            .await;
+        poller.abort();
+
+        assert!(matches!(result, Err(BrowserError::Timeout(_))));
+        let elapsed = started.elapsed();
+        assert!(elapsed >= Duration::from_millis(300));
+        assert!(elapsed < Duration::from_secs(2));
+    }
+
+    #[tokio::test]
+    async fn test_stuck_request_times_out() {
+        let monitor = NetworkMonitor::new();
+        monitor.request_started("long-poll");
+
+        let result = monitor
+            .wait_for_idle(Duration::from_millis(10), Duration::from_millis(100))
+            .await;
+
+        match result {
+            Err(BrowserError::Timeout(msg)) => assert!(msg.contains("1 requests in flight")),
+            other => panic!("expected timeout, got {other:?}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_reset_forgets_requests_of_previous_page() {
+        let monitor = NetworkMonitor::new();
+        monitor.request_started("long-poll");
+        monitor.reset();
+        assert_eq!(monitor.in_flight(), 0);
+
+        // A late event from the old page changes nothing
+        monitor.request_finished("long-poll");
+        monitor
+            .wait_for_idle(Duration::from_millis(10), Duration::from_millis(100))
+            .await
+            .expect("network idle after reset");
+    }
+
+    #[tokio::test]
+    async fn test_redirect_counts_once() {
+        let monitor = NetworkMonitor::new();
+        monitor.request_started("doc");
+        monitor.request_started("doc");
+        monitor.request_finished("doc");
+        assert_eq!(monitor.in_flight(), 0);
+    }
+}
@@ -29,3 +29,74 @@ async fn test_rate_limiting() {
     // Immediate second navigation to same domain should fail
     assert!(engine.navigate("https://example.com/page2").await.is_err());
 }
+
+/// Serve `page` at `/` and answer every other path after `xhr_delay_ms`.
+async fn serve_mock_page(page: &'static str, xhr_delay_ms: u64) -> String {
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    tokio::spawn(async move {
+        while let Ok((mut socket, _)) = listener.accept().await {
+            tokio::spawn(async move {
+                let mut buf = [0u8; 2048];
+                let n = socket.read(&mut buf).await.unwrap_or(0);
+                let request = String::from_utf8_lossy(&buf[..n]);
+                let body = if request.starts_with("GET / ") {
+                    page
+                } else {
+                    tokio::time::sleep(std::time::Duration::from_millis(xhr_delay_ms)).await;
+                    "{}"
+                };
+                let response = format!(
+                    "HTTP/1.1 200 OK\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                    body.len(),
+                    body
+                );
+                let _ = socket.write_all(response.as_bytes()).await;
+            });
+        }
+    });
+    format!("http://{addr}/")
+}
+
+#[tokio::test]
+#[ignore] // Requires Chrome/Chromium installed
+async fn test_wait_for_network_idle_waits_for_delayed_xhrs() {
+    let url = serve_mock_page(
+        "<html><body><div id='results'></div><script>
+            setTimeout(() => fetch('/results').then(() => {
+                document.getElementById('results').textContent = 'loaded';
+            }), 200);
+        </script></body></html>",
+        300,
+    )
+    .await;
+    let engine = BrowserEngine::new().await.unwrap();
+    engine.navigate(&url).await.unwrap();
+
+    engine.wait_for_network_idle(400, 5000).await.unwrap();
+
+    let text = engine.extract_text("#results").await.unwrap();
+    assert_eq!(text, "loaded");
+}
+
+#[tokio::test]
+#[ignore] // Requires Chrome/Chromium installed
+async fn test_wait_for_network_idle_times_out_on_polling_page() {
+    let url = serve_mock_page(
+        "<html><body><script>
+            setInterval(() => fetch('/poll?' + Date.now()), 50);
+        </script></body></html>",
+        50,
+    )
+    .await;
+    let engine = BrowserEngine::new().await.unwrap();
+    engine.navigate(&url).await.unwrap();
+
+    let result = engine.wait_for_network_idle(500, 1500).await;
+    assert!(matches!(
+        result,
+        Err(spectral_browser::BrowserError::Timeout(_))
+    ));
+}
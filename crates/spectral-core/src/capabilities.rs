@@ -3,9 +3,29 @@
 //! This module provides a way to check which features are available at runtime,
 //! allowing the application to gracefully degrade when optional features
 //! (like LLM integration) are not configured or available.
+//!
+//! Features whose availability depends on the environment (a local Ollama
+//! install, a browser binary, SMTP credentials) can register an async probe.
+//! Probe results are cached for a configurable TTL and carry the reason a
+//! feature is unavailable so the UI can explain why it is disabled.
 
 use serde::{Deserialize, Serialize};
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// Default time a probe result is trusted before the probe is re-run.
+pub const DEFAULT_PROBE_TTL: Duration = Duration::from_secs(60);
+
+/// Boxed future returned by a capability probe.
+///
+/// Resolves to `Ok(())` when the feature can run, or `Err(reason)` when it can't.
+pub type ProbeFuture = Pin<Box<dyn Future<Output = Result<(), String>> + Send>>;
+
+type ProbeFn = Arc<dyn Fn() -> ProbeFuture + Send + Sync>;
 
 /// Identifies features that can be enabled or disabled at runtime.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
@@ -35,6 +55,8 @@ pub enum FeatureId {
     NetworkTelemetry,
     /// Plugin system
     Plugins,
+    /// Sending removal emails over SMTP
+    EmailSending,
 }
 
 impl FeatureId {
@@ -54,6 +76,7 @@ impl FeatureId {
             Self::LocalDiscovery => "Local PII Discovery",
             Self::NetworkTelemetry => "Network Telemetry",
             Self::Plugins => "Plugin System",
+            Self::EmailSending => "Email Sending",
         }
     }
 
@@ -75,6 +98,7 @@ impl FeatureId {
             Self::LocalDiscovery => "Scan local files for PII exposure",
             Self::NetworkTelemetry => "Monitor network connections for privacy insights",
             Self::Plugins => "Extend Spectral with custom broker definitions and integrations",
+            Self::EmailSending => "Send removal emails directly over SMTP",
         }
     }
 
@@ -96,14 +120,67 @@ impl FeatureId {
     }
 }
 
+/// Availability of a feature after enablement and runtime checks.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "status", rename_all = "snake_case")]
+pub enum FeatureStatus {
+    /// The feature is enabled and its probe (if any) passed
+    Available,
+    /// The feature cannot be used right now
+    Unavailable {
+        /// Human-readable explanation
+        reason: String,
+    },
+}
+
+impl FeatureStatus {
+    /// Check if the status is `Available`.
+    #[must_use]
+    pub fn is_available(&self) -> bool {
+        matches!(self, Self::Available)
+    }
+
+    /// Get the reason the feature is unavailable, if any.
+    #[must_use]
+    pub fn reason(&self) -> Option<&str> {
+        match self {
+            Self::Available => None,
+            Self::Unavailable { reason } => Some(reason),
+        }
+    }
+}
+
+/// Cached result of a probe run.
+#[derive(Debug, Clone)]
+struct ProbeResult {
+    status: FeatureStatus,
+    checked_at: Instant,
+}
+
 /// Registry tracking which features are currently available.
 ///
-/// In Phase 1, this is a stub that returns false for LLM features.
-/// In later phases, it will dynamically check configuration and provider availability.
-#[derive(Debug, Clone)]
+/// Features are first gated by an enabled flag. Features with a registered
+/// probe are additionally gated by the probe's most recent result.
+#[derive(Clone)]
 pub struct CapabilityRegistry {
     /// Set of currently enabled features
     enabled_features: HashSet<FeatureId>,
+    /// Runtime probes keyed by feature
+    probes: HashMap<FeatureId, ProbeFn>,
+    /// Cached probe results, shared between clones
+    probe_cache: Arc<Mutex<HashMap<FeatureId, ProbeResult>>>,
+    /// How long a probe result is trusted
+    probe_ttl: Duration,
+}
+
+impl fmt::Debug for CapabilityRegistry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CapabilityRegistry")
+            .field("enabled_features", &self.enabled_features)
+            .field("probed_features", &self.probes.keys().collect::<Vec<_>>())
+            .field("probe_ttl", &self.probe_ttl)
+            .finish_non_exhaustive()
+    }
 }
 
 impl Default for CapabilityRegistry {
@@ -125,13 +202,125 @@ impl CapabilityRegistry {
         enabled_features.insert(FeatureId::ManualScanning);
         enabled_features.insert(FeatureId::EncryptedVault);
 
-        Self { enabled_features }
+        Self {
+            enabled_features,
+            probes: HashMap::new(),
+            probe_cache: Arc::new(Mutex::new(HashMap::new())),
+            probe_ttl: DEFAULT_PROBE_TTL,
+        }
+    }
+
+    /// Set how long probe results are cached before being re-checked.
+    #[must_use]
+    pub fn with_probe_ttl(mut self, ttl: Duration) -> Self {
+        self.probe_ttl = ttl;
+        self
+    }
+
+    /// Register an async runtime probe for a feature.
+    ///
+    /// The probe resolves to `Ok(())` if the feature can run, or `Err(reason)`
+    /// describing what is missing. Replaces any existing probe for the feature
+    /// and clears its cached result.
+    pub fn register_probe<F, Fut>(&mut self, feature: FeatureId, probe: F)
+    where
+        F: Fn() -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<(), String>> + Send + 'static,
+    {
+        tracing::debug!("Registering capability probe for: {:?}", feature);
+        self.probes
+            .insert(feature, Arc::new(move || Box::pin(probe()) as ProbeFuture));
+        self.invalidate(feature);
+    }
+
+    /// Discard the cached probe result for a feature so the next check re-runs it.
+    pub fn invalidate(&self, feature: FeatureId) {
+        self.cache().remove(&feature);
     }
 
     /// Check if a feature is currently available.
+    ///
+    /// For probed features this uses the most recent cached probe result,
+    /// even if it has expired; call [`CapabilityRegistry::check_feature`] to
+    /// refresh it. A probed feature that has never been checked is reported
+    /// using its enabled flag alone.
     #[must_use]
     pub fn is_feature_available(&self, feature: FeatureId) -> bool {
-        self.enabled_features.contains(&feature)
+        if !self.enabled_features.contains(&feature) {
+            return false;
+        }
+
+        self.cache()
+            .get(&feature)
+            .map_or(true, |result| result.status.is_available())
+    }
+
+    /// Get the reason a feature is unavailable, based on cached state.
+    #[must_use]
+    pub fn unavailable_reason(&self, feature: FeatureId) -> Option<String> {
+        if !self.enabled_features.contains(&feature) {
+            return Some(Self::disabled_reason(feature));
+        }
+
+        self.cache()
+            .get(&feature)
+            .and_then(|result| result.status.reason().map(str::to_string))
+    }
+
+    /// Check a feature's availability, running its probe if the cached result is stale.
+    pub async fn check_feature(&self, feature: FeatureId) -> FeatureStatus {
+        if !self.enabled_features.contains(&feature) {
+            return FeatureStatus::Unavailable {
+                reason: Self::disabled_reason(feature),
+            };
+        }
+
+        let Some(probe) = self.probes.get(&feature) else {
+            return FeatureStatus::Available;
+        };
+
+        if let Some(cached) = self.cache().get(&feature) {
+            if cached.checked_at.elapsed() < self.probe_ttl {
+                return cached.status.clone();
+            }
+        }
+
+        let status = match probe().await {
+            Ok(()) => FeatureStatus::Available,
+            Err(reason) => {
+                tracing::debug!("Capability probe failed for {:?}: {}", feature, reason);
+                FeatureStatus::Unavailable { reason }
+            }
+        };
+
+        self.cache().insert(
+            feature,
+            ProbeResult {
+                status: status.clone(),
+                checked_at: Instant::now(),
+            },
+        );
+
+        status
+    }
+
+    /// Check every known feature, running stale probes.
+    pub async fn check_all(&self) -> Vec<(FeatureId, FeatureStatus)> {
+        let mut statuses = Vec::new();
+        for feature in Self::all_features() {
+            statuses.push((feature, self.check_feature(feature).await));
+        }
+        statuses
+    }
+
+    fn disabled_reason(feature: FeatureId) -> String {
+        format!("{} is not enabled", feature.display_name())
+    }
+
+    fn cache(&self) -> std::sync::MutexGuard<'_, HashMap<FeatureId, ProbeResult>> {
+        self.probe_cache
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
     }
 
     /// Enable a feature.
@@ -170,6 +359,7 @@ impl CapabilityRegistry {
             FeatureId::LocalDiscovery,
             FeatureId::NetworkTelemetry,
             FeatureId::Plugins,
+            FeatureId::EmailSending,
         ]
     }
 
@@ -266,4 +456,70 @@ mod tests {
         assert!(enabled.contains(&FeatureId::EncryptedVault));
         assert!(enabled.contains(&FeatureId::BrowserAutomation));
     }
+
+    #[tokio::test]
+    async fn test_failing_probe_reports_reason() {
+        let mut registry = CapabilityRegistry::new();
+        registry.enable_feature(FeatureId::BrowserAutomation);
+        registry.register_probe(FeatureId::BrowserAutomation, || async {
+            Err("Chromium binary not found".to_string())
+        });
+
+        let status = registry.check_feature(FeatureId::BrowserAutomation).await;
+        assert_eq!(
+            status,
+            FeatureStatus::Unavailable {
+                reason: "Chromium binary not found".to_string()
+            }
+        );
+        assert!(!registry.is_feature_available(FeatureId::BrowserAutomation));
+        assert_eq!(
+            registry.unavailable_reason(FeatureId::BrowserAutomation),
+            Some("Chromium binary not found".to_string())
+        );
+    }
+
+    #[tokio::test]
+    async fn test_passing_probe_and_disabled_feature() {
+        let mut registry = CapabilityRegistry::new();
+        registry.register_probe(FeatureId::LlmChat, || async { Ok(()) });
+
+        // Probe passes but the feature is not enabled
+        let status = registry.check_feature(FeatureId::LlmChat).await;
+        assert!(!status.is_available());
+        assert!(status.reason().is_some_and(|r| r.contains("not enabled")));
+
+        registry.enable_feature(FeatureId::LlmChat);
+        assert!(registry
+            .check_feature(FeatureId::LlmChat)
+            .await
+            .is_available());
+        assert!(registry.is_feature_available(FeatureId::LlmChat));
+    }
+
+    #[tokio::test]
+    async fn test_probe_results_are_cached_until_ttl() {
+        use std::sync::atomic::{AtomicU32, Ordering};
+
+        let calls = Arc::new(AtomicU32::new(0));
+        let calls_clone = Arc::clone(&calls);
+
+        let mut registry = CapabilityRegistry::new().with_probe_ttl(Duration::from_millis(50));
+        registry.register_probe(FeatureId::ManualScanning, move || {
+            calls_clone.fetch_add(1, Ordering::SeqCst);
+            async { Ok(()) }
+        });
+
+        registry.check_feature(FeatureId::ManualScanning).await;
+        registry.check_feature(FeatureId::ManualScanning).await;
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+
+        tokio::time::sleep(Duration::from_millis(60)).await;
+        registry.check_feature(FeatureId::ManualScanning).await;
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+
+        registry.invalidate(FeatureId::ManualScanning);
+        registry.check_feature(FeatureId::ManualScanning).await;
+        assert_eq!(calls.load(Ordering::SeqCst), 3);
+    }
 }
@@ -0,0 +1,107 @@
+//! Injectable time source.
+//!
+//! Code whose behavior depends on the current time (vault auto-lock,
+//! permission expiry, scheduler due times) reads it from a [`Clock`] rather
+//! than calling `Utc::now()` directly. Production uses [`SystemClock`];
+//! tests use [`MockClock`] and move time forward with
+//! [`MockClock::advance`] instead of sleeping.
+
+use chrono::{DateTime, Duration, Utc};
+use std::fmt::Debug;
+use std::sync::{Arc, Mutex};
+
+/// Source of the current time.
+pub trait Clock: Debug + Send + Sync {
+    /// The current time.
+    fn now(&self) -> DateTime<Utc>;
+}
+
+/// Clock shared between the components that read it.
+pub type SharedClock = Arc<dyn Clock>;
+
+/// The system's wall clock.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemClock;
+
+impl SystemClock {
+    /// A [`SharedClock`] reading the system time.
+    #[must_use]
+    pub fn shared() -> SharedClock {
+        Arc::new(Self)
+    }
+}
+
+impl Clock for SystemClock {
+    fn now(&self) -> DateTime<Utc> {
+        Utc::now()
+    }
+}
+
+/// Clock that only moves when told to, for tests.
+#[derive(Debug)]
+pub struct MockClock {
+    now: Mutex<DateTime<Utc>>,
+}
+
+impl MockClock {
+    /// Create a clock stopped at `start`.
+    #[must_use]
+    pub fn new(start: DateTime<Utc>) -> Self {
+        Self {
+            now: Mutex::new(start),
+        }
+    }
+
+    /// Move the clock forward by `duration`.
+    pub fn advance(&self, duration: Duration) {
+        *self.now.lock().expect("clock lock poisoned") += duration;
+    }
+
+    /// Set the clock to `now`.
+    pub fn set(&self, now: DateTime<Utc>) {
+        *self.now.lock().expect("clock lock poisoned") = now;
+    }
+}
+
+impl Default for MockClock {
+    /// A clock stopped at the current system time.
+    fn default() -> Self {
+        Self::new(Utc::now())
+    }
+}
+
+impl Clock for MockClock {
+    fn now(&self) -> DateTime<Utc> {
+        *self.now.lock().expect("clock lock poisoned")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mock_clock_moves_only_when_advanced() {
+        let start = DateTime::parse_from_rfc3339("2026-01-01T00:00:00Z")
+            .expect("valid")
+            .with_timezone(&Utc);
+        let clock = MockClock::new(start);
+
+        assert_eq!(clock.now(), start);
+        assert_eq!(clock.now(), start);
+
+        clock.advance(Duration::minutes(15));
+        assert_eq!(clock.now(), start + Duration::minutes(15));
+
+        clock.set(start);
+        assert_eq!(clock.now(), start);
+    }
+
+    #[test]
+    fn test_system_clock_follows_wall_time() {
+        let before = Utc::now();
+        let now = SystemClock.now();
+        assert!(now >= before);
+        assert!(now <= Utc::now());
+    }
+}
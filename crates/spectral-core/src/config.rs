@@ -5,17 +5,30 @@
 
 use crate::error::{ConfigError, ConfigResult};
 use directories::ProjectDirs;
+use notify::{RecommendedWatcher, RecursiveMode, Watcher};
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{self, RecvTimeoutError, Sender};
+use std::thread::JoinHandle;
+use std::time::Duration;
+
+/// Version of the config file shape written by this build.
+///
+/// Files without a `config_version` are version 1. See
+/// [`AppConfig::load_and_migrate`] for how older shapes are upgraded.
+pub const CONFIG_VERSION: u32 = 2;
 
 /// Main application configuration.
 ///
 /// This is loaded from `~/.config/spectral/config.toml` (or platform equivalent).
 /// If the file doesn't exist, default values are used.
-#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(default)]
 pub struct AppConfig {
+    /// Shape of the config file, see [`CONFIG_VERSION`]
+    pub config_version: u32,
     /// General application settings
     pub general: GeneralConfig,
     /// Vault and encryption settings
@@ -28,30 +41,312 @@ pub struct AppConfig {
     pub llm: LlmConfig,
     /// Notification settings
     pub notifications: NotificationConfig,
+    /// CAPTCHA solving settings
+    pub captcha: CaptchaConfig,
+}
+
+impl Default for AppConfig {
+    fn default() -> Self {
+        Self {
+            config_version: CONFIG_VERSION,
+            general: GeneralConfig::default(),
+            vault: VaultConfig::default(),
+            scanning: ScanningConfig::default(),
+            browser: BrowserConfig::default(),
+            llm: LlmConfig::default(),
+            notifications: NotificationConfig::default(),
+            captcha: CaptchaConfig::default(),
+        }
+    }
 }
 
 impl AppConfig {
     /// Load configuration from disk, falling back to defaults if not found.
     ///
+    /// An older config file is upgraded and written back, see
+    /// [`AppConfig::load_and_migrate`].
+    ///
     /// # Errors
     /// Returns error if:
     /// - Config directory cannot be determined
-    /// - File exists but cannot be read
+    /// - File exists but cannot be read or written back
     /// - File contents are not valid TOML
+    /// - File was written by a newer version of Spectral
     pub fn load() -> ConfigResult<Self> {
         let config_path = Self::config_path()?;
 
         if config_path.exists() {
-            tracing::debug!("Loading config from {}", config_path.display());
-            let contents = fs::read_to_string(&config_path)?;
-            let config = toml::from_str(&contents)?;
-            Ok(config)
+            Self::load_and_migrate(&config_path)
         } else {
             tracing::debug!("Config file not found, using defaults");
             Ok(Self::default())
         }
     }
 
+    /// Load and validate configuration from a specific file.
+    ///
+    /// An older config file is upgraded in memory only; the file is left as is.
+    ///
+    /// # Errors
+    /// Returns error if the file cannot be read, is not valid TOML, was
+    /// written by a newer version of Spectral, or contains out-of-range values.
+    pub fn load_from(path: &Path) -> ConfigResult<Self> {
+        tracing::debug!("Loading config from {}", path.display());
+        let contents = fs::read_to_string(path)?;
+        Self::parse(&contents)
+    }
+
+    /// Load configuration from a file, upgrading an older shape in place.
+    ///
+    /// Fields that were renamed or moved since the file's `config_version`
+    /// are carried over to their current names. Each change is logged, and
+    /// the upgraded config is written back so the upgrade happens once. The
+    /// file is rewritten from the parsed config, so comments and keys that
+    /// are no longer recognized are not kept.
+    ///
+    /// Out-of-range values are logged rather than rejected, so a config that
+    /// loaded before [`AppConfig::validate`] existed still loads.
+    ///
+    /// # Errors
+    /// Returns error if the file cannot be read or written, is not valid
+    /// TOML, or has a `config_version` newer than [`CONFIG_VERSION`]. A newer
+    /// file is never downgraded.
+    pub fn load_and_migrate(path: &Path) -> ConfigResult<Self> {
+        tracing::debug!("Loading config from {}", path.display());
+        let contents = fs::read_to_string(path)?;
+        let (config, changes) = Self::parse_migrating(&contents)?;
+        if let Err(e) = config.validate() {
+            tracing::warn!("Config {} has an invalid value: {}", path.display(), e);
+        }
+
+        if let Some(changes) = changes {
+            for change in &changes {
+                tracing::info!("Config migration: {}", change);
+            }
+            fs::write(path, toml::to_string_pretty(&config)?)?;
+            tracing::info!(
+                "Upgraded config {} to version {}",
+                path.display(),
+                CONFIG_VERSION
+            );
+        }
+
+        Ok(config)
+    }
+
+    /// Parse and validate configuration from a TOML string.
+    fn parse(contents: &str) -> ConfigResult<Self> {
+        let (config, _) = Self::parse_migrating(contents)?;
+        config.validate()?;
+        Ok(config)
+    }
+
+    /// Parse configuration, upgrading an older shape first.
+    ///
+    /// Returns the changes made, or `None` if the contents were already at
+    /// [`CONFIG_VERSION`].
+    fn parse_migrating(contents: &str) -> ConfigResult<(Self, Option<Vec<String>>)> {
+        let mut table: toml::Table = toml::from_str(contents)?;
+        let changes = migrate(&mut table)?;
+        let config: Self = table.try_into()?;
+        Ok((config, changes))
+    }
+
+    /// Check that configuration values are within acceptable ranges.
+    ///
+    /// # Errors
+    /// Returns `ConfigError::InvalidValue` naming the first offending field.
+    pub fn validate(&self) -> ConfigResult<()> {
+        fn invalid(field: &str, reason: &str) -> ConfigError {
+            ConfigError::InvalidValue {
+                field: field.to_string(),
+                reason: reason.to_string(),
+            }
+        }
+
+        if !matches!(self.general.theme.as_str(), "light" | "dark" | "system") {
+            return Err(invalid(
+                "general.theme",
+                "must be \"light\", \"dark\", or \"system\"",
+            ));
+        }
+        if self.scanning.concurrent_scans == 0 {
+            return Err(invalid("scanning.concurrent_scans", "must be at least 1"));
+        }
+        if self.scanning.max_retries == 0 {
+            return Err(invalid("scanning.max_retries", "must be at least 1"));
+        }
+        if self.scanning.timeout_secs == 0 {
+            return Err(invalid("scanning.timeout_secs", "must be at least 1"));
+        }
+        if self.scanning.job_timeout_mins == 0 {
+            return Err(invalid("scanning.job_timeout_mins", "must be at least 1"));
+        }
+        if self.scanning.max_requests_per_second > 0 && self.scanning.request_burst == 0 {
+            return Err(invalid(
+                "scanning.request_burst",
+                "must be at least 1 when a request budget is set",
+            ));
+        }
+        for (name, broker_ids) in &self.scanning.custom_tiers {
+            if name.trim().is_empty() || name.trim() != name {
+                return Err(invalid(
+                    "scanning.custom_tiers",
+                    "tier names must be non-empty without surrounding whitespace",
+                ));
+            }
+            if ScanTier::is_built_in_name(name) {
+                return Err(invalid(
+                    "scanning.custom_tiers",
+                    &format!("\"{name}\" is the name of a built-in tier"),
+                ));
+            }
+            if broker_ids.is_empty() {
+                return Err(invalid(
+                    "scanning.custom_tiers",
+                    &format!("tier \"{name}\" must list at least one broker"),
+                ));
+            }
+        }
+        if let ScanTier::Named(name) = &self.scanning.default_tier {
+            if self.scanning.custom_tier(name).is_none() {
+                return Err(invalid(
+                    "scanning.default_tier",
+                    &format!("no custom tier named \"{name}\" is defined"),
+                ));
+            }
+        }
+        if self.browser.window_width == 0 || self.browser.window_height == 0 {
+            return Err(invalid("browser.window_size", "must be non-zero"));
+        }
+        if self.browser.navigation_timeout_secs == 0 {
+            return Err(invalid(
+                "browser.navigation_timeout_secs",
+                "must be at least 1",
+            ));
+        }
+        if !(0.0..=2.0).contains(&self.llm.temperature) {
+            return Err(invalid("llm.temperature", "must be between 0.0 and 2.0"));
+        }
+        if self.captcha.external_solver_enabled && !self.captcha.service_url.starts_with("https://")
+        {
+            return Err(invalid("captcha.service_url", "must be an https:// URL"));
+        }
+        if self.captcha.solve_timeout_secs == 0 {
+            return Err(invalid("captcha.solve_timeout_secs", "must be at least 1"));
+        }
+
+        Ok(())
+    }
+
+    /// Watch a config file and invoke `on_change` whenever it changes.
+    ///
+    /// Uses the default [`WatchOptions`]. See [`AppConfig::watch_with`].
+    ///
+    /// # Errors
+    /// Returns `ConfigError::Watch` if the file system watcher cannot be
+    /// started.
+    pub fn watch<F>(path: impl Into<PathBuf>, on_change: F) -> ConfigResult<ConfigWatcher>
+    where
+        F: Fn(AppConfig) + Send + 'static,
+    {
+        Self::watch_with(path, WatchOptions::default(), on_change)
+    }
+
+    /// Watch a config file with a custom debounce interval.
+    ///
+    /// The file's directory is watched rather than the file itself, so edits
+    /// that replace the file (as most editors do) are seen too. After a
+    /// change the watcher waits until no further events arrive for
+    /// `options.debounce` before reading, so a burst of writes from an editor
+    /// produces a single reload. The callback only fires when the new
+    /// contents differ, parse and validate; a broken edit is logged and
+    /// ignored until the next change.
+    ///
+    /// # Errors
+    /// Returns `ConfigError::Watch` if the file system watcher cannot be
+    /// started, or `ConfigError::Io` if the config directory cannot be
+    /// created.
+    pub fn watch_with<F>(
+        path: impl Into<PathBuf>,
+        options: WatchOptions,
+        on_change: F,
+    ) -> ConfigResult<ConfigWatcher>
+    where
+        F: Fn(AppConfig) + Send + 'static,
+    {
+        let path = path.into();
+        let dir = match path.parent() {
+            Some(parent) if !parent.as_os_str().is_empty() => parent.to_path_buf(),
+            _ => PathBuf::from("."),
+        };
+        fs::create_dir_all(&dir)?;
+
+        let (events_tx, events) = mpsc::channel::<WatchEvent>();
+        let file_name = path.file_name().map(ToOwned::to_owned);
+        let mut watcher = notify::recommended_watcher({
+            let events_tx = events_tx.clone();
+            move |result: notify::Result<notify::Event>| {
+                let Ok(event) = result else {
+                    return;
+                };
+                if event.kind.is_access() {
+                    return;
+                }
+                if event
+                    .paths
+                    .iter()
+                    .any(|p| p.file_name() == file_name.as_deref())
+                {
+                    let _ = events_tx.send(WatchEvent::Changed);
+                }
+            }
+        })?;
+        watcher.watch(&dir, RecursiveMode::NonRecursive)?;
+
+        let mut last_applied = fs::read_to_string(&path).ok();
+        let handle = std::thread::spawn(move || {
+            while let Ok(WatchEvent::Changed) = events.recv() {
+                // Wait for the burst of events to settle
+                loop {
+                    match events.recv_timeout(options.debounce) {
+                        Ok(WatchEvent::Changed) => {}
+                        Err(RecvTimeoutError::Timeout) => break,
+                        Ok(WatchEvent::Stop) | Err(RecvTimeoutError::Disconnected) => return,
+                    }
+                }
+
+                let Ok(contents) = fs::read_to_string(&path) else {
+                    continue;
+                };
+                if last_applied.as_deref() == Some(contents.as_str()) {
+                    continue;
+                }
+
+                match Self::parse(&contents) {
+                    Ok(config) => {
+                        tracing::info!("Config reloaded from {}", path.display());
+                        last_applied = Some(contents);
+                        on_change(config);
+                    }
+                    Err(e) => {
+                        tracing::warn!(
+                            "Ignoring invalid config change in {}: {}",
+                            path.display(),
+                            e
+                        );
+                    }
+                }
+            }
+        });
+
+        Ok(ConfigWatcher {
+            watcher: Some(watcher),
+            stop: events_tx,
+            handle: Some(handle),
+        })
+    }
+
     /// Load configuration with environment variable overrides.
     ///
     /// Supports the following environment variables:
@@ -134,6 +429,173 @@ impl AppConfig {
     }
 }
 
+/// Upgrades from each older config shape, in order.
+///
+/// `MIGRATIONS[n]` upgrades version `n + 1` to `n + 2`, recording a line for
+/// each field it moves.
+const MIGRATIONS: [fn(&mut toml::Table, &mut Vec<String>); 1] = [migrate_v1_to_v2];
+
+/// Upgrade a parsed config file to [`CONFIG_VERSION`].
+///
+/// Returns the changes made, or `None` if it was already current.
+fn migrate(table: &mut toml::Table) -> ConfigResult<Option<Vec<String>>> {
+    let version =
+        match table.get("config_version") {
+            None => 1,
+            Some(toml::Value::Integer(n)) => u32::try_from(*n)
+                .ok()
+                .filter(|v| *v >= 1)
+                .ok_or_else(|| ConfigError::InvalidValue {
+                    field: "config_version".to_string(),
+                    reason: "must be a positive integer".to_string(),
+                })?,
+            Some(_) => {
+                return Err(ConfigError::InvalidValue {
+                    field: "config_version".to_string(),
+                    reason: "must be a positive integer".to_string(),
+                })
+            }
+        };
+
+    if version > CONFIG_VERSION {
+        return Err(ConfigError::UnsupportedVersion {
+            found: version,
+            supported: CONFIG_VERSION,
+        });
+    }
+    if version == CONFIG_VERSION {
+        return Ok(None);
+    }
+
+    let mut changes = Vec::new();
+    for step in &MIGRATIONS[(version - 1) as usize..] {
+        step(table, &mut changes);
+    }
+    table.insert(
+        "config_version".to_string(),
+        toml::Value::Integer(CONFIG_VERSION.into()),
+    );
+    changes.push(format!("config_version {version} -> {CONFIG_VERSION}"));
+    Ok(Some(changes))
+}
+
+/// Version 1 kept the auto-lock timeout under `[general]`, the user agent
+/// under `[browser]`, and used shorter LLM and notification field names.
+fn migrate_v1_to_v2(table: &mut toml::Table, changes: &mut Vec<String>) {
+    for (from, to) in [
+        (
+            ("general", "auto_lock_minutes"),
+            ("vault", "auto_lock_minutes"),
+        ),
+        (("browser", "user_agent"), ("scanning", "user_agent")),
+        (("llm", "provider"), ("llm", "default_provider")),
+        (
+            ("notifications", "on_removal_confirmed"),
+            ("notifications", "notify_removal_confirmed"),
+        ),
+        (
+            ("notifications", "on_new_listing_found"),
+            ("notifications", "notify_pii_found"),
+        ),
+    ] {
+        move_field(table, from, to, changes);
+    }
+}
+
+/// Move `from` (section, key) to `to`, unless `to` is already set.
+fn move_field(
+    table: &mut toml::Table,
+    from: (&str, &str),
+    to: (&str, &str),
+    changes: &mut Vec<String>,
+) {
+    let Some(value) = table
+        .get_mut(from.0)
+        .and_then(toml::Value::as_table_mut)
+        .and_then(|section| section.remove(from.1))
+    else {
+        return;
+    };
+
+    let Some(section) = table
+        .entry(to.0)
+        .or_insert_with(|| toml::Value::Table(toml::Table::new()))
+        .as_table_mut()
+    else {
+        return;
+    };
+
+    if section.contains_key(to.1) {
+        changes.push(format!(
+            "dropped {}.{} because {}.{} is already set",
+            from.0, from.1, to.0, to.1
+        ));
+    } else {
+        section.insert(to.1.to_string(), value);
+        changes.push(format!("{}.{} -> {}.{}", from.0, from.1, to.0, to.1));
+    }
+}
+
+/// Debounce interval for [`AppConfig::watch_with`].
+#[derive(Debug, Clone, Copy)]
+pub struct WatchOptions {
+    /// How long the file must go without changes before it is re-read
+    pub debounce: Duration,
+}
+
+impl Default for WatchOptions {
+    fn default() -> Self {
+        Self {
+            debounce: Duration::from_millis(500),
+        }
+    }
+}
+
+/// Message to a config watcher's background thread.
+enum WatchEvent {
+    Changed,
+    Stop,
+}
+
+/// Handle to a running config file watcher.
+///
+/// The background thread stops when this handle is dropped.
+pub struct ConfigWatcher {
+    watcher: Option<RecommendedWatcher>,
+    stop: Sender<WatchEvent>,
+    handle: Option<JoinHandle<()>>,
+}
+
+impl std::fmt::Debug for ConfigWatcher {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ConfigWatcher")
+            .field("running", &self.handle.is_some())
+            .finish_non_exhaustive()
+    }
+}
+
+impl ConfigWatcher {
+    /// Stop watching and wait for the background thread to exit.
+    pub fn stop(mut self) {
+        self.shutdown();
+    }
+
+    fn shutdown(&mut self) {
+        // No more file events once the watcher is gone
+        self.watcher.take();
+        let _ = self.stop.send(WatchEvent::Stop);
+        if let Some(handle) = self.handle.take() {
+            let _ = handle.join();
+        }
+    }
+}
+
+impl Drop for ConfigWatcher {
+    fn drop(&mut self) {
+        self.shutdown();
+    }
+}
+
 /// General application settings.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(default)]
@@ -166,6 +628,9 @@ pub struct VaultConfig {
     pub argon2_memory_kb: u32,
     /// Argon2 iteration count
     pub argon2_iterations: u32,
+    /// Move vaults created with weaker key derivation parameters onto the
+    /// current defaults when they are unlocked
+    pub upgrade_kdf_on_unlock: bool,
 }
 
 impl Default for VaultConfig {
@@ -174,6 +639,7 @@ impl Default for VaultConfig {
             auto_lock_minutes: 15,
             argon2_memory_kb: 262_144, // 256 MB
             argon2_iterations: 4,
+            upgrade_kdf_on_unlock: false,
         }
     }
 }
@@ -192,17 +658,158 @@ pub struct ScanningConfig {
     pub timeout_secs: u64,
     /// User agent string
     pub user_agent: String,
+    /// Global request budget shared across all brokers (0 = unlimited)
+    pub max_requests_per_second: u32,
+    /// Requests that may be issued back-to-back before the budget applies
+    pub request_burst: u32,
+    /// Brokers to scan when a scan does not name a tier or brokers
+    pub default_tier: ScanTier,
+    /// Attempts per page fetch before a broker scan is marked failed
+    pub max_retries: u32,
+    /// User-defined tiers, by name, each listing the broker IDs it covers
+    pub custom_tiers: BTreeMap<String, Vec<String>>,
+    /// How much profile data searches send to brokers
+    pub disclosure: ScanDisclosure,
+    /// Minutes a running scan job may go without progress before it is
+    /// marked failed as timed out
+    pub job_timeout_mins: u32,
+    /// How exhaustively a scan searches when the scan does not say
+    pub depth: ScanDepth,
+    /// Names each broker is searched for, counting the profile's own; above
+    /// 1, aliases and nicknames are searched too, one request each
+    pub name_variants: u32,
+}
+
+impl ScanningConfig {
+    /// Broker IDs of the custom tier called `name`, if one is defined.
+    #[must_use]
+    pub fn custom_tier(&self, name: &str) -> Option<&[String]> {
+        self.custom_tiers.get(name).map(Vec::as_slice)
+    }
+}
+
+/// How much profile data a scan discloses to the brokers it searches.
+///
+/// A broker's search is itself a disclosure: whatever fields go into the
+/// search URL are sent to the broker.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum ScanDisclosure {
+    /// Send only the fields each broker requires, even when the search
+    /// accepts more. Fewer listings may be matched.
+    Minimal,
+    /// Send every field the broker's search accepts
+    #[default]
+    Full,
+}
+
+/// How exhaustively a scan searches.
+///
+/// One switch over broker selection, pagination, browser use and retries.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum ScanDepth {
+    /// Fast "am I newly exposed?" check: tier 1 brokers only, the first
+    /// result page over plain HTTP, and no retries
+    Quick,
+    /// Cover as much as possible: every broker in the tier, every result
+    /// page, the browser where one is installed, and the configured retries
+    #[default]
+    Thorough,
+}
+
+/// Broker tiers a scan can cover, by scan priority or by a user-defined list.
+///
+/// Serialized as a plain string: the built-in tier names `Tier1`, `Tier2`
+/// and `All` (matched case-insensitively), or the name of a custom tier.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub enum ScanTier {
+    /// Top-priority brokers only
+    Tier1,
+    /// Tier 1 and tier 2 brokers
+    Tier2,
+    /// Every broker except manual-only ones
+    #[default]
+    All,
+    /// A custom tier the user defined, by name
+    Named(String),
+}
+
+impl ScanTier {
+    /// Names of the built-in tiers, which custom tiers cannot reuse.
+    pub const BUILT_IN_NAMES: [&'static str; 3] = ["Tier1", "Tier2", "All"];
+
+    /// Whether `name` refers to a built-in tier.
+    #[must_use]
+    pub fn is_built_in_name(name: &str) -> bool {
+        Self::BUILT_IN_NAMES
+            .iter()
+            .any(|built_in| built_in.eq_ignore_ascii_case(name.trim()))
+    }
+}
+
+impl std::fmt::Display for ScanTier {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Tier1 => f.write_str("Tier1"),
+            Self::Tier2 => f.write_str("Tier2"),
+            Self::All => f.write_str("All"),
+            Self::Named(name) => f.write_str(name),
+        }
+    }
+}
+
+impl std::str::FromStr for ScanTier {
+    type Err = ConfigError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        if s.is_empty() {
+            return Err(ConfigError::InvalidValue {
+                field: "scan tier".to_string(),
+                reason: "tier name cannot be empty".to_string(),
+            });
+        }
+        Ok(match s.to_ascii_lowercase().as_str() {
+            "tier1" => Self::Tier1,
+            "tier2" => Self::Tier2,
+            "all" => Self::All,
+            _ => Self::Named(s.to_string()),
+        })
+    }
+}
+
+impl TryFrom<String> for ScanTier {
+    type Error = ConfigError;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        value.parse()
+    }
+}
+
+impl From<ScanTier> for String {
+    fn from(tier: ScanTier) -> Self {
+        tier.to_string()
+    }
 }
 
 impl Default for ScanningConfig {
     fn default() -> Self {
         Self {
-            concurrent_scans: 3,
+            concurrent_scans: 4,
             delay_between_scans_ms: 2000,
             respect_robots_txt: true,
             timeout_secs: 30,
             user_agent: "Spectral/0.1.0 (+https://github.com/spectral-privacy/spectral)"
                 .to_string(),
+            max_requests_per_second: 0,
+            request_burst: 2,
+            default_tier: ScanTier::All,
+            max_retries: 3,
+            custom_tiers: BTreeMap::new(),
+            disclosure: ScanDisclosure::Full,
+            job_timeout_mins: 30,
+            depth: ScanDepth::Thorough,
+            name_variants: 1,
         }
     }
 }
@@ -219,6 +826,9 @@ pub struct BrowserConfig {
     pub window_height: u32,
     /// Navigation timeout in seconds
     pub navigation_timeout_secs: u64,
+    /// Type into opt-out forms and click with human-like timing instead of
+    /// filling them instantly
+    pub humanize_input: bool,
 }
 
 impl Default for BrowserConfig {
@@ -228,6 +838,7 @@ impl Default for BrowserConfig {
             window_width: 1920,
             window_height: 1080,
             navigation_timeout_secs: 30,
+            humanize_input: true,
         }
     }
 }
@@ -293,6 +904,33 @@ impl Default for NotificationConfig {
     }
 }
 
+/// CAPTCHA solving settings.
+///
+/// By default a CAPTCHA on an opt-out form queues the removal for the user.
+/// Enabling the external solver sends the page URL and the CAPTCHA's site
+/// key to a paid third-party service instead. The service's API key is kept
+/// in the vault, not in this file.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CaptchaConfig {
+    /// Send CAPTCHAs on opt-out forms to an external solving service
+    pub external_solver_enabled: bool,
+    /// Base URL of a solving service with a 2captcha-compatible API
+    pub service_url: String,
+    /// Seconds to wait for the service to return a solution
+    pub solve_timeout_secs: u64,
+}
+
+impl Default for CaptchaConfig {
+    fn default() -> Self {
+        Self {
+            external_solver_enabled: false,
+            service_url: "https://2captcha.com".to_string(),
+            solve_timeout_secs: 180,
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -303,9 +941,11 @@ mod tests {
         let config = AppConfig::default();
         assert_eq!(config.general.theme, "system");
         assert_eq!(config.vault.auto_lock_minutes, 15);
-        assert_eq!(config.scanning.concurrent_scans, 3);
+        assert_eq!(config.scanning.concurrent_scans, 4);
         assert!(config.browser.headless);
+        assert!(config.browser.humanize_input);
         assert!(!config.llm.enabled);
+        assert!(!config.captcha.external_solver_enabled);
     }
 
     #[test]
@@ -378,7 +1018,390 @@ auto_lock_minutes = 20
         assert_eq!(config.general.theme, "dark");
         assert_eq!(config.vault.auto_lock_minutes, 20);
         // These should be defaults
-        assert_eq!(config.scanning.concurrent_scans, 3);
+        assert_eq!(config.scanning.concurrent_scans, 4);
+        assert_eq!(config.scanning.default_tier, ScanTier::All);
+        assert_eq!(config.scanning.max_retries, 3);
+        assert!(config.browser.headless);
+    }
+
+    const V1_CONFIG: &str = r#"
+[general]
+auto_lock_minutes = 45
+theme = "dark"
+
+[scanning]
+concurrent_scans = 5
+
+[browser]
+headless = false
+user_agent = "Mozilla/5.0 (test)"
+
+[llm]
+enabled = true
+provider = "ollama"
+
+[notifications]
+enabled = true
+on_removal_confirmed = false
+on_new_listing_found = false
+"#;
+
+    #[test]
+    fn test_load_and_migrate_upgrades_v1_config() {
+        let tmp = TempDir::new().expect("create temp dir");
+        let config_path = tmp.path().join("config.toml");
+        fs::write(&config_path, V1_CONFIG).expect("write config file");
+
+        let config = AppConfig::load_and_migrate(&config_path).expect("migrate config");
+
+        assert_eq!(config.config_version, CONFIG_VERSION);
+        assert_eq!(config.general.theme, "dark");
+        assert_eq!(config.vault.auto_lock_minutes, 45);
+        assert_eq!(config.scanning.concurrent_scans, 5);
+        assert_eq!(config.scanning.user_agent, "Mozilla/5.0 (test)");
+        assert!(!config.browser.headless);
+        assert!(config.llm.enabled);
+        assert_eq!(config.llm.default_provider, "ollama");
+        assert!(!config.notifications.notify_removal_confirmed);
+        assert!(!config.notifications.notify_pii_found);
+        assert!(config.notifications.notify_scan_complete);
+
+        // The upgraded shape is written back
+        let written = fs::read_to_string(&config_path).expect("read config file");
+        let table: toml::Table = toml::from_str(&written).expect("parse written config");
+        assert_eq!(
+            table["config_version"].as_integer(),
+            Some(i64::from(CONFIG_VERSION))
+        );
+        assert_eq!(table["vault"]["auto_lock_minutes"].as_integer(), Some(45));
+        assert!(table["general"].get("auto_lock_minutes").is_none());
+        assert_eq!(table["llm"]["default_provider"].as_str(), Some("ollama"));
+
+        let reloaded = AppConfig::load_and_migrate(&config_path).expect("reload config");
+        assert_eq!(reloaded.vault.auto_lock_minutes, 45);
+        assert_eq!(
+            fs::read_to_string(&config_path).expect("read config file"),
+            written
+        );
+    }
+
+    #[test]
+    fn test_migration_keeps_current_field_over_old_one() {
+        let mut table: toml::Table = toml::from_str(
+            "
+[general]
+auto_lock_minutes = 45
+
+[vault]
+auto_lock_minutes = 10
+",
+        )
+        .expect("parse config");
+
+        let changes = migrate(&mut table)
+            .expect("migrate")
+            .expect("version 1 is upgraded");
+        assert!(changes
+            .iter()
+            .any(|c| c.starts_with("dropped general.auto_lock_minutes")));
+
+        let config: AppConfig = table.try_into().expect("deserialize config");
+        assert_eq!(config.vault.auto_lock_minutes, 10);
+    }
+
+    #[test]
+    fn test_current_config_is_not_rewritten() {
+        let tmp = TempDir::new().expect("create temp dir");
+        let config_path = tmp.path().join("config.toml");
+        let contents = format!(
+            "# my settings\nconfig_version = {CONFIG_VERSION}\n\n[general]\ntheme = \"light\"\n"
+        );
+        fs::write(&config_path, &contents).expect("write config file");
+
+        let config = AppConfig::load_and_migrate(&config_path).expect("load config");
+
+        assert_eq!(config.general.theme, "light");
+        assert_eq!(
+            fs::read_to_string(&config_path).expect("read config file"),
+            contents
+        );
+    }
+
+    #[test]
+    fn test_out_of_range_values_still_load() {
+        let tmp = TempDir::new().expect("create temp dir");
+        let config_path = tmp.path().join("config.toml");
+        let contents =
+            format!("config_version = {CONFIG_VERSION}\n\n[scanning]\nconcurrent_scans = 0\n");
+        fs::write(&config_path, &contents).expect("write config file");
+
+        let config = AppConfig::load_and_migrate(&config_path).expect("load config");
+        assert_eq!(config.scanning.concurrent_scans, 0);
+
+        // Loading a specific file still checks the values
+        assert!(matches!(
+            AppConfig::load_from(&config_path),
+            Err(ConfigError::InvalidValue { .. })
+        ));
+    }
+
+    #[test]
+    fn test_future_config_version_is_rejected() {
+        let tmp = TempDir::new().expect("create temp dir");
+        let config_path = tmp.path().join("config.toml");
+        let contents = format!("config_version = {}\n", CONFIG_VERSION + 1);
+        fs::write(&config_path, &contents).expect("write config file");
+
+        let err = AppConfig::load_and_migrate(&config_path).expect_err("newer version");
+
+        assert!(matches!(
+            err,
+            ConfigError::UnsupportedVersion { found, supported: CONFIG_VERSION }
+                if found == CONFIG_VERSION + 1
+        ));
+        assert_eq!(
+            fs::read_to_string(&config_path).expect("read config file"),
+            contents
+        );
+    }
+
+    #[test]
+    fn test_scan_tier_parses_from_toml() {
+        let config: AppConfig =
+            toml::from_str("[scanning]\ndefault_tier = \"Tier1\"\n").expect("parse config");
+        assert_eq!(config.scanning.default_tier, ScanTier::Tier1);
+    }
+
+    #[test]
+    fn test_scan_disclosure_parses_from_toml() {
+        assert_eq!(
+            AppConfig::default().scanning.disclosure,
+            ScanDisclosure::Full
+        );
+
+        let config: AppConfig =
+            toml::from_str("[scanning]\ndisclosure = \"Minimal\"\n").expect("parse config");
+        assert_eq!(config.scanning.disclosure, ScanDisclosure::Minimal);
+    }
+
+    #[test]
+    fn test_scan_depth_parses_from_toml() {
+        assert_eq!(AppConfig::default().scanning.depth, ScanDepth::Thorough);
+
+        let config: AppConfig =
+            toml::from_str("[scanning]\ndepth = \"Quick\"\n").expect("parse config");
+        assert_eq!(config.scanning.depth, ScanDepth::Quick);
+    }
+
+    #[test]
+    fn test_humanize_input_can_be_disabled() {
+        let config: AppConfig =
+            toml::from_str("[browser]\nhumanize_input = false\n").expect("parse config");
+        assert!(!config.browser.humanize_input);
         assert!(config.browser.headless);
     }
+
+    #[test]
+    fn test_scan_tier_from_str_and_custom_names() {
+        assert_eq!("tier2".parse::<ScanTier>().expect("parse"), ScanTier::Tier2);
+        assert_eq!(" ALL ".parse::<ScanTier>().expect("parse"), ScanTier::All);
+        assert_eq!(
+            "my top 5".parse::<ScanTier>().expect("parse"),
+            ScanTier::Named("my top 5".to_string())
+        );
+        assert!("  ".parse::<ScanTier>().is_err());
+        assert!(ScanTier::is_built_in_name("tier1"));
+        assert!(!ScanTier::is_built_in_name("my top 5"));
+
+        let config: AppConfig =
+            toml::from_str("[scanning]\ndefault_tier = \"my top 5\"\n").expect("parse config");
+        assert_eq!(
+            config.scanning.default_tier,
+            ScanTier::Named("my top 5".to_string())
+        );
+        let serialized = toml::to_string(&config).expect("serialize config");
+        assert!(serialized.contains("default_tier = \"my top 5\""));
+    }
+
+    #[test]
+    fn test_custom_tiers_parse_and_validate() {
+        let config: AppConfig = toml::from_str(
+            "[scanning]\ndefault_tier = \"my top 5\"\n\n[scanning.custom_tiers]\n\"my top 5\" = [\"spokeo\", \"beenverified\"]\n",
+        )
+        .expect("parse config");
+        assert!(config.validate().is_ok());
+        assert_eq!(
+            config.scanning.custom_tier("my top 5"),
+            Some(&["spokeo".to_string(), "beenverified".to_string()][..])
+        );
+        assert_eq!(config.scanning.custom_tier("other"), None);
+
+        let mut config = AppConfig::default();
+        config.scanning.default_tier = ScanTier::Named("missing".to_string());
+        assert!(matches!(
+            config.validate(),
+            Err(ConfigError::InvalidValue { field, .. }) if field == "scanning.default_tier"
+        ));
+
+        let mut config = AppConfig::default();
+        config
+            .scanning
+            .custom_tiers
+            .insert("tier1".to_string(), vec!["spokeo".to_string()]);
+        assert!(config.validate().is_err());
+
+        let mut config = AppConfig::default();
+        config
+            .scanning
+            .custom_tiers
+            .insert("empty".to_string(), Vec::new());
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn test_validate_rejects_out_of_range_values() {
+        assert!(AppConfig::default().validate().is_ok());
+
+        let mut config = AppConfig::default();
+        config.scanning.concurrent_scans = 0;
+        assert!(matches!(
+            config.validate(),
+            Err(ConfigError::InvalidValue { field, .. }) if field == "scanning.concurrent_scans"
+        ));
+
+        let mut config = AppConfig::default();
+        config.general.theme = "neon".to_string();
+        assert!(config.validate().is_err());
+
+        let mut config = AppConfig::default();
+        config.scanning.max_requests_per_second = 2;
+        config.scanning.request_burst = 0;
+        assert!(config.validate().is_err());
+        config.scanning.max_requests_per_second = 0;
+        assert!(config.validate().is_ok());
+
+        let mut config = AppConfig::default();
+        config.captcha.external_solver_enabled = true;
+        config.captcha.service_url = "http://2captcha.com".to_string();
+        assert!(matches!(
+            config.validate(),
+            Err(ConfigError::InvalidValue { field, .. }) if field == "captcha.service_url"
+        ));
+
+        let mut config = AppConfig::default();
+        config.scanning.job_timeout_mins = 0;
+        assert!(matches!(
+            config.validate(),
+            Err(ConfigError::InvalidValue { field, .. }) if field == "scanning.job_timeout_mins"
+        ));
+    }
+
+    fn fast_watch_options() -> WatchOptions {
+        WatchOptions {
+            debounce: Duration::from_millis(60),
+        }
+    }
+
+    #[test]
+    fn test_watch_reloads_on_change() {
+        let tmp = TempDir::new().expect("create temp dir");
+        let config_path = tmp.path().join("config.toml");
+        fs::write(&config_path, "[scanning]\nconcurrent_scans = 3\n").expect("write config");
+
+        let (tx, rx) = std::sync::mpsc::channel();
+        let watcher = AppConfig::watch_with(&config_path, fast_watch_options(), move |config| {
+            let _ = tx.send(config);
+        })
+        .expect("start watcher");
+
+        fs::write(&config_path, "[scanning]\nconcurrent_scans = 7\n").expect("update config");
+
+        let reloaded = rx
+            .recv_timeout(Duration::from_secs(5))
+            .expect("callback should fire");
+        assert_eq!(reloaded.scanning.concurrent_scans, 7);
+
+        watcher.stop();
+    }
+
+    #[test]
+    fn test_watch_reloads_on_replaced_file() {
+        let tmp = TempDir::new().expect("create temp dir");
+        let config_path = tmp.path().join("config.toml");
+        fs::write(&config_path, "[llm]\nenabled = false\n").expect("write config");
+
+        let (tx, rx) = std::sync::mpsc::channel();
+        let _watcher = AppConfig::watch_with(&config_path, fast_watch_options(), move |config| {
+            let _ = tx.send(config);
+        })
+        .expect("start watcher");
+
+        // Editors commonly save by writing a temp file and renaming it over
+        let staged = tmp.path().join("config.toml.tmp");
+        fs::write(&staged, "[llm]\nenabled = true\n").expect("write staged config");
+        fs::rename(&staged, &config_path).expect("replace config");
+
+        let reloaded = rx
+            .recv_timeout(Duration::from_secs(5))
+            .expect("callback should fire");
+        assert!(reloaded.llm.enabled);
+    }
+
+    #[test]
+    fn test_watch_ignores_invalid_intermediate_write() {
+        let tmp = TempDir::new().expect("create temp dir");
+        let config_path = tmp.path().join("config.toml");
+        fs::write(&config_path, "[browser]\nheadless = true\n").expect("write config");
+
+        let (tx, rx) = std::sync::mpsc::channel();
+        let _watcher = AppConfig::watch_with(&config_path, fast_watch_options(), move |config| {
+            let _ = tx.send(config);
+        })
+        .expect("start watcher");
+
+        // Broken TOML, then a value that parses but fails validation
+        fs::write(&config_path, "[browser\nheadless = ").expect("write broken config");
+        assert!(rx.recv_timeout(Duration::from_millis(300)).is_err());
+
+        fs::write(&config_path, "[scanning]\nconcurrent_scans = 0\n")
+            .expect("write invalid config");
+        assert!(rx.recv_timeout(Duration::from_millis(300)).is_err());
+
+        fs::write(&config_path, "[browser]\nheadless = false\n").expect("write fixed config");
+        let reloaded = rx
+            .recv_timeout(Duration::from_secs(5))
+            .expect("callback should fire for valid config");
+        assert!(!reloaded.browser.headless);
+    }
+
+    #[test]
+    fn test_watch_debounces_rapid_writes() {
+        let tmp = TempDir::new().expect("create temp dir");
+        let config_path = tmp.path().join("config.toml");
+        fs::write(&config_path, "[vault]\nauto_lock_minutes = 15\n").expect("write config");
+
+        let (tx, rx) = std::sync::mpsc::channel();
+        let options = WatchOptions {
+            debounce: Duration::from_millis(200),
+        };
+        let _watcher = AppConfig::watch_with(&config_path, options, move |config| {
+            let _ = tx.send(config);
+        })
+        .expect("start watcher");
+
+        for minutes in 16..=20 {
+            fs::write(
+                &config_path,
+                format!("[vault]\nauto_lock_minutes = {minutes}\n"),
+            )
+            .expect("write config");
+            std::thread::sleep(Duration::from_millis(30));
+        }
+
+        let reloaded = rx
+            .recv_timeout(Duration::from_secs(5))
+            .expect("callback should fire");
+        assert_eq!(reloaded.vault.auto_lock_minutes, 20);
+        assert!(rx.recv_timeout(Duration::from_millis(400)).is_err());
+    }
 }
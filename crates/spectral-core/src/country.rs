@@ -0,0 +1,203 @@
+//! Country-specific address conventions.
+//!
+//! Profiles and brokers were originally modelled on US addresses (state plus
+//! ZIP code). [`AddressFormat`] describes what a country's postal address
+//! looks like so that completeness scoring, validation and URL building can
+//! apply the right expectations for users outside the US.
+
+use crate::types::PiiField;
+use regex::Regex;
+use std::collections::HashMap;
+use std::sync::OnceLock;
+
+/// Country assumed for profiles that do not record one.
+///
+/// Profiles created before country support was added were US-only.
+pub const DEFAULT_COUNTRY: &str = "US";
+
+/// Postal address conventions for a single country.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AddressFormat {
+    /// ISO 3166-1 alpha-2 country code
+    pub country_code: &'static str,
+    /// Whether addresses include a state, province or territory
+    pub has_states: bool,
+    /// Anchored pattern for valid postcodes, `None` when any value is accepted
+    postcode_pattern: Option<&'static str>,
+}
+
+const KNOWN_FORMATS: &[AddressFormat] = &[
+    AddressFormat {
+        country_code: "US",
+        has_states: true,
+        postcode_pattern: Some(r"^\d{5}(-\d{4})?$"),
+    },
+    AddressFormat {
+        country_code: "CA",
+        has_states: true,
+        postcode_pattern: Some(r"^[A-Z]\d[A-Z] ?\d[A-Z]\d$"),
+    },
+    AddressFormat {
+        country_code: "AU",
+        has_states: true,
+        postcode_pattern: Some(r"^\d{4}$"),
+    },
+    AddressFormat {
+        country_code: "GB",
+        has_states: false,
+        postcode_pattern: Some(r"^[A-Z]{1,2}\d[A-Z\d]? ?\d[A-Z]{2}$"),
+    },
+    AddressFormat {
+        country_code: "IE",
+        has_states: false,
+        postcode_pattern: Some(r"^[A-Z]\d[\dW] ?[A-Z\d]{4}$"),
+    },
+    AddressFormat {
+        country_code: "NZ",
+        has_states: false,
+        postcode_pattern: Some(r"^\d{4}$"),
+    },
+    AddressFormat {
+        country_code: "DE",
+        has_states: false,
+        postcode_pattern: Some(r"^\d{5}$"),
+    },
+    AddressFormat {
+        country_code: "FR",
+        has_states: false,
+        postcode_pattern: Some(r"^\d{5}$"),
+    },
+];
+
+/// Normalize a user-entered country to an ISO 3166-1 alpha-2 code.
+///
+/// Accepts codes in any case and a few common names and aliases (e.g.
+/// "United Kingdom", "UK", "USA"). Unrecognized values are returned trimmed
+/// and upper-cased.
+#[must_use]
+pub fn normalize_country(country: &str) -> String {
+    let upper = country.trim().to_uppercase();
+    let code = match upper.as_str() {
+        "USA" | "UNITED STATES" | "UNITED STATES OF AMERICA" => "US",
+        "UK" | "UNITED KINGDOM" | "GREAT BRITAIN" | "ENGLAND" | "SCOTLAND" | "WALES"
+        | "NORTHERN IRELAND" => "GB",
+        "CANADA" => "CA",
+        "AUSTRALIA" => "AU",
+        "IRELAND" => "IE",
+        "NEW ZEALAND" => "NZ",
+        "GERMANY" | "DEUTSCHLAND" => "DE",
+        "FRANCE" => "FR",
+        _ => return upper,
+    };
+    code.to_string()
+}
+
+impl AddressFormat {
+    /// Look up the address format for a country.
+    ///
+    /// `None` falls back to [`DEFAULT_COUNTRY`]. Countries without a known
+    /// format get a permissive format with no states and no postcode check.
+    #[must_use]
+    pub fn for_country(country: Option<&str>) -> Self {
+        let code = normalize_country(country.unwrap_or(DEFAULT_COUNTRY));
+        KNOWN_FORMATS
+            .iter()
+            .find(|format| format.country_code == code)
+            .copied()
+            .unwrap_or(Self {
+                country_code: "",
+                has_states: false,
+                postcode_pattern: None,
+            })
+    }
+
+    /// Check whether a profile field is meaningful for addresses in this country.
+    ///
+    /// Only `State` varies by country; every other field applies everywhere.
+    #[must_use]
+    pub fn expects_field(&self, field: PiiField) -> bool {
+        match field {
+            PiiField::State => self.has_states,
+            _ => true,
+        }
+    }
+
+    /// Check whether a postcode matches this country's format.
+    ///
+    /// Comparison ignores case and surrounding whitespace.
+    #[must_use]
+    pub fn is_valid_postcode(&self, postcode: &str) -> bool {
+        let Some(pattern) = self.postcode_pattern else {
+            return !postcode.trim().is_empty();
+        };
+        postcode_regexes()[pattern].is_match(&postcode.trim().to_uppercase())
+    }
+}
+
+/// Compiled postcode patterns of [`KNOWN_FORMATS`], keyed by pattern.
+fn postcode_regexes() -> &'static HashMap<&'static str, Regex> {
+    static REGEXES: OnceLock<HashMap<&'static str, Regex>> = OnceLock::new();
+    REGEXES.get_or_init(|| {
+        KNOWN_FORMATS
+            .iter()
+            .filter_map(|format| format.postcode_pattern)
+            .map(|pattern| {
+                let regex = Regex::new(pattern).expect("postcode pattern should be valid");
+                (pattern, regex)
+            })
+            .collect()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalize_country_aliases() {
+        assert_eq!(normalize_country("uk"), "GB");
+        assert_eq!(normalize_country(" United Kingdom "), "GB");
+        assert_eq!(normalize_country("USA"), "US");
+        assert_eq!(normalize_country("ca"), "CA");
+        assert_eq!(normalize_country("se"), "SE");
+    }
+
+    #[test]
+    fn test_missing_country_defaults_to_us() {
+        let format = AddressFormat::for_country(None);
+        assert_eq!(format.country_code, "US");
+        assert!(format.expects_field(PiiField::State));
+    }
+
+    #[test]
+    fn test_uk_has_no_states() {
+        let format = AddressFormat::for_country(Some("GB"));
+        assert!(!format.expects_field(PiiField::State));
+        assert!(format.expects_field(PiiField::ZipCode));
+    }
+
+    #[test]
+    fn test_postcode_validation_per_country() {
+        let us = AddressFormat::for_country(Some("US"));
+        assert!(us.is_valid_postcode("94102"));
+        assert!(us.is_valid_postcode("94102-1234"));
+        assert!(!us.is_valid_postcode("SW1A 1AA"));
+
+        let uk = AddressFormat::for_country(Some("United Kingdom"));
+        assert!(uk.is_valid_postcode("SW1A 1AA"));
+        assert!(uk.is_valid_postcode("m1 1ae"));
+        assert!(!uk.is_valid_postcode("94102"));
+
+        let canada = AddressFormat::for_country(Some("CA"));
+        assert!(canada.is_valid_postcode("K1A 0B1"));
+        assert!(!canada.is_valid_postcode("12345"));
+    }
+
+    #[test]
+    fn test_unknown_country_is_permissive() {
+        let format = AddressFormat::for_country(Some("SE"));
+        assert!(!format.has_states);
+        assert!(format.is_valid_postcode("114 55"));
+        assert!(!format.is_valid_postcode("  "));
+    }
+}
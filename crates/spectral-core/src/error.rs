@@ -3,12 +3,20 @@
 //! This module defines the central error type used across all subsystems.
 //! Each subsystem error is represented as a variant for clear error propagation.
 
+use serde::Serialize;
+use std::fmt;
 use thiserror::Error;
 
+/// Error kept as the cause of a [`SpectralError`].
+pub type BoxError = Box<dyn std::error::Error + Send + Sync + 'static>;
+
 /// Central error type for all Spectral operations.
 ///
 /// Each variant represents an error from a specific subsystem, allowing
 /// for clear error propagation and handling across module boundaries.
+/// Variants keep the error that caused them, so
+/// [`source`](std::error::Error::source) walks back to the original failure
+/// while the message shown to the user stays the subsystem's own.
 #[derive(Error, Debug)]
 pub enum SpectralError {
     /// Configuration errors (file loading, parsing, validation)
@@ -16,28 +24,64 @@ pub enum SpectralError {
     Config(#[from] ConfigError),
 
     /// Vault errors (encryption, decryption, locking)
-    #[error("vault error: {0}")]
-    Vault(String),
+    #[error("vault error: {message}")]
+    Vault {
+        /// What went wrong
+        message: String,
+        /// Underlying error, if any
+        #[source]
+        source: Option<BoxError>,
+    },
 
     /// Database errors (connection, queries, migrations)
-    #[error("database error: {0}")]
-    Database(String),
+    #[error("database error: {message}")]
+    Database {
+        /// What went wrong
+        message: String,
+        /// Underlying error, if any
+        #[source]
+        source: Option<BoxError>,
+    },
 
     /// Broker errors (definitions, scanning, parsing)
-    #[error("broker error: {0}")]
-    Broker(String),
+    #[error("broker error: {message}")]
+    Broker {
+        /// What went wrong
+        message: String,
+        /// Underlying error, if any
+        #[source]
+        source: Option<BoxError>,
+    },
 
     /// LLM errors (provider connection, completions, routing)
-    #[error("LLM error: {0}")]
-    Llm(String),
+    #[error("LLM error: {message}")]
+    Llm {
+        /// What went wrong
+        message: String,
+        /// Underlying error, if any
+        #[source]
+        source: Option<BoxError>,
+    },
 
     /// Browser automation errors (navigation, element not found)
-    #[error("browser error: {0}")]
-    Browser(String),
+    #[error("browser error: {message}")]
+    Browser {
+        /// What went wrong
+        message: String,
+        /// Underlying error, if any
+        #[source]
+        source: Option<BoxError>,
+    },
 
     /// Network errors (HTTP requests, DNS)
-    #[error("network error: {0}")]
-    Network(String),
+    #[error("network error: {message}")]
+    Network {
+        /// What went wrong
+        message: String,
+        /// Underlying error, if any
+        #[source]
+        source: Option<BoxError>,
+    },
 
     /// Permission errors (action not allowed)
     #[error("permission denied: {0}")]
@@ -47,13 +91,36 @@ pub enum SpectralError {
     #[error("validation error: {0}")]
     Validation(String),
 
+    /// Invalid identifier (profile or broker ID)
+    #[error(transparent)]
+    InvalidId(#[from] IdError),
+
     /// I/O errors
     #[error("I/O error: {0}")]
     Io(#[from] std::io::Error),
 
     /// Generic internal errors
-    #[error("internal error: {0}")]
-    Internal(String),
+    #[error("internal error: {message}")]
+    Internal {
+        /// What went wrong
+        message: String,
+        /// Underlying error, if any
+        #[source]
+        source: Option<BoxError>,
+    },
+}
+
+impl SpectralError {
+    /// The innermost error in the [`source`](std::error::Error::source)
+    /// chain, or `self` if nothing caused it.
+    #[must_use]
+    pub fn root_cause(&self) -> &(dyn std::error::Error + 'static) {
+        let mut cause: &(dyn std::error::Error + 'static) = self;
+        while let Some(next) = cause.source() {
+            cause = next;
+        }
+        cause
+    }
 }
 
 /// Configuration-specific errors.
@@ -82,6 +149,19 @@ pub enum ConfigError {
     #[error("I/O error: {0}")]
     Io(#[from] std::io::Error),
 
+    /// Failed to start watching the config file for changes
+    #[error("failed to watch config file: {0}")]
+    Watch(#[from] notify::Error),
+
+    /// Config file was written by a newer version of Spectral
+    #[error("config version {found} is newer than the supported version {supported}")]
+    UnsupportedVersion {
+        /// Version recorded in the file
+        found: u32,
+        /// Newest version this build understands
+        supported: u32,
+    },
+
     /// Invalid configuration value
     #[error("invalid config value for {field}: {reason}")]
     InvalidValue {
@@ -92,6 +172,70 @@ pub enum ConfigError {
     },
 }
 
+/// An identifier that failed validation.
+///
+/// Only the length of the rejected input is kept, never the input itself,
+/// since raw IDs can be sensitive.
+#[derive(Error, Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[error("invalid {id_type}: {}", .kind.describe(*.input_len))]
+pub struct IdError {
+    /// Which kind of identifier was rejected
+    pub id_type: IdType,
+    /// Why it was rejected
+    pub kind: IdErrorKind,
+    /// Length of the rejected input in bytes
+    pub input_len: usize,
+}
+
+/// Kinds of validated identifiers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum IdType {
+    /// A [`ProfileId`](crate::types::ProfileId)
+    Profile,
+    /// A [`BrokerId`](crate::types::BrokerId)
+    Broker,
+}
+
+impl fmt::Display for IdType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Profile => write!(f, "profile ID"),
+            Self::Broker => write!(f, "broker ID"),
+        }
+    }
+}
+
+/// Reasons an identifier can fail validation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum IdErrorKind {
+    /// The input was empty
+    Empty,
+    /// The input was shorter than the minimum length
+    TooShort,
+    /// The input was longer than the maximum length
+    TooLong,
+    /// The input contained a character the ID type does not allow
+    InvalidChar,
+    /// The characters were allowed but not arranged as the ID type requires
+    InvalidFormat,
+}
+
+impl IdErrorKind {
+    /// Human-readable reason, suitable for showing to the user.
+    #[must_use]
+    pub fn describe(self, input_len: usize) -> String {
+        match self {
+            Self::Empty => "must not be empty".to_string(),
+            Self::TooShort => format!("too short ({input_len} characters)"),
+            Self::TooLong => format!("too long ({input_len} characters)"),
+            Self::InvalidChar => "contains characters that are not allowed".to_string(),
+            Self::InvalidFormat => "not in the expected format".to_string(),
+        }
+    }
+}
+
 /// Result type alias using `SpectralError`.
 pub type Result<T> = std::result::Result<T, SpectralError>;
 
@@ -121,10 +265,77 @@ mod tests {
         assert!(matches!(spectral_err, SpectralError::Config(_)));
     }
 
+    #[test]
+    fn test_id_error_display_omits_input() {
+        let err = IdError {
+            id_type: IdType::Broker,
+            kind: IdErrorKind::TooLong,
+            input_len: 51,
+        };
+        assert_eq!(
+            err.to_string(),
+            "invalid broker ID: too long (51 characters)"
+        );
+
+        let spectral_err: SpectralError = err.into();
+        assert!(matches!(spectral_err, SpectralError::InvalidId(_)));
+        assert_eq!(
+            spectral_err.to_string(),
+            "invalid broker ID: too long (51 characters)"
+        );
+    }
+
     #[test]
     fn test_error_from_io() {
         let io_err = std::io::Error::new(std::io::ErrorKind::NotFound, "test");
         let spectral_err: SpectralError = io_err.into();
         assert!(matches!(spectral_err, SpectralError::Io(_)));
     }
+
+    #[test]
+    fn test_source_chain_reaches_root_cause() {
+        use std::error::Error as _;
+
+        let io_err = std::io::Error::new(std::io::ErrorKind::PermissionDenied, "disk says no");
+        let config_err = ConfigError::from(io_err);
+        let err = SpectralError::Database {
+            message: "failed to open vault database".to_string(),
+            source: Some(Box::new(SpectralError::from(config_err))),
+        };
+
+        assert_eq!(
+            err.to_string(),
+            "database error: failed to open vault database"
+        );
+
+        let chain: Vec<String> = std::iter::successors(err.source(), |e| (*e).source())
+            .map(ToString::to_string)
+            .collect();
+        assert_eq!(
+            chain,
+            [
+                "configuration error: I/O error: disk says no",
+                "I/O error: disk says no",
+                "disk says no",
+            ]
+        );
+
+        let root = err
+            .root_cause()
+            .downcast_ref::<std::io::Error>()
+            .expect("root cause is the I/O error");
+        assert_eq!(root.kind(), std::io::ErrorKind::PermissionDenied);
+    }
+
+    #[test]
+    fn test_root_cause_without_source_is_self() {
+        let err = SpectralError::Network {
+            message: "timed out".to_string(),
+            source: None,
+        };
+        assert_eq!(err.root_cause().to_string(), "network error: timed out");
+
+        let err = SpectralError::Validation("bad input".to_string());
+        assert_eq!(err.root_cause().to_string(), "validation error: bad input");
+    }
 }
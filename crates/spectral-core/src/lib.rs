@@ -8,7 +8,11 @@
 //! - [`error`] - Central error types using thiserror
 //! - [`config`] - TOML-based configuration with XDG paths
 //! - [`types`] - Shared newtypes and enums (`ProfileId`, `BrokerId`, `PiiField`, `Timestamp`)
+//! - [`country`] - Country-specific address conventions
 //! - [`capabilities`] - Feature capability registry for LLM-optional architecture
+//! - [`clock`] - Injectable time source, mockable in tests
+//! - [`metrics`] - In-memory operational counters and gauges
+//! - [`rng`] - Random number source, seedable in tests
 //!
 //! # Example
 //!
@@ -36,15 +40,27 @@
 #![allow(clippy::missing_panics_doc)]
 
 pub mod capabilities;
+pub mod clock;
 pub mod config;
+pub mod country;
 pub mod error;
+pub mod metrics;
+pub mod names;
+pub mod rng;
 pub mod types;
 
 // Re-export commonly used types
-pub use capabilities::{CapabilityRegistry, FeatureId};
+pub use capabilities::{CapabilityRegistry, FeatureId, FeatureStatus};
+pub use clock::{Clock, MockClock, SharedClock, SystemClock};
 pub use config::{
-    AppConfig, BrowserConfig, GeneralConfig, LlmConfig, NotificationConfig, ScanningConfig,
-    VaultConfig,
+    AppConfig, BrowserConfig, CaptchaConfig, ConfigWatcher, GeneralConfig, LlmConfig,
+    NotificationConfig, ScanDepth, ScanDisclosure, ScanTier, ScanningConfig, VaultConfig,
+    WatchOptions, CONFIG_VERSION,
 };
-pub use error::{ConfigError, ConfigResult, Result, SpectralError};
+pub use country::{normalize_country, AddressFormat, DEFAULT_COUNTRY};
+pub use error::{
+    BoxError, ConfigError, ConfigResult, IdError, IdErrorKind, IdType, Result, SpectralError,
+};
+pub use metrics::{Counter, Gauge, Metrics, MetricsSnapshot};
+pub use rng::RngSource;
 pub use types::{BrokerId, PiiField, ProfileId, Timestamp};
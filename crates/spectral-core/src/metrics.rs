@@ -0,0 +1,278 @@
+//! In-memory operational metrics.
+//!
+//! This module provides a lightweight registry of atomic counters and gauges
+//! for operational visibility (scans started, findings created, removals
+//! submitted, LLM calls). Metrics are process-local, never persisted, and
+//! carry no PII — only aggregate numbers keyed by a fixed set of names.
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::sync::atomic::{AtomicI64, AtomicU64, Ordering};
+use std::sync::OnceLock;
+
+use crate::types::Timestamp;
+
+/// Monotonically increasing counters.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Counter {
+    /// Scan jobs started
+    ScansStarted,
+    /// Scan jobs that completed
+    ScansCompleted,
+    /// Scan jobs that failed
+    ScansFailed,
+    /// Findings created from broker results
+    FindingsCreated,
+    /// Removal requests submitted to a broker
+    RemovalsSubmitted,
+    /// Removal requests blocked by a CAPTCHA
+    RemovalsCaptchaRequired,
+    /// Removal requests that failed
+    RemovalsFailed,
+    /// Removal requests left for the user to finish, e.g. ID verification
+    RemovalsNeedsUserAction,
+    /// LLM completion calls made
+    LlmCalls,
+    /// LLM completion calls that returned an error
+    LlmErrors,
+}
+
+impl Counter {
+    /// All counters, in declaration order.
+    pub const ALL: [Counter; 10] = [
+        Self::ScansStarted,
+        Self::ScansCompleted,
+        Self::ScansFailed,
+        Self::FindingsCreated,
+        Self::RemovalsSubmitted,
+        Self::RemovalsCaptchaRequired,
+        Self::RemovalsFailed,
+        Self::RemovalsNeedsUserAction,
+        Self::LlmCalls,
+        Self::LlmErrors,
+    ];
+
+    fn index(self) -> usize {
+        self as usize
+    }
+}
+
+/// Point-in-time gauges that can move up and down.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Gauge {
+    /// Scan jobs currently running
+    ActiveScans,
+    /// Removal attempts currently being processed by workers
+    ActiveRemovals,
+}
+
+impl Gauge {
+    /// All gauges, in declaration order.
+    pub const ALL: [Gauge; 2] = [Self::ActiveScans, Self::ActiveRemovals];
+
+    fn index(self) -> usize {
+        self as usize
+    }
+}
+
+/// Registry of atomic counters and gauges.
+///
+/// All operations are lock-free and safe to call from any thread or task.
+#[derive(Debug)]
+pub struct Metrics {
+    counters: [AtomicU64; Counter::ALL.len()],
+    gauges: [AtomicI64; Gauge::ALL.len()],
+}
+
+impl Default for Metrics {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Metrics {
+    /// Create a new registry with every metric at zero.
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            counters: std::array::from_fn(|_| AtomicU64::new(0)),
+            gauges: std::array::from_fn(|_| AtomicI64::new(0)),
+        }
+    }
+
+    /// Increment a counter by one.
+    pub fn increment(&self, counter: Counter) {
+        self.add(counter, 1);
+    }
+
+    /// Increment a counter by `value`.
+    pub fn add(&self, counter: Counter, value: u64) {
+        self.counters[counter.index()].fetch_add(value, Ordering::Relaxed);
+    }
+
+    /// Record the current value of a gauge.
+    pub fn observe(&self, gauge: Gauge, value: i64) {
+        self.gauges[gauge.index()].store(value, Ordering::Relaxed);
+    }
+
+    /// Move a gauge up or down by `delta`.
+    pub fn adjust(&self, gauge: Gauge, delta: i64) {
+        self.gauges[gauge.index()].fetch_add(delta, Ordering::Relaxed);
+    }
+
+    /// Read the current value of a counter.
+    #[must_use]
+    pub fn counter(&self, counter: Counter) -> u64 {
+        self.counters[counter.index()].load(Ordering::Relaxed)
+    }
+
+    /// Read the current value of a gauge.
+    #[must_use]
+    pub fn gauge(&self, gauge: Gauge) -> i64 {
+        self.gauges[gauge.index()].load(Ordering::Relaxed)
+    }
+
+    /// Capture the current value of every metric.
+    #[must_use]
+    pub fn snapshot(&self) -> MetricsSnapshot {
+        MetricsSnapshot {
+            counters: Counter::ALL
+                .iter()
+                .map(|c| (*c, self.counter(*c)))
+                .collect(),
+            gauges: Gauge::ALL.iter().map(|g| (*g, self.gauge(*g))).collect(),
+            captured_at: Timestamp::now(),
+        }
+    }
+
+    /// Reset every metric to zero.
+    pub fn reset(&self) {
+        for counter in &self.counters {
+            counter.store(0, Ordering::Relaxed);
+        }
+        for gauge in &self.gauges {
+            gauge.store(0, Ordering::Relaxed);
+        }
+    }
+}
+
+/// Point-in-time copy of all metric values.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MetricsSnapshot {
+    /// Counter values keyed by counter name
+    pub counters: BTreeMap<Counter, u64>,
+    /// Gauge values keyed by gauge name
+    pub gauges: BTreeMap<Gauge, i64>,
+    /// When the snapshot was taken
+    pub captured_at: Timestamp,
+}
+
+impl MetricsSnapshot {
+    /// Get a counter value from the snapshot.
+    #[must_use]
+    pub fn counter(&self, counter: Counter) -> u64 {
+        self.counters.get(&counter).copied().unwrap_or(0)
+    }
+
+    /// Get a gauge value from the snapshot.
+    #[must_use]
+    pub fn gauge(&self, gauge: Gauge) -> i64 {
+        self.gauges.get(&gauge).copied().unwrap_or(0)
+    }
+}
+
+/// Process-wide metrics registry.
+///
+/// Subsystems record into this registry; the diagnostics screen reads from it.
+#[must_use]
+pub fn global() -> &'static Metrics {
+    static GLOBAL: OnceLock<Metrics> = OnceLock::new();
+    GLOBAL.get_or_init(Metrics::new)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+
+    #[test]
+    fn test_new_registry_is_zeroed() {
+        let metrics = Metrics::new();
+        let snapshot = metrics.snapshot();
+
+        assert_eq!(snapshot.counters.len(), Counter::ALL.len());
+        assert_eq!(snapshot.gauges.len(), Gauge::ALL.len());
+        assert!(snapshot.counters.values().all(|v| *v == 0));
+        assert!(snapshot.gauges.values().all(|v| *v == 0));
+    }
+
+    #[test]
+    fn test_increment_and_add() {
+        let metrics = Metrics::new();
+        metrics.increment(Counter::ScansStarted);
+        metrics.increment(Counter::ScansStarted);
+        metrics.add(Counter::FindingsCreated, 7);
+
+        assert_eq!(metrics.counter(Counter::ScansStarted), 2);
+        assert_eq!(metrics.counter(Counter::FindingsCreated), 7);
+        assert_eq!(metrics.counter(Counter::LlmCalls), 0);
+    }
+
+    #[test]
+    fn test_gauges() {
+        let metrics = Metrics::new();
+        metrics.observe(Gauge::ActiveScans, 4);
+        metrics.adjust(Gauge::ActiveScans, -1);
+
+        assert_eq!(metrics.gauge(Gauge::ActiveScans), 3);
+    }
+
+    #[test]
+    fn test_concurrent_increments() {
+        let metrics = Arc::new(Metrics::new());
+        let handles: Vec<_> = (0..8)
+            .map(|_| {
+                let metrics = Arc::clone(&metrics);
+                std::thread::spawn(move || {
+                    for _ in 0..1000 {
+                        metrics.increment(Counter::RemovalsSubmitted);
+                        metrics.adjust(Gauge::ActiveRemovals, 1);
+                    }
+                    metrics.add(Counter::FindingsCreated, 5);
+                })
+            })
+            .collect();
+
+        for handle in handles {
+            handle.join().expect("thread panicked");
+        }
+
+        let snapshot = metrics.snapshot();
+        assert_eq!(snapshot.counter(Counter::RemovalsSubmitted), 8000);
+        assert_eq!(snapshot.counter(Counter::FindingsCreated), 40);
+        assert_eq!(snapshot.gauge(Gauge::ActiveRemovals), 8000);
+    }
+
+    #[test]
+    fn test_reset() {
+        let metrics = Metrics::new();
+        metrics.increment(Counter::LlmCalls);
+        metrics.observe(Gauge::ActiveScans, 2);
+        metrics.reset();
+
+        assert_eq!(metrics.counter(Counter::LlmCalls), 0);
+        assert_eq!(metrics.gauge(Gauge::ActiveScans), 0);
+    }
+
+    #[test]
+    fn test_snapshot_serializes_with_snake_case_keys() {
+        let metrics = Metrics::new();
+        metrics.increment(Counter::ScansCompleted);
+
+        let json = serde_json::to_value(metrics.snapshot()).expect("serialize");
+        assert_eq!(json["counters"]["scans_completed"], 1);
+        assert_eq!(json["gauges"]["active_scans"], 0);
+    }
+}
@@ -0,0 +1,95 @@
+//! Given names and the nicknames people are listed under.
+//!
+//! Brokers list people as "Bob" as often as "Robert". Scans search brokers
+//! for a profile's nicknames, and findings are grouped by person treating a
+//! nickname as the same first name.
+
+/// Common English given names and their nicknames, lowercase.
+const NICKNAMES: &[(&str, &[&str])] = &[
+    ("alexander", &["alex"]),
+    ("andrew", &["andy", "drew"]),
+    ("anthony", &["tony"]),
+    ("barbara", &["barb"]),
+    ("benjamin", &["ben"]),
+    ("catherine", &["cathy", "kate"]),
+    ("charles", &["charlie", "chuck"]),
+    ("christopher", &["chris"]),
+    ("daniel", &["dan", "danny"]),
+    ("deborah", &["debbie", "deb"]),
+    ("edward", &["ed", "eddie"]),
+    ("elizabeth", &["liz", "beth", "betty"]),
+    ("james", &["jim", "jimmy"]),
+    ("jennifer", &["jen", "jenny"]),
+    ("jessica", &["jess"]),
+    ("john", &["jack", "johnny"]),
+    ("jonathan", &["jon"]),
+    ("joseph", &["joe"]),
+    ("katherine", &["kate", "kathy"]),
+    ("kenneth", &["ken"]),
+    ("margaret", &["maggie", "peggy"]),
+    ("matthew", &["matt"]),
+    ("michael", &["mike"]),
+    ("nicholas", &["nick"]),
+    ("patricia", &["pat", "patty"]),
+    ("rebecca", &["becky"]),
+    ("richard", &["rick", "dick"]),
+    ("robert", &["bob", "rob", "bobby"]),
+    ("samuel", &["sam"]),
+    ("stephen", &["steve"]),
+    ("steven", &["steve"]),
+    ("susan", &["sue"]),
+    ("thomas", &["tom"]),
+    ("timothy", &["tim"]),
+    ("victoria", &["vicky"]),
+    ("william", &["bill", "will", "billy"]),
+];
+
+/// Nicknames of `first_name`, or the given names it is a nickname of,
+/// lowercase.
+#[must_use]
+pub fn nicknames_for(first_name: &str) -> Vec<&'static str> {
+    let name = first_name.trim().to_lowercase();
+    let mut names = Vec::new();
+    for (given, nicknames) in NICKNAMES {
+        if *given == name {
+            names.extend(nicknames.iter().copied());
+        } else if nicknames.contains(&name.as_str()) {
+            names.push(*given);
+        }
+    }
+    names
+}
+
+/// Whether two first names can belong to the same person: the same name
+/// ignoring case, or one a nickname of the other.
+#[must_use]
+pub fn is_same_first_name(a: &str, b: &str) -> bool {
+    let (a, b) = (a.trim(), b.trim());
+    a.eq_ignore_ascii_case(b)
+        || nicknames_for(a)
+            .iter()
+            .any(|name| name.eq_ignore_ascii_case(b))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_nicknames_for() {
+        assert_eq!(nicknames_for("William"), ["bill", "will", "billy"]);
+        assert_eq!(nicknames_for("bob"), ["robert"]);
+        assert!(nicknames_for("Zebulon").is_empty());
+    }
+
+    #[test]
+    fn test_is_same_first_name() {
+        assert!(is_same_first_name("John", "john"));
+        assert!(is_same_first_name("Robert", "Bob"));
+        assert!(is_same_first_name("bob", "Robert"));
+
+        assert!(!is_same_first_name("Mark", "Mary"));
+        assert!(!is_same_first_name("Dan", "Don"));
+        assert!(!is_same_first_name("Jim", "Tim"));
+    }
+}
@@ -0,0 +1,119 @@
+//! Random number source that tests can make deterministic.
+//!
+//! Salts, nonces and retry jitter draw from an [`RngSource`]. Production code
+//! uses [`RngSource::os`], which reads the operating system's CSPRNG. With
+//! the `test-util` feature, [`RngSource::seeded`] gives a reproducible
+//! source so tests can pin outcomes. The seeded constructor does not exist in
+//! normal builds, so a release binary cannot end up with predictable salts
+//! or nonces.
+
+use rand::rngs::OsRng;
+use rand::{Rng, RngCore};
+use std::ops::RangeInclusive;
+
+#[cfg(any(test, feature = "test-util"))]
+use rand::{rngs::StdRng, SeedableRng};
+#[cfg(any(test, feature = "test-util"))]
+use std::sync::Mutex;
+
+/// Source of random bytes and numbers.
+#[derive(Debug, Default)]
+pub struct RngSource {
+    inner: Inner,
+}
+
+#[derive(Debug, Default)]
+enum Inner {
+    /// Operating system CSPRNG
+    #[default]
+    Os,
+    /// Deterministic generator for tests
+    #[cfg(any(test, feature = "test-util"))]
+    Seeded(Box<Mutex<StdRng>>),
+}
+
+impl RngSource {
+    /// Source backed by the operating system's CSPRNG.
+    #[must_use]
+    pub fn os() -> Self {
+        Self { inner: Inner::Os }
+    }
+
+    /// Deterministic source for tests; the same seed yields the same values.
+    #[cfg(any(test, feature = "test-util"))]
+    #[must_use]
+    pub fn seeded(seed: u64) -> Self {
+        Self {
+            inner: Inner::Seeded(Box::new(Mutex::new(StdRng::seed_from_u64(seed)))),
+        }
+    }
+
+    /// Whether values come from a test seed rather than the OS CSPRNG.
+    #[must_use]
+    pub fn is_seeded(&self) -> bool {
+        !matches!(self.inner, Inner::Os)
+    }
+
+    /// Fill `dest` with random bytes.
+    pub fn fill_bytes(&self, dest: &mut [u8]) {
+        match &self.inner {
+            Inner::Os => OsRng.fill_bytes(dest),
+            #[cfg(any(test, feature = "test-util"))]
+            Inner::Seeded(rng) => rng.lock().expect("rng lock poisoned").fill_bytes(dest),
+        }
+    }
+
+    /// Uniform sample from `range`.
+    ///
+    /// # Panics
+    /// Panics if `range` is empty.
+    #[must_use]
+    pub fn gen_range(&self, range: RangeInclusive<u64>) -> u64 {
+        match &self.inner {
+            Inner::Os => OsRng.gen_range(range),
+            #[cfg(any(test, feature = "test-util"))]
+            Inner::Seeded(rng) => rng.lock().expect("rng lock poisoned").gen_range(range),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bytes(rng: &RngSource) -> [u8; 32] {
+        let mut buf = [0u8; 32];
+        rng.fill_bytes(&mut buf);
+        buf
+    }
+
+    #[test]
+    fn test_seeded_sources_are_reproducible() {
+        let first = RngSource::seeded(42);
+        let second = RngSource::seeded(42);
+
+        assert_eq!(bytes(&first), bytes(&second));
+        assert_eq!(first.gen_range(0..=1000), second.gen_range(0..=1000));
+        assert_ne!(bytes(&RngSource::seeded(42)), bytes(&RngSource::seeded(43)));
+    }
+
+    #[test]
+    fn test_default_source_is_os_csprng() {
+        let rng = RngSource::default();
+
+        assert!(!rng.is_seeded());
+        assert!(RngSource::seeded(1).is_seeded());
+        assert_ne!(bytes(&rng), bytes(&rng));
+    }
+
+    #[test]
+    fn test_gen_range_is_bounded() {
+        for rng in [RngSource::os(), RngSource::seeded(7)] {
+            for _ in 0..100 {
+                let value = rng.gen_range(10..=20);
+                assert!((10..=20).contains(&value));
+            }
+            assert_eq!(rng.gen_range(5..=5), 5);
+        }
+    }
+}
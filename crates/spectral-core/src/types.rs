@@ -3,34 +3,35 @@
 //! This module defines common newtypes and enums that provide type safety
 //! and clear domain modeling.
 
-use crate::error::SpectralError;
+use crate::error::{IdError, IdErrorKind, IdType, SpectralError};
 use chrono::{DateTime, Utc};
 use regex::Regex;
 use serde::{Deserialize, Serialize};
 use std::fmt;
-use std::sync::OnceLock;
+use std::sync::{Arc, OnceLock};
 
 /// Newtype for profile identifiers with validation.
 ///
-/// Profile IDs must be valid UUIDs (v4 format).
+/// Profile IDs must be valid UUIDs (v4 format). The ID is shared, so a clone
+/// bumps a reference count instead of copying the string.
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
-pub struct ProfileId(String);
+pub struct ProfileId(Arc<str>);
 
 impl ProfileId {
     /// Create a new `ProfileId` from a string.
     ///
     /// # Errors
-    /// Returns error if the ID is not a valid UUID v4.
-    pub fn new(id: impl Into<String>) -> Result<Self, SpectralError> {
+    /// Returns `IdError` if the ID is not a valid UUID v4.
+    pub fn new(id: impl Into<String>) -> Result<Self, IdError> {
         let id = id.into();
         Self::validate(&id)?;
-        Ok(Self(id))
+        Ok(Self(id.into()))
     }
 
     /// Create a new random `ProfileId` using UUID v4.
     #[must_use]
     pub fn generate() -> Self {
-        Self(uuid::Uuid::new_v4().to_string())
+        Self(uuid::Uuid::new_v4().to_string().into())
     }
 
     /// Get the inner string value.
@@ -39,21 +40,34 @@ impl ProfileId {
         &self.0
     }
 
-    /// Validate that a string is a valid UUID v4.
-    fn validate(id: &str) -> Result<(), SpectralError> {
+    /// Validate that a string is a valid lowercase UUID v4.
+    fn validate(id: &str) -> Result<(), IdError> {
+        const UUID_LEN: usize = 36;
         static UUID_REGEX: OnceLock<Regex> = OnceLock::new();
         let regex = UUID_REGEX.get_or_init(|| {
             Regex::new(r"^[0-9a-f]{8}-[0-9a-f]{4}-4[0-9a-f]{3}-[89ab][0-9a-f]{3}-[0-9a-f]{12}$")
                 .expect("valid regex")
         });
 
-        if regex.is_match(id) {
-            Ok(())
+        let kind = if id.is_empty() {
+            IdErrorKind::Empty
+        } else if id.len() > UUID_LEN {
+            IdErrorKind::TooLong
+        } else if !id.chars().all(|c| matches!(c, '0'..='9' | 'a'..='f' | '-')) {
+            IdErrorKind::InvalidChar
+        } else if id.len() < UUID_LEN {
+            IdErrorKind::TooShort
+        } else if !regex.is_match(id) {
+            IdErrorKind::InvalidFormat
         } else {
-            Err(SpectralError::Validation(format!(
-                "invalid profile ID: must be a valid UUID v4, got '{id}'"
-            )))
-        }
+            return Ok(());
+        };
+
+        Err(IdError {
+            id_type: IdType::Profile,
+            kind,
+            input_len: id.len(),
+        })
     }
 }
 
@@ -66,18 +80,19 @@ impl fmt::Display for ProfileId {
 /// Newtype for broker identifiers with validation.
 ///
 /// Broker IDs must be lowercase alphanumeric with hyphens, 3-50 characters.
+/// Like [`ProfileId`], clones share the string.
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
-pub struct BrokerId(String);
+pub struct BrokerId(Arc<str>);
 
 impl BrokerId {
     /// Create a new `BrokerId` from a string.
     ///
     /// # Errors
-    /// Returns error if the ID doesn't match the required format.
-    pub fn new(id: impl Into<String>) -> Result<Self, SpectralError> {
+    /// Returns `IdError` if the ID doesn't match the required format.
+    pub fn new(id: impl Into<String>) -> Result<Self, IdError> {
         let id = id.into();
         Self::validate(&id)?;
-        Ok(Self(id))
+        Ok(Self(id.into()))
     }
 
     /// Get the inner string value.
@@ -86,26 +101,31 @@ impl BrokerId {
         &self.0
     }
 
-    /// Validate broker ID format: lowercase alphanumeric with hyphens, 3-50 chars.
-    fn validate(id: &str) -> Result<(), SpectralError> {
-        static BROKER_REGEX: OnceLock<Regex> = OnceLock::new();
-        let regex = BROKER_REGEX
-            .get_or_init(|| Regex::new(r"^[a-z0-9][a-z0-9-]{1,48}[a-z0-9]$").expect("valid regex"));
-
-        if id.len() < 3 || id.len() > 50 {
-            return Err(SpectralError::Validation(format!(
-                "invalid broker ID: must be 3-50 characters, got {} characters",
-                id.len()
-            )));
-        }
-
-        if regex.is_match(id) {
-            Ok(())
+    /// Validate broker ID format: lowercase alphanumeric with hyphens, 3-50 chars,
+    /// not starting or ending with a hyphen.
+    fn validate(id: &str) -> Result<(), IdError> {
+        let kind = if id.is_empty() {
+            IdErrorKind::Empty
+        } else if id.len() > 50 {
+            IdErrorKind::TooLong
+        } else if !id
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-')
+        {
+            IdErrorKind::InvalidChar
+        } else if id.len() < 3 {
+            IdErrorKind::TooShort
+        } else if id.starts_with('-') || id.ends_with('-') {
+            IdErrorKind::InvalidFormat
         } else {
-            Err(SpectralError::Validation(format!(
-                "invalid broker ID: must be lowercase alphanumeric with hyphens, got '{id}'"
-            )))
-        }
+            return Ok(());
+        };
+
+        Err(IdError {
+            id_type: IdType::Broker,
+            kind,
+            input_len: id.len(),
+        })
     }
 }
 
@@ -171,6 +191,64 @@ pub enum PiiField {
 }
 
 impl PiiField {
+    /// Every PII field, in declaration order.
+    ///
+    /// Iterate this instead of listing variants by hand so new fields are
+    /// picked up everywhere.
+    #[must_use]
+    pub fn all() -> &'static [PiiField] {
+        &[
+            Self::FullName,
+            Self::FirstName,
+            Self::MiddleName,
+            Self::LastName,
+            Self::Email,
+            Self::Phone,
+            Self::Address,
+            Self::City,
+            Self::State,
+            Self::ZipCode,
+            Self::Country,
+            Self::DateOfBirth,
+            Self::Age,
+            Self::Ssn,
+            Self::Employer,
+            Self::JobTitle,
+            Self::Education,
+            Self::SocialMedia,
+            Self::IpAddress,
+            Self::Photo,
+            Self::Relatives,
+            Self::PreviousAddress,
+            Self::Other,
+        ]
+    }
+
+    /// Label for the field in forms and lists; same as [`Self::display_name`].
+    #[must_use]
+    pub fn display_label(&self) -> &'static str {
+        self.display_name()
+    }
+
+    /// Whether the field is highly sensitive (SSN and date of birth).
+    ///
+    /// Highly sensitive fields warrant extra confirmation before they are
+    /// shared with a broker or an external service.
+    #[must_use]
+    pub fn is_sensitive(&self) -> bool {
+        self.sensitivity_level() >= 3
+    }
+
+    /// Whether the field may only be shared once the user has opted in
+    /// (SSN and photo).
+    ///
+    /// Unlike a name or address, these are never needed to find or remove a
+    /// listing, so no permission covers them by default.
+    #[must_use]
+    pub fn requires_opt_in(&self) -> bool {
+        matches!(self, Self::Ssn | Self::Photo)
+    }
+
     /// Get a human-readable display name for the PII field.
     #[must_use]
     pub fn display_name(&self) -> &'static str {
@@ -292,6 +370,39 @@ impl From<Timestamp> for DateTime<Utc> {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_pii_field_all_is_complete() {
+        let all = PiiField::all();
+        assert_eq!(all.len(), 23);
+
+        let unique: std::collections::HashSet<_> = all.iter().collect();
+        assert_eq!(unique.len(), all.len());
+
+        for field in all {
+            assert!(!field.display_label().is_empty(), "{field:?}");
+        }
+    }
+
+    #[test]
+    fn test_pii_field_is_sensitive() {
+        let sensitive: Vec<_> = PiiField::all()
+            .iter()
+            .copied()
+            .filter(PiiField::is_sensitive)
+            .collect();
+        assert_eq!(sensitive, vec![PiiField::DateOfBirth, PiiField::Ssn]);
+    }
+
+    #[test]
+    fn test_pii_field_requires_opt_in() {
+        let opt_in: Vec<_> = PiiField::all()
+            .iter()
+            .copied()
+            .filter(PiiField::requires_opt_in)
+            .collect();
+        assert_eq!(opt_in, vec![PiiField::Ssn, PiiField::Photo]);
+    }
+
     #[test]
     fn test_profile_id_valid() {
         let id = "550e8400-e29b-41d4-a716-446655440000";
@@ -313,6 +424,45 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_profile_id_error_kinds() {
+        let cases = [
+            ("", IdErrorKind::Empty),
+            ("550e8400-e29b", IdErrorKind::TooShort),
+            (
+                "550e8400-e29b-41d4-a716-446655440000-00",
+                IdErrorKind::TooLong,
+            ),
+            (
+                "550e8400-e29b-41d4-x716-446655440000",
+                IdErrorKind::InvalidChar,
+            ),
+            (
+                "550E8400-E29B-41D4-A716-446655440000",
+                IdErrorKind::InvalidChar,
+            ),
+            (
+                "550e8400-e29b-51d4-a716-446655440000",
+                IdErrorKind::InvalidFormat,
+            ),
+        ];
+
+        for (id, kind) in cases {
+            let err = ProfileId::new(id).expect_err(id);
+            assert_eq!(err.id_type, IdType::Profile);
+            assert_eq!(err.kind, kind, "{id}");
+            assert_eq!(err.input_len, id.len());
+        }
+    }
+
+    #[test]
+    fn test_profile_id_error_does_not_echo_input() {
+        let id = "jane.doe@example.com";
+        let err = ProfileId::new(id).expect_err("email is not a profile ID");
+        assert!(!err.to_string().contains(id));
+        assert!(!format!("{err:?}").contains(id));
+    }
+
     #[test]
     fn test_profile_id_generate() {
         let id1 = ProfileId::generate();
@@ -353,6 +503,70 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_broker_id_error_kinds() {
+        let too_long = "a".repeat(51);
+        let cases = [
+            ("", IdErrorKind::Empty),
+            ("ab", IdErrorKind::TooShort),
+            (too_long.as_str(), IdErrorKind::TooLong),
+            ("Spokeo", IdErrorKind::InvalidChar),
+            ("been_verified", IdErrorKind::InvalidChar),
+            ("AB", IdErrorKind::InvalidChar),
+            ("-spokeo", IdErrorKind::InvalidFormat),
+            ("spokeo-", IdErrorKind::InvalidFormat),
+        ];
+
+        for (id, kind) in cases {
+            let err = BrokerId::new(id).expect_err(id);
+            assert_eq!(err.id_type, IdType::Broker);
+            assert_eq!(err.kind, kind, "{id}");
+            assert_eq!(err.input_len, id.len());
+        }
+    }
+
+    #[test]
+    fn test_ids_compare_and_hash_by_value() {
+        use std::collections::HashSet;
+
+        let a = BrokerId::new("spokeo").expect("valid broker ID");
+        let b = BrokerId::new(String::from("spokeo")).expect("valid broker ID");
+        assert_eq!(a, b);
+        assert_ne!(a, BrokerId::new("radaris").expect("valid broker ID"));
+        let set: HashSet<_> = [a.clone(), b].into_iter().collect();
+        assert_eq!(set.len(), 1);
+        assert!(set.contains(&a));
+
+        // Serialized as a bare string, as before
+        let json = serde_json::to_string(&a).expect("serialize");
+        assert_eq!(json, "\"spokeo\"");
+        let parsed: BrokerId = serde_json::from_str(&json).expect("deserialize");
+        assert_eq!(parsed, a);
+
+        let profile_id = ProfileId::generate();
+        let json = serde_json::to_string(&profile_id).expect("serialize");
+        let parsed: ProfileId = serde_json::from_str(&json).expect("deserialize");
+        assert_eq!(parsed, profile_id);
+        assert_eq!(format!("{profile_id:?}"), format!("ProfileId({json})"));
+    }
+
+    #[test]
+    fn test_id_clone_shares_string() {
+        let broker_id = BrokerId::new("spokeo").expect("valid broker ID");
+        let clones: Vec<BrokerId> = (0..100).map(|_| broker_id.clone()).collect();
+        assert_eq!(Arc::strong_count(&broker_id.0), 101);
+        assert!(clones
+            .iter()
+            .all(|clone| std::ptr::eq(clone.as_str(), broker_id.as_str())));
+        drop(clones);
+        assert_eq!(Arc::strong_count(&broker_id.0), 1);
+
+        let profile_id = ProfileId::generate();
+        let clone = profile_id.clone();
+        assert!(std::ptr::eq(clone.as_str(), profile_id.as_str()));
+        assert_eq!(Arc::strong_count(&profile_id.0), 2);
+    }
+
     #[test]
     fn test_pii_field_display() {
         assert_eq!(PiiField::FullName.to_string(), "Full Name");
@@ -0,0 +1,7 @@
+-- Run conditions for scheduled jobs: quiet hours (local "HH:MM" times) and
+-- metered-connection skipping. deferred_reason records why the current
+-- next_run_at was pushed back, and is cleared when the job runs.
+ALTER TABLE scheduled_jobs ADD COLUMN quiet_hours_start TEXT;
+ALTER TABLE scheduled_jobs ADD COLUMN quiet_hours_end TEXT;
+ALTER TABLE scheduled_jobs ADD COLUMN skip_on_metered INTEGER NOT NULL DEFAULT 0;
+ALTER TABLE scheduled_jobs ADD COLUMN deferred_reason TEXT;
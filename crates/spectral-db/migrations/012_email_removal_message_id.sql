@@ -0,0 +1,5 @@
+-- Message-ID of opt-out emails sent over SMTP, used to match bounce reports
+-- back to the removal attempt. NULL for emails handed off via mailto.
+ALTER TABLE email_removals ADD COLUMN message_id TEXT;
+
+CREATE INDEX IF NOT EXISTS idx_email_removals_recipient ON email_removals(recipient);
@@ -0,0 +1,32 @@
+-- Allow the NeedsUserAction status for removals the user must finish
+-- themselves, such as brokers that require a government ID upload.
+-- SQLite cannot alter a CHECK constraint, so the table is rebuilt under the
+-- same name. removal_evidence and email_removals rows point at attempt ids
+-- that briefly do not exist, so foreign key checks wait until commit.
+PRAGMA defer_foreign_keys = ON;
+
+CREATE TABLE removal_attempts_old AS SELECT * FROM removal_attempts;
+DROP TABLE removal_attempts;
+
+CREATE TABLE removal_attempts (
+    id TEXT PRIMARY KEY,
+    finding_id TEXT NOT NULL,
+    broker_id TEXT NOT NULL,
+    status TEXT NOT NULL CHECK(status IN ('Pending', 'Submitted', 'Completed', 'Failed', 'NeedsUserAction')),
+    created_at TEXT NOT NULL,
+    submitted_at TEXT,
+    completed_at TEXT,
+    error_message TEXT,
+    FOREIGN KEY (finding_id) REFERENCES findings(id) ON DELETE CASCADE
+);
+
+INSERT INTO removal_attempts
+    (id, finding_id, broker_id, status, created_at, submitted_at, completed_at, error_message)
+SELECT id, finding_id, broker_id, status, created_at, submitted_at, completed_at, error_message
+FROM removal_attempts_old;
+
+DROP TABLE removal_attempts_old;
+
+CREATE INDEX idx_removal_attempts_finding ON removal_attempts(finding_id);
+CREATE INDEX idx_removal_attempts_status ON removal_attempts(status);
+CREATE INDEX idx_removal_attempts_created_at ON removal_attempts(created_at DESC);
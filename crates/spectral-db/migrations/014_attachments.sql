@@ -0,0 +1,16 @@
+-- Migration: Add attachments table for encrypted documents kept in the vault
+--
+-- Stores files such as ID documents or removal confirmations. The label and
+-- contents are encrypted with the vault key (nonce || ciphertext); only the
+-- MIME type, size and creation time are stored in the clear.
+
+CREATE TABLE IF NOT EXISTS attachments (
+    id TEXT PRIMARY KEY NOT NULL,
+    mime_type TEXT NOT NULL,
+    label BLOB NOT NULL,
+    data BLOB NOT NULL,
+    size_bytes INTEGER NOT NULL,
+    created_at TEXT NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_attachments_created_at ON attachments (created_at);
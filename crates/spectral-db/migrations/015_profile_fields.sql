@@ -0,0 +1,16 @@
+-- Migration: Add per-field profile storage
+--
+-- storage_version 1 keeps the whole profile in profiles.data as one encrypted
+-- blob. Version 2 leaves profiles.data empty and stores each profile field as
+-- its own encrypted row in profile_fields, so a corrupt row loses only that
+-- field and single fields can be decrypted on their own.
+
+ALTER TABLE profiles ADD COLUMN storage_version INTEGER NOT NULL DEFAULT 1;
+
+CREATE TABLE IF NOT EXISTS profile_fields (
+    profile_id TEXT NOT NULL REFERENCES profiles(id) ON DELETE CASCADE,
+    field TEXT NOT NULL,           -- Profile field name (e.g., "first_name")
+    data BLOB NOT NULL,            -- Encrypted field value (ChaCha20-Poly1305)
+    nonce BLOB NOT NULL,           -- 12-byte nonce for AEAD encryption
+    PRIMARY KEY (profile_id, field)
+);
@@ -0,0 +1,5 @@
+-- Review priority for pending findings. match_confidence is how closely the
+-- listing matched the profile (0.0 to 1.0), NULL when it was not scored.
+-- broker_weight is the risk weight of the broker's category.
+ALTER TABLE findings ADD COLUMN match_confidence REAL;
+ALTER TABLE findings ADD COLUMN broker_weight REAL NOT NULL DEFAULT 1.0;
@@ -0,0 +1,37 @@
+-- Allow the Cancelled status for removals the user withdrew, and move
+-- CAPTCHA-blocked attempts out of Pending. Those were kept Pending with a
+-- CAPTCHA_REQUIRED error message; they now wait in NeedsUserAction.
+-- The table is rebuilt as in 013 because SQLite cannot alter a CHECK
+-- constraint, with foreign key checks deferred until commit.
+PRAGMA defer_foreign_keys = ON;
+
+CREATE TABLE removal_attempts_old AS SELECT * FROM removal_attempts;
+DROP TABLE removal_attempts;
+
+CREATE TABLE removal_attempts (
+    id TEXT PRIMARY KEY,
+    finding_id TEXT NOT NULL,
+    broker_id TEXT NOT NULL,
+    status TEXT NOT NULL CHECK(status IN ('Pending', 'Submitted', 'Completed', 'Failed', 'NeedsUserAction', 'Cancelled')),
+    created_at TEXT NOT NULL,
+    submitted_at TEXT,
+    completed_at TEXT,
+    error_message TEXT,
+    FOREIGN KEY (finding_id) REFERENCES findings(id) ON DELETE CASCADE
+);
+
+INSERT INTO removal_attempts
+    (id, finding_id, broker_id, status, created_at, submitted_at, completed_at, error_message)
+SELECT id, finding_id, broker_id,
+       CASE
+           WHEN status = 'Pending' AND error_message LIKE 'CAPTCHA_REQUIRED%' THEN 'NeedsUserAction'
+           ELSE status
+       END,
+       created_at, submitted_at, completed_at, error_message
+FROM removal_attempts_old;
+
+DROP TABLE removal_attempts_old;
+
+CREATE INDEX idx_removal_attempts_finding ON removal_attempts(finding_id);
+CREATE INDEX idx_removal_attempts_status ON removal_attempts(status);
+CREATE INDEX idx_removal_attempts_created_at ON removal_attempts(created_at DESC);
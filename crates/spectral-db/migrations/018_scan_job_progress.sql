@@ -0,0 +1,6 @@
+-- Heartbeat for running scan jobs. The scan task bumps last_progress_at as
+-- brokers finish, so a job that stops making progress (its task panicked, or
+-- the app crashed mid-scan) can be told apart from one that is just slow.
+ALTER TABLE scan_jobs ADD COLUMN last_progress_at TEXT;
+
+UPDATE scan_jobs SET last_progress_at = started_at;
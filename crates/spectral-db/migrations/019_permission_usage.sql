@@ -0,0 +1,10 @@
+-- How often each granted permission has been used, per vault, so the
+-- transparency view can show usage that survives a restart. Written in
+-- batches by the permission manager's usage sink.
+CREATE TABLE IF NOT EXISTS permission_usage (
+    vault_id TEXT NOT NULL,
+    permission TEXT NOT NULL,
+    use_count INTEGER NOT NULL DEFAULT 0,
+    last_used TEXT,
+    PRIMARY KEY (vault_id, permission)
+);
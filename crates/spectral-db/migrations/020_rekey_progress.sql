@@ -0,0 +1,16 @@
+-- Progress of an in-flight vault re-encryption (password change, salt
+-- rotation or KDF upgrade). Profiles and attachments are moved to the new
+-- key in batches; the cursors record the last ID moved so an interrupted
+-- rekey resumes where it stopped. The new key is stored encrypted under the
+-- old one, which keeps unlocking the vault until the last batch commits.
+-- At most one row exists, and only while a rekey is unfinished.
+CREATE TABLE IF NOT EXISTS rekey_progress (
+    id INTEGER PRIMARY KEY CHECK (id = 1),
+    wrapped_key BLOB NOT NULL,
+    audit_event TEXT NOT NULL,
+    profile_cursor TEXT,
+    attachment_cursor TEXT,
+    profiles_done INTEGER NOT NULL DEFAULT 0,
+    profiles_total INTEGER NOT NULL,
+    started_at TEXT NOT NULL
+);
@@ -0,0 +1,27 @@
+-- Allow the Cancelled status for brokers a cancelled scan never finished,
+-- so they are not recorded as Failed. SQLite cannot alter a CHECK
+-- constraint, so the table is rebuilt. Dropping a table deletes its rows
+-- first, which would cascade to findings; migrations run with foreign keys
+-- off, so the rebuild leaves them in place.
+CREATE TABLE broker_scans_new (
+    id TEXT PRIMARY KEY,
+    scan_job_id TEXT NOT NULL,
+    broker_id TEXT NOT NULL,
+    status TEXT NOT NULL CHECK(status IN ('Pending', 'InProgress', 'Success', 'Failed', 'Skipped', 'Cancelled')),
+    started_at TEXT,
+    completed_at TEXT,
+    error_message TEXT,
+    findings_count INTEGER DEFAULT 0,
+    FOREIGN KEY (scan_job_id) REFERENCES scan_jobs(id) ON DELETE CASCADE
+);
+
+INSERT INTO broker_scans_new
+    (id, scan_job_id, broker_id, status, started_at, completed_at, error_message, findings_count)
+SELECT id, scan_job_id, broker_id, status, started_at, completed_at, error_message, findings_count
+FROM broker_scans;
+
+DROP TABLE broker_scans;
+ALTER TABLE broker_scans_new RENAME TO broker_scans;
+
+CREATE INDEX idx_broker_scans_job ON broker_scans(scan_job_id);
+CREATE INDEX idx_broker_scans_broker ON broker_scans(broker_id);
@@ -0,0 +1,22 @@
+-- Count how often each removal attempt has failed, so repeated failures can
+-- be escalated. Attempts already failed when this runs count once.
+ALTER TABLE removal_attempts ADD COLUMN failure_count INTEGER NOT NULL DEFAULT 0;
+UPDATE removal_attempts SET failure_count = 1 WHERE status = 'Failed';
+
+-- Formal CCPA/GDPR deletion requests drafted for removals that kept failing.
+-- Drafts wait for the user to review and send them; nothing here is sent
+-- automatically. The body is kept until the request is sent or discarded.
+CREATE TABLE IF NOT EXISTS legal_requests (
+    id TEXT PRIMARY KEY,
+    attempt_id TEXT NOT NULL UNIQUE,
+    broker_id TEXT NOT NULL,
+    jurisdiction TEXT NOT NULL,
+    recipient TEXT NOT NULL,
+    subject TEXT NOT NULL,
+    body TEXT NOT NULL,
+    status TEXT NOT NULL DEFAULT 'Draft' CHECK(status IN ('Draft', 'Sent', 'Discarded')),
+    created_at TEXT NOT NULL,
+    FOREIGN KEY (attempt_id) REFERENCES removal_attempts(id) ON DELETE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS idx_legal_requests_status ON legal_requests(status);
@@ -0,0 +1,17 @@
+-- Findings moved back to active after their listing reappeared, so they can
+-- get a fresh removal request. Each row is one reopening, shown in the
+-- listing's timeline; the removal attempt the finding was detached from and
+-- the sighting that showed the listing again are kept for reference.
+CREATE TABLE IF NOT EXISTS finding_reopenings (
+    id TEXT PRIMARY KEY,
+    finding_id TEXT NOT NULL,
+    reappearance_id TEXT,
+    previous_attempt_id TEXT,
+    reason TEXT NOT NULL CHECK(reason IN ('Reappeared', 'UserRequested')),
+    reopened_at TEXT NOT NULL,
+    FOREIGN KEY (finding_id) REFERENCES findings(id) ON DELETE CASCADE,
+    FOREIGN KEY (reappearance_id) REFERENCES findings(id) ON DELETE SET NULL,
+    FOREIGN KEY (previous_attempt_id) REFERENCES removal_attempts(id) ON DELETE SET NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_finding_reopenings_finding ON finding_reopenings(finding_id);
@@ -0,0 +1,5 @@
+-- Legal requests no longer store their text. The subject and body carry the
+-- user's name and email outside the encrypted profile, so they are rendered
+-- from the profile whenever a draft is shown instead.
+ALTER TABLE legal_requests DROP COLUMN subject;
+ALTER TABLE legal_requests DROP COLUMN body;
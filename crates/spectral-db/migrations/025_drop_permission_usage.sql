@@ -0,0 +1,3 @@
+-- Permission usage counts are kept in memory only. The app never attached
+-- a writer to this table, so it holds no rows worth keeping.
+DROP TABLE IF EXISTS permission_usage;
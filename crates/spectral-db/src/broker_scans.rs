@@ -6,6 +6,7 @@
 
 use chrono::Utc;
 use sqlx::{Pool, Row, Sqlite};
+use std::collections::HashMap;
 
 /// A record representing an individual broker scan within a scan job.
 #[derive(Debug, Clone)]
@@ -16,7 +17,7 @@ pub struct BrokerScan {
     pub scan_job_id: String,
     /// ID of the broker being scanned
     pub broker_id: String,
-    /// Current status (Pending, Success, Failed, Skipped)
+    /// Current status (`Pending`, `InProgress`, `Success`, `Failed`, `Skipped`, `Cancelled`)
     pub status: String,
     /// When the scan started (RFC3339 timestamp)
     pub started_at: Option<String>,
@@ -28,6 +29,16 @@ pub struct BrokerScan {
     pub findings_count: i64,
 }
 
+impl BrokerScan {
+    /// Whether the scan reached an outcome (`Success`, `Failed` or
+    /// `Skipped`) rather than being interrupted while `Pending` or
+    /// `InProgress`, or `Cancelled` before it could finish.
+    #[must_use]
+    pub fn is_finished(&self) -> bool {
+        !matches!(self.status.as_str(), "Pending" | "InProgress" | "Cancelled")
+    }
+}
+
 /// Create a new broker scan record.
 ///
 /// # Errors
@@ -89,6 +100,25 @@ pub async fn update_status(
     Ok(())
 }
 
+/// Mark a broker scan as started, clearing any earlier outcome.
+///
+/// Records created up front as `Pending` get their real start time here, and
+/// a record left `InProgress` by an interrupted scan starts afresh.
+///
+/// # Errors
+/// Returns `sqlx::Error` if the database update fails.
+pub async fn mark_started(pool: &Pool<Sqlite>, id: &str) -> Result<(), sqlx::Error> {
+    sqlx::query(
+        "UPDATE broker_scans SET status = 'InProgress', started_at = ?, completed_at = NULL, error_message = NULL WHERE id = ?",
+    )
+    .bind(Utc::now().to_rfc3339())
+    .bind(id)
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
+
 /// Get all broker scans for a specific scan job.
 ///
 /// # Errors
@@ -148,6 +178,40 @@ pub async fn get_by_id(pool: &Pool<Sqlite>, id: &str) -> Result<Option<BrokerSca
     }
 }
 
+/// Average wall-clock duration in seconds of each broker's most recent
+/// finished scans, keyed by broker ID.
+///
+/// Only scans that ran to `Success` or `Failed` are counted, at most
+/// `recent` per broker, so estimates follow a broker's current speed.
+/// Brokers that have never finished a scan are absent.
+///
+/// # Errors
+/// Returns `sqlx::Error` if the database query fails.
+pub async fn average_durations(
+    pool: &Pool<Sqlite>,
+    recent: u32,
+) -> Result<HashMap<String, f64>, sqlx::Error> {
+    let rows = sqlx::query(
+        "SELECT broker_id, AVG(seconds) AS seconds FROM (
+             SELECT broker_id,
+                    (julianday(completed_at) - julianday(started_at)) * 86400.0 AS seconds,
+                    ROW_NUMBER() OVER (PARTITION BY broker_id ORDER BY started_at DESC) AS n
+             FROM broker_scans
+             WHERE status IN ('Success', 'Failed')
+               AND started_at IS NOT NULL AND completed_at IS NOT NULL
+         )
+         WHERE n <= ? AND seconds >= 0
+         GROUP BY broker_id",
+    )
+    .bind(recent)
+    .fetch_all(pool)
+    .await?;
+
+    rows.into_iter()
+        .map(|row| Ok((row.try_get("broker_id")?, row.try_get("seconds")?)))
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -229,6 +293,38 @@ mod tests {
         assert!(updated.completed_at.is_some());
     }
 
+    #[tokio::test]
+    async fn test_mark_started_clears_previous_outcome() {
+        let db = setup_test_db().await;
+
+        let scan = create_broker_scan(db.pool(), "job-123".to_string(), "test-broker".to_string())
+            .await
+            .expect("create broker scan");
+        assert!(!scan.is_finished());
+
+        update_status(db.pool(), &scan.id, "Failed", Some("timeout".to_string()))
+            .await
+            .expect("update status");
+        let failed = get_by_id(db.pool(), &scan.id)
+            .await
+            .expect("get by id")
+            .expect("scan exists");
+        assert!(failed.is_finished());
+
+        mark_started(db.pool(), &scan.id)
+            .await
+            .expect("mark started");
+        let restarted = get_by_id(db.pool(), &scan.id)
+            .await
+            .expect("get by id")
+            .expect("scan exists");
+
+        assert_eq!(restarted.status, "InProgress");
+        assert!(!restarted.is_finished());
+        assert_eq!(restarted.completed_at, None);
+        assert_eq!(restarted.error_message, None);
+    }
+
     #[tokio::test]
     async fn test_get_by_scan_job() {
         let db = setup_test_db().await;
@@ -247,4 +343,60 @@ mod tests {
 
         assert_eq!(scans.len(), 2);
     }
+
+    #[tokio::test]
+    async fn test_average_durations_uses_recent_finished_scans() {
+        let db = setup_test_db().await;
+
+        for (id, broker_id, status, started_at, completed_at) in [
+            // Oldest broker-1 scan falls outside the two most recent
+            (
+                "s1",
+                "broker-1",
+                "Success",
+                "2025-01-01T00:00:00Z",
+                "2025-01-01T00:01:00Z",
+            ),
+            (
+                "s2",
+                "broker-1",
+                "Success",
+                "2025-01-02T00:00:00Z",
+                "2025-01-02T00:00:02Z",
+            ),
+            (
+                "s3",
+                "broker-1",
+                "Failed",
+                "2025-01-03T00:00:00Z",
+                "2025-01-03T00:00:04Z",
+            ),
+            (
+                "s4",
+                "broker-2",
+                "Skipped",
+                "2025-01-03T00:00:00Z",
+                "2025-01-03T00:00:00Z",
+            ),
+        ] {
+            sqlx::query(
+                "INSERT INTO broker_scans (id, scan_job_id, broker_id, status, started_at, completed_at) VALUES (?, 'job-123', ?, ?, ?, ?)",
+            )
+            .bind(id)
+            .bind(broker_id)
+            .bind(status)
+            .bind(started_at)
+            .bind(completed_at)
+            .execute(db.pool())
+            .await
+            .expect("insert scan");
+        }
+
+        let durations = average_durations(db.pool(), 2)
+            .await
+            .expect("average durations");
+
+        assert_eq!(durations.len(), 1);
+        assert!((durations["broker-1"] - 3.0).abs() < 0.01);
+    }
 }
@@ -0,0 +1,146 @@
+//! Change notifications for reactive UIs.
+//!
+//! Components that write scan, finding or removal state publish a
+//! [`DbChange`] through [`Database::notify`], and the UI layer subscribes
+//! with [`Database::listen_for_changes`] instead of polling. The channel is a
+//! bounded broadcast: subscribers only see changes published after they
+//! subscribed, and one that falls more than [`CHANGE_CHANNEL_CAPACITY`]
+//! changes behind skips the oldest.
+
+use crate::removal_attempts::RemovalStatus;
+use crate::Database;
+use serde::Serialize;
+use tokio::sync::broadcast;
+
+/// Number of changes buffered for slow subscribers.
+pub const CHANGE_CHANNEL_CAPACITY: usize = 256;
+
+/// A write that the UI may want to reflect.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum DbChange {
+    /// A scan job started, completed or failed
+    ScanStatusChanged {
+        /// ID of the scan job
+        scan_job_id: String,
+        /// New status of the scan job
+        status: String,
+    },
+    /// A finding was recorded for a scan job
+    FindingCreated {
+        /// ID of the scan job that found it
+        scan_job_id: String,
+        /// ID of the new finding
+        finding_id: String,
+        /// Broker the listing was found on
+        broker_id: String,
+    },
+    /// A removal attempt moved to a new status
+    RemovalStatusChanged {
+        /// ID of the removal attempt
+        removal_attempt_id: String,
+        /// New status of the attempt
+        status: RemovalStatus,
+    },
+}
+
+/// Create the sending half of a change channel.
+pub(crate) fn channel() -> broadcast::Sender<DbChange> {
+    broadcast::channel(CHANGE_CHANNEL_CAPACITY).0
+}
+
+impl Database {
+    /// Subscribe to changes published after this call.
+    ///
+    /// The receiver reports `Closed` once the database is dropped.
+    #[must_use]
+    pub fn listen_for_changes(&self) -> broadcast::Receiver<DbChange> {
+        self.changes.subscribe()
+    }
+
+    /// Publish a change to current subscribers.
+    ///
+    /// Changes published while nobody is listening are dropped.
+    pub fn notify(&self, change: DbChange) {
+        // An error only means there are no subscribers
+        let _ = self.changes.send(change);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    async fn test_db() -> Database {
+        Database::new(":memory:", vec![0x42; 32])
+            .await
+            .expect("create database")
+    }
+
+    fn scan_completed(scan_job_id: &str) -> DbChange {
+        DbChange::ScanStatusChanged {
+            scan_job_id: scan_job_id.to_string(),
+            status: "Completed".to_string(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_subscribers_receive_changes() {
+        let db = test_db().await;
+        let mut first = db.listen_for_changes();
+        let mut second = db.listen_for_changes();
+
+        db.notify(scan_completed("job-1"));
+
+        assert_eq!(
+            first.recv().await.expect("receive"),
+            scan_completed("job-1")
+        );
+        assert_eq!(
+            second.recv().await.expect("receive"),
+            scan_completed("job-1")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_late_subscriber_misses_earlier_changes() {
+        let db = test_db().await;
+
+        // Nobody is listening, so this is dropped
+        db.notify(scan_completed("job-1"));
+
+        let mut rx = db.listen_for_changes();
+        db.notify(scan_completed("job-2"));
+
+        assert_eq!(rx.recv().await.expect("receive"), scan_completed("job-2"));
+        assert!(rx.try_recv().is_err());
+    }
+
+    #[tokio::test]
+    async fn test_channel_closes_when_database_is_dropped() {
+        let db = test_db().await;
+        let mut rx = db.listen_for_changes();
+
+        drop(db);
+
+        assert_eq!(rx.recv().await, Err(broadcast::error::RecvError::Closed));
+    }
+
+    #[test]
+    fn test_change_serialization() {
+        let change = DbChange::RemovalStatusChanged {
+            removal_attempt_id: "attempt-1".to_string(),
+            status: RemovalStatus::Submitted,
+        };
+
+        let json = serde_json::to_value(&change).expect("serialize change");
+        assert_eq!(
+            json,
+            serde_json::json!({
+                "kind": "removal_status_changed",
+                "removal_attempt_id": "attempt-1",
+                "status": "Submitted"
+            })
+        );
+    }
+}
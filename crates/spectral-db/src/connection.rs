@@ -17,7 +17,7 @@ use zeroize::Zeroizing;
 #[derive(Debug)]
 pub struct EncryptedPool {
     pool: Pool<Sqlite>,
-    _key: Zeroizing<Vec<u8>>,
+    key: Zeroizing<Vec<u8>>,
 }
 
 impl EncryptedPool {
@@ -65,7 +65,7 @@ impl EncryptedPool {
 
         tracing::info!("Encrypted database pool created at {}", path_str);
 
-        Ok(Self { pool, _key: key })
+        Ok(Self { pool, key })
     }
 
     /// Create an `EncryptedPool` from an existing pool and key.
@@ -84,7 +84,7 @@ impl EncryptedPool {
         assert_eq!(key.len(), 32, "Encryption key must be exactly 32 bytes");
         Self {
             pool,
-            _key: Zeroizing::new(key),
+            key: Zeroizing::new(key),
         }
     }
 
@@ -106,18 +106,114 @@ impl EncryptedPool {
 
     /// Verify that the database is accessible with the provided key.
     ///
-    /// This performs a simple query to ensure the encryption key is correct.
+    /// Reads the schema, the first read of the file, so a wrong key fails
+    /// here rather than on a later query.
     ///
     /// # Errors
     /// Returns `DatabaseError::InvalidKey` if the key is incorrect or the database is corrupted.
     pub async fn verify_key(&self) -> Result<()> {
-        sqlx::query("SELECT 1")
+        sqlx::query("SELECT COUNT(*) FROM sqlite_master")
             .execute(&self.pool)
             .await
             .map_err(|_| DatabaseError::InvalidKey)?;
 
         Ok(())
     }
+
+    /// Re-encrypt the database file at `path` under `new_key` and open a pool
+    /// with the new key.
+    ///
+    /// Runs `SQLCipher`'s `PRAGMA rekey`, which rewrites the file in one
+    /// transaction, on a single connection. The pool's other connections
+    /// still hold the old key, so this pool is closed and the returned one
+    /// takes its place. Not for `:memory:` databases, which would reopen
+    /// empty.
+    ///
+    /// # Errors
+    /// Returns `DatabaseError::InvalidKey` if `new_key` is not 32 bytes, or
+    /// `DatabaseError::Encryption` if the file cannot be re-encrypted. If
+    /// reopening fails, the file is already under `new_key`.
+    pub async fn rekey(&self, path: impl AsRef<Path>, new_key: Vec<u8>) -> Result<Self> {
+        if new_key.len() != 32 {
+            return Err(DatabaseError::InvalidKey);
+        }
+        let new_key = Zeroizing::new(new_key);
+
+        let pragma = Zeroizing::new(format!("PRAGMA rekey = \"x'{}'\"", hex::encode(&*new_key)));
+        sqlx::query(&pragma)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| {
+                DatabaseError::Encryption(format!("failed to re-encrypt database: {e}"))
+            })?;
+        self.pool.close().await;
+
+        Self::new(path, new_key.to_vec()).await
+    }
+
+    /// Write a consistent copy of the database to `dest`, under the same key,
+    /// while the pool stays in use.
+    ///
+    /// Uses `VACUUM INTO`, which copies from a single read transaction, so
+    /// writes committed during the backup are either all in the copy or all
+    /// missing from it, and pages still in the write-ahead log are included.
+    /// A plain file copy of a database in WAL mode gives neither guarantee.
+    /// The copy is then opened with this pool's key and integrity-checked, so
+    /// a backup that is unreadable with the key is reported instead of kept.
+    ///
+    /// # Errors
+    /// Returns `DatabaseError::Backup` if `dest` already exists or is not
+    /// valid UTF-8, if the copy cannot be written, or if it does not open and
+    /// pass an integrity check with this pool's key. A failed copy is removed.
+    pub async fn backup_to(&self, dest: impl AsRef<Path>) -> Result<()> {
+        let dest = dest.as_ref();
+        let dest_str = dest.to_str().ok_or_else(|| {
+            DatabaseError::Backup("invalid backup path: not valid UTF-8".to_string())
+        })?;
+        // VACUUM INTO refuses a non-empty file, but would write into an empty one
+        if dest.exists() {
+            return Err(DatabaseError::Backup(format!(
+                "{} already exists",
+                dest.display()
+            )));
+        }
+
+        let result = self.write_backup(dest, dest_str).await;
+        if result.is_err() {
+            tokio::fs::remove_file(dest).await.ok();
+        }
+        result
+    }
+
+    async fn write_backup(&self, dest: &Path, dest_str: &str) -> Result<()> {
+        sqlx::query("VACUUM INTO ?")
+            .bind(dest_str)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| DatabaseError::Backup(format!("failed to write backup: {e}")))?;
+
+        let backup = Self::new(dest, self.key.to_vec())
+            .await
+            .map_err(|e| DatabaseError::Backup(format!("failed to open backup: {e}")))?;
+        let check: std::result::Result<String, sqlx::Error> =
+            sqlx::query_scalar("PRAGMA quick_check")
+                .fetch_one(&backup.pool)
+                .await;
+        backup.close().await;
+
+        match check {
+            Ok(status) if status == "ok" => {
+                tracing::info!("Database backed up to {}", dest.display());
+                Ok(())
+            }
+            Ok(status) => Err(DatabaseError::Backup(format!(
+                "backup failed integrity check: {status}"
+            ))),
+            Err(e) => Err(DatabaseError::Backup(format!(
+                "backup is not readable with the database key: {e}"
+            ))),
+        }
+    }
 }
 
 #[cfg(test)]
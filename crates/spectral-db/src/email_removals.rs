@@ -0,0 +1,547 @@
+//! Opt-out emails sent to brokers, and matching inbound mail back to them.
+//!
+//! Every email removal is logged to `email_removals` when it is sent, with
+//! the Message-ID we set on SMTP sends. Inbound replies and bounce reports
+//! are matched back to the removal attempt by that Message-ID, falling back
+//! to the broker address for mailto sends where we could not set one.
+
+use crate::removal_attempts::RemovalStatus;
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use sqlx::{Pool, Row, Sqlite};
+use std::collections::HashMap;
+
+/// Error message recorded on removal attempts whose email bounced.
+pub const BOUNCE_REASON: &str = "delivery bounced";
+
+/// An opt-out email sent for a removal attempt
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EmailRemoval {
+    /// Unique identifier
+    pub id: String,
+    /// Removal attempt the email was sent for
+    pub attempt_id: Option<String>,
+    /// Broker the email was sent to
+    pub broker_id: String,
+    /// ISO 8601 timestamp when sent
+    pub sent_at: String,
+    /// How the email was sent (`smtp` or `mailto`)
+    pub method: String,
+    /// Broker address the email was sent to
+    pub recipient: String,
+    /// Email subject
+    pub subject: String,
+    /// SHA-256 of the body (the body itself is never stored)
+    pub body_hash: String,
+    /// Message-ID without angle brackets, `None` for mailto sends
+    pub message_id: Option<String>,
+}
+
+/// Parameters for logging a sent email removal
+#[derive(Debug)]
+pub struct CreateEmailRemoval {
+    /// Removal attempt ID
+    pub attempt_id: String,
+    /// Broker ID
+    pub broker_id: String,
+    /// Send method (`smtp` or `mailto`)
+    pub method: String,
+    /// Broker address
+    pub recipient: String,
+    /// Email subject
+    pub subject: String,
+    /// SHA-256 of the body
+    pub body_hash: String,
+    /// Message-ID set on the email, if we controlled it
+    pub message_id: Option<String>,
+}
+
+/// Log a sent email removal.
+///
+/// # Errors
+/// Returns `sqlx::Error` if the database insert fails.
+pub async fn insert_email_removal(
+    pool: &Pool<Sqlite>,
+    params: CreateEmailRemoval,
+) -> Result<EmailRemoval, sqlx::Error> {
+    let id = uuid::Uuid::new_v4().to_string();
+    let sent_at = Utc::now().to_rfc3339();
+    let message_id = params
+        .message_id
+        .as_deref()
+        .map(|message_id| normalize_message_id(message_id).to_string());
+
+    sqlx::query(
+        "INSERT INTO email_removals (id, attempt_id, broker_id, sent_at, method, recipient, subject, body_hash, message_id)
+         VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)",
+    )
+    .bind(&id)
+    .bind(&params.attempt_id)
+    .bind(&params.broker_id)
+    .bind(&sent_at)
+    .bind(&params.method)
+    .bind(&params.recipient)
+    .bind(&params.subject)
+    .bind(&params.body_hash)
+    .bind(&message_id)
+    .execute(pool)
+    .await?;
+
+    Ok(EmailRemoval {
+        id,
+        attempt_id: Some(params.attempt_id),
+        broker_id: params.broker_id,
+        sent_at,
+        method: params.method,
+        recipient: params.recipient,
+        subject: params.subject,
+        body_hash: params.body_hash,
+        message_id,
+    })
+}
+
+/// Get the emails sent for a removal attempt, oldest first.
+///
+/// # Errors
+/// Returns `sqlx::Error` if the database query fails.
+pub async fn get_by_attempt_id(
+    pool: &Pool<Sqlite>,
+    attempt_id: &str,
+) -> Result<Vec<EmailRemoval>, sqlx::Error> {
+    let rows = sqlx::query(
+        "SELECT id, attempt_id, broker_id, sent_at, method, recipient, subject, body_hash, message_id
+         FROM email_removals
+         WHERE attempt_id = ?
+         ORDER BY sent_at ASC",
+    )
+    .bind(attempt_id)
+    .fetch_all(pool)
+    .await?;
+
+    rows.into_iter()
+        .map(|row| {
+            Ok(EmailRemoval {
+                id: row.try_get("id")?,
+                attempt_id: row.try_get("attempt_id")?,
+                broker_id: row.try_get("broker_id")?,
+                sent_at: row.try_get("sent_at")?,
+                method: row.try_get("method")?,
+                recipient: row.try_get("recipient")?,
+                subject: row.try_get("subject")?,
+                body_hash: row.try_get("body_hash")?,
+                message_id: row.try_get("message_id")?,
+            })
+        })
+        .collect()
+}
+
+/// Find the submitted removal attempt an inbound reply belongs to.
+///
+/// `references` are the Message-IDs from the reply's `In-Reply-To` and
+/// `References` headers. When none of them is one we sent, the reply is
+/// matched by sender instead, taking the oldest email to that address whose
+/// attempt is still `Submitted`.
+///
+/// # Errors
+/// Returns `sqlx::Error` if a database query fails.
+pub async fn find_attempt_for_reply(
+    pool: &Pool<Sqlite>,
+    sender: &str,
+    references: &[String],
+) -> Result<Option<String>, sqlx::Error> {
+    let references: Vec<&str> = references
+        .iter()
+        .map(|message_id| normalize_message_id(message_id))
+        .collect();
+    find_submitted_attempt(pool, sender, &references).await
+}
+
+/// Broker addresses with a removal email still awaiting an answer.
+///
+/// Maps each lower-cased recipient to the attempt of the oldest email sent
+/// to it whose attempt is still `Submitted`, the same attempt an inbound
+/// message without a usable Message-ID is matched to.
+///
+/// # Errors
+/// Returns `sqlx::Error` if the query fails.
+pub async fn submitted_recipients(
+    pool: &Pool<Sqlite>,
+) -> Result<HashMap<String, String>, sqlx::Error> {
+    let rows = sqlx::query_as::<_, (String, String)>(
+        "SELECT lower(trim(e.recipient)), e.attempt_id FROM email_removals e
+         JOIN removal_attempts r ON r.id = e.attempt_id
+         WHERE r.status = ?
+         ORDER BY e.sent_at DESC",
+    )
+    .bind(RemovalStatus::Submitted.to_string())
+    .fetch_all(pool)
+    .await?;
+
+    // Newest first, so the oldest email to each address is inserted last
+    Ok(rows.into_iter().collect())
+}
+
+/// Mark the removal attempt behind a bounced email as failed.
+///
+/// The attempt is matched by the bounced message's Message-ID. A bounce that
+/// does not quote one is matched by recipient instead, taking the oldest
+/// email to that address whose attempt is still `Submitted`; a bounce whose
+/// Message-ID is not one we sent matches nothing, so it cannot fail an
+/// unrelated request. Message-IDs are compared without angle brackets and
+/// recipients without case.
+///
+/// Returns the ID of the attempt that was marked failed, or `None` if no
+/// submitted attempt matches.
+///
+/// # Errors
+/// Returns `sqlx::Error` if a database query fails.
+pub async fn mark_bounced(
+    pool: &Pool<Sqlite>,
+    recipient: &str,
+    message_id: Option<&str>,
+) -> Result<Option<String>, sqlx::Error> {
+    let attempt_id = match message_id {
+        Some(message_id) => {
+            find_submitted_by_message_id(pool, &[normalize_message_id(message_id)]).await?
+        }
+        None => find_oldest_submitted_to(pool, recipient).await?,
+    };
+    let Some(attempt_id) = attempt_id else {
+        return Ok(None);
+    };
+
+    let submitted = RemovalStatus::Submitted.to_string();
+    sqlx::query(
+        "UPDATE removal_attempts
+         SET status = ?, completed_at = ?, error_message = ?
+         WHERE id = ? AND status = ?",
+    )
+    .bind(RemovalStatus::Failed.to_string())
+    .bind(Utc::now().to_rfc3339())
+    .bind(BOUNCE_REASON)
+    .bind(&attempt_id)
+    .bind(&submitted)
+    .execute(pool)
+    .await?;
+
+    tracing::info!("Removal attempt {} failed: {}", attempt_id, BOUNCE_REASON);
+    Ok(Some(attempt_id))
+}
+
+/// Find a `Submitted` attempt by any of `message_ids`, else by recipient.
+async fn find_submitted_attempt(
+    pool: &Pool<Sqlite>,
+    recipient: &str,
+    message_ids: &[&str],
+) -> Result<Option<String>, sqlx::Error> {
+    match find_submitted_by_message_id(pool, message_ids).await? {
+        Some(attempt_id) => Ok(Some(attempt_id)),
+        None => find_oldest_submitted_to(pool, recipient).await,
+    }
+}
+
+/// Find a `Submitted` attempt whose email has any of `message_ids`.
+async fn find_submitted_by_message_id(
+    pool: &Pool<Sqlite>,
+    message_ids: &[&str],
+) -> Result<Option<String>, sqlx::Error> {
+    let submitted = RemovalStatus::Submitted.to_string();
+
+    for message_id in message_ids {
+        let attempt_id: Option<String> = sqlx::query_scalar(
+            "SELECT e.attempt_id FROM email_removals e
+             JOIN removal_attempts r ON r.id = e.attempt_id
+             WHERE e.message_id = ? AND r.status = ?
+             LIMIT 1",
+        )
+        .bind(message_id)
+        .bind(&submitted)
+        .fetch_optional(pool)
+        .await?;
+        if attempt_id.is_some() {
+            return Ok(attempt_id);
+        }
+    }
+    Ok(None)
+}
+
+/// Find the `Submitted` attempt with the oldest email to `recipient`.
+async fn find_oldest_submitted_to(
+    pool: &Pool<Sqlite>,
+    recipient: &str,
+) -> Result<Option<String>, sqlx::Error> {
+    sqlx::query_scalar(
+        "SELECT e.attempt_id FROM email_removals e
+         JOIN removal_attempts r ON r.id = e.attempt_id
+         WHERE lower(e.recipient) = lower(?) AND r.status = ?
+         ORDER BY e.sent_at ASC
+         LIMIT 1",
+    )
+    .bind(recipient.trim())
+    .bind(RemovalStatus::Submitted.to_string())
+    .fetch_optional(pool)
+    .await
+}
+
+/// Strip surrounding whitespace and angle brackets from a Message-ID.
+fn normalize_message_id(message_id: &str) -> &str {
+    message_id
+        .trim()
+        .trim_start_matches('<')
+        .trim_end_matches('>')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::removal_attempts::{create_removal_attempt, get_by_id, update_status};
+    use crate::Database;
+
+    async fn setup_test_db() -> Database {
+        let db = Database::new(":memory:", vec![0u8; 32])
+            .await
+            .expect("create database");
+        db.run_migrations().await.expect("run migrations");
+
+        // Findings need a profile, scan job and broker scan (foreign keys)
+        let now = Utc::now().to_rfc3339();
+        sqlx::query(
+            "INSERT INTO profiles (id, data, nonce, created_at, updated_at)
+             VALUES ('profile-1', x'00', x'00', ?, ?)",
+        )
+        .bind(&now)
+        .bind(&now)
+        .execute(db.pool())
+        .await
+        .expect("insert profile");
+        sqlx::query(
+            "INSERT INTO scan_jobs (id, profile_id, started_at, status, total_brokers, completed_brokers)
+             VALUES ('job-1', 'profile-1', ?, 'Completed', 1, 1)",
+        )
+        .bind(&now)
+        .execute(db.pool())
+        .await
+        .expect("insert scan job");
+        sqlx::query(
+            "INSERT INTO broker_scans (id, scan_job_id, broker_id, status, started_at)
+             VALUES ('scan-1', 'job-1', 'spokeo', 'Success', ?)",
+        )
+        .bind(&now)
+        .execute(db.pool())
+        .await
+        .expect("insert broker scan");
+        sqlx::query(
+            "INSERT INTO findings (id, broker_scan_id, broker_id, profile_id, listing_url, verification_status, extracted_data, discovered_at)
+             VALUES ('finding-1', 'scan-1', 'spokeo', 'profile-1', 'https://example.com/1', 'Confirmed', '{}', ?)",
+        )
+        .bind(&now)
+        .execute(db.pool())
+        .await
+        .expect("insert finding");
+
+        db
+    }
+
+    /// Create a submitted attempt and log its email.
+    async fn submit_email(
+        db: &Database,
+        recipient: &str,
+        sent_at: &str,
+        message_id: Option<&str>,
+    ) -> String {
+        let attempt = create_removal_attempt(db.pool(), "finding-1".into(), "spokeo".into())
+            .await
+            .expect("create attempt");
+        update_status(
+            db.pool(),
+            &attempt.id,
+            RemovalStatus::Submitted,
+            Some(Utc::now()),
+            None,
+            None,
+        )
+        .await
+        .expect("mark submitted");
+
+        sqlx::query(
+            "INSERT INTO email_removals (id, attempt_id, broker_id, sent_at, method, recipient, subject, body_hash, message_id)
+             VALUES (?, ?, 'spokeo', ?, 'smtp', ?, 'Opt-Out Request', 'hash', ?)",
+        )
+        .bind(uuid::Uuid::new_v4().to_string())
+        .bind(&attempt.id)
+        .bind(sent_at)
+        .bind(recipient)
+        .bind(message_id)
+        .execute(db.pool())
+        .await
+        .expect("log email removal");
+
+        attempt.id
+    }
+
+    async fn status_of(db: &Database, attempt_id: &str) -> (RemovalStatus, Option<String>) {
+        let attempt = get_by_id(db.pool(), attempt_id)
+            .await
+            .expect("get attempt")
+            .expect("attempt exists");
+        (attempt.status, attempt.error_message)
+    }
+
+    #[tokio::test]
+    async fn test_insert_stores_message_id() {
+        let db = setup_test_db().await;
+        let attempt = create_removal_attempt(db.pool(), "finding-1".into(), "spokeo".into())
+            .await
+            .expect("create attempt");
+
+        let inserted = insert_email_removal(
+            db.pool(),
+            CreateEmailRemoval {
+                attempt_id: attempt.id.clone(),
+                broker_id: "spokeo".into(),
+                method: "smtp".into(),
+                recipient: "optout@spokeo.com".into(),
+                subject: "Opt-Out Request".into(),
+                body_hash: "hash".into(),
+                message_id: Some("<sent-1@example.com>".into()),
+            },
+        )
+        .await
+        .expect("insert email removal");
+        assert_eq!(inserted.message_id.as_deref(), Some("sent-1@example.com"));
+
+        let stored = get_by_attempt_id(db.pool(), &attempt.id)
+            .await
+            .expect("get email removals");
+        assert_eq!(stored.len(), 1);
+        assert_eq!(stored[0].message_id.as_deref(), Some("sent-1@example.com"));
+        assert_eq!(stored[0].recipient, "optout@spokeo.com");
+    }
+
+    #[tokio::test]
+    async fn test_find_attempt_for_reply_by_references() {
+        let db = setup_test_db().await;
+        let first = submit_email(
+            &db,
+            "optout@spokeo.com",
+            "2026-01-01T00:00:00Z",
+            Some("a@example.com"),
+        )
+        .await;
+        let second = submit_email(
+            &db,
+            "optout@spokeo.com",
+            "2026-01-02T00:00:00Z",
+            Some("b@example.com"),
+        )
+        .await;
+
+        // A reply to the second email, threaded under an unrelated message
+        let references = vec![
+            "<thread-root@spokeo.com>".to_string(),
+            "<b@example.com>".to_string(),
+        ];
+        let found = find_attempt_for_reply(db.pool(), "optout@spokeo.com", &references)
+            .await
+            .expect("find attempt");
+        assert_eq!(found.as_deref(), Some(second.as_str()));
+
+        // Without references the oldest outstanding email to the sender wins
+        let found = find_attempt_for_reply(db.pool(), "optout@spokeo.com", &[])
+            .await
+            .expect("find attempt");
+        assert_eq!(found.as_deref(), Some(first.as_str()));
+
+        let found = find_attempt_for_reply(db.pool(), "someone@else.com", &[])
+            .await
+            .expect("find attempt");
+        assert_eq!(found, None);
+    }
+
+    #[tokio::test]
+    async fn test_mark_bounced_by_message_id() {
+        let db = setup_test_db().await;
+        let first = submit_email(
+            &db,
+            "optout@spokeo.com",
+            "2026-01-01T00:00:00Z",
+            Some("a@example.com"),
+        )
+        .await;
+        let second = submit_email(
+            &db,
+            "optout@spokeo.com",
+            "2026-01-02T00:00:00Z",
+            Some("b@example.com"),
+        )
+        .await;
+
+        let failed = mark_bounced(db.pool(), "optout@spokeo.com", Some("<b@example.com>"))
+            .await
+            .expect("mark bounced");
+
+        assert_eq!(failed.as_deref(), Some(second.as_str()));
+        assert_eq!(
+            status_of(&db, &second).await,
+            (RemovalStatus::Failed, Some(BOUNCE_REASON.to_string()))
+        );
+        assert_eq!(status_of(&db, &first).await.0, RemovalStatus::Submitted);
+    }
+
+    #[tokio::test]
+    async fn test_mark_bounced_ignores_unknown_message_id() {
+        let db = setup_test_db().await;
+        let attempt = submit_email(&db, "optout@spokeo.com", "2026-01-01T00:00:00Z", None).await;
+
+        let failed = mark_bounced(db.pool(), "optout@spokeo.com", Some("unknown@mailer"))
+            .await
+            .expect("mark bounced");
+        assert_eq!(failed, None);
+        assert_eq!(status_of(&db, &attempt).await.0, RemovalStatus::Submitted);
+    }
+
+    #[tokio::test]
+    async fn test_mark_bounced_without_message_id_falls_back_to_oldest_by_recipient() {
+        let db = setup_test_db().await;
+        let newer = submit_email(&db, "optout@spokeo.com", "2026-01-02T00:00:00Z", None).await;
+        let older = submit_email(&db, "optout@spokeo.com", "2026-01-01T00:00:00Z", None).await;
+        let other = submit_email(&db, "privacy@other.com", "2025-12-31T00:00:00Z", None).await;
+
+        let failed = mark_bounced(db.pool(), "OptOut@Spokeo.com", None)
+            .await
+            .expect("mark bounced");
+        assert_eq!(failed.as_deref(), Some(older.as_str()));
+
+        // The next bounce to the same address takes the next outstanding email
+        let failed = mark_bounced(db.pool(), "optout@spokeo.com", None)
+            .await
+            .expect("mark bounced");
+        assert_eq!(failed.as_deref(), Some(newer.as_str()));
+
+        assert_eq!(status_of(&db, &other).await.0, RemovalStatus::Submitted);
+        assert_eq!(
+            mark_bounced(db.pool(), "optout@spokeo.com", None)
+                .await
+                .expect("mark bounced"),
+            None
+        );
+    }
+
+    #[tokio::test]
+    async fn test_submitted_recipients_maps_to_oldest_attempt() {
+        let db = setup_test_db().await;
+        submit_email(&db, "optout@spokeo.com", "2026-01-02T00:00:00Z", None).await;
+        let older = submit_email(&db, "OptOut@Spokeo.com", "2026-01-01T00:00:00Z", None).await;
+        let other = submit_email(&db, "privacy@other.com", "2025-12-31T00:00:00Z", None).await;
+        mark_bounced(db.pool(), "privacy@other.com", None)
+            .await
+            .expect("mark bounced");
+
+        let recipients = submitted_recipients(db.pool())
+            .await
+            .expect("submitted recipients");
+        assert_eq!(recipients.len(), 1);
+        assert_eq!(recipients.get("optout@spokeo.com"), Some(&older));
+        assert!(!recipients.values().any(|attempt| attempt == &other));
+    }
+}
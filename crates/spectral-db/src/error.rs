@@ -19,6 +19,18 @@ pub enum DatabaseError {
     #[error("migration failed: {0}")]
     Migration(String),
 
+    /// A migration already applied to this database differs from the one
+    /// embedded in this build, e.g. because the file was edited after release.
+    #[error(
+        "migration {version} ({description}) does not match the version applied to this database"
+    )]
+    MigrationMismatch {
+        /// Version of the mismatched migration
+        version: i64,
+        /// Description of the mismatched migration
+        description: String,
+    },
+
     /// Query execution failed.
     #[error("query failed: {0}")]
     Query(String),
@@ -43,9 +55,30 @@ pub enum DatabaseError {
     #[error("invalid encryption key")]
     InvalidKey,
 
+    /// A statement that could write was run through a read-only view.
+    #[error("write attempted through read-only view: {0}")]
+    ReadOnly(String),
+
+    /// A removal attempt cannot move between these statuses.
+    #[error("invalid removal status transition from {from} to {to}")]
+    InvalidTransition {
+        /// Status the attempt currently has
+        from: crate::removal_attempts::RemovalStatus,
+        /// Status that was requested
+        to: crate::removal_attempts::RemovalStatus,
+    },
+
+    /// A finding cannot be reopened.
+    #[error("cannot reopen finding: {0}")]
+    NotReopenable(crate::findings::NotReopenableReason),
+
+    /// An online backup could not be written or did not verify.
+    #[error("backup failed: {0}")]
+    Backup(String),
+
     /// Serialization/deserialization failed.
     #[error("serialization error: {0}")]
-    SerializationError(String),
+    Serialization(String),
 
     /// Underlying `SQLx` error.
     #[error("database error: {0}")]
@@ -56,5 +89,77 @@ pub enum DatabaseError {
     Io(#[from] std::io::Error),
 }
 
+impl DatabaseError {
+    /// Whether the operation may succeed if tried again.
+    ///
+    /// True for connection and I/O failures, an exhausted pool, and `SQLite`
+    /// reporting the database as busy or locked by another connection.
+    #[must_use]
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            Self::PoolExhausted | Self::Io(_) => true,
+            Self::Sqlx(e) => match e {
+                sqlx::Error::Io(_) | sqlx::Error::PoolTimedOut | sqlx::Error::WorkerCrashed => true,
+                sqlx::Error::Database(db) => {
+                    // SQLITE_BUSY and SQLITE_LOCKED, including extended codes
+                    db.code().is_some_and(|code| {
+                        code.parse::<i32>()
+                            .is_ok_and(|code| matches!(code & 0xff, 5 | 6))
+                    })
+                }
+                _ => false,
+            },
+            _ => false,
+        }
+    }
+}
+
 /// Result type alias for database operations.
 pub type Result<T> = std::result::Result<T, DatabaseError>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_connection_errors_are_retryable() {
+        let refused = std::io::Error::from(std::io::ErrorKind::ConnectionRefused);
+        assert!(DatabaseError::from(sqlx::Error::Io(refused)).is_retryable());
+        assert!(DatabaseError::from(sqlx::Error::PoolTimedOut).is_retryable());
+        assert!(DatabaseError::PoolExhausted.is_retryable());
+    }
+
+    #[test]
+    fn test_permanent_errors_are_not_retryable() {
+        assert!(!DatabaseError::NotFound.is_retryable());
+        assert!(!DatabaseError::InvalidKey.is_retryable());
+        assert!(!DatabaseError::from(sqlx::Error::RowNotFound).is_retryable());
+        assert!(!DatabaseError::Migration("bad migration".to_string()).is_retryable());
+    }
+
+    #[tokio::test]
+    async fn test_locked_database_is_retryable() {
+        let dir = tempfile::tempdir().expect("create temp dir");
+        let url = format!("sqlite://{}?mode=rwc", dir.path().join("lock.db").display());
+        let opts: sqlx::sqlite::SqliteConnectOptions = url.parse().expect("parse url");
+        let opts = opts.busy_timeout(std::time::Duration::ZERO);
+
+        let pool = sqlx::SqlitePool::connect_with(opts).await.expect("connect");
+        sqlx::query("CREATE TABLE t (id INTEGER)")
+            .execute(&pool)
+            .await
+            .expect("create table");
+
+        let mut holder = pool.acquire().await.expect("acquire holder");
+        sqlx::query("BEGIN EXCLUSIVE")
+            .execute(&mut *holder)
+            .await
+            .expect("lock database");
+
+        let err = sqlx::query("INSERT INTO t VALUES (1)")
+            .execute(&pool)
+            .await
+            .expect_err("database is locked");
+        assert!(DatabaseError::from(err).is_retryable());
+    }
+}
@@ -3,10 +3,15 @@
 //! This module provides CRUD operations for the `findings` table,
 //! which stores potential matches found during broker scans.
 
-use chrono::{DateTime, Utc};
+use crate::error::DatabaseError;
+use crate::removal_attempts::{self, RemovalStatus};
+use crate::trends::{self, DayRange};
+use chrono::{DateTime, NaiveDate, Utc};
 use serde::{Deserialize, Serialize};
 use serde_json::Value as JsonValue;
-use sqlx::{Pool, Row, Sqlite};
+use spectral_core::names;
+use sqlx::{Executor, Pool, Row, Sqlite};
+use std::fmt;
 
 /// A finding represents a potential match found on a data broker site.
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -35,6 +40,16 @@ pub struct Finding {
     pub removal_attempt_id: Option<String>,
 }
 
+impl Finding {
+    /// Whether a new removal request can be created for this finding: it is
+    /// confirmed as the user's and has no removal attempt, or was reopened.
+    #[must_use]
+    pub fn is_eligible_for_removal(&self) -> bool {
+        self.verification_status == VerificationStatus::Confirmed
+            && self.removal_attempt_id.is_none()
+    }
+}
+
 /// Verification status for a finding.
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
 pub enum VerificationStatus {
@@ -145,6 +160,60 @@ pub async fn update_verification_status(
     Ok(())
 }
 
+/// Record how a finding should be ranked in the review queue.
+///
+/// `match_confidence` is how closely the listing matched the profile, from
+/// 0.0 to 1.0, or `None` when the listing was not scored. `broker_weight` is
+/// the risk weight of the broker's category.
+///
+/// # Errors
+/// Returns `sqlx::Error` if the database update fails.
+pub async fn set_review_priority(
+    pool: &Pool<Sqlite>,
+    finding_id: &str,
+    match_confidence: Option<f64>,
+    broker_weight: f64,
+) -> Result<(), sqlx::Error> {
+    sqlx::query("UPDATE findings SET match_confidence = ?, broker_weight = ? WHERE id = ?")
+        .bind(match_confidence)
+        .bind(broker_weight)
+        .bind(finding_id)
+        .execute(pool)
+        .await?;
+
+    Ok(())
+}
+
+/// Get the findings of a scan job that still await verification, most
+/// likely matches first.
+///
+/// Findings are ordered by match confidence, then broker weight, both
+/// descending. Unscored findings come after scored ones, and ties fall back
+/// to discovery order.
+///
+/// # Errors
+/// Returns `sqlx::Error` if the database query fails.
+pub async fn get_for_review(
+    pool: &Pool<Sqlite>,
+    scan_job_id: &str,
+) -> Result<Vec<Finding>, sqlx::Error> {
+    let rows = sqlx::query(
+        "SELECT f.id, f.broker_scan_id, f.broker_id, f.profile_id, f.listing_url,
+                f.verification_status, f.extracted_data, f.discovered_at,
+                f.verified_at, f.verified_by_user, f.removal_attempt_id
+         FROM findings f
+         JOIN broker_scans bs ON f.broker_scan_id = bs.id
+         WHERE bs.scan_job_id = ? AND f.verification_status = 'PendingVerification'
+         ORDER BY f.match_confidence IS NULL, f.match_confidence DESC,
+                  f.broker_weight DESC, f.discovered_at ASC",
+    )
+    .bind(scan_job_id)
+    .fetch_all(pool)
+    .await?;
+
+    parse_findings_from_rows(rows)
+}
+
 /// Get all findings for a specific scan job.
 ///
 /// # Errors
@@ -200,6 +269,14 @@ pub async fn get_by_id(
     pool: &Pool<Sqlite>,
     finding_id: &str,
 ) -> Result<Option<Finding>, sqlx::Error> {
+    fetch_by_id(pool, finding_id).await
+}
+
+/// [`get_by_id`] on any executor, so it can run inside a transaction.
+async fn fetch_by_id<'e, E>(executor: E, finding_id: &str) -> Result<Option<Finding>, sqlx::Error>
+where
+    E: Executor<'e, Database = Sqlite>,
+{
     let row = sqlx::query(
         "SELECT id, broker_scan_id, broker_id, profile_id, listing_url,
                 verification_status, extracted_data, discovered_at,
@@ -208,7 +285,7 @@ pub async fn get_by_id(
          WHERE id = ?",
     )
     .bind(finding_id)
-    .fetch_optional(pool)
+    .fetch_optional(executor)
     .await?;
 
     match row {
@@ -240,6 +317,241 @@ pub async fn verify_finding(
     update_verification_status(pool, finding_id, status, verified_by_user).await
 }
 
+/// Outcome of a [`bulk_verify`] call.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BulkVerifyResult {
+    /// Number of findings whose status was updated
+    pub updated: u64,
+    /// Requested IDs that did not match any finding
+    pub missing: Vec<String>,
+}
+
+/// Confirm or reject many findings at once.
+///
+/// All updates run in a single transaction. IDs that do not exist are
+/// reported in [`BulkVerifyResult::missing`] rather than silently skipped;
+/// duplicate IDs are only applied once.
+///
+/// # Errors
+/// Returns `sqlx::Error` if any update fails, in which case no finding is changed.
+pub async fn bulk_verify(
+    pool: &Pool<Sqlite>,
+    ids: &[String],
+    is_match: bool,
+    verified_by_user: bool,
+) -> Result<BulkVerifyResult, sqlx::Error> {
+    let status = if is_match {
+        VerificationStatus::Confirmed
+    } else {
+        VerificationStatus::Rejected
+    };
+    let verified_at = Utc::now().to_rfc3339();
+
+    let mut seen = std::collections::HashSet::new();
+    let mut result = BulkVerifyResult::default();
+    let mut tx = pool.begin().await?;
+
+    for id in ids.iter().filter(|id| seen.insert(id.as_str())) {
+        let affected = sqlx::query(
+            "UPDATE findings
+             SET verification_status = ?, verified_at = ?, verified_by_user = ?
+             WHERE id = ?",
+        )
+        .bind(status.to_string())
+        .bind(&verified_at)
+        .bind(verified_by_user)
+        .bind(id)
+        .execute(&mut *tx)
+        .await?
+        .rows_affected();
+
+        if affected == 0 {
+            result.missing.push(id.clone());
+        } else {
+            result.updated += affected;
+        }
+    }
+
+    tx.commit().await?;
+    Ok(result)
+}
+
+/// Why a finding was reopened.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub enum ReopenReason {
+    /// The listing was found again after its removal was requested
+    Reappeared,
+    /// The user asked for the listing to be removed again
+    UserRequested,
+}
+
+impl ReopenReason {
+    /// Value stored in the database.
+    #[must_use]
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Reappeared => "Reappeared",
+            Self::UserRequested => "UserRequested",
+        }
+    }
+
+    /// Parse a stored value.
+    #[must_use]
+    pub fn parse(s: &str) -> Option<Self> {
+        match s {
+            "Reappeared" => Some(Self::Reappeared),
+            "UserRequested" => Some(Self::UserRequested),
+            _ => None,
+        }
+    }
+}
+
+/// Why [`reopen`] refused to reopen a finding.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NotReopenableReason {
+    /// Only confirmed findings are removed, so only they can be reopened
+    NotConfirmed(VerificationStatus),
+    /// The finding has no removal attempt and is already eligible for one
+    NoRemovalAttempt,
+    /// The current removal attempt has not been sent or settled yet
+    RemovalInProgress(RemovalStatus),
+    /// [`ReopenReason::Reappeared`] was given but the listing has not been
+    /// found again since the finding
+    NoReappearance,
+}
+
+impl fmt::Display for NotReopenableReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NotConfirmed(status) => write!(f, "finding is {status}, not Confirmed"),
+            Self::NoRemovalAttempt => write!(f, "finding has no removal attempt"),
+            Self::RemovalInProgress(status) => write!(f, "removal attempt is still {status}"),
+            Self::NoReappearance => write!(f, "listing has not reappeared"),
+        }
+    }
+}
+
+/// A confirmed finding moved back to active by [`reopen`].
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct Reopening {
+    /// Unique identifier
+    pub id: String,
+    /// The finding that was reopened
+    pub finding_id: String,
+    /// The latest sighting of the same listing after the finding, if any
+    pub reappearance_id: Option<String>,
+    /// The removal attempt the finding was detached from, if any
+    pub previous_attempt_id: Option<String>,
+    /// Why it was reopened
+    pub reason: ReopenReason,
+    /// When it was reopened
+    pub reopened_at: DateTime<Utc>,
+}
+
+/// Move a confirmed finding back to active so it gets a fresh removal
+/// request.
+///
+/// Only a finding whose removal attempt has been sent or settled
+/// (`Submitted`, `Completed` or `Failed`) can be reopened; one still being
+/// worked on is left alone. The finding is detached from that attempt, which
+/// keeps its status as history, and so becomes
+/// [eligible for removal](Finding::is_eligible_for_removal) again. The
+/// reopening is linked to the latest sighting of the same listing discovered
+/// after the finding, which [`ReopenReason::Reappeared`] requires, and shows
+/// in the listing's timeline.
+///
+/// The finding is read and updated in one transaction, so a removal attempt
+/// created concurrently is never detached unseen.
+///
+/// # Errors
+/// Returns [`DatabaseError::NotFound`] if the finding does not exist,
+/// [`DatabaseError::NotReopenable`] if it cannot be reopened, or
+/// [`DatabaseError::Sqlx`] if a database query fails.
+pub async fn reopen(
+    pool: &Pool<Sqlite>,
+    finding_id: &str,
+    reason: ReopenReason,
+) -> Result<Reopening, DatabaseError> {
+    let mut tx = pool.begin().await?;
+
+    let finding = fetch_by_id(&mut *tx, finding_id)
+        .await?
+        .ok_or(DatabaseError::NotFound)?;
+    if finding.verification_status != VerificationStatus::Confirmed {
+        return Err(DatabaseError::NotReopenable(
+            NotReopenableReason::NotConfirmed(finding.verification_status),
+        ));
+    }
+
+    let attempt_id = finding
+        .removal_attempt_id
+        .as_deref()
+        .ok_or(DatabaseError::NotReopenable(
+            NotReopenableReason::NoRemovalAttempt,
+        ))?;
+    let attempt_status: RemovalStatus =
+        sqlx::query_scalar("SELECT status FROM removal_attempts WHERE id = ?")
+            .bind(attempt_id)
+            .fetch_one(&mut *tx)
+            .await?;
+    if !matches!(
+        attempt_status,
+        RemovalStatus::Submitted | RemovalStatus::Completed | RemovalStatus::Failed
+    ) {
+        return Err(DatabaseError::NotReopenable(
+            NotReopenableReason::RemovalInProgress(attempt_status),
+        ));
+    }
+
+    let reappearance_id: Option<String> = sqlx::query_scalar(
+        "SELECT id FROM findings
+         WHERE profile_id = ? AND broker_id = ? AND listing_url = ? AND discovered_at > ?
+         ORDER BY discovered_at DESC
+         LIMIT 1",
+    )
+    .bind(&finding.profile_id)
+    .bind(&finding.broker_id)
+    .bind(&finding.listing_url)
+    .bind(finding.discovered_at.to_rfc3339())
+    .fetch_optional(&mut *tx)
+    .await?;
+    if reason == ReopenReason::Reappeared && reappearance_id.is_none() {
+        return Err(DatabaseError::NotReopenable(
+            NotReopenableReason::NoReappearance,
+        ));
+    }
+
+    let reopening = Reopening {
+        id: uuid::Uuid::new_v4().to_string(),
+        finding_id: finding.id,
+        reappearance_id,
+        previous_attempt_id: finding.removal_attempt_id,
+        reason,
+        reopened_at: Utc::now(),
+    };
+
+    sqlx::query("UPDATE findings SET removal_attempt_id = NULL WHERE id = ?")
+        .bind(&reopening.finding_id)
+        .execute(&mut *tx)
+        .await?;
+    sqlx::query(
+        "INSERT INTO finding_reopenings
+             (id, finding_id, reappearance_id, previous_attempt_id, reason, reopened_at)
+         VALUES (?, ?, ?, ?, ?, ?)",
+    )
+    .bind(&reopening.id)
+    .bind(&reopening.finding_id)
+    .bind(&reopening.reappearance_id)
+    .bind(&reopening.previous_attempt_id)
+    .bind(reason.as_str())
+    .bind(reopening.reopened_at.to_rfc3339())
+    .execute(&mut *tx)
+    .await?;
+    tx.commit().await?;
+
+    Ok(reopening)
+}
+
 /// Check if a finding already exists for the given scan job and listing URL.
 ///
 /// This is used for deduplication to prevent creating duplicate findings
@@ -267,130 +579,851 @@ pub async fn finding_exists_by_url(
     Ok(result)
 }
 
-/// Helper function to parse findings from database rows.
-fn parse_findings_from_rows(
-    rows: Vec<sqlx::sqlite::SqliteRow>,
-) -> Result<Vec<Finding>, sqlx::Error> {
-    let mut findings = Vec::new();
+/// Count confirmed findings per broker, ordered by broker ID.
+///
+/// Confirmed findings are the ones that count against the privacy score.
+///
+/// # Errors
+/// Returns `sqlx::Error` if the database query fails.
+pub async fn count_confirmed_by_broker(
+    pool: &Pool<Sqlite>,
+) -> Result<Vec<(String, i64)>, sqlx::Error> {
+    sqlx::query_as(
+        "SELECT broker_id, COUNT(*) FROM findings
+         WHERE verification_status = 'Confirmed'
+         GROUP BY broker_id
+         ORDER BY broker_id",
+    )
+    .fetch_all(pool)
+    .await
+}
 
-    for row in rows {
-        // These are temporary strings for database deserialization.
-        // Actual PII is encrypted at application layer via spectral-vault.
-        let extracted_data_str: String = row.try_get("extracted_data")?; // nosemgrep: use-zeroize-for-secrets
-        let extracted_data = serde_json::from_str(&extracted_data_str).unwrap_or(JsonValue::Null);
+/// Count findings discovered on each day of `range`, oldest first.
+///
+/// Days are local days at the range's UTC offset, and days without findings
+/// are included with a count of zero. See [`trends`] for how timezones are
+/// handled.
+///
+/// # Errors
+/// Returns `sqlx::Error` if the database query fails.
+pub async fn counts_by_day(
+    pool: &Pool<Sqlite>,
+    range: &DayRange,
+) -> Result<Vec<(NaiveDate, i64)>, sqlx::Error> {
+    trends::counts_by_day(pool, "findings", "discovered_at", range).await
+}
 
-        let discovered_at_str: String = row.try_get("discovered_at")?; // nosemgrep: use-zeroize-for-secrets
-        let discovered_at = DateTime::parse_from_rfc3339(&discovered_at_str)
-            .map_or_else(|_| Utc::now(), |dt| dt.with_timezone(&Utc));
+/// Similarity two last names need, from 0.0 to 1.0, to be treated as the
+/// same person's. First names must match exactly or as nicknames.
+const NAME_SIMILARITY_THRESHOLD: f64 = 0.85;
 
-        let verified_at: Option<String> = row.try_get("verified_at")?;
-        let verified_at = verified_at.and_then(|s| {
-            DateTime::parse_from_rfc3339(&s)
-                .ok()
-                .map(|dt| dt.with_timezone(&Utc))
-        });
+/// Largest difference between two listed ages that can still be one person,
+/// allowing for listings updated in different years.
+const MAX_AGE_DIFFERENCE: u32 = 2;
 
-        let verified_by_user: Option<i64> = row.try_get("verified_by_user")?;
-        let verified_by_user = verified_by_user.map(|v| v != 0);
+/// Name suffixes ignored when comparing names.
+const NAME_SUFFIXES: &[&str] = &["jr", "sr", "ii", "iii", "iv"];
 
-        let verification_status_str: String = row.try_get("verification_status")?; // nosemgrep: use-zeroize-for-secrets
-        let verification_status = VerificationStatus::parse(&verification_status_str);
+/// Findings from one scan that appear to describe the same person.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IdentityCluster {
+    /// Name to show for the cluster, from its first finding that has one
+    pub display_name: Option<String>,
+    /// Distinct brokers the person was found on
+    pub broker_ids: Vec<String>,
+    /// Findings in the cluster, newest first
+    pub findings: Vec<Finding>,
+}
 
-        findings.push(Finding {
-            id: row.try_get("id")?,
-            broker_scan_id: row.try_get("broker_scan_id")?,
-            broker_id: row.try_get("broker_id")?,
-            profile_id: row.try_get("profile_id")?,
-            listing_url: row.try_get("listing_url")?,
-            verification_status,
-            extracted_data,
-            discovered_at,
-            verified_at,
-            verified_by_user,
-            removal_attempt_id: row.try_get("removal_attempt_id")?,
-        });
+/// Group the findings of a scan job by the person they describe.
+///
+/// Two findings are the same person when their first names are equal or
+/// nicknames of each other, their last names are similar, they share a city
+/// and state unless one lists no address, and their ages, if both list one,
+/// are close. Matches backed by a shared location or age are transitive, so
+/// a listing that only shares an old address with another still joins its
+/// cluster. A listing matched only for lack of an address or age to compare
+/// joins a cluster only when it matches no other, so it never bridges two
+/// people. Findings without a name are left on their own, as is any finding
+/// that matches nothing. Clusters are ordered by the number of brokers they
+/// span.
+///
+/// # Errors
+/// Returns `sqlx::Error` if the database query fails.
+pub async fn cluster_by_identity(
+    pool: &Pool<Sqlite>,
+    scan_job_id: &str,
+) -> Result<Vec<IdentityCluster>, sqlx::Error> {
+    let findings = get_by_scan_job(pool, scan_job_id).await?;
+    let identities: Vec<_> = findings.iter().map(ListingIdentity::from).collect();
+
+    // Union-find over the findings whose location or age agree
+    let mut parent: Vec<usize> = (0..findings.len()).collect();
+    let mut weak_matches = Vec::new();
+    for i in 0..identities.len() {
+        for j in (i + 1)..identities.len() {
+            match identities[i].match_strength(&identities[j]) {
+                IdentityMatch::Strong => {
+                    let (a, b) = (root(&mut parent, i), root(&mut parent, j));
+                    parent[b] = a;
+                }
+                IdentityMatch::Weak => weak_matches.push((i, j)),
+                IdentityMatch::None => {}
+            }
+        }
     }
 
-    Ok(findings)
+    // A listing with only weak matches joins the one cluster they point to
+    let mut candidates = vec![std::collections::BTreeSet::new(); findings.len()];
+    for &(i, j) in &weak_matches {
+        candidates[i].insert(root(&mut parent, j));
+        candidates[j].insert(root(&mut parent, i));
+    }
+    let mut cluster_sizes = vec![0usize; findings.len()];
+    for i in 0..findings.len() {
+        cluster_sizes[root(&mut parent, i)] += 1;
+    }
+    for (i, roots) in candidates.iter().enumerate() {
+        let own = root(&mut parent, i);
+        match roots.first() {
+            Some(&target) if roots.len() == 1 && cluster_sizes[own] == 1 => {
+                parent[own] = root(&mut parent, target);
+            }
+            _ => {}
+        }
+    }
+
+    // Findings are newest first, so each cluster keeps that order
+    let mut clusters: Vec<IdentityCluster> = Vec::new();
+    let mut cluster_of_root = std::collections::HashMap::new();
+    for (i, finding) in findings.into_iter().enumerate() {
+        let index = *cluster_of_root
+            .entry(root(&mut parent, i))
+            .or_insert_with(|| {
+                clusters.push(IdentityCluster {
+                    display_name: None,
+                    broker_ids: Vec::new(),
+                    findings: Vec::new(),
+                });
+                clusters.len() - 1
+            });
+        let cluster = &mut clusters[index];
+        if cluster.display_name.is_none() {
+            cluster.display_name.clone_from(&identities[i].display_name);
+        }
+        if !cluster.broker_ids.contains(&finding.broker_id) {
+            cluster.broker_ids.push(finding.broker_id.clone());
+        }
+        cluster.findings.push(finding);
+    }
+
+    // Stable, so clusters of the same size stay newest first
+    clusters.sort_by_key(|cluster| std::cmp::Reverse(cluster.broker_ids.len()));
+    Ok(clusters)
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use crate::Database;
+/// Representative of the set `i` belongs to in a union-find forest.
+fn root(parent: &mut [usize], mut i: usize) -> usize {
+    while parent[i] != i {
+        parent[i] = parent[parent[i]];
+        i = parent[i];
+    }
+    i
+}
 
-    async fn setup_test_db() -> Database {
-        let key = vec![0u8; 32];
-        // nosemgrep: no-unwrap-in-production
-        let db = Database::new(":memory:", key).await.unwrap();
-        // nosemgrep: no-unwrap-in-production
-        db.run_migrations().await.unwrap();
+/// The parts of a finding's extracted data that identify a person.
+#[derive(Debug)]
+struct ListingIdentity {
+    display_name: Option<String>,
+    /// First and last name, lowercased, without middle names or suffixes
+    name: Option<(String, String)>,
+    /// Normalized "city state" of each address
+    locations: Vec<String>,
+    /// Inclusive range the person's age falls in
+    ages: Option<(u32, u32)>,
+}
 
-        // Create test profile (required by foreign key)
-        let dummy_data = [0u8; 32];
-        let dummy_nonce = [0u8; 12];
-        // nosemgrep: no-unwrap-in-production
-        sqlx::query(
-            "INSERT INTO profiles (id, data, nonce, created_at, updated_at) VALUES (?, ?, ?, ?, ?)",
-        )
-        .bind("profile-123")
-        .bind(&dummy_data[..])
-        .bind(&dummy_nonce[..])
-        .bind(Utc::now().to_rfc3339())
-        .bind(Utc::now().to_rfc3339())
-        .execute(db.pool())
-        .await
-        .unwrap();
+impl From<&Finding> for ListingIdentity {
+    fn from(finding: &Finding) -> Self {
+        let data = &finding.extracted_data;
+        let display_name = data
+            .get("name")
+            .and_then(JsonValue::as_str)
+            .map(str::trim)
+            .filter(|name| !name.is_empty())
+            .map(str::to_string);
+
+        let locations = data
+            .get("addresses")
+            .and_then(JsonValue::as_array)
+            .map(|addresses| {
+                addresses
+                    .iter()
+                    .filter_map(JsonValue::as_str)
+                    .filter_map(normalize_location)
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        let age = data
+            .get("age")
+            .and_then(JsonValue::as_u64)
+            .and_then(|age| u32::try_from(age).ok());
+        let age_range = data
+            .get("age_range")
+            .and_then(JsonValue::as_array)
+            .and_then(|range| match range.as_slice() {
+                [low, high] => Some((
+                    u32::try_from(low.as_u64()?).ok()?,
+                    u32::try_from(high.as_u64()?).ok()?,
+                )),
+                _ => None,
+            });
+
+        Self {
+            name: display_name.as_deref().and_then(normalize_name),
+            display_name,
+            locations,
+            ages: age.map(|age| (age, age)).or(age_range),
+        }
+    }
+}
 
-        // Create test scan job
-        // nosemgrep: no-unwrap-in-production
-        sqlx::query(
-            "INSERT INTO scan_jobs (id, profile_id, started_at, status, total_brokers, completed_brokers) VALUES (?, ?, ?, ?, ?, ?)"
-        )
-        .bind("job-456")
-        .bind("profile-123")
-        .bind(Utc::now().to_rfc3339())
-        .bind("InProgress")
-        .bind(5)
-        .bind(0)
-        .execute(db.pool())
-        .await
-        .unwrap();
+/// How well two listings match as the same person.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum IdentityMatch {
+    /// Different names, places or ages
+    None,
+    /// Same name and nothing contradicts it, but no location or age to
+    /// compare either
+    Weak,
+    /// Same name and a shared location or a close age
+    Strong,
+}
 
-        // Create test broker scan
-        // nosemgrep: no-unwrap-in-production
-        sqlx::query(
-            "INSERT INTO broker_scans (id, scan_job_id, broker_id, status, started_at) VALUES (?, ?, ?, ?, ?)"
-        )
-        .bind("scan-789")
-        .bind("job-456")
-        .bind("spokeo")
-        .bind("Success")
-        .bind(Utc::now().to_rfc3339())
-        .execute(db.pool())
-        .await
-        .unwrap();
+impl ListingIdentity {
+    fn match_strength(&self, other: &Self) -> IdentityMatch {
+        let (Some((first_a, last_a)), Some((first_b, last_b))) = (&self.name, &other.name) else {
+            return IdentityMatch::None;
+        };
+        if !names::is_same_first_name(first_a, first_b)
+            || similarity(last_a, last_b) < NAME_SIMILARITY_THRESHOLD
+        {
+            return IdentityMatch::None;
+        }
 
-        db
+        // `None` when either listing leaves it out
+        let shares_location = (!self.locations.is_empty() && !other.locations.is_empty())
+            .then(|| self.locations.iter().any(|l| other.locations.contains(l)));
+        let ages_overlap = match (self.ages, other.ages) {
+            (Some((low_a, high_a)), Some((low_b, high_b))) => Some(
+                low_a <= high_b.saturating_add(MAX_AGE_DIFFERENCE)
+                    && low_b <= high_a.saturating_add(MAX_AGE_DIFFERENCE),
+            ),
+            _ => None,
+        };
+
+        match (shares_location, ages_overlap) {
+            (Some(false), _) | (_, Some(false)) => IdentityMatch::None,
+            (Some(true), _) | (_, Some(true)) => IdentityMatch::Strong,
+            (None, None) => IdentityMatch::Weak,
+        }
     }
+}
 
-    #[tokio::test]
-    async fn test_create_finding() {
-        let db = setup_test_db().await;
+/// First and last name of a full name, lowercased and without punctuation,
+/// middle names or suffixes. `None` for a single word.
+fn normalize_name(name: &str) -> Option<(String, String)> {
+    let words: Vec<String> = name
+        .split_whitespace()
+        .map(|word| {
+            word.chars()
+                .filter(|c| c.is_alphanumeric())
+                .flat_map(char::to_lowercase)
+                .collect::<String>()
+        })
+        .filter(|word| !word.is_empty() && !NAME_SUFFIXES.contains(&word.as_str()))
+        .collect();
+    match words.as_slice() {
+        [first, .., last] => Some((first.clone(), last.clone())),
+        _ => None,
+    }
+}
 
-        let extracted = serde_json::json!({
-            "name": "John Doe",
-            "age": 35,
-            "location": "Los Angeles, CA"
-        });
+/// City and state of an address such as "12 Oak St, Springfield, IL 62704",
+/// lowercased and without the street or ZIP code.
+fn normalize_location(address: &str) -> Option<String> {
+    let parts: Vec<&str> = address.split(',').map(str::trim).collect();
+    let city_state = match parts.as_slice() {
+        [] | [""] => return None,
+        [only] => vec![*only],
+        [.., city, state] => vec![*city, *state],
+    };
+    let words: Vec<String> = city_state
+        .iter()
+        .flat_map(|part| part.split_whitespace())
+        .filter(|word| !word.chars().any(|c| c.is_ascii_digit()))
+        .map(str::to_lowercase)
+        .collect();
+    (!words.is_empty()).then(|| words.join(" "))
+}
 
-        let finding = create_finding(
-            db.pool(),
-            "scan-789".to_string(),
-            "spokeo".to_string(),
-            "profile-123".to_string(),
-            "https://example.com/profile/123".to_string(),
-            extracted,
+/// Similarity of two words from 0.0 to 1.0, based on their edit distance.
+#[allow(clippy::cast_precision_loss)]
+fn similarity(a: &str, b: &str) -> f64 {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    let longest = a.len().max(b.len());
+    if longest == 0 {
+        return 1.0;
+    }
+
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.iter().enumerate() {
+        let mut current = vec![i + 1];
+        for (j, cb) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(ca != cb);
+            current.push(substitution.min(previous[j + 1] + 1).min(current[j] + 1));
+        }
+        previous = current;
+    }
+    1.0 - previous[b.len()] as f64 / longest as f64
+}
+
+/// Kind of event in a listing's history.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub enum TimelineEventKind {
+    /// The listing was first found
+    Discovered,
+    /// The listing was confirmed as the user's
+    Confirmed,
+    /// The listing was rejected as not the user's
+    Rejected,
+    /// A removal request was created
+    RemovalRequested,
+    /// The removal request was sent to the broker
+    RemovalSubmitted,
+    /// The removal request failed
+    RemovalFailed,
+    /// The broker removed the listing
+    Removed,
+    /// The listing was found again after it was removed
+    Reappeared,
+    /// The listing was reopened for a new removal request
+    Reopened,
+}
+
+/// One event in a listing's history.
+///
+/// `detail` is a short description without personal data, safe to show in
+/// the listing's detail view.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct TimelineEvent {
+    /// What happened
+    pub kind: TimelineEventKind,
+    /// When it happened
+    pub timestamp: DateTime<Utc>,
+    /// Short description of the event
+    pub detail: String,
+}
+
+/// Get the history of the listing a finding belongs to, oldest first.
+///
+/// Each scan records its own finding, so a listing is every finding for the
+/// same profile, broker and listing URL. The timeline covers their discovery
+/// and verification, the removal attempts made for any of them, and sightings
+/// after a removal completed, which are reported as reappearances. Returns an
+/// empty timeline if the finding does not exist.
+///
+/// # Errors
+/// Returns `sqlx::Error` if a database query fails.
+pub async fn get_timeline(
+    pool: &Pool<Sqlite>,
+    finding_id: &str,
+) -> Result<Vec<TimelineEvent>, sqlx::Error> {
+    let Some(finding) = get_by_id(pool, finding_id).await? else {
+        return Ok(Vec::new());
+    };
+
+    let rows = sqlx::query(
+        "SELECT id, broker_scan_id, broker_id, profile_id, listing_url,
+                verification_status, extracted_data, discovered_at,
+                verified_at, verified_by_user, removal_attempt_id
+         FROM findings
+         WHERE profile_id = ? AND broker_id = ? AND listing_url = ?
+         ORDER BY discovered_at ASC",
+    )
+    .bind(&finding.profile_id)
+    .bind(&finding.broker_id)
+    .bind(&finding.listing_url)
+    .fetch_all(pool)
+    .await?;
+    let sightings = parse_findings_from_rows(rows)?;
+
+    let rows = sqlx::query(
+        "SELECT ra.id, ra.finding_id, ra.broker_id, ra.status, ra.created_at,
+                ra.submitted_at, ra.completed_at, ra.error_message
+         FROM removal_attempts ra
+         JOIN findings f ON ra.finding_id = f.id
+         WHERE f.profile_id = ? AND f.broker_id = ? AND f.listing_url = ?",
+    )
+    .bind(&finding.profile_id)
+    .bind(&finding.broker_id)
+    .bind(&finding.listing_url)
+    .fetch_all(pool)
+    .await?;
+    let attempts = removal_attempts::parse_removal_attempts_from_rows(rows)?;
+
+    let reopenings: Vec<(String, String)> = sqlx::query_as(
+        "SELECT r.reason, r.reopened_at
+         FROM finding_reopenings r
+         JOIN findings f ON r.finding_id = f.id
+         WHERE f.profile_id = ? AND f.broker_id = ? AND f.listing_url = ?",
+    )
+    .bind(&finding.profile_id)
+    .bind(&finding.broker_id)
+    .bind(&finding.listing_url)
+    .fetch_all(pool)
+    .await?;
+
+    let mut events = sighting_events(&sightings, &attempts, &finding.broker_id);
+    events.extend(removal_events(&attempts, &finding.broker_id));
+    events.extend(reopen_events(&reopenings));
+
+    // Stable, so events at the same instant keep the order they were added in
+    events.sort_by_key(|event| event.timestamp);
+    Ok(events)
+}
+
+/// Discovery, verification and reappearance events for a listing's sightings.
+///
+/// `sightings` must be ordered by discovery time.
+fn sighting_events(
+    sightings: &[Finding],
+    attempts: &[removal_attempts::RemovalAttempt],
+    broker: &str,
+) -> Vec<TimelineEvent> {
+    let removals: Vec<DateTime<Utc>> = attempts
+        .iter()
+        .filter(|attempt| attempt.status == RemovalStatus::Completed)
+        .filter_map(|attempt| attempt.completed_at)
+        .collect();
+
+    let mut events = Vec::new();
+    let mut previous_sighting: Option<DateTime<Utc>> = None;
+    for sighting in sightings {
+        match previous_sighting {
+            None => events.push(TimelineEvent {
+                kind: TimelineEventKind::Discovered,
+                timestamp: sighting.discovered_at,
+                detail: format!("Listing found on {broker}"),
+            }),
+            Some(previous) => {
+                let removed_since = removals
+                    .iter()
+                    .any(|removed| previous <= *removed && *removed < sighting.discovered_at);
+                if removed_since {
+                    events.push(TimelineEvent {
+                        kind: TimelineEventKind::Reappeared,
+                        timestamp: sighting.discovered_at,
+                        detail: format!("Listing found on {broker} again after removal"),
+                    });
+                }
+            }
+        }
+        previous_sighting = Some(sighting.discovered_at);
+
+        let Some(verified_at) = sighting.verified_at else {
+            continue;
+        };
+        let by = if sighting.verified_by_user == Some(true) {
+            "by you"
+        } else {
+            "automatically"
+        };
+        let (kind, detail) = match sighting.verification_status {
+            VerificationStatus::Confirmed => (
+                TimelineEventKind::Confirmed,
+                format!("Confirmed as your listing {by}"),
+            ),
+            VerificationStatus::Rejected => (
+                TimelineEventKind::Rejected,
+                format!("Marked as not your listing {by}"),
+            ),
+            VerificationStatus::PendingVerification => continue,
+        };
+        events.push(TimelineEvent {
+            kind,
+            timestamp: verified_at,
+            detail,
+        });
+    }
+    events
+}
+
+/// Request, submission and outcome events for a listing's removal attempts.
+fn removal_events(
+    attempts: &[removal_attempts::RemovalAttempt],
+    broker: &str,
+) -> Vec<TimelineEvent> {
+    let mut events = Vec::new();
+    for attempt in attempts {
+        events.push(TimelineEvent {
+            kind: TimelineEventKind::RemovalRequested,
+            timestamp: attempt.created_at,
+            detail: "Removal request created".to_string(),
+        });
+        if let Some(submitted_at) = attempt.submitted_at {
+            events.push(TimelineEvent {
+                kind: TimelineEventKind::RemovalSubmitted,
+                timestamp: submitted_at,
+                detail: format!("Removal request sent to {broker}"),
+            });
+        }
+        match attempt.status {
+            RemovalStatus::Completed => {
+                if let Some(completed_at) = attempt.completed_at {
+                    events.push(TimelineEvent {
+                        kind: TimelineEventKind::Removed,
+                        timestamp: completed_at,
+                        detail: format!("Listing removed from {broker}"),
+                    });
+                }
+            }
+            // Error messages can quote broker pages, so only the failure is recorded
+            RemovalStatus::Failed => events.push(TimelineEvent {
+                kind: TimelineEventKind::RemovalFailed,
+                timestamp: attempt
+                    .completed_at
+                    .or(attempt.submitted_at)
+                    .unwrap_or(attempt.created_at),
+                detail: "Removal request failed".to_string(),
+            }),
+            RemovalStatus::Pending
+            | RemovalStatus::Submitted
+            | RemovalStatus::NeedsUserAction
+            | RemovalStatus::Cancelled => {}
+        }
+    }
+    events
+}
+
+/// Events for a listing's reopenings, from `(reason, reopened_at)` rows.
+fn reopen_events(reopenings: &[(String, String)]) -> Vec<TimelineEvent> {
+    reopenings
+        .iter()
+        .filter_map(|(reason, reopened_at)| {
+            let timestamp = DateTime::parse_from_rfc3339(reopened_at)
+                .ok()?
+                .with_timezone(&Utc);
+            let detail = match ReopenReason::parse(reason) {
+                Some(ReopenReason::Reappeared) => {
+                    "Reopened for a new removal request after the listing reappeared"
+                }
+                Some(ReopenReason::UserRequested) | None => "Reopened for a new removal request",
+            };
+            Some(TimelineEvent {
+                kind: TimelineEventKind::Reopened,
+                timestamp,
+                detail: detail.to_string(),
+            })
+        })
+        .collect()
+}
+
+/// A column of the CSV produced by [`export_csv`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CsvColumn {
+    /// Broker the listing was found on
+    Broker,
+    /// URL of the listing
+    Url,
+    /// Verification status
+    Status,
+    /// When the finding was discovered (RFC 3339)
+    DiscoveredAt,
+    /// Name extracted from the listing
+    Name,
+    /// Age extracted from the listing
+    Age,
+    /// Addresses extracted from the listing, separated by `"; "`
+    Addresses,
+}
+
+impl CsvColumn {
+    /// Header row label.
+    #[must_use]
+    pub fn header(self) -> &'static str {
+        match self {
+            Self::Broker => "broker",
+            Self::Url => "url",
+            Self::Status => "status",
+            Self::DiscoveredAt => "discovered_at",
+            Self::Name => "name",
+            Self::Age => "age",
+            Self::Addresses => "addresses",
+        }
+    }
+
+    /// Whether the column holds PII extracted from the listing.
+    #[must_use]
+    pub fn is_pii(self) -> bool {
+        matches!(self, Self::Name | Self::Age | Self::Addresses)
+    }
+
+    fn value(self, finding: &Finding) -> String {
+        let extracted = |key: &str| finding.extracted_data.get(key).unwrap_or(&JsonValue::Null);
+        match self {
+            Self::Broker => finding.broker_id.clone(),
+            Self::Url => finding.listing_url.clone(),
+            Self::Status => finding.verification_status.to_string(),
+            Self::DiscoveredAt => finding.discovered_at.to_rfc3339(),
+            Self::Name => extracted("name").as_str().unwrap_or_default().to_string(),
+            Self::Age => extracted("age")
+                .as_u64()
+                .map(|age| age.to_string())
+                .unwrap_or_default(),
+            Self::Addresses => extracted("addresses")
+                .as_array()
+                .map(|addresses| {
+                    addresses
+                        .iter()
+                        .filter_map(JsonValue::as_str)
+                        .collect::<Vec<_>>()
+                        .join("; ")
+                })
+                .unwrap_or_default(),
+        }
+    }
+}
+
+/// Options for [`export_csv`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CsvExportOptions {
+    /// Columns to include, in order
+    pub columns: Vec<CsvColumn>,
+    /// Leave out the PII columns even if they are listed in `columns`
+    pub redact_pii: bool,
+}
+
+impl CsvExportOptions {
+    /// All columns, including the extracted name, age and addresses.
+    #[must_use]
+    pub fn with_pii() -> Self {
+        Self {
+            columns: vec![
+                CsvColumn::Broker,
+                CsvColumn::Url,
+                CsvColumn::Status,
+                CsvColumn::DiscoveredAt,
+                CsvColumn::Name,
+                CsvColumn::Age,
+                CsvColumn::Addresses,
+            ],
+            redact_pii: false,
+        }
+    }
+}
+
+impl Default for CsvExportOptions {
+    /// Broker, URL, status and discovery time.
+    fn default() -> Self {
+        Self {
+            columns: vec![
+                CsvColumn::Broker,
+                CsvColumn::Url,
+                CsvColumn::Status,
+                CsvColumn::DiscoveredAt,
+            ],
+            redact_pii: false,
+        }
+    }
+}
+
+/// Export the findings of a scan job as RFC 4180 CSV.
+///
+/// The first row holds the column headers. Rows end in CRLF and are in the
+/// same order as [`get_by_scan_job`]. Listing data comes from broker sites,
+/// so values that a spreadsheet would run as a formula are prefixed with `'`.
+///
+/// # Errors
+/// Returns `sqlx::Error` if the database query fails.
+pub async fn export_csv(
+    pool: &Pool<Sqlite>,
+    scan_job_id: &str,
+    options: &CsvExportOptions,
+) -> Result<String, sqlx::Error> {
+    let findings = get_by_scan_job(pool, scan_job_id).await?;
+    let columns: Vec<CsvColumn> = options
+        .columns
+        .iter()
+        .copied()
+        .filter(|column| !(options.redact_pii && column.is_pii()))
+        .collect();
+
+    let mut csv = String::new();
+    push_csv_record(
+        &mut csv,
+        columns.iter().map(|column| column.header().to_string()),
+    );
+    for finding in &findings {
+        push_csv_record(&mut csv, columns.iter().map(|column| column.value(finding)));
+    }
+    Ok(csv)
+}
+
+/// Append one CSV record, escaping each field.
+fn push_csv_record(csv: &mut String, fields: impl Iterator<Item = String>) {
+    for (index, field) in fields.enumerate() {
+        if index > 0 {
+            csv.push(',');
+        }
+        csv.push_str(&escape_csv_field(&field));
+    }
+    csv.push_str("\r\n");
+}
+
+/// Quote a field if it contains a delimiter, quote or line break, doubling
+/// any quotes, and defuse leading formula characters.
+fn escape_csv_field(field: &str) -> String {
+    let field = if field.starts_with(['=', '+', '-', '@', '\t', '\r']) {
+        format!("'{field}")
+    } else {
+        field.to_string()
+    };
+
+    if field.contains([',', '"', '\r', '\n']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field
+    }
+}
+
+/// Helper function to parse findings from database rows.
+fn parse_findings_from_rows(
+    rows: Vec<sqlx::sqlite::SqliteRow>,
+) -> Result<Vec<Finding>, sqlx::Error> {
+    let mut findings = Vec::new();
+
+    for row in rows {
+        // These are temporary strings for database deserialization.
+        // Actual PII is encrypted at application layer via spectral-vault.
+        let extracted_data_str: String = row.try_get("extracted_data")?; // nosemgrep: use-zeroize-for-secrets
+        let extracted_data = serde_json::from_str(&extracted_data_str).unwrap_or(JsonValue::Null);
+
+        let discovered_at_str: String = row.try_get("discovered_at")?; // nosemgrep: use-zeroize-for-secrets
+        let discovered_at = DateTime::parse_from_rfc3339(&discovered_at_str)
+            .map_or_else(|_| Utc::now(), |dt| dt.with_timezone(&Utc));
+
+        let verified_at: Option<String> = row.try_get("verified_at")?;
+        let verified_at = verified_at.and_then(|s| {
+            DateTime::parse_from_rfc3339(&s)
+                .ok()
+                .map(|dt| dt.with_timezone(&Utc))
+        });
+
+        let verified_by_user: Option<i64> = row.try_get("verified_by_user")?;
+        let verified_by_user = verified_by_user.map(|v| v != 0);
+
+        let verification_status_str: String = row.try_get("verification_status")?; // nosemgrep: use-zeroize-for-secrets
+        let verification_status = VerificationStatus::parse(&verification_status_str);
+
+        findings.push(Finding {
+            id: row.try_get("id")?,
+            broker_scan_id: row.try_get("broker_scan_id")?,
+            broker_id: row.try_get("broker_id")?,
+            profile_id: row.try_get("profile_id")?,
+            listing_url: row.try_get("listing_url")?,
+            verification_status,
+            extracted_data,
+            discovered_at,
+            verified_at,
+            verified_by_user,
+            removal_attempt_id: row.try_get("removal_attempt_id")?,
+        });
+    }
+
+    Ok(findings)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Database;
+
+    async fn setup_test_db() -> Database {
+        let key = vec![0u8; 32];
+        // nosemgrep: no-unwrap-in-production
+        let db = Database::new(":memory:", key).await.unwrap();
+        // nosemgrep: no-unwrap-in-production
+        db.run_migrations().await.unwrap();
+
+        // Create test profile (required by foreign key)
+        let dummy_data = [0u8; 32];
+        let dummy_nonce = [0u8; 12];
+        // nosemgrep: no-unwrap-in-production
+        sqlx::query(
+            "INSERT INTO profiles (id, data, nonce, created_at, updated_at) VALUES (?, ?, ?, ?, ?)",
+        )
+        .bind("profile-123")
+        .bind(&dummy_data[..])
+        .bind(&dummy_nonce[..])
+        .bind(Utc::now().to_rfc3339())
+        .bind(Utc::now().to_rfc3339())
+        .execute(db.pool())
+        .await
+        .unwrap();
+
+        // Create test scan job
+        // nosemgrep: no-unwrap-in-production
+        sqlx::query(
+            "INSERT INTO scan_jobs (id, profile_id, started_at, status, total_brokers, completed_brokers) VALUES (?, ?, ?, ?, ?, ?)"
+        )
+        .bind("job-456")
+        .bind("profile-123")
+        .bind(Utc::now().to_rfc3339())
+        .bind("InProgress")
+        .bind(5)
+        .bind(0)
+        .execute(db.pool())
+        .await
+        .unwrap();
+
+        // Create test broker scan
+        // nosemgrep: no-unwrap-in-production
+        sqlx::query(
+            "INSERT INTO broker_scans (id, scan_job_id, broker_id, status, started_at) VALUES (?, ?, ?, ?, ?)"
+        )
+        .bind("scan-789")
+        .bind("job-456")
+        .bind("spokeo")
+        .bind("Success")
+        .bind(Utc::now().to_rfc3339())
+        .execute(db.pool())
+        .await
+        .unwrap();
+
+        db
+    }
+
+    #[tokio::test]
+    async fn test_create_finding() {
+        let db = setup_test_db().await;
+
+        let extracted = serde_json::json!({
+            "name": "John Doe",
+            "age": 35,
+            "location": "Los Angeles, CA"
+        });
+
+        let finding = create_finding(
+            db.pool(),
+            "scan-789".to_string(),
+            "spokeo".to_string(),
+            "profile-123".to_string(),
+            "https://example.com/profile/123".to_string(),
+            extracted,
         )
         .await
         .expect("create finding");
@@ -475,6 +1508,97 @@ mod tests {
         assert_eq!(findings.len(), 2);
     }
 
+    #[tokio::test]
+    async fn test_get_for_review_orders_by_confidence_then_weight() {
+        let db = setup_test_db().await;
+
+        // (listing, match confidence, broker weight)
+        let seeds = [
+            ("low-confidence", Some(0.4), 2.0),
+            ("unscored", None, 2.0),
+            ("high-confidence", Some(0.9), 1.0),
+            ("mid-confidence-heavy", Some(0.7), 1.5),
+            ("mid-confidence-light", Some(0.7), 1.0),
+            ("confirmed", Some(1.0), 2.0),
+        ];
+        for (listing, confidence, weight) in seeds {
+            let finding = create_finding(
+                db.pool(),
+                "scan-789".to_string(),
+                "spokeo".to_string(),
+                "profile-123".to_string(),
+                format!("https://example.com/{listing}"),
+                serde_json::json!({}),
+            )
+            .await
+            .expect("create finding");
+            set_review_priority(db.pool(), &finding.id, confidence, weight)
+                .await
+                .expect("set review priority");
+            if listing == "confirmed" {
+                update_verification_status(
+                    db.pool(),
+                    &finding.id,
+                    VerificationStatus::Confirmed,
+                    true,
+                )
+                .await
+                .expect("confirm finding");
+            }
+        }
+
+        let findings = get_for_review(db.pool(), "job-456")
+            .await
+            .expect("get for review");
+
+        let order: Vec<&str> = findings
+            .iter()
+            .map(|f| f.listing_url.trim_start_matches("https://example.com/"))
+            .collect();
+        assert_eq!(
+            order,
+            [
+                "high-confidence",
+                "mid-confidence-heavy",
+                "mid-confidence-light",
+                "low-confidence",
+                "unscored"
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_get_for_review_defaults_to_discovery_order() {
+        let db = setup_test_db().await;
+
+        for i in 0..3 {
+            create_finding(
+                db.pool(),
+                "scan-789".to_string(),
+                "spokeo".to_string(),
+                "profile-123".to_string(),
+                format!("https://example.com/{i}"),
+                serde_json::json!({}),
+            )
+            .await
+            .expect("create finding");
+        }
+
+        let findings = get_for_review(db.pool(), "job-456")
+            .await
+            .expect("get for review");
+
+        let urls: Vec<&str> = findings.iter().map(|f| f.listing_url.as_str()).collect();
+        assert_eq!(
+            urls,
+            [
+                "https://example.com/0",
+                "https://example.com/1",
+                "https://example.com/2"
+            ]
+        );
+    }
+
     #[tokio::test]
     async fn test_get_by_broker_scan() {
         let db = setup_test_db().await;
@@ -573,4 +1697,717 @@ mod tests {
             VerificationStatus::PendingVerification
         );
     }
+
+    async fn create_test_findings(db: &Database, count: usize) -> Vec<String> {
+        let mut ids = Vec::new();
+        for i in 0..count {
+            let finding = create_finding(
+                db.pool(),
+                "scan-789".to_string(),
+                "spokeo".to_string(),
+                "profile-123".to_string(),
+                format!("https://example.com/bulk/{i}"),
+                serde_json::json!({"name": "Bulk"}),
+            )
+            .await
+            .expect("create finding");
+            ids.push(finding.id);
+        }
+        ids
+    }
+
+    #[tokio::test]
+    async fn test_bulk_verify_confirms_all() {
+        let db = setup_test_db().await;
+        let ids = create_test_findings(&db, 3).await;
+
+        let result = bulk_verify(db.pool(), &ids, true, true)
+            .await
+            .expect("bulk verify");
+
+        assert_eq!(result.updated, 3);
+        assert!(result.missing.is_empty());
+
+        let findings = get_by_broker_scan(db.pool(), "scan-789")
+            .await
+            .expect("get findings");
+        assert_eq!(findings.len(), 3);
+        for finding in findings {
+            assert_eq!(finding.verification_status, VerificationStatus::Confirmed);
+            assert_eq!(finding.verified_by_user, Some(true));
+            assert!(finding.verified_at.is_some());
+        }
+    }
+
+    #[tokio::test]
+    async fn test_bulk_verify_reports_missing_ids() {
+        let db = setup_test_db().await;
+        let mut ids = create_test_findings(&db, 2).await;
+        ids.push("does-not-exist".to_string());
+        ids.push(ids[0].clone());
+
+        let result = bulk_verify(db.pool(), &ids, false, true)
+            .await
+            .expect("bulk verify");
+
+        assert_eq!(result.updated, 2);
+        assert_eq!(result.missing, vec!["does-not-exist".to_string()]);
+
+        let finding = get_by_id(db.pool(), &ids[1])
+            .await
+            .expect("get finding")
+            .expect("finding exists");
+        assert_eq!(finding.verification_status, VerificationStatus::Rejected);
+    }
+
+    #[tokio::test]
+    async fn test_count_confirmed_by_broker() {
+        let db = setup_test_db().await;
+
+        for (i, (broker_id, status)) in [
+            ("spokeo", VerificationStatus::Confirmed),
+            ("spokeo", VerificationStatus::Confirmed),
+            ("spokeo", VerificationStatus::Rejected),
+            ("beenverified", VerificationStatus::Confirmed),
+            ("whitepages", VerificationStatus::PendingVerification),
+        ]
+        .into_iter()
+        .enumerate()
+        {
+            let finding = create_finding(
+                db.pool(),
+                "scan-789".to_string(),
+                broker_id.to_string(),
+                "profile-123".to_string(),
+                format!("https://example.com/{i}"),
+                serde_json::json!({}),
+            )
+            .await
+            .expect("create finding");
+            update_verification_status(db.pool(), &finding.id, status, true)
+                .await
+                .expect("update status");
+        }
+
+        let counts = count_confirmed_by_broker(db.pool())
+            .await
+            .expect("count findings");
+        assert_eq!(
+            counts,
+            vec![("beenverified".to_string(), 1), ("spokeo".to_string(), 2)]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_counts_by_day_buckets_local_days_and_fills_gaps() {
+        let db = setup_test_db().await;
+
+        for (i, discovered_at) in [
+            "2026-02-28T23:00:00+00:00",
+            "2026-03-01T03:00:00+00:00",
+            "2026-03-01T23:30:00.123456789+00:00",
+            "2026-03-03T12:00:00+00:00",
+            "2026-03-05T02:00:00+00:00",
+        ]
+        .into_iter()
+        .enumerate()
+        {
+            let finding =
+                spokeo_finding(&db, "scan-789", &format!("https://example.com/{i}")).await;
+            sqlx::query("UPDATE findings SET discovered_at = ? WHERE id = ?")
+                .bind(discovered_at)
+                .bind(&finding.id)
+                .execute(db.pool())
+                .await
+                .expect("backdate finding");
+        }
+
+        let day = |s: &str| NaiveDate::parse_from_str(s, "%Y-%m-%d").expect("valid date");
+        let range = DayRange::utc(day("2026-03-01"), day("2026-03-04"));
+        assert_eq!(
+            counts_by_day(db.pool(), &range)
+                .await
+                .expect("count by day"),
+            vec![
+                (day("2026-03-01"), 2),
+                (day("2026-03-02"), 0),
+                (day("2026-03-03"), 1),
+                (day("2026-03-04"), 0),
+            ]
+        );
+
+        // Five hours behind UTC, the early 1 March finding was on 28 February
+        // and the 5 March one was on the evening of 4 March
+        let new_york = chrono::FixedOffset::west_opt(5 * 3600).expect("valid offset");
+        assert_eq!(
+            counts_by_day(db.pool(), &range.with_utc_offset(new_york))
+                .await
+                .expect("count by day"),
+            vec![
+                (day("2026-03-01"), 1),
+                (day("2026-03-02"), 0),
+                (day("2026-03-03"), 1),
+                (day("2026-03-04"), 1),
+            ]
+        );
+    }
+
+    async fn spokeo_finding(db: &Database, broker_scan_id: &str, listing_url: &str) -> Finding {
+        create_finding(
+            db.pool(),
+            broker_scan_id.to_string(),
+            "spokeo".to_string(),
+            "profile-123".to_string(),
+            listing_url.to_string(),
+            serde_json::json!({"name": "John Doe"}),
+        )
+        .await
+        .expect("create finding")
+    }
+
+    #[tokio::test]
+    async fn test_reopen_reappeared_listing_for_new_removal() {
+        let db = setup_test_db().await;
+        let url = "https://example.com/profile/123";
+
+        let first = spokeo_finding(&db, "scan-789", url).await;
+        verify_finding(db.pool(), &first.id, true, true)
+            .await
+            .expect("verify finding");
+        let attempt = removal_attempts::create_removal_attempt(
+            db.pool(),
+            first.id.clone(),
+            "spokeo".to_string(),
+        )
+        .await
+        .expect("create attempt");
+        removal_attempts::update_status(
+            db.pool(),
+            &attempt.id,
+            RemovalStatus::Submitted,
+            Some(Utc::now()),
+            None,
+            None,
+        )
+        .await
+        .expect("submit attempt");
+        let first = get_by_id(db.pool(), &first.id)
+            .await
+            .expect("get finding")
+            .expect("finding exists");
+        assert!(!first.is_eligible_for_removal());
+
+        // A later scan finds the listing again
+        sqlx::query(
+            "INSERT INTO broker_scans (id, scan_job_id, broker_id, status, started_at) VALUES (?, ?, ?, ?, ?)",
+        )
+        .bind("scan-790")
+        .bind("job-456")
+        .bind("spokeo")
+        .bind("Success")
+        .bind(Utc::now().to_rfc3339())
+        .execute(db.pool())
+        .await
+        .expect("insert broker scan");
+        let second = spokeo_finding(&db, "scan-790", url).await;
+        sqlx::query("UPDATE findings SET discovered_at = ? WHERE id = ?")
+            .bind("2026-01-01T00:00:00Z")
+            .bind(&first.id)
+            .execute(db.pool())
+            .await
+            .expect("set discovery time");
+
+        let reopening = reopen(db.pool(), &first.id, ReopenReason::Reappeared)
+            .await
+            .expect("reopen finding");
+        assert_eq!(
+            reopening.reappearance_id.as_deref(),
+            Some(second.id.as_str())
+        );
+        assert_eq!(
+            reopening.previous_attempt_id.as_deref(),
+            Some(attempt.id.as_str())
+        );
+
+        let reopened = get_by_id(db.pool(), &first.id)
+            .await
+            .expect("get finding")
+            .expect("finding exists");
+        assert_eq!(reopened.verification_status, VerificationStatus::Confirmed);
+        assert!(reopened.is_eligible_for_removal());
+
+        // The old attempt is kept as history
+        let old = removal_attempts::get_by_id(db.pool(), &attempt.id)
+            .await
+            .expect("get attempt")
+            .expect("attempt exists");
+        assert_eq!(old.status, RemovalStatus::Submitted);
+
+        let timeline = get_timeline(db.pool(), &first.id)
+            .await
+            .expect("get timeline");
+        let last = timeline.last().expect("timeline has events");
+        assert_eq!(last.kind, TimelineEventKind::Reopened);
+        assert!(last.detail.contains("reappeared"));
+
+        let fresh = removal_attempts::create_removal_attempt(
+            db.pool(),
+            first.id.clone(),
+            "spokeo".to_string(),
+        )
+        .await
+        .expect("create new attempt");
+        assert_eq!(fresh.status, RemovalStatus::Pending);
+        let relinked = get_by_id(db.pool(), &first.id)
+            .await
+            .expect("get finding")
+            .expect("finding exists");
+        assert_eq!(relinked.removal_attempt_id, Some(fresh.id));
+        assert!(!relinked.is_eligible_for_removal());
+    }
+
+    #[tokio::test]
+    async fn test_reopen_requires_confirmed_finding() {
+        let db = setup_test_db().await;
+        let pending = spokeo_finding(&db, "scan-789", "https://example.com/profile/1").await;
+
+        let result = reopen(db.pool(), &pending.id, ReopenReason::UserRequested).await;
+        assert!(matches!(
+            result,
+            Err(DatabaseError::NotReopenable(
+                NotReopenableReason::NotConfirmed(VerificationStatus::PendingVerification)
+            ))
+        ));
+        let result = reopen(db.pool(), "missing", ReopenReason::UserRequested).await;
+        assert!(matches!(result, Err(DatabaseError::NotFound)));
+    }
+
+    #[tokio::test]
+    async fn test_reopen_requires_finished_attempt() {
+        let db = setup_test_db().await;
+        let finding = spokeo_finding(&db, "scan-789", "https://example.com/profile/1").await;
+        verify_finding(db.pool(), &finding.id, true, true)
+            .await
+            .expect("verify finding");
+
+        // Already eligible for removal
+        let result = reopen(db.pool(), &finding.id, ReopenReason::UserRequested).await;
+        assert!(matches!(
+            result,
+            Err(DatabaseError::NotReopenable(
+                NotReopenableReason::NoRemovalAttempt
+            ))
+        ));
+
+        // A queued attempt is still in progress
+        let attempt = removal_attempts::create_removal_attempt(
+            db.pool(),
+            finding.id.clone(),
+            "spokeo".to_string(),
+        )
+        .await
+        .expect("create attempt");
+        let result = reopen(db.pool(), &finding.id, ReopenReason::UserRequested).await;
+        assert!(matches!(
+            result,
+            Err(DatabaseError::NotReopenable(
+                NotReopenableReason::RemovalInProgress(RemovalStatus::Pending)
+            ))
+        ));
+
+        // Once settled it can be reopened, but only as reappeared if it was
+        // found again
+        removal_attempts::update_status(
+            db.pool(),
+            &attempt.id,
+            RemovalStatus::Failed,
+            None,
+            None,
+            Some("rejected".to_string()),
+        )
+        .await
+        .expect("fail attempt");
+        let result = reopen(db.pool(), &finding.id, ReopenReason::Reappeared).await;
+        assert!(matches!(
+            result,
+            Err(DatabaseError::NotReopenable(
+                NotReopenableReason::NoReappearance
+            ))
+        ));
+        let reopening = reopen(db.pool(), &finding.id, ReopenReason::UserRequested)
+            .await
+            .expect("reopen finding");
+        assert_eq!(reopening.reappearance_id, None);
+        assert_eq!(
+            reopening.previous_attempt_id.as_deref(),
+            Some(attempt.id.as_str())
+        );
+    }
+
+    #[tokio::test]
+    async fn test_get_timeline_orders_listing_history() {
+        let db = setup_test_db().await;
+        let url = "https://example.com/profile/123";
+
+        let first = spokeo_finding(&db, "scan-789", url).await;
+        verify_finding(db.pool(), &first.id, true, true)
+            .await
+            .expect("verify finding");
+        let attempt = removal_attempts::create_removal_attempt(
+            db.pool(),
+            first.id.clone(),
+            "spokeo".to_string(),
+        )
+        .await
+        .expect("create attempt");
+        removal_attempts::update_status(
+            db.pool(),
+            &attempt.id,
+            RemovalStatus::Completed,
+            Some("2026-01-03T00:00:00Z".parse().expect("valid")),
+            Some("2026-01-10T00:00:00Z".parse().expect("valid")),
+            None,
+        )
+        .await
+        .expect("update attempt");
+
+        // A later scan finds the same listing again
+        sqlx::query(
+            "INSERT INTO broker_scans (id, scan_job_id, broker_id, status, started_at) VALUES (?, ?, ?, ?, ?)",
+        )
+        .bind("scan-790")
+        .bind("job-456")
+        .bind("spokeo")
+        .bind("Success")
+        .bind(Utc::now().to_rfc3339())
+        .execute(db.pool())
+        .await
+        .expect("insert broker scan");
+        let second = spokeo_finding(&db, "scan-790", url).await;
+
+        // An unrelated listing stays out of the timeline
+        spokeo_finding(&db, "scan-789", "https://example.com/profile/456").await;
+
+        for (sql, id, at) in [
+            (
+                "UPDATE findings SET discovered_at = ? WHERE id = ?",
+                &first.id,
+                "2026-01-01T00:00:00Z",
+            ),
+            (
+                "UPDATE findings SET verified_at = ? WHERE id = ?",
+                &first.id,
+                "2026-01-02T00:00:00Z",
+            ),
+            (
+                "UPDATE removal_attempts SET created_at = ? WHERE id = ?",
+                &attempt.id,
+                "2026-01-02T12:00:00Z",
+            ),
+            (
+                "UPDATE findings SET discovered_at = ? WHERE id = ?",
+                &second.id,
+                "2026-02-01T00:00:00Z",
+            ),
+        ] {
+            sqlx::query(sql)
+                .bind(at)
+                .bind(id)
+                .execute(db.pool())
+                .await
+                .expect("set timestamp");
+        }
+
+        let timeline = get_timeline(db.pool(), &first.id)
+            .await
+            .expect("get timeline");
+        let kinds: Vec<_> = timeline.iter().map(|event| event.kind).collect();
+        assert_eq!(
+            kinds,
+            [
+                TimelineEventKind::Discovered,
+                TimelineEventKind::Confirmed,
+                TimelineEventKind::RemovalRequested,
+                TimelineEventKind::RemovalSubmitted,
+                TimelineEventKind::Removed,
+                TimelineEventKind::Reappeared,
+            ]
+        );
+        assert!(timeline
+            .windows(2)
+            .all(|pair| pair[0].timestamp <= pair[1].timestamp));
+        assert!(timeline
+            .iter()
+            .all(|event| !event.detail.contains("John") && !event.detail.contains(url)));
+
+        // Any sighting of the listing yields the same history
+        assert_eq!(
+            get_timeline(db.pool(), &second.id)
+                .await
+                .expect("get timeline"),
+            timeline
+        );
+        assert!(get_timeline(db.pool(), "missing")
+            .await
+            .expect("get timeline")
+            .is_empty());
+    }
+
+    async fn create_listing(db: &Database, url: &str, extracted_data: JsonValue) -> Finding {
+        create_finding(
+            db.pool(),
+            "scan-789".to_string(),
+            "spokeo".to_string(),
+            "profile-123".to_string(),
+            url.to_string(),
+            extracted_data,
+        )
+        .await
+        .expect("create finding")
+    }
+
+    #[tokio::test]
+    async fn test_export_csv_escapes_fields() {
+        let db = setup_test_db().await;
+        let finding = create_listing(
+            &db,
+            "https://spokeo.com/John-Doe",
+            serde_json::json!({
+                "name": "John \"Johnny\" Doe",
+                "age": 42,
+                "addresses": ["123 Main St, Springfield", "9 Elm Rd"]
+            }),
+        )
+        .await;
+
+        let csv = export_csv(db.pool(), "job-456", &CsvExportOptions::with_pii())
+            .await
+            .expect("export csv");
+
+        let lines: Vec<&str> = csv.split_terminator("\r\n").collect();
+        assert_eq!(
+            lines,
+            vec![
+                "broker,url,status,discovered_at,name,age,addresses".to_string(),
+                format!(
+                    "spokeo,https://spokeo.com/John-Doe,PendingVerification,{},\
+                     \"John \"\"Johnny\"\" Doe\",42,\"123 Main St, Springfield; 9 Elm Rd\"",
+                    finding.discovered_at.to_rfc3339()
+                ),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_export_csv_redaction_omits_pii_columns() {
+        let db = setup_test_db().await;
+        create_listing(
+            &db,
+            "https://spokeo.com/John-Doe",
+            serde_json::json!({"name": "John Doe", "age": 42, "addresses": ["123 Main St"]}),
+        )
+        .await;
+
+        let options = CsvExportOptions {
+            redact_pii: true,
+            ..CsvExportOptions::with_pii()
+        };
+        let csv = export_csv(db.pool(), "job-456", &options)
+            .await
+            .expect("export csv");
+
+        assert!(csv.starts_with("broker,url,status,discovered_at\r\n"));
+        assert!(!csv.contains("John Doe"));
+        assert!(!csv.contains("123 Main St"));
+        assert_eq!(csv.lines().count(), 2);
+        assert!(csv.lines().all(|line| line.split(',').count() == 4));
+    }
+
+    #[test]
+    fn test_escape_csv_field() {
+        assert_eq!(escape_csv_field("plain"), "plain");
+        assert_eq!(escape_csv_field("a,b"), "\"a,b\"");
+        assert_eq!(escape_csv_field("say \"hi\""), "\"say \"\"hi\"\"\"");
+        assert_eq!(escape_csv_field("line\nbreak"), "\"line\nbreak\"");
+        assert_eq!(escape_csv_field("=HYPERLINK(1)"), "'=HYPERLINK(1)");
+        assert_eq!(escape_csv_field(""), "");
+    }
+
+    async fn create_broker_listing(db: &Database, broker_id: &str, extracted_data: JsonValue) {
+        create_finding(
+            db.pool(),
+            "scan-789".to_string(),
+            broker_id.to_string(),
+            "profile-123".to_string(),
+            format!("https://{broker_id}.example.com/listing"),
+            extracted_data,
+        )
+        .await
+        .expect("create finding");
+    }
+
+    #[tokio::test]
+    async fn test_cluster_by_identity_groups_one_person() {
+        let db = setup_test_db().await;
+        create_broker_listing(
+            &db,
+            "spokeo",
+            serde_json::json!({"name": "John A. Smith", "age": 42,
+                "addresses": ["12 Oak St, Springfield, IL 62704"]}),
+        )
+        .await;
+        create_broker_listing(
+            &db,
+            "whitepages",
+            serde_json::json!({"name": "John Smith Jr", "age_range": [40, 44],
+                "addresses": ["Springfield, IL"]}),
+        )
+        .await;
+        create_broker_listing(
+            &db,
+            "beenverified",
+            serde_json::json!({"name": "Johnny Smith",
+                "addresses": ["9 Elm Ave, Chicago, IL 60601", "Springfield, IL 62704"]}),
+        )
+        .await;
+
+        let clusters = cluster_by_identity(db.pool(), "job-456")
+            .await
+            .expect("cluster findings");
+
+        assert_eq!(clusters.len(), 1);
+        assert_eq!(clusters[0].findings.len(), 3);
+        let mut brokers = clusters[0].broker_ids.clone();
+        brokers.sort();
+        assert_eq!(brokers, vec!["beenverified", "spokeo", "whitepages"]);
+        assert!(clusters[0].display_name.is_some());
+    }
+
+    #[tokio::test]
+    async fn test_cluster_by_identity_separates_namesakes() {
+        let db = setup_test_db().await;
+        for broker in ["spokeo", "whitepages"] {
+            create_broker_listing(
+                &db,
+                broker,
+                serde_json::json!({"name": "John Smith", "age": 42,
+                    "addresses": ["Springfield, IL"]}),
+            )
+            .await;
+        }
+        // Same name, but elsewhere
+        create_broker_listing(
+            &db,
+            "radaris",
+            serde_json::json!({"name": "John Smith", "addresses": ["Portland, OR 97201"]}),
+        )
+        .await;
+        // Same name and city, but decades older
+        create_broker_listing(
+            &db,
+            "truepeoplesearch",
+            serde_json::json!({"name": "John Smith", "age": 78,
+                "addresses": ["Springfield, IL"]}),
+        )
+        .await;
+        // Different person
+        create_broker_listing(
+            &db,
+            "fastpeoplesearch",
+            serde_json::json!({"name": "Jane Doe", "addresses": ["Springfield, IL"]}),
+        )
+        .await;
+        // Similar name, different person
+        create_broker_listing(
+            &db,
+            "peoplefinders",
+            serde_json::json!({"name": "Jim Smith", "age": 42,
+                "addresses": ["Springfield, IL"]}),
+        )
+        .await;
+        // No name to compare
+        create_broker_listing(&db, "mylife", serde_json::json!({"age": 42})).await;
+
+        let clusters = cluster_by_identity(db.pool(), "job-456")
+            .await
+            .expect("cluster findings");
+
+        let sizes: Vec<usize> = clusters.iter().map(|c| c.findings.len()).collect();
+        assert_eq!(sizes, vec![2, 1, 1, 1, 1, 1]);
+        let mut brokers = clusters[0].broker_ids.clone();
+        brokers.sort();
+        assert_eq!(brokers, vec!["spokeo", "whitepages"]);
+        assert!(clusters
+            .iter()
+            .any(|c| c.display_name.is_none() && c.broker_ids == vec!["mylife"]));
+    }
+
+    #[tokio::test]
+    async fn test_cluster_by_identity_listing_without_details_does_not_bridge() {
+        let db = setup_test_db().await;
+        for (broker, city) in [
+            ("spokeo", "Springfield, IL"),
+            ("whitepages", "Springfield, IL"),
+            ("radaris", "Portland, OR"),
+            ("truepeoplesearch", "Portland, OR"),
+        ] {
+            create_broker_listing(
+                &db,
+                broker,
+                serde_json::json!({"name": "John Smith", "addresses": [city]}),
+            )
+            .await;
+        }
+        // Matches both people for lack of an address or age
+        create_broker_listing(&db, "mylife", serde_json::json!({"name": "John Smith"})).await;
+
+        let clusters = cluster_by_identity(db.pool(), "job-456")
+            .await
+            .expect("cluster findings");
+
+        let sizes: Vec<usize> = clusters.iter().map(|c| c.findings.len()).collect();
+        assert_eq!(sizes, vec![2, 2, 1]);
+        assert_eq!(clusters[2].broker_ids, vec!["mylife"]);
+    }
+
+    #[tokio::test]
+    async fn test_cluster_by_identity_attaches_listing_without_details_to_one_match() {
+        let db = setup_test_db().await;
+        for broker in ["spokeo", "whitepages"] {
+            create_broker_listing(
+                &db,
+                broker,
+                serde_json::json!({"name": "John Smith", "addresses": ["Springfield, IL"]}),
+            )
+            .await;
+        }
+        create_broker_listing(&db, "mylife", serde_json::json!({"name": "John Smith"})).await;
+
+        let clusters = cluster_by_identity(db.pool(), "job-456")
+            .await
+            .expect("cluster findings");
+
+        assert_eq!(clusters.len(), 1);
+        assert_eq!(clusters[0].findings.len(), 3);
+    }
+
+    #[test]
+    fn test_identity_normalization() {
+        assert_eq!(
+            normalize_name("John Q. Public, Jr."),
+            Some(("john".to_string(), "public".to_string()))
+        );
+        assert_eq!(normalize_name("Cher"), None);
+        assert_eq!(
+            normalize_location("12 Oak St, Springfield, IL 62704"),
+            Some("springfield il".to_string())
+        );
+        assert_eq!(
+            normalize_location("Springfield"),
+            Some("springfield".to_string())
+        );
+        assert_eq!(normalize_location(""), None);
+        assert!((similarity("smith", "smith") - 1.0).abs() < f64::EPSILON);
+        assert!((similarity("john", "jon") - 0.75).abs() < f64::EPSILON);
+    }
 }
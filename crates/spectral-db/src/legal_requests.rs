@@ -0,0 +1,282 @@
+//! Formal legal deletion requests drafted for removals that keep failing.
+//!
+//! When a removal attempt has failed too often, a deletion request addressed
+//! to the broker's legal contact is drafted and stored here with status
+//! `Draft`. Drafts are never sent automatically: they wait in
+//! [`get_review_queue`] until the user sends or discards them. As with
+//! [`email_removals`](crate::email_removals), only the request's metadata is
+//! stored; the text holds profile data, so it is rendered from the profile
+//! whenever the draft is shown.
+
+use crate::error::DatabaseError;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{Pool, Row, Sqlite};
+use std::fmt;
+use uuid::Uuid;
+
+/// Where a legal request is in its review.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub enum LegalRequestStatus {
+    /// Waiting for the user to review it
+    Draft,
+    /// The user sent it to the broker
+    Sent,
+    /// The user decided not to send it
+    Discarded,
+}
+
+impl LegalRequestStatus {
+    fn parse(status: &str) -> Option<Self> {
+        match status {
+            "Draft" => Some(Self::Draft),
+            "Sent" => Some(Self::Sent),
+            "Discarded" => Some(Self::Discarded),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for LegalRequestStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Draft => write!(f, "Draft"),
+            Self::Sent => write!(f, "Sent"),
+            Self::Discarded => write!(f, "Discarded"),
+        }
+    }
+}
+
+/// A legal deletion request drafted for a removal attempt.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LegalRequest {
+    /// Unique identifier
+    pub id: String,
+    /// Removal attempt that kept failing
+    pub attempt_id: String,
+    /// Broker the request is addressed to
+    pub broker_id: String,
+    /// Law the request cites (`ccpa`, `gdpr` or `generic`)
+    pub jurisdiction: String,
+    /// Broker's legal or privacy contact address
+    pub recipient: String,
+    /// Review status
+    pub status: LegalRequestStatus,
+    /// When the request was drafted
+    pub created_at: DateTime<Utc>,
+}
+
+/// Parameters for storing a drafted legal request
+#[derive(Debug)]
+pub struct CreateLegalRequest {
+    /// Removal attempt ID
+    pub attempt_id: String,
+    /// Broker ID
+    pub broker_id: String,
+    /// Law the request cites
+    pub jurisdiction: String,
+    /// Broker's legal contact address
+    pub recipient: String,
+}
+
+/// Store a drafted legal request for the user to review.
+///
+/// # Errors
+/// Returns `sqlx::Error` if the database insert fails, including when the
+/// attempt already has a legal request.
+pub async fn insert_legal_request(
+    pool: &Pool<Sqlite>,
+    params: CreateLegalRequest,
+) -> Result<LegalRequest, sqlx::Error> {
+    let id = Uuid::new_v4().to_string();
+    let created_at = Utc::now();
+
+    sqlx::query(
+        "INSERT INTO legal_requests (id, attempt_id, broker_id, jurisdiction, recipient, status, created_at)
+         VALUES (?, ?, ?, ?, ?, ?, ?)",
+    )
+    .bind(&id)
+    .bind(&params.attempt_id)
+    .bind(&params.broker_id)
+    .bind(&params.jurisdiction)
+    .bind(&params.recipient)
+    .bind(LegalRequestStatus::Draft.to_string())
+    .bind(created_at.to_rfc3339())
+    .execute(pool)
+    .await?;
+
+    Ok(LegalRequest {
+        id,
+        attempt_id: params.attempt_id,
+        broker_id: params.broker_id,
+        jurisdiction: params.jurisdiction,
+        recipient: params.recipient,
+        status: LegalRequestStatus::Draft,
+        created_at,
+    })
+}
+
+/// Get the legal request drafted for a removal attempt, if any.
+///
+/// # Errors
+/// Returns `sqlx::Error` if the database query fails.
+pub async fn get_by_attempt_id(
+    pool: &Pool<Sqlite>,
+    attempt_id: &str,
+) -> Result<Option<LegalRequest>, sqlx::Error> {
+    let rows = sqlx::query(
+        "SELECT id, attempt_id, broker_id, jurisdiction, recipient, status, created_at
+         FROM legal_requests
+         WHERE attempt_id = ?",
+    )
+    .bind(attempt_id)
+    .fetch_all(pool)
+    .await?;
+
+    Ok(parse_rows(rows)?.pop())
+}
+
+/// Get the drafts waiting for the user to review, oldest first.
+///
+/// # Errors
+/// Returns `sqlx::Error` if the database query fails.
+pub async fn get_review_queue(pool: &Pool<Sqlite>) -> Result<Vec<LegalRequest>, sqlx::Error> {
+    let rows = sqlx::query(
+        "SELECT id, attempt_id, broker_id, jurisdiction, recipient, status, created_at
+         FROM legal_requests
+         WHERE status = ?
+         ORDER BY created_at ASC, id ASC",
+    )
+    .bind(LegalRequestStatus::Draft.to_string())
+    .fetch_all(pool)
+    .await?;
+
+    parse_rows(rows)
+}
+
+/// Record that the user sent or discarded a legal request.
+///
+/// # Errors
+/// Returns [`DatabaseError::NotFound`] if there is no request with this ID,
+/// or [`DatabaseError::Sqlx`] if the database update fails.
+pub async fn update_status(
+    pool: &Pool<Sqlite>,
+    id: &str,
+    status: LegalRequestStatus,
+) -> Result<(), DatabaseError> {
+    let result = sqlx::query("UPDATE legal_requests SET status = ? WHERE id = ?")
+        .bind(status.to_string())
+        .bind(id)
+        .execute(pool)
+        .await?;
+
+    if result.rows_affected() == 0 {
+        return Err(DatabaseError::NotFound);
+    }
+    Ok(())
+}
+
+fn parse_rows(rows: Vec<sqlx::sqlite::SqliteRow>) -> Result<Vec<LegalRequest>, sqlx::Error> {
+    rows.into_iter()
+        .map(|row| {
+            let status: String = row.try_get("status")?;
+            let status = LegalRequestStatus::parse(&status).ok_or_else(|| {
+                sqlx::Error::Decode(format!("unknown legal request status {status:?}").into())
+            })?;
+            let created_at: String = row.try_get("created_at")?;
+            let created_at = DateTime::parse_from_rfc3339(&created_at)
+                .map_err(|e| sqlx::Error::Decode(Box::new(e)))?
+                .with_timezone(&Utc);
+
+            Ok(LegalRequest {
+                id: row.try_get("id")?,
+                attempt_id: row.try_get("attempt_id")?,
+                broker_id: row.try_get("broker_id")?,
+                jurisdiction: row.try_get("jurisdiction")?,
+                recipient: row.try_get("recipient")?,
+                status,
+                created_at,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::removal_attempts::create_removal_attempt;
+    use crate::Database;
+
+    async fn setup_test_db() -> Database {
+        let db = Database::new(":memory:", vec![0u8; 32])
+            .await
+            .expect("create database");
+        db.run_migrations().await.expect("run migrations");
+
+        // Removal attempts need a finding to belong to
+        let now = Utc::now().to_rfc3339();
+        for sql in [
+            "INSERT INTO profiles (id, data, nonce, created_at, updated_at) VALUES ('p1', x'00', x'00', ?1, ?1)",
+            "INSERT INTO scan_jobs (id, profile_id, started_at, status, total_brokers, completed_brokers) VALUES ('j1', 'p1', ?1, 'Completed', 1, 1)",
+            "INSERT INTO broker_scans (id, scan_job_id, broker_id, status, started_at) VALUES ('s1', 'j1', 'broker-1', 'Success', ?1)",
+            "INSERT INTO findings (id, broker_scan_id, broker_id, profile_id, listing_url, verification_status, extracted_data, discovered_at) VALUES ('finding-1', 's1', 'broker-1', 'p1', 'https://broker.example/1', 'Confirmed', '{}', ?1)",
+        ] {
+            sqlx::query(sql)
+                .bind(&now)
+                .execute(db.pool())
+                .await
+                .expect("seed row");
+        }
+        db
+    }
+
+    fn draft(attempt_id: &str) -> CreateLegalRequest {
+        CreateLegalRequest {
+            attempt_id: attempt_id.to_string(),
+            broker_id: "broker-1".to_string(),
+            jurisdiction: "gdpr".to_string(),
+            recipient: "dpo@broker.example".to_string(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_drafts_wait_for_review() {
+        let db = setup_test_db().await;
+        let attempt =
+            create_removal_attempt(db.pool(), "finding-1".to_string(), "broker-1".to_string())
+                .await
+                .expect("create removal attempt");
+
+        let request = insert_legal_request(db.pool(), draft(&attempt.id))
+            .await
+            .expect("insert legal request");
+        assert_eq!(request.status, LegalRequestStatus::Draft);
+
+        // One request per attempt
+        assert!(insert_legal_request(db.pool(), draft(&attempt.id))
+            .await
+            .is_err());
+
+        let stored = get_by_attempt_id(db.pool(), &attempt.id)
+            .await
+            .expect("get legal request")
+            .expect("request stored");
+        assert_eq!(stored.id, request.id);
+        assert_eq!(stored.jurisdiction, "gdpr");
+
+        let queue = get_review_queue(db.pool()).await.expect("review queue");
+        assert_eq!(queue.len(), 1);
+
+        update_status(db.pool(), &request.id, LegalRequestStatus::Sent)
+            .await
+            .expect("mark sent");
+        assert!(get_review_queue(db.pool())
+            .await
+            .expect("review queue")
+            .is_empty());
+        assert!(matches!(
+            update_status(db.pool(), "missing", LegalRequestStatus::Discarded).await,
+            Err(DatabaseError::NotFound)
+        ));
+    }
+}
@@ -35,19 +35,31 @@
 #![allow(clippy::missing_panics_doc)]
 
 pub mod broker_scans;
+pub mod changes;
 pub mod connection;
 pub mod discovery_findings;
+pub mod email_removals;
 pub mod error;
 pub mod findings;
+pub mod legal_requests;
 pub mod migrations;
+pub mod orphans;
+pub mod read_only;
 pub mod removal_attempts;
 /// Scan job management for tracking broker scan operations.
 pub mod scan_jobs;
 pub mod settings;
+pub mod stats;
+pub mod trends;
 
 // Re-export commonly used types
+pub use changes::DbChange;
 pub use connection::EncryptedPool;
 pub use error::{DatabaseError, Result};
+pub use orphans::{OrphanCount, OrphanReport};
+pub use read_only::ReadOnlyDb;
+pub use stats::TableStat;
+pub use trends::DayRange;
 
 use std::path::Path;
 
@@ -58,6 +70,7 @@ use std::path::Path;
 #[derive(Debug)]
 pub struct Database {
     pool: EncryptedPool,
+    changes: tokio::sync::broadcast::Sender<DbChange>,
 }
 
 impl Database {
@@ -74,7 +87,7 @@ impl Database {
     /// Returns `DatabaseError` if the database cannot be opened or the key is invalid.
     pub async fn new(path: impl AsRef<Path>, key: Vec<u8>) -> Result<Self> {
         let pool = EncryptedPool::new(path, key).await?;
-        Ok(Self { pool })
+        Ok(Self::from_encrypted_pool(pool))
     }
 
     /// Create a database instance from an existing encrypted pool.
@@ -86,7 +99,10 @@ impl Database {
     /// * `pool` - An existing encrypted connection pool
     #[must_use]
     pub fn from_encrypted_pool(pool: EncryptedPool) -> Self {
-        Self { pool }
+        Self {
+            pool,
+            changes: changes::channel(),
+        }
     }
 
     /// Run all pending database migrations.
@@ -135,6 +151,35 @@ impl Database {
         self.pool.verify_key().await
     }
 
+    /// Re-encrypt the database file at `path` under `new_key`, returning the
+    /// database reopened with it.
+    ///
+    /// See [`EncryptedPool::rekey`]. This database's pool is closed;
+    /// listeners from [`listen_for_changes`](Self::listen_for_changes) keep
+    /// receiving changes made through the returned database.
+    ///
+    /// # Errors
+    /// Returns `DatabaseError` if the file cannot be re-encrypted or reopened.
+    pub async fn rekey(&self, path: impl AsRef<Path>, new_key: Vec<u8>) -> Result<Self> {
+        let pool = self.pool.rekey(path, new_key).await?;
+        Ok(Self {
+            pool,
+            changes: self.changes.clone(),
+        })
+    }
+
+    /// Write a consistent, encrypted copy of the database to `dest` while it
+    /// stays in use.
+    ///
+    /// See [`EncryptedPool::backup_to`].
+    ///
+    /// # Errors
+    /// Returns `DatabaseError::Backup` if the copy cannot be written or does
+    /// not verify with this database's key.
+    pub async fn backup_to(&self, dest: impl AsRef<Path>) -> Result<()> {
+        self.pool.backup_to(dest).await
+    }
+
     /// Close the database connection gracefully.
     ///
     /// This ensures all connections are properly closed and resources are cleaned up.
@@ -144,8 +189,22 @@ impl Database {
 
     /// Get all scheduled jobs
     pub async fn get_scheduled_jobs(&self) -> Result<Vec<spectral_scheduler::ScheduledJob>> {
-        let rows = sqlx::query_as::<_, (String, String, i64, String, Option<String>, i64)>(
-            r"SELECT id, job_type, interval_days, next_run_at, last_run_at, enabled
+        type JobRow = (
+            String,
+            String,
+            i64,
+            String,
+            Option<String>,
+            i64,
+            Option<String>,
+            Option<String>,
+            i64,
+            Option<String>,
+        );
+
+        let rows = sqlx::query_as::<_, JobRow>(
+            r"SELECT id, job_type, interval_days, next_run_at, last_run_at, enabled,
+                      quiet_hours_start, quiet_hours_end, skip_on_metered, deferred_reason
                FROM scheduled_jobs",
         )
         .fetch_all(self.pool.pool())
@@ -154,13 +213,40 @@ impl Database {
         let jobs: Result<Vec<_>> = rows
             .into_iter()
             .map(
-                |(id, job_type_str, interval_days, next_run_at, last_run_at, enabled)| {
+                |(
+                    id,
+                    job_type_str,
+                    interval_days,
+                    next_run_at,
+                    last_run_at,
+                    enabled,
+                    quiet_start,
+                    quiet_end,
+                    skip_on_metered,
+                    deferred_reason,
+                )| {
                     let job_type: spectral_scheduler::JobType =
                         serde_json::from_str(&format!("\"{job_type_str}\"")).map_err(|e| {
                             DatabaseError::Decode(format!(
                                 "Invalid job_type '{job_type_str}' in scheduled_jobs table: {e}"
                             ))
                         })?;
+                    let quiet_hours = match (quiet_start, quiet_end) {
+                        (Some(start), Some(end)) => Some(spectral_scheduler::QuietHours::new(
+                            parse_quiet_hours_time(&id, &start)?,
+                            parse_quiet_hours_time(&id, &end)?,
+                        )),
+                        _ => None,
+                    };
+                    let deferred_reason = deferred_reason
+                        .map(|reason| {
+                            spectral_scheduler::DeferReason::parse(&reason).ok_or_else(|| {
+                                DatabaseError::Decode(format!(
+                                    "Invalid deferred_reason '{reason}' for scheduled job '{id}'"
+                                ))
+                            })
+                        })
+                        .transpose()?;
                     #[allow(clippy::cast_sign_loss, clippy::cast_possible_truncation)]
                     let interval_days = interval_days as u32;
                     Ok(spectral_scheduler::ScheduledJob {
@@ -170,6 +256,9 @@ impl Database {
                         next_run_at,
                         last_run_at,
                         enabled: enabled != 0,
+                        quiet_hours,
+                        skip_on_metered: skip_on_metered != 0,
+                        deferred_reason,
                     })
                 },
             )
@@ -179,19 +268,144 @@ impl Database {
     }
 
     /// Update job's `next_run_at` and `last_run_at` timestamps
+    ///
+    /// Called after a job runs, so any recorded deferral is cleared.
     pub async fn update_job_next_run(
         &self,
         job_id: &str,
         next_run_at: &str,
         last_run_at: &str,
     ) -> Result<()> {
-        let result =
-            sqlx::query("UPDATE scheduled_jobs SET next_run_at = ?, last_run_at = ? WHERE id = ?")
-                .bind(next_run_at)
-                .bind(last_run_at)
+        let result = sqlx::query(
+            "UPDATE scheduled_jobs SET next_run_at = ?, last_run_at = ?, deferred_reason = NULL
+             WHERE id = ?",
+        )
+        .bind(next_run_at)
+        .bind(last_run_at)
+        .bind(job_id)
+        .execute(self.pool.pool())
+        .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(DatabaseError::NotFoundWithMessage(format!(
+                "Scheduled job '{job_id}' not found"
+            )));
+        }
+
+        Ok(())
+    }
+
+    /// Defer a due job to `next_run_at`, recording why it did not run
+    pub async fn defer_job(
+        &self,
+        job_id: &str,
+        next_run_at: &str,
+        reason: spectral_scheduler::DeferReason,
+    ) -> Result<()> {
+        let result = sqlx::query(
+            "UPDATE scheduled_jobs SET next_run_at = ?, deferred_reason = ? WHERE id = ?",
+        )
+        .bind(next_run_at)
+        .bind(reason.as_str())
+        .bind(job_id)
+        .execute(self.pool.pool())
+        .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(DatabaseError::NotFoundWithMessage(format!(
+                "Scheduled job '{job_id}' not found"
+            )));
+        }
+
+        tracing::info!(
+            "Deferred scheduled job {} to {} ({})",
+            job_id,
+            next_run_at,
+            reason.as_str()
+        );
+        Ok(())
+    }
+
+    /// Set a job's quiet hours and metered-connection preference
+    pub async fn update_job_conditions(
+        &self,
+        job_id: &str,
+        quiet_hours: Option<spectral_scheduler::QuietHours>,
+        skip_on_metered: bool,
+    ) -> Result<()> {
+        let format = |t: chrono::NaiveTime| t.format("%H:%M").to_string();
+        let result = sqlx::query(
+            "UPDATE scheduled_jobs
+             SET quiet_hours_start = ?, quiet_hours_end = ?, skip_on_metered = ?
+             WHERE id = ?",
+        )
+        .bind(quiet_hours.map(|q| format(q.start)))
+        .bind(quiet_hours.map(|q| format(q.end)))
+        .bind(i64::from(skip_on_metered))
+        .bind(job_id)
+        .execute(self.pool.pool())
+        .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(DatabaseError::NotFoundWithMessage(format!(
+                "Scheduled job '{job_id}' not found"
+            )));
+        }
+
+        Ok(())
+    }
+
+    /// Enable or disable a scheduled job
+    ///
+    /// A disabled job keeps its `next_run_at`; the scheduler skips it until it
+    /// is enabled again, at which point it runs if that time has passed.
+    pub async fn set_job_enabled(&self, job_id: &str, enabled: bool) -> Result<()> {
+        let result = sqlx::query("UPDATE scheduled_jobs SET enabled = ? WHERE id = ?")
+            .bind(i64::from(enabled))
+            .bind(job_id)
+            .execute(self.pool.pool())
+            .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(DatabaseError::NotFoundWithMessage(format!(
+                "Scheduled job '{job_id}' not found"
+            )));
+        }
+
+        Ok(())
+    }
+
+    /// Change how often a job runs, rescheduling its next run to match
+    ///
+    /// The next run is `interval_days` after the job last ran, or after now if
+    /// it has never run, so shortening the interval of a job that ran long
+    /// ago makes it due straight away. Any recorded deferral is cleared.
+    pub async fn set_job_interval(&self, job_id: &str, interval_days: u32) -> Result<()> {
+        let last_run_at: Option<Option<String>> =
+            sqlx::query_scalar("SELECT last_run_at FROM scheduled_jobs WHERE id = ?")
                 .bind(job_id)
-                .execute(self.pool.pool())
+                .fetch_optional(self.pool.pool())
                 .await?;
+        let Some(last_run_at) = last_run_at else {
+            return Err(DatabaseError::NotFoundWithMessage(format!(
+                "Scheduled job '{job_id}' not found"
+            )));
+        };
+
+        let base = last_run_at
+            .and_then(|ts| chrono::DateTime::parse_from_rfc3339(&ts).ok())
+            .map_or_else(chrono::Utc::now, |ts| ts.with_timezone(&chrono::Utc));
+        let next_run_at = (base + chrono::Duration::days(i64::from(interval_days))).to_rfc3339();
+
+        let result = sqlx::query(
+            "UPDATE scheduled_jobs SET interval_days = ?, next_run_at = ?, deferred_reason = NULL
+             WHERE id = ?",
+        )
+        .bind(i64::from(interval_days))
+        .bind(&next_run_at)
+        .bind(job_id)
+        .execute(self.pool.pool())
+        .await?;
 
         if result.rows_affected() == 0 {
             return Err(DatabaseError::NotFoundWithMessage(format!(
@@ -199,10 +413,25 @@ impl Database {
             )));
         }
 
+        tracing::info!(
+            "Scheduled job {} now runs every {} days, next at {}",
+            job_id,
+            interval_days,
+            next_run_at
+        );
         Ok(())
     }
 }
 
+/// Parse a stored `HH:MM` quiet-hours boundary.
+fn parse_quiet_hours_time(job_id: &str, value: &str) -> Result<chrono::NaiveTime> {
+    chrono::NaiveTime::parse_from_str(value, "%H:%M").map_err(|e| {
+        DatabaseError::Decode(format!(
+            "Invalid quiet hours time '{value}' for scheduled job '{job_id}': {e}"
+        ))
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -230,7 +459,7 @@ mod tests {
         db.run_migrations().await.expect("run migrations");
 
         let version_after = db.get_schema_version().await.expect("get version");
-        assert_eq!(version_after, 10);
+        assert_eq!(version_after, 25);
     }
 
     #[tokio::test]
@@ -253,13 +482,18 @@ mod tests {
         assert_eq!(
             tables,
             vec![
+                "attachments",
                 "audit_log",
                 "broker_results",
                 "broker_scans",
                 "discovery_findings",
                 "email_removals",
+                "finding_reopenings",
                 "findings",
+                "legal_requests",
+                "profile_fields",
                 "profiles",
+                "rekey_progress",
                 "removal_attempts",
                 "removal_evidence",
                 "scan_jobs",
@@ -277,7 +511,14 @@ mod tests {
 
         assert_eq!(
             profile_columns,
-            vec!["id", "data", "nonce", "created_at", "updated_at"]
+            vec![
+                "id",
+                "data",
+                "nonce",
+                "created_at",
+                "updated_at",
+                "storage_version"
+            ]
         );
     }
 
@@ -290,6 +531,102 @@ mod tests {
 
         db.close().await; // Should not panic
     }
+
+    #[tokio::test]
+    async fn test_backup_while_writing() {
+        let dir = tempfile::tempdir().expect("create temp dir");
+        let key = vec![7u8; 32];
+        let db = std::sync::Arc::new(
+            Database::new(dir.path().join("live.db"), key.clone())
+                .await
+                .expect("create database"),
+        );
+        db.run_migrations().await.expect("run migrations");
+
+        // Each transaction writes a pair of rows, so a consistent copy holds
+        // as many `a` rows as `b` rows
+        let writer = {
+            let db = std::sync::Arc::clone(&db);
+            tokio::spawn(async move {
+                for i in 0..200 {
+                    let mut tx = db.pool().begin().await.expect("begin");
+                    for prefix in ["a", "b"] {
+                        sqlx::query("INSERT INTO settings (key, value) VALUES (?, ?)")
+                            .bind(format!("{prefix}-{i}"))
+                            .bind(i.to_string())
+                            .execute(&mut *tx)
+                            .await
+                            .expect("insert setting");
+                    }
+                    tx.commit().await.expect("commit");
+                    tokio::task::yield_now().await;
+                }
+            })
+        };
+
+        let backup_path = dir.path().join("backup.db");
+        tokio::time::sleep(std::time::Duration::from_millis(20)).await;
+        db.backup_to(&backup_path).await.expect("back up");
+        writer.await.expect("writer finished");
+
+        // Never overwrite an existing file
+        assert!(matches!(
+            db.backup_to(&backup_path).await,
+            Err(DatabaseError::Backup(_))
+        ));
+
+        let backup = Database::new(&backup_path, key)
+            .await
+            .expect("open backup with the same key");
+        backup.verify_key().await.expect("backup readable with key");
+        assert_eq!(backup.get_schema_version().await.expect("version"), 25);
+
+        let count = |prefix: &'static str| {
+            sqlx::query_scalar::<_, i64>("SELECT COUNT(*) FROM settings WHERE key LIKE ?")
+                .bind(format!("{prefix}-%"))
+                .fetch_one(backup.pool())
+        };
+        let a = count("a").await.expect("count a rows");
+        let b = count("b").await.expect("count b rows");
+        assert_eq!(a, b, "backup holds half of a transaction");
+        assert!(a <= 200);
+    }
+
+    #[tokio::test]
+    async fn test_rekey_reopens_with_new_key() {
+        let dir = tempfile::tempdir().expect("create temp dir");
+        let path = dir.path().join("vault.db");
+        let db = Database::new(&path, vec![1u8; 32])
+            .await
+            .expect("create database");
+        db.run_migrations().await.expect("run migrations");
+        crate::settings::set_setting(db.pool(), "kept", &serde_json::json!(true))
+            .await
+            .expect("save setting");
+        let mut changes = db.listen_for_changes();
+
+        let rekeyed = db.rekey(&path, vec![2u8; 32]).await.expect("rekey");
+        assert!(db.pool().is_closed());
+        rekeyed.verify_key().await.expect("verify new key");
+        assert_eq!(
+            crate::settings::get_setting(rekeyed.pool(), "kept")
+                .await
+                .expect("read setting"),
+            Some(serde_json::json!(true))
+        );
+
+        let change = DbChange::ScanStatusChanged {
+            scan_job_id: "job-1".to_string(),
+            status: "Completed".to_string(),
+        };
+        rekeyed.notify(change.clone());
+        assert_eq!(changes.try_recv().expect("change received"), change);
+
+        assert!(matches!(
+            rekeyed.rekey(&path, vec![3u8; 16]).await,
+            Err(DatabaseError::InvalidKey)
+        ));
+    }
 }
 
 #[cfg(test)]
@@ -329,6 +666,159 @@ mod migration_tests {
             .expect("removal_evidence table must exist");
     }
 
+    #[tokio::test]
+    async fn test_013_removal_attempts_rebuild_keeps_references() {
+        let key = vec![0u8; 32];
+        let db = Database::new(":memory:", key)
+            .await
+            .expect("create database");
+        db.run_migrations().await.expect("run migrations");
+
+        let now = "2026-01-01T00:00:00Z";
+        for sql in [
+            "INSERT INTO profiles (id, data, nonce, created_at, updated_at) VALUES ('p1', x'00', x'00', ?1, ?1)",
+            "INSERT INTO scan_jobs (id, profile_id, started_at, status, total_brokers, completed_brokers) VALUES ('j1', 'p1', ?1, 'Completed', 1, 1)",
+            "INSERT INTO broker_scans (id, scan_job_id, broker_id, status, started_at) VALUES ('s1', 'j1', 'broker', 'Success', ?1)",
+            "INSERT INTO findings (id, broker_scan_id, broker_id, profile_id, listing_url, verification_status, extracted_data, discovered_at) VALUES ('f1', 's1', 'broker', 'p1', 'https://broker.example/1', 'Confirmed', '{}', ?1)",
+            "INSERT INTO removal_attempts (id, finding_id, broker_id, status, created_at) VALUES ('a1', 'f1', 'broker', 'Submitted', ?1)",
+            "INSERT INTO email_removals (id, attempt_id, broker_id, sent_at, method, recipient, subject, body_hash) VALUES ('e1', 'a1', 'broker', ?1, 'smtp', 'optout@broker.example', 'Opt-Out', 'hash')",
+        ] {
+            sqlx::query(sql)
+                .bind(now)
+                .execute(db.pool())
+                .await
+                .expect("seed row");
+        }
+
+        // Rebuilding again with referencing rows present must keep them intact
+        let mut tx = db.pool().begin().await.expect("begin");
+        sqlx::raw_sql(include_str!(
+            "../migrations/013_removal_needs_user_action.sql"
+        ))
+        .execute(&mut *tx)
+        .await
+        .expect("rebuild removal_attempts");
+        tx.commit().await.expect("commit rebuild");
+
+        let attempt = removal_attempts::get_by_id(db.pool(), "a1")
+            .await
+            .expect("get attempt")
+            .expect("attempt kept");
+        assert_eq!(attempt.status, removal_attempts::RemovalStatus::Submitted);
+        let logged = email_removals::get_by_attempt_id(db.pool(), "a1")
+            .await
+            .expect("get email removals");
+        assert_eq!(logged.len(), 1);
+
+        sqlx::query("UPDATE removal_attempts SET status = 'NeedsUserAction' WHERE id = 'a1'")
+            .execute(db.pool())
+            .await
+            .expect("NeedsUserAction is an allowed status");
+    }
+
+    #[tokio::test]
+    async fn test_017_moves_captcha_attempts_out_of_pending() {
+        let key = vec![0u8; 32];
+        let db = Database::new(":memory:", key)
+            .await
+            .expect("create database");
+        db.run_migrations().await.expect("run migrations");
+
+        let now = "2026-01-01T00:00:00Z";
+        for sql in [
+            "INSERT INTO profiles (id, data, nonce, created_at, updated_at) VALUES ('p1', x'00', x'00', ?1, ?1)",
+            "INSERT INTO scan_jobs (id, profile_id, started_at, status, total_brokers, completed_brokers) VALUES ('j1', 'p1', ?1, 'Completed', 1, 1)",
+            "INSERT INTO broker_scans (id, scan_job_id, broker_id, status, started_at) VALUES ('s1', 'j1', 'broker', 'Success', ?1)",
+            "INSERT INTO findings (id, broker_scan_id, broker_id, profile_id, listing_url, verification_status, extracted_data, discovered_at) VALUES ('f1', 's1', 'broker', 'p1', 'https://broker.example/1', 'Confirmed', '{}', ?1)",
+            "INSERT INTO removal_attempts (id, finding_id, broker_id, status, created_at, error_message) VALUES ('a1', 'f1', 'broker', 'Pending', ?1, 'CAPTCHA_REQUIRED:https://broker.example/optout')",
+            "INSERT INTO removal_attempts (id, finding_id, broker_id, status, created_at, error_message) VALUES ('a2', 'f1', 'broker', 'Pending', ?1, 'Queued')",
+        ] {
+            sqlx::query(sql)
+                .bind(now)
+                .execute(db.pool())
+                .await
+                .expect("seed row");
+        }
+
+        let mut tx = db.pool().begin().await.expect("begin");
+        sqlx::raw_sql(include_str!("../migrations/017_removal_cancelled.sql"))
+            .execute(&mut *tx)
+            .await
+            .expect("rebuild removal_attempts");
+        tx.commit().await.expect("commit rebuild");
+
+        let pool = db.pool();
+        let status = |id: &'static str| async move {
+            removal_attempts::get_by_id(pool, id)
+                .await
+                .expect("get attempt")
+                .expect("attempt kept")
+                .status
+        };
+        assert_eq!(
+            status("a1").await,
+            removal_attempts::RemovalStatus::NeedsUserAction
+        );
+        assert_eq!(status("a2").await, removal_attempts::RemovalStatus::Pending);
+
+        sqlx::query("UPDATE removal_attempts SET status = 'Cancelled' WHERE id = 'a2'")
+            .execute(db.pool())
+            .await
+            .expect("Cancelled is an allowed status");
+    }
+
+    #[tokio::test]
+    async fn test_021_broker_scans_rebuild_keeps_findings() {
+        let key = vec![0u8; 32];
+        let db = Database::new(":memory:", key)
+            .await
+            .expect("create database");
+        db.run_migrations().await.expect("run migrations");
+
+        let now = "2026-01-01T00:00:00Z";
+        for sql in [
+            "INSERT INTO profiles (id, data, nonce, created_at, updated_at) VALUES ('p1', x'00', x'00', ?1, ?1)",
+            "INSERT INTO scan_jobs (id, profile_id, started_at, status, total_brokers, completed_brokers) VALUES ('j1', 'p1', ?1, 'Completed', 1, 1)",
+            "INSERT INTO broker_scans (id, scan_job_id, broker_id, status, started_at) VALUES ('s1', 'j1', 'broker', 'Success', ?1)",
+            "INSERT INTO findings (id, broker_scan_id, broker_id, profile_id, listing_url, verification_status, extracted_data, discovered_at) VALUES ('f1', 's1', 'broker', 'p1', 'https://broker.example/1', 'Confirmed', '{}', ?1)",
+        ] {
+            sqlx::query(sql)
+                .bind(now)
+                .execute(db.pool())
+                .await
+                .expect("seed row");
+        }
+
+        // Re-run the rebuild with findings present, with foreign keys off as
+        // migrations run
+        let mut conn = db.pool().acquire().await.expect("acquire connection");
+        sqlx::query("PRAGMA foreign_keys = OFF")
+            .execute(conn.as_mut())
+            .await
+            .expect("disable foreign keys");
+        sqlx::raw_sql(include_str!("../migrations/021_broker_scan_cancelled.sql"))
+            .execute(conn.as_mut())
+            .await
+            .expect("rebuild broker_scans");
+        sqlx::query("PRAGMA foreign_keys = ON")
+            .execute(conn.as_mut())
+            .await
+            .expect("enable foreign keys");
+        drop(conn);
+
+        let findings: i64 =
+            sqlx::query_scalar("SELECT COUNT(*) FROM findings WHERE broker_scan_id = 's1'")
+                .fetch_one(db.pool())
+                .await
+                .expect("count findings");
+        assert_eq!(findings, 1);
+
+        sqlx::query("UPDATE broker_scans SET status = 'Cancelled' WHERE id = 's1'")
+            .execute(db.pool())
+            .await
+            .expect("Cancelled is an allowed status");
+    }
+
     #[tokio::test]
     async fn test_008_scheduled_jobs_migration() {
         let key = vec![0u8; 32];
@@ -447,6 +937,144 @@ mod migration_tests {
             Some("2026-02-01T00:00:00Z".to_string())
         );
     }
+
+    #[tokio::test]
+    async fn test_job_conditions_and_deferral_round_trip() {
+        use spectral_scheduler::{DeferReason, QuietHours};
+
+        let key = vec![0u8; 32];
+        let db = Database::new(":memory:", key)
+            .await
+            .expect("create database");
+        db.run_migrations().await.expect("run migrations");
+
+        let quiet_hours = QuietHours::new(
+            chrono::NaiveTime::from_hms_opt(22, 0, 0).expect("valid time"),
+            chrono::NaiveTime::from_hms_opt(7, 30, 0).expect("valid time"),
+        );
+        db.update_job_conditions("default-scan-all", Some(quiet_hours), true)
+            .await
+            .expect("update conditions");
+        db.defer_job(
+            "default-scan-all",
+            "2026-02-18T07:30:00Z",
+            DeferReason::QuietHours,
+        )
+        .await
+        .expect("defer job");
+
+        let jobs = db.get_scheduled_jobs().await.expect("get jobs");
+        let job = jobs
+            .iter()
+            .find(|j| j.id == "default-scan-all")
+            .expect("scan-all job");
+        assert_eq!(job.quiet_hours, Some(quiet_hours));
+        assert!(job.skip_on_metered);
+        assert_eq!(job.next_run_at, "2026-02-18T07:30:00Z");
+        assert_eq!(job.deferred_reason, Some(DeferReason::QuietHours));
+
+        // Running the job clears the recorded deferral
+        db.update_job_next_run(
+            "default-scan-all",
+            "2026-02-25T07:30:00Z",
+            "2026-02-18T07:30:00Z",
+        )
+        .await
+        .expect("update next run");
+        let jobs = db.get_scheduled_jobs().await.expect("get jobs");
+        let job = jobs
+            .iter()
+            .find(|j| j.id == "default-scan-all")
+            .expect("scan-all job");
+        assert_eq!(job.deferred_reason, None);
+        assert_eq!(job.quiet_hours, Some(quiet_hours));
+
+        let result = db
+            .defer_job(
+                "missing",
+                "2026-02-18T07:30:00Z",
+                DeferReason::MeteredConnection,
+            )
+            .await;
+        assert!(matches!(result, Err(DatabaseError::NotFoundWithMessage(_))));
+    }
+
+    #[tokio::test]
+    async fn test_set_job_enabled_and_interval() {
+        let key = vec![0u8; 32];
+        let db = Database::new(":memory:", key)
+            .await
+            .expect("create database");
+        db.run_migrations().await.expect("run migrations");
+
+        let find = |jobs: Vec<spectral_scheduler::ScheduledJob>| {
+            jobs.into_iter()
+                .find(|j| j.id == "default-scan-all")
+                .expect("scan-all job")
+        };
+
+        db.set_job_enabled("default-scan-all", false)
+            .await
+            .expect("disable job");
+        let job = find(db.get_scheduled_jobs().await.expect("get jobs"));
+        assert!(!job.enabled);
+        db.set_job_enabled("default-scan-all", true)
+            .await
+            .expect("enable job");
+        let job = find(db.get_scheduled_jobs().await.expect("get jobs"));
+        assert!(job.enabled);
+
+        // Next run is counted from the last run
+        db.update_job_next_run(
+            "default-scan-all",
+            "2026-03-20T09:00:00+00:00",
+            "2026-02-18T09:00:00+00:00",
+        )
+        .await
+        .expect("update next run");
+        db.set_job_interval("default-scan-all", 14)
+            .await
+            .expect("set interval");
+        let job = find(db.get_scheduled_jobs().await.expect("get jobs"));
+        assert_eq!(job.interval_days, 14);
+        assert_eq!(job.next_run_at, "2026-03-04T09:00:00+00:00");
+
+        let missing = db.set_job_enabled("missing", true).await;
+        assert!(matches!(
+            missing,
+            Err(DatabaseError::NotFoundWithMessage(_))
+        ));
+        let missing = db.set_job_interval("missing", 7).await;
+        assert!(matches!(
+            missing,
+            Err(DatabaseError::NotFoundWithMessage(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_set_job_interval_without_previous_run() {
+        let key = vec![0u8; 32];
+        let db = Database::new(":memory:", key)
+            .await
+            .expect("create database");
+        db.run_migrations().await.expect("run migrations");
+
+        let before = chrono::Utc::now();
+        db.set_job_interval("default-scan-all", 3)
+            .await
+            .expect("set interval");
+        let after = chrono::Utc::now();
+
+        let jobs = db.get_scheduled_jobs().await.expect("get jobs");
+        let job = jobs
+            .iter()
+            .find(|j| j.id == "default-scan-all")
+            .expect("scan-all job");
+        assert!(job.last_run_at.is_none());
+        let next = chrono::DateTime::parse_from_rfc3339(&job.next_run_at).expect("valid timestamp");
+        assert!(next >= before + chrono::Duration::days(3));
+        assert!(next <= after + chrono::Duration::days(3));
+    }
 }
 
 #[cfg(test)]
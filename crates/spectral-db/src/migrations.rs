@@ -4,7 +4,11 @@
 //! Uses `SQLx`'s built-in migration support with compile-time embedding.
 
 use crate::error::{DatabaseError, Result};
-use sqlx::{Pool, Sqlite};
+use sqlx::migrate::Migrator;
+use sqlx::{Pool, Sqlite, SqliteConnection};
+
+/// Migrations embedded from the `migrations/` directory.
+static MIGRATOR: Migrator = sqlx::migrate!("./migrations");
 
 /// Run all pending database migrations.
 ///
@@ -12,20 +16,84 @@ use sqlx::{Pool, Sqlite};
 /// haven't been applied yet. It uses `SQLx`'s built-in migration system which
 /// tracks applied migrations in a `_sqlx_migrations` table.
 ///
+/// Migrations run with foreign keys off, as `SQLite` requires for rebuilding a
+/// table: dropping the old table would otherwise cascade-delete the rows
+/// referencing it. The pragma cannot change inside the transaction each
+/// migration runs in, so it is set on the connection beforehand.
+///
+/// Before anything is applied, every migration already recorded is checked
+/// against the embedded file of the same version, so an edited migration
+/// stops startup instead of leaving installs with diverging schemas.
+///
 /// # Errors
-/// Returns `DatabaseError::Migration` if any migration fails to execute.
+/// Returns `DatabaseError::MigrationMismatch` if an applied migration's
+/// checksum differs from the embedded one, or `DatabaseError::Migration` if
+/// any migration fails to execute.
 pub async fn run_migrations(pool: &Pool<Sqlite>) -> Result<()> {
     tracing::info!("Running database migrations");
 
-    sqlx::migrate!("./migrations")
-        .run(pool)
-        .await
-        .map_err(|e| DatabaseError::Migration(format!("migration execution failed: {e}")))?;
+    let mut conn = pool.acquire().await?;
+    verify_checksums(&mut conn).await?;
+
+    sqlx::query("PRAGMA foreign_keys = OFF")
+        .execute(&mut *conn)
+        .await?;
+
+    let result = MIGRATOR.run(&mut *conn).await;
+
+    // Restore enforcement before the connection goes back to the pool
+    sqlx::query("PRAGMA foreign_keys = ON")
+        .execute(&mut *conn)
+        .await?;
+    result.map_err(|e| DatabaseError::Migration(format!("migration execution failed: {e}")))?;
 
     tracing::info!("Database migrations completed successfully");
     Ok(())
 }
 
+/// Check each applied migration against the embedded migration of the same
+/// version.
+///
+/// Applied versions this build does not embed are left to the migrator.
+async fn verify_checksums(conn: &mut SqliteConnection) -> Result<()> {
+    let table_exists = sqlx::query_scalar::<_, i64>(
+        "SELECT COUNT(*) FROM sqlite_master WHERE type='table' AND name='_sqlx_migrations'",
+    )
+    .fetch_one(&mut *conn)
+    .await?
+        > 0;
+    if !table_exists {
+        return Ok(());
+    }
+
+    let applied = sqlx::query_as::<_, (i64, Vec<u8>)>(
+        "SELECT version, checksum FROM _sqlx_migrations WHERE success = 1 ORDER BY version",
+    )
+    .fetch_all(&mut *conn)
+    .await?;
+
+    for (version, checksum) in applied {
+        let embedded = MIGRATOR
+            .iter()
+            .find(|m| m.version == version && !m.migration_type.is_down_migration());
+        if let Some(migration) = embedded {
+            if *migration.checksum != *checksum {
+                tracing::error!(
+                    "Migration {} ({}) was edited after it was applied",
+                    version,
+                    migration.description
+                );
+                return Err(DatabaseError::MigrationMismatch {
+                    version,
+                    description: migration.description.to_string(),
+                });
+            }
+        }
+    }
+
+    Ok(())
+}
+
 /// Get the current schema version.
 ///
 /// Returns the number of applied migrations. Returns 0 if no migrations
@@ -80,13 +148,18 @@ mod tests {
         assert_eq!(
             tables,
             vec![
+                "attachments",
                 "audit_log",
                 "broker_results",
                 "broker_scans",
                 "discovery_findings",
                 "email_removals",
+                "finding_reopenings",
                 "findings",
+                "legal_requests",
+                "profile_fields",
                 "profiles",
+                "rekey_progress",
                 "removal_attempts",
                 "removal_evidence",
                 "scan_jobs",
@@ -111,7 +184,7 @@ mod tests {
         run_migrations(pool.pool()).await.expect("run migrations");
 
         let version = get_schema_version(pool.pool()).await.expect("get version");
-        assert_eq!(version, 10); // Ten migrations applied
+        assert_eq!(version, 25); // Twenty-five migrations applied
     }
 
     #[tokio::test]
@@ -131,6 +204,35 @@ mod tests {
             .expect("second migration run should be idempotent");
 
         let version = get_schema_version(pool.pool()).await.expect("get version");
-        assert_eq!(version, 10);
+        assert_eq!(version, 25);
+    }
+
+    #[tokio::test]
+    async fn test_edited_migration_is_rejected() {
+        let key = vec![0u8; 32];
+        let pool = EncryptedPool::new(":memory:", key)
+            .await
+            .expect("create encrypted pool");
+        run_migrations(pool.pool()).await.expect("run migrations");
+
+        // As if migration 5 had been applied from a different file
+        sqlx::query("UPDATE _sqlx_migrations SET checksum = x'00' WHERE version = 5")
+            .execute(pool.pool())
+            .await
+            .expect("alter recorded checksum");
+
+        let err = run_migrations(pool.pool())
+            .await
+            .expect_err("checksum mismatch");
+        match err {
+            DatabaseError::MigrationMismatch {
+                version,
+                description,
+            } => {
+                assert_eq!(version, 5);
+                assert_eq!(description, "audit log");
+            }
+            other => panic!("expected MigrationMismatch, got {other:?}"),
+        }
     }
 }
@@ -0,0 +1,304 @@
+//! Detection and cleanup of rows whose parent row is gone.
+//!
+//! Databases written before foreign keys were enforced, or by partial
+//! deletes, can hold evidence for a deleted removal attempt or findings for a
+//! deleted broker scan. [`Database::find_orphans`] reports such rows and
+//! [`Database::clean_orphans`] removes them, for use by the maintenance
+//! action.
+
+use crate::error::Result;
+use crate::Database;
+use serde::{Deserialize, Serialize};
+
+/// A child column that must point at an existing parent row.
+struct Relation {
+    child: &'static str,
+    column: &'static str,
+    parent: &'static str,
+    /// The reference is optional: orphans are detached (set to NULL) rather
+    /// than deleted
+    nullable: bool,
+}
+
+/// Relations checked for orphans, parents before children, so rows orphaned
+/// by an earlier cleanup step are caught by a later one.
+const RELATIONS: &[Relation] = &[
+    Relation {
+        child: "broker_scans",
+        column: "scan_job_id",
+        parent: "scan_jobs",
+        nullable: false,
+    },
+    Relation {
+        child: "findings",
+        column: "broker_scan_id",
+        parent: "broker_scans",
+        nullable: false,
+    },
+    Relation {
+        child: "removal_attempts",
+        column: "finding_id",
+        parent: "findings",
+        nullable: false,
+    },
+    Relation {
+        child: "findings",
+        column: "removal_attempt_id",
+        parent: "removal_attempts",
+        nullable: true,
+    },
+    Relation {
+        child: "removal_evidence",
+        column: "attempt_id",
+        parent: "removal_attempts",
+        nullable: false,
+    },
+    Relation {
+        child: "email_removals",
+        column: "attempt_id",
+        parent: "removal_attempts",
+        nullable: true,
+    },
+    Relation {
+        child: "profile_fields",
+        column: "profile_id",
+        parent: "profiles",
+        nullable: false,
+    },
+];
+
+/// Orphaned rows found (or cleaned) for one child→parent relation.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OrphanCount {
+    /// Table holding the orphaned rows
+    pub table: String,
+    /// Column referencing the missing parent
+    pub column: String,
+    /// Table the column references
+    pub parent: String,
+    /// Number of orphaned rows
+    pub count: i64,
+}
+
+/// Orphaned rows per relation; relations without orphans are left out.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OrphanReport {
+    /// Relations that had orphans, parents before children
+    pub orphans: Vec<OrphanCount>,
+}
+
+impl OrphanReport {
+    /// Whether no orphans were found.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.orphans.is_empty()
+    }
+
+    /// Total orphaned rows across all relations.
+    #[must_use]
+    pub fn total(&self) -> i64 {
+        self.orphans.iter().map(|o| o.count).sum()
+    }
+
+    /// Orphaned rows in `table`, across all of its relations.
+    #[must_use]
+    pub fn count_for(&self, table: &str) -> i64 {
+        self.orphans
+            .iter()
+            .filter(|o| o.table == table)
+            .map(|o| o.count)
+            .sum()
+    }
+
+    fn push(&mut self, relation: &Relation, count: i64) {
+        if count > 0 {
+            self.orphans.push(OrphanCount {
+                table: relation.child.to_string(),
+                column: relation.column.to_string(),
+                parent: relation.parent.to_string(),
+                count,
+            });
+        }
+    }
+}
+
+impl Database {
+    /// Count rows whose referenced parent row no longer exists.
+    pub async fn find_orphans(&self) -> Result<OrphanReport> {
+        let mut report = OrphanReport::default();
+        for relation in RELATIONS {
+            // Table and column names are constants, not user input
+            let sql = format!(
+                "SELECT COUNT(*) FROM {child} c
+                 LEFT JOIN {parent} p ON p.id = c.{column}
+                 WHERE c.{column} IS NOT NULL AND p.id IS NULL",
+                child = relation.child,
+                parent = relation.parent,
+                column = relation.column,
+            );
+            let count: i64 = sqlx::query_scalar(&sql).fetch_one(self.pool()).await?;
+            report.push(relation, count);
+        }
+        Ok(report)
+    }
+
+    /// Remove orphaned rows in one transaction, returning what was cleaned.
+    ///
+    /// Orphans are deleted, except optional references, which are set to
+    /// NULL. Rows that become orphans when their parent is deleted here are
+    /// removed too; the report counts rows changed directly, not those
+    /// removed by `ON DELETE CASCADE`.
+    pub async fn clean_orphans(&self) -> Result<OrphanReport> {
+        let mut tx = self.pool().begin().await?;
+
+        // Children of a deleted orphan are cleaned by later steps; check the
+        // foreign keys once they all have been
+        sqlx::query("PRAGMA defer_foreign_keys = ON")
+            .execute(&mut *tx)
+            .await?;
+
+        let mut report = OrphanReport::default();
+        for relation in RELATIONS {
+            let orphaned = format!(
+                "{child}.{column} IS NOT NULL
+                 AND NOT EXISTS (SELECT 1 FROM {parent} p WHERE p.id = {child}.{column})",
+                child = relation.child,
+                parent = relation.parent,
+                column = relation.column,
+            );
+            let sql = if relation.nullable {
+                format!(
+                    "UPDATE {child} SET {column} = NULL WHERE {orphaned}",
+                    child = relation.child,
+                    column = relation.column,
+                )
+            } else {
+                format!("DELETE FROM {} WHERE {orphaned}", relation.child)
+            };
+            let result = sqlx::query(&sql).execute(&mut *tx).await?;
+            report.push(
+                relation,
+                i64::try_from(result.rows_affected()).unwrap_or(i64::MAX),
+            );
+        }
+
+        tx.commit().await?;
+
+        if !report.is_empty() {
+            tracing::info!(rows = report.total(), "Cleaned orphaned rows");
+        }
+        Ok(report)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    async fn test_db() -> Database {
+        let db = Database::new(":memory:", vec![0x42; 32])
+            .await
+            .expect("create db");
+        db.run_migrations().await.expect("run migrations");
+        db
+    }
+
+    /// Run `sql` with foreign key enforcement off, as older builds did.
+    async fn insert_unchecked(db: &Database, sql: &str) {
+        let mut conn = db.pool().acquire().await.expect("acquire connection");
+        sqlx::query("PRAGMA foreign_keys = OFF")
+            .execute(&mut *conn)
+            .await
+            .expect("disable foreign keys");
+        sqlx::query(sql)
+            .execute(&mut *conn)
+            .await
+            .expect("insert row");
+        sqlx::query("PRAGMA foreign_keys = ON")
+            .execute(&mut *conn)
+            .await
+            .expect("enable foreign keys");
+    }
+
+    #[tokio::test]
+    async fn test_orphaned_evidence_is_found_and_cleaned() {
+        let db = test_db().await;
+        assert!(db.find_orphans().await.expect("find orphans").is_empty());
+
+        insert_unchecked(
+            &db,
+            "INSERT INTO removal_evidence (id, attempt_id, screenshot_bytes, captured_at)
+             VALUES ('ev-1', 'deleted-attempt', x'89504e47', '2026-01-01T00:00:00Z')",
+        )
+        .await;
+
+        let report = db.find_orphans().await.expect("find orphans");
+        assert_eq!(
+            report.orphans,
+            vec![OrphanCount {
+                table: "removal_evidence".to_string(),
+                column: "attempt_id".to_string(),
+                parent: "removal_attempts".to_string(),
+                count: 1,
+            }]
+        );
+
+        let cleaned = db.clean_orphans().await.expect("clean orphans");
+        assert_eq!(cleaned, report);
+        assert!(db.find_orphans().await.expect("find orphans").is_empty());
+
+        let remaining: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM removal_evidence")
+            .fetch_one(db.pool())
+            .await
+            .expect("count evidence");
+        assert_eq!(remaining, 0);
+    }
+
+    #[tokio::test]
+    async fn test_cleaning_an_orphan_also_cleans_its_children() {
+        let db = test_db().await;
+
+        // A removal attempt whose finding is gone, with evidence and a sent
+        // email still pointing at it
+        insert_unchecked(
+            &db,
+            "INSERT INTO removal_attempts (id, finding_id, broker_id, status, created_at)
+             VALUES ('attempt-1', 'deleted-finding', 'spokeo', 'Submitted', '2026-01-01T00:00:00Z')",
+        )
+        .await;
+        sqlx::query(
+            "INSERT INTO removal_evidence (id, attempt_id, screenshot_bytes, captured_at)
+             VALUES ('ev-1', 'attempt-1', x'89504e47', '2026-01-01T00:00:00Z')",
+        )
+        .execute(db.pool())
+        .await
+        .expect("insert evidence");
+        sqlx::query(
+            "INSERT INTO email_removals
+                 (id, attempt_id, broker_id, sent_at, method, recipient, subject, body_hash)
+             VALUES ('email-1', 'attempt-1', 'spokeo', '2026-01-01T00:00:00Z', 'smtp',
+                     'privacy@spokeo.example', 'Opt-out request', 'abc123')",
+        )
+        .execute(db.pool())
+        .await
+        .expect("insert email removal");
+
+        let report = db.find_orphans().await.expect("find orphans");
+        assert_eq!(report.total(), 1);
+        assert_eq!(report.count_for("removal_attempts"), 1);
+
+        let cleaned = db.clean_orphans().await.expect("clean orphans");
+        assert_eq!(cleaned.count_for("removal_attempts"), 1);
+        assert_eq!(cleaned.count_for("removal_evidence"), 1);
+        assert_eq!(cleaned.count_for("email_removals"), 1);
+        assert!(db.find_orphans().await.expect("find orphans").is_empty());
+
+        // The sent email is kept as a record, detached from the attempt
+        let attempt_id: Option<String> =
+            sqlx::query_scalar("SELECT attempt_id FROM email_removals WHERE id = 'email-1'")
+                .fetch_one(db.pool())
+                .await
+                .expect("email removal kept");
+        assert_eq!(attempt_id, None);
+    }
+}
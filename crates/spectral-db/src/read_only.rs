@@ -0,0 +1,364 @@
+//! Read-only access to the database.
+//!
+//! Command handlers that only report on data (dashboards, summaries, lists)
+//! take a [`ReadOnlyDb`] instead of the full [`Database`]. The view exposes
+//! fetch methods and a handful of list queries, but no `execute` and no
+//! access to the underlying pool, so a mutating statement cannot be run
+//! through it by accident:
+//!
+//! ```no_run
+//! async fn count_findings(db: &spectral_db::Database) -> spectral_db::Result<i64> {
+//!     db.read_only_view()
+//!         .fetch_scalar(sqlx::query_scalar("SELECT COUNT(*) FROM findings"))
+//!         .await
+//! }
+//! ```
+//!
+//! ```compile_fail
+//! async fn clear_findings(db: &spectral_db::Database) {
+//!     let view = db.read_only_view();
+//!     view.execute(sqlx::query("DELETE FROM findings")).await;
+//! }
+//! ```
+//!
+//! ```compile_fail
+//! async fn clear_findings(db: &spectral_db::Database) {
+//!     let view = db.read_only_view();
+//!     sqlx::query("DELETE FROM findings").execute(view.pool()).await;
+//! }
+//! ```
+//!
+//! Fetch methods also reject SQL that is not a plain `SELECT`, since
+//! `DELETE ... RETURNING` can be fetched like a query.
+
+use crate::error::{DatabaseError, Result};
+use crate::findings::Finding;
+use crate::removal_attempts::{RemovalAttempt, RemovalJobSummary, RemovalStatus};
+use crate::Database;
+use sqlx::query::{QueryAs, QueryScalar};
+use sqlx::sqlite::{SqliteArguments, SqliteRow};
+use sqlx::{Execute, FromRow, Sqlite};
+
+/// Keywords that mark a statement as able to modify the database.
+const WRITE_KEYWORDS: &[&str] = &[
+    "INSERT", "UPDATE", "DELETE", "REPLACE", "UPSERT", "CREATE", "DROP", "ALTER", "ATTACH",
+    "DETACH", "PRAGMA", "VACUUM", "REINDEX",
+];
+
+/// Query-only view of a [`Database`], obtained with [`Database::read_only_view`].
+#[derive(Debug, Clone, Copy)]
+pub struct ReadOnlyDb<'a> {
+    db: &'a Database,
+}
+
+impl Database {
+    /// Borrow a view of this database that can only run queries.
+    #[must_use]
+    pub fn read_only_view(&self) -> ReadOnlyDb<'_> {
+        ReadOnlyDb { db: self }
+    }
+}
+
+impl ReadOnlyDb<'_> {
+    /// Fetch every row returned by a `SELECT`.
+    ///
+    /// # Errors
+    /// Returns `DatabaseError::ReadOnly` if the statement could write.
+    pub async fn fetch_all<'q, O>(
+        &self,
+        query: QueryAs<'q, Sqlite, O, SqliteArguments<'q>>,
+    ) -> Result<Vec<O>>
+    where
+        O: Send + Unpin + for<'r> FromRow<'r, SqliteRow>,
+    {
+        ensure_read_only(query.sql())?;
+        Ok(query.fetch_all(self.db.pool()).await?)
+    }
+
+    /// Fetch exactly one row returned by a `SELECT`.
+    ///
+    /// # Errors
+    /// Returns `DatabaseError::ReadOnly` if the statement could write.
+    pub async fn fetch_one<'q, O>(
+        &self,
+        query: QueryAs<'q, Sqlite, O, SqliteArguments<'q>>,
+    ) -> Result<O>
+    where
+        O: Send + Unpin + for<'r> FromRow<'r, SqliteRow>,
+    {
+        ensure_read_only(query.sql())?;
+        Ok(query.fetch_one(self.db.pool()).await?)
+    }
+
+    /// Fetch at most one row returned by a `SELECT`.
+    ///
+    /// # Errors
+    /// Returns `DatabaseError::ReadOnly` if the statement could write.
+    pub async fn fetch_optional<'q, O>(
+        &self,
+        query: QueryAs<'q, Sqlite, O, SqliteArguments<'q>>,
+    ) -> Result<Option<O>>
+    where
+        O: Send + Unpin + for<'r> FromRow<'r, SqliteRow>,
+    {
+        ensure_read_only(query.sql())?;
+        Ok(query.fetch_optional(self.db.pool()).await?)
+    }
+
+    /// Fetch a single scalar value, such as a `COUNT(*)`.
+    ///
+    /// # Errors
+    /// Returns `DatabaseError::ReadOnly` if the statement could write.
+    pub async fn fetch_scalar<'q, O>(
+        &self,
+        query: QueryScalar<'q, Sqlite, O, SqliteArguments<'q>>,
+    ) -> Result<O>
+    where
+        O: Send + Unpin,
+        (O,): for<'r> FromRow<'r, SqliteRow>,
+    {
+        ensure_read_only(query.sql())?;
+        Ok(query.fetch_one(self.db.pool()).await?)
+    }
+
+    /// Get all scheduled jobs.
+    pub async fn scheduled_jobs(&self) -> Result<Vec<spectral_scheduler::ScheduledJob>> {
+        self.db.get_scheduled_jobs().await
+    }
+
+    /// Get findings for a scan job, newest first.
+    pub async fn findings_by_scan_job(&self, scan_job_id: &str) -> Result<Vec<Finding>> {
+        Ok(crate::findings::get_by_scan_job(self.db.pool(), scan_job_id).await?)
+    }
+
+    /// Get removal attempts for a scan job.
+    pub async fn removal_attempts_by_scan_job(
+        &self,
+        scan_job_id: &str,
+    ) -> Result<Vec<RemovalAttempt>> {
+        Ok(crate::removal_attempts::get_by_scan_job_id(self.db.pool(), scan_job_id).await?)
+    }
+
+    /// Get removal attempts blocked on a CAPTCHA.
+    pub async fn captcha_queue(&self) -> Result<Vec<RemovalAttempt>> {
+        Ok(crate::removal_attempts::get_captcha_queue(self.db.pool()).await?)
+    }
+
+    /// Get removal attempts the user must finish themselves.
+    pub async fn user_action_queue(&self) -> Result<Vec<RemovalAttempt>> {
+        Ok(crate::removal_attempts::get_user_action_queue(self.db.pool()).await?)
+    }
+
+    /// Get removal attempts that failed.
+    pub async fn failed_queue(&self) -> Result<Vec<RemovalAttempt>> {
+        Ok(crate::removal_attempts::get_failed_queue(self.db.pool()).await?)
+    }
+
+    /// Get one page of removal attempts with a status, oldest first.
+    pub async fn removal_attempts_by_status(
+        &self,
+        status: RemovalStatus,
+        limit: u32,
+        offset: u32,
+    ) -> Result<Vec<RemovalAttempt>> {
+        Ok(crate::removal_attempts::get_by_status(self.db.pool(), status, limit, offset).await?)
+    }
+
+    /// Count removal attempts with a status.
+    pub async fn count_removal_attempts(&self, status: RemovalStatus) -> Result<i64> {
+        Ok(crate::removal_attempts::count_by_status(self.db.pool(), status).await?)
+    }
+
+    /// Get per-scan-job removal summaries.
+    pub async fn removal_job_history(&self) -> Result<Vec<RemovalJobSummary>> {
+        Ok(crate::removal_attempts::get_job_history(self.db.pool()).await?)
+    }
+}
+
+/// Reject statements that are not a single plain `SELECT`.
+///
+/// The SQL is split into keywords, skipping string literals, quoted
+/// identifiers and comments. A write keyword is refused wherever it is used
+/// as a statement keyword; followed by `(` it is a function call such as
+/// `replace(name, ' ', '')` and is allowed.
+fn ensure_read_only(sql: &str) -> Result<()> {
+    let tokens = sql_tokens(sql);
+    let starts_with_read = matches!(
+        tokens.first(),
+        Some(SqlToken::Word(word)) if word == "SELECT" || word == "WITH"
+    );
+    let has_write = tokens.iter().enumerate().any(|(i, token)| match token {
+        // Anything after a `;` is a second statement
+        SqlToken::Symbol(';') => i + 1 < tokens.len(),
+        SqlToken::Word(word) => {
+            WRITE_KEYWORDS.contains(&word.as_str())
+                && tokens.get(i + 1) != Some(&SqlToken::Symbol('('))
+        }
+        SqlToken::Symbol(_) => false,
+    });
+
+    if starts_with_read && !has_write {
+        Ok(())
+    } else {
+        Err(DatabaseError::ReadOnly(sql.trim().to_string()))
+    }
+}
+
+/// A keyword, identifier or punctuation character of a SQL statement.
+#[derive(Debug, PartialEq, Eq)]
+enum SqlToken {
+    /// Unquoted word, uppercased
+    Word(String),
+    /// Punctuation or operator character
+    Symbol(char),
+}
+
+/// Split `sql` into words and symbols, dropping literals, quoted identifiers,
+/// comments and whitespace.
+fn sql_tokens(sql: &str) -> Vec<SqlToken> {
+    let mut tokens = Vec::new();
+    let mut chars = sql.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '\'' | '"' | '`' | '[' => {
+                let close = if c == '[' { ']' } else { c };
+                // A doubled quote inside a literal is an escaped quote, which
+                // reads the same as a closed literal followed by a new one
+                for next in chars.by_ref() {
+                    if next == close {
+                        break;
+                    }
+                }
+            }
+            '-' if chars.peek() == Some(&'-') => {
+                for next in chars.by_ref() {
+                    if next == '\n' {
+                        break;
+                    }
+                }
+            }
+            '/' if chars.peek() == Some(&'*') => {
+                chars.next();
+                let mut previous = None;
+                for next in chars.by_ref() {
+                    if previous == Some('*') && next == '/' {
+                        break;
+                    }
+                    previous = Some(next);
+                }
+            }
+            c if c.is_alphanumeric() || c == '_' => {
+                let mut word = c.to_uppercase().collect::<String>();
+                while let Some(&next) = chars.peek() {
+                    if !(next.is_alphanumeric() || next == '_') {
+                        break;
+                    }
+                    word.extend(next.to_uppercase());
+                    chars.next();
+                }
+                tokens.push(SqlToken::Word(word));
+            }
+            c if c.is_whitespace() => {}
+            c => tokens.push(SqlToken::Symbol(c)),
+        }
+    }
+
+    tokens
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    async fn test_db() -> Database {
+        let db = Database::new(":memory:", vec![0x42; 32])
+            .await
+            .expect("create db");
+        db.run_migrations().await.expect("run migrations");
+        db
+    }
+
+    #[test]
+    fn test_ensure_read_only() {
+        assert!(ensure_read_only("SELECT COUNT(*) FROM findings").is_ok());
+        assert!(ensure_read_only(
+            "WITH recent AS (SELECT * FROM scan_jobs) SELECT updated_at FROM recent;"
+        )
+        .is_ok());
+
+        assert!(ensure_read_only("DELETE FROM findings RETURNING id").is_err());
+        assert!(ensure_read_only("select 1; drop table findings").is_err());
+        assert!(ensure_read_only(
+            "WITH gone AS (SELECT id FROM findings) DELETE FROM findings RETURNING id"
+        )
+        .is_err());
+        assert!(ensure_read_only("PRAGMA query_only").is_err());
+    }
+
+    #[test]
+    fn test_ensure_read_only_allows_write_words_outside_statements() {
+        assert!(ensure_read_only("SELECT replace(name, ' ', '') FROM brokers").is_ok());
+        assert!(ensure_read_only("SELECT id FROM findings WHERE note = 'delete; drop'").is_ok());
+        assert!(ensure_read_only("SELECT \"update\" FROM t -- insert\n").is_ok());
+        assert!(ensure_read_only("SELECT 1;").is_ok());
+
+        assert!(ensure_read_only("REPLACE INTO brokers (id) VALUES ('x')").is_err());
+        assert!(ensure_read_only("SELECT 1; /* c */ DELETE FROM findings").is_err());
+    }
+
+    #[tokio::test]
+    async fn test_read_only_view_runs_selects() {
+        let db = test_db().await;
+        let view = db.read_only_view();
+
+        let count: i64 = view
+            .fetch_scalar(sqlx::query_scalar("SELECT COUNT(*) FROM findings"))
+            .await
+            .expect("count findings");
+        assert_eq!(count, 0);
+
+        let rows: Vec<(String, String)> = view
+            .fetch_all(sqlx::query_as("SELECT id, status FROM scan_jobs"))
+            .await
+            .expect("list scan jobs");
+        assert!(rows.is_empty());
+
+        assert!(view
+            .captcha_queue()
+            .await
+            .expect("captcha queue")
+            .is_empty());
+        assert!(view
+            .findings_by_scan_job("missing")
+            .await
+            .expect("findings")
+            .is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_read_only_view_rejects_returning_writes() {
+        let db = test_db().await;
+        sqlx::query(
+            "INSERT INTO profiles (id, data, nonce, created_at, updated_at)
+             VALUES ('p1', x'00', x'00', '2026-01-01', '2026-01-01')",
+        )
+        .execute(db.pool())
+        .await
+        .expect("insert profile");
+
+        let result = db
+            .read_only_view()
+            .fetch_optional(sqlx::query_as::<_, (String,)>(
+                "DELETE FROM profiles WHERE id = 'p1' RETURNING id",
+            ))
+            .await;
+        assert!(matches!(result, Err(DatabaseError::ReadOnly(_))));
+
+        let remaining: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM profiles")
+            .fetch_one(db.pool())
+            .await
+            .expect("count profiles");
+        assert_eq!(remaining, 1);
+    }
+}
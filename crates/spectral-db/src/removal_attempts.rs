@@ -2,8 +2,24 @@
 //!
 //! This module provides CRUD operations for the `removal_attempts` table,
 //! which stores removal request submissions for confirmed findings.
-
-use chrono::{DateTime, Utc};
+//!
+//! # Status transitions
+//!
+//! [`update_status`] only moves an attempt along these edges. Writing the
+//! current status again is always allowed, e.g. to update the error message.
+//!
+//! | From              | To                                                      |
+//! |-------------------|---------------------------------------------------------|
+//! | `Pending`         | `Submitted`, `Completed`, `Failed`, `NeedsUserAction`, `Cancelled` |
+//! | `Submitted`       | `Completed`, `Failed`, `NeedsUserAction`, `Cancelled`   |
+//! | `NeedsUserAction` | `Pending`, `Submitted`, `Completed`, `Failed`, `Cancelled` |
+//! | `Failed`          | `Pending`, `Completed`, `Cancelled`                     |
+//! | `Completed`       | none                                                    |
+//! | `Cancelled`       | none                                                    |
+
+use crate::error::DatabaseError;
+use crate::trends::{self, DayRange};
+use chrono::{DateTime, NaiveDate, Utc};
 use serde::{Deserialize, Serialize};
 use sqlx::{Pool, Row, Sqlite};
 use std::fmt;
@@ -21,6 +37,31 @@ pub enum RemovalStatus {
     Completed,
     /// Removal request failed
     Failed,
+    /// The user must finish the removal themselves (e.g. solve a CAPTCHA or
+    /// upload an ID)
+    NeedsUserAction,
+    /// The user withdrew the removal request
+    Cancelled,
+}
+
+impl RemovalStatus {
+    /// Whether an attempt with this status may move to `next`.
+    ///
+    /// See the [module documentation](self) for the full table.
+    #[must_use]
+    pub fn can_transition_to(self, next: Self) -> bool {
+        use RemovalStatus::{Cancelled, Completed, Failed, NeedsUserAction, Pending, Submitted};
+
+        if self == next {
+            return true;
+        }
+        match self {
+            Pending | NeedsUserAction => true,
+            Submitted => matches!(next, Completed | Failed | NeedsUserAction | Cancelled),
+            Failed => matches!(next, Pending | Completed | Cancelled),
+            Completed | Cancelled => false,
+        }
+    }
 }
 
 impl fmt::Display for RemovalStatus {
@@ -30,6 +71,8 @@ impl fmt::Display for RemovalStatus {
             Self::Submitted => write!(f, "Submitted"),
             Self::Completed => write!(f, "Completed"),
             Self::Failed => write!(f, "Failed"),
+            Self::NeedsUserAction => write!(f, "NeedsUserAction"),
+            Self::Cancelled => write!(f, "Cancelled"),
         }
     }
 }
@@ -124,10 +167,14 @@ pub async fn get_by_finding_id(
 
 /// Update the status of a removal attempt.
 ///
-/// Updates the status field and optionally updates timestamp fields.
+/// Updates the status field and optionally updates timestamp fields. The
+/// move must be allowed by [`RemovalStatus::can_transition_to`]. Moving to
+/// `Failed` from another status adds one to the attempt's [`failure_count`].
 ///
 /// # Errors
-/// Returns `sqlx::Error` if the database update fails.
+/// Returns [`DatabaseError::InvalidTransition`] if the attempt cannot move to
+/// `new_status`, [`DatabaseError::NotFound`] if there is no attempt with this
+/// ID, or [`DatabaseError::Sqlx`] if the database update fails.
 pub async fn update_status(
     pool: &Pool<Sqlite>,
     id: &str,
@@ -135,16 +182,31 @@ pub async fn update_status(
     submitted_at: Option<DateTime<Utc>>,
     completed_at: Option<DateTime<Utc>>,
     error_message: Option<String>,
-) -> Result<(), sqlx::Error> {
+) -> Result<(), DatabaseError> {
+    let current = get_by_id(pool, id)
+        .await?
+        .ok_or(DatabaseError::NotFound)?
+        .status;
+    if !current.can_transition_to(new_status) {
+        return Err(DatabaseError::InvalidTransition {
+            from: current,
+            to: new_status,
+        });
+    }
+
+    // Each move into Failed counts as one more failure
+    let failed = i64::from(new_status == RemovalStatus::Failed && current != RemovalStatus::Failed);
     sqlx::query(
         "UPDATE removal_attempts
-         SET status = ?, submitted_at = ?, completed_at = ?, error_message = ?
+         SET status = ?, submitted_at = ?, completed_at = ?, error_message = ?,
+             failure_count = failure_count + ?
          WHERE id = ?",
     )
     .bind(new_status.to_string())
     .bind(submitted_at.map(|dt| dt.to_rfc3339()))
     .bind(completed_at.map(|dt| dt.to_rfc3339()))
     .bind(error_message)
+    .bind(failed)
     .bind(id)
     .execute(pool)
     .await?;
@@ -152,6 +214,20 @@ pub async fn update_status(
     Ok(())
 }
 
+/// How many times a removal attempt has moved to `Failed`, counting failures
+/// it was later retried after.
+///
+/// # Errors
+/// Returns [`DatabaseError::NotFound`] if there is no attempt with this ID,
+/// or [`DatabaseError::Sqlx`] if the database query fails.
+pub async fn failure_count(pool: &Pool<Sqlite>, id: &str) -> Result<u32, DatabaseError> {
+    sqlx::query_scalar("SELECT failure_count FROM removal_attempts WHERE id = ?")
+        .bind(id)
+        .fetch_optional(pool)
+        .await?
+        .ok_or(DatabaseError::NotFound)
+}
+
 /// Get a removal attempt by its ID.
 ///
 /// # Errors
@@ -181,7 +257,7 @@ pub async fn get_by_id(
 /// Parse database rows into `RemovalAttempt` structs.
 ///
 /// Helper function to avoid code duplication across query functions.
-fn parse_removal_attempts_from_rows(
+pub(crate) fn parse_removal_attempts_from_rows(
     rows: Vec<sqlx::sqlite::SqliteRow>,
 ) -> Result<Vec<RemovalAttempt>, sqlx::Error> {
     rows.into_iter()
@@ -191,6 +267,8 @@ fn parse_removal_attempts_from_rows(
                 "Submitted" => RemovalStatus::Submitted,
                 "Completed" => RemovalStatus::Completed,
                 "Failed" => RemovalStatus::Failed,
+                "NeedsUserAction" => RemovalStatus::NeedsUserAction,
+                "Cancelled" => RemovalStatus::Cancelled,
                 _ => RemovalStatus::Pending,
             };
 
@@ -227,24 +305,138 @@ fn parse_removal_attempts_from_rows(
         .collect()
 }
 
+/// Prefix written to `error_message` when a submission is blocked by a CAPTCHA,
+/// followed by the URL of the page showing it.
+pub const CAPTCHA_REQUIRED_PREFIX: &str = "CAPTCHA_REQUIRED:";
+
+/// `LIKE` pattern for [`CAPTCHA_REQUIRED_PREFIX`], also matching a bare
+/// `CAPTCHA_REQUIRED` with no URL.
+const CAPTCHA_ERROR_PATTERN: &str = "CAPTCHA_REQUIRED%";
+
+/// Prefix written to `error_message` when a broker requires a government ID,
+/// followed by the URL of the broker's instructions.
+pub const ID_VERIFICATION_PREFIX: &str = "ID_VERIFICATION_REQUIRED:";
+
+/// Prefix written to `error_message` when a broker only accepts removals from
+/// account holders, followed by the URL where the user signs in.
+pub const ACCOUNT_REQUIRED_PREFIX: &str = "ACCOUNT_REQUIRED:";
+
+/// Which `NeedsUserAction` attempts a queue holds, by the action required.
+#[derive(Clone, Copy)]
+enum ActionFilter {
+    /// Every attempt with the status
+    Any,
+    /// Only attempts blocked by a CAPTCHA
+    Captcha,
+    /// Attempts waiting on anything but a CAPTCHA
+    NotCaptcha,
+}
+
+/// Query removal attempts with one status, one page at a time.
+///
+/// Rows are ordered by `created_at` and then `id` in the same direction, so
+/// pages are stable even when attempts share a timestamp. A negative `limit`
+/// returns every remaining row.
+async fn query_by_status(
+    pool: &Pool<Sqlite>,
+    status: RemovalStatus,
+    action: ActionFilter,
+    newest_first: bool,
+    limit: i64,
+    offset: i64,
+) -> Result<Vec<RemovalAttempt>, sqlx::Error> {
+    // Only these constant fragments are interpolated; values are bound.
+    let action_filter = match action {
+        ActionFilter::Any => "",
+        ActionFilter::Captcha => "AND error_message LIKE ?",
+        ActionFilter::NotCaptcha => "AND (error_message IS NULL OR error_message NOT LIKE ?)",
+    };
+    let direction = if newest_first { "DESC" } else { "ASC" };
+    let sql = format!(
+        "SELECT id, finding_id, broker_id, status, created_at, submitted_at, completed_at, error_message
+         FROM removal_attempts
+         WHERE status = ? {action_filter}
+         ORDER BY created_at {direction}, id {direction}
+         LIMIT ? OFFSET ?"
+    );
+
+    let mut query = sqlx::query(&sql).bind(status.to_string());
+    if !matches!(action, ActionFilter::Any) {
+        query = query.bind(CAPTCHA_ERROR_PATTERN);
+    }
+    let rows = query.bind(limit).bind(offset).fetch_all(pool).await?;
+
+    parse_removal_attempts_from_rows(rows)
+}
+
+/// Get one page of removal attempts with the given status, oldest first.
+///
+/// # Errors
+/// Returns `sqlx::Error` if the database query fails.
+pub async fn get_by_status(
+    pool: &Pool<Sqlite>,
+    status: RemovalStatus,
+    limit: u32,
+    offset: u32,
+) -> Result<Vec<RemovalAttempt>, sqlx::Error> {
+    query_by_status(
+        pool,
+        status,
+        ActionFilter::Any,
+        false,
+        i64::from(limit),
+        i64::from(offset),
+    )
+    .await
+}
+
+/// Count removal attempts with the given status.
+///
+/// # Errors
+/// Returns `sqlx::Error` if the database query fails.
+pub async fn count_by_status(
+    pool: &Pool<Sqlite>,
+    status: RemovalStatus,
+) -> Result<i64, sqlx::Error> {
+    sqlx::query_scalar("SELECT COUNT(*) FROM removal_attempts WHERE status = ?")
+        .bind(status.to_string())
+        .fetch_one(pool)
+        .await
+}
+
+/// Count removal attempts created on each day of `range`, oldest first.
+///
+/// Days are local days at the range's UTC offset, and days without attempts
+/// are included with a count of zero.
+///
+/// # Errors
+/// Returns `sqlx::Error` if the database query fails.
+pub async fn counts_by_day(
+    pool: &Pool<Sqlite>,
+    range: &DayRange,
+) -> Result<Vec<(NaiveDate, i64)>, sqlx::Error> {
+    trends::counts_by_day(pool, "removal_attempts", "created_at", range).await
+}
+
 /// Get all removal attempts in the CAPTCHA queue.
 ///
-/// Returns removal attempts that are pending and require CAPTCHA resolution,
-/// ordered by oldest first (`created_at` ASC) for FIFO processing.
+/// Returns `NeedsUserAction` attempts blocked by a CAPTCHA, whose
+/// `error_message` starts with [`CAPTCHA_REQUIRED_PREFIX`], ordered by oldest
+/// first (`created_at` ASC) for FIFO processing. Attempts in any other status
+/// are never included, whatever their error message says.
 ///
 /// # Errors
 /// Returns `sqlx::Error` if the database query fails.
 pub async fn get_captcha_queue(pool: &Pool<Sqlite>) -> Result<Vec<RemovalAttempt>, sqlx::Error> {
-    let rows = sqlx::query(
-        "SELECT id, finding_id, broker_id, status, created_at, submitted_at, completed_at, error_message
-         FROM removal_attempts
-         WHERE status = 'Pending' AND error_message LIKE 'CAPTCHA_REQUIRED%'
-         ORDER BY created_at ASC",
+    query_by_status(
+        pool,
+        RemovalStatus::NeedsUserAction,
+        ActionFilter::Captcha,
+        false,
+        -1,
+        0,
     )
-    .fetch_all(pool)
-    .await?;
-
-    parse_removal_attempts_from_rows(rows)
+    .await
 }
 
 /// Get all removal attempts in the failed queue.
@@ -255,16 +447,30 @@ pub async fn get_captcha_queue(pool: &Pool<Sqlite>) -> Result<Vec<RemovalAttempt
 /// # Errors
 /// Returns `sqlx::Error` if the database query fails.
 pub async fn get_failed_queue(pool: &Pool<Sqlite>) -> Result<Vec<RemovalAttempt>, sqlx::Error> {
-    let rows = sqlx::query(
-        "SELECT id, finding_id, broker_id, status, created_at, submitted_at, completed_at, error_message
-         FROM removal_attempts
-         WHERE status = 'Failed'
-         ORDER BY created_at DESC",
-    )
-    .fetch_all(pool)
-    .await?;
+    query_by_status(pool, RemovalStatus::Failed, ActionFilter::Any, true, -1, 0).await
+}
 
-    parse_removal_attempts_from_rows(rows)
+/// Get all removal attempts the user must finish themselves.
+///
+/// Returns attempts with status `NeedsUserAction`, such as brokers requiring
+/// a government ID or an account, ordered oldest first. CAPTCHA-blocked
+/// attempts are left to [`get_captcha_queue`]. `error_message` holds what the
+/// user needs to do, e.g. [`ID_VERIFICATION_PREFIX`] and an instructions URL.
+///
+/// # Errors
+/// Returns `sqlx::Error` if the database query fails.
+pub async fn get_user_action_queue(
+    pool: &Pool<Sqlite>,
+) -> Result<Vec<RemovalAttempt>, sqlx::Error> {
+    query_by_status(
+        pool,
+        RemovalStatus::NeedsUserAction,
+        ActionFilter::NotCaptcha,
+        false,
+        -1,
+        0,
+    )
+    .await
 }
 
 /// Summary of removal attempts grouped by scan job.
@@ -676,6 +882,100 @@ mod tests {
         assert_eq!(updated.error_message, Some("Network timeout".to_string()));
     }
 
+    #[tokio::test]
+    async fn test_counts_by_day_fills_gaps() {
+        let db = setup_test_db().await;
+
+        for created_at in [
+            "2026-04-10T08:00:00+00:00",
+            "2026-04-10T23:00:00+00:00",
+            "2026-04-13T01:00:00+00:00",
+            "2026-04-20T09:00:00+00:00",
+        ] {
+            let attempt = create_removal_attempt(
+                db.pool(),
+                "finding-123".to_string(),
+                "broker-1".to_string(),
+            )
+            .await
+            .expect("create removal attempt");
+            sqlx::query("UPDATE removal_attempts SET created_at = ? WHERE id = ?")
+                .bind(created_at)
+                .bind(&attempt.id)
+                .execute(db.pool())
+                .await
+                .expect("backdate attempt");
+        }
+
+        let day = |s: &str| NaiveDate::parse_from_str(s, "%Y-%m-%d").expect("valid date");
+        let range = DayRange::utc(day("2026-04-09"), day("2026-04-13"));
+        assert_eq!(
+            counts_by_day(db.pool(), &range)
+                .await
+                .expect("count by day"),
+            vec![
+                (day("2026-04-09"), 0),
+                (day("2026-04-10"), 2),
+                (day("2026-04-11"), 0),
+                (day("2026-04-12"), 0),
+                (day("2026-04-13"), 1),
+            ]
+        );
+
+        // Two hours ahead of UTC, the late 10 April attempt was on 11 April
+        let offset = chrono::FixedOffset::east_opt(2 * 3600).expect("valid offset");
+        let counts = counts_by_day(db.pool(), &range.with_utc_offset(offset))
+            .await
+            .expect("count by day");
+        assert_eq!(counts[1], (day("2026-04-10"), 1));
+        assert_eq!(counts[2], (day("2026-04-11"), 1));
+    }
+
+    #[tokio::test]
+    async fn test_failure_count_counts_each_failure() {
+        let db = setup_test_db().await;
+
+        let attempt =
+            create_removal_attempt(db.pool(), "finding-123".to_string(), "broker-1".to_string())
+                .await
+                .expect("create removal attempt");
+        assert_eq!(
+            failure_count(db.pool(), &attempt.id).await.expect("count"),
+            0
+        );
+
+        let set = |status, error: Option<&str>| {
+            update_status(
+                db.pool(),
+                &attempt.id,
+                status,
+                None,
+                None,
+                error.map(str::to_string),
+            )
+        };
+        set(RemovalStatus::Failed, Some("timeout"))
+            .await
+            .expect("fail");
+        // Rewriting the error message is not another failure
+        set(RemovalStatus::Failed, Some("timeout again"))
+            .await
+            .expect("update error");
+        set(RemovalStatus::Pending, None).await.expect("retry");
+        set(RemovalStatus::Failed, Some("form changed"))
+            .await
+            .expect("fail again");
+
+        assert_eq!(
+            failure_count(db.pool(), &attempt.id).await.expect("count"),
+            2
+        );
+        assert!(matches!(
+            failure_count(db.pool(), "missing").await,
+            Err(DatabaseError::NotFound)
+        ));
+    }
+
     #[tokio::test]
     async fn test_get_by_id() {
         let db = setup_test_db().await;
@@ -725,11 +1025,11 @@ mod tests {
                 .await
                 .expect("create removal attempt 3");
 
-        // Mark attempt1 and attempt2 with CAPTCHA errors
+        // Mark attempt1 and attempt2 as blocked by a CAPTCHA
         update_status(
             db.pool(),
             &attempt1.id,
-            RemovalStatus::Pending,
+            RemovalStatus::NeedsUserAction,
             None,
             None,
             Some("CAPTCHA_REQUIRED: reCAPTCHA v2 detected".to_string()),
@@ -740,7 +1040,7 @@ mod tests {
         update_status(
             db.pool(),
             &attempt2.id,
-            RemovalStatus::Pending,
+            RemovalStatus::NeedsUserAction,
             None,
             None,
             Some("CAPTCHA_REQUIRED: hCaptcha detected".to_string()),
@@ -748,14 +1048,14 @@ mod tests {
         .await
         .expect("update status 2");
 
-        // Mark attempt3 as submitted (no CAPTCHA)
+        // attempt3 carries the message but is still Pending
         update_status(
             db.pool(),
             &attempt3.id,
-            RemovalStatus::Submitted,
-            Some(Utc::now()),
+            RemovalStatus::Pending,
             None,
             None,
+            Some("CAPTCHA_REQUIRED: stale message".to_string()),
         )
         .await
         .expect("update status 3");
@@ -769,17 +1069,104 @@ mod tests {
         // Verify ordered by created_at ASC (oldest first)
         assert_eq!(captcha_queue[0].id, attempt1.id);
         assert_eq!(captcha_queue[1].id, attempt2.id);
-        // Verify both have CAPTCHA error messages
-        assert!(captcha_queue[0]
-            .error_message
-            .as_ref()
-            .unwrap()
-            .starts_with("CAPTCHA_REQUIRED"));
-        assert!(captcha_queue[1]
-            .error_message
-            .as_ref()
-            .unwrap()
-            .starts_with("CAPTCHA_REQUIRED"));
+        assert!(captcha_queue
+            .iter()
+            .all(|a| a.status == RemovalStatus::NeedsUserAction));
+
+        // CAPTCHA-blocked attempts are not repeated in the user-action queue
+        let user_action = get_user_action_queue(db.pool())
+            .await
+            .expect("get user action queue");
+        assert!(user_action.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_update_status_rejects_illegal_transition() {
+        let db = setup_test_db().await;
+        let attempt =
+            create_removal_attempt(db.pool(), "finding-123".to_string(), "broker-1".to_string())
+                .await
+                .expect("create removal attempt");
+
+        update_status(
+            db.pool(),
+            &attempt.id,
+            RemovalStatus::Completed,
+            None,
+            Some(Utc::now()),
+            None,
+        )
+        .await
+        .expect("complete attempt");
+
+        let err = update_status(
+            db.pool(),
+            &attempt.id,
+            RemovalStatus::Pending,
+            None,
+            None,
+            None,
+        )
+        .await
+        .expect_err("Completed -> Pending must be rejected");
+        assert!(matches!(
+            err,
+            DatabaseError::InvalidTransition {
+                from: RemovalStatus::Completed,
+                to: RemovalStatus::Pending,
+            }
+        ));
+
+        let unchanged = get_by_id(db.pool(), &attempt.id)
+            .await
+            .expect("get by id")
+            .expect("found attempt");
+        assert_eq!(unchanged.status, RemovalStatus::Completed);
+        assert!(unchanged.completed_at.is_some());
+    }
+
+    #[tokio::test]
+    async fn test_update_status_unknown_attempt() {
+        let db = setup_test_db().await;
+
+        let err = update_status(
+            db.pool(),
+            "missing",
+            RemovalStatus::Submitted,
+            None,
+            None,
+            None,
+        )
+        .await
+        .expect_err("no such attempt");
+        assert!(matches!(err, DatabaseError::NotFound));
+    }
+
+    #[test]
+    fn test_transition_table() {
+        use RemovalStatus::{Cancelled, Completed, Failed, NeedsUserAction, Pending, Submitted};
+        let all = [
+            Pending,
+            Submitted,
+            Completed,
+            Failed,
+            NeedsUserAction,
+            Cancelled,
+        ];
+
+        for status in all {
+            assert!(status.can_transition_to(status));
+            assert!(Pending.can_transition_to(status));
+            assert!(NeedsUserAction.can_transition_to(status));
+        }
+        for terminal in [Completed, Cancelled] {
+            for next in all.into_iter().filter(|s| *s != terminal) {
+                assert!(!terminal.can_transition_to(next), "{terminal} -> {next}");
+            }
+        }
+        assert!(!Submitted.can_transition_to(Pending));
+        assert!(Failed.can_transition_to(Pending));
+        assert!(!Failed.can_transition_to(Submitted));
     }
 
     #[tokio::test]
@@ -856,4 +1243,206 @@ mod tests {
         assert!(failed_queue[0].error_message.is_some());
         assert!(failed_queue[1].error_message.is_some());
     }
+
+    /// Create `count` removal attempts a few milliseconds apart, oldest first.
+    async fn create_attempts(db: &Database, count: usize) -> Vec<RemovalAttempt> {
+        let mut attempts = Vec::new();
+        for index in 0..count {
+            attempts.push(
+                create_removal_attempt(
+                    db.pool(),
+                    "finding-123".to_string(),
+                    format!("broker-{index}"),
+                )
+                .await
+                .expect("create removal attempt"),
+            );
+            tokio::time::sleep(tokio::time::Duration::from_millis(5)).await;
+        }
+        attempts
+    }
+
+    #[tokio::test]
+    async fn test_get_by_status_pagination_boundaries() {
+        let db = setup_test_db().await;
+        let attempts = create_attempts(&db, 5).await;
+        update_status(
+            db.pool(),
+            &attempts[2].id,
+            RemovalStatus::Submitted,
+            Some(Utc::now()),
+            None,
+            None,
+        )
+        .await
+        .expect("update status");
+
+        let pending: Vec<&str> = [0, 1, 3, 4]
+            .iter()
+            .map(|&i| attempts[i].id.as_str())
+            .collect();
+        assert_eq!(
+            count_by_status(db.pool(), RemovalStatus::Pending)
+                .await
+                .expect("count"),
+            4
+        );
+        assert_eq!(
+            count_by_status(db.pool(), RemovalStatus::Submitted)
+                .await
+                .expect("count"),
+            1
+        );
+
+        let mut seen = Vec::new();
+        for (offset, expected_len) in [(0, 3), (3, 1), (4, 0)] {
+            let page = get_by_status(db.pool(), RemovalStatus::Pending, 3, offset)
+                .await
+                .expect("get page");
+            assert_eq!(page.len(), expected_len, "page at offset {offset}");
+            seen.extend(page.into_iter().map(|a| a.id));
+        }
+        assert_eq!(seen, pending);
+
+        let empty = get_by_status(db.pool(), RemovalStatus::Pending, 0, 0)
+            .await
+            .expect("get empty page");
+        assert!(empty.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_get_by_status_orders_ties_by_id() {
+        let db = setup_test_db().await;
+        for id in ["ra-c", "ra-a", "ra-b"] {
+            sqlx::query(
+                "INSERT INTO removal_attempts (id, finding_id, broker_id, status, created_at)
+                 VALUES (?, 'finding-123', 'broker-1', 'Pending', '2026-01-01T00:00:00+00:00')",
+            )
+            .bind(id)
+            .execute(db.pool())
+            .await
+            .expect("insert attempt");
+        }
+
+        let first = get_by_status(db.pool(), RemovalStatus::Pending, 2, 0)
+            .await
+            .expect("first page");
+        let second = get_by_status(db.pool(), RemovalStatus::Pending, 2, 2)
+            .await
+            .expect("second page");
+        let ids: Vec<_> = first.iter().chain(&second).map(|a| a.id.as_str()).collect();
+        assert_eq!(ids, ["ra-a", "ra-b", "ra-c"]);
+    }
+
+    #[tokio::test]
+    async fn test_queues_match_original_queries() {
+        let db = setup_test_db().await;
+        let attempts = create_attempts(&db, 6).await;
+        let updates = [
+            (
+                RemovalStatus::NeedsUserAction,
+                Some("CAPTCHA_REQUIRED: reCAPTCHA"),
+            ),
+            (RemovalStatus::Failed, Some("Network timeout")),
+            (
+                RemovalStatus::NeedsUserAction,
+                Some("CAPTCHA_REQUIRED: hCaptcha"),
+            ),
+            (RemovalStatus::Failed, Some("CAPTCHA_REQUIRED: gave up")),
+            (RemovalStatus::Pending, Some("Queued")),
+            (RemovalStatus::Failed, None),
+        ];
+        for (attempt, (status, error)) in attempts.iter().zip(updates) {
+            update_status(
+                db.pool(),
+                &attempt.id,
+                status,
+                None,
+                None,
+                error.map(str::to_string),
+            )
+            .await
+            .expect("update status");
+        }
+
+        let pool = db.pool();
+        let original_ids = |sql: &'static str| async move {
+            sqlx::query_scalar::<_, String>(sql)
+                .fetch_all(pool)
+                .await
+                .expect("original query")
+        };
+        let ids = |queue: Vec<RemovalAttempt>| -> Vec<String> {
+            queue.into_iter().map(|a| a.id).collect()
+        };
+
+        let captcha = ids(get_captcha_queue(db.pool()).await.expect("captcha queue"));
+        assert_eq!(
+            captcha,
+            original_ids(
+                "SELECT id FROM removal_attempts
+                 WHERE status = 'NeedsUserAction' AND error_message LIKE 'CAPTCHA_REQUIRED%'
+                 ORDER BY created_at ASC"
+            )
+            .await
+        );
+        assert_eq!(captcha, [attempts[0].id.clone(), attempts[2].id.clone()]);
+
+        let failed = ids(get_failed_queue(db.pool()).await.expect("failed queue"));
+        assert_eq!(
+            failed,
+            original_ids(
+                "SELECT id FROM removal_attempts WHERE status = 'Failed' ORDER BY created_at DESC"
+            )
+            .await
+        );
+        assert_eq!(failed.len(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_get_user_action_queue() {
+        let db = setup_test_db().await;
+
+        let needs_id =
+            create_removal_attempt(db.pool(), "finding-123".to_string(), "broker-1".to_string())
+                .await
+                .expect("create removal attempt 1");
+        let failed =
+            create_removal_attempt(db.pool(), "finding-123".to_string(), "broker-2".to_string())
+                .await
+                .expect("create removal attempt 2");
+
+        let instructions = format!("{ID_VERIFICATION_PREFIX}https://broker-1.example/id");
+        update_status(
+            db.pool(),
+            &needs_id.id,
+            RemovalStatus::NeedsUserAction,
+            None,
+            None,
+            Some(instructions.clone()),
+        )
+        .await
+        .expect("update status 1");
+        update_status(
+            db.pool(),
+            &failed.id,
+            RemovalStatus::Failed,
+            None,
+            None,
+            Some("Timeout".to_string()),
+        )
+        .await
+        .expect("update status 2");
+
+        let queue = get_user_action_queue(db.pool())
+            .await
+            .expect("get user action queue");
+        assert_eq!(queue.len(), 1);
+        assert_eq!(queue[0].id, needs_id.id);
+        assert_eq!(queue[0].status, RemovalStatus::NeedsUserAction);
+        assert_eq!(
+            queue[0].error_message.as_deref(),
+            Some(instructions.as_str())
+        );
+    }
 }
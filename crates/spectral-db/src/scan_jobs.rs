@@ -1,4 +1,9 @@
 //! Scan job management for tracking broker scan operations.
+//!
+//! A running job records a heartbeat in `last_progress_at` through
+//! [`record_progress`]. Jobs whose heartbeat stops, because their task
+//! panicked or the app exited mid-scan, are failed by [`fail_stalled_jobs`]
+//! instead of staying `InProgress` forever.
 
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
@@ -63,12 +68,13 @@ pub async fn create_scan_job(
     let status = ScanJobStatus::InProgress;
 
     sqlx::query(
-        "INSERT INTO scan_jobs (id, profile_id, started_at, status, total_brokers, completed_brokers)
-         VALUES (?, ?, ?, ?, ?, 0)"
+        "INSERT INTO scan_jobs (id, profile_id, started_at, last_progress_at, status, total_brokers, completed_brokers)
+         VALUES (?, ?, ?, ?, ?, ?, 0)"
     )
     .bind(&id)
     .bind(&profile_id)
     .bind(started_at.to_rfc3339())
+    .bind(started_at.to_rfc3339())
     .bind(status.to_string())
     .bind(i64::from(total_brokers))
     .execute(pool)
@@ -86,6 +92,77 @@ pub async fn create_scan_job(
     })
 }
 
+/// Error message recorded on jobs failed by [`fail_stalled_jobs`].
+pub const TIMED_OUT_MESSAGE: &str = "Scan timed out: no progress was reported";
+
+/// Record that a running scan job is still making progress.
+///
+/// # Errors
+/// Returns an error if the database update fails.
+pub async fn record_progress(pool: &SqlitePool, job_id: &str) -> Result<(), sqlx::Error> {
+    sqlx::query("UPDATE scan_jobs SET last_progress_at = ? WHERE id = ?")
+        .bind(Utc::now().to_rfc3339())
+        .bind(job_id)
+        .execute(pool)
+        .await?;
+
+    Ok(())
+}
+
+/// Fail `InProgress` jobs that have reported no progress for `max_idle`.
+///
+/// Each stalled job is marked `Failed` with [`TIMED_OUT_MESSAGE`]. Jobs that
+/// never recorded progress are judged by their start time. Returns the IDs
+/// of the jobs that were failed.
+///
+/// # Errors
+/// Returns an error if the database update fails.
+pub async fn fail_stalled_jobs(
+    pool: &SqlitePool,
+    max_idle: chrono::Duration,
+) -> Result<Vec<String>, sqlx::Error> {
+    let now = Utc::now();
+    sqlx::query_scalar(
+        "UPDATE scan_jobs
+         SET status = ?, completed_at = ?, error_message = ?
+         WHERE status = ?
+           AND julianday(COALESCE(last_progress_at, started_at)) < julianday(?)
+         RETURNING id",
+    )
+    .bind(ScanJobStatus::Failed.to_string())
+    .bind(now.to_rfc3339())
+    .bind(TIMED_OUT_MESSAGE)
+    .bind(ScanJobStatus::InProgress.to_string())
+    .bind((now - max_idle).to_rfc3339())
+    .fetch_all(pool)
+    .await
+}
+
+/// Put an interrupted job back `InProgress` so its remaining brokers can be
+/// scanned.
+///
+/// Only jobs that are still `InProgress` or that `Failed`, for instance
+/// through [`fail_stalled_jobs`], can be reopened. Returns the job's profile
+/// ID, or `None` if there is no such job or it completed or was cancelled.
+///
+/// # Errors
+/// Returns an error if the database update fails.
+pub async fn reopen_job(pool: &SqlitePool, job_id: &str) -> Result<Option<String>, sqlx::Error> {
+    sqlx::query_scalar(
+        "UPDATE scan_jobs
+         SET status = ?, completed_at = NULL, error_message = NULL, last_progress_at = ?
+         WHERE id = ? AND status IN (?, ?)
+         RETURNING profile_id",
+    )
+    .bind(ScanJobStatus::InProgress.to_string())
+    .bind(Utc::now().to_rfc3339())
+    .bind(job_id)
+    .bind(ScanJobStatus::InProgress.to_string())
+    .bind(ScanJobStatus::Failed.to_string())
+    .fetch_optional(pool)
+    .await
+}
+
 #[cfg(test)]
 #[allow(clippy::unwrap_used)]
 mod tests {
@@ -126,4 +203,125 @@ mod tests {
         assert_eq!(job.completed_brokers, 0);
         assert_eq!(job.status, ScanJobStatus::InProgress);
     }
+
+    async fn create_job_for_test(db: &Database) -> ScanJob {
+        sqlx::query(
+            "INSERT OR IGNORE INTO profiles (id, data, nonce, created_at, updated_at)
+             VALUES ('profile-123', 'encrypted_data', 'nonce', datetime('now'), datetime('now'))",
+        )
+        .execute(db.pool())
+        .await
+        .expect("create test profile");
+
+        create_scan_job(db.pool(), "profile-123".to_string(), 5)
+            .await
+            .expect("create scan job")
+    }
+
+    async fn job_state(db: &Database, job_id: &str) -> (String, Option<String>) {
+        sqlx::query_as("SELECT status, error_message FROM scan_jobs WHERE id = ?")
+            .bind(job_id)
+            .fetch_one(db.pool())
+            .await
+            .expect("get scan job")
+    }
+
+    #[tokio::test]
+    async fn test_fail_stalled_jobs_reaps_job_without_recent_progress() {
+        let db = setup_test_db().await;
+        let stalled = create_job_for_test(&db).await;
+        let alive = create_job_for_test(&db).await;
+
+        // Both started long ago; only `alive` is still reporting progress
+        let two_hours_ago = (Utc::now() - chrono::Duration::hours(2)).to_rfc3339();
+        sqlx::query("UPDATE scan_jobs SET started_at = ?, last_progress_at = ?")
+            .bind(&two_hours_ago)
+            .bind(&two_hours_ago)
+            .execute(db.pool())
+            .await
+            .expect("backdate jobs");
+        record_progress(db.pool(), &alive.id)
+            .await
+            .expect("record progress");
+
+        let reaped = fail_stalled_jobs(db.pool(), chrono::Duration::minutes(30))
+            .await
+            .expect("fail stalled jobs");
+
+        assert_eq!(reaped, vec![stalled.id.clone()]);
+        assert_eq!(
+            job_state(&db, &stalled.id).await,
+            ("Failed".to_string(), Some(TIMED_OUT_MESSAGE.to_string()))
+        );
+        assert_eq!(
+            job_state(&db, &alive.id).await,
+            ("InProgress".to_string(), None)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_fail_stalled_jobs_ignores_finished_and_new_jobs() {
+        let db = setup_test_db().await;
+        let finished = create_job_for_test(&db).await;
+        let fresh = create_job_for_test(&db).await;
+
+        let two_hours_ago = (Utc::now() - chrono::Duration::hours(2)).to_rfc3339();
+        sqlx::query(
+            "UPDATE scan_jobs SET status = 'Completed', started_at = ?, last_progress_at = ?
+             WHERE id = ?",
+        )
+        .bind(&two_hours_ago)
+        .bind(&two_hours_ago)
+        .bind(&finished.id)
+        .execute(db.pool())
+        .await
+        .expect("backdate finished job");
+
+        let reaped = fail_stalled_jobs(db.pool(), chrono::Duration::minutes(30))
+            .await
+            .expect("fail stalled jobs");
+
+        assert!(reaped.is_empty());
+        assert_eq!(job_state(&db, &finished.id).await.0, "Completed");
+        assert_eq!(job_state(&db, &fresh.id).await.0, "InProgress");
+    }
+
+    #[tokio::test]
+    async fn test_reopen_job_only_reopens_unfinished_jobs() {
+        let db = setup_test_db().await;
+        let timed_out = create_job_for_test(&db).await;
+        let completed = create_job_for_test(&db).await;
+
+        sqlx::query("UPDATE scan_jobs SET last_progress_at = ?")
+            .bind((Utc::now() - chrono::Duration::hours(2)).to_rfc3339())
+            .execute(db.pool())
+            .await
+            .expect("backdate jobs");
+        sqlx::query("UPDATE scan_jobs SET status = 'Completed' WHERE id = ?")
+            .bind(&completed.id)
+            .execute(db.pool())
+            .await
+            .expect("complete job");
+        fail_stalled_jobs(db.pool(), chrono::Duration::minutes(30))
+            .await
+            .expect("fail stalled jobs");
+
+        let profile_id = reopen_job(db.pool(), &timed_out.id)
+            .await
+            .expect("reopen job");
+        assert_eq!(profile_id.as_deref(), Some("profile-123"));
+        assert_eq!(
+            job_state(&db, &timed_out.id).await,
+            ("InProgress".to_string(), None)
+        );
+
+        // Reopening refreshes the heartbeat so the watchdog leaves it alone
+        let reaped = fail_stalled_jobs(db.pool(), chrono::Duration::minutes(30))
+            .await
+            .expect("fail stalled jobs");
+        assert!(reaped.is_empty());
+
+        assert_eq!(reopen_job(db.pool(), &completed.id).await.unwrap(), None);
+        assert_eq!(reopen_job(db.pool(), "missing-job").await.unwrap(), None);
+    }
 }
@@ -9,8 +9,8 @@ use sqlx::SqlitePool;
 
 /// Set a setting in the database
 pub async fn set_setting(pool: &SqlitePool, key: &str, value: &Value) -> Result<()> {
-    let value_str = serde_json::to_string(value)
-        .map_err(|e| DatabaseError::SerializationError(e.to_string()))?;
+    let value_str =
+        serde_json::to_string(value).map_err(|e| DatabaseError::Serialization(e.to_string()))?;
 
     sqlx::query(
         r"
@@ -45,7 +45,7 @@ pub async fn get_setting(pool: &SqlitePool, key: &str) -> Result<Option<Value>>
     match row {
         Some((value_str,)) => {
             let value: Value = serde_json::from_str(&value_str)
-                .map_err(|e| DatabaseError::SerializationError(e.to_string()))?;
+                .map_err(|e| DatabaseError::Serialization(e.to_string()))?;
             Ok(Some(value))
         }
         None => Ok(None),
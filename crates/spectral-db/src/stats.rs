@@ -0,0 +1,126 @@
+//! Storage usage statistics for diagnostics.
+//!
+//! Row counts per table and the size of the database on disk, used by the
+//! storage usage screen and for spotting tables that grow without bound
+//! (e.g. evidence screenshots).
+
+use crate::error::Result;
+use crate::Database;
+use serde::{Deserialize, Serialize};
+
+/// Row count for a single table.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TableStat {
+    /// Table name
+    pub name: String,
+    /// Number of rows in the table
+    pub row_count: i64,
+}
+
+impl Database {
+    /// Count the rows in every user table, sorted by table name.
+    ///
+    /// `SQLite` internal tables and the migration bookkeeping table are
+    /// excluded.
+    pub async fn table_stats(&self) -> Result<Vec<TableStat>> {
+        let names: Vec<String> = sqlx::query_scalar(
+            "SELECT name FROM sqlite_master
+             WHERE type = 'table' AND name NOT LIKE 'sqlite_%' AND name != '_sqlx_migrations'
+             ORDER BY name",
+        )
+        .fetch_all(self.pool())
+        .await?;
+
+        let mut stats = Vec::with_capacity(names.len());
+        for name in names {
+            // Names come from the schema, but quote them in case one needs it
+            let sql = format!("SELECT COUNT(*) FROM \"{}\"", name.replace('"', "\"\""));
+            let row_count: i64 = sqlx::query_scalar(&sql).fetch_one(self.pool()).await?;
+            stats.push(TableStat { name, row_count });
+        }
+
+        Ok(stats)
+    }
+
+    /// Size of the main database file in bytes.
+    ///
+    /// Computed from the page count, so it also works for in-memory databases
+    /// and does not include a pending write-ahead log.
+    pub async fn file_size(&self) -> Result<u64> {
+        let page_count: i64 = sqlx::query_scalar("PRAGMA page_count")
+            .fetch_one(self.pool())
+            .await?;
+        let page_size: i64 = sqlx::query_scalar("PRAGMA page_size")
+            .fetch_one(self.pool())
+            .await?;
+        Ok(u64::try_from(page_count * page_size).unwrap_or(0))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    async fn test_db() -> Database {
+        let db = Database::new(":memory:", vec![0x42; 32])
+            .await
+            .expect("create db");
+        db.run_migrations().await.expect("run migrations");
+        db
+    }
+
+    fn row_count(stats: &[TableStat], name: &str) -> i64 {
+        stats
+            .iter()
+            .find(|stat| stat.name == name)
+            .unwrap_or_else(|| panic!("missing stats for {name}"))
+            .row_count
+    }
+
+    #[tokio::test]
+    async fn test_table_stats_counts_seeded_rows() {
+        let db = test_db().await;
+        for id in ["p1", "p2"] {
+            sqlx::query(
+                "INSERT INTO profiles (id, data, nonce, created_at, updated_at)
+                 VALUES (?, x'00', x'00', '2026-01-01', '2026-01-01')",
+            )
+            .bind(id)
+            .execute(db.pool())
+            .await
+            .expect("insert profile");
+        }
+
+        let stats = db.table_stats().await.expect("table stats");
+        assert_eq!(row_count(&stats, "profiles"), 2);
+        assert_eq!(row_count(&stats, "findings"), 0);
+        assert_eq!(row_count(&stats, "scan_jobs"), 0);
+        assert!(stats.iter().all(|stat| stat.name != "_sqlx_migrations"));
+        assert!(stats.iter().all(|stat| !stat.name.starts_with("sqlite_")));
+
+        let names: Vec<&str> = stats.iter().map(|stat| stat.name.as_str()).collect();
+        let mut sorted = names.clone();
+        sorted.sort_unstable();
+        assert_eq!(names, sorted);
+    }
+
+    #[tokio::test]
+    async fn test_file_size_grows_with_data() {
+        let db = test_db().await;
+        let before = db.file_size().await.expect("file size");
+        assert!(before > 0);
+
+        for i in 0..50 {
+            sqlx::query(
+                "INSERT INTO profiles (id, data, nonce, created_at, updated_at)
+                 VALUES (?, zeroblob(4096), x'00', '2026-01-01', '2026-01-01')",
+            )
+            .bind(format!("p{i}"))
+            .execute(db.pool())
+            .await
+            .expect("insert profile");
+        }
+
+        assert!(db.file_size().await.expect("file size") > before);
+    }
+}
@@ -0,0 +1,136 @@
+//! Per-day counts for trend charts.
+//!
+//! Timestamps are stored in UTC, but a user thinks of "today" in their own
+//! timezone: a listing found at 23:30 in New York on Monday was found on
+//! Tuesday in UTC. A [`DayRange`] therefore carries the UTC offset to bucket
+//! by, and rows are grouped with `SQLite`'s `date(ts, '±N minutes')`, which
+//! shifts each UTC timestamp to local time before truncating it to a day.
+//!
+//! The offset is fixed for the whole range. Across a daylight saving change,
+//! rows within an hour of local midnight on the days with the other offset
+//! can land in the neighbouring day; charts tolerate that.
+
+use chrono::{FixedOffset, NaiveDate};
+use sqlx::{Pool, Sqlite};
+
+/// Inclusive range of local days to count, and the offset that defines them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DayRange {
+    /// First day, in local time
+    pub start: NaiveDate,
+    /// Last day, in local time, included in the range
+    pub end: NaiveDate,
+    /// Offset of local time from UTC, e.g. `-05:00` for New York in winter
+    pub utc_offset: FixedOffset,
+}
+
+impl DayRange {
+    /// Days `start` to `end` inclusive, bucketed by UTC days.
+    #[must_use]
+    pub fn utc(start: NaiveDate, end: NaiveDate) -> Self {
+        Self {
+            start,
+            end,
+            utc_offset: FixedOffset::east_opt(0).expect("zero offset is valid"),
+        }
+    }
+
+    /// Bucket by local days at `utc_offset` instead of UTC days.
+    #[must_use]
+    pub fn with_utc_offset(mut self, utc_offset: FixedOffset) -> Self {
+        self.utc_offset = utc_offset;
+        self
+    }
+
+    /// Every day in the range, in order. Empty if `start` is after `end`.
+    pub fn days(&self) -> impl Iterator<Item = NaiveDate> {
+        let end = self.end;
+        self.start.iter_days().take_while(move |day| *day <= end)
+    }
+
+    /// `SQLite` date modifier that shifts a UTC timestamp to local time.
+    fn sqlite_modifier(&self) -> String {
+        format!("{:+} minutes", self.utc_offset.local_minus_utc() / 60)
+    }
+}
+
+/// Count the rows of `table` per local day of `timestamp_column`, with a
+/// zero for every day in `range` that has none.
+///
+/// Both names come from this crate, never from user input.
+pub(crate) async fn counts_by_day(
+    pool: &Pool<Sqlite>,
+    table: &str,
+    timestamp_column: &str,
+    range: &DayRange,
+) -> Result<Vec<(NaiveDate, i64)>, sqlx::Error> {
+    let sql = format!(
+        "SELECT date({timestamp_column}, ?1) AS day, COUNT(*) FROM {table}
+         WHERE date({timestamp_column}, ?1) BETWEEN ?2 AND ?3
+         GROUP BY day"
+    );
+    let rows: Vec<(String, i64)> = sqlx::query_as(&sql)
+        .bind(range.sqlite_modifier())
+        .bind(range.start.to_string())
+        .bind(range.end.to_string())
+        .fetch_all(pool)
+        .await?;
+
+    let counts = rows
+        .into_iter()
+        .map(|(day, count)| {
+            NaiveDate::parse_from_str(&day, "%Y-%m-%d")
+                .map(|day| (day, count))
+                .map_err(|e| sqlx::Error::Decode(Box::new(e)))
+        })
+        .collect::<Result<std::collections::HashMap<_, _>, _>>()?;
+
+    Ok(range
+        .days()
+        .map(|day| (day, counts.get(&day).copied().unwrap_or(0)))
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn day(s: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(s, "%Y-%m-%d").expect("valid date")
+    }
+
+    #[test]
+    fn test_days_are_inclusive() {
+        let range = DayRange::utc(day("2026-02-27"), day("2026-03-02"));
+        let days: Vec<_> = range.days().collect();
+        assert_eq!(
+            days,
+            vec![
+                day("2026-02-27"),
+                day("2026-02-28"),
+                day("2026-03-01"),
+                day("2026-03-02")
+            ]
+        );
+
+        let backwards = DayRange::utc(day("2026-03-02"), day("2026-03-01"));
+        assert_eq!(backwards.days().count(), 0);
+    }
+
+    #[test]
+    fn test_sqlite_modifier() {
+        let range = DayRange::utc(day("2026-01-01"), day("2026-01-01"));
+        assert_eq!(range.sqlite_modifier(), "+0 minutes");
+
+        let india = FixedOffset::east_opt(5 * 3600 + 1800).expect("valid offset");
+        assert_eq!(
+            range.with_utc_offset(india).sqlite_modifier(),
+            "+330 minutes"
+        );
+        let new_york = FixedOffset::west_opt(5 * 3600).expect("valid offset");
+        assert_eq!(
+            range.with_utc_offset(new_york).sqlite_modifier(),
+            "-300 minutes"
+        );
+    }
+}
@@ -0,0 +1,514 @@
+//! Browser data PII discovery scanner
+//!
+//! Looks for PII that browsers have stored locally: email addresses and
+//! phone numbers in visited URLs and page titles, and addresses, phones and
+//! emails saved for form autofill. Chrome and Firefox keep this data in
+//! SQLite databases inside each profile directory.
+//!
+//! Browsers hold locks on these files while running, so every database is
+//! copied into a temporary directory and the copy is opened read-only. The
+//! originals are never opened.
+
+use crate::filesystem::{PiiMatch, PiiPatterns};
+use serde::{Deserialize, Serialize};
+use spectral_permissions::{Permission, PermissionManager};
+use sqlx::sqlite::{SqliteConnectOptions, SqliteRow};
+use sqlx::{ConnectOptions, Row};
+use std::path::{Path, PathBuf};
+use tokio::fs;
+use tracing::{debug, info, warn};
+
+/// Chrome profile roots, relative to the user's home directory
+const CHROME_ROOTS: &[&str] = &[
+    ".config/google-chrome",
+    ".config/chromium",
+    "Library/Application Support/Google/Chrome",
+    "AppData/Local/Google/Chrome/User Data",
+];
+
+/// Firefox profile roots, relative to the user's home directory
+const FIREFOX_ROOTS: &[&str] = &[
+    ".mozilla/firefox",
+    "Library/Application Support/Firefox/Profiles",
+    "AppData/Roaming/Mozilla/Firefox/Profiles",
+];
+
+/// Queries for Chrome's `History` database, each selecting a field name and a value
+const CHROME_HISTORY_QUERIES: &[&str] = &[
+    "SELECT 'url', url FROM urls",
+    "SELECT 'title', title FROM urls",
+    "SELECT 'search', term FROM keyword_search_terms",
+];
+
+/// Queries for Chrome's `Web Data` database. The address tables differ
+/// between Chrome versions, so both the old and new ones are queried.
+const CHROME_AUTOFILL_QUERIES: &[&str] = &[
+    "SELECT name, value FROM autofill",
+    "SELECT 'email', email FROM autofill_profile_emails",
+    "SELECT 'phone', number FROM autofill_profile_phones",
+    "SELECT 'street_address', street_address FROM autofill_profiles",
+    "SELECT 'street_address', street_address FROM local_addresses",
+];
+
+/// Queries for Firefox's `places.sqlite` database
+const FIREFOX_HISTORY_QUERIES: &[&str] = &[
+    "SELECT 'url', url FROM moz_places",
+    "SELECT 'title', title FROM moz_places",
+];
+
+/// Queries for Firefox's `formhistory.sqlite` database
+const FIREFOX_AUTOFILL_QUERIES: &[&str] = &["SELECT fieldname, value FROM moz_formhistory"];
+
+/// Browser that owns a database
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum BrowserKind {
+    Chrome,
+    Firefox,
+}
+
+impl BrowserKind {
+    /// Get the browser's display name
+    pub fn name(&self) -> &'static str {
+        match self {
+            BrowserKind::Chrome => "Chrome",
+            BrowserKind::Firefox => "Firefox",
+        }
+    }
+}
+
+/// Kind of data a browser database holds
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum BrowserDataKind {
+    History,
+    Autofill,
+}
+
+impl BrowserDataKind {
+    /// Get human-readable description of the data
+    pub fn description(&self) -> &'static str {
+        match self {
+            BrowserDataKind::History => "browsing history",
+            BrowserDataKind::Autofill => "saved form data",
+        }
+    }
+}
+
+/// A browser database found in a profile directory
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BrowserDatabase {
+    pub browser: BrowserKind,
+    pub kind: BrowserDataKind,
+    pub path: PathBuf,
+}
+
+impl BrowserDatabase {
+    fn queries(&self) -> &'static [&'static str] {
+        match (self.browser, self.kind) {
+            (BrowserKind::Chrome, BrowserDataKind::History) => CHROME_HISTORY_QUERIES,
+            (BrowserKind::Chrome, BrowserDataKind::Autofill) => CHROME_AUTOFILL_QUERIES,
+            (BrowserKind::Firefox, BrowserDataKind::History) => FIREFOX_HISTORY_QUERIES,
+            (BrowserKind::Firefox, BrowserDataKind::Autofill) => FIREFOX_AUTOFILL_QUERIES,
+        }
+    }
+}
+
+/// Result of scanning a browser database
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BrowserScanResult {
+    pub browser: BrowserKind,
+    pub source: BrowserDataKind,
+    /// Path of the original database
+    pub path: PathBuf,
+    pub matches: Vec<PiiMatch>,
+}
+
+/// Find the Chrome and Firefox databases under a home directory
+pub fn find_browser_databases(home: &Path) -> Vec<BrowserDatabase> {
+    let mut databases = Vec::new();
+
+    for root in CHROME_ROOTS {
+        for profile in profile_dirs(&home.join(root)) {
+            push_if_exists(
+                &mut databases,
+                BrowserKind::Chrome,
+                BrowserDataKind::History,
+                profile.join("History"),
+            );
+            push_if_exists(
+                &mut databases,
+                BrowserKind::Chrome,
+                BrowserDataKind::Autofill,
+                profile.join("Web Data"),
+            );
+        }
+    }
+
+    for root in FIREFOX_ROOTS {
+        for profile in profile_dirs(&home.join(root)) {
+            push_if_exists(
+                &mut databases,
+                BrowserKind::Firefox,
+                BrowserDataKind::History,
+                profile.join("places.sqlite"),
+            );
+            push_if_exists(
+                &mut databases,
+                BrowserKind::Firefox,
+                BrowserDataKind::Autofill,
+                profile.join("formhistory.sqlite"),
+            );
+        }
+    }
+
+    databases
+}
+
+/// List the profile directories directly under a browser's profile root
+fn profile_dirs(root: &Path) -> Vec<PathBuf> {
+    let Ok(entries) = std::fs::read_dir(root) else {
+        return Vec::new();
+    };
+
+    let mut dirs: Vec<PathBuf> = entries
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| entry.file_type().is_ok_and(|t| t.is_dir()))
+        .map(|entry| entry.path())
+        .collect();
+    dirs.sort();
+    dirs
+}
+
+fn push_if_exists(
+    databases: &mut Vec<BrowserDatabase>,
+    browser: BrowserKind,
+    kind: BrowserDataKind,
+    path: PathBuf,
+) {
+    // symlink_metadata so a symlinked database is never followed
+    if std::fs::symlink_metadata(&path).is_ok_and(|m| m.is_file()) {
+        databases.push(BrowserDatabase {
+            browser,
+            kind,
+            path,
+        });
+    }
+}
+
+/// Scan the browser databases under a home directory for PII
+///
+/// Requires the [`Permission::ScanBrowserData`] permission. Databases that
+/// cannot be copied or read are logged and skipped.
+///
+/// # Errors
+/// Returns `PermissionError::Denied` if browser data scanning is not permitted.
+pub async fn scan_browser_data(
+    permissions: &PermissionManager,
+    home: &Path,
+    patterns: &PiiPatterns,
+) -> Result<Vec<BrowserScanResult>, spectral_permissions::PermissionError> {
+    permissions.request(Permission::ScanBrowserData)?;
+
+    let mut results = Vec::new();
+    for database in find_browser_databases(home) {
+        info!(
+            "Scanning {} {}: {:?}",
+            database.browser.name(),
+            database.kind.description(),
+            database.path
+        );
+        if let Some(result) = scan_browser_database(&database, patterns).await {
+            results.push(result);
+        }
+    }
+
+    Ok(results)
+}
+
+/// Scan a copy of a single browser database
+async fn scan_browser_database(
+    database: &BrowserDatabase,
+    patterns: &PiiPatterns,
+) -> Option<BrowserScanResult> {
+    let temp_dir = match tempfile::tempdir() {
+        Ok(dir) => dir,
+        Err(e) => {
+            warn!("Failed to create temporary directory: {}", e);
+            return None;
+        }
+    };
+
+    let copy = match copy_database(&database.path, temp_dir.path()).await {
+        Ok(copy) => copy,
+        Err(e) => {
+            warn!("Failed to copy {:?}: {}", database.path, e);
+            return None;
+        }
+    };
+
+    let mut conn = match SqliteConnectOptions::new()
+        .filename(&copy)
+        .read_only(true)
+        .connect()
+        .await
+    {
+        Ok(conn) => conn,
+        Err(e) => {
+            warn!("Failed to open copy of {:?}: {}", database.path, e);
+            return None;
+        }
+    };
+
+    let mut matches = Vec::new();
+    for query in database.queries() {
+        // Tables vary between browser versions, so missing ones are expected
+        let rows: Vec<SqliteRow> = match sqlx::query(query).fetch_all(&mut conn).await {
+            Ok(rows) => rows,
+            Err(e) => {
+                debug!("Skipping query on {:?}: {}", database.path, e);
+                continue;
+            }
+        };
+
+        for row in rows {
+            let field: String = row
+                .try_get::<Option<String>, _>(0)
+                .ok()
+                .flatten()
+                .unwrap_or_default();
+            let Some(value) = row.try_get::<Option<String>, _>(1).ok().flatten() else {
+                continue;
+            };
+            for found in find_in_field(patterns, &field, &value) {
+                if !matches.contains(&found) {
+                    matches.push(found);
+                }
+            }
+        }
+    }
+
+    if matches.is_empty() {
+        None
+    } else {
+        debug!("Found PII in browser database: {:?}", database.path);
+        Some(BrowserScanResult {
+            browser: database.browser,
+            source: database.kind,
+            path: database.path.clone(),
+            matches,
+        })
+    }
+}
+
+/// Copy a database, and its write-ahead log if present, into `dir`
+///
+/// Recent writes may still be in the WAL rather than the main file.
+async fn copy_database(original: &Path, dir: &Path) -> std::io::Result<PathBuf> {
+    let copy = dir.join("browser.sqlite");
+    fs::copy(original, &copy).await?;
+
+    let mut wal = original.as_os_str().to_owned();
+    wal.push("-wal");
+    let wal = PathBuf::from(wal);
+    if fs::symlink_metadata(&wal).await.is_ok_and(|m| m.is_file()) {
+        fs::copy(&wal, dir.join("browser.sqlite-wal")).await?;
+    }
+
+    Ok(copy)
+}
+
+/// Find PII in a stored value, using its field name to recognise addresses
+fn find_in_field(patterns: &PiiPatterns, field: &str, value: &str) -> Vec<PiiMatch> {
+    let mut matches = patterns.find_all(value);
+    if is_address_field(field) && !value.trim().is_empty() {
+        matches.push(PiiMatch::Address);
+    }
+    matches
+}
+
+/// Check if a form or autofill field name holds a postal address
+fn is_address_field(field: &str) -> bool {
+    let field = field.to_lowercase();
+    field == "address"
+        || field.contains("street")
+        || field.contains("address-line")
+        || field.contains("address_line")
+        || field.contains("addressline")
+        || field.contains("addr1")
+        || field.contains("addr2")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use spectral_permissions::GrantSource;
+    use sqlx::Connection;
+
+    async fn create_fixture(path: &Path, statements: &[&str]) {
+        std::fs::create_dir_all(path.parent().expect("fixture has a parent"))
+            .expect("create profile dir");
+        let mut conn = SqliteConnectOptions::new()
+            .filename(path)
+            .create_if_missing(true)
+            .connect()
+            .await
+            .expect("create fixture");
+        for statement in statements {
+            sqlx::query(statement)
+                .execute(&mut conn)
+                .await
+                .expect("populate fixture");
+        }
+        conn.close().await.expect("close fixture");
+    }
+
+    /// Home directory with a Chrome profile and a Firefox profile
+    async fn fixture_home() -> tempfile::TempDir {
+        let home = tempfile::tempdir().expect("create home");
+        let chrome = home.path().join(".config/google-chrome/Default");
+        let firefox = home
+            .path()
+            .join(".mozilla/firefox/abcd1234.default-release");
+
+        create_fixture(
+            &chrome.join("History"),
+            &[
+                "CREATE TABLE urls (id INTEGER PRIMARY KEY, url TEXT, title TEXT)",
+                "INSERT INTO urls (url, title) VALUES
+                    ('https://example.com/account?email=jane.doe@example.com', 'My account'),
+                    ('https://example.com/', 'Example Domain')",
+            ],
+        )
+        .await;
+        create_fixture(
+            &chrome.join("Web Data"),
+            &[
+                "CREATE TABLE autofill (name TEXT, value TEXT)",
+                "INSERT INTO autofill VALUES ('tel', '(555) 123-4567')",
+                "CREATE TABLE autofill_profiles (guid TEXT, street_address TEXT)",
+                "INSERT INTO autofill_profiles VALUES ('guid-1', '123 Main St')",
+            ],
+        )
+        .await;
+        create_fixture(
+            &firefox.join("places.sqlite"),
+            &[
+                "CREATE TABLE moz_places (id INTEGER PRIMARY KEY, url TEXT, title TEXT)",
+                "INSERT INTO moz_places (url, title) VALUES ('https://example.org/', 'Example')",
+            ],
+        )
+        .await;
+        create_fixture(
+            &firefox.join("formhistory.sqlite"),
+            &[
+                "CREATE TABLE moz_formhistory (id INTEGER PRIMARY KEY, fieldname TEXT, value TEXT)",
+                "INSERT INTO moz_formhistory (fieldname, value) VALUES
+                    ('street-address', '42 Elm Street'),
+                    ('email', 'jane@example.net')",
+            ],
+        )
+        .await;
+
+        home
+    }
+
+    fn permitted() -> PermissionManager {
+        let manager = PermissionManager::new();
+        manager.grant(Permission::ScanBrowserData, GrantSource::UserExplicit);
+        manager
+    }
+
+    fn result_for(
+        results: &[BrowserScanResult],
+        browser: BrowserKind,
+        source: BrowserDataKind,
+    ) -> Option<&BrowserScanResult> {
+        results
+            .iter()
+            .find(|r| r.browser == browser && r.source == source)
+    }
+
+    #[tokio::test]
+    async fn test_finds_browser_databases() {
+        let home = fixture_home().await;
+
+        let databases = find_browser_databases(home.path());
+
+        assert_eq!(databases.len(), 4);
+        assert!(databases.iter().any(|d| d.browser == BrowserKind::Chrome
+            && d.kind == BrowserDataKind::Autofill
+            && d.path.ends_with("Default/Web Data")));
+        assert!(databases.iter().any(|d| d.browser == BrowserKind::Firefox
+            && d.kind == BrowserDataKind::History
+            && d.path.ends_with("places.sqlite")));
+    }
+
+    #[tokio::test]
+    async fn test_scan_discovers_stored_pii() {
+        let home = fixture_home().await;
+
+        let results = scan_browser_data(&permitted(), home.path(), &PiiPatterns::new())
+            .await
+            .expect("scan browser data");
+
+        let history = result_for(&results, BrowserKind::Chrome, BrowserDataKind::History)
+            .expect("chrome history finding");
+        assert_eq!(history.matches, vec![PiiMatch::Email]);
+
+        let autofill = result_for(&results, BrowserKind::Chrome, BrowserDataKind::Autofill)
+            .expect("chrome autofill finding");
+        assert!(autofill.matches.contains(&PiiMatch::Phone));
+        assert!(autofill.matches.contains(&PiiMatch::Address));
+
+        let form_history = result_for(&results, BrowserKind::Firefox, BrowserDataKind::Autofill)
+            .expect("firefox form history finding");
+        assert!(form_history.matches.contains(&PiiMatch::Address));
+        assert!(form_history.matches.contains(&PiiMatch::Email));
+
+        // Firefox history has no PII, so it is not reported
+        assert!(result_for(&results, BrowserKind::Firefox, BrowserDataKind::History).is_none());
+    }
+
+    #[tokio::test]
+    async fn test_scan_leaves_originals_untouched() {
+        let home = fixture_home().await;
+        let databases = find_browser_databases(home.path());
+        let before: Vec<Vec<u8>> = databases
+            .iter()
+            .map(|d| std::fs::read(&d.path).expect("read original"))
+            .collect();
+
+        scan_browser_data(&permitted(), home.path(), &PiiPatterns::new())
+            .await
+            .expect("scan browser data");
+
+        for (database, original) in databases.iter().zip(before) {
+            assert_eq!(
+                std::fs::read(&database.path).expect("read original"),
+                original
+            );
+            let mut journal = database.path.as_os_str().to_owned();
+            journal.push("-journal");
+            assert!(!PathBuf::from(journal).exists());
+        }
+    }
+
+    #[tokio::test]
+    async fn test_scan_requires_permission() {
+        let home = fixture_home().await;
+
+        let result =
+            scan_browser_data(&PermissionManager::new(), home.path(), &PiiPatterns::new()).await;
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_is_address_field() {
+        assert!(is_address_field("street-address"));
+        assert!(is_address_field("street_address"));
+        assert!(is_address_field("address-line1"));
+        assert!(is_address_field("Address"));
+        assert!(!is_address_field("email_address"));
+        assert!(!is_address_field("tel"));
+    }
+}
@@ -93,6 +93,7 @@ pub enum PiiMatch {
     Email,
     Phone,
     Ssn,
+    Address,
 }
 
 impl PiiMatch {
@@ -102,6 +103,7 @@ impl PiiMatch {
             PiiMatch::Email => "Email address",
             PiiMatch::Phone => "Phone number",
             PiiMatch::Ssn => "Social Security Number",
+            PiiMatch::Address => "Postal address",
         }
     }
 
@@ -111,6 +113,7 @@ impl PiiMatch {
             PiiMatch::Email => "medium",
             PiiMatch::Phone => "medium",
             PiiMatch::Ssn => "critical",
+            PiiMatch::Address => "high",
         }
     }
 }
@@ -295,6 +298,7 @@ mod tests {
         assert_eq!(PiiMatch::Email.description(), "Email address");
         assert_eq!(PiiMatch::Phone.description(), "Phone number");
         assert_eq!(PiiMatch::Ssn.description(), "Social Security Number");
+        assert_eq!(PiiMatch::Address.description(), "Postal address");
     }
 
     #[test]
@@ -302,6 +306,7 @@ mod tests {
         assert_eq!(PiiMatch::Email.risk_level(), "medium");
         assert_eq!(PiiMatch::Phone.risk_level(), "medium");
         assert_eq!(PiiMatch::Ssn.risk_level(), "critical");
+        assert_eq!(PiiMatch::Address.risk_level(), "high");
     }
 
     #[test]
@@ -2,9 +2,14 @@
 //!
 //! Local PII discovery for scanning filesystems, browsers, and email.
 
+pub mod browser;
 pub mod filesystem;
 
 // Re-export main types
+pub use browser::{
+    find_browser_databases, scan_browser_data, BrowserDataKind, BrowserDatabase, BrowserKind,
+    BrowserScanResult,
+};
 pub use filesystem::{
     is_scannable, scan_directory, scan_file, FileScanResult, PiiMatch, PiiPatterns,
 };
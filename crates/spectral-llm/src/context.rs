@@ -0,0 +1,331 @@
+//! Fitting requests into a provider's context window.
+//!
+//! Broker pages fed to LLM-guided parsing can be far larger than a model's
+//! context window. Before a request is sent, the router counts its tokens
+//! with the provider's [`count_tokens`](crate::LlmProvider::count_tokens)
+//! and, if it would not fit, shortens the last message using a
+//! [`TruncationStrategy`].
+
+use crate::error::{LlmError, Result};
+use crate::provider::CompletionRequest;
+use once_cell::sync::Lazy;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+
+/// Rough number of characters per token for English text and HTML.
+///
+/// Real tokenizers vary by model; this errs on the side of overestimating so
+/// a truncated request still fits.
+const CHARS_PER_TOKEN: usize = 4;
+
+/// Letters per token assumed for a word in [`count_bpe_tokens`]; common
+/// English words up to this length are a single token.
+const LETTERS_PER_TOKEN: usize = 7;
+
+/// Pre-tokenization pattern of `OpenAI`'s `cl100k_base` tokenizer, without
+/// the whitespace lookahead the `regex` crate does not support.
+static BPE_PIECE_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(
+        r"(?i:'s|'t|'re|'ve|'m|'ll|'d)|[^\r\n\p{L}\p{N}]?\p{L}+|\p{N}{1,3}| ?[^\s\p{L}\p{N}]+[\r\n]*|\s*[\r\n]+|\s+",
+    )
+    .expect("valid BPE piece regex")
+});
+
+/// Marker inserted where content was removed.
+pub const TRUNCATION_MARKER: &str = "\n[... content truncated ...]\n";
+
+/// Which part of an oversized message to keep.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TruncationStrategy {
+    /// Keep the start of the message and drop the end
+    Head,
+    /// Keep the end of the message and drop the start
+    Tail,
+    /// Keep the start and end of the message and drop the middle
+    #[default]
+    MiddleOut,
+}
+
+/// Estimate the number of tokens in a piece of text.
+///
+/// A character-count heuristic for providers without a known tokenizer.
+#[must_use]
+pub fn estimate_tokens(text: &str) -> usize {
+    text.chars().count().div_ceil(CHARS_PER_TOKEN)
+}
+
+/// Count tokens the way `OpenAI`'s byte-pair tokenizers split text.
+///
+/// Text is split into the pieces `cl100k_base` merges within: words with
+/// their leading space, runs of up to three digits, punctuation and
+/// whitespace. Each piece is one token, except that words longer than
+/// [`LETTERS_PER_TOKEN`] take more and non-ASCII letters take one each.
+/// Without the merge table this is an approximation, but it tracks real
+/// counts far more closely than [`estimate_tokens`] on prose.
+#[must_use]
+pub fn count_bpe_tokens(text: &str) -> usize {
+    BPE_PIECE_REGEX
+        .find_iter(text)
+        .map(|piece| {
+            let piece = piece.as_str();
+            let ascii_letters = piece.chars().filter(char::is_ascii_alphabetic).count();
+            let other_letters = piece
+                .chars()
+                .filter(|c| c.is_alphabetic() && !c.is_ascii())
+                .count();
+            (ascii_letters.div_ceil(LETTERS_PER_TOKEN) + other_letters).max(1)
+        })
+        .sum()
+}
+
+/// Estimate the tokens a request occupies in the context window, including
+/// the tokens reserved for the response.
+#[must_use]
+pub fn estimate_request_tokens(request: &CompletionRequest) -> usize {
+    count_request_tokens(request, estimate_tokens)
+}
+
+/// Count the tokens a request occupies in the context window with `count`,
+/// including the tokens reserved for the response.
+#[must_use]
+pub fn count_request_tokens(request: &CompletionRequest, count: impl Fn(&str) -> usize) -> usize {
+    let prompt: usize = request
+        .system_prompt
+        .iter()
+        .map(String::as_str)
+        .chain(
+            request
+                .messages
+                .iter()
+                .map(|message| message.content.as_str()),
+        )
+        .map(count)
+        .sum();
+    prompt + response_reserve(request)
+}
+
+/// Shorten the last message of `request` so it fits in `max_context_tokens`,
+/// counting tokens with [`estimate_tokens`].
+///
+/// Returns the estimated number of tokens removed, or `None` if the request
+/// already fit.
+///
+/// # Errors
+/// Returns `LlmError::InvalidRequest` if the request does not fit even with
+/// the last message removed entirely.
+pub fn fit_to_context(
+    request: &mut CompletionRequest,
+    max_context_tokens: usize,
+    strategy: TruncationStrategy,
+) -> Result<Option<usize>> {
+    fit_to_context_with(request, max_context_tokens, strategy, estimate_tokens)
+}
+
+/// Shorten the last message of `request` so it fits in `max_context_tokens`,
+/// counting tokens with `count`.
+///
+/// Returns the number of tokens removed, or `None` if the request already
+/// fit.
+///
+/// # Errors
+/// Returns `LlmError::InvalidRequest` if the request does not fit even with
+/// the last message removed entirely.
+pub fn fit_to_context_with(
+    request: &mut CompletionRequest,
+    max_context_tokens: usize,
+    strategy: TruncationStrategy,
+    count: impl Fn(&str) -> usize,
+) -> Result<Option<usize>> {
+    let counted = count_request_tokens(request, &count);
+    if counted <= max_context_tokens {
+        return Ok(None);
+    }
+
+    let Some(last) = request.messages.last_mut() else {
+        return Err(too_large(counted, max_context_tokens));
+    };
+    let last_tokens = count(&last.content);
+    let fixed_tokens = counted - last_tokens;
+    let marker_tokens = count(TRUNCATION_MARKER);
+    if fixed_tokens + marker_tokens >= max_context_tokens {
+        return Err(too_large(counted, max_context_tokens));
+    }
+
+    // Start from the share of characters the budget allows and shrink until
+    // the counter agrees, since tokens per character vary across the text
+    let budget = max_context_tokens - fixed_tokens;
+    let chars = last.content.chars().count();
+    let mut keep_chars = chars * (budget - marker_tokens) / last_tokens.max(1);
+    let truncated = loop {
+        let truncated = truncate_text(&last.content, keep_chars, strategy);
+        if keep_chars == 0 || count(&truncated) <= budget {
+            break truncated;
+        }
+        keep_chars = keep_chars * 9 / 10;
+    };
+    last.content = truncated;
+    Ok(Some(last_tokens.saturating_sub(count(&last.content))))
+}
+
+/// Keep at most `keep_chars` characters of `text`, plus the truncation marker.
+fn truncate_text(text: &str, keep_chars: usize, strategy: TruncationStrategy) -> String {
+    let chars: Vec<char> = text.chars().collect();
+    if chars.len() <= keep_chars {
+        return text.to_string();
+    }
+
+    let (head, tail) = match strategy {
+        TruncationStrategy::Head => (keep_chars, 0),
+        TruncationStrategy::Tail => (0, keep_chars),
+        TruncationStrategy::MiddleOut => (keep_chars.div_ceil(2), keep_chars / 2),
+    };
+
+    let mut truncated = String::with_capacity(keep_chars * 4 + TRUNCATION_MARKER.len());
+    truncated.extend(&chars[..head]);
+    truncated.push_str(TRUNCATION_MARKER);
+    truncated.extend(&chars[chars.len() - tail..]);
+    truncated
+}
+
+/// Tokens set aside for the response.
+fn response_reserve(request: &CompletionRequest) -> usize {
+    request
+        .max_tokens
+        .map_or(0, |tokens| usize::try_from(tokens).unwrap_or(usize::MAX))
+}
+
+fn too_large(estimated: usize, max_context_tokens: usize) -> LlmError {
+    LlmError::InvalidRequest(format!(
+        "request needs about {estimated} tokens but the context window is {max_context_tokens}"
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_estimate_tokens_rounds_up() {
+        assert_eq!(estimate_tokens(""), 0);
+        assert_eq!(estimate_tokens("abc"), 1);
+        assert_eq!(estimate_tokens("abcdefghi"), 3);
+    }
+
+    #[test]
+    fn test_estimate_tokens_grows_with_length() {
+        let text = "Please remove my listing, 123 Main St, Springfield. ".repeat(20);
+        let mut previous = 0;
+        for end in (0..=text.len()).filter(|&i| text.is_char_boundary(i)) {
+            let tokens = estimate_tokens(&text[..end]);
+            assert!(tokens >= previous, "count fell at {end} characters");
+            previous = tokens;
+        }
+    }
+
+    #[test]
+    fn test_bpe_counts_match_known_counts() {
+        // Token counts from OpenAI's cl100k_base tokenizer
+        for (text, expected) in [
+            ("hello world", 2),
+            ("Hello, world!", 4),
+            ("The quick brown fox jumps over the lazy dog.", 10),
+            ("tiktoken is great!", 6),
+            ("1234567", 3),
+        ] {
+            let counted = count_bpe_tokens(text);
+            let tolerance = (expected / 10).max(1);
+            assert!(
+                counted.abs_diff(expected) <= tolerance,
+                "{text:?}: counted {counted}, expected {expected}"
+            );
+        }
+        assert_eq!(count_bpe_tokens(""), 0);
+    }
+
+    #[test]
+    fn test_fit_with_custom_counter() {
+        // Every word is a token, so prose is denser than the heuristic
+        let words = |text: &str| text.split_whitespace().count();
+        let mut request = CompletionRequest::new("word ".repeat(500));
+
+        let removed = fit_to_context_with(&mut request, 100, TruncationStrategy::Head, words)
+            .expect("truncated");
+        assert!(removed.is_some());
+        assert!(count_request_tokens(&request, words) <= 100);
+    }
+
+    #[test]
+    fn test_request_that_fits_is_untouched() {
+        let mut request = CompletionRequest::new("short page");
+        let removed = fit_to_context(&mut request, 100, TruncationStrategy::Head).expect("fits");
+        assert_eq!(removed, None);
+        assert_eq!(request.messages[0].content, "short page");
+    }
+
+    #[test]
+    fn test_head_keeps_start() {
+        let page = format!("{}{}", "a".repeat(400), "z".repeat(400));
+        let mut request = CompletionRequest::new(page);
+
+        let removed =
+            fit_to_context(&mut request, 100, TruncationStrategy::Head).expect("truncated");
+        assert!(removed.is_some());
+        assert!(estimate_request_tokens(&request) <= 100);
+
+        let content = &request.messages[0].content;
+        assert!(content.starts_with('a'));
+        assert!(content.ends_with(TRUNCATION_MARKER));
+        assert!(!content.contains('z'));
+    }
+
+    #[test]
+    fn test_tail_keeps_end() {
+        let page = format!("{}{}", "x".repeat(400), "z".repeat(400));
+        let mut request = CompletionRequest::new(page);
+
+        fit_to_context(&mut request, 100, TruncationStrategy::Tail).expect("truncated");
+        assert!(estimate_request_tokens(&request) <= 100);
+
+        let content = &request.messages[0].content;
+        assert!(content.starts_with(TRUNCATION_MARKER));
+        assert!(content.ends_with('z'));
+        assert!(!content.contains('x'));
+    }
+
+    #[test]
+    fn test_middle_out_keeps_both_ends() {
+        let page = format!("{}{}{}", "a".repeat(300), "m".repeat(400), "z".repeat(300));
+        let mut request = CompletionRequest::new(page);
+
+        fit_to_context(&mut request, 100, TruncationStrategy::MiddleOut).expect("truncated");
+        assert!(estimate_request_tokens(&request) <= 100);
+
+        let content = &request.messages[0].content;
+        assert!(content.starts_with('a'));
+        assert!(content.ends_with('z'));
+        assert!(content.contains(TRUNCATION_MARKER));
+        assert!(!content.contains('m'));
+    }
+
+    #[test]
+    fn test_system_prompt_and_response_reserve_count_against_budget() {
+        let mut request = CompletionRequest::new("x".repeat(800))
+            .with_system_prompt("s".repeat(80))
+            .with_max_tokens(40);
+
+        fit_to_context(&mut request, 100, TruncationStrategy::Head).expect("truncated");
+        assert!(estimate_request_tokens(&request) <= 100);
+        assert_eq!(
+            request.system_prompt.as_deref(),
+            Some("s".repeat(80).as_str())
+        );
+    }
+
+    #[test]
+    fn test_request_that_cannot_fit_is_rejected() {
+        let mut request = CompletionRequest::new("page").with_max_tokens(200);
+        let result = fit_to_context(&mut request, 100, TruncationStrategy::Head);
+        assert!(matches!(result, Err(LlmError::InvalidRequest(_))));
+    }
+}
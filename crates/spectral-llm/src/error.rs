@@ -1,5 +1,7 @@
 //! Error types for the LLM subsystem.
 
+use crate::pii_filter::PiiType;
+use std::time::Duration;
 use thiserror::Error;
 
 /// Errors that can occur during LLM operations.
@@ -45,6 +47,15 @@ pub enum LlmError {
         message: String,
     },
 
+    /// Provider returned HTTP 429, optionally saying when to retry
+    #[error("rate limited by {provider}{}", retry_after.map(|d| format!(", retry after {}s", d.as_secs())).unwrap_or_default())]
+    RateLimited {
+        /// Provider name
+        provider: String,
+        /// Delay requested by the provider's `Retry-After` header
+        retry_after: Option<Duration>,
+    },
+
     /// Invalid API key or authentication failure
     #[error("authentication failed for {provider}: {message}")]
     AuthenticationFailed {
@@ -74,6 +85,37 @@ pub enum LlmError {
         details: String,
     },
 
+    /// A cloud provider's streamed output contained high-risk PII, so the
+    /// stream was stopped
+    #[error("LLM output contained {}, stream stopped", pii_type.as_str())]
+    OutputPiiLeak {
+        /// Type of PII found in the output
+        pii_type: PiiType,
+    },
+
+    /// No prompt template with this name is stored
+    #[error("prompt template not found: {name}")]
+    TemplateNotFound {
+        /// Template name
+        name: String,
+    },
+
+    /// A prompt template uses variables that were not supplied
+    #[error("prompt template '{template}' is missing variables: {missing}")]
+    TemplateVariablesMissing {
+        /// Template name
+        template: String,
+        /// Names of the missing variables, comma-separated
+        missing: String,
+    },
+
+    /// Output that drives an action looks like a prompt injection
+    #[error("suspected prompt injection in LLM output: {markers}")]
+    SuspectedInjection {
+        /// Labels of the injection markers found
+        markers: String,
+    },
+
     /// Network error
     #[error("network error: {0}")]
     Network(#[from] reqwest::Error),
@@ -119,6 +161,21 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_rate_limited_display() {
+        let err = LlmError::RateLimited {
+            provider: "openai".to_string(),
+            retry_after: Some(Duration::from_secs(20)),
+        };
+        assert_eq!(err.to_string(), "rate limited by openai, retry after 20s");
+
+        let err = LlmError::RateLimited {
+            provider: "openai".to_string(),
+            retry_after: None,
+        };
+        assert_eq!(err.to_string(), "rate limited by openai");
+    }
+
     #[test]
     fn test_pii_blocked_error() {
         let err = LlmError::PiiBlocked {
@@ -126,4 +183,23 @@ mod tests {
         };
         assert!(err.to_string().contains("PII detected"));
     }
+
+    #[test]
+    fn test_output_pii_leak_error() {
+        let err = LlmError::OutputPiiLeak {
+            pii_type: PiiType::Ssn,
+        };
+        assert_eq!(err.to_string(), "LLM output contained SSN, stream stopped");
+    }
+
+    #[test]
+    fn test_suspected_injection_error() {
+        let err = LlmError::SuspectedInjection {
+            markers: "ignore previous instructions".to_string(),
+        };
+        assert_eq!(
+            err.to_string(),
+            "suspected prompt injection in LLM output: ignore previous instructions"
+        );
+    }
 }
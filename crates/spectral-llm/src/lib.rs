@@ -10,6 +10,10 @@
 //! - **PII Filtering**: Detect and sanitize personally identifiable information
 //! - **Multiple Strategies**: Redact, tokenize, or block PII in requests
 //! - **Local-First**: Prefer local models for sensitive data
+//! - **Context Guard**: Truncate oversized requests to the provider's
+//!   context window
+//! - **Output Sanitization**: Strip control sequences and refuse injected
+//!   instructions before acting on a response
 //!
 //! # Example
 //!
@@ -57,15 +61,23 @@
 #![allow(clippy::missing_errors_doc)]
 #![allow(clippy::missing_panics_doc)]
 
+pub mod context;
 pub mod error;
 pub mod pii_filter;
+pub mod prompt;
 pub mod provider;
 pub mod providers;
 pub mod router;
+pub mod sanitize;
 
 // Re-export commonly used types
+pub use context::{
+    count_bpe_tokens, count_request_tokens, estimate_request_tokens, estimate_tokens,
+    TruncationStrategy,
+};
 pub use error::{LlmError, Result};
 pub use pii_filter::{FilterResult, FilterStrategy, PiiFilter, PiiType};
+pub use prompt::{PromptTemplate, PromptTemplateStore};
 pub use provider::{
     CompletionRequest, CompletionResponse, CompletionStream, LlmProvider, Message,
     ProviderCapabilities, Role, StreamChunk, Usage,
@@ -73,4 +85,5 @@ pub use provider::{
 pub use providers::{
     AnthropicProvider, GeminiProvider, LmStudioProvider, OllamaProvider, OpenAiProvider,
 };
-pub use router::{LlmRouter, RoutingPreference, TaskType};
+pub use router::{LlmRouter, ModelRoute, RateLimitRetry, RoutingPreference, TaskType};
+pub use sanitize::{sanitize_llm_output, SanitizedOutput};
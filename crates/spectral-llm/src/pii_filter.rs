@@ -1,6 +1,13 @@
 //! PII detection and filtering for LLM requests.
+//!
+//! [`PiiFilter::filter`] cleans requests before they are sent.
+//! [`PiiFilter::guard_stream`] watches what comes back: a model can still
+//! echo PII it saw elsewhere, so streamed output is stopped as soon as it
+//! contains a high-risk type.
 
 use crate::error::{LlmError, Result};
+use crate::provider::CompletionStream;
+use futures::{future, StreamExt};
 use once_cell::sync::Lazy;
 use regex::Regex;
 use serde::{Deserialize, Serialize};
@@ -139,6 +146,48 @@ impl PiiFilter {
         (result, token_map)
     }
 
+    /// Stop `stream` as soon as its output contains high-risk PII (see
+    /// [`PiiType::is_high_risk`]).
+    ///
+    /// The text streamed so far is rescanned with each chunk, so a value
+    /// split across chunks is still caught. The chunk that completes the
+    /// match is replaced by `LlmError::OutputPiiLeak` and the stream ends.
+    /// Independent of the filter strategy, which applies to requests.
+    #[must_use]
+    pub fn guard_stream(&self, stream: CompletionStream) -> CompletionStream {
+        let patterns: Vec<PiiPattern> = self
+            .patterns
+            .iter()
+            .filter(|p| p.pii_type.is_high_risk())
+            .cloned()
+            .collect();
+
+        Box::pin(
+            stream.scan((String::new(), false), move |(output, stopped), item| {
+                if *stopped {
+                    return future::ready(None);
+                }
+                let item = item.and_then(|chunk| {
+                    output.push_str(&chunk.delta);
+                    match patterns.iter().find(|p| p.regex.is_match(output)) {
+                        Some(pattern) => {
+                            *stopped = true;
+                            tracing::warn!(
+                                pii_type = pattern.pii_type.as_str(),
+                                "Stopped LLM stream that emitted PII"
+                            );
+                            Err(LlmError::OutputPiiLeak {
+                                pii_type: pattern.pii_type,
+                            })
+                        }
+                        None => Ok(chunk),
+                    }
+                });
+                future::ready(Some(item))
+            }),
+        )
+    }
+
     /// Detokenize text by replacing tokens with original PII values.
     #[must_use]
     pub fn detokenize(&self, text: &str, token_map: &HashMap<String, String>) -> String {
@@ -238,6 +287,17 @@ impl PiiType {
             Self::IpAddress => "IP_ADDRESS",
         }
     }
+
+    /// Whether model output containing this type stops a stream (see
+    /// [`PiiFilter::guard_stream`]).
+    ///
+    /// Emails, phone numbers and addresses routinely appear in legitimate
+    /// answers (a broker's opt-out address, say); SSNs and card numbers do
+    /// not.
+    #[must_use]
+    pub fn is_high_risk(&self) -> bool {
+        matches!(self, Self::Ssn | Self::CreditCard)
+    }
 }
 
 /// A PII detection pattern.
@@ -309,6 +369,7 @@ static IPV4_REGEX: Lazy<Regex> =
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::provider::StreamChunk;
 
     #[test]
     fn test_email_detection() {
@@ -411,6 +472,56 @@ mod tests {
         assert!(types.contains(&PiiType::Phone));
     }
 
+    fn chunks(deltas: &[&str]) -> CompletionStream {
+        let chunks: Vec<Result<StreamChunk>> = deltas
+            .iter()
+            .map(|delta| {
+                Ok(StreamChunk {
+                    delta: (*delta).to_string(),
+                    is_final: false,
+                    stop_reason: None,
+                })
+            })
+            .collect();
+        Box::pin(futures::stream::iter(chunks))
+    }
+
+    #[tokio::test]
+    async fn test_guard_stream_stops_on_ssn() {
+        let guarded = PiiFilter::new().guard_stream(chunks(&[
+            "The record lists ",
+            "SSN 123-45-",
+            "6789 for this person",
+            " and more",
+        ]));
+        let items: Vec<Result<StreamChunk>> = guarded.collect().await;
+
+        // The SSN is split across chunks; the chunk completing it is withheld
+        assert_eq!(items.len(), 3);
+        assert_eq!(items[1].as_ref().expect("chunk").delta, "SSN 123-45-");
+        assert!(matches!(
+            items[2],
+            Err(LlmError::OutputPiiLeak {
+                pii_type: PiiType::Ssn
+            })
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_guard_stream_passes_low_risk_output() {
+        let deltas = ["Email privacy@broker.example ", "or call (555) 123-4567."];
+        let items: Vec<Result<StreamChunk>> = PiiFilter::new()
+            .guard_stream(chunks(&deltas))
+            .collect()
+            .await;
+
+        let text: String = items
+            .into_iter()
+            .map(|item| item.expect("chunk").delta)
+            .collect();
+        assert_eq!(text, deltas.concat());
+    }
+
     #[test]
     fn test_ipv4_detection() {
         let filter = PiiFilter::new();
@@ -0,0 +1,177 @@
+//! Named prompt templates kept as data rather than in code.
+//!
+//! A [`PromptTemplate`] holds a prompt (and optionally a system prompt) with
+//! `{{variable}}` placeholders. Templates are looked up by name from a
+//! [`PromptTemplateStore`], so prompts can be changed without recompiling;
+//! [`LlmRouter::complete_template`](crate::LlmRouter::complete_template)
+//! renders one and sends it.
+
+use crate::error::{LlmError, Result};
+use crate::provider::CompletionRequest;
+use crate::router::TaskType;
+use async_trait::async_trait;
+use once_cell::sync::Lazy;
+use regex::{Captures, Regex};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::hash::BuildHasher;
+
+/// A named prompt with `{{variable}}` placeholders.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PromptTemplate {
+    /// Name the template is looked up by
+    pub name: String,
+    /// The user prompt
+    pub prompt: String,
+    /// Optional system prompt, which may also use variables
+    #[serde(default)]
+    pub system_prompt: Option<String>,
+    /// Task the prompt is for, used for routing
+    #[serde(default)]
+    pub task_type: Option<TaskType>,
+}
+
+impl PromptTemplate {
+    /// Create a template with just a user prompt.
+    #[must_use]
+    pub fn new(name: impl Into<String>, prompt: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            prompt: prompt.into(),
+            system_prompt: None,
+            task_type: None,
+        }
+    }
+
+    /// Set the system prompt.
+    #[must_use]
+    pub fn with_system_prompt(mut self, system_prompt: impl Into<String>) -> Self {
+        self.system_prompt = Some(system_prompt.into());
+        self
+    }
+
+    /// Set the task type.
+    #[must_use]
+    pub fn with_task_type(mut self, task_type: TaskType) -> Self {
+        self.task_type = Some(task_type);
+        self
+    }
+
+    /// Fill in the placeholders and build a request.
+    ///
+    /// # Errors
+    /// Returns `LlmError::TemplateVariablesMissing` listing every placeholder
+    /// without a value in `vars`.
+    pub fn render(&self, vars: &HashMap<String, String>) -> Result<CompletionRequest> {
+        let mut missing = Vec::new();
+        let prompt = fill(&self.prompt, vars, &mut missing);
+        let system_prompt = self
+            .system_prompt
+            .as_deref()
+            .map(|system| fill(system, vars, &mut missing));
+
+        if !missing.is_empty() {
+            return Err(LlmError::TemplateVariablesMissing {
+                template: self.name.clone(),
+                missing: missing.join(", "),
+            });
+        }
+
+        let mut request = CompletionRequest::new(prompt);
+        if let Some(system_prompt) = system_prompt {
+            request = request.with_system_prompt(system_prompt);
+        }
+        if let Some(task_type) = self.task_type {
+            request = request.with_task_type(task_type);
+        }
+        Ok(request)
+    }
+}
+
+/// Source of prompt templates by name.
+#[async_trait]
+pub trait PromptTemplateStore: Send + Sync {
+    /// Look up the template called `name`, or `None` if there is none.
+    async fn load_template(&self, name: &str) -> Result<Option<PromptTemplate>>;
+}
+
+/// Templates held in memory, keyed by name.
+#[async_trait]
+impl<S: BuildHasher + Send + Sync> PromptTemplateStore for HashMap<String, PromptTemplate, S> {
+    async fn load_template(&self, name: &str) -> Result<Option<PromptTemplate>> {
+        Ok(self.get(name).cloned())
+    }
+}
+
+static PLACEHOLDER_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"\{\{\s*([A-Za-z0-9_]+)\s*\}\}").expect("valid placeholder regex"));
+
+/// Replace placeholders in `text`, adding names without a value to `missing`.
+fn fill(text: &str, vars: &HashMap<String, String>, missing: &mut Vec<String>) -> String {
+    PLACEHOLDER_REGEX
+        .replace_all(text, |caps: &Captures| {
+            let name = &caps[1];
+            if let Some(value) = vars.get(name) {
+                value.clone()
+            } else {
+                if !missing.iter().any(|m| m == name) {
+                    missing.push(name.to_string());
+                }
+                String::new()
+            }
+        })
+        .into_owned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn vars(pairs: &[(&str, &str)]) -> HashMap<String, String> {
+        pairs
+            .iter()
+            .map(|(k, v)| ((*k).to_string(), (*v).to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn test_render_fills_prompt_and_system_prompt() {
+        let template = PromptTemplate::new(
+            "classify_reply",
+            "Classify this reply from {{ broker }}:\n{{reply}}",
+        )
+        .with_system_prompt("Answer with one word. Broker: {{broker}}")
+        .with_task_type(TaskType::Classification);
+
+        let request = template
+            .render(&vars(&[
+                ("broker", "Spokeo"),
+                ("reply", "Your data was removed."),
+            ]))
+            .expect("render");
+
+        assert_eq!(
+            request.messages[0].content,
+            "Classify this reply from Spokeo:\nYour data was removed."
+        );
+        assert_eq!(
+            request.system_prompt.as_deref(),
+            Some("Answer with one word. Broker: Spokeo")
+        );
+        assert_eq!(request.task_type, Some(TaskType::Classification));
+    }
+
+    #[test]
+    fn test_render_reports_every_missing_variable() {
+        let template = PromptTemplate::new("draft", "Dear {{broker}}, remove {{name}} ({{name}})")
+            .with_system_prompt("Write in {{language}}");
+
+        let err = template
+            .render(&vars(&[("broker", "Spokeo")]))
+            .expect_err("variables missing");
+        assert_eq!(
+            err.to_string(),
+            "prompt template 'draft' is missing variables: name, language"
+        );
+    }
+}
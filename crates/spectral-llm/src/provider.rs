@@ -1,6 +1,7 @@
 //! Core LLM provider trait and request/response types.
 
 use crate::error::Result;
+use crate::router::TaskType;
 use async_trait::async_trait;
 use futures::stream::BoxStream;
 use serde::{Deserialize, Serialize};
@@ -28,6 +29,14 @@ pub trait LlmProvider: Send + Sync {
 
     /// Get the unique identifier for this provider.
     fn provider_id(&self) -> &str;
+
+    /// Count the tokens `text` takes up for this provider.
+    ///
+    /// Providers with a known tokenizer override this; the default is the
+    /// character-count heuristic [`estimate_tokens`](crate::estimate_tokens).
+    fn count_tokens(&self, text: &str) -> usize {
+        crate::context::estimate_tokens(text)
+    }
 }
 
 /// Capabilities of an LLM provider.
@@ -56,6 +65,20 @@ pub struct ProviderCapabilities {
 
     /// Relative cost per token (0 = free/local, higher = more expensive)
     pub cost_tier: u8,
+
+    /// Models the provider can serve, empty when the provider does not report them
+    #[serde(default)]
+    pub available_models: Vec<String>,
+}
+
+impl ProviderCapabilities {
+    /// Whether the provider can serve `model`.
+    ///
+    /// Providers that do not report their models are assumed to serve any model.
+    #[must_use]
+    pub fn has_model(&self, model: &str) -> bool {
+        self.available_models.is_empty() || self.available_models.iter().any(|m| m == model)
+    }
 }
 
 /// Request for LLM completion.
@@ -76,6 +99,14 @@ pub struct CompletionRequest {
     /// Stop sequences (optional)
     pub stop_sequences: Vec<String>,
 
+    /// Kind of task, used by the router to pick a provider and model (optional)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub task_type: Option<TaskType>,
+
+    /// Model to use instead of the provider's configured model (optional)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub model: Option<String>,
+
     /// Additional provider-specific options
     #[serde(flatten)]
     pub extra: serde_json::Value,
@@ -91,10 +122,26 @@ impl CompletionRequest {
             temperature: None,
             system_prompt: None,
             stop_sequences: Vec::new(),
+            task_type: None,
+            model: None,
             extra: serde_json::Value::Null,
         }
     }
 
+    /// Set the task type.
+    #[must_use]
+    pub fn with_task_type(mut self, task_type: TaskType) -> Self {
+        self.task_type = Some(task_type);
+        self
+    }
+
+    /// Set the model, overriding the provider's configured model.
+    #[must_use]
+    pub fn with_model(mut self, model: impl Into<String>) -> Self {
+        self.model = Some(model.into());
+        self
+    }
+
     /// Set the maximum tokens to generate.
     #[must_use]
     pub fn with_max_tokens(mut self, max_tokens: u32) -> Self {
@@ -286,6 +333,7 @@ mod tests {
             supports_structured_output: true,
             model_name: "llama3.1:8b".to_string(),
             cost_tier: 0,
+            available_models: vec!["llama3.1:8b".to_string()],
         };
 
         let json = serde_json::to_string(&caps).expect("serialize capabilities");
@@ -295,5 +343,25 @@ mod tests {
         assert_eq!(deserialized.max_context_tokens, 8192);
         assert!(deserialized.is_local);
         assert_eq!(deserialized.cost_tier, 0);
+        assert_eq!(deserialized.available_models, ["llama3.1:8b"]);
+    }
+
+    #[test]
+    fn test_has_model() {
+        let mut caps = ProviderCapabilities {
+            max_context_tokens: 8192,
+            is_local: true,
+            supports_vision: false,
+            supports_tool_use: false,
+            supports_structured_output: false,
+            model_name: "llama3.1:8b".to_string(),
+            cost_tier: 0,
+            available_models: Vec::new(),
+        };
+        assert!(caps.has_model("mistral:7b"));
+
+        caps.available_models = vec!["llama3.1:8b".to_string()];
+        assert!(caps.has_model("llama3.1:8b"));
+        assert!(!caps.has_model("mistral:7b"));
     }
 }
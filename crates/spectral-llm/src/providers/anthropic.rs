@@ -1,5 +1,6 @@
 //! Anthropic Claude provider implementation.
 
+use super::common;
 use crate::error::{LlmError, Result};
 use crate::provider::{
     CompletionRequest, CompletionResponse, CompletionStream, LlmProvider, ProviderCapabilities,
@@ -64,7 +65,7 @@ impl AnthropicProvider {
             .collect();
 
         AnthropicRequest {
-            model: self.model.clone(),
+            model: request.model.clone().unwrap_or_else(|| self.model.clone()),
             messages,
             max_tokens: request.max_tokens.unwrap_or(4096),
             temperature: request.temperature,
@@ -120,17 +121,8 @@ impl LlmProvider for AnthropicProvider {
             .await?;
 
         // Check for HTTP errors
-        let status = response.status();
-        if !status.is_success() {
-            let error_text = response
-                .text()
-                .await
-                .unwrap_or_else(|_| "Unknown error".to_string());
-            return Err(LlmError::ApiError {
-                provider: "anthropic".to_string(),
-                status: status.as_u16(),
-                message: error_text,
-            });
+        if !response.status().is_success() {
+            return Err(common::error_from_response("anthropic", response).await);
         }
 
         // Parse the JSON response
@@ -177,6 +169,7 @@ impl LlmProvider for AnthropicProvider {
             supports_structured_output: true,
             model_name: self.model.clone(),
             cost_tier: 2, // Cloud API with cost
+            available_models: Vec::new(),
         }
     }
 
@@ -278,4 +271,14 @@ mod tests {
         assert_eq!(api_request.messages.len(), 1);
         assert_eq!(api_request.messages[0].content, "Hello");
     }
+
+    #[test]
+    fn test_request_model_overrides_provider_model() {
+        let provider = AnthropicProvider::new("test-key").expect("create provider");
+        let request = CompletionRequest::new("Hello").with_model("claude-3-5-haiku-20241022");
+
+        let api_request = provider.to_api_request(&request);
+
+        assert_eq!(api_request.model, "claude-3-5-haiku-20241022");
+    }
 }
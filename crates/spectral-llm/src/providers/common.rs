@@ -2,7 +2,7 @@
 
 use crate::error::{LlmError, Result};
 use crate::provider::{CompletionRequest, CompletionResponse, CompletionStream, Role, Usage};
-use reqwest::Client;
+use reqwest::{Client, Response, StatusCode};
 use serde::{Deserialize, Serialize};
 use std::time::Duration;
 
@@ -20,6 +20,50 @@ pub fn build_http_client(timeout_secs: Option<u64>) -> Result<Client> {
         .map_err(|e| LlmError::Internal(format!("failed to create HTTP client: {e}")))
 }
 
+/// Convert an unsuccessful HTTP response into an error.
+///
+/// HTTP 429 becomes `LlmError::RateLimited` carrying the `Retry-After`
+/// delay; any other status becomes `LlmError::ApiError` with the body text.
+pub async fn error_from_response(provider: &str, response: Response) -> LlmError {
+    let status = response.status();
+    if status == StatusCode::TOO_MANY_REQUESTS {
+        let retry_after = response
+            .headers()
+            .get(reqwest::header::RETRY_AFTER)
+            .and_then(|value| value.to_str().ok())
+            .and_then(parse_retry_after);
+        return LlmError::RateLimited {
+            provider: provider.to_string(),
+            retry_after,
+        };
+    }
+
+    let message = response
+        .text()
+        .await
+        .unwrap_or_else(|_| "Unknown error".to_string());
+    LlmError::ApiError {
+        provider: provider.to_string(),
+        status: status.as_u16(),
+        message,
+    }
+}
+
+/// Parse a `Retry-After` header value.
+///
+/// Accepts delay-seconds (`"120"`) or an HTTP date. Dates in the past give a
+/// zero delay.
+#[must_use]
+pub fn parse_retry_after(value: &str) -> Option<Duration> {
+    let value = value.trim();
+    if let Ok(seconds) = value.parse::<u64>() {
+        return Some(Duration::from_secs(seconds));
+    }
+    let date = chrono::DateTime::parse_from_rfc2822(value).ok()?;
+    let delay = date.with_timezone(&chrono::Utc) - chrono::Utc::now();
+    Some(delay.to_std().unwrap_or(Duration::ZERO))
+}
+
 /// Convert internal Role enum to standard role string.
 ///
 /// Most providers use "system", "user", "assistant" roles.
@@ -128,6 +172,21 @@ mod tests {
         assert!(client.is_ok());
     }
 
+    #[test]
+    fn test_parse_retry_after() {
+        assert_eq!(parse_retry_after("120"), Some(Duration::from_secs(120)));
+        assert_eq!(parse_retry_after(" 0 "), Some(Duration::ZERO));
+        assert_eq!(
+            parse_retry_after("Wed, 21 Oct 2015 07:28:00 GMT"),
+            Some(Duration::ZERO)
+        );
+        assert_eq!(parse_retry_after("soon"), None);
+
+        let future = (chrono::Utc::now() + chrono::Duration::seconds(90)).to_rfc2822();
+        let delay = parse_retry_after(&future).expect("future date parses");
+        assert!(delay > Duration::from_secs(80) && delay <= Duration::from_secs(90));
+    }
+
     #[test]
     fn test_convert_role_standard() {
         assert_eq!(convert_role_standard(Role::System), "system");
@@ -1,6 +1,6 @@
 //! Google Gemini API provider implementation.
 
-use super::common::{build_http_client, convert_role_gemini, streaming_not_implemented};
+use super::common::{self, build_http_client, convert_role_gemini, streaming_not_implemented};
 use crate::error::{LlmError, Result};
 use crate::provider::{
     CompletionRequest, CompletionResponse, CompletionStream, LlmProvider, ProviderCapabilities,
@@ -129,7 +129,9 @@ impl LlmProvider for GeminiProvider {
             .client
             .post(format!(
                 "{}/models/{}:generateContent?key={}",
-                self.base_url, self.model, self.api_key
+                self.base_url,
+                request.model.as_deref().unwrap_or(&self.model),
+                self.api_key
             ))
             .header("Content-Type", "application/json")
             .json(&api_request)
@@ -137,17 +139,8 @@ impl LlmProvider for GeminiProvider {
             .await?;
 
         // Check for HTTP errors
-        let status = response.status();
-        if !status.is_success() {
-            let error_text = response
-                .text()
-                .await
-                .unwrap_or_else(|_| "Unknown error".to_string());
-            return Err(LlmError::ApiError {
-                provider: "gemini".to_string(),
-                status: status.as_u16(),
-                message: error_text,
-            });
+        if !response.status().is_success() {
+            return Err(common::error_from_response("gemini", response).await);
         }
 
         // Parse the JSON response
@@ -175,6 +168,7 @@ impl LlmProvider for GeminiProvider {
             supports_structured_output: true,
             model_name: self.model.clone(),
             cost_tier: 2, // Cloud API with cost
+            available_models: Vec::new(),
         }
     }
 
@@ -314,6 +308,8 @@ mod tests {
             temperature: None,
             system_prompt: None,
             stop_sequences: Vec::new(),
+            task_type: None,
+            model: None,
             extra: serde_json::Value::Null,
         };
 
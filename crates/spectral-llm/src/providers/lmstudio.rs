@@ -1,7 +1,7 @@
 //! LM Studio local provider implementation.
 
 use super::common::{
-    build_http_client, convert_role_standard, streaming_not_implemented, StandardMessage,
+    self, build_http_client, convert_role_standard, streaming_not_implemented, StandardMessage,
     StandardUsage,
 };
 use crate::error::{LlmError, Result};
@@ -58,7 +58,7 @@ impl LmStudioProvider {
             .send()
             .await;
 
-        Ok(result.is_ok() && result.map(|r| r.status().is_success()).unwrap_or(false))
+        Ok(result.is_ok_and(|r| r.status().is_success()))
     }
 
     /// Convert internal request to LM Studio (OpenAI-compatible) API format.
@@ -83,7 +83,7 @@ impl LmStudioProvider {
         }
 
         LmStudioRequest {
-            model: self.model.clone(),
+            model: request.model.clone().unwrap_or_else(|| self.model.clone()),
             messages,
             max_tokens: request.max_tokens,
             temperature: request.temperature,
@@ -142,17 +142,8 @@ impl LlmProvider for LmStudioProvider {
             .await?;
 
         // Check for HTTP errors
-        let status = response.status();
-        if !status.is_success() {
-            let error_text = response
-                .text()
-                .await
-                .unwrap_or_else(|_| "Unknown error".to_string());
-            return Err(LlmError::ApiError {
-                provider: "lmstudio".to_string(),
-                status: status.as_u16(),
-                message: error_text,
-            });
+        if !response.status().is_success() {
+            return Err(common::error_from_response("lmstudio", response).await);
         }
 
         // Parse the JSON response
@@ -180,6 +171,7 @@ impl LlmProvider for LmStudioProvider {
             supports_structured_output: false,
             model_name: self.model.clone(),
             cost_tier: 0, // Local is free
+            available_models: Vec::new(),
         }
     }
 
@@ -1,5 +1,6 @@
 //! Ollama local LLM provider implementation.
 
+use super::common;
 use crate::error::{LlmError, Result};
 use crate::provider::{
     CompletionRequest, CompletionResponse, CompletionStream, LlmProvider, ProviderCapabilities,
@@ -10,6 +11,12 @@ use futures::stream;
 use reqwest::Client;
 use serde::{Deserialize, Serialize};
 
+/// Address of a local Ollama install.
+const DEFAULT_URL: &str = "http://localhost:11434";
+
+/// Model used when none is configured, and preferred when installed.
+const DEFAULT_MODEL: &str = "llama3.1:8b";
+
 /// Ollama local LLM provider.
 ///
 /// Connects to a local Ollama instance for privacy-preserving LLM operations.
@@ -18,6 +25,8 @@ pub struct OllamaProvider {
     model: String,
     client: Client,
     base_url: String,
+    /// Models installed in Ollama, empty until discovered
+    available_models: Vec<String>,
 }
 
 impl OllamaProvider {
@@ -26,7 +35,66 @@ impl OllamaProvider {
     /// # Errors
     /// Returns error if the HTTP client cannot be created.
     pub fn new() -> Result<Self> {
-        Self::with_model("llama3.1:8b")
+        Self::with_model(DEFAULT_MODEL)
+    }
+
+    /// Create a provider for the local Ollama install using its installed
+    /// models.
+    ///
+    /// # Errors
+    /// Returns error if Ollama cannot be reached or has no models installed.
+    pub async fn discover() -> Result<Self> {
+        Self::discover_at(DEFAULT_URL).await
+    }
+
+    /// Create a provider for the Ollama instance at `base_url` using its
+    /// installed models.
+    ///
+    /// The default model is used when installed, otherwise the first model
+    /// Ollama lists.
+    ///
+    /// # Errors
+    /// Returns error if Ollama cannot be reached or has no models installed.
+    pub async fn discover_at(base_url: impl Into<String>) -> Result<Self> {
+        let mut provider = Self::with_url(base_url, DEFAULT_MODEL)?;
+        let models = provider.list_models().await?;
+        if !models.iter().any(|m| m == DEFAULT_MODEL) {
+            provider.model.clone_from(&models[0]);
+        }
+        provider.available_models = models;
+        Ok(provider)
+    }
+
+    /// Query `/api/tags` for the names of the installed models.
+    ///
+    /// # Errors
+    /// Returns error if the request fails or no models are installed.
+    pub async fn list_models(&self) -> Result<Vec<String>> {
+        let response = self
+            .client
+            .get(format!("{}/api/tags", self.base_url))
+            .send()
+            .await?;
+
+        if !response.status().is_success() {
+            return Err(common::error_from_response("ollama", response).await);
+        }
+
+        let tags: OllamaTags = response.json().await.map_err(|e| LlmError::ParseError {
+            provider: "ollama".to_string(),
+            message: format!("Failed to parse model list: {e}"),
+        })?;
+
+        if tags.models.is_empty() {
+            return Err(LlmError::ProviderError {
+                provider: "ollama".to_string(),
+                message: format!(
+                    "no models installed, pull one with `ollama pull {DEFAULT_MODEL}`"
+                ),
+            });
+        }
+
+        Ok(tags.models.into_iter().map(|m| m.name).collect())
     }
 
     /// Create a new Ollama provider with a specific model.
@@ -34,7 +102,7 @@ impl OllamaProvider {
     /// # Errors
     /// Returns error if the HTTP client cannot be created.
     pub fn with_model(model: impl Into<String>) -> Result<Self> {
-        Self::with_url("http://localhost:11434", model)
+        Self::with_url(DEFAULT_URL, model)
     }
 
     /// Create a new Ollama provider with custom URL and model.
@@ -51,6 +119,7 @@ impl OllamaProvider {
             model: model.into(),
             client,
             base_url: base_url.into(),
+            available_models: Vec::new(),
         })
     }
 
@@ -78,7 +147,7 @@ impl OllamaProvider {
         let prompt = prompt_parts.join("\n\n");
 
         OllamaRequest {
-            model: self.model.clone(),
+            model: request.model.clone().unwrap_or_else(|| self.model.clone()),
             prompt,
             stream: false,
             options: OllamaOptions {
@@ -136,17 +205,8 @@ impl LlmProvider for OllamaProvider {
             .await?;
 
         // Check for HTTP errors
-        let status = response.status();
-        if !status.is_success() {
-            let error_text = response
-                .text()
-                .await
-                .unwrap_or_else(|_| "Unknown error".to_string());
-            return Err(LlmError::ApiError {
-                provider: "ollama".to_string(),
-                status: status.as_u16(),
-                message: error_text,
-            });
+        if !response.status().is_success() {
+            return Err(common::error_from_response("ollama", response).await);
         }
 
         // Parse the JSON response
@@ -195,6 +255,7 @@ impl LlmProvider for OllamaProvider {
             supports_structured_output: false,
             model_name: self.model.clone(),
             cost_tier: 0, // Local is free
+            available_models: self.available_models.clone(),
         }
     }
 
@@ -223,6 +284,17 @@ struct OllamaOptions {
     stop: Option<Vec<String>>,
 }
 
+#[derive(Debug, Deserialize)]
+struct OllamaTags {
+    #[serde(default)]
+    models: Vec<OllamaModel>,
+}
+
+#[derive(Debug, Deserialize)]
+struct OllamaModel {
+    name: String,
+}
+
 #[derive(Debug, Deserialize)]
 #[allow(dead_code)]
 struct OllamaResponse {
@@ -234,6 +306,73 @@ struct OllamaResponse {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::TcpListener;
+
+    /// Serve one `/api/tags` request with `body`, returning the base URL.
+    async fn serve_tags(body: &'static str) -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").await.expect("bind");
+        let base_url = format!("http://{}", listener.local_addr().expect("addr"));
+
+        tokio::spawn(async move {
+            let (mut socket, _) = listener.accept().await.expect("accept");
+            let mut buf = [0u8; 4096];
+            let n = socket.read(&mut buf).await.expect("read");
+            let request = String::from_utf8_lossy(&buf[..n]);
+            assert!(request.starts_with("GET /api/tags "), "{request}");
+
+            let response = format!(
+                "HTTP/1.1 200 OK\r\ncontent-type: application/json\r\ncontent-length: {}\r\nconnection: close\r\n\r\n{body}",
+                body.len()
+            );
+            socket.write_all(response.as_bytes()).await.expect("write");
+        });
+
+        base_url
+    }
+
+    #[tokio::test]
+    async fn test_discover_uses_installed_models() {
+        let base_url = serve_tags(
+            r#"{"models":[{"name":"mistral:7b","size":4109865159},{"name":"qwen2.5:3b"}]}"#,
+        )
+        .await;
+
+        let provider = OllamaProvider::discover_at(base_url)
+            .await
+            .expect("discover models");
+
+        assert_eq!(provider.model, "mistral:7b");
+        let caps = provider.capabilities();
+        assert_eq!(caps.model_name, "mistral:7b");
+        assert_eq!(caps.available_models, ["mistral:7b", "qwen2.5:3b"]);
+        assert!(!caps.has_model("llama3.1:8b"));
+    }
+
+    #[tokio::test]
+    async fn test_discover_prefers_default_model() {
+        let base_url =
+            serve_tags(r#"{"models":[{"name":"mistral:7b"},{"name":"llama3.1:8b"}]}"#).await;
+
+        let provider = OllamaProvider::discover_at(base_url)
+            .await
+            .expect("discover models");
+
+        assert_eq!(provider.model, "llama3.1:8b");
+    }
+
+    #[tokio::test]
+    async fn test_discover_without_models_fails() {
+        let base_url = serve_tags(r#"{"models":[]}"#).await;
+
+        let err = OllamaProvider::discover_at(base_url)
+            .await
+            .err()
+            .expect("discovery should fail");
+
+        assert!(matches!(err, LlmError::ProviderError { .. }));
+        assert!(err.to_string().contains("no models installed"), "{err}");
+    }
 
     #[test]
     fn test_provider_creation() {
@@ -266,6 +405,7 @@ mod tests {
         assert!(!caps.supports_vision);
         assert!(!caps.supports_tool_use);
         assert_eq!(caps.cost_tier, 0); // Local is free
+        assert!(caps.available_models.is_empty()); // Not discovered
     }
 
     #[test]
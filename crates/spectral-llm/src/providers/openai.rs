@@ -1,9 +1,10 @@
 //! `OpenAI` API provider implementation.
 
 use super::common::{
-    build_http_client, convert_role_standard, streaming_not_implemented, StandardMessage,
+    self, build_http_client, convert_role_standard, streaming_not_implemented, StandardMessage,
     StandardUsage,
 };
+use crate::context;
 use crate::error::{LlmError, Result};
 use crate::provider::{
     CompletionRequest, CompletionResponse, CompletionStream, LlmProvider, ProviderCapabilities,
@@ -66,7 +67,7 @@ impl OpenAiProvider {
         }
 
         OpenAiRequest {
-            model: self.model.clone(),
+            model: request.model.clone().unwrap_or_else(|| self.model.clone()),
             messages,
             max_tokens: request.max_tokens,
             temperature: request.temperature,
@@ -120,17 +121,8 @@ impl LlmProvider for OpenAiProvider {
             .await?;
 
         // Check for HTTP errors
-        let status = response.status();
-        if !status.is_success() {
-            let error_text = response
-                .text()
-                .await
-                .unwrap_or_else(|_| "Unknown error".to_string());
-            return Err(LlmError::ApiError {
-                provider: "openai".to_string(),
-                status: status.as_u16(),
-                message: error_text,
-            });
+        if !response.status().is_success() {
+            return Err(common::error_from_response("openai", response).await);
         }
 
         // Parse the JSON response
@@ -158,12 +150,17 @@ impl LlmProvider for OpenAiProvider {
             supports_structured_output: true,
             model_name: self.model.clone(),
             cost_tier: 3, // Cloud API with moderate cost
+            available_models: Vec::new(),
         }
     }
 
     fn provider_id(&self) -> &'static str {
         "openai"
     }
+
+    fn count_tokens(&self, text: &str) -> usize {
+        context::count_bpe_tokens(text)
+    }
 }
 
 // OpenAI API types
@@ -206,6 +203,16 @@ mod tests {
         assert_eq!(provider.model, "gpt-4o");
     }
 
+    #[test]
+    fn test_count_tokens_uses_bpe_counter() {
+        let provider = OpenAiProvider::new("test-key").expect("create provider");
+        assert_eq!(provider.count_tokens("Hello, world!"), 4);
+        assert_eq!(
+            provider.count_tokens("Hello, world!"),
+            context::count_bpe_tokens("Hello, world!")
+        );
+    }
+
     #[test]
     fn test_provider_with_custom_model() {
         let provider =
@@ -1,21 +1,87 @@
 //! LLM routing with privacy-aware provider selection.
 
+use crate::context::{self, TruncationStrategy};
 use crate::error::{LlmError, Result};
 use crate::pii_filter::{FilterStrategy, PiiFilter};
+use crate::prompt::PromptTemplateStore;
 use crate::provider::{
     CompletionRequest, CompletionResponse, CompletionStream, LlmProvider, ProviderCapabilities,
 };
+use crate::sanitize::sanitize_llm_output;
 use serde::{Deserialize, Serialize};
+use spectral_core::metrics::{self, Counter};
+use std::collections::HashMap;
 use std::sync::Arc;
+use std::time::Duration;
 
 /// Router that selects appropriate LLM providers based on routing preferences.
 ///
 /// The router applies PII filtering before sending requests to cloud providers
 /// and can fallback between providers based on availability and preferences.
+/// Requests larger than the selected provider's context window are truncated
+/// to fit (see [`TruncationStrategy`]).
+///
+/// Requests can be sent to a particular provider and model based on their
+/// [`TaskType`], for example a cheap model for classification and a stronger
+/// one for extraction (see [`Self::with_task_model`]).
+///
+/// Prompts can be kept as named templates in a [`PromptTemplateStore`] and
+/// sent with [`Self::complete_template`].
 pub struct LlmRouter {
     providers: Vec<Arc<dyn LlmProvider>>,
     pii_filter: PiiFilter,
     preference: RoutingPreference,
+    rate_limit_retry: Option<RateLimitRetry>,
+    truncation_strategy: TruncationStrategy,
+    task_models: HashMap<TaskType, ModelRoute>,
+    default_model: Option<ModelRoute>,
+    templates: Option<Arc<dyn PromptTemplateStore>>,
+}
+
+/// A provider and the model to request from it.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ModelRoute {
+    /// ID of a registered provider (see [`LlmProvider::provider_id`])
+    pub provider_id: String,
+    /// Model name passed to the provider
+    pub model: String,
+}
+
+impl ModelRoute {
+    /// Create a route to `model` on the provider `provider_id`.
+    #[must_use]
+    pub fn new(provider_id: impl Into<String>, model: impl Into<String>) -> Self {
+        Self {
+            provider_id: provider_id.into(),
+            model: model.into(),
+        }
+    }
+}
+
+/// How the router waits out rate limiting (HTTP 429) from a provider.
+///
+/// The router sleeps for the provider's `Retry-After` delay, or
+/// `default_delay` when none was given, and tries again. It gives up and
+/// returns `LlmError::RateLimited` once `max_retries` is used up or when the
+/// next wait would take the total past `max_total_wait`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RateLimitRetry {
+    /// Maximum number of retries after the first attempt
+    pub max_retries: u32,
+    /// Upper bound on the total time spent waiting across retries
+    pub max_total_wait: Duration,
+    /// Delay used when the provider does not send `Retry-After`
+    pub default_delay: Duration,
+}
+
+impl Default for RateLimitRetry {
+    fn default() -> Self {
+        Self {
+            max_retries: 3,
+            max_total_wait: Duration::from_secs(60),
+            default_delay: Duration::from_secs(5),
+        }
+    }
 }
 
 impl LlmRouter {
@@ -26,9 +92,54 @@ impl LlmRouter {
             providers: Vec::new(),
             pii_filter: PiiFilter::with_strategy(FilterStrategy::Tokenize),
             preference,
+            rate_limit_retry: None,
+            truncation_strategy: TruncationStrategy::default(),
+            task_models: HashMap::new(),
+            default_model: None,
+            templates: None,
         }
     }
 
+    /// Send requests for tasks without their own model to `model` on the
+    /// provider `provider_id`.
+    #[must_use]
+    pub fn with_default_model(
+        mut self,
+        provider_id: impl Into<String>,
+        model: impl Into<String>,
+    ) -> Self {
+        self.default_model = Some(ModelRoute::new(provider_id, model));
+        self
+    }
+
+    /// Send requests for `task` to `model` on the provider `provider_id`.
+    #[must_use]
+    pub fn with_task_model(
+        mut self,
+        task: TaskType,
+        provider_id: impl Into<String>,
+        model: impl Into<String>,
+    ) -> Self {
+        self.task_models
+            .insert(task, ModelRoute::new(provider_id, model));
+        self
+    }
+
+    /// Look up prompt templates for [`Self::complete_template`] in `store`.
+    #[must_use]
+    pub fn with_template_store(mut self, store: Arc<dyn PromptTemplateStore>) -> Self {
+        self.templates = Some(store);
+        self
+    }
+
+    /// Get the provider and model configured for a task, falling back to the
+    /// default model.
+    #[must_use]
+    pub fn model_route(&self, task: Option<TaskType>) -> Option<&ModelRoute> {
+        task.and_then(|task| self.task_models.get(&task))
+            .or(self.default_model.as_ref())
+    }
+
     /// Add a provider to the router.
     pub fn add_provider(&mut self, provider: Arc<dyn LlmProvider>) {
         self.providers.push(provider);
@@ -50,12 +161,27 @@ impl LlmRouter {
         self.preference = preference;
     }
 
+    /// Wait and retry when a provider is rate limited, or `None` (the
+    /// default) to return `LlmError::RateLimited` straight away.
+    pub fn set_rate_limit_retry(&mut self, retry: Option<RateLimitRetry>) {
+        self.rate_limit_retry = retry;
+    }
+
+    /// Set how requests that exceed a provider's context window are shortened.
+    pub fn set_truncation_strategy(&mut self, strategy: TruncationStrategy) {
+        self.truncation_strategy = strategy;
+    }
+
     /// Complete a request by routing to an appropriate provider.
     ///
+    /// Control sequences are stripped from the response content with
+    /// [`sanitize_llm_output`].
+    ///
     /// # Errors
     /// Returns error if no suitable provider is available or if the request fails.
     pub async fn complete(&self, request: CompletionRequest) -> Result<CompletionResponse> {
-        let provider = self.select_provider(&request)?;
+        let (provider, request) = self.route_request(request)?;
+        let request = self.fit_to_provider(provider, request)?;
 
         // Apply PII filtering for cloud providers
         let (filtered_request, token_map) = if provider.capabilities().is_local {
@@ -72,41 +198,187 @@ impl LlmRouter {
             (filtered_req, filter_result.token_map)
         };
 
-        // Send request to provider
-        let mut response = provider.complete(filtered_request).await?;
+        let mut response = self.send_with_retry(provider, filtered_request).await?;
 
         // Detokenize response if needed
         if let Some(token_map) = token_map {
             response.content = self.pii_filter.detokenize(&response.content, &token_map);
         }
 
+        response.content = sanitize_llm_output(&response.content).content;
         Ok(response)
     }
 
+    /// Render the stored prompt template `name` with `vars` and complete it
+    /// like [`Self::complete`].
+    ///
+    /// # Errors
+    /// Returns `LlmError::TemplateNotFound` if no store is set or it has no
+    /// such template, `LlmError::TemplateVariablesMissing` if `vars` lacks a
+    /// variable the template uses, or any error [`Self::complete`] can
+    /// return.
+    pub async fn complete_template(
+        &self,
+        name: &str,
+        vars: &HashMap<String, String>,
+    ) -> Result<CompletionResponse> {
+        let not_found = || LlmError::TemplateNotFound {
+            name: name.to_string(),
+        };
+        let store = self.templates.as_ref().ok_or_else(not_found)?;
+        let template = store.load_template(name).await?.ok_or_else(not_found)?;
+        self.complete(template.render(vars)?).await
+    }
+
+    /// Send a request, waiting out rate limiting according to the retry policy.
+    async fn send_with_retry(
+        &self,
+        provider: &Arc<dyn LlmProvider>,
+        request: CompletionRequest,
+    ) -> Result<CompletionResponse> {
+        let mut retries = 0;
+        let mut waited = Duration::ZERO;
+        loop {
+            metrics::global().increment(Counter::LlmCalls);
+            let error = match provider.complete(request.clone()).await {
+                Ok(response) => return Ok(response),
+                Err(error) => error,
+            };
+            metrics::global().increment(Counter::LlmErrors);
+
+            let (LlmError::RateLimited { retry_after, .. }, Some(policy)) =
+                (&error, self.rate_limit_retry)
+            else {
+                return Err(error);
+            };
+            let delay = retry_after.unwrap_or(policy.default_delay);
+            if retries >= policy.max_retries || waited + delay > policy.max_total_wait {
+                return Err(error);
+            }
+
+            retries += 1;
+            waited += delay;
+            tokio::time::sleep(delay).await;
+        }
+    }
+
     /// Stream a completion by routing to an appropriate provider.
     ///
+    /// Streams from cloud providers end with `LlmError::OutputPiiLeak` if
+    /// the output starts to contain high-risk PII (see
+    /// [`PiiFilter::guard_stream`]).
+    ///
     /// # Errors
     /// Returns error if no suitable provider is available.
     pub async fn stream(&self, request: CompletionRequest) -> Result<CompletionStream> {
-        let provider = self.select_provider(&request)?;
+        let (provider, request) = self.route_request(request)?;
+        let request = self.fit_to_provider(provider, request)?;
+
+        if provider.capabilities().is_local {
+            return provider.stream(request).await;
+        }
 
         // For streaming, we apply PII filtering but don't tokenize (more complex)
-        let filtered_request = if provider.capabilities().is_local {
-            request
-        } else {
-            let filter_result = self.pii_filter.filter(&Self::extract_text(&request))?;
-            let mut filtered_req = request.clone();
-            if let Some(last_message) = filtered_req.messages.last_mut() {
-                last_message.content = filter_result.filtered_text;
+        let filter_result = self.pii_filter.filter(&Self::extract_text(&request))?;
+        let mut filtered_request = request;
+        if let Some(last_message) = filtered_request.messages.last_mut() {
+            last_message.content = filter_result.filtered_text;
+        }
+
+        let stream = provider.stream(filtered_request).await?;
+        Ok(self.pii_filter.guard_stream(stream))
+    }
+
+    /// Count the tokens `request` would take up with the provider it would
+    /// be routed to, including the tokens reserved for the response.
+    ///
+    /// For budgeting before a request is sent, e.g. to estimate its cost or
+    /// warn that it will be truncated.
+    ///
+    /// # Errors
+    /// Returns error if no suitable provider is available.
+    pub fn count_request_tokens(&self, request: &CompletionRequest) -> Result<usize> {
+        let (provider, request) = self.route_request(request.clone())?;
+        Ok(context::count_request_tokens(&request, |text| {
+            provider.count_tokens(text)
+        }))
+    }
+
+    /// Truncate the request to the provider's context window if needed.
+    fn fit_to_provider(
+        &self,
+        provider: &Arc<dyn LlmProvider>,
+        mut request: CompletionRequest,
+    ) -> Result<CompletionRequest> {
+        let max_context_tokens = provider.capabilities().max_context_tokens;
+        if let Some(removed) = context::fit_to_context_with(
+            &mut request,
+            max_context_tokens,
+            self.truncation_strategy,
+            |text| provider.count_tokens(text),
+        )? {
+            tracing::warn!(
+                provider = provider.provider_id(),
+                max_context_tokens,
+                removed_tokens = removed,
+                strategy = ?self.truncation_strategy,
+                "Request exceeded context window and was truncated"
+            );
+        }
+        Ok(request)
+    }
+
+    /// Pick the provider for a request, applying the model configured for its
+    /// task.
+    ///
+    /// The configured provider is used only if it is registered, has the
+    /// model installed and the routing preference allows it for the task;
+    /// otherwise the request goes to [`Self::select_provider`] with the
+    /// provider's own model. A model set on the request itself is kept.
+    fn route_request(
+        &self,
+        mut request: CompletionRequest,
+    ) -> Result<(&Arc<dyn LlmProvider>, CompletionRequest)> {
+        if let Some(route) = self.model_route(request.task_type) {
+            let task = request.task_type.unwrap_or(TaskType::General);
+            let model = request.model.as_deref().unwrap_or(&route.model);
+            let provider = self.providers.iter().find(|p| {
+                let caps = p.capabilities();
+                p.provider_id() == route.provider_id
+                    && (caps.is_local || self.preference.allows_cloud(task))
+                    && caps.has_model(model)
+            });
+            if let Some(provider) = provider {
+                request.model.get_or_insert_with(|| route.model.clone());
+                return Ok((provider, request));
             }
-            filtered_req
-        };
+            tracing::debug!(
+                provider = %route.provider_id,
+                task = ?task,
+                "Configured model route is unavailable, using routing preference"
+            );
+        }
 
-        provider.stream(filtered_request).await
+        let provider = self.select_provider(&request)?;
+        Ok((provider, request))
+    }
+
+    /// Providers that can serve the model set on the request, or all
+    /// providers when it sets none.
+    fn candidates(
+        &self,
+        request: &CompletionRequest,
+    ) -> impl Iterator<Item = &Arc<dyn LlmProvider>> {
+        let model = request.model.clone();
+        self.providers.iter().filter(move |p| {
+            model
+                .as_deref()
+                .map_or(true, |model| p.capabilities().has_model(model))
+        })
     }
 
     /// Select the best provider for the given request.
-    fn select_provider(&self, _request: &CompletionRequest) -> Result<&Arc<dyn LlmProvider>> {
+    fn select_provider(&self, request: &CompletionRequest) -> Result<&Arc<dyn LlmProvider>> {
         if self.providers.is_empty() {
             return Err(LlmError::NoProviderAvailable);
         }
@@ -114,23 +386,21 @@ impl LlmRouter {
         match &self.preference {
             RoutingPreference::LocalOnly => {
                 // Find first local provider
-                self.providers
-                    .iter()
+                self.candidates(request)
                     .find(|p| p.capabilities().is_local)
                     .ok_or(LlmError::NoProviderAvailable)
             }
-            RoutingPreference::PreferLocal {
-                cloud_allowed_tasks,
-            } => {
+            RoutingPreference::PreferLocal { .. } => {
                 // Try local first
-                if let Some(provider) = self.providers.iter().find(|p| p.capabilities().is_local) {
+                if let Some(provider) = self.candidates(request).find(|p| p.capabilities().is_local)
+                {
                     return Ok(provider);
                 }
 
                 // Fallback to cloud if task is allowed
-                if cloud_allowed_tasks.contains(&TaskType::General) {
-                    self.providers
-                        .iter()
+                let task = request.task_type.unwrap_or(TaskType::General);
+                if self.preference.allows_cloud(task) {
+                    self.candidates(request)
                         .find(|p| !p.capabilities().is_local)
                         .ok_or(LlmError::NoProviderAvailable)
                 } else {
@@ -139,8 +409,7 @@ impl LlmRouter {
             }
             RoutingPreference::BestAvailable => {
                 // Select based on capabilities and cost
-                self.providers
-                    .iter()
+                self.candidates(request)
                     .max_by_key(|p| {
                         let caps = p.capabilities();
                         // Prefer local, then highest context window, then lowest cost
@@ -203,6 +472,20 @@ pub enum RoutingPreference {
     BestAvailable,
 }
 
+impl RoutingPreference {
+    /// Whether requests for `task` may be sent to a cloud provider.
+    #[must_use]
+    pub fn allows_cloud(&self, task: TaskType) -> bool {
+        match self {
+            Self::LocalOnly => false,
+            Self::PreferLocal {
+                cloud_allowed_tasks,
+            } => cloud_allowed_tasks.contains(&task),
+            Self::BestAvailable => true,
+        }
+    }
+}
+
 impl Default for RoutingPreference {
     fn default() -> Self {
         Self::PreferLocal {
@@ -212,7 +495,7 @@ impl Default for RoutingPreference {
 }
 
 /// Types of tasks for routing decisions.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
 pub enum TaskType {
     /// General purpose tasks (non-sensitive)
@@ -229,19 +512,37 @@ pub enum TaskType {
 
     /// Natural language queries
     NaturalLanguage,
+
+    /// Sorting text into a fixed set of labels, such as whether a page is a
+    /// listing
+    Classification,
+
+    /// Pulling structured data out of text, such as fields from a listing
+    Extraction,
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::pii_filter::PiiType;
+    use crate::provider::StreamChunk;
     use async_trait::async_trait;
-    use futures::stream;
+    use futures::{stream, StreamExt};
+    use std::collections::VecDeque;
+    use std::sync::atomic::{AtomicU32, Ordering};
+    use std::sync::Mutex;
 
     // Mock provider for testing
     struct MockProvider {
         id: String,
         is_local: bool,
         max_tokens: usize,
+        content: Option<String>,
+        /// 429 responses (with their `Retry-After`) returned before succeeding
+        rate_limits: Mutex<VecDeque<Option<Duration>>>,
+        calls: AtomicU32,
+        last_request: Mutex<Option<CompletionRequest>>,
+        models: Vec<String>,
     }
 
     impl MockProvider {
@@ -250,15 +551,67 @@ mod tests {
                 id: id.to_string(),
                 is_local,
                 max_tokens: 4096,
+                content: None,
+                rate_limits: Mutex::new(VecDeque::new()),
+                calls: AtomicU32::new(0),
+                last_request: Mutex::new(None),
+                models: Vec::new(),
             }
         }
+
+        fn with_models(mut self, models: &[&str]) -> Self {
+            self.models = models.iter().map(ToString::to_string).collect();
+            self
+        }
+
+        fn with_max_tokens(mut self, max_tokens: usize) -> Self {
+            self.max_tokens = max_tokens;
+            self
+        }
+
+        fn last_request(&self) -> CompletionRequest {
+            self.last_request
+                .lock()
+                .expect("last request")
+                .clone()
+                .expect("provider was called")
+        }
+
+        fn with_rate_limits(self, retry_afters: &[Option<Duration>]) -> Self {
+            self.rate_limits
+                .lock()
+                .expect("rate limit queue")
+                .extend(retry_afters);
+            self
+        }
+
+        fn with_content(mut self, content: &str) -> Self {
+            self.content = Some(content.to_string());
+            self
+        }
     }
 
     #[async_trait]
     impl LlmProvider for MockProvider {
-        async fn complete(&self, _request: CompletionRequest) -> Result<CompletionResponse> {
+        async fn complete(&self, request: CompletionRequest) -> Result<CompletionResponse> {
+            self.calls.fetch_add(1, Ordering::SeqCst);
+            *self.last_request.lock().expect("last request") = Some(request);
+            if let Some(retry_after) = self
+                .rate_limits
+                .lock()
+                .expect("rate limit queue")
+                .pop_front()
+            {
+                return Err(LlmError::RateLimited {
+                    provider: self.id.clone(),
+                    retry_after,
+                });
+            }
             Ok(CompletionResponse {
-                content: format!("Response from {}", self.id),
+                content: self
+                    .content
+                    .clone()
+                    .unwrap_or_else(|| format!("Response from {}", self.id)),
                 model: self.id.clone(),
                 stop_reason: Some("end_turn".to_string()),
                 usage: None,
@@ -268,7 +621,21 @@ mod tests {
         }
 
         async fn stream(&self, _request: CompletionRequest) -> Result<CompletionStream> {
-            Ok(Box::pin(stream::empty()))
+            // Stream the configured content a word at a time
+            let chunks: Vec<Result<StreamChunk>> = self
+                .content
+                .as_deref()
+                .unwrap_or_default()
+                .split_inclusive(' ')
+                .map(|delta| {
+                    Ok(StreamChunk {
+                        delta: delta.to_string(),
+                        is_final: false,
+                        stop_reason: None,
+                    })
+                })
+                .collect();
+            Ok(Box::pin(stream::iter(chunks)))
         }
 
         fn capabilities(&self) -> ProviderCapabilities {
@@ -280,6 +647,7 @@ mod tests {
                 supports_structured_output: false,
                 model_name: self.id.clone(),
                 cost_tier: u8::from(!self.is_local),
+                available_models: self.models.clone(),
             }
         }
 
@@ -338,6 +706,327 @@ mod tests {
         assert!(matches!(result, Err(LlmError::NoProviderAvailable)));
     }
 
+    #[tokio::test]
+    async fn test_complete_strips_control_sequences() {
+        let mut router = LlmRouter::new(RoutingPreference::LocalOnly);
+        router.add_provider(Arc::new(
+            MockProvider::new("ollama", true).with_content("\x1b[2Jclick\u{200B} #opt-out"),
+        ));
+
+        let response = router
+            .complete(CompletionRequest::new("Hello"))
+            .await
+            .expect("complete request");
+        assert_eq!(response.content, "click #opt-out");
+    }
+
+    #[tokio::test]
+    async fn test_complete_template_sends_rendered_prompt() {
+        use crate::prompt::PromptTemplate;
+
+        let provider = Arc::new(MockProvider::new("ollama", true));
+        let templates: HashMap<String, PromptTemplate> = HashMap::from([(
+            "summarize".to_string(),
+            PromptTemplate::new("summarize", "Summarize the listing on {{broker}}"),
+        )]);
+        let mut router =
+            LlmRouter::new(RoutingPreference::LocalOnly).with_template_store(Arc::new(templates));
+        router.add_provider(provider.clone());
+
+        let vars = HashMap::from([("broker".to_string(), "Spokeo".to_string())]);
+        router
+            .complete_template("summarize", &vars)
+            .await
+            .expect("complete template");
+        assert_eq!(
+            provider.last_request().messages[0].content,
+            "Summarize the listing on Spokeo"
+        );
+
+        assert!(matches!(
+            router.complete_template("missing", &vars).await,
+            Err(LlmError::TemplateNotFound { .. })
+        ));
+        assert!(matches!(
+            router.complete_template("summarize", &HashMap::new()).await,
+            Err(LlmError::TemplateVariablesMissing { .. })
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_cloud_stream_stops_on_ssn_in_output() {
+        let leaky = "The SSN on file is 123-45-6789 according to the listing";
+
+        let mut router = LlmRouter::new(RoutingPreference::BestAvailable);
+        router.add_provider(Arc::new(
+            MockProvider::new("anthropic", false).with_content(leaky),
+        ));
+        let items: Vec<Result<StreamChunk>> = router
+            .stream(CompletionRequest::new("Summarize this listing"))
+            .await
+            .expect("start stream")
+            .collect()
+            .await;
+        assert!(matches!(
+            items.last(),
+            Some(Err(LlmError::OutputPiiLeak {
+                pii_type: PiiType::Ssn
+            }))
+        ));
+        let streamed: String = items
+            .iter()
+            .filter_map(|item| item.as_ref().ok())
+            .map(|chunk| chunk.delta.as_str())
+            .collect();
+        assert!(!streamed.contains("6789"));
+
+        // Local output never leaves the machine and is not guarded
+        let mut router = LlmRouter::new(RoutingPreference::LocalOnly);
+        router.add_provider(Arc::new(
+            MockProvider::new("ollama", true).with_content(leaky),
+        ));
+        let items: Vec<Result<StreamChunk>> = router
+            .stream(CompletionRequest::new("Summarize this listing"))
+            .await
+            .expect("start stream")
+            .collect()
+            .await;
+        assert!(items.iter().all(Result::is_ok));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_rate_limited_request_is_retried_after_delay() {
+        let provider = Arc::new(
+            MockProvider::new("anthropic", false).with_rate_limits(&[Some(Duration::from_secs(2))]),
+        );
+        let mut router = LlmRouter::new(RoutingPreference::BestAvailable);
+        router.add_provider(provider.clone());
+        router.set_rate_limit_retry(Some(RateLimitRetry::default()));
+
+        let started = tokio::time::Instant::now();
+        let response = router
+            .complete(CompletionRequest::new("Hello"))
+            .await
+            .expect("succeeds after retry");
+
+        assert_eq!(response.content, "Response from anthropic");
+        assert_eq!(provider.calls.load(Ordering::SeqCst), 2);
+        assert!(started.elapsed() >= Duration::from_secs(2));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_rate_limit_retry_uses_default_delay_and_max_retries() {
+        let provider = Arc::new(MockProvider::new("anthropic", false).with_rate_limits(&[None; 3]));
+        let mut router = LlmRouter::new(RoutingPreference::BestAvailable);
+        router.add_provider(provider.clone());
+        router.set_rate_limit_retry(Some(RateLimitRetry {
+            max_retries: 2,
+            max_total_wait: Duration::from_secs(60),
+            default_delay: Duration::from_secs(5),
+        }));
+
+        let started = tokio::time::Instant::now();
+        let result = router.complete(CompletionRequest::new("Hello")).await;
+
+        assert!(matches!(result, Err(LlmError::RateLimited { .. })));
+        assert_eq!(provider.calls.load(Ordering::SeqCst), 3);
+        assert_eq!(started.elapsed(), Duration::from_secs(10));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_rate_limit_retry_respects_wait_budget() {
+        let provider = Arc::new(
+            MockProvider::new("anthropic", false)
+                .with_rate_limits(&[Some(Duration::from_secs(120))]),
+        );
+        let mut router = LlmRouter::new(RoutingPreference::BestAvailable);
+        router.add_provider(provider.clone());
+        router.set_rate_limit_retry(Some(RateLimitRetry::default()));
+
+        let started = tokio::time::Instant::now();
+        let result = router.complete(CompletionRequest::new("Hello")).await;
+
+        match result {
+            Err(LlmError::RateLimited { retry_after, .. }) => {
+                assert_eq!(retry_after, Some(Duration::from_secs(120)));
+            }
+            other => panic!("expected RateLimited, got {other:?}"),
+        }
+        assert_eq!(provider.calls.load(Ordering::SeqCst), 1);
+        assert_eq!(started.elapsed(), Duration::ZERO);
+    }
+
+    #[tokio::test]
+    async fn test_rate_limit_not_retried_without_policy() {
+        let provider = Arc::new(
+            MockProvider::new("anthropic", false).with_rate_limits(&[Some(Duration::ZERO)]),
+        );
+        let mut router = LlmRouter::new(RoutingPreference::BestAvailable);
+        router.add_provider(provider.clone());
+
+        let result = router.complete(CompletionRequest::new("Hello")).await;
+        assert!(matches!(result, Err(LlmError::RateLimited { .. })));
+        assert_eq!(provider.calls.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn test_oversized_request_is_truncated_to_context_window() {
+        let provider = Arc::new(MockProvider::new("ollama", true).with_max_tokens(200));
+        let mut router = LlmRouter::new(RoutingPreference::LocalOnly);
+        router.add_provider(provider.clone());
+        router.set_truncation_strategy(TruncationStrategy::Tail);
+
+        let page = format!("{}{}", "<nav>".repeat(1000), "<table>results</table>");
+        router
+            .complete(CompletionRequest::new(page).with_max_tokens(50))
+            .await
+            .expect("complete request");
+
+        let sent = provider.last_request();
+        assert!(context::estimate_request_tokens(&sent) <= 200);
+        assert!(sent.messages[0]
+            .content
+            .starts_with(context::TRUNCATION_MARKER));
+        assert!(sent.messages[0].content.ends_with("<table>results</table>"));
+    }
+
+    #[tokio::test]
+    async fn test_request_too_large_for_any_truncation_is_rejected() {
+        let provider = Arc::new(MockProvider::new("ollama", true).with_max_tokens(100));
+        let mut router = LlmRouter::new(RoutingPreference::LocalOnly);
+        router.add_provider(provider.clone());
+
+        let request = CompletionRequest::new("page")
+            .with_system_prompt("s".repeat(1000))
+            .with_max_tokens(50);
+        let result = router.complete(request).await;
+
+        assert!(matches!(result, Err(LlmError::InvalidRequest(_))));
+        assert_eq!(provider.calls.load(Ordering::SeqCst), 0);
+    }
+
+    /// Router with a cheap local model for classification and a stronger
+    /// cloud model for extraction.
+    fn task_routed_router(
+        local: Arc<MockProvider>,
+        cloud: Arc<MockProvider>,
+        preference: RoutingPreference,
+    ) -> LlmRouter {
+        let mut router = LlmRouter::new(preference)
+            .with_default_model("ollama", "llama3.1:8b")
+            .with_task_model(TaskType::Classification, "ollama", "llama3.2:1b")
+            .with_task_model(TaskType::Extraction, "anthropic", "claude-3-5-sonnet");
+        router.add_provider(local);
+        router.add_provider(cloud);
+        router
+    }
+
+    #[tokio::test]
+    async fn test_task_type_selects_configured_model() {
+        let local = Arc::new(MockProvider::new("ollama", true));
+        let cloud = Arc::new(MockProvider::new("anthropic", false));
+        let router = task_routed_router(
+            local.clone(),
+            cloud.clone(),
+            RoutingPreference::BestAvailable,
+        );
+
+        router
+            .complete(
+                CompletionRequest::new("Is this a listing?")
+                    .with_task_type(TaskType::Classification),
+            )
+            .await
+            .expect("complete classification");
+        assert_eq!(local.last_request().model.as_deref(), Some("llama3.2:1b"));
+
+        let response = router
+            .complete(
+                CompletionRequest::new("Extract the address").with_task_type(TaskType::Extraction),
+            )
+            .await
+            .expect("complete extraction");
+        assert_eq!(response.model, "anthropic");
+        assert_eq!(
+            cloud.last_request().model.as_deref(),
+            Some("claude-3-5-sonnet")
+        );
+
+        // Unmapped tasks use the default model
+        router
+            .complete(CompletionRequest::new("Hello").with_task_type(TaskType::General))
+            .await
+            .expect("complete general");
+        assert_eq!(local.last_request().model.as_deref(), Some("llama3.1:8b"));
+        assert_eq!(cloud.calls.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn test_task_model_respects_routing_preference() {
+        let local = Arc::new(MockProvider::new("ollama", true));
+        let cloud = Arc::new(MockProvider::new("anthropic", false));
+        let router = task_routed_router(local.clone(), cloud.clone(), RoutingPreference::LocalOnly);
+
+        let response = router
+            .complete(
+                CompletionRequest::new("Extract the address").with_task_type(TaskType::Extraction),
+            )
+            .await
+            .expect("complete extraction");
+
+        // The cloud route is not allowed, so the local provider's own model is used
+        assert_eq!(response.model, "ollama");
+        assert_eq!(local.last_request().model, None);
+        assert_eq!(cloud.calls.load(Ordering::SeqCst), 0);
+    }
+
+    #[tokio::test]
+    async fn test_request_model_is_kept() {
+        let local = Arc::new(MockProvider::new("ollama", true));
+        let cloud = Arc::new(MockProvider::new("anthropic", false));
+        let router = task_routed_router(local.clone(), cloud, RoutingPreference::LocalOnly);
+
+        router
+            .complete(
+                CompletionRequest::new("Is this a listing?")
+                    .with_task_type(TaskType::Classification)
+                    .with_model("qwen2.5:3b"),
+            )
+            .await
+            .expect("complete classification");
+        assert_eq!(local.last_request().model.as_deref(), Some("qwen2.5:3b"));
+    }
+
+    #[tokio::test]
+    async fn test_route_skips_provider_without_model() {
+        let local = Arc::new(MockProvider::new("ollama", true).with_models(&["llama3.1:8b"]));
+        let cloud = Arc::new(MockProvider::new("anthropic", false));
+        let router = task_routed_router(
+            local.clone(),
+            cloud.clone(),
+            RoutingPreference::BestAvailable,
+        );
+
+        // llama3.2:1b is configured for classification but not installed
+        let response = router
+            .complete(
+                CompletionRequest::new("Is this a listing?")
+                    .with_task_type(TaskType::Classification),
+            )
+            .await
+            .expect("complete classification");
+        assert_eq!(response.model, "ollama");
+        assert_eq!(local.last_request().model, None);
+
+        // An explicitly requested model that is not installed goes elsewhere
+        let response = router
+            .complete(CompletionRequest::new("Hello").with_model("qwen2.5:3b"))
+            .await
+            .expect("complete with model");
+        assert_eq!(response.model, "anthropic");
+        assert_eq!(cloud.last_request().model.as_deref(), Some("qwen2.5:3b"));
+    }
+
     #[test]
     fn test_all_capabilities() {
         let mut router = LlmRouter::new(RoutingPreference::BestAvailable);
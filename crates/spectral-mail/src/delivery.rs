@@ -0,0 +1,165 @@
+//! Delivering removal emails: SMTP when configured, `mailto:` otherwise.
+//!
+//! Sending directly over SMTP is preferred because the user does not have to
+//! do anything and we control the Message-ID, so replies and bounces can be
+//! matched back to the attempt. When no SMTP server is configured, or it
+//! keeps failing, the email is handed to the user's mail client instead.
+
+use crate::sender::{self, SmtpConfig};
+use crate::templates::EmailTemplate;
+use spectral_db::email_removals::{self, CreateEmailRemoval, EmailRemoval};
+use spectral_vault::Vault;
+use sqlx::SqlitePool;
+use std::future::Future;
+use std::time::Duration;
+
+/// Settings key holding the SMTP configuration, without the password.
+pub const SMTP_SETTINGS_KEY: &str = "mail.smtp";
+
+/// Name of the vault secret holding the SMTP password.
+pub const SMTP_PASSWORD_SECRET: &str = "mail.smtp.password";
+
+/// SMTP send attempts before falling back to `mailto:`.
+const SMTP_ATTEMPTS: u32 = 2;
+
+/// Delay between SMTP send attempts.
+const SMTP_RETRY_DELAY: Duration = Duration::from_secs(2);
+
+/// How a removal email reached the broker.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Delivery {
+    /// Sent over SMTP with this Message-ID (without angle brackets)
+    Smtp { message_id: String },
+    /// Handed to the user's mail client through this `mailto:` URL
+    Mailto { url: String },
+}
+
+impl Delivery {
+    /// Method name recorded in `email_removals.method`.
+    pub fn method(&self) -> &'static str {
+        match self {
+            Self::Smtp { .. } => "smtp",
+            Self::Mailto { .. } => "mailto",
+        }
+    }
+
+    /// Message-ID of an SMTP send.
+    pub fn message_id(&self) -> Option<&str> {
+        match self {
+            Self::Smtp { message_id } => Some(message_id),
+            Self::Mailto { .. } => None,
+        }
+    }
+}
+
+/// Load the SMTP configuration from settings, if one has been saved, with
+/// the password from the vault.
+///
+/// Configurations saved before the password moved to the vault still hold
+/// it in plain text; it is moved to the vault on first load.
+pub async fn load_smtp_config(vault: &Vault) -> Result<Option<SmtpConfig>, String> {
+    let pool = vault.database().map_err(|e| e.to_string())?.pool();
+    let Some(value) = spectral_db::settings::get_setting(pool, SMTP_SETTINGS_KEY)
+        .await
+        .map_err(|e| format!("Failed to read SMTP settings: {e}"))?
+    else {
+        return Ok(None);
+    };
+    let mut config: SmtpConfig =
+        serde_json::from_value(value).map_err(|e| format!("Invalid SMTP settings: {e}"))?;
+
+    let password = vault
+        .load_secret(SMTP_PASSWORD_SECRET)
+        .await
+        .map_err(|e| format!("Failed to read SMTP password: {e}"))?;
+    match password {
+        Some(password) => config.password = password.to_string(),
+        None if !config.password.is_empty() => store_smtp_config(vault, &config).await?,
+        None => {}
+    }
+    Ok(Some(config))
+}
+
+/// Save the SMTP configuration to settings and its password to the vault.
+///
+/// Rejects configurations that fail [`SmtpConfig::validate`].
+pub async fn save_smtp_config(vault: &Vault, config: &SmtpConfig) -> Result<(), String> {
+    config.validate()?;
+    store_smtp_config(vault, config).await
+}
+
+async fn store_smtp_config(vault: &Vault, config: &SmtpConfig) -> Result<(), String> {
+    // The password is stored first, so the settings never lose it
+    vault
+        .store_secret(SMTP_PASSWORD_SECRET, &config.password)
+        .await
+        .map_err(|e| format!("Failed to save SMTP password: {e}"))?;
+
+    let pool = vault.database().map_err(|e| e.to_string())?.pool();
+    let value = serde_json::to_value(config).map_err(|e| format!("Invalid SMTP settings: {e}"))?;
+    spectral_db::settings::set_setting(pool, SMTP_SETTINGS_KEY, &value)
+        .await
+        .map_err(|e| format!("Failed to save SMTP settings: {e}"))
+}
+
+/// Deliver a removal email from `from`, preferring SMTP when `config` is set.
+pub async fn deliver(email: &EmailTemplate, from: &str, config: Option<&SmtpConfig>) -> Delivery {
+    match config {
+        Some(config) => deliver_with(email, || sender::send_smtp(email, from, config)).await,
+        None => Delivery::Mailto {
+            url: sender::to_mailto_url(email),
+        },
+    }
+}
+
+/// Try `send_smtp` up to [`SMTP_ATTEMPTS`] times, falling back to `mailto:`
+/// if every attempt fails.
+///
+/// `send_smtp` returns the Message-ID of the sent email.
+pub async fn deliver_with<F, Fut>(email: &EmailTemplate, send_smtp: F) -> Delivery
+where
+    F: Fn() -> Fut,
+    Fut: Future<Output = Result<String, String>>,
+{
+    for attempt in 1..=SMTP_ATTEMPTS {
+        match send_smtp().await {
+            Ok(message_id) => return Delivery::Smtp { message_id },
+            Err(_) => {
+                // The server's error text can echo the recipient, so it is not logged
+                tracing::warn!(attempt, "SMTP send failed");
+                if attempt < SMTP_ATTEMPTS {
+                    tokio::time::sleep(SMTP_RETRY_DELAY).await;
+                }
+            }
+        }
+    }
+
+    tracing::info!("Falling back to mailto after SMTP failures");
+    Delivery::Mailto {
+        url: sender::to_mailto_url(email),
+    }
+}
+
+/// Log a delivered removal email to `email_removals`.
+pub async fn record_delivery(
+    pool: &SqlitePool,
+    attempt_id: &str,
+    broker_id: &str,
+    email: &EmailTemplate,
+    delivery: &Delivery,
+) -> Result<EmailRemoval, String> {
+    email_removals::insert_email_removal(
+        pool,
+        CreateEmailRemoval {
+            attempt_id: attempt_id.to_string(),
+            broker_id: broker_id.to_string(),
+            method: delivery.method().to_string(),
+            recipient: email.to.clone(),
+            subject: email.subject.clone(),
+            body_hash: sender::body_hash(&email.body),
+            message_id: delivery.message_id().map(str::to_string),
+        },
+    )
+    .await
+    .map_err(|e| format!("Failed to log email removal: {e}"))
+}
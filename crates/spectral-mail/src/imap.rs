@@ -1,5 +1,6 @@
 //! IMAP poller — monitors inbox for broker verification emails.
 
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
 /// Maximum age of verification emails to search for
@@ -12,11 +13,14 @@ pub fn matches_broker_sender(sender: &str, broker_emails: &[String]) -> bool {
 }
 
 /// Configuration for the IMAP poller
-#[derive(Clone)]
+#[derive(Clone, Serialize, Deserialize)]
 pub struct ImapConfig {
     pub host: String,
     pub port: u16,
     pub username: String,
+    /// Kept in the vault, never in the settings JSON; see
+    /// [`crate::inbox::load_imap_config`].
+    #[serde(default, skip_serializing)]
     pub password: String,
 }
 
@@ -31,14 +35,35 @@ impl std::fmt::Debug for ImapConfig {
     }
 }
 
+/// Delivery failure report (bounce) for an email we sent
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Bounce {
+    /// Address the undelivered message was sent to
+    pub recipient: String,
+    /// Message-ID of the undelivered message, when the report includes it
+    pub message_id: Option<String>,
+}
+
+/// Inbound message that threads under an earlier message
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Reply {
+    /// Sender address, lower-cased
+    pub from: String,
+    /// Message-IDs from `In-Reply-To` and `References`, without angle brackets
+    pub references: Vec<String>,
+}
+
 /// Result of a single polling pass
 #[derive(Debug, Default)]
 pub struct PollResult {
     pub verified: HashMap<String, String>,
+    pub replies: Vec<Reply>,
+    pub bounces: Vec<Bounce>,
     pub errors: Vec<String>,
 }
 
-/// Poll IMAP inbox for broker verification emails (SYNCHRONOUS - wrap in spawn_blocking if needed)
+/// Poll IMAP inbox for broker verification emails and bounce reports
+/// (SYNCHRONOUS - wrap in spawn_blocking if needed)
 pub fn poll_for_verifications(
     config: &ImapConfig,
     broker_email_to_attempt: &HashMap<String, String>,
@@ -85,6 +110,8 @@ pub fn poll_for_verifications(
     let uid_list: Vec<String> = uids.iter().map(|u| u.to_string()).collect();
     let fetch_query = uid_list.join(",");
 
+    // Headers first: verifications, replies and bounces are all recognised
+    // by them, and only bounce reports need their bodies read
     let messages = match session.fetch(&fetch_query, "RFC822.HEADER") {
         Ok(m) => m,
         Err(e) => {
@@ -96,6 +123,41 @@ pub fn poll_for_verifications(
     };
 
     result.verified = extract_verifications_from_messages(messages.iter(), broker_email_to_attempt);
+    result.replies = messages
+        .iter()
+        .filter_map(|msg| parse_reply(&String::from_utf8_lossy(msg.header()?)))
+        .collect();
+
+    let bounce_ids: Vec<String> = messages
+        .iter()
+        .filter(|msg| {
+            msg.header()
+                .is_some_and(|header| is_bounce(&String::from_utf8_lossy(header)))
+        })
+        .map(|msg| msg.message.to_string())
+        .collect();
+    if !bounce_ids.is_empty() {
+        // PEEK so that reading bounce bodies does not mark messages as seen
+        match session.fetch(bounce_ids.join(","), "(RFC822.HEADER BODY.PEEK[TEXT])") {
+            Ok(reports) => {
+                result.bounces = reports
+                    .iter()
+                    .filter_map(|msg| {
+                        let headers = String::from_utf8_lossy(msg.header()?);
+                        let body = String::from_utf8_lossy(msg.text().unwrap_or_default());
+                        parse_bounce(&headers, &body)
+                    })
+                    .collect();
+            }
+            Err(e) => {
+                tracing::warn!("IMAP fetch error: {}", e);
+                result.errors.push(format!("IMAP fetch error: {e}"));
+            }
+        }
+    }
+    if !result.bounces.is_empty() {
+        tracing::info!("Found {} bounce reports", result.bounces.len());
+    }
 
     let _ = session.logout();
     result
@@ -183,6 +245,122 @@ fn extract_from_header(headers: &str) -> Option<String> {
     None
 }
 
+/// Extract the sender and referenced Message-IDs of a reply.
+///
+/// Returns `None` for messages without `In-Reply-To` or `References`
+/// headers, which cannot be matched to a sent message by ID.
+pub fn parse_reply(headers: &str) -> Option<Reply> {
+    let headers = unfold_headers(headers);
+    let mut references = Vec::new();
+    for name in ["in-reply-to", "references"] {
+        let Some(value) = header_value(&headers, name) else {
+            continue;
+        };
+        for id in value.split_whitespace() {
+            let id = id.trim_start_matches('<').trim_end_matches('>');
+            if !id.is_empty() && !references.iter().any(|seen| seen == id) {
+                references.push(id.to_string());
+            }
+        }
+    }
+
+    if references.is_empty() {
+        return None;
+    }
+    Some(Reply {
+        from: extract_from_header(&headers)?,
+        references,
+    })
+}
+
+/// Recognise a bounce report and extract the failed recipient.
+///
+/// A message is treated as a bounce when it comes from a mailer daemon or
+/// postmaster, or is a `multipart/report` delivery status notification. The
+/// recipient is taken from the DSN `Final-Recipient`/`Original-Recipient`
+/// fields, falling back to the `X-Failed-Recipients` header. The Message-ID
+/// comes from `Original-Message-ID` or the returned copy of the original
+/// headers.
+pub fn parse_bounce(headers: &str, body: &str) -> Option<Bounce> {
+    if !is_bounce(headers) {
+        return None;
+    }
+    let headers = unfold_headers(headers);
+
+    let body = unfold_headers(body);
+    let recipient = header_value(&body, "final-recipient")
+        .or_else(|| header_value(&body, "original-recipient"))
+        .map(|value| match value.split_once(';') {
+            // "rfc822; optout@broker.com"
+            Some((_, address)) => address.trim().to_string(),
+            None => value,
+        })
+        .or_else(|| {
+            header_value(&headers, "x-failed-recipients")
+                .and_then(|value| value.split(',').next().map(|a| a.trim().to_string()))
+        })
+        .map(|address| {
+            address
+                .trim_start_matches('<')
+                .trim_end_matches('>')
+                .to_lowercase()
+        })
+        .filter(|address| !address.is_empty())?;
+
+    let message_id = header_value(&body, "original-message-id")
+        .or_else(|| header_value(&body, "message-id"))
+        .filter(|id| !id.is_empty());
+
+    Some(Bounce {
+        recipient,
+        message_id,
+    })
+}
+
+/// Whether a message's headers mark it as a bounce report: sent by a mailer
+/// daemon or postmaster, or a `multipart/report` delivery status
+/// notification.
+pub fn is_bounce(headers: &str) -> bool {
+    let headers = unfold_headers(headers);
+    let from = extract_from_header(&headers).unwrap_or_default();
+    let local_part = from.split('@').next().unwrap_or_default();
+    let content_type = header_value(&headers, "content-type")
+        .unwrap_or_default()
+        .to_ascii_lowercase();
+
+    local_part == "mailer-daemon"
+        || local_part == "postmaster"
+        || (content_type.starts_with("multipart/report")
+            && content_type.contains("delivery-status"))
+}
+
+/// Join folded header continuation lines onto the line they continue.
+fn unfold_headers(text: &str) -> String {
+    let mut unfolded = String::with_capacity(text.len());
+    for line in text.lines() {
+        if line.starts_with([' ', '\t']) && !unfolded.is_empty() {
+            unfolded.push(' ');
+            unfolded.push_str(line.trim());
+        } else {
+            if !unfolded.is_empty() {
+                unfolded.push('\n');
+            }
+            unfolded.push_str(line);
+        }
+    }
+    unfolded
+}
+
+/// Value of the first `name:` line, matched case-insensitively.
+fn header_value(text: &str, name: &str) -> Option<String> {
+    text.lines().find_map(|line| {
+        let (key, value) = line.split_once(':')?;
+        key.trim()
+            .eq_ignore_ascii_case(name)
+            .then(|| value.trim().to_string())
+    })
+}
+
 fn format_imap_date(unix_secs: u64) -> String {
     use chrono::{DateTime, Utc};
     let dt = DateTime::<Utc>::from_timestamp(unix_secs as i64, 0).unwrap_or_else(Utc::now);
@@ -204,4 +382,107 @@ mod tests {
         let broker_emails = vec!["optout@spokeo.com".to_string()];
         assert!(!matches_broker_sender("noreply@random.com", &broker_emails));
     }
+
+    const DSN_HEADERS: &str = "From: Mail Delivery System <MAILER-DAEMON@mx.example.com>\r
+To: user@example.com\r
+Subject: Undelivered Mail Returned to Sender\r
+Content-Type: multipart/report;\r
+\treport-type=delivery-status; boundary=\"b1\"\r
+";
+
+    const DSN_BODY: &str = "--b1\r
+Content-Type: text/plain\r
+\r
+I'm sorry to have to inform you that your message could not be delivered.\r
+--b1\r
+Content-Type: message/delivery-status\r
+\r
+Reporting-MTA: dns; mx.example.com\r
+\r
+Final-Recipient: rfc822; OptOut@Broker.com\r
+Action: failed\r
+Status: 5.1.1\r
+--b1\r
+Content-Type: text/rfc822-headers\r
+\r
+From: user@example.com\r
+To: optout@broker.com\r
+Message-ID: <abc123@example.com>\r
+Subject: Opt-Out Request\r
+--b1--\r
+";
+
+    #[test]
+    fn test_parse_bounce_dsn() {
+        let bounce = parse_bounce(DSN_HEADERS, DSN_BODY).expect("bounce detected");
+        assert_eq!(bounce.recipient, "optout@broker.com");
+        assert_eq!(bounce.message_id.as_deref(), Some("<abc123@example.com>"));
+    }
+
+    #[test]
+    fn test_parse_bounce_report_without_daemon_sender() {
+        let headers = "From: postmaster-notices@example.com\r
+Content-Type: multipart/report; report-type=delivery-status; boundary=b1\r
+";
+        let bounce = parse_bounce(headers, DSN_BODY).expect("bounce detected");
+        assert_eq!(bounce.recipient, "optout@broker.com");
+    }
+
+    #[test]
+    fn test_parse_bounce_falls_back_to_failed_recipients_header() {
+        let headers = "From: postmaster@mx.example.com\r
+X-Failed-Recipients: optout@broker.com\r
+Content-Type: text/plain\r
+";
+        let bounce = parse_bounce(headers, "This message was created automatically.")
+            .expect("bounce detected");
+        assert_eq!(bounce.recipient, "optout@broker.com");
+        assert_eq!(bounce.message_id, None);
+    }
+
+    #[test]
+    fn test_parse_reply_collects_references() {
+        let headers = "From: Spokeo Privacy <Privacy@Spokeo.com>\r
+In-Reply-To: <sent-1@example.com>\r
+References: <root@spokeo.com>\r
+ <sent-1@example.com>\r
+Subject: Re: Opt-Out Request\r
+";
+        let reply = parse_reply(headers).expect("reply detected");
+        assert_eq!(reply.from, "privacy@spokeo.com");
+        assert_eq!(
+            reply.references,
+            vec![
+                "sent-1@example.com".to_string(),
+                "root@spokeo.com".to_string()
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_reply_ignores_unthreaded_mail() {
+        let headers = "From: optout@spokeo.com\r
+Subject: Please confirm your request\r
+";
+        assert_eq!(parse_reply(headers), None);
+    }
+
+    #[test]
+    fn test_is_bounce_from_headers_alone() {
+        assert!(is_bounce(DSN_HEADERS));
+        assert!(!is_bounce(
+            "From: Spokeo <optout@spokeo.com>\r\nContent-Type: text/plain\r\n"
+        ));
+    }
+
+    #[test]
+    fn test_parse_bounce_ignores_regular_mail() {
+        let headers = "From: Spokeo <optout@spokeo.com>\r
+Content-Type: text/plain\r
+";
+        assert_eq!(
+            parse_bounce(headers, "Final-Recipient: rfc822; x@y.com"),
+            None
+        );
+    }
 }
@@ -0,0 +1,97 @@
+//! Checking the inbox for bounce reports on removal emails.
+//!
+//! A removal email that bounced never reached the broker, so its attempt is
+//! failed and can be retried rather than waiting for an answer that will
+//! not come. The IMAP configuration is stored like the SMTP one: the server
+//! settings in the settings table and the password in the vault.
+
+use crate::imap::{self, Bounce, ImapConfig};
+use spectral_db::email_removals;
+use spectral_vault::Vault;
+use sqlx::SqlitePool;
+
+/// Settings key holding the IMAP configuration, without the password.
+pub const IMAP_SETTINGS_KEY: &str = "mail.imap";
+
+/// Name of the vault secret holding the IMAP password.
+pub const IMAP_PASSWORD_SECRET: &str = "mail.imap.password";
+
+/// Load the IMAP configuration from settings, if one has been saved, with
+/// the password from the vault.
+pub async fn load_imap_config(vault: &Vault) -> Result<Option<ImapConfig>, String> {
+    let pool = vault.database().map_err(|e| e.to_string())?.pool();
+    let Some(value) = spectral_db::settings::get_setting(pool, IMAP_SETTINGS_KEY)
+        .await
+        .map_err(|e| format!("Failed to read IMAP settings: {e}"))?
+    else {
+        return Ok(None);
+    };
+    let mut config: ImapConfig =
+        serde_json::from_value(value).map_err(|e| format!("Invalid IMAP settings: {e}"))?;
+
+    if let Some(password) = vault
+        .load_secret(IMAP_PASSWORD_SECRET)
+        .await
+        .map_err(|e| format!("Failed to read IMAP password: {e}"))?
+    {
+        config.password = password.to_string();
+    }
+    Ok(Some(config))
+}
+
+/// Save the IMAP configuration to settings and its password to the vault.
+pub async fn save_imap_config(vault: &Vault, config: &ImapConfig) -> Result<(), String> {
+    // The password is stored first, so the settings never lose it
+    vault
+        .store_secret(IMAP_PASSWORD_SECRET, &config.password)
+        .await
+        .map_err(|e| format!("Failed to save IMAP password: {e}"))?;
+
+    let pool = vault.database().map_err(|e| e.to_string())?.pool();
+    let value = serde_json::to_value(config).map_err(|e| format!("Invalid IMAP settings: {e}"))?;
+    spectral_db::settings::set_setting(pool, IMAP_SETTINGS_KEY, &value)
+        .await
+        .map_err(|e| format!("Failed to save IMAP settings: {e}"))
+}
+
+/// Poll the inbox once and fail the removal attempts whose emails bounced.
+///
+/// Only mail about addresses with a submitted removal email is of interest,
+/// so the inbox is not opened when there are none. Returns the IDs of the
+/// attempts marked failed.
+pub async fn check_inbox(pool: &SqlitePool, config: &ImapConfig) -> Result<Vec<String>, String> {
+    let recipients = email_removals::submitted_recipients(pool)
+        .await
+        .map_err(|e| format!("Failed to load sent removal emails: {e}"))?;
+    if recipients.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let config = config.clone();
+    let result =
+        tokio::task::spawn_blocking(move || imap::poll_for_verifications(&config, &recipients))
+            .await
+            .map_err(|e| format!("IMAP poll task failed: {e}"))?;
+
+    // Errors after the inbox was read still leave bounces worth applying
+    match result.errors.first() {
+        Some(error) if result.bounces.is_empty() => Err(error.clone()),
+        _ => apply_bounces(pool, &result.bounces).await,
+    }
+}
+
+/// Fail the submitted removal attempt behind each bounce.
+///
+/// Returns the IDs of the attempts marked failed; bounces that match no
+/// submitted attempt are skipped.
+pub async fn apply_bounces(pool: &SqlitePool, bounces: &[Bounce]) -> Result<Vec<String>, String> {
+    let mut failed = Vec::new();
+    for bounce in bounces {
+        let attempt_id =
+            email_removals::mark_bounced(pool, &bounce.recipient, bounce.message_id.as_deref())
+                .await
+                .map_err(|e| format!("Failed to record bounce: {e}"))?;
+        failed.extend(attempt_id);
+    }
+    Ok(failed)
+}
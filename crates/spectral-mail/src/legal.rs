@@ -0,0 +1,408 @@
+//! Formal deletion requests for removals that keep failing.
+//!
+//! Once a removal attempt has failed [`ESCALATION_THRESHOLD`] times, the
+//! user's remaining recourse is a deletion request citing the privacy law
+//! that covers them, or a formal request citing none where no template
+//! covers their residence. [`escalate_failed_removal`] drafts one from the profile
+//! fields, addressed to the broker's legal contact, and queues it in
+//! `legal_requests` for the user to review. Drafts are never sent
+//! automatically. Only the draft's metadata is stored; [`render_draft`]
+//! renders its text from the profile when the user reviews it.
+
+use crate::templates::{validate_template, EmailTemplate};
+use chrono::NaiveDate;
+use spectral_broker::BrokerDefinition;
+use spectral_core::country::{normalize_country, DEFAULT_COUNTRY};
+use spectral_db::legal_requests::{self, CreateLegalRequest, LegalRequest};
+use spectral_db::removal_attempts;
+use sqlx::SqlitePool;
+use std::collections::HashMap;
+
+/// Failed submissions of one removal attempt before a legal request is drafted.
+pub const ESCALATION_THRESHOLD: u32 = 3;
+
+/// Countries covered by the GDPR: the EU, the wider EEA, and the UK, whose
+/// UK GDPR grants the same right to erasure.
+const GDPR_COUNTRIES: &[&str] = &[
+    "AT", "BE", "BG", "HR", "CY", "CZ", "DK", "EE", "FI", "FR", "DE", "GR", "HU", "IE", "IT", "LV",
+    "LT", "LU", "MT", "NL", "PL", "PT", "RO", "SK", "SI", "ES", "SE", "IS", "LI", "NO", "GB",
+];
+
+const CCPA_SUBJECT: &str = "CCPA Request to Delete Personal Information — {{full_name}}";
+
+const CCPA_BODY: &str = "To the {{broker_name}} privacy team,
+
+Under the California Consumer Privacy Act (Cal. Civ. Code § 1798.105), I request that you delete all personal information you have collected about me. Under § 1798.120, I also direct you not to sell or share my personal information.
+
+Please use these details to locate my records:
+Name: {{full_name}}
+Email: {{email}}
+Listing: {{listing_url}}
+
+My earlier requests through your opt-out process did not succeed. Please confirm receipt within 10 business days and complete this request within 45 days, as the CCPA requires, and tell me if you need anything further to verify my identity.
+
+Sincerely,
+{{full_name}}
+{{date}}
+";
+
+const GENERIC_SUBJECT: &str = "Request to Delete Personal Information — {{full_name}}";
+
+const GENERIC_BODY: &str = "To the {{broker_name}} privacy team,
+
+I request that you delete all personal information you hold about me and stop selling, sharing or publishing it.
+
+Please use these details to locate my records:
+Name: {{full_name}}
+Email: {{email}}
+Listing: {{listing_url}}
+
+My earlier requests through your opt-out process did not succeed. Please confirm in writing once my information has been deleted, and tell me if you need anything further to verify my identity.
+
+Sincerely,
+{{full_name}}
+{{date}}
+";
+
+const GDPR_SUBJECT: &str = "Request for Erasure under Article 17 GDPR — {{full_name}}";
+
+const GDPR_BODY: &str = "To the {{broker_name}} data protection officer,
+
+Under Article 17 of the General Data Protection Regulation, I request the erasure without undue delay of all personal data you hold about me. Under Article 21, I also object to any further processing of my personal data, including for direct marketing.
+
+Please use these details to locate my records:
+Name: {{full_name}}
+Email: {{email}}
+Listing: {{listing_url}}
+
+My earlier requests through your opt-out process did not succeed. Article 12(3) requires you to act on this request within one month of receiving it. Please confirm the erasure in writing, including to any recipients you disclosed my data to (Article 19).
+
+Sincerely,
+{{full_name}}
+{{date}}
+";
+
+/// Privacy law a legal request cites, chosen from where the user lives.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Jurisdiction {
+    /// California Consumer Privacy Act, for California residents
+    Ccpa,
+    /// General Data Protection Regulation, for EU, EEA and UK residents
+    Gdpr,
+    /// No particular law, for everyone else
+    Generic,
+}
+
+impl Jurisdiction {
+    /// The law covering residents of `state` in `country`.
+    ///
+    /// Profiles without a country are treated as US profiles. The CCPA only
+    /// covers California, so residents of other US states, and of countries
+    /// without a template for the local law, get a request citing no law.
+    pub fn for_residence(country: Option<&str>, state: Option<&str>) -> Self {
+        let code = normalize_country(country.unwrap_or(DEFAULT_COUNTRY));
+        let in_california = state.is_some_and(|state| state.trim().eq_ignore_ascii_case("CA"));
+        if code == "US" && in_california {
+            Self::Ccpa
+        } else if GDPR_COUNTRIES.contains(&code.as_str()) {
+            Self::Gdpr
+        } else {
+            Self::Generic
+        }
+    }
+
+    /// Name recorded in `legal_requests.jurisdiction`.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Ccpa => "ccpa",
+            Self::Gdpr => "gdpr",
+            Self::Generic => "generic",
+        }
+    }
+
+    /// Parse a name recorded by [`as_str`](Self::as_str).
+    pub fn parse(name: &str) -> Option<Self> {
+        match name {
+            "ccpa" => Some(Self::Ccpa),
+            "gdpr" => Some(Self::Gdpr),
+            "generic" => Some(Self::Generic),
+            _ => None,
+        }
+    }
+
+    fn templates(self) -> (&'static str, &'static str) {
+        match self {
+            Self::Ccpa => (CCPA_SUBJECT, CCPA_BODY),
+            Self::Gdpr => (GDPR_SUBJECT, GDPR_BODY),
+            Self::Generic => (GENERIC_SUBJECT, GENERIC_BODY),
+        }
+    }
+}
+
+/// Render the legal request for `jurisdiction` to `to`, dated `date`.
+///
+/// `profile_fields` must hold `email` and `listing_url`, and either
+/// `full_name` or `first_name` and `last_name`. Returns the placeholders
+/// left without a value otherwise.
+pub fn render_legal_request(
+    jurisdiction: Jurisdiction,
+    to: &str,
+    broker_name: &str,
+    profile_fields: &HashMap<String, String>,
+    date: NaiveDate,
+) -> Result<EmailTemplate, Vec<String>> {
+    let mut values = profile_fields.clone();
+    if !values.contains_key("full_name") {
+        if let (Some(first), Some(last)) = (values.get("first_name"), values.get("last_name")) {
+            let full_name = format!("{first} {last}");
+            values.insert("full_name".to_string(), full_name);
+        }
+    }
+    values.insert("broker_name".to_string(), broker_name.to_string());
+    values.insert("date".to_string(), date.format("%Y-%m-%d").to_string());
+
+    let (subject, body) = jurisdiction.templates();
+    let available: Vec<&str> = values.keys().map(String::as_str).collect();
+    let mut unresolved = validate_template(subject, &available)
+        .err()
+        .unwrap_or_default();
+    for name in validate_template(body, &available)
+        .err()
+        .unwrap_or_default()
+    {
+        if !unresolved.contains(&name) {
+            unresolved.push(name);
+        }
+    }
+    if !unresolved.is_empty() {
+        return Err(unresolved);
+    }
+
+    let fill = |template: &str| {
+        values
+            .iter()
+            .fold(template.to_string(), |text, (key, value)| {
+                text.replace(&format!("{{{{{key}}}}}"), value)
+            })
+    };
+    Ok(EmailTemplate {
+        to: to.to_string(),
+        subject: fill(subject),
+        body: fill(body),
+    })
+}
+
+/// Render a stored draft for the user to review, dated the day it was
+/// drafted.
+///
+/// `profile_fields` are those of the profile the removal attempt was made
+/// for; see [`render_legal_request`].
+pub fn render_draft(
+    request: &LegalRequest,
+    broker_name: &str,
+    profile_fields: &HashMap<String, String>,
+) -> Result<EmailTemplate, String> {
+    let jurisdiction = Jurisdiction::parse(&request.jurisdiction)
+        .ok_or_else(|| format!("Unknown jurisdiction {:?}", request.jurisdiction))?;
+    render_legal_request(
+        jurisdiction,
+        &request.recipient,
+        broker_name,
+        profile_fields,
+        request.created_at.date_naive(),
+    )
+    .map_err(missing_fields_error)
+}
+
+fn missing_fields_error(missing: Vec<String>) -> String {
+    format!(
+        "Legal request is missing profile fields: {}",
+        missing.join(", ")
+    )
+}
+
+/// Draft a legal request for a removal attempt that has failed at least
+/// `threshold` times, queueing it for the user to review.
+///
+/// The law cited follows the user's `country` and `state`; see
+/// [`Jurisdiction::for_residence`]. The request is rendered once to check
+/// the profile has every field it needs, but only its metadata is stored.
+/// Returns `None` when the attempt has not failed often enough, already has
+/// a legal request, or the broker has no legal contact.
+pub async fn escalate_failed_removal(
+    pool: &SqlitePool,
+    attempt_id: &str,
+    broker: &BrokerDefinition,
+    profile_fields: &HashMap<String, String>,
+    country: Option<&str>,
+    state: Option<&str>,
+    threshold: u32,
+) -> Result<Option<LegalRequest>, String> {
+    let failures = removal_attempts::failure_count(pool, attempt_id)
+        .await
+        .map_err(|e| format!("Failed to count removal failures: {e}"))?;
+    if failures < threshold {
+        return Ok(None);
+    }
+
+    let existing = legal_requests::get_by_attempt_id(pool, attempt_id)
+        .await
+        .map_err(|e| format!("Failed to load legal request: {e}"))?;
+    if existing.is_some() {
+        return Ok(None);
+    }
+
+    let Some(recipient) = broker.legal_contact() else {
+        tracing::info!(
+            "No legal contact for {}; not drafting a legal request",
+            broker.id()
+        );
+        return Ok(None);
+    };
+    let jurisdiction = Jurisdiction::for_residence(country, state);
+
+    render_legal_request(
+        jurisdiction,
+        recipient,
+        broker.name(),
+        profile_fields,
+        chrono::Utc::now().date_naive(),
+    )
+    .map_err(missing_fields_error)?;
+
+    let request = legal_requests::insert_legal_request(
+        pool,
+        CreateLegalRequest {
+            attempt_id: attempt_id.to_string(),
+            broker_id: broker.id().to_string(),
+            jurisdiction: jurisdiction.as_str().to_string(),
+            recipient: recipient.to_string(),
+        },
+    )
+    .await
+    .map_err(|e| format!("Failed to store legal request: {e}"))?;
+
+    tracing::info!(
+        "Drafted {} legal request for attempt {} after {} failures",
+        jurisdiction.as_str(),
+        attempt_id,
+        failures
+    );
+    Ok(Some(request))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fields() -> HashMap<String, String> {
+        HashMap::from([
+            ("first_name".to_string(), "Alice".to_string()),
+            ("last_name".to_string(), "Smith".to_string()),
+            ("email".to_string(), "alice@example.com".to_string()),
+            (
+                "listing_url".to_string(),
+                "https://broker.example/p/1".to_string(),
+            ),
+        ])
+    }
+
+    fn date() -> NaiveDate {
+        NaiveDate::from_ymd_opt(2026, 3, 14).expect("valid date")
+    }
+
+    #[test]
+    fn test_jurisdiction_for_residence() {
+        assert_eq!(
+            Jurisdiction::for_residence(None, Some("CA")),
+            Jurisdiction::Ccpa
+        );
+        assert_eq!(
+            Jurisdiction::for_residence(Some("usa"), Some(" ca ")),
+            Jurisdiction::Ccpa
+        );
+        assert_eq!(
+            Jurisdiction::for_residence(Some("DE"), None),
+            Jurisdiction::Gdpr
+        );
+        assert_eq!(
+            Jurisdiction::for_residence(Some("United Kingdom"), None),
+            Jurisdiction::Gdpr
+        );
+
+        // The CCPA covers California only
+        assert_eq!(
+            Jurisdiction::for_residence(Some("US"), Some("WA")),
+            Jurisdiction::Generic
+        );
+        assert_eq!(
+            Jurisdiction::for_residence(None, None),
+            Jurisdiction::Generic
+        );
+        assert_eq!(
+            Jurisdiction::for_residence(Some("AU"), None),
+            Jurisdiction::Generic
+        );
+    }
+
+    #[test]
+    fn test_render_gdpr_request() {
+        let email = render_legal_request(
+            Jurisdiction::Gdpr,
+            "dpo@broker.example",
+            "Broker",
+            &fields(),
+            date(),
+        )
+        .expect("render");
+        assert_eq!(email.to, "dpo@broker.example");
+        assert_eq!(
+            email.subject,
+            "Request for Erasure under Article 17 GDPR — Alice Smith"
+        );
+        assert!(email
+            .body
+            .starts_with("To the Broker data protection officer"));
+        assert!(email
+            .body
+            .contains("Name: Alice Smith\nEmail: alice@example.com\n"));
+        assert!(email.body.ends_with("Alice Smith\n2026-03-14\n"));
+        assert!(!email.body.contains("{{"));
+    }
+
+    #[test]
+    fn test_render_generic_request_cites_no_law() {
+        let email = render_legal_request(
+            Jurisdiction::Generic,
+            "privacy@broker.example",
+            "Broker",
+            &fields(),
+            date(),
+        )
+        .expect("render");
+        assert_eq!(
+            email.subject,
+            "Request to Delete Personal Information — Alice Smith"
+        );
+        assert!(!email.body.contains("CCPA") && !email.body.contains("Article"));
+        assert!(!email.body.contains("{{"));
+    }
+
+    #[test]
+    fn test_render_reports_missing_fields() {
+        let mut fields = fields();
+        fields.remove("email");
+        fields.remove("last_name");
+        assert_eq!(
+            render_legal_request(
+                Jurisdiction::Ccpa,
+                "privacy@broker.example",
+                "Broker",
+                &fields,
+                date()
+            )
+            .map(|_| ()),
+            Err(vec!["full_name".to_string(), "email".to_string()])
+        );
+    }
+}
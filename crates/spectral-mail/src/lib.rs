@@ -1,7 +1,12 @@
+pub mod delivery;
 pub mod imap;
+pub mod inbox;
+pub mod legal;
 pub mod sender;
 pub mod templates;
 
-pub use imap::{ImapConfig, PollResult};
+pub use delivery::Delivery;
+pub use imap::{Bounce, ImapConfig, PollResult, Reply};
+pub use legal::Jurisdiction;
 pub use sender::SmtpConfig;
 pub use templates::EmailTemplate;
@@ -1,11 +1,63 @@
 use crate::templates::EmailTemplate;
+use serde::{Deserialize, Serialize};
 use sha2::{Digest, Sha256};
 
+/// SMTP server settings. Deliberately not `Debug` so the password never ends
+/// up in logs.
+///
+/// The envelope sender and the `From` header are set separately: the
+/// envelope sender is what SPF checks and where bounces go, so it must be an
+/// address the server is allowed to send for, while the header can show the
+/// user's own address.
+#[derive(Clone, Serialize, Deserialize)]
 pub struct SmtpConfig {
     pub host: String,
     pub port: u16,
     pub username: String,
+    /// Kept in the vault, never in the settings JSON; see
+    /// [`crate::delivery::load_smtp_config`].
+    #[serde(default, skip_serializing)]
     pub password: String,
+    /// Envelope sender (`MAIL FROM`). When empty, as in configurations
+    /// saved before it existed, the `From` address is used.
+    #[serde(default)]
+    pub envelope_from: String,
+    /// `From` header; the sender passed to [`send_smtp`] when unset.
+    #[serde(default)]
+    pub header_from: Option<String>,
+    /// `Reply-To` header, so broker replies reach an inbox the user reads.
+    #[serde(default)]
+    pub reply_to: Option<String>,
+}
+
+impl SmtpConfig {
+    /// Check that every configured address parses.
+    pub fn validate(&self) -> Result<(), String> {
+        if !self.envelope_from.trim().is_empty() {
+            parse_address(&self.envelope_from, "envelope-from")?;
+        }
+        if let Some(header_from) = &self.header_from {
+            parse_mailbox(header_from, "from")?;
+        }
+        if let Some(reply_to) = &self.reply_to {
+            parse_mailbox(reply_to, "reply-to")?;
+        }
+        Ok(())
+    }
+}
+
+fn parse_address(address: &str, what: &str) -> Result<lettre::Address, String> {
+    address
+        .trim()
+        .parse()
+        .map_err(|e| format!("Bad {what} address: {e}"))
+}
+
+fn parse_mailbox(mailbox: &str, what: &str) -> Result<lettre::message::Mailbox, String> {
+    mailbox
+        .trim()
+        .parse()
+        .map_err(|e| format!("Bad {what} address: {e}"))
 }
 
 /// Returns a `mailto:` URL for the given email.
@@ -15,25 +67,76 @@ pub fn to_mailto_url(email: &EmailTemplate) -> String {
     format!("mailto:{}?subject={}&body={}", email.to, subject, body)
 }
 
-/// Sends via SMTP using lettre.
-pub async fn send_smtp(
+/// Returns a new Message-ID (without angle brackets) on the sender's domain.
+pub fn new_message_id(from: &str) -> String {
+    let domain = from
+        .rsplit_once('@')
+        .map(|(_, domain)| domain.trim_end_matches('>'))
+        .filter(|domain| !domain.is_empty())
+        .unwrap_or("localhost");
+    format!("{}@{domain}", uuid::Uuid::new_v4())
+}
+
+/// Builds the message for an SMTP send, returning it with its Message-ID
+/// (without angle brackets).
+///
+/// The `From` header is `config.header_from`, or `from` when unset, and the
+/// Message-ID is on that address's domain. The envelope goes from
+/// `config.envelope_from`, or the `From` address when that is empty, to the
+/// broker. The body is sent as UTF-8 plain text.
+pub fn build_message(
     email: &EmailTemplate,
     from: &str,
     config: &SmtpConfig,
-) -> Result<(), String> {
-    use lettre::transport::smtp::authentication::Credentials;
-    use lettre::{Message, SmtpTransport, Transport};
-
-    let msg = Message::builder()
-        .from(from.parse().map_err(|e| format!("Bad from address: {e}"))?)
-        .to(email
-            .to
-            .parse()
-            .map_err(|e| format!("Bad to address: {e}"))?)
+) -> Result<(lettre::Message, String), String> {
+    use lettre::address::Envelope;
+    use lettre::message::header::ContentType;
+    use lettre::Message;
+
+    config.validate()?;
+    let header_from = parse_mailbox(config.header_from.as_deref().unwrap_or(from), "from")?;
+    let to = parse_mailbox(&email.to, "to")?;
+    let envelope_from = if config.envelope_from.trim().is_empty() {
+        header_from.email.clone()
+    } else {
+        parse_address(&config.envelope_from, "envelope-from")?
+    };
+    let envelope = Envelope::new(Some(envelope_from), vec![to.email.clone()])
+        .map_err(|e| format!("Bad envelope: {e}"))?;
+
+    let message_id = new_message_id(header_from.email.as_ref());
+    let mut builder = Message::builder()
+        .envelope(envelope)
+        .message_id(Some(format!("<{message_id}>")))
+        .from(header_from)
+        .to(to)
         .subject(&email.subject)
+        .header(ContentType::TEXT_PLAIN);
+    if let Some(reply_to) = &config.reply_to {
+        builder = builder.reply_to(parse_mailbox(reply_to, "reply-to")?);
+    }
+    let msg = builder
         .body(email.body.clone())
         .map_err(|e| format!("Failed to build message: {e}"))?;
 
+    Ok((msg, message_id))
+}
+
+/// Sends via SMTP using lettre.
+///
+/// Returns the Message-ID set on the message (without angle brackets) so that
+/// replies and bounce reports can be matched back to it. Fails without
+/// sending if `config` does not pass [`SmtpConfig::validate`].
+pub async fn send_smtp(
+    email: &EmailTemplate,
+    from: &str,
+    config: &SmtpConfig,
+) -> Result<String, String> {
+    use lettre::transport::smtp::authentication::Credentials;
+    use lettre::{SmtpTransport, Transport};
+
+    let (msg, message_id) = build_message(email, from, config)?;
+
     let creds = Credentials::new(config.username.clone(), config.password.clone());
     let transport = SmtpTransport::relay(&config.host)
         .map_err(|e| format!("SMTP relay error: {e}"))?
@@ -44,7 +147,7 @@ pub async fn send_smtp(
     transport
         .send(&msg)
         .map_err(|e| format!("SMTP send failed: {e}"))?;
-    Ok(())
+    Ok(message_id)
 }
 
 /// Returns SHA-256 hex of email body (for logging — never store the body itself).
@@ -71,6 +174,103 @@ mod tests {
         assert!(url.contains("subject="));
     }
 
+    #[test]
+    fn test_new_message_id_uses_sender_domain() {
+        let id = new_message_id("user@example.com");
+        assert!(id.ends_with("@example.com"));
+        assert_ne!(id, new_message_id("user@example.com"));
+        assert!(new_message_id("not-an-address").ends_with("@localhost"));
+    }
+
+    fn smtp_config() -> SmtpConfig {
+        SmtpConfig {
+            host: "smtp.example.com".to_string(),
+            port: 587,
+            username: "user".to_string(),
+            password: "secret".to_string(),
+            envelope_from: "bounces@mail.example.com".to_string(),
+            header_from: Some("Jane Doe <jane@example.com>".to_string()),
+            reply_to: Some("jane.replies@example.com".to_string()),
+        }
+    }
+
+    fn opt_out() -> EmailTemplate {
+        EmailTemplate {
+            to: "privacy@broker.com".to_string(),
+            subject: "Opt-Out Request".to_string(),
+            body: "Please remove my listing. — Jane".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_build_message_sets_envelope_and_headers() {
+        let (msg, message_id) =
+            build_message(&opt_out(), "user@fallback.com", &smtp_config()).expect("build");
+
+        let envelope = msg.envelope();
+        assert_eq!(
+            envelope.from().map(ToString::to_string).as_deref(),
+            Some("bounces@mail.example.com")
+        );
+        assert_eq!(
+            envelope
+                .to()
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>(),
+            vec!["privacy@broker.com"]
+        );
+
+        let formatted = String::from_utf8(msg.formatted()).expect("utf-8 message");
+        assert!(formatted.contains("From: \"Jane Doe\" <jane@example.com>\r\n"));
+        assert!(formatted.contains("Reply-To: jane.replies@example.com\r\n"));
+        assert!(formatted.contains("To: privacy@broker.com\r\n"));
+        assert!(formatted.contains("Content-Type: text/plain; charset=utf-8\r\n"));
+        assert!(formatted.contains(&format!("Message-ID: <{message_id}>\r\n")));
+        assert!(message_id.ends_with("@example.com"));
+    }
+
+    #[test]
+    fn test_header_from_defaults_to_sender() {
+        let config = SmtpConfig {
+            header_from: None,
+            reply_to: None,
+            ..smtp_config()
+        };
+        let (msg, _) = build_message(&opt_out(), "user@fallback.com", &config).expect("build");
+
+        let formatted = String::from_utf8(msg.formatted()).expect("utf-8 message");
+        assert!(formatted.contains("From: user@fallback.com\r\n"));
+        assert!(!formatted.contains("Reply-To:"));
+        assert_eq!(
+            msg.envelope().from().map(ToString::to_string).as_deref(),
+            Some("bounces@mail.example.com")
+        );
+    }
+
+    #[test]
+    fn test_envelope_from_defaults_to_from_address() {
+        let config = SmtpConfig {
+            envelope_from: String::new(),
+            header_from: None,
+            ..smtp_config()
+        };
+        assert!(config.validate().is_ok());
+        let (msg, _) = build_message(&opt_out(), "user@example.com", &config).expect("build");
+        assert_eq!(
+            msg.envelope().from().map(ToString::to_string).as_deref(),
+            Some("user@example.com")
+        );
+
+        let invalid = SmtpConfig {
+            envelope_from: "not an address".to_string(),
+            ..smtp_config()
+        };
+        assert!(invalid.validate().is_err());
+        assert!(build_message(&opt_out(), "user@example.com", &invalid).is_err());
+        assert!(smtp_config().validate().is_ok());
+    }
+
     #[test]
     fn test_body_hash_is_deterministic() {
         let h1 = body_hash("hello");
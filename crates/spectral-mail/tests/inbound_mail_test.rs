@@ -0,0 +1,222 @@
+use chrono::Utc;
+use spectral_db::email_removals::{find_attempt_for_reply, mark_bounced, BOUNCE_REASON};
+use spectral_db::removal_attempts::{self, RemovalStatus};
+use spectral_db::Database;
+use spectral_mail::imap::{parse_bounce, parse_reply, ImapConfig};
+use spectral_mail::inbox::{
+    apply_bounces, load_imap_config, save_imap_config, IMAP_PASSWORD_SECRET, IMAP_SETTINGS_KEY,
+};
+use spectral_vault::Vault;
+
+const BOUNCE_HEADERS: &str = "From: Mail Delivery Subsystem <mailer-daemon@googlemail.com>\r
+To: user@example.com\r
+Subject: Delivery Status Notification (Failure)\r
+Content-Type: multipart/report; boundary=\"000\"; report-type=delivery-status\r
+";
+
+const BOUNCE_BODY: &str = "--000\r
+Content-Type: text/plain\r
+\r
+Address not found. Your message wasn't delivered to optout@broker.example.\r
+--000\r
+Content-Type: message/delivery-status\r
+\r
+Reporting-MTA: dns; googlemail.com\r
+\r
+Final-Recipient: rfc822; optout@broker.example\r
+Action: failed\r
+Status: 5.1.1\r
+--000\r
+Content-Type: message/rfc822\r
+\r
+From: user@example.com\r
+To: optout@broker.example\r
+Message-ID: <bounced-1@example.com>\r
+Subject: Opt-Out Request\r
+--000--\r
+";
+
+/// Set up a database with one submitted attempt per `(message_id, recipient)`.
+async fn setup_submitted_emails(emails: &[(&str, &str)]) -> (Database, Vec<String>) {
+    let db = Database::new(":memory:", vec![0u8; 32])
+        .await
+        .expect("create database");
+    db.run_migrations().await.expect("run migrations");
+
+    let now = Utc::now().to_rfc3339();
+    for sql in [
+        "INSERT INTO profiles (id, data, nonce, created_at, updated_at) VALUES ('p1', x'00', x'00', ?1, ?1)",
+        "INSERT INTO scan_jobs (id, profile_id, started_at, status, total_brokers, completed_brokers) VALUES ('j1', 'p1', ?1, 'Completed', 1, 1)",
+        "INSERT INTO broker_scans (id, scan_job_id, broker_id, status, started_at) VALUES ('s1', 'j1', 'broker', 'Success', ?1)",
+        "INSERT INTO findings (id, broker_scan_id, broker_id, profile_id, listing_url, verification_status, extracted_data, discovered_at) VALUES ('f1', 's1', 'broker', 'p1', 'https://broker.example/1', 'Confirmed', '{}', ?1)",
+    ] {
+        sqlx::query(sql)
+            .bind(&now)
+            .execute(db.pool())
+            .await
+            .expect("seed row");
+    }
+
+    let mut attempt_ids = Vec::new();
+    for (message_id, recipient) in emails {
+        let attempt =
+            removal_attempts::create_removal_attempt(db.pool(), "f1".into(), "broker".into())
+                .await
+                .expect("create attempt");
+        removal_attempts::update_status(
+            db.pool(),
+            &attempt.id,
+            RemovalStatus::Submitted,
+            Some(Utc::now()),
+            None,
+            None,
+        )
+        .await
+        .expect("mark submitted");
+        sqlx::query(
+            "INSERT INTO email_removals (id, attempt_id, broker_id, sent_at, method, recipient, subject, body_hash, message_id)
+             VALUES (?, ?, 'broker', ?, 'smtp', ?, 'Opt-Out Request', 'hash', ?)",
+        )
+        .bind(format!("email-{message_id}"))
+        .bind(&attempt.id)
+        .bind(&now)
+        .bind(recipient)
+        .bind(message_id)
+        .execute(db.pool())
+        .await
+        .expect("log email removal");
+        attempt_ids.push(attempt.id);
+    }
+
+    (db, attempt_ids)
+}
+
+#[tokio::test]
+async fn test_bounce_marks_matching_attempt_failed() {
+    let (db, attempts) = setup_submitted_emails(&[
+        ("sent-0@example.com", "optout@broker.example"),
+        ("bounced-1@example.com", "optout@broker.example"),
+    ])
+    .await;
+
+    let bounce = parse_bounce(BOUNCE_HEADERS, BOUNCE_BODY).expect("bounce detected");
+    let failed = mark_bounced(db.pool(), &bounce.recipient, bounce.message_id.as_deref())
+        .await
+        .expect("mark bounced");
+    assert_eq!(failed.as_deref(), Some(attempts[1].as_str()));
+
+    let bounced = removal_attempts::get_by_id(db.pool(), &attempts[1])
+        .await
+        .expect("get attempt")
+        .expect("attempt exists");
+    assert_eq!(bounced.status, RemovalStatus::Failed);
+    assert_eq!(bounced.error_message.as_deref(), Some(BOUNCE_REASON));
+    assert!(bounced.submitted_at.is_some());
+
+    let untouched = removal_attempts::get_by_id(db.pool(), &attempts[0])
+        .await
+        .expect("get attempt")
+        .expect("attempt exists");
+    assert_eq!(untouched.status, RemovalStatus::Submitted);
+}
+
+#[tokio::test]
+async fn test_bounce_for_unknown_recipient_changes_nothing() {
+    let (db, attempts) =
+        setup_submitted_emails(&[("other@example.com", "privacy@other.example")]).await;
+
+    let bounce = parse_bounce(BOUNCE_HEADERS, BOUNCE_BODY).expect("bounce detected");
+    let failed = mark_bounced(db.pool(), &bounce.recipient, bounce.message_id.as_deref())
+        .await
+        .expect("mark bounced");
+    assert_eq!(failed, None);
+
+    let attempt = removal_attempts::get_by_id(db.pool(), &attempts[0])
+        .await
+        .expect("get attempt")
+        .expect("attempt exists");
+    assert_eq!(attempt.status, RemovalStatus::Submitted);
+}
+
+#[tokio::test]
+async fn test_apply_bounces_fails_each_matching_attempt_once() {
+    let (db, attempts) = setup_submitted_emails(&[
+        ("sent-0@example.com", "optout@broker.example"),
+        ("bounced-1@example.com", "optout@broker.example"),
+    ])
+    .await;
+
+    // The same report seen twice fails its attempt only once
+    let bounce = parse_bounce(BOUNCE_HEADERS, BOUNCE_BODY).expect("bounce detected");
+    let failed = apply_bounces(db.pool(), &[bounce.clone(), bounce])
+        .await
+        .expect("apply bounces");
+    assert_eq!(failed, vec![attempts[1].clone()]);
+
+    let untouched = removal_attempts::get_by_id(db.pool(), &attempts[0])
+        .await
+        .expect("get attempt")
+        .expect("attempt exists");
+    assert_eq!(untouched.status, RemovalStatus::Submitted);
+}
+
+#[tokio::test]
+async fn test_imap_config_keeps_password_in_vault() {
+    let vault = Vault::new_in_memory("password")
+        .await
+        .expect("create vault");
+    assert!(load_imap_config(&vault)
+        .await
+        .expect("load config")
+        .is_none());
+
+    let config = ImapConfig {
+        host: "imap.example.com".to_string(),
+        port: 993,
+        username: "user@example.com".to_string(),
+        password: "app-password".to_string(),
+    };
+    save_imap_config(&vault, &config)
+        .await
+        .expect("save config");
+
+    let loaded = load_imap_config(&vault)
+        .await
+        .expect("load config")
+        .expect("config saved");
+    assert_eq!(loaded.host, "imap.example.com");
+    assert_eq!(loaded.port, 993);
+    assert_eq!(loaded.password, "app-password");
+
+    let stored =
+        spectral_db::settings::get_setting(vault.database().unwrap().pool(), IMAP_SETTINGS_KEY)
+            .await
+            .expect("read settings")
+            .expect("config saved");
+    assert!(!stored.to_string().contains("app-password"));
+    assert!(vault
+        .load_secret(IMAP_PASSWORD_SECRET)
+        .await
+        .expect("load secret")
+        .is_some());
+}
+
+#[tokio::test]
+async fn test_reply_matched_by_message_id() {
+    let (db, attempts) = setup_submitted_emails(&[
+        ("sent-0@example.com", "optout@broker.example"),
+        ("sent-1@example.com", "optout@broker.example"),
+    ])
+    .await;
+
+    // The broker replies from a different address than the one we wrote to
+    let headers = "From: Broker Support <support@broker.example>\r
+In-Reply-To: <sent-1@example.com>\r
+Subject: Re: Opt-Out Request\r
+";
+    let reply = parse_reply(headers).expect("reply detected");
+    let attempt = find_attempt_for_reply(db.pool(), &reply.from, &reply.references)
+        .await
+        .expect("find attempt");
+    assert_eq!(attempt.as_deref(), Some(attempts[1].as_str()));
+}
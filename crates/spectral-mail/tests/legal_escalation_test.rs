@@ -0,0 +1,215 @@
+use chrono::Utc;
+use spectral_broker::definition::{
+    BrokerCategory, BrokerDefinition, BrokerMetadata, RemovalConfirmation, RemovalDifficulty,
+    RemovalMethod, SearchMethod,
+};
+use spectral_core::BrokerId;
+use spectral_db::legal_requests::{get_review_queue, LegalRequestStatus};
+use spectral_db::removal_attempts::{self, RemovalStatus};
+use spectral_db::Database;
+use spectral_mail::legal::{escalate_failed_removal, render_draft, ESCALATION_THRESHOLD};
+use std::collections::HashMap;
+
+/// Set up a database with one pending removal attempt.
+async fn setup_attempt() -> (Database, String) {
+    let db = Database::new(":memory:", vec![0u8; 32])
+        .await
+        .expect("create database");
+    db.run_migrations().await.expect("run migrations");
+
+    let now = Utc::now().to_rfc3339();
+    for sql in [
+        "INSERT INTO profiles (id, data, nonce, created_at, updated_at) VALUES ('p1', x'00', x'00', ?1, ?1)",
+        "INSERT INTO scan_jobs (id, profile_id, started_at, status, total_brokers, completed_brokers) VALUES ('j1', 'p1', ?1, 'Completed', 1, 1)",
+        "INSERT INTO broker_scans (id, scan_job_id, broker_id, status, started_at) VALUES ('s1', 'j1', 'broker', 'Success', ?1)",
+        "INSERT INTO findings (id, broker_scan_id, broker_id, profile_id, listing_url, verification_status, extracted_data, discovered_at) VALUES ('f1', 's1', 'broker', 'p1', 'https://broker.example/1', 'Confirmed', '{}', ?1)",
+    ] {
+        sqlx::query(sql)
+            .bind(&now)
+            .execute(db.pool())
+            .await
+            .expect("seed row");
+    }
+
+    let attempt = removal_attempts::create_removal_attempt(db.pool(), "f1".into(), "broker".into())
+        .await
+        .expect("create removal attempt");
+    (db, attempt.id)
+}
+
+/// Web-form broker with a separate privacy officer address.
+fn broker() -> BrokerDefinition {
+    BrokerDefinition {
+        broker: BrokerMetadata {
+            id: BrokerId::new("broker").expect("valid broker ID"),
+            name: "Example Broker".to_string(),
+            url: "https://broker.example".to_string(),
+            domain: "broker.example".to_string(),
+            category: BrokerCategory::PeopleSearch,
+            difficulty: RemovalDifficulty::Hard,
+            typical_removal_days: 14,
+            recheck_interval_days: 30,
+            last_verified: chrono::NaiveDate::from_ymd_opt(2026, 1, 1).expect("valid date"),
+            scan_priority: spectral_broker::ScanPriority::OnRequest,
+            region_relevance: vec!["Global".to_string()],
+            countries: vec![],
+            requires_id_verification: false,
+            requires_account: false,
+            related_brokers: vec![],
+            request_headers: HashMap::new(),
+            legal_contact: Some("privacy-officer@broker.example".to_string()),
+        },
+        search: SearchMethod::Manual {
+            url: "https://broker.example/search".to_string(),
+            instructions: "Search by name".to_string(),
+        },
+        removal: RemovalMethod::Manual {
+            instructions: "Use the opt-out page".to_string(),
+        },
+        fixture: None,
+        confirmation: RemovalConfirmation::None,
+    }
+}
+
+fn profile_fields() -> HashMap<String, String> {
+    HashMap::from([
+        ("first_name".to_string(), "Alice".to_string()),
+        ("last_name".to_string(), "Smith".to_string()),
+        ("email".to_string(), "alice@example.com".to_string()),
+        (
+            "listing_url".to_string(),
+            "https://broker.example/1".to_string(),
+        ),
+    ])
+}
+
+/// Record one more failed submission of the attempt, retrying it first.
+async fn fail(db: &Database, attempt_id: &str) {
+    for status in [RemovalStatus::Pending, RemovalStatus::Failed] {
+        removal_attempts::update_status(
+            db.pool(),
+            attempt_id,
+            status,
+            None,
+            None,
+            Some("form submission failed".to_string()),
+        )
+        .await
+        .expect("update status");
+    }
+}
+
+#[tokio::test]
+async fn test_legal_request_drafted_after_repeated_failures() {
+    let (db, attempt_id) = setup_attempt().await;
+    let broker = broker();
+    let fields = profile_fields();
+    let escalate = || {
+        escalate_failed_removal(
+            db.pool(),
+            &attempt_id,
+            &broker,
+            &fields,
+            Some("US"),
+            Some("CA"),
+            ESCALATION_THRESHOLD,
+        )
+    };
+
+    // Below the threshold nothing is drafted
+    for _ in 1..ESCALATION_THRESHOLD {
+        fail(&db, &attempt_id).await;
+        assert!(escalate().await.expect("escalate").is_none());
+    }
+    assert!(get_review_queue(db.pool())
+        .await
+        .expect("review queue")
+        .is_empty());
+
+    fail(&db, &attempt_id).await;
+    let request = escalate()
+        .await
+        .expect("escalate")
+        .expect("legal request drafted");
+    assert_eq!(request.recipient, "privacy-officer@broker.example");
+    assert_eq!(request.jurisdiction, "ccpa");
+    assert_eq!(request.status, LegalRequestStatus::Draft);
+
+    // The text is rendered from the profile for review
+    let email = render_draft(&request, broker.name(), &fields).expect("render draft");
+    assert_eq!(email.to, "privacy-officer@broker.example");
+    assert_eq!(
+        email.subject,
+        "CCPA Request to Delete Personal Information — Alice Smith"
+    );
+    assert!(email
+        .body
+        .starts_with("To the Example Broker privacy team,"));
+    assert!(email.body.contains("Cal. Civ. Code § 1798.105"));
+    assert!(email.body.contains(
+        "Name: Alice Smith\nEmail: alice@example.com\nListing: https://broker.example/1\n"
+    ));
+    assert!(email
+        .body
+        .ends_with(&format!("{}\n", request.created_at.format("%Y-%m-%d"))));
+    assert!(!email.body.contains("{{"));
+
+    // Nothing but the metadata is stored
+    let columns: Vec<String> =
+        sqlx::query_scalar("SELECT name FROM pragma_table_info('legal_requests')")
+            .fetch_all(db.pool())
+            .await
+            .expect("table info");
+    assert!(!columns.iter().any(|c| c == "subject" || c == "body"));
+
+    // Queued for review, and not drafted again on later failures
+    let queue = get_review_queue(db.pool()).await.expect("review queue");
+    assert_eq!(queue.len(), 1);
+    assert_eq!(queue[0].id, request.id);
+
+    fail(&db, &attempt_id).await;
+    assert!(escalate().await.expect("escalate").is_none());
+    assert_eq!(
+        get_review_queue(db.pool())
+            .await
+            .expect("review queue")
+            .len(),
+        1
+    );
+}
+
+#[tokio::test]
+async fn test_gdpr_request_falls_back_to_removal_address() {
+    let (db, attempt_id) = setup_attempt().await;
+    let mut broker = broker();
+    broker.broker.legal_contact = None;
+    broker.removal = RemovalMethod::Email {
+        email: "optout@broker.example".to_string(),
+        subject: "Opt-out".to_string(),
+        body: "Please remove {{full_name}}".to_string(),
+        response_days: 30,
+        notes: String::new(),
+    };
+
+    for _ in 0..ESCALATION_THRESHOLD {
+        fail(&db, &attempt_id).await;
+    }
+    let request = escalate_failed_removal(
+        db.pool(),
+        &attempt_id,
+        &broker,
+        &profile_fields(),
+        Some("DE"),
+        None,
+        ESCALATION_THRESHOLD,
+    )
+    .await
+    .expect("escalate")
+    .expect("legal request drafted");
+
+    assert_eq!(request.recipient, "optout@broker.example");
+    assert_eq!(request.jurisdiction, "gdpr");
+    let email = render_draft(&request, broker.name(), &profile_fields()).expect("render draft");
+    assert!(email.body.contains("Article 17"));
+    assert!(email.body.contains("Name: Alice Smith"));
+}
@@ -0,0 +1,206 @@
+use chrono::Utc;
+use spectral_db::email_removals::get_by_attempt_id;
+use spectral_db::removal_attempts;
+use spectral_db::Database;
+use spectral_mail::delivery::{
+    deliver, deliver_with, load_smtp_config, record_delivery, save_smtp_config, Delivery,
+    SMTP_PASSWORD_SECRET, SMTP_SETTINGS_KEY,
+};
+use spectral_mail::sender::build_message;
+use spectral_mail::{EmailTemplate, SmtpConfig};
+use spectral_vault::Vault;
+use std::sync::atomic::{AtomicU32, Ordering};
+
+/// Set up a database with one pending removal attempt.
+async fn setup_attempt() -> (Database, String) {
+    let db = Database::new(":memory:", vec![0u8; 32])
+        .await
+        .expect("create database");
+    db.run_migrations().await.expect("run migrations");
+
+    let now = Utc::now().to_rfc3339();
+    for sql in [
+        "INSERT INTO profiles (id, data, nonce, created_at, updated_at) VALUES ('p1', x'00', x'00', ?1, ?1)",
+        "INSERT INTO scan_jobs (id, profile_id, started_at, status, total_brokers, completed_brokers) VALUES ('j1', 'p1', ?1, 'Completed', 1, 1)",
+        "INSERT INTO broker_scans (id, scan_job_id, broker_id, status, started_at) VALUES ('s1', 'j1', 'broker', 'Success', ?1)",
+        "INSERT INTO findings (id, broker_scan_id, broker_id, profile_id, listing_url, verification_status, extracted_data, discovered_at) VALUES ('f1', 's1', 'broker', 'p1', 'https://broker.example/1', 'Confirmed', '{}', ?1)",
+    ] {
+        sqlx::query(sql)
+            .bind(&now)
+            .execute(db.pool())
+            .await
+            .expect("seed row");
+    }
+
+    let attempt = removal_attempts::create_removal_attempt(db.pool(), "f1".into(), "broker".into())
+        .await
+        .expect("create attempt");
+    (db, attempt.id)
+}
+
+fn removal_email() -> EmailTemplate {
+    EmailTemplate {
+        to: "optout@broker.example".to_string(),
+        subject: "Opt-Out Request".to_string(),
+        body: "Please remove my listing.".to_string(),
+    }
+}
+
+#[tokio::test]
+async fn test_working_smtp_sends_and_records_smtp() {
+    let (db, attempt_id) = setup_attempt().await;
+    let email = removal_email();
+
+    let delivery = deliver_with(&email, || async { Ok("sent-1@example.com".to_string()) }).await;
+    assert_eq!(
+        delivery,
+        Delivery::Smtp {
+            message_id: "sent-1@example.com".to_string()
+        }
+    );
+
+    record_delivery(db.pool(), &attempt_id, "broker", &email, &delivery)
+        .await
+        .expect("record delivery");
+    let logged = get_by_attempt_id(db.pool(), &attempt_id)
+        .await
+        .expect("get email removals");
+    let [logged] = logged.as_slice() else {
+        panic!("expected one email removal, got {}", logged.len());
+    };
+    assert_eq!(logged.method, "smtp");
+    assert_eq!(logged.message_id.as_deref(), Some("sent-1@example.com"));
+    assert_eq!(logged.recipient, "optout@broker.example");
+}
+
+#[tokio::test]
+async fn test_failing_smtp_retries_then_falls_back_to_mailto() {
+    let (db, attempt_id) = setup_attempt().await;
+    let email = removal_email();
+    let attempts = AtomicU32::new(0);
+
+    // Skip the real retry delay; the database needs real time again afterwards
+    tokio::time::pause();
+    let delivery = deliver_with(&email, || async {
+        attempts.fetch_add(1, Ordering::SeqCst);
+        Err::<String, _>("SMTP send failed: connection refused".to_string())
+    })
+    .await;
+    tokio::time::resume();
+
+    assert_eq!(attempts.load(Ordering::SeqCst), 2);
+    let Delivery::Mailto { url } = &delivery else {
+        panic!("expected mailto fallback, got {delivery:?}");
+    };
+    assert!(url.starts_with("mailto:optout@broker.example"));
+
+    record_delivery(db.pool(), &attempt_id, "broker", &email, &delivery)
+        .await
+        .expect("record delivery");
+    let logged = get_by_attempt_id(db.pool(), &attempt_id)
+        .await
+        .expect("get email removals");
+    let [logged] = logged.as_slice() else {
+        panic!("expected one email removal, got {}", logged.len());
+    };
+    assert_eq!(logged.method, "mailto");
+    assert_eq!(logged.message_id, None);
+}
+
+#[tokio::test]
+async fn test_no_smtp_config_uses_mailto() {
+    let vault = Vault::new_in_memory("password")
+        .await
+        .expect("create vault");
+    assert!(load_smtp_config(&vault)
+        .await
+        .expect("load config")
+        .is_none());
+
+    let delivery = deliver(&removal_email(), "user@example.com", None).await;
+    assert_eq!(delivery.method(), "mailto");
+
+    let config = SmtpConfig {
+        host: "smtp.example.com".to_string(),
+        port: 587,
+        username: "user@example.com".to_string(),
+        password: "app-password".to_string(),
+        envelope_from: "user@example.com".to_string(),
+        header_from: None,
+        reply_to: None,
+    };
+    save_smtp_config(&vault, &config)
+        .await
+        .expect("save config");
+    let loaded = load_smtp_config(&vault)
+        .await
+        .expect("load config")
+        .expect("config saved");
+    assert_eq!(loaded.host, "smtp.example.com");
+    assert_eq!(loaded.port, 587);
+    assert_eq!(loaded.envelope_from, "user@example.com");
+    assert_eq!(loaded.password, "app-password");
+
+    // The password is only in the vault
+    let stored =
+        spectral_db::settings::get_setting(vault.database().unwrap().pool(), SMTP_SETTINGS_KEY)
+            .await
+            .expect("read settings")
+            .expect("config saved");
+    assert!(!stored.to_string().contains("app-password"));
+
+    // A configuration that could not send is not saved
+    let bad_envelope = SmtpConfig {
+        envelope_from: "not an address".to_string(),
+        ..config
+    };
+    assert!(save_smtp_config(&vault, &bad_envelope).await.is_err());
+}
+
+#[tokio::test]
+async fn test_smtp_config_saved_before_envelope_from_still_sends() {
+    let vault = Vault::new_in_memory("password")
+        .await
+        .expect("create vault");
+    let pool = vault.database().unwrap().pool();
+    let saved = serde_json::json!({
+        "host": "smtp.example.com",
+        "port": 587,
+        "username": "user@example.com",
+        "password": "app-password",
+    });
+    spectral_db::settings::set_setting(pool, SMTP_SETTINGS_KEY, &saved)
+        .await
+        .expect("save old config");
+
+    let config = load_smtp_config(&vault)
+        .await
+        .expect("load config")
+        .expect("config saved");
+    assert!(config.validate().is_ok());
+    assert_eq!(config.password, "app-password");
+
+    // The plain-text password has moved to the vault
+    let stored = spectral_db::settings::get_setting(pool, SMTP_SETTINGS_KEY)
+        .await
+        .expect("read settings")
+        .expect("config saved");
+    assert!(!stored.to_string().contains("app-password"));
+    let secret = vault
+        .load_secret(SMTP_PASSWORD_SECRET)
+        .await
+        .expect("load secret")
+        .expect("password moved");
+    assert_eq!(secret.as_str(), "app-password");
+
+    let (message, _) =
+        build_message(&removal_email(), "user@example.com", &config).expect("build message");
+    assert_eq!(
+        message
+            .envelope()
+            .from()
+            .map(ToString::to_string)
+            .as_deref(),
+        Some("user@example.com")
+    );
+}
@@ -15,7 +15,7 @@
 //! # #[tokio::main]
 //! # async fn main() -> Result<(), Box<dyn std::error::Error>> {
 //! // Create a permission manager with the Balanced preset
-//! let manager = PermissionManager::new_with_preset(PermissionPreset::Balanced);
+//! let manager = PermissionManager::new_with_preset(&PermissionPreset::Balanced);
 //!
 //! // Check if a permission is granted
 //! if manager.is_granted(Permission::ScanBrokers) {
@@ -36,11 +36,13 @@ mod audit;
 mod manager;
 mod presets;
 mod prompts;
+mod snapshot;
 
 pub use audit::{AuditEntry, AuditLogger, AuditOutcome};
 pub use manager::{PermissionDecision, PermissionManager};
 pub use presets::PermissionPreset;
 pub use prompts::PermissionPrompt;
+pub use snapshot::{PermissionSnapshot, SnapshotGrant};
 
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
@@ -67,6 +69,10 @@ pub enum PermissionError {
     #[error("audit log error: {0}")]
     AuditError(String),
 
+    /// Reading or writing stored permission data failed
+    #[error("permission storage error: {0}")]
+    Storage(String),
+
     /// I/O error
     #[error("I/O error: {0}")]
     Io(#[from] std::io::Error),
@@ -131,6 +137,26 @@ pub enum Permission {
 }
 
 impl Permission {
+    /// Get every permission, in declaration order.
+    #[must_use]
+    pub fn all() -> Vec<Self> {
+        vec![
+            Self::ScanBrokers,
+            Self::SubmitRemovalForms,
+            Self::SendEmails,
+            Self::NetworkAccess,
+            Self::UseLlmCloud,
+            Self::UseLlmLocal,
+            Self::LlmGuidedBrowsing,
+            Self::ScanFilesystem,
+            Self::ScanBrowserData,
+            Self::ScanEmails,
+            Self::AutoScheduleScans,
+            Self::AutoSubmitRemovals,
+            Self::BackgroundExecution,
+        ]
+    }
+
     /// Get a human-readable name for the permission.
     #[must_use]
     pub fn display_name(&self) -> &'static str {
@@ -194,23 +220,35 @@ impl Permission {
     }
 
     /// Get which PII fields this permission might access.
+    ///
+    /// Fields that need an explicit opt-in, such as the SSN, are left out;
+    /// see [`pii_access_with_opt_in`](Self::pii_access_with_opt_in).
     #[must_use]
     pub fn pii_access(&self) -> Vec<PiiField> {
+        self.pii_access_with_opt_in(&[])
+    }
+
+    /// Get which PII fields this permission might access once the user has
+    /// opted in to sharing the fields in `opted_in`.
+    ///
+    /// Opting in only adds fields this permission could use; it never gives
+    /// PII access to a permission that has none.
+    #[must_use]
+    pub fn pii_access_with_opt_in(&self, opted_in: &[PiiField]) -> Vec<PiiField> {
         match self {
-            // These can access all PII
+            // These can access all PII; list the sensitive fields
             Self::ScanBrokers
             | Self::SubmitRemovalForms
             | Self::SendEmails
             | Self::UseLlmCloud
             | Self::UseLlmLocal
             | Self::LlmGuidedBrowsing
-            | Self::AutoSubmitRemovals => vec![
-                PiiField::FullName,
-                PiiField::Email,
-                PiiField::Phone,
-                PiiField::Address,
-                PiiField::DateOfBirth,
-            ],
+            | Self::AutoSubmitRemovals => PiiField::all()
+                .iter()
+                .copied()
+                .filter(|field| field.sensitivity_level() >= 2)
+                .filter(|field| !field.requires_opt_in() || opted_in.contains(field))
+                .collect(),
 
             // Local scanning can discover PII
             Self::ScanFilesystem | Self::ScanBrowserData | Self::ScanEmails => vec![
@@ -269,17 +307,24 @@ impl PermissionGrant {
     /// Check if this grant has expired.
     #[must_use]
     pub fn is_expired(&self) -> bool {
-        if let Some(expires_at) = self.expires_at {
-            Utc::now() > expires_at
-        } else {
-            false
-        }
+        self.is_expired_at(Utc::now())
+    }
+
+    /// Check if this grant had expired at `now`.
+    #[must_use]
+    pub fn is_expired_at(&self, now: DateTime<Utc>) -> bool {
+        self.expires_at.is_some_and(|expires_at| now > expires_at)
     }
 
     /// Record that this permission was used.
     pub fn record_use(&mut self) {
+        self.record_use_at(Utc::now());
+    }
+
+    /// Record that this permission was used at `now`.
+    pub fn record_use_at(&mut self, now: DateTime<Utc>) {
         self.use_count += 1;
-        self.last_used = Some(Utc::now());
+        self.last_used = Some(now);
     }
 }
 
@@ -327,6 +372,26 @@ mod tests {
         assert!(no_pii.is_empty());
     }
 
+    #[test]
+    fn test_permission_pii_access_needs_opt_in_for_ssn_and_photo() {
+        let pii = Permission::SendEmails.pii_access();
+        assert!(pii.contains(&PiiField::DateOfBirth));
+        assert!(!pii.contains(&PiiField::Ssn));
+        assert!(!pii.contains(&PiiField::Photo));
+
+        let pii = Permission::SendEmails.pii_access_with_opt_in(&[PiiField::Ssn]);
+        assert!(pii.contains(&PiiField::Ssn));
+        assert!(!pii.contains(&PiiField::Photo));
+
+        // Opting in does not widen a permission without PII access
+        assert!(Permission::NetworkAccess
+            .pii_access_with_opt_in(&[PiiField::Ssn, PiiField::Photo])
+            .is_empty());
+        assert!(!Permission::ScanFilesystem
+            .pii_access_with_opt_in(&[PiiField::Ssn])
+            .contains(&PiiField::Ssn));
+    }
+
     #[test]
     fn test_permission_grant_expiry() {
         let mut grant = PermissionGrant::new(Permission::ScanBrokers, GrantSource::UserExplicit);
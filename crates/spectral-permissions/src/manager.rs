@@ -4,10 +4,15 @@ use crate::{
     audit::{AuditLogger, AuditOutcome},
     presets::PermissionPreset,
     prompts::PermissionPrompt,
+    snapshot::{permission_from_name, permission_name, PermissionSnapshot, SnapshotGrant},
     GrantSource, Permission, PermissionError, PermissionGrant, Result,
 };
+use chrono::Duration;
+use spectral_core::{SharedClock, SystemClock};
 use std::collections::{HashMap, HashSet};
-use std::sync::{Arc, RwLock};
+use std::future::Future;
+use std::sync::{Arc, Mutex, RwLock};
+use tokio::sync::OnceCell;
 use tracing::{debug, info, warn};
 
 /// Manages permission grants and handles permission checks.
@@ -20,8 +25,13 @@ pub struct PermissionManager {
     grants: Arc<RwLock<HashMap<Permission, PermissionGrant>>>,
     denials: Arc<RwLock<HashSet<Permission>>>,
     audit_logger: Arc<RwLock<AuditLogger>>,
+    clock: SharedClock,
+    pending_prompts: Arc<Mutex<HashMap<Permission, PendingPrompt>>>,
 }
 
+/// Decision for a prompt that is on screen, shared by everyone waiting on it.
+type PendingPrompt = Arc<OnceCell<PermissionDecision>>;
+
 impl PermissionManager {
     /// Create a new permission manager with no permissions granted.
     #[must_use]
@@ -30,12 +40,21 @@ impl PermissionManager {
             grants: Arc::new(RwLock::new(HashMap::new())),
             denials: Arc::new(RwLock::new(HashSet::new())),
             audit_logger: Arc::new(RwLock::new(AuditLogger::new())),
+            clock: SystemClock::shared(),
+            pending_prompts: Arc::new(Mutex::new(HashMap::new())),
         }
     }
 
+    /// Use `clock` to timestamp grants and decide when they expire.
+    #[must_use]
+    pub fn with_clock(mut self, clock: SharedClock) -> Self {
+        self.clock = clock;
+        self
+    }
+
     /// Create a new permission manager initialized with a preset.
     #[must_use]
-    pub fn new_with_preset(preset: PermissionPreset) -> Self {
+    pub fn new_with_preset(preset: &PermissionPreset) -> Self {
         let manager = Self::new();
         manager.apply_preset(preset, GrantSource::FirstRunWizard);
         manager
@@ -49,7 +68,7 @@ impl PermissionManager {
         let grants = self.grants.read().expect("grants lock poisoned");
 
         if let Some(grant) = grants.get(&permission) {
-            !grant.is_expired()
+            !grant.is_expired_at(self.clock.now())
         } else {
             false
         }
@@ -89,13 +108,13 @@ impl PermissionManager {
         let mut grants = self.grants.write().expect("grants lock poisoned");
 
         if let Some(grant) = grants.get_mut(&permission) {
-            if grant.is_expired() {
+            if grant.is_expired_at(self.clock.now()) {
                 // Grant expired, remove it
                 debug!(permission = %permission.display_name(), "permission grant expired");
                 grants.remove(&permission);
             } else {
                 // Grant is valid, record usage
-                grant.record_use();
+                grant.record_use_at(self.clock.now());
                 info!(
                     permission = %permission.display_name(),
                     use_count = grant.use_count,
@@ -121,11 +140,118 @@ impl PermissionManager {
         )))
     }
 
+    /// Record one use of a granted permission without a permission check.
+    ///
+    /// The count is updated under the grants lock, so concurrent calls are
+    /// never lost.
+    /// Returns `false` if the permission is not granted or has expired.
+    #[must_use]
+    pub fn record_use(&self, permission: Permission) -> bool {
+        let mut grants = self.grants.write().expect("grants lock poisoned");
+        match grants.get_mut(&permission) {
+            Some(grant) if !grant.is_expired_at(self.clock.now()) => {
+                grant.record_use_at(self.clock.now());
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Request a permission, asking the user if it has not been decided yet.
+    ///
+    /// `prompt` is called with a [`PermissionPrompt`] when the permission is
+    /// neither granted nor denied. Concurrent requests for the same
+    /// permission share one prompt: only the first caller shows it, and
+    /// every waiter gets that prompt's decision. `Allow` grants the
+    /// permission, `Deny` denies it, and `AllowOnce` lets the waiting
+    /// requests through without remembering anything.
+    ///
+    /// If the caller showing the prompt is cancelled before the user
+    /// answers, the next waiter prompts instead.
+    ///
+    /// # Errors
+    /// Returns `PermissionError::Denied` if the permission is, or gets, denied.
+    pub async fn request_with_prompt<F, Fut>(&self, permission: Permission, prompt: F) -> Result<()>
+    where
+        F: FnOnce(PermissionPrompt) -> Fut,
+        Fut: Future<Output = PermissionDecision>,
+    {
+        if self.is_granted(permission) || self.is_denied(permission) {
+            return self.request(permission);
+        }
+
+        let pending = Arc::clone(
+            self.pending_prompts
+                .lock()
+                .expect("pending prompts lock poisoned")
+                .entry(permission)
+                .or_default(),
+        );
+
+        let decision = *pending
+            .get_or_init(|| async {
+                debug!(permission = %permission.display_name(), "prompting for permission");
+                let decision = prompt(self.create_prompt(permission)).await;
+                match decision {
+                    PermissionDecision::Allow => self.grant(permission, GrantSource::UserExplicit),
+                    PermissionDecision::Deny => self.deny(permission),
+                    PermissionDecision::AllowOnce => {}
+                }
+                decision
+            })
+            .await;
+
+        // Later requests go through the grant or denial just recorded; only
+        // clear the entry if nobody has replaced it with a newer prompt.
+        {
+            let mut pending_prompts = self
+                .pending_prompts
+                .lock()
+                .expect("pending prompts lock poisoned");
+            if pending_prompts
+                .get(&permission)
+                .is_some_and(|current| Arc::ptr_eq(current, &pending))
+            {
+                pending_prompts.remove(&permission);
+            }
+        }
+
+        match decision {
+            PermissionDecision::AllowOnce => {
+                self.audit_logger
+                    .write()
+                    .expect("audit logger lock poisoned")
+                    .log_permission_check(permission, &AuditOutcome::Allowed);
+                Ok(())
+            }
+            PermissionDecision::Allow | PermissionDecision::Deny => self.request(permission),
+        }
+    }
+
     /// Grant a permission.
     pub fn grant(&self, permission: Permission, source: GrantSource) {
+        self.insert_grant(permission, source, None);
+    }
+
+    /// Grant a permission that expires after `duration`.
+    ///
+    /// Once expired, checks treat the permission as not granted and the
+    /// user has to grant it again.
+    pub fn grant_for(&self, permission: Permission, source: GrantSource, duration: Duration) {
+        self.insert_grant(permission, source, Some(duration));
+    }
+
+    fn insert_grant(
+        &self,
+        permission: Permission,
+        source: GrantSource,
+        duration: Option<Duration>,
+    ) {
         info!(permission = %permission.display_name(), ?source, "granting permission");
 
-        let grant = PermissionGrant::new(permission, source);
+        let mut grant = PermissionGrant::new(permission, source);
+        grant.granted_at = self.clock.now();
+        grant.expires_at = duration.map(|duration| grant.granted_at + duration);
         self.grants
             .write()
             .expect("grants lock poisoned")
@@ -186,7 +312,7 @@ impl PermissionManager {
     }
 
     /// Apply a permission preset.
-    pub fn apply_preset(&self, preset: PermissionPreset, source: GrantSource) {
+    pub fn apply_preset(&self, preset: &PermissionPreset, source: GrantSource) {
         info!(?preset, ?source, "applying permission preset");
 
         let permissions = preset.permissions();
@@ -201,7 +327,7 @@ impl PermissionManager {
         let grants = self.grants.read().expect("grants lock poisoned");
         grants
             .iter()
-            .filter(|(_, grant)| !grant.is_expired())
+            .filter(|(_, grant)| !grant.is_expired_at(self.clock.now()))
             .map(|(perm, _)| *perm)
             .collect()
     }
@@ -213,6 +339,85 @@ impl PermissionManager {
         denials.iter().copied().collect()
     }
 
+    /// Take a serializable snapshot of all grants and denials.
+    #[must_use]
+    pub fn snapshot(&self) -> PermissionSnapshot {
+        let grants = self.grants.read().expect("grants lock poisoned");
+        let denials = self.denials.read().expect("denials lock poisoned");
+
+        let mut snapshot = PermissionSnapshot {
+            taken_at: self.clock.now(),
+            grants: grants.values().map(SnapshotGrant::from).collect(),
+            denials: denials.iter().copied().map(permission_name).collect(),
+        };
+        snapshot
+            .grants
+            .sort_by(|a, b| a.permission.cmp(&b.permission));
+        snapshot.denials.sort();
+        snapshot
+    }
+
+    /// Replace all grants and denials with those from a snapshot.
+    ///
+    /// Permissions this build does not know (e.g. from a snapshot taken by a
+    /// newer version) are skipped with a warning. Returns their names.
+    pub fn restore(&self, snapshot: &PermissionSnapshot) -> Vec<String> {
+        info!(
+            grants = snapshot.grants.len(),
+            denials = snapshot.denials.len(),
+            "restoring permission snapshot"
+        );
+
+        let mut unknown = Vec::new();
+
+        let mut restored_grants = HashMap::new();
+        for entry in &snapshot.grants {
+            match entry.to_grant() {
+                Some(grant) => {
+                    restored_grants.insert(grant.permission, grant);
+                }
+                None => unknown.push(entry.permission.clone()),
+            }
+        }
+
+        let mut restored_denials = HashSet::new();
+        for name in &snapshot.denials {
+            match permission_from_name(name) {
+                Some(permission) => {
+                    restored_denials.insert(permission);
+                }
+                None => unknown.push(name.clone()),
+            }
+        }
+
+        for name in &unknown {
+            warn!(permission = %name, "skipping unknown permission in snapshot");
+        }
+
+        let granted: Vec<_> = restored_grants
+            .values()
+            .map(|grant| (grant.permission, grant.granted_by))
+            .collect();
+        let denied: Vec<_> = restored_denials.iter().copied().collect();
+
+        *self.grants.write().expect("grants lock poisoned") = restored_grants;
+        *self.denials.write().expect("denials lock poisoned") = restored_denials;
+
+        let mut audit = self
+            .audit_logger
+            .write()
+            .expect("audit logger lock poisoned");
+        for (permission, source) in granted {
+            audit.log_permission_granted(permission, source);
+        }
+        for permission in denied {
+            audit.log_permission_denied(permission);
+        }
+        drop(audit);
+
+        unknown
+    }
+
     /// Create a permission prompt for user interaction.
     #[must_use]
     pub fn create_prompt(&self, permission: Permission) -> PermissionPrompt {
@@ -276,6 +481,7 @@ pub enum PermissionDecision {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use spectral_core::MockClock;
 
     #[test]
     fn test_manager_new() {
@@ -324,6 +530,26 @@ mod tests {
         assert!(!manager.is_denied(Permission::ScanBrokers));
     }
 
+    #[test]
+    fn test_timed_grant_expires_when_clock_advances() {
+        let clock = Arc::new(MockClock::default());
+        let manager = PermissionManager::new().with_clock(clock.clone());
+        manager.grant_for(
+            Permission::ScanBrowserData,
+            GrantSource::UserExplicit,
+            Duration::hours(1),
+        );
+
+        clock.advance(Duration::minutes(59));
+        assert!(manager.is_granted(Permission::ScanBrowserData));
+        assert!(manager.request(Permission::ScanBrowserData).is_ok());
+
+        clock.advance(Duration::minutes(2));
+        assert!(!manager.is_granted(Permission::ScanBrowserData));
+        assert!(manager.request(Permission::ScanBrowserData).is_err());
+        assert!(manager.granted_permissions().is_empty());
+    }
+
     #[test]
     fn test_usage_tracking() {
         let manager = PermissionManager::new();
@@ -351,7 +577,7 @@ mod tests {
     #[test]
     fn test_preset_application() {
         let manager = PermissionManager::new();
-        manager.apply_preset(PermissionPreset::Minimal, GrantSource::FirstRunWizard);
+        manager.apply_preset(&PermissionPreset::Minimal, GrantSource::FirstRunWizard);
 
         let granted = manager.granted_permissions();
         assert!(!granted.is_empty());
@@ -373,4 +599,188 @@ mod tests {
         assert_eq!(denied.len(), 1);
         assert!(denied.contains(&Permission::UseLlmCloud));
     }
+
+    #[test]
+    fn test_snapshot_restore_round_trip() {
+        let manager = PermissionManager::new();
+        manager.grant(Permission::ScanBrokers, GrantSource::UserExplicit);
+        manager.grant(Permission::UseLlmLocal, GrantSource::Settings);
+        manager.deny(Permission::UseLlmCloud);
+        manager
+            .request(Permission::ScanBrokers)
+            .expect("should be granted");
+
+        let snapshot = manager.snapshot();
+        let json = serde_json::to_string(&snapshot).expect("serialize");
+        let snapshot: PermissionSnapshot = serde_json::from_str(&json).expect("deserialize");
+
+        let restored = PermissionManager::new_with_preset(&PermissionPreset::Maximum);
+        let unknown = restored.restore(&snapshot);
+        assert!(unknown.is_empty());
+
+        let mut expected = manager.granted_permissions();
+        let mut granted = restored.granted_permissions();
+        expected.sort_by_key(|p| permission_name(*p));
+        granted.sort_by_key(|p| permission_name(*p));
+        assert_eq!(granted, expected);
+        assert_eq!(restored.denied_permissions(), vec![Permission::UseLlmCloud]);
+
+        let stats = restored
+            .get_usage_stats(Permission::ScanBrokers)
+            .expect("should have stats");
+        assert_eq!(stats.use_count, 1);
+        assert_eq!(stats.granted_by, GrantSource::UserExplicit);
+
+        let mut resnapshot = restored.snapshot();
+        resnapshot.taken_at = snapshot.taken_at;
+        assert_eq!(resnapshot, snapshot);
+    }
+
+    #[test]
+    fn test_restore_skips_unknown_permissions() {
+        let source = PermissionManager::new();
+        source.grant(Permission::ScanBrokers, GrantSource::Settings);
+        let mut snapshot = source.snapshot();
+
+        let mut future_grant = snapshot.grants[0].clone();
+        future_grant.permission = "teleport_user".to_string();
+        snapshot.grants.push(future_grant);
+        snapshot.denials.push("read_minds".to_string());
+
+        let manager = PermissionManager::new();
+        manager.grant(Permission::SendEmails, GrantSource::UserExplicit);
+        let unknown = manager.restore(&snapshot);
+
+        assert_eq!(unknown, vec!["teleport_user", "read_minds"]);
+        assert_eq!(manager.granted_permissions(), vec![Permission::ScanBrokers]);
+        assert!(!manager.is_granted(Permission::SendEmails));
+        assert!(manager.denied_permissions().is_empty());
+    }
+
+    /// Run two concurrent requests for `permission` against a prompt that
+    /// answers `decision` once both are waiting. Returns both results and
+    /// how many prompts were shown.
+    async fn concurrent_requests(
+        manager: &PermissionManager,
+        permission: Permission,
+        decision: PermissionDecision,
+    ) -> (Result<()>, Result<()>, usize) {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+        use tokio::sync::Notify;
+
+        let prompts = AtomicUsize::new(0);
+        let answer = Notify::new();
+        let prompt = |shown: PermissionPrompt| {
+            assert_eq!(shown.permission, permission);
+            prompts.fetch_add(1, Ordering::SeqCst);
+            async {
+                answer.notified().await;
+                decision
+            }
+        };
+
+        let (first, second, ()) = tokio::join!(
+            manager.request_with_prompt(permission, prompt),
+            manager.request_with_prompt(permission, prompt),
+            async {
+                // Let both requests reach the prompt before the user answers.
+                for _ in 0..10 {
+                    tokio::task::yield_now().await;
+                }
+                answer.notify_one();
+            }
+        );
+
+        (first, second, prompts.load(Ordering::SeqCst))
+    }
+
+    #[tokio::test]
+    async fn test_concurrent_requests_share_granted_prompt() {
+        let manager = PermissionManager::new();
+
+        let (first, second, prompts) =
+            concurrent_requests(&manager, Permission::SendEmails, PermissionDecision::Allow).await;
+
+        assert_eq!(prompts, 1);
+        assert!(first.is_ok());
+        assert!(second.is_ok());
+        assert!(manager.is_granted(Permission::SendEmails));
+        assert_eq!(
+            manager
+                .get_usage_stats(Permission::SendEmails)
+                .expect("should have stats")
+                .use_count,
+            2
+        );
+    }
+
+    #[tokio::test]
+    async fn test_concurrent_requests_share_denied_prompt() {
+        let manager = PermissionManager::new();
+
+        let (first, second, prompts) =
+            concurrent_requests(&manager, Permission::SendEmails, PermissionDecision::Deny).await;
+
+        assert_eq!(prompts, 1);
+        assert!(matches!(first, Err(PermissionError::Denied(_))));
+        assert!(matches!(second, Err(PermissionError::Denied(_))));
+        assert!(manager.is_denied(Permission::SendEmails));
+
+        // The denial is remembered, so later requests don't prompt again.
+        let result = manager
+            .request_with_prompt(Permission::SendEmails, |_| async {
+                panic!("denied permission should not prompt")
+            })
+            .await;
+        assert!(result.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_allow_once_is_not_remembered() {
+        let manager = PermissionManager::new();
+
+        let (first, second, prompts) = concurrent_requests(
+            &manager,
+            Permission::SendEmails,
+            PermissionDecision::AllowOnce,
+        )
+        .await;
+
+        assert_eq!(prompts, 1);
+        assert!(first.is_ok());
+        assert!(second.is_ok());
+        assert!(!manager.is_granted(Permission::SendEmails));
+        assert!(!manager.is_denied(Permission::SendEmails));
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+    async fn test_concurrent_record_use_loses_no_counts() {
+        const TASKS: usize = 16;
+        const USES_PER_TASK: usize = 50;
+
+        let manager = PermissionManager::new();
+        manager.grant(Permission::UseLlmCloud, GrantSource::UserExplicit);
+
+        let tasks: Vec<_> = (0..TASKS)
+            .map(|_| {
+                let manager = manager.clone();
+                tokio::spawn(async move {
+                    for _ in 0..USES_PER_TASK {
+                        assert!(manager.record_use(Permission::UseLlmCloud));
+                        tokio::task::yield_now().await;
+                    }
+                })
+            })
+            .collect();
+        for task in tasks {
+            task.await.expect("task panicked");
+        }
+
+        let stats = manager
+            .get_usage_stats(Permission::UseLlmCloud)
+            .expect("should have stats");
+        assert_eq!(stats.use_count, (TASKS * USES_PER_TASK) as u64);
+        assert!(stats.last_used.is_some());
+        assert!(!manager.record_use(Permission::SendEmails));
+    }
 }
@@ -1,14 +1,15 @@
 //! Permission presets for quick configuration.
 
-use crate::Permission;
+use crate::{Permission, PermissionManager};
 use serde::{Deserialize, Serialize};
 
 /// Pre-configured permission profiles for different user preferences.
 ///
 /// These presets provide sensible defaults for different privacy/automation
 /// trade-offs, making it easy for users to configure permissions during
-/// first-run setup.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+/// first-run setup. The task-oriented presets grant just what one kind of use
+/// needs, and [`PermissionPreset::Custom`] captures any other grant set.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
 pub enum PermissionPreset {
     /// Minimal permissions - manual everything, no automation.
@@ -40,6 +41,39 @@ pub enum PermissionPreset {
     /// - All local scanning enabled
     /// - Full automation
     Maximum,
+
+    /// Broker scanning only.
+    ///
+    /// Best for: Users who want to see their exposure before acting on it
+    /// - Broker scanning and network access
+    /// - No removal submission or emails
+    /// - No LLM usage
+    /// - No local scanning
+    /// - No automation
+    ScanOnly,
+
+    /// Local scanning only - nothing leaves the machine.
+    ///
+    /// Best for: Auditing local PII exposure offline
+    /// - Filesystem, browser data and email scanning
+    /// - Local LLM allowed
+    /// - No network access
+    /// - No automation
+    LocalOnly,
+
+    /// Hands-off broker scanning and removal.
+    ///
+    /// Best for: Users who want removals handled without prompts but no
+    /// data sent to cloud LLMs
+    /// - Automatic broker scanning
+    /// - Automatic removal submission, including by email
+    /// - Local LLM allowed
+    /// - No cloud LLM
+    /// - No local scanning
+    FullAuto,
+
+    /// A user-chosen permission set, e.g. from [`PermissionPreset::from_current`].
+    Custom(Vec<Permission>),
 }
 
 impl PermissionPreset {
@@ -50,6 +84,10 @@ impl PermissionPreset {
             Self::Minimal => "Minimal",
             Self::Balanced => "Balanced",
             Self::Maximum => "Maximum",
+            Self::ScanOnly => "Scan Only",
+            Self::LocalOnly => "Local Only",
+            Self::FullAuto => "Full Auto",
+            Self::Custom(_) => "Custom",
         }
     }
 
@@ -60,6 +98,10 @@ impl PermissionPreset {
             Self::Minimal => "Manual control over everything. No automation, no LLM, maximum privacy.",
             Self::Balanced => "Automated scanning with manual removal. Local LLM only. Good balance of privacy and convenience.",
             Self::Maximum => "Fully automated. Cloud LLM enabled. Maximum convenience with reasonable privacy protections.",
+            Self::ScanOnly => "Scan data brokers for your information. No removals, no LLM, no automation.",
+            Self::LocalOnly => "Scan files, browsers and email on this machine. No network access, local LLM only.",
+            Self::FullAuto => "Automated scanning and removal, including by email. Local LLM only, no local scanning.",
+            Self::Custom(_) => "Your own selection of permissions.",
         }
     }
 
@@ -108,19 +150,75 @@ impl PermissionPreset {
                 Permission::AutoSubmitRemovals,
                 Permission::BackgroundExecution,
             ],
+
+            Self::ScanOnly => vec![Permission::ScanBrokers, Permission::NetworkAccess],
+
+            Self::LocalOnly => vec![
+                Permission::UseLlmLocal,
+                Permission::ScanFilesystem,
+                Permission::ScanBrowserData,
+                Permission::ScanEmails,
+            ],
+
+            Self::FullAuto => vec![
+                // Scanning and removal over the network
+                Permission::ScanBrokers,
+                Permission::SubmitRemovalForms,
+                Permission::SendEmails,
+                Permission::NetworkAccess,
+                // Local LLM only
+                Permission::UseLlmLocal,
+                Permission::LlmGuidedBrowsing,
+                // Full automation
+                Permission::AutoScheduleScans,
+                Permission::AutoSubmitRemovals,
+                Permission::BackgroundExecution,
+            ],
+
+            Self::Custom(permissions) => permissions.clone(),
         }
     }
 
+    /// Capture the permissions `manager` currently grants as a preset.
+    ///
+    /// Returns the named preset with exactly those permissions if there is
+    /// one, otherwise [`PermissionPreset::Custom`] listing them in
+    /// [`Permission::all`] order. Expired grants are not included.
+    #[must_use]
+    pub fn from_current(manager: &PermissionManager) -> Self {
+        let granted = manager.granted_permissions();
+        let permissions: Vec<Permission> = Permission::all()
+            .into_iter()
+            .filter(|permission| granted.contains(permission))
+            .collect();
+
+        Self::all()
+            .into_iter()
+            .find(|preset| {
+                let preset_permissions = preset.permissions();
+                preset_permissions.len() == permissions.len()
+                    && preset_permissions.iter().all(|p| permissions.contains(p))
+            })
+            .unwrap_or(Self::Custom(permissions))
+    }
+
     /// Get the recommended preset for most users.
     #[must_use]
     pub fn recommended() -> Self {
         Self::Balanced
     }
 
-    /// Get all available presets.
+    /// Get all named presets.
     #[must_use]
     pub fn all() -> Vec<Self> {
-        vec![Self::Minimal, Self::Balanced, Self::Maximum]
+        vec![
+            Self::Minimal,
+            Self::Balanced,
+            Self::Maximum,
+            Self::ScanOnly,
+            Self::LocalOnly,
+            Self::FullAuto,
+        ]
     }
 }
 
@@ -133,6 +231,7 @@ impl Default for PermissionPreset {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::GrantSource;
 
     #[test]
     fn test_preset_permissions() {
@@ -172,10 +271,13 @@ mod tests {
     #[test]
     fn test_preset_all() {
         let all = PermissionPreset::all();
-        assert_eq!(all.len(), 3);
+        assert_eq!(all.len(), 6);
         assert!(all.contains(&PermissionPreset::Minimal));
         assert!(all.contains(&PermissionPreset::Balanced));
         assert!(all.contains(&PermissionPreset::Maximum));
+        assert!(all.contains(&PermissionPreset::ScanOnly));
+        assert!(all.contains(&PermissionPreset::LocalOnly));
+        assert!(all.contains(&PermissionPreset::FullAuto));
     }
 
     #[test]
@@ -210,4 +312,85 @@ mod tests {
         assert!(permissions.contains(&Permission::AutoSubmitRemovals));
         assert!(permissions.contains(&Permission::BackgroundExecution));
     }
+
+    fn sorted(mut permissions: Vec<Permission>) -> Vec<&'static str> {
+        permissions.sort_by_key(Permission::display_name);
+        permissions.iter().map(Permission::display_name).collect()
+    }
+
+    #[test]
+    fn test_task_presets_grant_exactly_their_permissions() {
+        assert_eq!(
+            sorted(PermissionPreset::ScanOnly.permissions()),
+            sorted(vec![Permission::ScanBrokers, Permission::NetworkAccess])
+        );
+        assert_eq!(
+            sorted(PermissionPreset::LocalOnly.permissions()),
+            sorted(vec![
+                Permission::UseLlmLocal,
+                Permission::ScanFilesystem,
+                Permission::ScanBrowserData,
+                Permission::ScanEmails,
+            ])
+        );
+        assert_eq!(
+            sorted(PermissionPreset::FullAuto.permissions()),
+            sorted(vec![
+                Permission::ScanBrokers,
+                Permission::SubmitRemovalForms,
+                Permission::SendEmails,
+                Permission::NetworkAccess,
+                Permission::UseLlmLocal,
+                Permission::LlmGuidedBrowsing,
+                Permission::AutoScheduleScans,
+                Permission::AutoSubmitRemovals,
+                Permission::BackgroundExecution,
+            ])
+        );
+    }
+
+    #[test]
+    fn test_named_presets_are_distinct() {
+        let all = PermissionPreset::all();
+        for (i, a) in all.iter().enumerate() {
+            for b in &all[i + 1..] {
+                assert_ne!(
+                    sorted(a.permissions()),
+                    sorted(b.permissions()),
+                    "{} and {} grant the same permissions",
+                    a.display_name(),
+                    b.display_name()
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_from_current_matches_named_preset() {
+        for preset in PermissionPreset::all() {
+            let manager = PermissionManager::new_with_preset(&preset);
+            assert_eq!(PermissionPreset::from_current(&manager), preset);
+        }
+    }
+
+    #[test]
+    fn test_from_current_captures_custom_grants() {
+        let manager = PermissionManager::new_with_preset(&PermissionPreset::ScanOnly);
+        manager.grant(Permission::UseLlmLocal, GrantSource::UserExplicit);
+        manager.revoke(Permission::NetworkAccess);
+
+        let preset = PermissionPreset::from_current(&manager);
+        assert_eq!(
+            preset,
+            PermissionPreset::Custom(vec![Permission::ScanBrokers, Permission::UseLlmLocal])
+        );
+        assert_eq!(preset.display_name(), "Custom");
+
+        // Applying the captured preset reproduces the grants
+        let restored = PermissionManager::new_with_preset(&preset);
+        assert_eq!(PermissionPreset::from_current(&restored), preset);
+
+        let json = serde_json::to_string(&preset).expect("serialize preset");
+        assert_eq!(json, r#"{"custom":["scan_brokers","use_llm_local"]}"#);
+    }
 }
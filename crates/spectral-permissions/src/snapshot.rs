@@ -0,0 +1,123 @@
+//! Serializable snapshots of a permission manager's grants and denials.
+//!
+//! Snapshots let users back up and restore their permission settings, for
+//! example as part of an encrypted vault export. Permissions are stored by
+//! name rather than as [`Permission`] values, so a snapshot taken by a newer
+//! build still deserializes here; names this build does not know are skipped
+//! on restore.
+
+use crate::{GrantSource, Permission, PermissionGrant};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Point-in-time copy of all permission grants and denials.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PermissionSnapshot {
+    /// When the snapshot was taken
+    pub taken_at: DateTime<Utc>,
+
+    /// Granted permissions with their metadata
+    pub grants: Vec<SnapshotGrant>,
+
+    /// Names of explicitly denied permissions
+    pub denials: Vec<String>,
+}
+
+/// A permission grant as stored in a snapshot.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SnapshotGrant {
+    /// Serialized permission name (e.g. `scan_brokers`)
+    pub permission: String,
+
+    /// When the permission was granted
+    pub granted_at: DateTime<Utc>,
+
+    /// Who granted the permission
+    pub granted_by: GrantSource,
+
+    /// Optional expiration time
+    pub expires_at: Option<DateTime<Utc>>,
+
+    /// How many times the permission has been used
+    pub use_count: u64,
+
+    /// When the permission was last used
+    pub last_used: Option<DateTime<Utc>>,
+}
+
+impl SnapshotGrant {
+    /// Convert back into a grant, or `None` if the permission is unknown.
+    pub(crate) fn to_grant(&self) -> Option<PermissionGrant> {
+        let permission = permission_from_name(&self.permission)?;
+        Some(PermissionGrant {
+            granted_at: self.granted_at,
+            granted_by: self.granted_by,
+            expires_at: self.expires_at,
+            use_count: self.use_count,
+            last_used: self.last_used,
+            ..PermissionGrant::new(permission, self.granted_by)
+        })
+    }
+}
+
+impl From<&PermissionGrant> for SnapshotGrant {
+    fn from(grant: &PermissionGrant) -> Self {
+        Self {
+            permission: permission_name(grant.permission),
+            granted_at: grant.granted_at,
+            granted_by: grant.granted_by,
+            expires_at: grant.expires_at,
+            use_count: grant.use_count,
+            last_used: grant.last_used,
+        }
+    }
+}
+
+/// Serialized name of a permission.
+pub(crate) fn permission_name(permission: Permission) -> String {
+    match serde_json::to_value(permission) {
+        Ok(serde_json::Value::String(name)) => name,
+        _ => unreachable!("permissions serialize as strings"),
+    }
+}
+
+/// Parse a serialized permission name, returning `None` for names this
+/// build does not know.
+pub(crate) fn permission_from_name(name: &str) -> Option<Permission> {
+    serde_json::from_value(serde_json::Value::String(name.to_string())).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_permission_name_round_trip() {
+        assert_eq!(permission_name(Permission::ScanBrokers), "scan_brokers");
+        assert_eq!(
+            permission_from_name("scan_brokers"),
+            Some(Permission::ScanBrokers)
+        );
+        assert_eq!(permission_from_name("teleport_user"), None);
+    }
+
+    #[test]
+    fn test_snapshot_with_unknown_permission_deserializes() {
+        let json = r#"{
+            "taken_at": "2026-01-01T00:00:00Z",
+            "grants": [{
+                "permission": "teleport_user",
+                "granted_at": "2026-01-01T00:00:00Z",
+                "granted_by": "settings",
+                "expires_at": null,
+                "use_count": 0,
+                "last_used": null
+            }],
+            "denials": ["scan_emails"]
+        }"#;
+
+        let snapshot: PermissionSnapshot = serde_json::from_str(json).expect("deserialize");
+        assert_eq!(snapshot.grants.len(), 1);
+        assert!(snapshot.grants[0].to_grant().is_none());
+    }
+}
@@ -1,21 +1,61 @@
 use crate::error::Result;
-use crate::types::{Feature, FeatureFlags, PermissionResult, PrivacyLevel};
+use crate::types::{Feature, FeatureFlags, FeatureOverride, PermissionResult, PrivacyLevel};
+use chrono::Duration;
+use spectral_core::{SharedClock, SystemClock};
+use spectral_permissions::PermissionManager;
 use sqlx::SqlitePool;
 
+/// Settings key holding the temporary feature overrides.
+const OVERRIDES_KEY: &str = "feature_overrides";
+
 /// Central orchestrator for all privacy-related decisions.
 ///
 /// The `PrivacyEngine` is the single source of truth for privacy settings,
 /// managing privacy levels, feature flags, and permission checks.
+///
+/// Whether a feature is allowed is decided in three layers, each applied
+/// on top of the one before:
+///
+/// 1. The privacy level sets the baseline: a preset's flags, or the stored
+///    custom flags for [`PrivacyLevel::Custom`].
+/// 2. Unexpired temporary overrides replace the baseline for their feature,
+///    enabling or disabling it whatever the level says.
+/// 3. With a [`PermissionManager`] attached, a feature stays enabled only
+///    while its [`Feature::permission`] is granted. Grants never enable a
+///    feature the first two layers disable.
+///
+/// [`effective_flags`](Self::effective_flags) returns the merged result and
+/// [`check_permission`](Self::check_permission) answers for one feature.
 #[derive(Debug, Clone)]
 pub struct PrivacyEngine {
     pool: SqlitePool,
+    permissions: Option<PermissionManager>,
+    clock: SharedClock,
 }
 
 impl PrivacyEngine {
     /// Create a new privacy engine with the given database pool.
     #[must_use]
     pub fn new(pool: SqlitePool) -> Self {
-        Self { pool }
+        Self {
+            pool,
+            permissions: None,
+            clock: SystemClock::shared(),
+        }
+    }
+
+    /// Require features to have their permission granted in `permissions`.
+    #[must_use]
+    pub fn with_permissions(mut self, permissions: PermissionManager) -> Self {
+        self.permissions = Some(permissions);
+        self
+    }
+
+    /// Use `clock` to decide when overrides expire.
+    #[must_use]
+    pub fn with_clock(mut self, clock: SharedClock) -> Self {
+        self.clock = clock;
+        self
     }
 
     /// Get the current privacy level.
@@ -48,31 +88,142 @@ impl PrivacyEngine {
 
     /// Check if a feature is allowed under the current privacy settings.
     ///
+    /// Layers are applied as described on [`PrivacyEngine`]; a denial names
+    /// the layer that disabled the feature.
+    ///
     /// # Errors
     /// Returns an error if the database query fails.
     pub async fn check_permission(&self, feature: Feature) -> Result<PermissionResult> {
-        let level = self.get_privacy_level().await?;
+        let overrides = self.feature_overrides().await?;
 
-        // For Custom level, check feature flags
-        if level == PrivacyLevel::Custom {
-            let flags = self.get_feature_flags().await?;
-            return Ok(flags.check_feature(feature));
-        }
+        let result = if let Some(over) = overrides.iter().find(|o| o.feature == feature) {
+            if over.allowed {
+                PermissionResult::Allowed
+            } else {
+                PermissionResult::Denied {
+                    reason: format!("{feature:?} is disabled by a temporary override"),
+                }
+            }
+        } else {
+            let level = self.get_privacy_level().await?;
 
-        // For predefined levels, use the level's feature flags
-        let flags = level.to_feature_flags();
-        let result = flags.check_feature(feature);
+            // For Custom level, check feature flags
+            if level == PrivacyLevel::Custom {
+                self.get_feature_flags().await?.check_feature(feature)
+            } else {
+                // For predefined levels, use the level's feature flags, naming
+                // the level in the denial reason
+                match level.to_feature_flags().check_feature(feature) {
+                    PermissionResult::Denied { reason: _ } => PermissionResult::Denied {
+                        reason: format!("Privacy level {level:?} does not allow {feature:?}"),
+                    },
+                    PermissionResult::Allowed => PermissionResult::Allowed,
+                }
+            }
+        };
 
-        // Add privacy level to denial reason for predefined levels
-        if let PermissionResult::Denied { reason: _ } = result {
+        if result.is_allowed() && !self.is_permission_granted(feature) {
             return Ok(PermissionResult::Denied {
-                reason: format!("Privacy level {level:?} does not allow {feature:?}"),
+                reason: format!(
+                    "Permission {} is not granted",
+                    feature.permission().display_name()
+                ),
             });
         }
 
         Ok(result)
     }
 
+    /// The feature flags in force, with overrides and permission grants
+    /// merged into the privacy level's flags.
+    ///
+    /// See [`PrivacyEngine`] for the order the layers are applied in.
+    ///
+    /// # Errors
+    /// Returns an error if the database query fails or a stored value is invalid.
+    pub async fn effective_flags(&self) -> Result<FeatureFlags> {
+        let level = self.get_privacy_level().await?;
+        let mut flags = if level == PrivacyLevel::Custom {
+            self.get_feature_flags().await?
+        } else {
+            level.to_feature_flags()
+        };
+
+        for over in self.feature_overrides().await? {
+            flags.set(over.feature, over.allowed);
+        }
+
+        for feature in Feature::ALL {
+            if !self.is_permission_granted(feature) {
+                flags.set(feature, false);
+            }
+        }
+
+        Ok(flags)
+    }
+
+    /// Enable or disable `feature` for `duration`, whatever the privacy
+    /// level allows, replacing any earlier override for it.
+    ///
+    /// # Errors
+    /// Returns an error if the database update fails.
+    pub async fn set_feature_override(
+        &self,
+        feature: Feature,
+        allowed: bool,
+        duration: Duration,
+    ) -> Result<()> {
+        let mut overrides = self.feature_overrides().await?;
+        overrides.retain(|o| o.feature != feature);
+        overrides.push(FeatureOverride {
+            feature,
+            allowed,
+            expires_at: self.clock.now() + duration,
+        });
+        self.save_overrides(&overrides).await
+    }
+
+    /// Remove the override for `feature`, if any.
+    ///
+    /// # Errors
+    /// Returns an error if the database update fails.
+    pub async fn clear_feature_override(&self, feature: Feature) -> Result<()> {
+        let mut overrides = self.feature_overrides().await?;
+        overrides.retain(|o| o.feature != feature);
+        self.save_overrides(&overrides).await
+    }
+
+    /// The overrides that have not yet expired.
+    ///
+    /// # Errors
+    /// Returns an error if the database query fails or the stored value is invalid.
+    pub async fn feature_overrides(&self) -> Result<Vec<FeatureOverride>> {
+        let value = spectral_db::settings::get_setting(&self.pool, OVERRIDES_KEY).await?;
+
+        let overrides: Vec<FeatureOverride> = match value {
+            Some(v) => serde_json::from_value(v)?,
+            None => Vec::new(),
+        };
+        let now = self.clock.now();
+        Ok(overrides
+            .into_iter()
+            .filter(|o| !o.is_expired_at(now))
+            .collect())
+    }
+
+    async fn save_overrides(&self, overrides: &[FeatureOverride]) -> Result<()> {
+        let value = serde_json::to_value(overrides)?;
+        spectral_db::settings::set_setting(&self.pool, OVERRIDES_KEY, &value).await?;
+        Ok(())
+    }
+
+    /// Whether the attached permission manager, if any, grants `feature`.
+    fn is_permission_granted(&self, feature: Feature) -> bool {
+        self.permissions
+            .as_ref()
+            .map_or(true, |p| p.is_granted(feature.permission()))
+    }
+
     /// Get the current feature flags.
     ///
     /// Returns default flags if none are set.
@@ -180,6 +331,87 @@ mod tests {
         assert!(result.reason().unwrap().contains("Paranoid"));
     }
 
+    #[tokio::test]
+    async fn test_effective_flags_layer_level_override_and_grants() {
+        use spectral_core::MockClock;
+        use spectral_permissions::{GrantSource, Permission};
+        use std::sync::Arc;
+
+        let pool = create_test_db().await;
+        let clock = Arc::new(MockClock::default());
+        let permissions = PermissionManager::new();
+        for permission in [
+            Permission::UseLlmLocal,
+            Permission::UseLlmCloud,
+            Permission::ScanBrokers,
+        ] {
+            permissions.grant(permission, GrantSource::UserExplicit);
+        }
+        let engine = PrivacyEngine::new(pool)
+            .with_permissions(permissions.clone())
+            .with_clock(clock.clone());
+
+        // nosemgrep: no-unwrap-in-production
+        engine
+            .set_privacy_level(PrivacyLevel::LocalPrivacy)
+            .await
+            .unwrap();
+        // nosemgrep: no-unwrap-in-production
+        engine
+            .set_feature_override(Feature::CloudLlm, true, Duration::hours(1))
+            .await
+            .unwrap();
+
+        let expected = FeatureFlags {
+            // Allowed by the level and granted
+            allow_local_llm: true,
+            // Disallowed by the level, enabled by the override, granted
+            allow_cloud_llm: true,
+            // Allowed by the level but not granted
+            allow_browser_automation: false,
+            allow_email_sending: false,
+            allow_imap_monitoring: false,
+            allow_pii_scanning: true,
+            allow_background_execution: false,
+        };
+        // nosemgrep: no-unwrap-in-production
+        assert_eq!(engine.effective_flags().await.unwrap(), expected);
+        // nosemgrep: no-unwrap-in-production
+        let email = engine
+            .check_permission(Feature::EmailSending)
+            .await
+            .unwrap();
+        assert!(email.reason().unwrap().contains("Send Emails"));
+
+        // A grant alone does not enable what an override disables
+        // nosemgrep: no-unwrap-in-production
+        engine
+            .set_feature_override(Feature::LocalLlm, false, Duration::hours(1))
+            .await
+            .unwrap();
+        // nosemgrep: no-unwrap-in-production
+        let flags = engine.effective_flags().await.unwrap();
+        assert!(!flags.allow_local_llm);
+        // nosemgrep: no-unwrap-in-production
+        let local = engine.check_permission(Feature::LocalLlm).await.unwrap();
+        assert!(local.reason().unwrap().contains("override"));
+
+        // Revoking the grant disables the overridden feature too
+        permissions.revoke(Permission::UseLlmCloud);
+        // nosemgrep: no-unwrap-in-production
+        assert!(!engine.effective_flags().await.unwrap().allow_cloud_llm);
+        permissions.grant(Permission::UseLlmCloud, GrantSource::UserExplicit);
+
+        // Once the overrides expire the level applies again
+        clock.advance(Duration::hours(2));
+        // nosemgrep: no-unwrap-in-production
+        let flags = engine.effective_flags().await.unwrap();
+        assert!(flags.allow_local_llm);
+        assert!(!flags.allow_cloud_llm);
+        // nosemgrep: no-unwrap-in-production
+        assert!(engine.feature_overrides().await.unwrap().is_empty());
+    }
+
     #[tokio::test]
     async fn test_custom_feature_flags() {
         let pool = create_test_db().await;
@@ -12,6 +12,7 @@
 //!
 //! - **Privacy Levels**: Predefined presets (Paranoid, `LocalPrivacy`, Balanced, Custom)
 //! - **Feature Flags**: Granular controls for LLM, automation, scanning, and email
+//! - **Overrides**: Temporary per-feature changes layered over the privacy level
 //! - **LLM Routing**: Privacy-aware provider selection with PII filtering
 //! - **Settings Storage**: Encrypted vault-scoped configuration in `SQLCipher` database
 //!
@@ -47,6 +48,8 @@ pub mod error;
 pub mod llm_router;
 /// LLM provider settings management.
 pub mod llm_settings;
+/// Named LLM prompt templates stored in settings.
+pub mod prompt_templates;
 /// Core types for privacy controls.
 pub mod types;
 
@@ -57,7 +60,10 @@ pub use llm_settings::{
     delete_api_key, get_api_key, get_primary_provider, get_provider_preference, set_api_key,
     set_primary_provider, set_provider_preference, LlmProvider, TaskType,
 };
-pub use types::{Feature, FeatureFlags, PermissionResult, PrivacyLevel};
+pub use prompt_templates::{
+    delete_prompt_template, get_prompt_template, save_prompt_template, SettingsPromptTemplates,
+};
+pub use types::{Feature, FeatureFlags, FeatureOverride, PermissionResult, PrivacyLevel};
 
 // Re-export commonly used LLM types for convenience
 pub use spectral_llm::{CompletionRequest, CompletionResponse};
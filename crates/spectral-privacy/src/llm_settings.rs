@@ -45,6 +45,15 @@ pub enum TaskType {
     FormFill,
 }
 
+impl TaskType {
+    /// Whether the task's output is acted on rather than shown for review,
+    /// such as values typed into a broker's form.
+    #[must_use]
+    pub fn drives_action(self) -> bool {
+        matches!(self, Self::FormFill)
+    }
+}
+
 /// Set API key for a provider.
 ///
 /// # Errors
@@ -0,0 +1,199 @@
+//! Prompt templates stored in the vault settings.
+//!
+//! Each template is kept under `llm.prompt_template.<name>`, so prompts used
+//! by LLM-guided flows can be edited without a new build.
+//! [`SettingsPromptTemplates`] serves them to
+//! [`LlmRouter::complete_template`](spectral_llm::LlmRouter::complete_template).
+
+use crate::error::Result;
+use async_trait::async_trait;
+use spectral_llm::{LlmError, PromptTemplate, PromptTemplateStore};
+use sqlx::SqlitePool;
+
+fn setting_key(name: &str) -> String {
+    format!("llm.prompt_template.{name}")
+}
+
+/// Save a prompt template, replacing any with the same name.
+///
+/// # Errors
+/// Returns error if database write fails.
+pub async fn save_prompt_template(pool: &SqlitePool, template: &PromptTemplate) -> Result<()> {
+    let value = serde_json::to_value(template)?;
+    spectral_db::settings::set_setting(pool, &setting_key(&template.name), &value).await?;
+    Ok(())
+}
+
+/// Get the prompt template called `name`.
+///
+/// # Errors
+/// Returns error if database read fails or value is malformed.
+pub async fn get_prompt_template(pool: &SqlitePool, name: &str) -> Result<Option<PromptTemplate>> {
+    let value = spectral_db::settings::get_setting(pool, &setting_key(name)).await?;
+
+    if let Some(v) = value {
+        Ok(Some(serde_json::from_value(v)?))
+    } else {
+        Ok(None)
+    }
+}
+
+/// Delete the prompt template called `name`.
+///
+/// # Errors
+/// Returns error if database write fails.
+pub async fn delete_prompt_template(pool: &SqlitePool, name: &str) -> Result<()> {
+    spectral_db::settings::delete_setting(pool, &setting_key(name)).await?;
+    Ok(())
+}
+
+/// [`PromptTemplateStore`] reading templates from the vault settings.
+#[derive(Debug, Clone)]
+pub struct SettingsPromptTemplates {
+    pool: SqlitePool,
+}
+
+impl SettingsPromptTemplates {
+    /// Read templates from the settings in `pool`.
+    #[must_use]
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl PromptTemplateStore for SettingsPromptTemplates {
+    async fn load_template(&self, name: &str) -> spectral_llm::Result<Option<PromptTemplate>> {
+        get_prompt_template(&self.pool, name)
+            .await
+            .map_err(|e| LlmError::Internal(format!("failed to load prompt template: {e}")))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use spectral_db::Database;
+    use spectral_llm::{
+        CompletionRequest, CompletionResponse, CompletionStream, LlmProvider, LlmRouter,
+        ProviderCapabilities, RoutingPreference,
+    };
+    use std::collections::HashMap;
+    use std::sync::{Arc, Mutex};
+
+    async fn create_test_db() -> SqlitePool {
+        let key = vec![0u8; 32];
+        let db = Database::new(":memory:", key)
+            .await
+            .expect("create test database");
+        db.run_migrations().await.expect("run migrations");
+        db.pool().clone()
+    }
+
+    /// Local provider that records the requests it receives.
+    #[derive(Default)]
+    struct RecordingProvider {
+        requests: Mutex<Vec<CompletionRequest>>,
+    }
+
+    #[async_trait]
+    impl LlmProvider for RecordingProvider {
+        async fn complete(
+            &self,
+            request: CompletionRequest,
+        ) -> spectral_llm::Result<CompletionResponse> {
+            self.requests.lock().expect("requests").push(request);
+            Ok(CompletionResponse {
+                content: "ok".to_string(),
+                model: "recorder".to_string(),
+                stop_reason: None,
+                usage: None,
+                provider_id: None,
+                pii_filtered: None,
+            })
+        }
+
+        async fn stream(
+            &self,
+            _request: CompletionRequest,
+        ) -> spectral_llm::Result<CompletionStream> {
+            Err(LlmError::Internal("not supported".to_string()))
+        }
+
+        fn capabilities(&self) -> ProviderCapabilities {
+            ProviderCapabilities {
+                max_context_tokens: 4096,
+                is_local: true,
+                supports_vision: false,
+                supports_tool_use: false,
+                supports_structured_output: false,
+                model_name: "recorder".to_string(),
+                cost_tier: 0,
+                available_models: Vec::new(),
+            }
+        }
+
+        fn provider_id(&self) -> &'static str {
+            "recorder"
+        }
+    }
+
+    #[tokio::test]
+    async fn test_stored_template_is_rendered_and_sent() {
+        let pool = create_test_db().await;
+        let template = PromptTemplate::new(
+            "draft_opt_out",
+            "Draft an opt-out request to {{broker}} for {{name}}.",
+        )
+        .with_system_prompt("Be brief.");
+        save_prompt_template(&pool, &template)
+            .await
+            .expect("save template");
+        assert_eq!(
+            get_prompt_template(&pool, "draft_opt_out")
+                .await
+                .expect("get template"),
+            Some(template)
+        );
+
+        let provider = Arc::new(RecordingProvider::default());
+        let mut router = LlmRouter::new(RoutingPreference::LocalOnly)
+            .with_template_store(Arc::new(SettingsPromptTemplates::new(pool.clone())));
+        router.add_provider(provider.clone());
+
+        let vars = HashMap::from([
+            ("broker".to_string(), "Spokeo".to_string()),
+            ("name".to_string(), "Jane Doe".to_string()),
+        ]);
+        router
+            .complete_template("draft_opt_out", &vars)
+            .await
+            .expect("complete template");
+
+        {
+            let requests = provider.requests.lock().expect("requests");
+            assert_eq!(requests.len(), 1);
+            assert_eq!(
+                requests[0].messages[0].content,
+                "Draft an opt-out request to Spokeo for Jane Doe."
+            );
+            assert_eq!(requests[0].system_prompt.as_deref(), Some("Be brief."));
+        }
+
+        // Missing variables fail before anything is sent
+        let err = router
+            .complete_template("draft_opt_out", &HashMap::new())
+            .await
+            .expect_err("variables missing");
+        assert!(matches!(err, LlmError::TemplateVariablesMissing { .. }));
+        assert_eq!(provider.requests.lock().expect("requests").len(), 1);
+
+        delete_prompt_template(&pool, "draft_opt_out")
+            .await
+            .expect("delete template");
+        assert!(matches!(
+            router.complete_template("draft_opt_out", &vars).await,
+            Err(LlmError::TemplateNotFound { .. })
+        ));
+    }
+}
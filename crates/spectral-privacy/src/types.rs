@@ -1,4 +1,6 @@
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use spectral_permissions::Permission;
 
 /// Privacy level presets
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
@@ -38,6 +40,14 @@ pub struct FeatureFlags {
     pub allow_imap_monitoring: bool,
     /// Allow PII scanning
     pub allow_pii_scanning: bool,
+    /// Allow scheduled jobs and their notifications; custom flags saved
+    /// before this flag existed keep it on
+    #[serde(default = "default_background_execution")]
+    pub allow_background_execution: bool,
+}
+
+fn default_background_execution() -> bool {
+    true
 }
 
 impl FeatureFlags {
@@ -52,6 +62,7 @@ impl FeatureFlags {
                 allow_email_sending: false,
                 allow_imap_monitoring: false,
                 allow_pii_scanning: false,
+                allow_background_execution: false,
             },
             PrivacyLevel::LocalPrivacy => Self {
                 allow_local_llm: true,
@@ -60,6 +71,7 @@ impl FeatureFlags {
                 allow_email_sending: true,
                 allow_imap_monitoring: true,
                 allow_pii_scanning: true,
+                allow_background_execution: true,
             },
             PrivacyLevel::Balanced => Self {
                 allow_local_llm: true,
@@ -68,24 +80,44 @@ impl FeatureFlags {
                 allow_email_sending: true,
                 allow_imap_monitoring: true,
                 allow_pii_scanning: true,
+                allow_background_execution: true,
             },
             PrivacyLevel::Custom => Self::default(),
         }
     }
 
-    /// Check if a feature is allowed under these flags
+    /// Whether `feature` is enabled in these flags
     #[must_use]
-    pub fn check_feature(&self, feature: Feature) -> PermissionResult {
-        let allowed = match feature {
+    pub fn allows(&self, feature: Feature) -> bool {
+        match feature {
             Feature::LocalLlm => self.allow_local_llm,
             Feature::CloudLlm => self.allow_cloud_llm,
             Feature::BrowserAutomation => self.allow_browser_automation,
             Feature::EmailSending => self.allow_email_sending,
             Feature::ImapMonitoring => self.allow_imap_monitoring,
             Feature::PiiScanning => self.allow_pii_scanning,
+            Feature::BackgroundExecution => self.allow_background_execution,
+        }
+    }
+
+    /// Enable or disable `feature`
+    pub fn set(&mut self, feature: Feature, allowed: bool) {
+        let flag = match feature {
+            Feature::LocalLlm => &mut self.allow_local_llm,
+            Feature::CloudLlm => &mut self.allow_cloud_llm,
+            Feature::BrowserAutomation => &mut self.allow_browser_automation,
+            Feature::EmailSending => &mut self.allow_email_sending,
+            Feature::ImapMonitoring => &mut self.allow_imap_monitoring,
+            Feature::PiiScanning => &mut self.allow_pii_scanning,
+            Feature::BackgroundExecution => &mut self.allow_background_execution,
         };
+        *flag = allowed;
+    }
 
-        if allowed {
+    /// Check if a feature is allowed under these flags
+    #[must_use]
+    pub fn check_feature(&self, feature: Feature) -> PermissionResult {
+        if self.allows(feature) {
             PermissionResult::Allowed
         } else {
             PermissionResult::Denied {
@@ -117,6 +149,55 @@ pub enum Feature {
     ImapMonitoring,
     /// PII scanning
     PiiScanning,
+    /// Scheduled jobs and their notifications
+    BackgroundExecution,
+}
+
+impl Feature {
+    /// Every feature, in flag order
+    pub const ALL: [Self; 7] = [
+        Self::LocalLlm,
+        Self::CloudLlm,
+        Self::BrowserAutomation,
+        Self::EmailSending,
+        Self::ImapMonitoring,
+        Self::PiiScanning,
+        Self::BackgroundExecution,
+    ];
+
+    /// Permission the user must grant before the feature is used
+    #[must_use]
+    pub fn permission(self) -> Permission {
+        match self {
+            Self::LocalLlm => Permission::UseLlmLocal,
+            Self::CloudLlm => Permission::UseLlmCloud,
+            Self::BrowserAutomation => Permission::SubmitRemovalForms,
+            Self::EmailSending => Permission::SendEmails,
+            Self::ImapMonitoring => Permission::ScanEmails,
+            Self::PiiScanning => Permission::ScanBrokers,
+            Self::BackgroundExecution => Permission::BackgroundExecution,
+        }
+    }
+}
+
+/// Temporary change to one feature, replacing what the privacy level
+/// allows until it expires
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FeatureOverride {
+    /// Feature the override applies to
+    pub feature: Feature,
+    /// Whether the feature is enabled while the override lasts
+    pub allowed: bool,
+    /// When the override stops applying
+    pub expires_at: DateTime<Utc>,
+}
+
+impl FeatureOverride {
+    /// Whether the override has stopped applying at `now`
+    #[must_use]
+    pub fn is_expired_at(&self, now: DateTime<Utc>) -> bool {
+        now >= self.expires_at
+    }
 }
 
 /// Result of permission check
@@ -198,6 +279,22 @@ mod tests {
         assert!(flags.allow_browser_automation);
     }
 
+    #[test]
+    fn test_background_execution_defaults_on_for_saved_flags() {
+        let saved = r#"{
+            "allow_local_llm": true,
+            "allow_cloud_llm": false,
+            "allow_browser_automation": true,
+            "allow_email_sending": true,
+            "allow_imap_monitoring": true,
+            "allow_pii_scanning": true
+        }"#;
+        let flags: FeatureFlags = serde_json::from_str(saved).expect("deserialize saved flags");
+        assert!(flags.allows(Feature::BackgroundExecution));
+        assert!(!FeatureFlags::from_privacy_level(PrivacyLevel::Paranoid)
+            .allows(Feature::BackgroundExecution));
+    }
+
     #[test]
     fn test_permission_result_is_allowed() {
         let allowed = PermissionResult::Allowed;
@@ -41,6 +41,18 @@ pub enum ScanError {
         retry_after: std::time::Duration,
     },
 
+    /// Scan named a custom tier that is not defined
+    #[error("unknown scan tier \"{0}\": no custom tier with that name is defined")]
+    UnknownTier(String),
+
+    /// The scan was cancelled before this work finished
+    #[error("scan cancelled")]
+    Cancelled,
+
+    /// Scan job does not exist or has already completed or been cancelled
+    #[error("scan job {0} cannot be resumed: it does not exist or has already finished")]
+    JobNotResumable(String),
+
     /// Profile missing required fields
     #[error("profile missing required fields: {0:?}")]
     MissingRequiredFields(Vec<String>),
@@ -0,0 +1,137 @@
+//! Page fetching backends for broker scans.
+//!
+//! Scans normally fetch pages through the headless browser so JavaScript
+//! rendered results are visible. When no browser is installed, brokers whose
+//! results page is a plain URL can still be scanned with [`HttpFetcher`].
+
+use async_trait::async_trait;
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue, ACCEPT, ACCEPT_LANGUAGE};
+use spectral_browser::{BrowserEngine, BrowserError};
+use std::collections::HashMap;
+use std::time::Duration;
+
+/// Request timeout for plain HTTP page fetches.
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// `Accept` header a desktop browser sends for a page navigation.
+const DEFAULT_ACCEPT: &str = "text/html,application/xhtml+xml,application/xml;q=0.9,*/*;q=0.8";
+
+/// `Accept-Language` header sent with plain HTTP page fetches.
+const DEFAULT_ACCEPT_LANGUAGE: &str = "en-US,en;q=0.9";
+
+/// Fetches the HTML of a page.
+///
+/// Errors use [`BrowserError`] so retry logic can treat every backend the
+/// same way, e.g. backing off on [`BrowserError::RateLimitExceeded`].
+#[async_trait]
+pub trait PageFetcher: Send + Sync {
+    /// Fetch `url` and return the page HTML.
+    ///
+    /// `headers` are the broker's extra request headers. They are sent on
+    /// top of the backend's defaults, replacing any default of the same name.
+    async fn fetch(
+        &self,
+        url: &str,
+        headers: &HashMap<String, String>,
+    ) -> spectral_browser::Result<String>;
+}
+
+#[async_trait]
+impl PageFetcher for BrowserEngine {
+    async fn fetch(
+        &self,
+        url: &str,
+        headers: &HashMap<String, String>,
+    ) -> spectral_browser::Result<String> {
+        self.fetch_page_content_with_headers(url, headers).await
+    }
+}
+
+/// Fetches pages with a plain HTTP GET, without running JavaScript.
+#[derive(Debug, Clone)]
+pub struct HttpFetcher {
+    client: reqwest::Client,
+}
+
+impl HttpFetcher {
+    /// Create a fetcher with the default timeout.
+    ///
+    /// # Panics
+    /// Panics if the TLS backend cannot be initialized.
+    #[must_use]
+    pub fn new() -> Self {
+        let client = reqwest::Client::builder()
+            .timeout(REQUEST_TIMEOUT)
+            .build()
+            .expect("HTTP client configuration is valid");
+        Self { client }
+    }
+
+    /// Create a fetcher that uses an existing HTTP client.
+    #[must_use]
+    pub fn with_client(client: reqwest::Client) -> Self {
+        Self { client }
+    }
+}
+
+impl Default for HttpFetcher {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl PageFetcher for HttpFetcher {
+    async fn fetch(
+        &self,
+        url: &str,
+        headers: &HashMap<String, String>,
+    ) -> spectral_browser::Result<String> {
+        let response = self
+            .client
+            .get(url)
+            .headers(request_headers(headers)?)
+            .send()
+            .await
+            .map_err(|e| {
+                if e.is_timeout() {
+                    BrowserError::Timeout(e.without_url().to_string())
+                } else {
+                    BrowserError::NavigationError(e.without_url().to_string())
+                }
+            })?;
+
+        let status = response.status();
+        if status == reqwest::StatusCode::TOO_MANY_REQUESTS {
+            let domain = response.url().host_str().unwrap_or_default().to_string();
+            return Err(BrowserError::RateLimitExceeded(domain));
+        }
+        if !status.is_success() {
+            return Err(BrowserError::NavigationError(format!("HTTP {status}")));
+        }
+
+        response
+            .text()
+            .await
+            .map_err(|e| BrowserError::NavigationError(e.without_url().to_string()))
+    }
+}
+
+/// Default page fetch headers, overridden by a broker's `extra` headers.
+fn request_headers(extra: &HashMap<String, String>) -> spectral_browser::Result<HeaderMap> {
+    let mut headers = HeaderMap::new();
+    headers.insert(ACCEPT, HeaderValue::from_static(DEFAULT_ACCEPT));
+    headers.insert(
+        ACCEPT_LANGUAGE,
+        HeaderValue::from_static(DEFAULT_ACCEPT_LANGUAGE),
+    );
+
+    for (name, value) in extra {
+        let invalid = || BrowserError::NavigationError(format!("invalid request header {name}"));
+        let name = HeaderName::from_bytes(name.as_bytes()).map_err(|_| invalid())?;
+        let value = HeaderValue::from_str(value).map_err(|_| invalid())?;
+        headers.insert(name, value);
+    }
+
+    Ok(headers)
+}
@@ -3,7 +3,7 @@
 
 use serde::{Deserialize, Serialize};
 use spectral_broker::{BrokerDefinition, SearchMethod};
-use spectral_core::PiiField;
+use spectral_core::{AddressFormat, PiiField, DEFAULT_COUNTRY};
 use spectral_vault::UserProfile;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -31,12 +31,39 @@ impl BrokerFilter {
     }
 }
 
+/// Decrypts the profile's country, if it has one.
+///
+/// A country that fails to decrypt is treated as absent, which falls back to
+/// [`spectral_core::DEFAULT_COUNTRY`] wherever the country is used.
+pub fn profile_country(profile: &UserProfile, key: &[u8; 32]) -> Option<String> {
+    profile
+        .country
+        .as_ref()
+        .and_then(|country| country.decrypt(key).ok())
+        .filter(|country| !country.trim().is_empty())
+}
+
+/// Checks if a broker lists residents of the profile's country.
+///
+/// Profiles without a country are treated as US residents.
+pub fn broker_covers_profile(
+    broker: &BrokerDefinition,
+    profile: &UserProfile,
+    key: &[u8; 32],
+) -> bool {
+    let country = profile_country(profile, key);
+    broker.covers_country(country.as_deref().unwrap_or(DEFAULT_COUNTRY))
+}
+
 /// Checks if the user profile contains all required fields for a broker.
 ///
+/// Fields that do not apply to the profile's country (such as `State` for a
+/// UK address) are never reported as missing.
+///
 /// # Parameters
 /// * `broker` - The broker definition containing search field requirements
 /// * `profile` - The user profile to check for completeness
-/// * `_key` - Encryption key (reserved for future use when validating field decryptability)
+/// * `key` - Encryption key used to read the profile's country
 ///
 /// # Returns
 /// * `Ok(())` if all required fields are present
@@ -44,7 +71,7 @@ impl BrokerFilter {
 pub fn check_profile_completeness(
     broker: &BrokerDefinition,
     profile: &UserProfile,
-    _key: &[u8; 32],
+    key: &[u8; 32],
 ) -> Result<(), Vec<PiiField>> {
     let requires_fields = match &broker.search {
         SearchMethod::UrlTemplate {
@@ -56,9 +83,15 @@ pub fn check_profile_completeness(
         SearchMethod::Manual { .. } => return Ok(()),
     };
 
+    let country = profile_country(profile, key);
+    let address_format = AddressFormat::for_country(country.as_deref());
     let mut missing = Vec::new();
 
     for field in requires_fields {
+        if !address_format.expects_field(*field) {
+            continue;
+        }
+
         #[allow(deprecated)]
         let is_present = match field {
             PiiField::FullName => profile.full_name.is_some(),
@@ -100,13 +133,78 @@ pub fn check_profile_completeness(
     }
 }
 
+/// How many more brokers a profile could be scanned on if it had one more
+/// field.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FieldImpact {
+    /// The missing field
+    pub field: PiiField,
+    /// Brokers whose search needs only this field to become scannable
+    pub unlocks_broker_count: usize,
+}
+
+/// For each field the profile is missing, count the brokers that adding it
+/// would make scannable.
+///
+/// Only brokers covering the profile's country are considered. A broker
+/// counts toward a field when that field is the only thing keeping it from
+/// being scanned; brokers missing several fields need more than one addition
+/// and count toward none. `Age` is derived from the date of birth, so it is
+/// reported as `DateOfBirth`.
+///
+/// Sorted by the number of brokers unlocked, most first.
+pub fn field_impacts(
+    brokers: &[BrokerDefinition],
+    profile: &UserProfile,
+    key: &[u8; 32],
+) -> Vec<FieldImpact> {
+    let mut counts: Vec<FieldImpact> = Vec::new();
+
+    for broker in brokers {
+        if !broker_covers_profile(broker, profile, key) {
+            continue;
+        }
+        let Err(missing) = check_profile_completeness(broker, profile, key) else {
+            continue;
+        };
+
+        let mut fields: Vec<PiiField> = Vec::new();
+        for field in missing {
+            let field = match field {
+                PiiField::Age => PiiField::DateOfBirth,
+                field => field,
+            };
+            if !fields.contains(&field) {
+                fields.push(field);
+            }
+        }
+        let [field] = fields[..] else {
+            continue;
+        };
+
+        match counts.iter_mut().find(|impact| impact.field == field) {
+            Some(impact) => impact.unlocks_broker_count += 1,
+            None => counts.push(FieldImpact {
+                field,
+                unlocks_broker_count: 1,
+            }),
+        }
+    }
+
+    counts.sort_by_key(|impact| std::cmp::Reverse(impact.unlocks_broker_count));
+    counts
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use chrono::NaiveDate;
-    use spectral_broker::{BrokerCategory, BrokerMetadata, RemovalDifficulty, RemovalMethod};
+    use spectral_broker::{
+        BrokerCategory, BrokerMetadata, RemovalConfirmation, RemovalDifficulty, RemovalMethod,
+    };
     use spectral_core::{BrokerId, ProfileId};
     use spectral_vault::EncryptedField;
+    use std::collections::HashMap;
 
     fn mock_broker(category: BrokerCategory, requires: Vec<PiiField>) -> BrokerDefinition {
         BrokerDefinition {
@@ -122,6 +220,12 @@ mod tests {
                 last_verified: NaiveDate::from_ymd_opt(2025, 1, 1).expect("valid test date"),
                 scan_priority: spectral_broker::ScanPriority::OnRequest,
                 region_relevance: vec!["Global".to_string()],
+                countries: vec![],
+                requires_id_verification: false,
+                requires_account: false,
+                related_brokers: vec![],
+                request_headers: HashMap::new(),
+                legal_contact: None,
             },
             search: SearchMethod::UrlTemplate {
                 template: "https://example.com/{first}-{last}".to_string(),
@@ -131,6 +235,8 @@ mod tests {
             removal: RemovalMethod::Manual {
                 instructions: "Manual removal".to_string(),
             },
+            fixture: None,
+            confirmation: RemovalConfirmation::None,
         }
     }
 
@@ -212,6 +318,96 @@ mod tests {
         assert!(result.is_ok());
     }
 
+    fn uk_profile(key: &[u8; 32]) -> UserProfile {
+        let profile_id =
+            ProfileId::new("550e8400-e29b-41d4-a716-446655440000").expect("valid test profile ID");
+        let mut profile = UserProfile::new(profile_id);
+        for (field, value) in [
+            (&mut profile.first_name, "Jane"),
+            (&mut profile.last_name, "Smith"),
+            (&mut profile.city, "London"),
+            (&mut profile.zip_code, "SW1A 2AA"),
+            (&mut profile.country, "GB"),
+        ] {
+            *field = Some(
+                EncryptedField::encrypt(&value.to_string(), key)
+                    .expect("encryption should succeed in test"),
+            );
+        }
+        profile
+    }
+
+    #[test]
+    fn test_uk_profile_not_missing_state() {
+        let key = [0x42; 32];
+        let broker = mock_broker(
+            BrokerCategory::PeopleSearch,
+            vec![PiiField::FirstName, PiiField::LastName, PiiField::State],
+        );
+
+        let profile = uk_profile(&key);
+        assert!(check_profile_completeness(&broker, &profile, &key).is_ok());
+    }
+
+    #[test]
+    fn test_us_only_broker_skipped_for_uk_profile() {
+        let key = [0x42; 32];
+        let mut us_only = mock_broker(BrokerCategory::PeopleSearch, vec![]);
+        us_only.broker.countries = vec!["US".to_string()];
+        let unrestricted = mock_broker(BrokerCategory::PeopleSearch, vec![]);
+
+        let uk = uk_profile(&key);
+        assert!(!broker_covers_profile(&us_only, &uk, &key));
+        assert!(broker_covers_profile(&unrestricted, &uk, &key));
+
+        // Profiles without a country are treated as US residents
+        let legacy = UserProfile::new(
+            ProfileId::new("550e8400-e29b-41d4-a716-446655440000").expect("valid test profile ID"),
+        );
+        assert!(broker_covers_profile(&us_only, &legacy, &key));
+    }
+
+    #[test]
+    fn test_field_impacts_counts_brokers_unlocked_by_dob() {
+        let key = [0x42; 32];
+        let name = vec![PiiField::FirstName, PiiField::LastName];
+        let with = |extra: &[PiiField]| {
+            let mut requires = name.clone();
+            requires.extend_from_slice(extra);
+            mock_broker(BrokerCategory::PeopleSearch, requires)
+        };
+        let mut us_only = with(&[PiiField::DateOfBirth]);
+        us_only.broker.countries = vec!["US".to_string()];
+
+        let brokers = vec![
+            with(&[]),
+            with(&[PiiField::DateOfBirth]),
+            with(&[PiiField::DateOfBirth]),
+            with(&[PiiField::Age]),
+            with(&[PiiField::DateOfBirth, PiiField::Age]),
+            with(&[PiiField::Phone]),
+            // Needs both DOB and phone, so neither alone unlocks it
+            with(&[PiiField::DateOfBirth, PiiField::Phone]),
+            // Does not list UK residents
+            us_only,
+        ];
+
+        let impacts = field_impacts(&brokers, &uk_profile(&key), &key);
+        assert_eq!(
+            impacts,
+            vec![
+                FieldImpact {
+                    field: PiiField::DateOfBirth,
+                    unlocks_broker_count: 4,
+                },
+                FieldImpact {
+                    field: PiiField::Phone,
+                    unlocks_broker_count: 1,
+                },
+            ]
+        );
+    }
+
     #[test]
     fn test_manual_search_method_always_succeeds() {
         let broker = BrokerDefinition {
@@ -227,6 +423,12 @@ mod tests {
                 last_verified: NaiveDate::from_ymd_opt(2025, 1, 1).expect("valid test date"),
                 scan_priority: spectral_broker::ScanPriority::OnRequest,
                 region_relevance: vec!["Global".to_string()],
+                countries: vec![],
+                requires_id_verification: false,
+                requires_account: false,
+                related_brokers: vec![],
+                request_headers: HashMap::new(),
+                legal_contact: None,
             },
             search: SearchMethod::Manual {
                 url: "https://example.com/search".to_string(),
@@ -235,6 +437,8 @@ mod tests {
             removal: RemovalMethod::Manual {
                 instructions: "Manual removal".to_string(),
             },
+            fixture: None,
+            confirmation: RemovalConfirmation::None,
         };
 
         let profile_id =
@@ -10,7 +10,10 @@
 //! - Retry logic with exponential backoff for transient failures
 //! - CAPTCHA detection and reporting
 //! - Rate limit handling with extended backoff
+//! - Global token-bucket request budget shared across all brokers
 //! - Automatic findings storage in encrypted database
+//! - Searches for aliases, nicknames and maiden names alongside the stored name
+//! - Plain HTTP fallback for URL-template brokers when no browser is installed
 //!
 //! # Example
 //!
@@ -28,7 +31,7 @@
 //!     scan_job_id,
 //!     broker_ids,
 //!     profile_id,
-//!     vault_key,
+//!     &vault_key,
 //! ).await?;
 //! ```
 
@@ -40,17 +43,30 @@
 #![allow(clippy::missing_panics_doc)]
 
 pub mod error;
+pub mod fetcher;
 #[allow(missing_docs)]
 pub mod filter;
+pub mod name_variants;
 pub mod orchestrator;
 #[allow(missing_docs)]
 pub mod parser;
+pub mod rate_limit;
+pub mod settings;
 #[allow(missing_docs)]
 pub mod url_builder;
+pub mod watchdog;
 
 // Re-export commonly used types
 pub use error::{Result, ScanError};
-pub use filter::{check_profile_completeness, BrokerFilter};
-pub use orchestrator::{BrokerScanResult, ScanOrchestrator};
+pub use fetcher::{HttpFetcher, PageFetcher};
+pub use filter::{
+    broker_covers_profile, check_profile_completeness, field_impacts, profile_country,
+    BrokerFilter, FieldImpact,
+};
+pub use name_variants::{name_variants, nicknames_for, NameVariant};
+pub use orchestrator::{BrokerScanResult, ScanOrchestrator, SkipReason};
 pub use parser::{ExtractedData, ListingMatch, ResultParser};
+pub use rate_limit::RateLimiter;
+pub use settings::ScanSettings;
 pub use url_builder::build_search_url;
+pub use watchdog::{reap_stalled_scans, spawn_watchdog};
@@ -0,0 +1,233 @@
+//! Alternative names a person may be listed under.
+//!
+//! Brokers index people under maiden names, nicknames, and with the middle
+//! name used as the first, so a search for the exact stored name misses
+//! listings. [`name_variants`] derives a bounded list of first/last name
+//! pairs to search from the profile, the primary name first.
+
+use spectral_core::names;
+use spectral_vault::cipher::EncryptedField;
+use spectral_vault::profile::RelationshipType;
+use spectral_vault::UserProfile;
+use zeroize::Zeroizing;
+
+/// A first and last name to search a broker for.
+#[derive(Clone, PartialEq, Eq)]
+pub struct NameVariant {
+    /// Given name searched for
+    pub first_name: Zeroizing<String>,
+    /// Family name searched for
+    pub last_name: Zeroizing<String>,
+}
+
+impl NameVariant {
+    fn new(first_name: &str, last_name: &str) -> Self {
+        Self {
+            first_name: Zeroizing::new(first_name.trim().to_string()),
+            last_name: Zeroizing::new(last_name.trim().to_string()),
+        }
+    }
+
+    /// First and last name separated by a space.
+    #[must_use]
+    pub fn full_name(&self) -> Zeroizing<String> {
+        Zeroizing::new(format!("{} {}", *self.first_name, *self.last_name))
+    }
+
+    fn same_name(&self, other: &Self) -> bool {
+        self.first_name.eq_ignore_ascii_case(&other.first_name)
+            && self.last_name.eq_ignore_ascii_case(&other.last_name)
+    }
+}
+
+impl std::fmt::Debug for NameVariant {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        // Names are PII; keep them out of logs
+        f.debug_struct("NameVariant").finish_non_exhaustive()
+    }
+}
+
+/// Nicknames of `first_name`, or the given names it is a nickname of,
+/// capitalized.
+#[must_use]
+pub fn nicknames_for(first_name: &str) -> Vec<String> {
+    names::nicknames_for(first_name)
+        .into_iter()
+        .map(capitalize)
+        .collect()
+}
+
+/// Up to `max` names to search for, without duplicates, starting with the
+/// profile's own first and last name.
+///
+/// After the primary name come the profile's aliases (an alias missing a
+/// first or last name borrows the primary one, and an alias nickname is
+/// paired with the last name), the middle name used as the first, the
+/// maiden names of siblings as an earlier last name, and finally nicknames
+/// of the first name. Empty if the profile has no first and last name.
+#[must_use]
+pub fn name_variants(profile: &UserProfile, vault_key: &[u8; 32], max: usize) -> Vec<NameVariant> {
+    let (Some(first), Some(last)) = (
+        decrypt(profile.first_name.as_ref(), vault_key),
+        decrypt(profile.last_name.as_ref(), vault_key),
+    ) else {
+        return Vec::new();
+    };
+
+    let mut candidates = vec![NameVariant::new(&first, &last)];
+
+    for alias in &profile.aliases {
+        let alias_first = decrypt(alias.first_name.as_ref(), vault_key);
+        let alias_last = decrypt(alias.last_name.as_ref(), vault_key);
+        if alias_first.is_some() || alias_last.is_some() {
+            candidates.push(NameVariant::new(
+                alias_first
+                    .as_deref()
+                    .map_or(first.as_str(), String::as_str),
+                alias_last.as_deref().map_or(last.as_str(), String::as_str),
+            ));
+        }
+        if let Some(nickname) = decrypt(alias.nickname.as_ref(), vault_key) {
+            let surname = alias_last.as_deref().map_or(last.as_str(), String::as_str);
+            candidates.push(NameVariant::new(&nickname, surname));
+        }
+    }
+
+    if let Some(middle) = decrypt(profile.middle_name.as_ref(), vault_key) {
+        candidates.push(NameVariant::new(&middle, &last));
+    }
+
+    // Siblings share the birth surname the person may still be listed under
+    for relative in &profile.relatives {
+        if relative.relationship == RelationshipType::Sibling {
+            if let Some(maiden_name) = decrypt(relative.maiden_name.as_ref(), vault_key) {
+                candidates.push(NameVariant::new(&first, &maiden_name));
+            }
+        }
+    }
+
+    for nickname in nicknames_for(&first) {
+        candidates.push(NameVariant::new(&nickname, &last));
+    }
+
+    let mut variants: Vec<NameVariant> = Vec::new();
+    for candidate in candidates {
+        if variants.len() >= max {
+            break;
+        }
+        if candidate.first_name.is_empty() || candidate.last_name.is_empty() {
+            continue;
+        }
+        if !variants.iter().any(|v| v.same_name(&candidate)) {
+            variants.push(candidate);
+        }
+    }
+    variants
+}
+
+/// Decrypt an optional name, treating undecryptable names as absent.
+fn decrypt(
+    field: Option<&EncryptedField<String>>,
+    vault_key: &[u8; 32],
+) -> Option<Zeroizing<String>> {
+    let value = field?.decrypt_zeroizing(vault_key).ok()?;
+    (!value.trim().is_empty()).then_some(value)
+}
+
+fn capitalize(name: &str) -> String {
+    let mut chars = name.chars();
+    chars.next().map_or_else(String::new, |first| {
+        first.to_uppercase().chain(chars).collect()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use spectral_core::ProfileId;
+    use spectral_vault::cipher::encrypt_string;
+    use spectral_vault::profile::{Alias, Relative};
+
+    const KEY: [u8; 32] = [0x42; 32];
+
+    fn enc(value: &str) -> EncryptedField<String> {
+        encrypt_string(value, &KEY).expect("encrypt")
+    }
+
+    fn names(variants: &[NameVariant]) -> Vec<String> {
+        variants.iter().map(|v| v.full_name().to_string()).collect()
+    }
+
+    fn profile() -> UserProfile {
+        let mut profile =
+            UserProfile::new(ProfileId::new("550e8400-e29b-41d4-a716-446655440000").expect("id"));
+        profile.first_name = Some(enc("Margaret"));
+        profile.middle_name = Some(enc("Anne"));
+        profile.last_name = Some(enc("Jones"));
+        profile.aliases = vec![Alias {
+            first_name: None,
+            middle_name: None,
+            last_name: Some(enc("Smith")),
+            nickname: None,
+        }];
+        profile.relatives = vec![Relative {
+            first_name: Some(enc("Paul")),
+            middle_name: None,
+            last_name: Some(enc("Brown")),
+            maiden_name: Some(enc("Brown")),
+            relationship: RelationshipType::Sibling,
+        }];
+        profile
+    }
+
+    #[test]
+    fn test_variants_in_order_without_duplicates() {
+        let mut profile = profile();
+        // Same as the primary name, differing only in case
+        profile.aliases.push(Alias {
+            first_name: Some(enc("MARGARET")),
+            middle_name: None,
+            last_name: Some(enc("jones")),
+            nickname: None,
+        });
+
+        assert_eq!(
+            names(&name_variants(&profile, &KEY, 10)),
+            [
+                "Margaret Jones",
+                "Margaret Smith",
+                "Anne Jones",
+                "Margaret Brown",
+                "Maggie Jones",
+                "Peggy Jones",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_variants_are_capped() {
+        assert_eq!(
+            names(&name_variants(&profile(), &KEY, 2)),
+            ["Margaret Jones", "Margaret Smith"]
+        );
+        assert_eq!(
+            names(&name_variants(&profile(), &KEY, 1)),
+            ["Margaret Jones"]
+        );
+    }
+
+    #[test]
+    fn test_nicknames_work_both_ways() {
+        assert_eq!(nicknames_for("William"), ["Bill", "Will", "Billy"]);
+        assert_eq!(nicknames_for("bob"), ["Robert"]);
+        assert_eq!(nicknames_for("Steve"), ["Stephen", "Steven"]);
+        assert!(nicknames_for("Zebulon").is_empty());
+    }
+
+    #[test]
+    fn test_no_variants_without_a_name() {
+        let mut profile = profile();
+        profile.last_name = None;
+        assert!(name_variants(&profile, &KEY, 5).is_empty());
+    }
+}
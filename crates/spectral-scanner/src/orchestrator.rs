@@ -5,17 +5,27 @@
 //! and findings storage.
 
 use crate::error::{Result, ScanError};
-use crate::filter::BrokerFilter;
+use crate::fetcher::{HttpFetcher, PageFetcher};
+use crate::filter::{broker_covers_profile, profile_country, BrokerFilter};
+use crate::name_variants::{name_variants, NameVariant};
+use crate::rate_limit::RateLimiter;
+use crate::settings::ScanSettings;
+use crate::url_builder::{remove_optional_placeholders, remove_state_placeholder};
 use futures::stream::{FuturesUnordered, StreamExt};
 use spectral_broker::{BrokerDefinition, BrokerRegistry};
 use spectral_browser::BrowserEngine;
-use spectral_core::BrokerId;
-use spectral_db::{scan_jobs, Database};
+use spectral_core::config::ScanDisclosure;
+use spectral_core::metrics::{self, Counter, Gauge};
+use spectral_core::{AddressFormat, BrokerId, RngSource};
+use spectral_db::{broker_scans, scan_jobs, Database, DbChange};
 use spectral_vault::UserProfile;
+use std::collections::HashMap;
 use std::sync::Arc;
 use std::time::Duration;
+use tokio_util::sync::CancellationToken;
+use zeroize::Zeroizing;
 
-/// Maximum number of retry attempts for transient errors.
+/// Default number of fetch attempts for transient errors.
 const MAX_RETRIES: u32 = 3;
 
 /// Base delay in milliseconds for retry backoff.
@@ -24,6 +34,20 @@ const RETRY_DELAY_MS: u64 = 2000;
 /// Rate limit backoff multiplier (longer wait for rate limits).
 const RATE_LIMIT_BACKOFF_MULTIPLIER: u64 = 3;
 
+/// Retry delays get up to `1 / RETRY_JITTER_DIVISOR` extra random delay,
+/// so concurrent scans that fail together don't retry in lockstep.
+const RETRY_JITTER_DIVISOR: u64 = 4;
+
+/// Assumed scan time in seconds for a broker that has never been scanned.
+const DEFAULT_BROKER_SCAN_SECS: f64 = 10.0;
+
+/// Recent scans per broker averaged when estimating scan durations.
+const DURATION_HISTORY_SCANS: u32 = 10;
+
+/// Default number of names each broker is searched for, the profile's own
+/// name included. Searching variants is opt-in.
+pub const DEFAULT_MAX_NAME_VARIANTS: usize = 1;
+
 /// Result of scanning a single broker.
 #[derive(Debug, Clone)]
 pub struct BrokerScanResult {
@@ -33,18 +57,54 @@ pub struct BrokerScanResult {
     pub findings_count: usize,
     /// Error message if scan failed
     pub error: Option<String>,
+    /// Why the broker was not scanned, if it was skipped
+    pub skip_reason: Option<SkipReason>,
+}
+
+/// Reason a broker was skipped rather than scanned.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SkipReason {
+    /// The broker's search needs a browser and none is installed
+    NoBrowser,
+    /// The scan was cancelled before the broker was scanned
+    Cancelled,
+}
+
+impl std::fmt::Display for SkipReason {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::NoBrowser => write!(f, "Skipped: no browser installed"),
+            Self::Cancelled => write!(f, "Cancelled: the scan was stopped"),
+        }
+    }
 }
 
 /// Orchestrates scanning operations across multiple brokers.
 pub struct ScanOrchestrator {
     /// Broker registry for broker definitions
     broker_registry: Arc<BrokerRegistry>,
-    /// Browser engine for page fetching
-    browser_engine: Arc<BrowserEngine>,
+    /// Fetches broker pages
+    fetcher: Arc<dyn PageFetcher>,
+    /// Whether `fetcher` is a browser that can handle browser-only brokers
+    browser_available: bool,
     /// Database for storing results
     db: Arc<Database>,
     /// Maximum concurrent scans
     max_concurrent_scans: usize,
+    /// Fetch attempts per page before giving up
+    max_retries: u32,
+    /// Global request budget shared by every broker scan
+    rate_limiter: Arc<RateLimiter>,
+    /// How much profile data searches send to brokers
+    disclosure: ScanDisclosure,
+    /// Randomness for retry jitter
+    rng: Arc<RngSource>,
+    /// Whether to follow "next page" links past the first result page
+    follow_pagination: bool,
+    /// Stops the scan; brokers not yet scanned are recorded as `Cancelled`
+    cancel: CancellationToken,
+    /// Names searched per broker, capping the requests a broker receives
+    max_name_variants: usize,
 }
 
 impl ScanOrchestrator {
@@ -54,12 +114,43 @@ impl ScanOrchestrator {
         broker_registry: Arc<BrokerRegistry>,
         browser_engine: Arc<BrowserEngine>,
         db: Arc<Database>,
+    ) -> Self {
+        Self::with_fetcher(broker_registry, browser_engine, true, db)
+    }
+
+    /// Create a scan orchestrator for when no browser is installed.
+    ///
+    /// URL-template brokers are fetched over plain HTTP; brokers whose search
+    /// requires a browser are skipped with [`SkipReason::NoBrowser`].
+    #[must_use]
+    pub fn without_browser(broker_registry: Arc<BrokerRegistry>, db: Arc<Database>) -> Self {
+        Self::with_fetcher(broker_registry, Arc::new(HttpFetcher::new()), false, db)
+    }
+
+    /// Create a scan orchestrator with a custom page fetcher.
+    ///
+    /// `is_browser` says whether the fetcher can handle brokers that require
+    /// a browser; if not, those brokers are skipped.
+    #[must_use]
+    pub fn with_fetcher(
+        broker_registry: Arc<BrokerRegistry>,
+        fetcher: Arc<dyn PageFetcher>,
+        is_browser: bool,
+        db: Arc<Database>,
     ) -> Self {
         Self {
             broker_registry,
-            browser_engine,
+            fetcher,
+            browser_available: is_browser,
             db,
             max_concurrent_scans: 5,
+            max_retries: MAX_RETRIES,
+            rate_limiter: Arc::new(RateLimiter::default()),
+            disclosure: ScanDisclosure::default(),
+            rng: Arc::new(RngSource::os()),
+            follow_pagination: true,
+            cancel: CancellationToken::new(),
+            max_name_variants: DEFAULT_MAX_NAME_VARIANTS,
         }
     }
 
@@ -70,6 +161,107 @@ impl ScanOrchestrator {
         self
     }
 
+    /// Set the number of fetch attempts per page before a broker scan fails.
+    #[must_use]
+    pub fn with_max_retries(mut self, max_retries: u32) -> Self {
+        self.max_retries = max_retries.max(1);
+        self
+    }
+
+    /// Set how much profile data searches send to brokers.
+    #[must_use]
+    pub fn with_disclosure(mut self, disclosure: ScanDisclosure) -> Self {
+        self.disclosure = disclosure;
+        self
+    }
+
+    /// Set whether result pages past the first are fetched.
+    #[must_use]
+    pub fn with_pagination(mut self, follow_pagination: bool) -> Self {
+        self.follow_pagination = follow_pagination;
+        self
+    }
+
+    /// Set how many names each broker is searched for, counting the
+    /// profile's own name.
+    ///
+    /// Besides the stored name, brokers are searched for aliases, nicknames
+    /// and other variants (see [`name_variants`]), one request each. With
+    /// minimal disclosure only the stored name is searched.
+    #[must_use]
+    pub fn with_max_name_variants(mut self, max: usize) -> Self {
+        self.max_name_variants = max.max(1);
+        self
+    }
+
+    /// Apply concurrency, retry, disclosure and depth settings resolved for
+    /// this scan.
+    #[must_use]
+    pub fn with_settings(self, settings: &ScanSettings) -> Self {
+        self.with_max_concurrent_scans(settings.max_concurrent_scans)
+            .with_max_retries(settings.effective_max_retries())
+            .with_disclosure(settings.disclosure)
+            .with_pagination(settings.follows_pagination())
+            .with_max_name_variants(settings.max_name_variants())
+    }
+
+    /// Set the source of randomness for retry jitter.
+    ///
+    /// Tests pass a seeded [`RngSource`] to get reproducible retry delays.
+    #[must_use]
+    pub fn with_rng_source(mut self, rng: RngSource) -> Self {
+        self.rng = Arc::new(rng);
+        self
+    }
+
+    /// Set the token that cancels this orchestrator's scans.
+    ///
+    /// Cancelling it stops in-flight fetches and leaves unscanned brokers
+    /// `Cancelled` rather than `Failed`, and the job itself `Cancelled`.
+    #[must_use]
+    pub fn with_cancellation(mut self, cancel: CancellationToken) -> Self {
+        self.cancel = cancel;
+        self
+    }
+
+    /// Set the global request budget.
+    ///
+    /// Pass the same limiter to several orchestrators to share one budget.
+    #[must_use]
+    pub fn with_rate_limiter(mut self, rate_limiter: Arc<RateLimiter>) -> Self {
+        self.rate_limiter = rate_limiter;
+        self
+    }
+
+    /// Estimate the wall-clock time to scan the brokers matching `broker_filter`.
+    ///
+    /// Each broker takes the average of its recent scan durations, or
+    /// [`DEFAULT_BROKER_SCAN_SECS`] if it has never been scanned, and brokers
+    /// that would be skipped take no time. These are spread over
+    /// `max_concurrent_scans` slots in the order `execute_scan_job` runs
+    /// them, so the estimate improves as scan history accumulates.
+    pub async fn estimate_duration(&self, broker_filter: &BrokerFilter) -> Result<Duration> {
+        let history =
+            broker_scans::average_durations(self.db.pool(), DURATION_HISTORY_SCANS).await?;
+
+        let durations: Vec<Duration> = self
+            .broker_registry
+            .get_all()
+            .iter()
+            .filter(|broker| broker_filter.matches(broker))
+            .filter(|broker| self.browser_available || !broker.search.requires_browser())
+            .map(|broker| {
+                let secs = history
+                    .get(broker.id().as_str())
+                    .copied()
+                    .unwrap_or(DEFAULT_BROKER_SCAN_SECS);
+                Duration::from_secs_f64(secs)
+            })
+            .collect();
+
+        Ok(concurrent_duration(&durations, self.max_concurrent_scans))
+    }
+
     /// Start a new scan job with the specified profile and broker filter.
     ///
     /// This creates a scan job in the database, launches background execution,
@@ -89,12 +281,14 @@ impl ScanOrchestrator {
         broker_filter: BrokerFilter,
         vault_key: &[u8; 32],
     ) -> Result<String> {
-        // Get list of brokers to scan
+        // Get list of brokers to scan, skipping brokers that don't cover the
+        // profile's country
         let brokers: Vec<_> = self
             .broker_registry
             .get_all()
             .into_iter()
             .filter(|broker| broker_filter.matches(broker))
+            .filter(|broker| broker_covers_profile(broker, profile, vault_key))
             .collect();
 
         let total_brokers = brokers.len() as u32;
@@ -108,46 +302,154 @@ impl ScanOrchestrator {
         )
         .await?;
 
+        self.db.notify(DbChange::ScanStatusChanged {
+            scan_job_id: job.id.clone(),
+            status: job.status.to_string(),
+        });
+
         let job_id = job.id.clone();
         let profile_id = profile.id.as_str().to_string();
-        let vault_key = *vault_key;
+        // The background task outlives the caller's key, so it gets a copy
+        // that is wiped when the scan ends
+        let vault_key = Zeroizing::new(*vault_key);
 
         // Clone Arc references for background task
         let orchestrator_clone = Arc::new(Self {
             broker_registry: self.broker_registry.clone(),
-            browser_engine: self.browser_engine.clone(),
+            fetcher: self.fetcher.clone(),
+            browser_available: self.browser_available,
             db: self.db.clone(),
             max_concurrent_scans: self.max_concurrent_scans,
+            max_retries: self.max_retries,
+            rate_limiter: self.rate_limiter.clone(),
+            disclosure: self.disclosure,
+            rng: self.rng.clone(),
+            follow_pagination: self.follow_pagination,
+            cancel: self.cancel.clone(),
+            max_name_variants: self.max_name_variants,
         });
 
         // Clone job_id for background task
         let job_id_for_task = job_id.clone();
 
+        metrics::global().increment(Counter::ScansStarted);
+        metrics::global().adjust(Gauge::ActiveScans, 1);
+
         // Launch scan execution in background
         tokio::spawn(async move {
             let result = orchestrator_clone
-                .execute_scan_job(job_id_for_task.clone(), broker_ids, profile_id, vault_key)
+                .execute_scan_job(job_id_for_task.clone(), broker_ids, profile_id, &vault_key)
                 .await;
 
             match result {
+                Ok(results) if orchestrator_clone.cancel.is_cancelled() => {
+                    let _ = orchestrator_clone
+                        .cancel_scan_job(&job_id_for_task, scanned_count(&results))
+                        .await;
+                }
                 Ok(results) => {
                     let completed = results.len() as u32;
                     let _ = orchestrator_clone
                         .complete_scan_job(&job_id_for_task, completed)
                         .await;
+                    metrics::global().increment(Counter::ScansCompleted);
                 }
                 Err(e) => {
                     tracing::error!("Scan job {} failed: {}", job_id_for_task, e);
                     let _ = orchestrator_clone
                         .fail_scan_job(&job_id_for_task, &e.to_string())
                         .await;
+                    metrics::global().increment(Counter::ScansFailed);
                 }
             }
+            metrics::global().adjust(Gauge::ActiveScans, -1);
         });
 
         Ok(job_id)
     }
 
+    /// Resume a scan job that was interrupted, for instance by a crash.
+    ///
+    /// Re-runs only the brokers whose `broker_scans` record never reached a
+    /// terminal status, then completes the job. Findings are deduplicated per
+    /// job, so a broker that was part-way through is not double-counted.
+    /// The job may be `InProgress` or `Failed`, as when the watchdog gave up
+    /// on it. Runs to completion rather than in the background.
+    ///
+    /// # Errors
+    /// Returns `ScanError::JobNotResumable` if the job does not exist or has
+    /// completed or been cancelled.
+    #[allow(clippy::cast_possible_truncation)]
+    pub async fn resume_scan(
+        &self,
+        job_id: &str,
+        vault_key: &[u8; 32],
+    ) -> Result<Vec<BrokerScanResult>> {
+        let profile_id = scan_jobs::reopen_job(self.db.pool(), job_id)
+            .await?
+            .ok_or_else(|| ScanError::JobNotResumable(job_id.to_string()))?;
+
+        self.db.notify(DbChange::ScanStatusChanged {
+            scan_job_id: job_id.to_string(),
+            status: scan_jobs::ScanJobStatus::InProgress.to_string(),
+        });
+
+        let scans = broker_scans::get_by_scan_job(self.db.pool(), job_id).await?;
+        let mut finished = 0;
+        let mut broker_ids = Vec::new();
+        for scan in scans {
+            if scan.is_finished() {
+                finished += 1;
+                continue;
+            }
+            match BrokerId::new(&scan.broker_id) {
+                Ok(broker_id) => broker_ids.push(broker_id),
+                Err(e) => {
+                    tracing::warn!("Not resuming invalid broker {}: {}", scan.broker_id, e);
+                    broker_scans::update_status(
+                        self.db.pool(),
+                        &scan.id,
+                        "Failed",
+                        Some(format!("Invalid broker ID: {e}")),
+                    )
+                    .await?;
+                    finished += 1;
+                }
+            }
+        }
+
+        tracing::info!(
+            "Resuming scan job {}: {} of {} brokers left",
+            job_id,
+            broker_ids.len(),
+            finished + broker_ids.len()
+        );
+
+        metrics::global().adjust(Gauge::ActiveScans, 1);
+        let result = self
+            .execute_scan_job(job_id.to_string(), broker_ids, profile_id, vault_key)
+            .await;
+        match &result {
+            Ok(results) if self.cancel.is_cancelled() => {
+                self.cancel_scan_job(job_id, finished as u32 + scanned_count(results))
+                    .await?;
+            }
+            Ok(results) => {
+                self.complete_scan_job(job_id, (finished + results.len()) as u32)
+                    .await?;
+                metrics::global().increment(Counter::ScansCompleted);
+            }
+            Err(e) => {
+                tracing::error!("Resumed scan job {} failed: {}", job_id, e);
+                let _ = self.fail_scan_job(job_id, &e.to_string()).await;
+                metrics::global().increment(Counter::ScansFailed);
+            }
+        }
+        metrics::global().adjust(Gauge::ActiveScans, -1);
+
+        result
+    }
+
     /// Mark a scan job as completed.
     async fn complete_scan_job(&self, job_id: &str, completed_brokers: u32) -> Result<()> {
         sqlx::query(
@@ -159,6 +461,30 @@ impl ScanOrchestrator {
         .execute(self.db.pool())
         .await?;
 
+        self.db.notify(DbChange::ScanStatusChanged {
+            scan_job_id: job_id.to_string(),
+            status: "Completed".to_string(),
+        });
+
+        Ok(())
+    }
+
+    /// Mark a scan job as cancelled, with the brokers it got through.
+    async fn cancel_scan_job(&self, job_id: &str, completed_brokers: u32) -> Result<()> {
+        sqlx::query(
+            "UPDATE scan_jobs SET status = 'Cancelled', completed_at = ?, completed_brokers = ? WHERE id = ?"
+        )
+        .bind(chrono::Utc::now().to_rfc3339())
+        .bind(completed_brokers)
+        .bind(job_id)
+        .execute(self.db.pool())
+        .await?;
+
+        self.db.notify(DbChange::ScanStatusChanged {
+            scan_job_id: job_id.to_string(),
+            status: "Cancelled".to_string(),
+        });
+
         Ok(())
     }
 
@@ -173,40 +499,76 @@ impl ScanOrchestrator {
         .execute(self.db.pool())
         .await?;
 
+        self.db.notify(DbChange::ScanStatusChanged {
+            scan_job_id: job_id.to_string(),
+            status: "Failed".to_string(),
+        });
+
         Ok(())
     }
 
     /// Execute a scan job across multiple brokers.
     ///
     /// This scans all specified brokers concurrently (up to `max_concurrent_scans`)
-    /// and stores findings in the database.
+    /// and stores findings in the database. A `Pending` `broker_scans` record
+    /// is written for every broker before any scanning starts, so
+    /// [`resume_scan`](Self::resume_scan) can tell which brokers an
+    /// interrupted job never got to. Existing records for the job are reused.
     pub async fn execute_scan_job(
         &self,
         scan_job_id: String,
         broker_ids: Vec<BrokerId>,
         profile_id: String,
-        vault_key: [u8; 32],
+        vault_key: &[u8; 32],
     ) -> Result<Vec<BrokerScanResult>> {
         let mut futures = FuturesUnordered::new();
         let mut results = Vec::new();
 
+        let mut scan_ids: HashMap<String, String> =
+            broker_scans::get_by_scan_job(self.db.pool(), &scan_job_id)
+                .await?
+                .into_iter()
+                .map(|scan| (scan.broker_id, scan.id))
+                .collect();
+        for broker_id in &broker_ids {
+            if !scan_ids.contains_key(broker_id.as_str()) {
+                let scan = broker_scans::create_broker_scan(
+                    self.db.pool(),
+                    scan_job_id.clone(),
+                    broker_id.to_string(),
+                )
+                .await?;
+                scan_ids.insert(scan.broker_id, scan.id);
+            }
+        }
+
         for broker_id in broker_ids {
+            let broker_scan_id = scan_ids[broker_id.as_str()].clone();
+
             // Get broker definition
             let broker_def = match self.broker_registry.get(&broker_id) {
                 Ok(def) => def,
                 Err(e) => {
                     tracing::error!("Failed to get broker definition for {}: {}", broker_id, e);
+                    broker_scans::update_status(
+                        self.db.pool(),
+                        &broker_scan_id,
+                        "Failed",
+                        Some(format!("Broker not found: {e}")),
+                    )
+                    .await?;
                     results.push(BrokerScanResult {
                         broker_id: broker_id.clone(),
                         findings_count: 0,
                         error: Some(format!("Broker not found: {e}")),
+                        skip_reason: None,
                     });
                     continue;
                 }
             };
 
             futures.push(self.scan_single_broker(
-                scan_job_id.clone(),
+                broker_scan_id,
                 broker_def.clone(),
                 profile_id.clone(),
                 vault_key,
@@ -221,6 +583,7 @@ impl ScanOrchestrator {
                             tracing::error!("Scan failed: {}", e);
                         }
                     }
+                    self.record_progress(&scan_job_id).await;
                 }
             }
         }
@@ -233,53 +596,76 @@ impl ScanOrchestrator {
                     tracing::error!("Scan failed: {}", e);
                 }
             }
+            self.record_progress(&scan_job_id).await;
         }
 
         Ok(results)
     }
 
+    /// Heartbeat the scan job so the watchdog knows it is still alive.
+    async fn record_progress(&self, scan_job_id: &str) {
+        if let Err(e) = scan_jobs::record_progress(self.db.pool(), scan_job_id).await {
+            tracing::warn!(
+                "Failed to record progress for scan job {}: {}",
+                scan_job_id,
+                e
+            );
+        }
+    }
+
     /// Scan a single broker with retry logic and error handling.
     ///
-    /// Creates a `broker_scan` record, fetches the page with retries,
-    /// parses results, and stores findings in the database.
+    /// Marks the broker's `broker_scan` record as started, fetches the page
+    /// with retries, parses results, and stores findings in the database.
+    /// If the scan is cancelled before the page arrives, the record is
+    /// marked `Cancelled` instead, so the broker is not counted as failing.
     #[allow(clippy::too_many_lines)]
     async fn scan_single_broker(
         &self,
-        scan_job_id: String,
+        broker_scan_id: String,
         broker_def: BrokerDefinition,
         profile_id: String,
-        vault_key: [u8; 32],
+        vault_key: &[u8; 32],
     ) -> Result<BrokerScanResult> {
         let broker_id = broker_def.broker.id.clone();
 
-        // Create broker_scan record
-        let broker_scan = spectral_db::broker_scans::create_broker_scan(
-            self.db.pool(),
-            scan_job_id.clone(),
-            broker_id.to_string(),
-        )
-        .await?;
+        if self.cancel.is_cancelled() {
+            return self.record_cancelled(&broker_scan_id, broker_id).await;
+        }
 
-        // Update status to InProgress
-        spectral_db::broker_scans::update_status(
-            self.db.pool(),
-            &broker_scan.id,
-            "InProgress",
-            None,
-        )
-        .await?;
+        broker_scans::mark_started(self.db.pool(), &broker_scan_id).await?;
 
-        // Build search URL from profile data and broker template
-        let search_url = match self
-            .build_search_url(&broker_def, &profile_id, &vault_key)
+        if !self.browser_available && broker_def.search.requires_browser() {
+            tracing::warn!("Skipping {}: search requires a browser", broker_id);
+            let reason = SkipReason::NoBrowser;
+            spectral_db::broker_scans::update_status(
+                self.db.pool(),
+                &broker_scan_id,
+                "Skipped",
+                Some(reason.to_string()),
+            )
+            .await?;
+
+            return Ok(BrokerScanResult {
+                broker_id,
+                findings_count: 0,
+                error: None,
+                skip_reason: Some(reason),
+            });
+        }
+
+        // Build search URLs from profile data and broker template, one per
+        // name variant
+        let search_urls = match self
+            .build_search_urls(&broker_def, &profile_id, vault_key)
             .await
         {
-            Ok(url) => url,
+            Ok(urls) => urls,
             Err(ScanError::MissingRequiredField(field)) => {
                 // Profile missing required field - mark as skipped
                 spectral_db::broker_scans::update_status(
                     self.db.pool(),
-                    &broker_scan.id,
+                    &broker_scan_id,
                     "Failed",
                     Some(format!("Profile missing required field: {field}")),
                 )
@@ -289,13 +675,14 @@ impl ScanOrchestrator {
                     broker_id,
                     findings_count: 0,
                     error: Some(format!("Missing required field: {field}")),
+                    skip_reason: None,
                 });
             }
             Err(e) => {
                 // Other error building URL
                 spectral_db::broker_scans::update_status(
                     self.db.pool(),
-                    &broker_scan.id,
+                    &broker_scan_id,
                     "Failed",
                     Some(format!("Failed to build search URL: {e}")),
                 )
@@ -305,18 +692,28 @@ impl ScanOrchestrator {
                     broker_id,
                     findings_count: 0,
                     error: Some(format!("URL building failed: {e}")),
+                    skip_reason: None,
                 });
             }
         };
 
-        // Fetch page with retry logic
-        let html = match self.fetch_with_retry(&search_url, &broker_id).await {
+        // Fetch page with retry logic, giving up as soon as the scan is cancelled
+        let (search_url, variant_urls) = search_urls
+            .split_first()
+            .expect("at least one search URL is built");
+        let html = match self
+            .fetch_unless_cancelled(search_url, &broker_id, &broker_def)
+            .await
+        {
             Ok(html) => html,
+            Err(ScanError::Cancelled) => {
+                return self.record_cancelled(&broker_scan_id, broker_id).await;
+            }
             Err(ScanError::CaptchaRequired { .. }) => {
                 // CAPTCHA detected - mark as failed, don't retry
                 spectral_db::broker_scans::update_status(
                     self.db.pool(),
-                    &broker_scan.id,
+                    &broker_scan_id,
                     "Failed",
                     Some("CAPTCHA required - manual intervention needed".to_string()),
                 )
@@ -326,13 +723,14 @@ impl ScanOrchestrator {
                     broker_id,
                     findings_count: 0,
                     error: Some("CAPTCHA challenge detected".to_string()),
+                    skip_reason: None,
                 });
             }
             Err(ScanError::RateLimited { retry_after, .. }) => {
                 // Rate limited - mark as failed with retry suggestion
                 spectral_db::broker_scans::update_status(
                     self.db.pool(),
-                    &broker_scan.id,
+                    &broker_scan_id,
                     "Failed",
                     Some(format!("Rate limited - retry after {retry_after:?}")),
                 )
@@ -342,13 +740,14 @@ impl ScanOrchestrator {
                     broker_id,
                     findings_count: 0,
                     error: Some("Rate limited".to_string()),
+                    skip_reason: None,
                 });
             }
             Err(e) => {
                 // Other error - mark as failed
                 spectral_db::broker_scans::update_status(
                     self.db.pool(),
-                    &broker_scan.id,
+                    &broker_scan_id,
                     "Failed",
                     Some(format!("Fetch error: {e}")),
                 )
@@ -358,36 +757,101 @@ impl ScanOrchestrator {
                     broker_id,
                     findings_count: 0,
                     error: Some(format!("Failed to fetch: {e}")),
+                    skip_reason: None,
                 });
             }
         };
 
-        // Parse results using ResultParser with broker-specific selectors
+        // Parse results, following "next page" links for paginated brokers
+        let mut matches = self.collect_listings(&html, &broker_def, &broker_id).await;
+
+        // Searches for other names add to the listings; listings found under
+        // several names are stored once
+        for url in variant_urls {
+            match self
+                .fetch_unless_cancelled(url, &broker_id, &broker_def)
+                .await
+            {
+                Ok(html) => {
+                    matches.extend(self.collect_listings(&html, &broker_def, &broker_id).await);
+                }
+                Err(ScanError::Cancelled) => break,
+                Err(e) => {
+                    tracing::warn!("Name variant search failed for {}: {}", broker_id, e);
+                }
+            }
+        }
+
         let findings_count = self
-            .parse_and_store_findings(&html, &broker_scan.id, &broker_id, &profile_id)
+            .store_findings(matches, &broker_scan_id, &broker_id, &profile_id)
             .await?;
 
         // Mark as success
-        spectral_db::broker_scans::update_status(self.db.pool(), &broker_scan.id, "Success", None)
+        spectral_db::broker_scans::update_status(self.db.pool(), &broker_scan_id, "Success", None)
             .await?;
 
         Ok(BrokerScanResult {
             broker_id,
             findings_count,
             error: None,
+            skip_reason: None,
+        })
+    }
+
+    /// Fetch `url` with retries, giving up as soon as the scan is cancelled.
+    async fn fetch_unless_cancelled(
+        &self,
+        url: &str,
+        broker_id: &BrokerId,
+        broker_def: &BrokerDefinition,
+    ) -> Result<String> {
+        tokio::select! {
+            () = self.cancel.cancelled() => Err(ScanError::Cancelled),
+            result = self.fetch_with_retry(url, broker_id, broker_def.request_headers()) => result,
+        }
+    }
+
+    /// Record a broker the scan was cancelled before finishing.
+    async fn record_cancelled(
+        &self,
+        broker_scan_id: &str,
+        broker_id: BrokerId,
+    ) -> Result<BrokerScanResult> {
+        let reason = SkipReason::Cancelled;
+        broker_scans::update_status(
+            self.db.pool(),
+            broker_scan_id,
+            "Cancelled",
+            Some(reason.to_string()),
+        )
+        .await?;
+
+        Ok(BrokerScanResult {
+            broker_id,
+            findings_count: 0,
+            error: None,
+            skip_reason: Some(reason),
         })
     }
 
     /// Fetch a page with retry logic and exponential backoff.
     ///
-    /// Retries transient errors up to `MAX_RETRIES` times with exponential backoff.
+    /// Makes up to `max_retries` attempts for transient errors, with exponential backoff.
     /// Rate limit errors use longer backoff. CAPTCHA errors are not retried.
-    async fn fetch_with_retry(&self, url: &str, broker_id: &BrokerId) -> Result<String> {
+    /// Every attempt waits for a token from the global request budget and
+    /// sends the broker's extra request `headers`.
+    async fn fetch_with_retry(
+        &self,
+        url: &str,
+        broker_id: &BrokerId,
+        headers: &HashMap<String, String>,
+    ) -> Result<String> {
         let mut last_error = None;
         let mut backoff_multiplier = 1;
 
-        for attempt in 0..MAX_RETRIES {
-            match self.browser_engine.fetch_page_content(url).await {
+        for attempt in 0..self.max_retries {
+            self.rate_limiter.acquire().await;
+            match self.fetcher.fetch(url, headers).await {
                 Ok(html) => {
                     // Check for CAPTCHA in HTML before returning
                     if Self::detect_captcha(&html) {
@@ -406,16 +870,14 @@ impl ScanOrchestrator {
 
                     last_error = Some(e);
 
-                    if attempt < MAX_RETRIES - 1 {
-                        let delay = Duration::from_millis(
-                            RETRY_DELAY_MS * backoff_multiplier * (u64::from(attempt) + 1),
-                        );
+                    if attempt < self.max_retries - 1 {
+                        let delay = retry_delay(attempt, backoff_multiplier, &self.rng);
 
                         tracing::warn!(
                             "Fetch failed for {} (attempt {}/{}), retrying in {:?}...",
                             broker_id,
                             attempt + 1,
-                            MAX_RETRIES,
+                            self.max_retries,
                             delay
                         );
 
@@ -436,7 +898,7 @@ impl ScanOrchestrator {
         }
 
         Err(ScanError::Browser(last_error.expect(
-            "last_error should be Some after MAX_RETRIES attempts",
+            "last_error should be Some after max_retries attempts",
         )))
     }
 
@@ -461,7 +923,7 @@ impl ScanOrchestrator {
         field: spectral_core::PiiField,
         profile: &UserProfile,
         vault_key: &[u8; 32],
-    ) -> Result<(&'static str, String)> {
+    ) -> Result<(&'static str, Zeroizing<String>)> {
         use spectral_core::PiiField;
 
         match field {
@@ -470,7 +932,7 @@ impl ScanOrchestrator {
                     .first_name
                     .as_ref()
                     .ok_or_else(|| ScanError::MissingRequiredField("first_name".to_string()))?
-                    .decrypt(vault_key)
+                    .decrypt_zeroizing(vault_key)
                     .map_err(|e| {
                         ScanError::DecryptionFailed(format!("Failed to decrypt first_name: {e}"))
                     })?;
@@ -481,7 +943,7 @@ impl ScanOrchestrator {
                     .middle_name
                     .as_ref()
                     .ok_or_else(|| ScanError::MissingRequiredField("middle_name".to_string()))?
-                    .decrypt(vault_key)
+                    .decrypt_zeroizing(vault_key)
                     .map_err(|e| {
                         ScanError::DecryptionFailed(format!("Failed to decrypt middle_name: {e}"))
                     })?;
@@ -492,7 +954,7 @@ impl ScanOrchestrator {
                     .last_name
                     .as_ref()
                     .ok_or_else(|| ScanError::MissingRequiredField("last_name".to_string()))?
-                    .decrypt(vault_key)
+                    .decrypt_zeroizing(vault_key)
                     .map_err(|e| {
                         ScanError::DecryptionFailed(format!("Failed to decrypt last_name: {e}"))
                     })?;
@@ -503,7 +965,7 @@ impl ScanOrchestrator {
                     .address
                     .as_ref()
                     .ok_or_else(|| ScanError::MissingRequiredField("address".to_string()))?
-                    .decrypt(vault_key)
+                    .decrypt_zeroizing(vault_key)
                     .map_err(|e| {
                         ScanError::DecryptionFailed(format!("Failed to decrypt address: {e}"))
                     })?;
@@ -514,7 +976,7 @@ impl ScanOrchestrator {
                     .city
                     .as_ref()
                     .ok_or_else(|| ScanError::MissingRequiredField("city".to_string()))?
-                    .decrypt(vault_key)
+                    .decrypt_zeroizing(vault_key)
                     .map_err(|e| {
                         ScanError::DecryptionFailed(format!("Failed to decrypt city: {e}"))
                     })?;
@@ -525,7 +987,7 @@ impl ScanOrchestrator {
                     .state
                     .as_ref()
                     .ok_or_else(|| ScanError::MissingRequiredField("state".to_string()))?
-                    .decrypt(vault_key)
+                    .decrypt_zeroizing(vault_key)
                     .map_err(|e| {
                         ScanError::DecryptionFailed(format!("Failed to decrypt state: {e}"))
                     })?;
@@ -536,7 +998,7 @@ impl ScanOrchestrator {
                     .zip_code
                     .as_ref()
                     .ok_or_else(|| ScanError::MissingRequiredField("zip_code".to_string()))?
-                    .decrypt(vault_key)
+                    .decrypt_zeroizing(vault_key)
                     .map_err(|e| {
                         ScanError::DecryptionFailed(format!("Failed to decrypt zip_code: {e}"))
                     })?;
@@ -550,7 +1012,7 @@ impl ScanOrchestrator {
                         .email
                         .as_ref()
                         .ok_or_else(|| ScanError::MissingRequiredField("email".to_string()))?
-                        .decrypt(vault_key)
+                        .decrypt_zeroizing(vault_key)
                         .map_err(|e| {
                             ScanError::DecryptionFailed(format!("Failed to decrypt email: {e}"))
                         })?
@@ -560,7 +1022,7 @@ impl ScanOrchestrator {
                         .first()
                         .ok_or_else(|| ScanError::MissingRequiredField("email".to_string()))?
                         .email
-                        .decrypt(vault_key)
+                        .decrypt_zeroizing(vault_key)
                         .map_err(|e| {
                             ScanError::DecryptionFailed(format!("Failed to decrypt email: {e}"))
                         })?
@@ -575,7 +1037,7 @@ impl ScanOrchestrator {
                         .phone
                         .as_ref()
                         .ok_or_else(|| ScanError::MissingRequiredField("phone".to_string()))?
-                        .decrypt(vault_key)
+                        .decrypt_zeroizing(vault_key)
                         .map_err(|e| {
                             ScanError::DecryptionFailed(format!("Failed to decrypt phone: {e}"))
                         })?
@@ -585,7 +1047,7 @@ impl ScanOrchestrator {
                         .first()
                         .ok_or_else(|| ScanError::MissingRequiredField("phone".to_string()))?
                         .number
-                        .decrypt(vault_key)
+                        .decrypt_zeroizing(vault_key)
                         .map_err(|e| {
                             ScanError::DecryptionFailed(format!("Failed to decrypt phone: {e}"))
                         })?
@@ -597,7 +1059,7 @@ impl ScanOrchestrator {
                     .date_of_birth
                     .as_ref()
                     .ok_or_else(|| ScanError::MissingRequiredField("date_of_birth".to_string()))?
-                    .decrypt(vault_key)
+                    .decrypt_zeroizing(vault_key)
                     .map_err(|e| {
                         ScanError::DecryptionFailed(format!("Failed to decrypt date_of_birth: {e}"))
                     })?;
@@ -608,7 +1070,7 @@ impl ScanOrchestrator {
                     .full_name
                     .as_ref()
                     .ok_or_else(|| ScanError::MissingRequiredField("full_name".to_string()))?
-                    .decrypt(vault_key)
+                    .decrypt_zeroizing(vault_key)
                     .map_err(|e| {
                         ScanError::DecryptionFailed(format!("Failed to decrypt full_name: {e}"))
                     })?;
@@ -619,7 +1081,7 @@ impl ScanOrchestrator {
                     .country
                     .as_ref()
                     .ok_or_else(|| ScanError::MissingRequiredField("country".to_string()))?
-                    .decrypt(vault_key)
+                    .decrypt_zeroizing(vault_key)
                     .map_err(|e| {
                         ScanError::DecryptionFailed(format!("Failed to decrypt country: {e}"))
                     })?;
@@ -630,7 +1092,7 @@ impl ScanOrchestrator {
                     .ssn
                     .as_ref()
                     .ok_or_else(|| ScanError::MissingRequiredField("ssn".to_string()))?
-                    .decrypt(vault_key)
+                    .decrypt_zeroizing(vault_key)
                     .map_err(|e| {
                         ScanError::DecryptionFailed(format!("Failed to decrypt ssn: {e}"))
                     })?;
@@ -641,7 +1103,7 @@ impl ScanOrchestrator {
                     .employer
                     .as_ref()
                     .ok_or_else(|| ScanError::MissingRequiredField("employer".to_string()))?
-                    .decrypt(vault_key)
+                    .decrypt_zeroizing(vault_key)
                     .map_err(|e| {
                         ScanError::DecryptionFailed(format!("Failed to decrypt employer: {e}"))
                     })?;
@@ -652,7 +1114,7 @@ impl ScanOrchestrator {
                     .job_title
                     .as_ref()
                     .ok_or_else(|| ScanError::MissingRequiredField("job_title".to_string()))?
-                    .decrypt(vault_key)
+                    .decrypt_zeroizing(vault_key)
                     .map_err(|e| {
                         ScanError::DecryptionFailed(format!("Failed to decrypt job_title: {e}"))
                     })?;
@@ -663,7 +1125,7 @@ impl ScanOrchestrator {
                     .education
                     .as_ref()
                     .ok_or_else(|| ScanError::MissingRequiredField("education".to_string()))?
-                    .decrypt(vault_key)
+                    .decrypt_zeroizing(vault_key)
                     .map_err(|e| {
                         ScanError::DecryptionFailed(format!("Failed to decrypt education: {e}"))
                     })?;
@@ -675,7 +1137,7 @@ impl ScanOrchestrator {
                     .social_media
                     .as_ref()
                     .ok_or_else(|| ScanError::MissingRequiredField("social_media".to_string()))?
-                    .decrypt(vault_key)
+                    .decrypt_zeroizing(vault_key)
                     .map_err(|e| {
                         ScanError::DecryptionFailed(format!("Failed to decrypt social_media: {e}"))
                     })?
@@ -686,7 +1148,7 @@ impl ScanOrchestrator {
                         )
                     })?
                     .clone();
-                Ok(("{social_media}", val))
+                Ok(("{social_media}", Zeroizing::new(val)))
             }
             PiiField::Relatives => {
                 // Use first relative's full name
@@ -698,16 +1160,18 @@ impl ScanOrchestrator {
                 let first_name = relative
                     .first_name
                     .as_ref()
-                    .and_then(|f| f.decrypt(vault_key).ok())
+                    .and_then(|f| f.decrypt_zeroizing(vault_key).ok())
                     .unwrap_or_default();
 
                 let last_name = relative
                     .last_name
                     .as_ref()
-                    .and_then(|f| f.decrypt(vault_key).ok())
+                    .and_then(|f| f.decrypt_zeroizing(vault_key).ok())
                     .unwrap_or_default();
 
-                let val = format!("{first_name} {last_name}").trim().to_string();
+                let full_name =
+                    Zeroizing::new(format!("{} {}", first_name.as_str(), last_name.as_str()));
+                let val = Zeroizing::new(full_name.trim().to_string());
                 if val.is_empty() {
                     return Err(ScanError::MissingRequiredField(
                         "relatives (no name data)".to_string(),
@@ -721,34 +1185,59 @@ impl ScanOrchestrator {
                     ScanError::MissingRequiredField("previous_address".to_string())
                 })?;
 
-                let address = prev_addr.address_line1.decrypt(vault_key).map_err(|e| {
-                    ScanError::DecryptionFailed(format!(
-                        "Failed to decrypt previous address line 1: {e}"
-                    ))
-                })?;
+                let address = prev_addr
+                    .address_line1
+                    .decrypt_zeroizing(vault_key)
+                    .map_err(|e| {
+                        ScanError::DecryptionFailed(format!(
+                            "Failed to decrypt previous address line 1: {e}"
+                        ))
+                    })?;
 
-                let city = prev_addr.city.decrypt(vault_key).map_err(|e| {
+                let city = prev_addr.city.decrypt_zeroizing(vault_key).map_err(|e| {
                     ScanError::DecryptionFailed(format!(
                         "Failed to decrypt previous address city: {e}"
                     ))
                 })?;
 
-                let state = prev_addr.state.decrypt(vault_key).map_err(|e| {
+                let state = prev_addr.state.decrypt_zeroizing(vault_key).map_err(|e| {
                     ScanError::DecryptionFailed(format!(
                         "Failed to decrypt previous address state: {e}"
                     ))
                 })?;
 
-                let zip = prev_addr.zip_code.decrypt(vault_key).map_err(|e| {
-                    ScanError::DecryptionFailed(format!(
-                        "Failed to decrypt previous address zip: {e}"
-                    ))
-                })?;
+                let zip = prev_addr
+                    .zip_code
+                    .decrypt_zeroizing(vault_key)
+                    .map_err(|e| {
+                        ScanError::DecryptionFailed(format!(
+                            "Failed to decrypt previous address zip: {e}"
+                        ))
+                    })?;
 
-                let val = format!("{address}, {city}, {state} {zip}");
+                let val = Zeroizing::new(format!(
+                    "{}, {}, {} {}",
+                    address.as_str(),
+                    city.as_str(),
+                    state.as_str(),
+                    zip.as_str()
+                ));
                 Ok(("{previous_address}", val))
             }
-            PiiField::Age | PiiField::IpAddress | PiiField::Photo | PiiField::Other => {
+            PiiField::Age => {
+                // Age is derived from the stored date of birth
+                let date_of_birth = profile
+                    .date_of_birth
+                    .as_ref()
+                    .ok_or_else(|| ScanError::MissingRequiredField("date_of_birth".to_string()))?
+                    .decrypt_zeroizing(vault_key)
+                    .map_err(|e| {
+                        ScanError::DecryptionFailed(format!("Failed to decrypt date_of_birth: {e}"))
+                    })?;
+                let age = age_on(&date_of_birth, chrono::Local::now().date_naive())?;
+                Ok(("{age}", Zeroizing::new(age.to_string())))
+            }
+            PiiField::IpAddress | PiiField::Photo | PiiField::Other => {
                 // These fields are not stored in UserProfile or cannot be derived
                 tracing::warn!("Unsupported PII field (not stored in profile): {:?}", field);
                 Err(ScanError::MissingRequiredField(format!(
@@ -758,17 +1247,19 @@ impl ScanOrchestrator {
         }
     }
 
-    /// Build search URL from broker definition and profile data.
+    /// Build the search URLs for a broker from its definition and the
+    /// profile, the profile's own name first.
     ///
-    /// Loads the profile from database, decrypts required fields,
-    /// and substitutes them into the URL template.
-    #[allow(clippy::too_many_lines)]
-    async fn build_search_url(
+    /// Loads the profile from database, decrypts required fields, and
+    /// substitutes them into the URL template once per name variant.
+    /// Variants that give the same URL, as for templates without a name,
+    /// are searched once.
+    async fn build_search_urls(
         &self,
         broker_def: &BrokerDefinition,
         profile_id: &str,
         vault_key: &[u8; 32],
-    ) -> Result<String> {
+    ) -> Result<Vec<String>> {
         use spectral_broker::SearchMethod;
 
         match &broker_def.search {
@@ -781,7 +1272,7 @@ impl ScanOrchestrator {
                 let profile_id_typed = spectral_core::ProfileId::new(profile_id.to_string())
                     .map_err(|e| ScanError::ProfileDataError {
                         broker_id: broker_def.broker.id.clone(),
-                        reason: format!("Invalid profile ID: {e}"),
+                        reason: e.to_string(),
                     })?;
                 let profile = UserProfile::load(&self.db, &profile_id_typed, vault_key)
                     .await
@@ -790,36 +1281,94 @@ impl ScanOrchestrator {
                         reason: format!("Failed to load profile: {e}"),
                     })?;
 
-                let mut url = template.clone();
-
-                // Substitute each required field
-                for field in requires_fields {
-                    // Extract field value using helper function
-                    let Ok((placeholder, value)) =
-                        Self::extract_pii_field_value(*field, &profile, vault_key)
-                    else {
-                        // Skip unsupported fields
-                        continue;
-                    };
-
-                    // URL encode the value and substitute
-                    let encoded = urlencoding::encode(&value);
-                    url = url.replace(placeholder, &encoded);
+                let mut urls =
+                    vec![self.fill_template(template, requires_fields, &profile, vault_key, None)];
+
+                let max_variants = match self.disclosure {
+                    ScanDisclosure::Minimal => 1,
+                    ScanDisclosure::Full => self.max_name_variants,
+                };
+                for variant in name_variants(&profile, vault_key, max_variants)
+                    .iter()
+                    .skip(1)
+                {
+                    let url = self.fill_template(
+                        template,
+                        requires_fields,
+                        &profile,
+                        vault_key,
+                        Some(variant),
+                    );
+                    if !urls.contains(&url) {
+                        urls.push(url);
+                    }
                 }
 
-                Ok(url)
+                Ok(urls)
             }
             SearchMethod::WebForm { url, .. } => {
                 // For now, just return the form URL - form submission not yet implemented
-                Ok(url.clone())
+                Ok(vec![url.clone()])
             }
             SearchMethod::Manual { url, .. } => {
                 // Manual search - return the URL for user to visit
-                Ok(url.clone())
+                Ok(vec![url.clone()])
             }
         }
     }
 
+    /// Substitute the profile's required fields into a URL template.
+    ///
+    /// With a `name`, its first and last name replace the profile's own in
+    /// the name placeholders.
+    fn fill_template(
+        &self,
+        template: &str,
+        requires_fields: &[spectral_core::PiiField],
+        profile: &UserProfile,
+        vault_key: &[u8; 32],
+        name: Option<&NameVariant>,
+    ) -> String {
+        use spectral_core::PiiField;
+
+        let mut url = match self.disclosure {
+            ScanDisclosure::Minimal => remove_optional_placeholders(template, requires_fields),
+            ScanDisclosure::Full => template.to_string(),
+        };
+        let address_format =
+            AddressFormat::for_country(profile_country(profile, vault_key).as_deref());
+
+        // Substitute each required field
+        for field in requires_fields {
+            let named = name.and_then(|name| match field {
+                PiiField::FirstName => Some(("{first_name}", name.first_name.clone())),
+                PiiField::LastName => Some(("{last_name}", name.last_name.clone())),
+                PiiField::FullName => Some(("{full_name}", name.full_name())),
+                _ => None,
+            });
+
+            // Extract field value using helper function
+            let extracted = match named {
+                Some(value) => Ok(value),
+                None => Self::extract_pii_field_value(*field, profile, vault_key),
+            };
+            let Ok((placeholder, value)) = extracted else {
+                // Countries without states (e.g. the UK) leave the
+                // state segment empty; skip other unsupported fields
+                if !address_format.expects_field(*field) {
+                    url = remove_state_placeholder(&url);
+                }
+                continue;
+            };
+
+            // URL encode the value and substitute
+            let encoded = urlencoding::encode(&value);
+            url = url.replace(placeholder, &encoded);
+        }
+
+        url
+    }
+
     /// Parse HTML and store findings in database.
     ///
     /// Uses `ResultParser` with configured selectors to extract structured data
@@ -859,6 +1408,59 @@ impl ScanOrchestrator {
             }
         };
 
+        self.store_findings(matches, broker_scan_id, broker_id, profile_id)
+            .await
+    }
+
+    /// Parse the first results page and, unless pagination is off, any
+    /// follow-up pages.
+    ///
+    /// Pages after the first are fetched through the same retry logic and
+    /// request budget as the first. Parse errors are logged and yield no
+    /// listings rather than failing the scan.
+    async fn collect_listings(
+        &self,
+        first_page: &str,
+        broker_def: &BrokerDefinition,
+        broker_id: &BrokerId,
+    ) -> Vec<crate::parser::ListingMatch> {
+        let Some(result_selectors) = broker_def.search.result_selectors() else {
+            tracing::warn!(
+                "Broker {} has no result selectors, skipping parsing",
+                broker_id
+            );
+            return Vec::new();
+        };
+
+        let parser =
+            crate::parser::ResultParser::new(result_selectors, broker_def.broker.url.clone());
+        if !self.follow_pagination {
+            return parser.parse(first_page).unwrap_or_else(|e| {
+                tracing::warn!("Failed to parse results for {}: {}", broker_id, e);
+                Vec::new()
+            });
+        }
+        let headers = broker_def.request_headers();
+        let fetch_page =
+            |url: String| async move { self.fetch_with_retry(&url, broker_id, headers).await };
+
+        match parser.parse_pages(first_page, fetch_page).await {
+            Ok(matches) => matches,
+            Err(e) => {
+                tracing::warn!("Failed to parse results for {}: {}", broker_id, e);
+                Vec::new()
+            }
+        }
+    }
+
+    /// Store parsed listings as findings, skipping URLs already recorded for the scan job.
+    async fn store_findings(
+        &self,
+        matches: Vec<crate::parser::ListingMatch>,
+        broker_scan_id: &str,
+        broker_id: &BrokerId,
+        profile_id: &str,
+    ) -> Result<usize> {
         // Get scan_job_id from broker_scan record
         let scan_job_id =
             sqlx::query_scalar::<_, String>("SELECT scan_job_id FROM broker_scans WHERE id = ?")
@@ -866,6 +1468,11 @@ impl ScanOrchestrator {
                 .fetch_one(self.db.pool())
                 .await?;
 
+        let broker_weight = self
+            .broker_registry
+            .get(broker_id)
+            .map_or(1.0, |def| def.category().risk_weight());
+
         let mut created_count = 0;
         let mut skipped_count = 0;
 
@@ -889,7 +1496,7 @@ impl ScanOrchestrator {
             let extracted_json = extracted_data_to_json(&listing_match.extracted_data);
 
             // Create finding record with PendingVerification status
-            spectral_db::findings::create_finding(
+            let finding = spectral_db::findings::create_finding(
                 self.db.pool(),
                 broker_scan_id.to_string(),
                 broker_id.to_string(),
@@ -899,9 +1506,26 @@ impl ScanOrchestrator {
             )
             .await?;
 
+            // The broker's own match label is the only confidence we have yet
+            spectral_db::findings::set_review_priority(
+                self.db.pool(),
+                &finding.id,
+                listing_match.extracted_data.source_confidence,
+                broker_weight,
+            )
+            .await?;
+
+            self.db.notify(DbChange::FindingCreated {
+                scan_job_id: scan_job_id.clone(),
+                finding_id: finding.id,
+                broker_id: broker_id.to_string(),
+            });
+
             created_count += 1;
         }
 
+        metrics::global().add(Counter::FindingsCreated, created_count as u64);
+
         if skipped_count > 0 {
             tracing::debug!(
                 "Skipped {} duplicate findings for broker {}",
@@ -919,6 +1543,7 @@ fn extracted_data_to_json(data: &crate::parser::ExtractedData) -> serde_json::Va
     serde_json::json!({
         "name": data.name,
         "age": data.age,
+        "age_range": data.age_range,
         "addresses": data.addresses,
         "phone_numbers": data.phone_numbers,
         "relatives": data.relatives,
@@ -926,9 +1551,57 @@ fn extracted_data_to_json(data: &crate::parser::ExtractedData) -> serde_json::Va
     })
 }
 
+/// Compute a person's age in whole years on `today`.
+///
+/// `date_of_birth` is an ISO 8601 date (`YYYY-MM-DD`); a trailing time
+/// component is ignored.
+fn age_on(date_of_birth: &str, today: chrono::NaiveDate) -> Result<u32> {
+    let trimmed = date_of_birth.trim();
+    let date = trimmed.get(..10).unwrap_or(trimmed);
+    let dob = chrono::NaiveDate::parse_from_str(date, "%Y-%m-%d")
+        .map_err(|e| ScanError::Parse(format!("invalid date_of_birth: {e}")))?;
+
+    today
+        .years_since(dob)
+        .ok_or_else(|| ScanError::Parse("date_of_birth is in the future".to_string()))
+}
+
+/// Delay before retrying after failed attempt `attempt` (zero-based).
+///
+/// Grows linearly with the attempt number, scaled by `backoff_multiplier`,
+/// plus random jitter of up to a quarter of the delay.
+fn retry_delay(attempt: u32, backoff_multiplier: u64, rng: &RngSource) -> Duration {
+    let base = RETRY_DELAY_MS * backoff_multiplier * (u64::from(attempt) + 1);
+    Duration::from_millis(base + rng.gen_range(0..=base / RETRY_JITTER_DIVISOR))
+}
+
+/// Number of `results` for brokers that were scanned rather than cancelled.
+#[allow(clippy::cast_possible_truncation)]
+fn scanned_count(results: &[BrokerScanResult]) -> u32 {
+    results
+        .iter()
+        .filter(|r| r.skip_reason != Some(SkipReason::Cancelled))
+        .count() as u32
+}
+
+/// Total time to run `durations` in order with at most `concurrency` at once,
+/// each starting as soon as a slot frees up.
+fn concurrent_duration(durations: &[Duration], concurrency: usize) -> Duration {
+    let mut slots = vec![Duration::ZERO; concurrency.clamp(1, durations.len().max(1))];
+    for duration in durations {
+        if let Some(earliest) = slots.iter_mut().min() {
+            *earliest += *duration;
+        }
+    }
+    slots.into_iter().max().unwrap_or_default()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use chrono::{Datelike, NaiveDate};
+    use spectral_core::PiiField;
+    use spectral_vault::cipher::encrypt_string;
 
     #[test]
     fn test_retry_constants() {
@@ -962,6 +1635,22 @@ mod tests {
         const _: () = assert!(DEFAULT_MAX <= 20);
     }
 
+    #[test]
+    fn test_concurrent_duration() {
+        let secs = Duration::from_secs;
+
+        assert_eq!(concurrent_duration(&[], 5), Duration::ZERO);
+        assert_eq!(concurrent_duration(&[secs(3); 10], 5), secs(6));
+        assert_eq!(concurrent_duration(&[secs(3); 10], 1), secs(30));
+        // A slow broker holds one slot while the others share the rest
+        assert_eq!(
+            concurrent_duration(&[secs(20), secs(2), secs(2), secs(2)], 2),
+            secs(20)
+        );
+        // Zero concurrency is treated as one slot
+        assert_eq!(concurrent_duration(&[secs(1), secs(1)], 0), secs(2));
+    }
+
     #[test]
     fn test_rate_limit_backoff() {
         // Verify rate limit backoff is longer than normal backoff
@@ -971,6 +1660,25 @@ mod tests {
         assert!(rate_limit_backoff >= 3 * normal_backoff);
     }
 
+    #[test]
+    fn test_retry_delay_jitter() {
+        let seeded: Vec<Duration> = (0..3)
+            .map(|attempt| retry_delay(attempt, 1, &RngSource::seeded(11)))
+            .collect();
+        let reseeded: Vec<Duration> = (0..3)
+            .map(|attempt| retry_delay(attempt, 1, &RngSource::seeded(11)))
+            .collect();
+        assert_eq!(seeded, reseeded);
+
+        let rng = RngSource::os();
+        for attempt in 0..3 {
+            let base = RETRY_DELAY_MS * (u64::from(attempt) + 1);
+            let delay = retry_delay(attempt, 1, &rng);
+            assert!(delay >= Duration::from_millis(base));
+            assert!(delay <= Duration::from_millis(base + base / RETRY_JITTER_DIVISOR));
+        }
+    }
+
     #[test]
     fn test_extracted_data_to_json() {
         use crate::parser;
@@ -978,18 +1686,73 @@ mod tests {
         let data = parser::ExtractedData {
             name: Some("John Doe".to_string()),
             age: Some(30),
+            age_range: Some((30, 34)),
             addresses: vec!["123 Main St".to_string()],
             phone_numbers: vec!["555-1234".to_string()],
             relatives: vec!["Jane Doe".to_string()],
             emails: vec!["john@example.com".to_string()],
+            source_confidence: Some(0.75),
         };
 
         let json = extracted_data_to_json(&data);
         assert_eq!(json["name"], "John Doe");
         assert_eq!(json["age"], 30);
+        assert_eq!(json["age_range"], serde_json::json!([30, 34]));
         assert_eq!(json["addresses"], serde_json::json!(["123 Main St"]));
         assert_eq!(json["phone_numbers"], serde_json::json!(["555-1234"]));
         assert_eq!(json["relatives"], serde_json::json!(["Jane Doe"]));
         assert_eq!(json["emails"], serde_json::json!(["john@example.com"]));
     }
+
+    #[test]
+    fn test_age_on() {
+        let today = NaiveDate::from_ymd_opt(2025, 6, 15).expect("valid date");
+
+        assert_eq!(age_on("1990-06-15", today).expect("valid dob"), 35);
+        assert_eq!(age_on("1990-06-16", today).expect("valid dob"), 34);
+        assert_eq!(
+            age_on("1990-01-01T00:00:00Z", today).expect("valid dob"),
+            35
+        );
+        assert!(matches!(
+            age_on("06/15/1990", today),
+            Err(ScanError::Parse(_))
+        ));
+        assert!(matches!(
+            age_on("2030-01-01", today),
+            Err(ScanError::Parse(_))
+        ));
+    }
+
+    #[test]
+    fn test_extract_age_from_date_of_birth() {
+        let key = [0x42; 32];
+        let today = chrono::Local::now().date_naive();
+        // Born 40 years ago yesterday, so the birthday has already passed
+        let yesterday = today.pred_opt().expect("valid date");
+        let dob = yesterday
+            .with_year(yesterday.year() - 40)
+            .unwrap_or_else(|| {
+                NaiveDate::from_ymd_opt(yesterday.year() - 40, 2, 28).expect("valid date")
+            });
+
+        let mut profile = UserProfile::new(spectral_core::ProfileId::generate());
+        profile.date_of_birth =
+            Some(encrypt_string(&dob.format("%Y-%m-%d").to_string(), &key).expect("encrypt dob"));
+
+        let (placeholder, value) =
+            ScanOrchestrator::extract_pii_field_value(PiiField::Age, &profile, &key)
+                .expect("age should be derived from date_of_birth");
+        assert_eq!(placeholder, "{age}");
+        assert_eq!(*value, "40");
+    }
+
+    #[test]
+    fn test_extract_age_without_date_of_birth() {
+        let key = [0x42; 32];
+        let profile = UserProfile::new(spectral_core::ProfileId::generate());
+
+        let result = ScanOrchestrator::extract_pii_field_value(PiiField::Age, &profile, &key);
+        assert!(matches!(result, Err(ScanError::MissingRequiredField(_))));
+    }
 }
@@ -8,6 +8,8 @@ use scraper::{ElementRef, Html, Selector};
 use serde::{Deserialize, Serialize};
 use spectral_broker::definition::ResultSelectors;
 use spectral_core::BrokerId;
+use std::collections::HashSet;
+use std::future::Future;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ListingMatch {
@@ -19,10 +21,117 @@ pub struct ListingMatch {
 pub struct ExtractedData {
     pub name: Option<String>,
     pub age: Option<u32>,
+    /// Inclusive age range for listings that show one (e.g. "Age 45-49")
+    /// instead of an exact age
+    #[serde(default)]
+    pub age_range: Option<(u32, u32)>,
     pub addresses: Vec<String>,
     pub phone_numbers: Vec<String>,
     pub relatives: Vec<String>,
     pub emails: Vec<String>,
+    /// How strongly the broker itself says the listing matches the search,
+    /// from 0.0 to 1.0, read from its match indicator
+    #[serde(default)]
+    pub source_confidence: Option<f64>,
+}
+
+/// Oldest age accepted from a listing; larger numbers are years or IDs.
+const MAX_LISTING_AGE: u32 = 120;
+
+/// Broker match labels and the confidence each implies. Labels are matched
+/// as whole words, the first listed winning when an indicator has several.
+const MATCH_LABELS: &[(&str, f64)] = &[
+    ("unlikely", 0.25),
+    ("weak", 0.25),
+    ("low", 0.25),
+    ("exact", 0.95),
+    ("best", 0.9),
+    ("strong", 0.9),
+    ("high", 0.9),
+    ("likely", 0.75),
+    ("good", 0.75),
+    ("possible", 0.5),
+    ("partial", 0.5),
+    ("medium", 0.5),
+];
+
+/// Words that deny the match label after them, as in "No exact match" or
+/// "not likely".
+const NEGATIONS: &[&str] = &["no", "not", "non"];
+
+/// Confidence for a match label that is negated.
+const NEGATED_MATCH_CONFIDENCE: f64 = 0.25;
+
+/// Parse an age cell such as "35", "Age: 35" or "Age 45-49".
+///
+/// Returns the exact age, or the inclusive range when the first two numbers
+/// are joined by a dash or "to".
+fn parse_age(text: &str) -> (Option<u32>, Option<(u32, u32)>) {
+    let mut numbers = Vec::new();
+    let mut start = None;
+    for (i, c) in text
+        .char_indices()
+        .chain(std::iter::once((text.len(), ' ')))
+    {
+        match (c.is_ascii_digit(), start) {
+            (true, None) => start = Some(i),
+            (false, Some(s)) => {
+                numbers.push((s, i));
+                start = None;
+            }
+            _ => {}
+        }
+    }
+    let value = |(s, e): (usize, usize)| {
+        text[s..e]
+            .parse::<u32>()
+            .ok()
+            .filter(|age| *age <= MAX_LISTING_AGE)
+    };
+
+    match numbers.as_slice() {
+        [] => (None, None),
+        [first, second, ..] => {
+            let between = text[first.1..second.0].trim().to_lowercase();
+            let is_range = matches!(between.as_str(), "-" | "\u{2013}" | "\u{2014}" | "to");
+            match (value(*first), value(*second)) {
+                (Some(low), Some(high)) if is_range => (None, Some((low.min(high), low.max(high)))),
+                (age, _) => (age, None),
+            }
+        }
+        [only] => (value(*only), None),
+    }
+}
+
+/// Read a broker's match indicator, e.g. "Likely match" or "92% match", as
+/// a confidence from 0.0 to 1.0.
+fn parse_match_confidence(text: &str) -> Option<f64> {
+    let text = text.to_lowercase();
+    if let Some(percent_at) = text.find('%') {
+        let before = text[..percent_at].trim_end();
+        let digits = &before[before.trim_end_matches(|c: char| c.is_ascii_digit()).len()..];
+        if let Ok(percent) = digits.parse::<u32>() {
+            return Some(f64::from(percent.min(100)) / 100.0);
+        }
+    }
+    let words: Vec<&str> = text
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .collect();
+    MATCH_LABELS.iter().find_map(|(label, confidence)| {
+        let at = words.iter().position(|word| word == label)?;
+        // A negation up to two words earlier, as in "not a good match"
+        let negated = words[..at]
+            .iter()
+            .rev()
+            .take(2)
+            .any(|word| NEGATIONS.contains(word));
+        Some(if negated {
+            NEGATED_MATCH_CONFIDENCE
+        } else {
+            *confidence
+        })
+    })
 }
 
 pub struct ResultParser<'a> {
@@ -101,13 +210,7 @@ impl<'a> ResultParser<'a> {
             .select(&url_selector)
             .next()
             .and_then(|el| el.value().attr("href"))
-            .map(|href| {
-                if href.starts_with("http") {
-                    href.to_string()
-                } else {
-                    format!("{}{}", self.base_url, href)
-                }
-            });
+            .map(|href| self.resolve_url(href));
 
         if listing_url.is_none() {
             return Ok(None);
@@ -115,24 +218,119 @@ impl<'a> ResultParser<'a> {
 
         // Extract data fields
         let name = self.extract_text(element, &self.selectors.name);
-        let age = self
+        let (age, age_range) = self
             .extract_text(element, &self.selectors.age)
-            .and_then(|s| s.parse::<u32>().ok());
+            .map_or((None, None), |text| parse_age(&text));
         let location = self.extract_text(element, &self.selectors.location);
+        let source_confidence = self
+            .extract_text(element, &self.selectors.match_indicator)
+            .and_then(|text| parse_match_confidence(&text));
 
         Ok(Some(ListingMatch {
             listing_url: listing_url.expect("listing_url is Some after is_none check"),
             extracted_data: ExtractedData {
                 name,
                 age,
+                age_range,
                 addresses: location.into_iter().collect(),
                 phone_numbers: vec![],
                 relatives: vec![],
                 emails: vec![],
+                source_confidence,
             },
         }))
     }
 
+    /// Find the link to the next page of results, if the broker paginates.
+    ///
+    /// Returns `None` when the definition has no `next_page` selector, the
+    /// selector is invalid, or the page has no next link (i.e. it is the last).
+    pub fn next_page_url(&self, html: &str) -> Option<String> {
+        let selector = Selector::parse(self.selectors.next_page.as_ref()?).ok()?;
+        let document = Html::parse_document(html);
+        let href = document
+            .select(&selector)
+            .next()?
+            .value()
+            .attr("href")?
+            .trim();
+        if href.is_empty() || href.starts_with('#') {
+            return None;
+        }
+        Some(self.resolve_url(href))
+    }
+
+    /// Parse the first page of results and follow "next page" links.
+    ///
+    /// `fetch` loads a follow-up page by URL. At most
+    /// [`ResultSelectors::page_limit`] pages are parsed, and a URL already
+    /// visited is never fetched again. Listings are deduplicated by URL,
+    /// keeping the first occurrence.
+    ///
+    /// Errors on the first page are returned. A failure to fetch or parse a
+    /// later page stops pagination and keeps the listings found so far.
+    pub async fn parse_pages<F, Fut>(
+        &self,
+        first_page: &str,
+        mut fetch: F,
+    ) -> Result<Vec<ListingMatch>>
+    where
+        F: FnMut(String) -> Fut,
+        Fut: Future<Output = Result<String>>,
+    {
+        let page_limit = self.selectors.page_limit();
+        let mut seen_listings = HashSet::new();
+        let mut visited_pages = HashSet::new();
+        let mut matches = Vec::new();
+
+        let mut html = first_page.to_string();
+        let mut page = 1;
+        loop {
+            let page_matches = match self.parse(&html) {
+                Ok(page_matches) => page_matches,
+                Err(e) if page == 1 => return Err(e),
+                Err(e) => {
+                    tracing::warn!("Failed to parse result page {}: {}", page, e);
+                    break;
+                }
+            };
+            for listing in page_matches {
+                if seen_listings.insert(listing.listing_url.clone()) {
+                    matches.push(listing);
+                }
+            }
+
+            if page >= page_limit {
+                break;
+            }
+            let Some(next_url) = self.next_page_url(&html) else {
+                break;
+            };
+            if !visited_pages.insert(next_url.clone()) {
+                break;
+            }
+
+            html = match fetch(next_url).await {
+                Ok(html) => html,
+                Err(e) => {
+                    tracing::warn!("Failed to fetch result page {}: {}", page + 1, e);
+                    break;
+                }
+            };
+            page += 1;
+        }
+
+        Ok(matches)
+    }
+
+    fn resolve_url(&self, href: &str) -> String {
+        if href.starts_with("http") {
+            href.to_string()
+        } else {
+            format!("{}{}", self.base_url, href)
+        }
+    }
+
     fn extract_text(&self, element: &ElementRef, selector: &Option<String>) -> Option<String> {
         selector.as_ref().and_then(|sel| {
             Selector::parse(sel)
@@ -146,6 +344,9 @@ impl<'a> ResultParser<'a> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::cell::RefCell;
+    use std::collections::HashMap;
+    use std::fmt::Write;
 
     #[test]
     fn test_parse_search_results() {
@@ -176,8 +377,11 @@ mod tests {
             relatives: None,
             phones: None,
             emails: None,
+            match_indicator: None,
             no_results_indicator: None,
             captcha_required: None,
+            next_page: None,
+            max_pages: None,
         };
 
         let parser = ResultParser::new(&selectors, "https://example.com".to_string());
@@ -191,4 +395,210 @@ mod tests {
             "https://example.com/profile/john-doe-123"
         );
     }
+
+    #[test]
+    fn test_parse_age_range_and_match_strength() {
+        let html = r#"
+            <div class="search-results">
+                <div class="result-card">
+                    <a class="profile-link" href="/p/1">View</a>
+                    <div class="age">Age 45-49</div>
+                    <span class="match">Likely match</span>
+                </div>
+                <div class="result-card">
+                    <a class="profile-link" href="/p/2">View</a>
+                    <div class="age">Age: 52</div>
+                    <span class="match">92% Match</span>
+                </div>
+                <div class="result-card">
+                    <a class="profile-link" href="/p/3">View</a>
+                    <div class="age">Unknown</div>
+                </div>
+            </div>
+        "#;
+        let selectors = ResultSelectors {
+            age: Some(".age".to_string()),
+            match_indicator: Some(".match".to_string()),
+            ..paginated_selectors(None)
+        };
+
+        let parser = ResultParser::new(&selectors, "https://example.com".to_string());
+        let matches = parser.parse(html).expect("parse should succeed");
+        let data: Vec<_> = matches.iter().map(|m| &m.extracted_data).collect();
+
+        assert_eq!(data[0].age, None);
+        assert_eq!(data[0].age_range, Some((45, 49)));
+        assert_eq!(data[0].source_confidence, Some(0.75));
+
+        assert_eq!(data[1].age, Some(52));
+        assert_eq!(data[1].age_range, None);
+        assert_eq!(data[1].source_confidence, Some(0.92));
+
+        assert_eq!(data[2].age, None);
+        assert_eq!(data[2].age_range, None);
+        assert_eq!(data[2].source_confidence, None);
+    }
+
+    #[test]
+    fn test_parse_age_forms() {
+        assert_eq!(parse_age("35"), (Some(35), None));
+        assert_eq!(parse_age("Age 30 \u{2013} 34"), (None, Some((30, 34))));
+        assert_eq!(parse_age("40 to 44"), (None, Some((40, 44))));
+        // A birth year is not an age, and two unrelated numbers are not a range
+        assert_eq!(parse_age("Age 38 (born 1987)"), (Some(38), None));
+        assert_eq!(parse_age("Born 1987"), (None, None));
+    }
+
+    #[test]
+    fn test_match_labels() {
+        assert_eq!(parse_match_confidence("Exact Match"), Some(0.95));
+        assert_eq!(parse_match_confidence("Unlikely match"), Some(0.25));
+        assert_eq!(parse_match_confidence("Possible match"), Some(0.5));
+        assert_eq!(parse_match_confidence("Match: 150 %"), Some(1.0));
+        assert_eq!(parse_match_confidence("View details"), None);
+    }
+
+    #[test]
+    fn test_match_labels_are_whole_words_and_can_be_negated() {
+        assert_eq!(parse_match_confidence("No exact match"), Some(0.25));
+        assert_eq!(parse_match_confidence("Not likely"), Some(0.25));
+        assert_eq!(parse_match_confidence("Not a good match"), Some(0.25));
+        // "low" inside longer words is not a label
+        assert_eq!(parse_match_confidence("See below"), None);
+        assert_eq!(parse_match_confidence("Follow for updates"), None);
+        assert_eq!(parse_match_confidence("Highlights"), None);
+    }
+
+    fn paginated_selectors(max_pages: Option<u32>) -> ResultSelectors {
+        ResultSelectors {
+            results_container: ".search-results".to_string(),
+            result_item: ".result-card".to_string(),
+            listing_url: "a.profile-link".to_string(),
+            name: Some(".name".to_string()),
+            age: None,
+            location: None,
+            relatives: None,
+            phones: None,
+            emails: None,
+            match_indicator: None,
+            no_results_indicator: None,
+            captcha_required: None,
+            next_page: Some("a.next".to_string()),
+            max_pages,
+        }
+    }
+
+    fn result_page(names: &[&str], next: Option<&str>) -> String {
+        let mut cards = String::new();
+        for name in names {
+            write!(
+                cards,
+                r#"<div class="result-card"><a class="profile-link" href="/profile/{name}">View</a><div class="name">{name}</div></div>"#
+            )
+            .expect("write to string");
+        }
+        let next = next
+            .map(|href| format!(r#"<a class="next" href="{href}">Next</a>"#))
+            .unwrap_or_default();
+        format!(r#"<div class="search-results">{cards}</div>{next}"#)
+    }
+
+    #[tokio::test]
+    async fn test_match_on_second_page_is_found() {
+        let selectors = paginated_selectors(None);
+        let parser = ResultParser::new(&selectors, "https://example.com".to_string());
+
+        let first = result_page(&["alice-smith", "bob-jones"], Some("/search?page=2"));
+        let pages = HashMap::from([(
+            "https://example.com/search?page=2".to_string(),
+            result_page(&["bob-jones", "john-doe"], None),
+        )]);
+        let fetched = RefCell::new(Vec::new());
+
+        let matches = parser
+            .parse_pages(&first, |url| {
+                fetched.borrow_mut().push(url.clone());
+                let page = pages.get(&url).cloned();
+                async move { page.ok_or_else(|| ScanError::Parse(format!("no page at {url}"))) }
+            })
+            .await
+            .expect("pagination should succeed");
+
+        let urls: Vec<_> = matches.iter().map(|m| m.listing_url.as_str()).collect();
+        assert_eq!(
+            urls,
+            [
+                "https://example.com/profile/alice-smith",
+                "https://example.com/profile/bob-jones",
+                "https://example.com/profile/john-doe",
+            ]
+        );
+        assert_eq!(
+            *fetched.borrow(),
+            ["https://example.com/search?page=2".to_string()]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_pagination_stops_at_page_limit() {
+        let selectors = paginated_selectors(Some(3));
+        let parser = ResultParser::new(&selectors, "https://example.com".to_string());
+
+        // Every page links to another, so only the cap ends the crawl
+        let first = result_page(&["person-1"], Some("/search?page=2"));
+        let fetched = RefCell::new(0u32);
+
+        let matches = parser
+            .parse_pages(&first, |url| {
+                *fetched.borrow_mut() += 1;
+                let page: u32 = url
+                    .rsplit('=')
+                    .next()
+                    .and_then(|n| n.parse().ok())
+                    .expect("page number");
+                let html = result_page(
+                    &[&format!("person-{page}")],
+                    Some(&format!("/search?page={}", page + 1)),
+                );
+                async move { Ok(html) }
+            })
+            .await
+            .expect("pagination should succeed");
+
+        assert_eq!(*fetched.borrow(), 2);
+        assert_eq!(matches.len(), 3);
+        assert_eq!(
+            matches.last().map(|m| m.listing_url.as_str()),
+            Some("https://example.com/profile/person-3")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_pagination_ignores_repeated_next_link() {
+        let selectors = paginated_selectors(Some(10));
+        let parser = ResultParser::new(&selectors, "https://example.com".to_string());
+
+        let first = result_page(&["person-1"], Some("/search?page=2"));
+        let fetched = RefCell::new(0u32);
+
+        let matches = parser
+            .parse_pages(&first, |_| {
+                *fetched.borrow_mut() += 1;
+                let html = result_page(&["person-2"], Some("/search?page=2"));
+                async move { Ok(html) }
+            })
+            .await
+            .expect("pagination should succeed");
+
+        assert_eq!(*fetched.borrow(), 1);
+        assert_eq!(matches.len(), 2);
+    }
+
+    #[test]
+    fn test_page_limit_without_next_selector() {
+        let mut selectors = paginated_selectors(Some(4));
+        assert_eq!(selectors.page_limit(), 4);
+        selectors.next_page = None;
+        assert_eq!(selectors.page_limit(), 1);
+    }
 }
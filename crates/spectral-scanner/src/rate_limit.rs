@@ -0,0 +1,200 @@
+//! Global request budget shared across all broker scans.
+//!
+//! The concurrency semaphore bounds how many brokers are scanned at once, but
+//! not how fast requests leave the machine. [`RateLimiter`] is a token bucket
+//! that caps total requests per second regardless of how many scans are in
+//! flight, smoothing traffic so a large scan does not saturate the user's
+//! connection.
+
+use spectral_core::config::ScanningConfig;
+use std::time::Duration;
+use tokio::sync::Mutex;
+use tokio::time::Instant;
+
+/// Token-bucket limiter for outgoing requests.
+///
+/// Waiters are served in FIFO order. Time is read through `tokio::time`, so
+/// tests can drive the limiter with a paused clock.
+#[derive(Debug)]
+pub struct RateLimiter {
+    /// Bucket state, `None` when unlimited
+    bucket: Option<Mutex<Bucket>>,
+}
+
+#[derive(Debug)]
+struct Bucket {
+    /// Tokens added per second
+    rate: f64,
+    /// Maximum tokens the bucket can hold
+    capacity: f64,
+    /// Tokens currently available
+    tokens: f64,
+    /// Last time tokens were added
+    last_refill: Instant,
+}
+
+impl Bucket {
+    fn refill(&mut self, now: Instant) {
+        let elapsed = now
+            .saturating_duration_since(self.last_refill)
+            .as_secs_f64();
+        self.tokens = (self.tokens + elapsed * self.rate).min(self.capacity);
+        self.last_refill = now;
+    }
+}
+
+impl RateLimiter {
+    /// Create a limiter allowing `requests_per_second` on average, with up to
+    /// `burst` requests issued back-to-back when the bucket is full.
+    ///
+    /// A rate of zero disables limiting. A burst of zero is treated as one.
+    #[must_use]
+    pub fn new(requests_per_second: u32, burst: u32) -> Self {
+        if requests_per_second == 0 {
+            return Self::unlimited();
+        }
+
+        let capacity = f64::from(burst.max(1));
+        Self {
+            bucket: Some(Mutex::new(Bucket {
+                rate: f64::from(requests_per_second),
+                capacity,
+                tokens: capacity,
+                last_refill: Instant::now(),
+            })),
+        }
+    }
+
+    /// Create a limiter that never waits.
+    #[must_use]
+    pub fn unlimited() -> Self {
+        Self { bucket: None }
+    }
+
+    /// Create a limiter from the scanning configuration.
+    #[must_use]
+    pub fn from_config(config: &ScanningConfig) -> Self {
+        Self::new(config.max_requests_per_second, config.request_burst)
+    }
+
+    /// Check whether this limiter imposes a budget.
+    #[must_use]
+    pub fn is_limited(&self) -> bool {
+        self.bucket.is_some()
+    }
+
+    /// Take one token, waiting until one is available.
+    pub async fn acquire(&self) {
+        let Some(bucket) = &self.bucket else {
+            return;
+        };
+
+        // Holding the lock while sleeping keeps waiters in arrival order.
+        let mut bucket = bucket.lock().await;
+        bucket.refill(Instant::now());
+
+        if bucket.tokens < 1.0 {
+            let wait = Duration::from_secs_f64((1.0 - bucket.tokens) / bucket.rate);
+            tokio::time::sleep(wait).await;
+            bucket.refill(Instant::now());
+        }
+
+        bucket.tokens = (bucket.tokens - 1.0).max(0.0);
+    }
+}
+
+impl Default for RateLimiter {
+    fn default() -> Self {
+        Self::from_config(&ScanningConfig::default())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+
+    #[tokio::test(start_paused = true)]
+    async fn test_requests_are_spaced_by_rate() {
+        let limiter = RateLimiter::new(2, 1);
+        let start = Instant::now();
+
+        let mut timestamps = Vec::new();
+        for _ in 0..5 {
+            limiter.acquire().await;
+            timestamps.push(start.elapsed());
+        }
+
+        let expected: Vec<_> = (0..5).map(|i| Duration::from_millis(500 * i)).collect();
+        assert_eq!(timestamps, expected);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_burst_is_allowed_then_throttled() {
+        let limiter = RateLimiter::new(4, 3);
+        let start = Instant::now();
+
+        for _ in 0..3 {
+            limiter.acquire().await;
+        }
+        assert_eq!(start.elapsed(), Duration::ZERO);
+
+        limiter.acquire().await;
+        assert_eq!(start.elapsed(), Duration::from_millis(250));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_budget_is_shared_across_concurrent_tasks() {
+        let limiter = Arc::new(RateLimiter::new(5, 1));
+        let start = Instant::now();
+
+        let handles: Vec<_> = (0..6)
+            .map(|_| {
+                let limiter = Arc::clone(&limiter);
+                tokio::spawn(async move {
+                    limiter.acquire().await;
+                    start.elapsed()
+                })
+            })
+            .collect();
+
+        let mut timestamps = Vec::new();
+        for handle in handles {
+            timestamps.push(handle.await.expect("task panicked"));
+        }
+        timestamps.sort();
+
+        for pair in timestamps.windows(2) {
+            assert!(pair[1].saturating_sub(pair[0]) >= Duration::from_millis(200));
+        }
+        assert_eq!(timestamps.last(), Some(&Duration::from_secs(1)));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_idle_time_refills_bucket() {
+        let limiter = RateLimiter::new(1, 2);
+        limiter.acquire().await;
+        limiter.acquire().await;
+
+        tokio::time::sleep(Duration::from_secs(10)).await;
+        let start = Instant::now();
+        limiter.acquire().await;
+        limiter.acquire().await;
+        assert_eq!(start.elapsed(), Duration::ZERO);
+
+        limiter.acquire().await;
+        assert_eq!(start.elapsed(), Duration::from_secs(1));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_zero_rate_is_unlimited() {
+        let limiter = RateLimiter::new(0, 0);
+        assert!(!limiter.is_limited());
+
+        let start = Instant::now();
+        for _ in 0..100 {
+            limiter.acquire().await;
+        }
+        assert_eq!(start.elapsed(), Duration::ZERO);
+    }
+}
@@ -0,0 +1,377 @@
+//! Per-scan settings resolved from the scanning configuration.
+//!
+//! The configured defaults apply to every scan; arguments given for a single
+//! scan override them.
+
+use crate::error::{Result, ScanError};
+use spectral_broker::{BrokerDefinition, ScanPriority};
+use spectral_core::config::{ScanDepth, ScanDisclosure, ScanTier, ScanningConfig};
+use std::collections::BTreeMap;
+
+/// Settings for one scan job.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ScanSettings {
+    /// Brokers to scan when the scan does not name a tier or brokers
+    pub tier: ScanTier,
+    /// Maximum number of brokers scanned at the same time
+    pub max_concurrent_scans: usize,
+    /// Attempts per page fetch before a broker scan is marked failed
+    pub max_retries: u32,
+    /// User-defined tiers, by name, each listing the broker IDs it covers
+    pub custom_tiers: BTreeMap<String, Vec<String>>,
+    /// How much profile data searches send to brokers
+    pub disclosure: ScanDisclosure,
+    /// How exhaustively the scan searches
+    pub depth: ScanDepth,
+    /// Names each broker is searched for on a thorough scan
+    pub name_variants: usize,
+}
+
+impl ScanSettings {
+    /// Create settings from the scanning configuration's defaults.
+    #[must_use]
+    pub fn from_config(config: &ScanningConfig) -> Self {
+        Self {
+            tier: config.default_tier.clone(),
+            max_concurrent_scans: usize::try_from(config.concurrent_scans).unwrap_or(usize::MAX),
+            max_retries: config.max_retries,
+            custom_tiers: config.custom_tiers.clone(),
+            disclosure: config.disclosure,
+            depth: config.depth,
+            name_variants: usize::try_from(config.name_variants).unwrap_or(usize::MAX),
+        }
+        .clamped()
+    }
+
+    /// Replace the defaults with any values given for this scan.
+    #[must_use]
+    pub fn with_overrides(
+        self,
+        tier: Option<ScanTier>,
+        max_concurrent_scans: Option<usize>,
+        max_retries: Option<u32>,
+    ) -> Self {
+        Self {
+            tier: tier.unwrap_or(self.tier),
+            max_concurrent_scans: max_concurrent_scans.unwrap_or(self.max_concurrent_scans),
+            max_retries: max_retries.unwrap_or(self.max_retries),
+            custom_tiers: self.custom_tiers,
+            disclosure: self.disclosure,
+            depth: self.depth,
+            name_variants: self.name_variants,
+        }
+        .clamped()
+    }
+
+    /// Use `depth` for this scan instead of the configured default.
+    #[must_use]
+    pub fn with_depth(self, depth: ScanDepth) -> Self {
+        Self { depth, ..self }
+    }
+
+    /// Fetch attempts per page; a quick scan gives up after the first.
+    #[must_use]
+    pub fn effective_max_retries(&self) -> u32 {
+        match self.depth {
+            ScanDepth::Quick => 1,
+            ScanDepth::Thorough => self.max_retries,
+        }
+    }
+
+    /// Names each broker is searched for; a quick scan searches only the
+    /// profile's own name.
+    #[must_use]
+    pub fn max_name_variants(&self) -> usize {
+        match self.depth {
+            ScanDepth::Quick => 1,
+            ScanDepth::Thorough => self.name_variants,
+        }
+    }
+
+    /// Whether to follow "next page" links beyond the first result page.
+    #[must_use]
+    pub fn follows_pagination(&self) -> bool {
+        self.depth == ScanDepth::Thorough
+    }
+
+    /// Whether to start a browser for brokers that need one; a quick scan
+    /// fetches over plain HTTP and skips browser-only brokers.
+    #[must_use]
+    pub fn uses_browser(&self) -> bool {
+        self.depth == ScanDepth::Thorough
+    }
+
+    /// Select the brokers covered by this scan's tier.
+    ///
+    /// Built-in tiers select by scan priority, and a quick scan narrows
+    /// them to tier 1. A named tier selects exactly the brokers its
+    /// definition lists, in registry order, at any depth. Naming a tier
+    /// that is not defined is an error.
+    pub fn select_brokers(&self, brokers: &[BrokerDefinition]) -> Result<Vec<BrokerDefinition>> {
+        let tier = match (&self.tier, self.depth) {
+            (ScanTier::Tier2 | ScanTier::All, ScanDepth::Quick) => &ScanTier::Tier1,
+            (tier, _) => tier,
+        };
+        let selected = match tier {
+            ScanTier::Named(name) => {
+                let ids = self
+                    .custom_tiers
+                    .get(name)
+                    .ok_or_else(|| ScanError::UnknownTier(name.clone()))?;
+                brokers
+                    .iter()
+                    .filter(|b| ids.iter().any(|id| id == b.broker.id.as_str()))
+                    .cloned()
+                    .collect()
+            }
+            ScanTier::Tier1 => brokers
+                .iter()
+                .filter(|b| b.broker.scan_priority == ScanPriority::AutoScanTier1)
+                .cloned()
+                .collect(),
+            ScanTier::Tier2 => brokers
+                .iter()
+                .filter(|b| {
+                    matches!(
+                        b.broker.scan_priority,
+                        ScanPriority::AutoScanTier1 | ScanPriority::AutoScanTier2
+                    )
+                })
+                .cloned()
+                .collect(),
+            ScanTier::All => brokers
+                .iter()
+                .filter(|b| b.broker.scan_priority != ScanPriority::ManualOnly)
+                .cloned()
+                .collect(),
+        };
+
+        Ok(selected)
+    }
+
+    /// A scan needs at least one worker, one fetch attempt and one name.
+    fn clamped(self) -> Self {
+        Self {
+            max_concurrent_scans: self.max_concurrent_scans.max(1),
+            max_retries: self.max_retries.max(1),
+            name_variants: self.name_variants.max(1),
+            ..self
+        }
+    }
+}
+
+impl Default for ScanSettings {
+    fn default() -> Self {
+        Self::from_config(&ScanningConfig::default())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDate;
+    use spectral_broker::{
+        BrokerCategory, BrokerMetadata, RemovalConfirmation, RemovalDifficulty, RemovalMethod,
+        SearchMethod,
+    };
+    use spectral_core::BrokerId;
+    use std::collections::HashMap;
+
+    fn mock_broker(id: &str, scan_priority: ScanPriority) -> BrokerDefinition {
+        BrokerDefinition {
+            broker: BrokerMetadata {
+                id: BrokerId::new(id).expect("valid test broker ID"),
+                name: id.to_string(),
+                url: "https://example.com".to_string(),
+                domain: "example.com".to_string(),
+                category: BrokerCategory::PeopleSearch,
+                difficulty: RemovalDifficulty::Easy,
+                typical_removal_days: 7,
+                recheck_interval_days: 30,
+                last_verified: NaiveDate::from_ymd_opt(2025, 1, 1).expect("valid test date"),
+                scan_priority,
+                region_relevance: vec!["Global".to_string()],
+                countries: vec![],
+                requires_id_verification: false,
+                requires_account: false,
+                related_brokers: vec![],
+                request_headers: HashMap::new(),
+                legal_contact: None,
+            },
+            search: SearchMethod::UrlTemplate {
+                template: "https://example.com/{first}-{last}".to_string(),
+                requires_fields: vec![],
+                result_selectors: None,
+            },
+            removal: RemovalMethod::Manual {
+                instructions: "Manual removal".to_string(),
+            },
+            fixture: None,
+            confirmation: RemovalConfirmation::None,
+        }
+    }
+
+    fn selected_ids(settings: &ScanSettings, brokers: &[BrokerDefinition]) -> Vec<String> {
+        settings
+            .select_brokers(brokers)
+            .expect("select brokers")
+            .iter()
+            .map(|b| b.broker.id.to_string())
+            .collect()
+    }
+
+    #[test]
+    fn test_settings_follow_config() {
+        let config = ScanningConfig {
+            concurrent_scans: 2,
+            default_tier: ScanTier::Tier1,
+            max_retries: 5,
+            disclosure: ScanDisclosure::Minimal,
+            ..ScanningConfig::default()
+        };
+
+        let settings = ScanSettings::from_config(&config);
+        assert_eq!(settings.max_concurrent_scans, 2);
+        assert_eq!(settings.tier, ScanTier::Tier1);
+        assert_eq!(settings.max_retries, 5);
+        assert_eq!(settings.disclosure, ScanDisclosure::Minimal);
+
+        let settings = settings.with_overrides(None, None, None);
+        assert_eq!(settings.max_concurrent_scans, 2);
+        assert_eq!(settings.tier, ScanTier::Tier1);
+    }
+
+    #[test]
+    fn test_explicit_overrides_win() {
+        let config = ScanningConfig {
+            concurrent_scans: 2,
+            ..ScanningConfig::default()
+        };
+
+        let settings = ScanSettings::from_config(&config).with_overrides(
+            Some(ScanTier::Tier2),
+            Some(6),
+            Some(1),
+        );
+        assert_eq!(settings.max_concurrent_scans, 6);
+        assert_eq!(settings.tier, ScanTier::Tier2);
+        assert_eq!(settings.max_retries, 1);
+    }
+
+    #[test]
+    fn test_zero_values_are_clamped() {
+        let settings = ScanSettings::default().with_overrides(None, Some(0), Some(0));
+        assert_eq!(settings.max_concurrent_scans, 1);
+        assert_eq!(settings.max_retries, 1);
+    }
+
+    #[test]
+    fn test_built_in_tiers_select_by_priority() {
+        let brokers = vec![
+            mock_broker("first", ScanPriority::AutoScanTier1),
+            mock_broker("second", ScanPriority::AutoScanTier2),
+            mock_broker("manual", ScanPriority::ManualOnly),
+        ];
+
+        let settings = ScanSettings::default();
+        assert_eq!(selected_ids(&settings, &brokers), ["first", "second"]);
+        let settings = settings.with_overrides(Some(ScanTier::Tier1), None, None);
+        assert_eq!(selected_ids(&settings, &brokers), ["first"]);
+    }
+
+    #[test]
+    fn test_custom_tier_selects_exactly_its_brokers() {
+        let brokers = vec![
+            mock_broker("first", ScanPriority::AutoScanTier1),
+            mock_broker("second", ScanPriority::AutoScanTier2),
+            mock_broker("manual", ScanPriority::ManualOnly),
+            mock_broker("other", ScanPriority::OnRequest),
+        ];
+        let mut config = ScanningConfig::default();
+        config.custom_tiers.insert(
+            "my top 5".to_string(),
+            vec!["manual".to_string(), "second".to_string()],
+        );
+
+        let settings = ScanSettings::from_config(&config).with_overrides(
+            Some(ScanTier::Named("my top 5".to_string())),
+            None,
+            None,
+        );
+        assert_eq!(selected_ids(&settings, &brokers), ["second", "manual"]);
+    }
+
+    #[test]
+    fn test_unknown_named_tier_errors() {
+        let settings = ScanSettings::default().with_overrides(
+            Some(ScanTier::Named("missing".to_string())),
+            None,
+            None,
+        );
+        let err = settings
+            .select_brokers(&[mock_broker("first", ScanPriority::AutoScanTier1)])
+            .expect_err("unknown tier");
+        assert!(matches!(&err, ScanError::UnknownTier(name) if name == "missing"));
+        assert!(err.to_string().contains("missing"));
+    }
+
+    #[test]
+    fn test_quick_depth_scans_fewer_brokers_without_pagination() {
+        let brokers = vec![
+            mock_broker("first", ScanPriority::AutoScanTier1),
+            mock_broker("second", ScanPriority::AutoScanTier2),
+            mock_broker("third", ScanPriority::OnRequest),
+        ];
+
+        let thorough = ScanSettings::default().with_depth(ScanDepth::Thorough);
+        assert_eq!(
+            selected_ids(&thorough, &brokers),
+            ["first", "second", "third"]
+        );
+        assert!(thorough.follows_pagination());
+        assert!(thorough.uses_browser());
+        assert_eq!(thorough.effective_max_retries(), thorough.max_retries);
+
+        let quick = thorough.with_depth(ScanDepth::Quick);
+        assert_eq!(selected_ids(&quick, &brokers), ["first"]);
+        assert!(!quick.follows_pagination());
+        assert!(!quick.uses_browser());
+        assert_eq!(quick.effective_max_retries(), 1);
+    }
+
+    #[test]
+    fn test_name_variants_are_opt_in() {
+        assert_eq!(ScanSettings::default().max_name_variants(), 1);
+
+        let config = ScanningConfig {
+            name_variants: 3,
+            ..ScanningConfig::default()
+        };
+        let settings = ScanSettings::from_config(&config);
+        assert_eq!(settings.max_name_variants(), 3);
+        assert_eq!(settings.with_depth(ScanDepth::Quick).max_name_variants(), 1);
+    }
+
+    #[test]
+    fn test_quick_depth_keeps_named_tier() {
+        let brokers = vec![
+            mock_broker("first", ScanPriority::AutoScanTier1),
+            mock_broker("second", ScanPriority::AutoScanTier2),
+        ];
+        let mut config = ScanningConfig {
+            depth: ScanDepth::Quick,
+            ..ScanningConfig::default()
+        };
+        config
+            .custom_tiers
+            .insert("mine".to_string(), vec!["second".to_string()]);
+
+        let settings = ScanSettings::from_config(&config).with_overrides(
+            Some(ScanTier::Named("mine".to_string())),
+            None,
+            None,
+        );
+        assert_eq!(settings.depth, ScanDepth::Quick);
+        assert_eq!(selected_ids(&settings, &brokers), ["second"]);
+    }
+}
@@ -1,9 +1,12 @@
 #![allow(clippy::uninlined_format_args)]
 
 use crate::error::{Result, ScanError};
+use crate::filter::profile_country;
 use spectral_broker::SearchMethod;
-use spectral_core::BrokerId;
+use spectral_core::config::ScanDisclosure;
+use spectral_core::{AddressFormat, BrokerId, PiiField};
 use spectral_vault::UserProfile;
+use zeroize::Zeroizing;
 
 /// Simple URL encoding for profile data
 /// Encodes spaces as hyphens and removes special characters
@@ -21,51 +24,116 @@ fn url_encode_simple(s: &str) -> String {
         .collect()
 }
 
+/// Template placeholders filled from the profile, with the field each one
+/// discloses.
+const PLACEHOLDER_FIELDS: [(&str, PiiField); 4] = [
+    ("{first}", PiiField::FirstName),
+    ("{last}", PiiField::LastName),
+    ("{state}", PiiField::State),
+    ("{city}", PiiField::City),
+];
+
+/// Remove a placeholder along with the separator joining it to the rest of
+/// the URL, so removing `{state}` from `/{first}-{last}/{state}/{city}` gives
+/// `/{first}-{last}/{city}`.
+fn remove_placeholder(url: &str, placeholder: &str) -> String {
+    [
+        format!("/{placeholder}"),
+        format!("{placeholder}-"),
+        format!("-{placeholder}"),
+        format!("_{placeholder}"),
+        placeholder.to_string(),
+    ]
+    .iter()
+    .fold(url.to_string(), |url, pattern| url.replace(pattern, ""))
+}
+
+/// Remove the `{state}` placeholder along with its leading path separator,
+/// so `/{first}-{last}/{state}/{city}` becomes `/{first}-{last}/{city}`.
+pub(crate) fn remove_state_placeholder(url: &str) -> String {
+    remove_placeholder(url, "{state}")
+}
+
+/// Remove the placeholders for fields the broker does not require, so a
+/// [`ScanDisclosure::Minimal`] search sends only the required fields.
+pub(crate) fn remove_optional_placeholders(template: &str, requires_fields: &[PiiField]) -> String {
+    PLACEHOLDER_FIELDS
+        .iter()
+        .filter(|(_, field)| !requires_fields.contains(field))
+        .fold(template.to_string(), |url, (placeholder, _)| {
+            remove_placeholder(&url, placeholder)
+        })
+}
+
+/// Build the search URL for a URL-template broker from the profile.
+///
+/// With [`ScanDisclosure::Minimal`], only the fields the broker lists in
+/// `requires_fields` are filled in; placeholders for other fields are
+/// dropped even when the profile has a value for them.
 pub fn build_search_url(
     broker_id: &BrokerId,
     method: &SearchMethod,
     profile: &UserProfile,
     key: &[u8; 32],
+    disclosure: ScanDisclosure,
 ) -> Result<String> {
     match method {
-        SearchMethod::UrlTemplate { template, .. } => {
-            let mut url = template.clone();
+        SearchMethod::UrlTemplate {
+            template,
+            requires_fields,
+            ..
+        } => {
+            let mut url = match disclosure {
+                ScanDisclosure::Minimal => remove_optional_placeholders(template, requires_fields),
+                ScanDisclosure::Full => template.clone(),
+            };
 
             // Replace placeholders
             if let Some(first) = &profile.first_name {
-                let decrypted = first
-                    .decrypt(key)
-                    .map_err(|e| ScanError::ProfileDataError {
-                        broker_id: broker_id.clone(),
-                        reason: format!("Failed to decrypt first_name: {}", e),
-                    })?;
-                let encoded = url_encode_simple(&decrypted.to_lowercase());
+                let decrypted =
+                    first
+                        .decrypt_zeroizing(key)
+                        .map_err(|e| ScanError::ProfileDataError {
+                            broker_id: broker_id.clone(),
+                            reason: format!("Failed to decrypt first_name: {}", e),
+                        })?;
+                let encoded = url_encode_simple(&Zeroizing::new(decrypted.to_lowercase()));
                 url = url.replace("{first}", &encoded);
             }
             if let Some(last) = &profile.last_name {
-                let decrypted = last.decrypt(key).map_err(|e| ScanError::ProfileDataError {
-                    broker_id: broker_id.clone(),
-                    reason: format!("Failed to decrypt last_name: {}", e),
-                })?;
-                let encoded = url_encode_simple(&decrypted.to_lowercase());
+                let decrypted =
+                    last.decrypt_zeroizing(key)
+                        .map_err(|e| ScanError::ProfileDataError {
+                            broker_id: broker_id.clone(),
+                            reason: format!("Failed to decrypt last_name: {}", e),
+                        })?;
+                let encoded = url_encode_simple(&Zeroizing::new(decrypted.to_lowercase()));
                 url = url.replace("{last}", &encoded);
             }
             if let Some(state) = &profile.state {
-                let decrypted = state
-                    .decrypt(key)
-                    .map_err(|e| ScanError::ProfileDataError {
-                        broker_id: broker_id.clone(),
-                        reason: format!("Failed to decrypt state: {}", e),
-                    })?;
+                let decrypted =
+                    state
+                        .decrypt_zeroizing(key)
+                        .map_err(|e| ScanError::ProfileDataError {
+                            broker_id: broker_id.clone(),
+                            reason: format!("Failed to decrypt state: {}", e),
+                        })?;
                 let encoded = url_encode_simple(&decrypted);
                 url = url.replace("{state}", &encoded);
+            } else if !AddressFormat::for_country(profile_country(profile, key).as_deref())
+                .has_states
+            {
+                // Countries without states drop the state path segment
+                url = remove_state_placeholder(&url);
             }
             if let Some(city) = &profile.city {
-                let decrypted = city.decrypt(key).map_err(|e| ScanError::ProfileDataError {
-                    broker_id: broker_id.clone(),
-                    reason: format!("Failed to decrypt city: {}", e),
-                })?;
-                let encoded = url_encode_simple(&decrypted.to_lowercase());
+                let decrypted =
+                    city.decrypt_zeroizing(key)
+                        .map_err(|e| ScanError::ProfileDataError {
+                            broker_id: broker_id.clone(),
+                            reason: format!("Failed to decrypt city: {}", e),
+                        })?;
+                let encoded = url_encode_simple(&Zeroizing::new(decrypted.to_lowercase()));
                 url = url.replace("{city}", &encoded);
             }
 
@@ -81,7 +149,7 @@ pub fn build_search_url(
 #[cfg(test)]
 mod tests {
     use super::*;
-    use spectral_core::{PiiField, ProfileId};
+    use spectral_core::ProfileId;
     use spectral_vault::cipher::encrypt_string;
 
     fn test_key() -> [u8; 32] {
@@ -114,9 +182,78 @@ mod tests {
 
         let profile = mock_profile();
         let key = test_key();
-        let url = build_search_url(&broker_id, &method, &profile, &key)
+        let url = build_search_url(&broker_id, &method, &profile, &key, ScanDisclosure::Full)
+            .expect("should build URL from template");
+
+        assert_eq!(url, "https://example.com/john-doe/CA/springfield");
+    }
+
+    #[test]
+    fn test_build_url_drops_state_for_uk_profile() {
+        let key = test_key();
+        let broker_id = BrokerId::new("test-broker").expect("valid broker id");
+        let method = SearchMethod::UrlTemplate {
+            template: "https://example.com/{first}-{last}/{state}/{city}".to_string(),
+            requires_fields: vec![
+                PiiField::FirstName,
+                PiiField::LastName,
+                PiiField::State,
+                PiiField::City,
+            ],
+            result_selectors: None,
+        };
+
+        let mut profile = mock_profile();
+        profile.state = None;
+        profile.city = Some(encrypt_string("London", &key).expect("encrypt city"));
+        profile.country = Some(encrypt_string("GB", &key).expect("encrypt country"));
+
+        let url = build_search_url(&broker_id, &method, &profile, &key, ScanDisclosure::Full)
+            .expect("should build URL from template");
+
+        assert_eq!(url, "https://example.com/john-doe/london");
+    }
+
+    #[test]
+    fn test_minimal_disclosure_omits_optional_fields() {
+        let key = test_key();
+        let broker_id = BrokerId::new("test-broker").expect("valid broker id");
+        let method = SearchMethod::UrlTemplate {
+            template: "https://example.com/{first}-{last}/{state}/{city}".to_string(),
+            requires_fields: vec![PiiField::FirstName, PiiField::LastName, PiiField::State],
+            result_selectors: None,
+        };
+        let profile = mock_profile();
+
+        let url = build_search_url(&broker_id, &method, &profile, &key, ScanDisclosure::Minimal)
             .expect("should build URL from template");
+        assert_eq!(url, "https://example.com/john-doe/CA");
 
+        // Full disclosure still sends the optional city
+        let url = build_search_url(&broker_id, &method, &profile, &key, ScanDisclosure::Full)
+            .expect("should build URL from template");
         assert_eq!(url, "https://example.com/john-doe/CA/springfield");
     }
+
+    #[test]
+    fn test_minimal_disclosure_removes_joined_placeholders() {
+        let key = test_key();
+        let broker_id = BrokerId::new("test-broker").expect("valid broker id");
+        let method = SearchMethod::UrlTemplate {
+            template: "https://example.com/name/{first}-{last}/{city}-{state}".to_string(),
+            requires_fields: vec![PiiField::FirstName, PiiField::LastName],
+            result_selectors: None,
+        };
+
+        let url = build_search_url(
+            &broker_id,
+            &method,
+            &mock_profile(),
+            &key,
+            ScanDisclosure::Minimal,
+        )
+        .expect("should build URL from template");
+
+        assert_eq!(url, "https://example.com/name/john-doe");
+    }
 }
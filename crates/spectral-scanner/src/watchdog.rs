@@ -0,0 +1,140 @@
+//! Watchdog for scan jobs that stopped making progress.
+//!
+//! The orchestrator records a heartbeat each time a broker finishes. A job
+//! whose background task panicked, hung, or died with the app stays
+//! `InProgress` in the database, so the watchdog fails any job whose
+//! heartbeat is older than the configured timeout. It runs once when started,
+//! which cleans up jobs orphaned by a crash, and then periodically.
+
+use crate::error::Result;
+use spectral_core::metrics::{self, Counter};
+use spectral_db::{scan_jobs, Database, DbChange};
+use std::sync::Weak;
+use std::time::Duration;
+use tokio::task::JoinHandle;
+
+/// How often the watchdog started by [`spawn_watchdog`] checks for stalled jobs.
+pub const DEFAULT_CHECK_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Fail scan jobs that have reported no progress for `max_idle`.
+///
+/// Returns the IDs of the jobs that were failed.
+pub async fn reap_stalled_scans(db: &Database, max_idle: Duration) -> Result<Vec<String>> {
+    let max_idle = chrono::Duration::from_std(max_idle).unwrap_or(chrono::Duration::MAX);
+    let reaped = scan_jobs::fail_stalled_jobs(db.pool(), max_idle).await?;
+
+    for job_id in &reaped {
+        tracing::warn!("Scan job {} made no progress and was marked failed", job_id);
+        metrics::global().increment(Counter::ScansFailed);
+        db.notify(DbChange::ScanStatusChanged {
+            scan_job_id: job_id.clone(),
+            status: scan_jobs::ScanJobStatus::Failed.to_string(),
+        });
+    }
+
+    Ok(reaped)
+}
+
+/// Run [`reap_stalled_scans`] now and then every `check_interval`.
+///
+/// Holds only a weak handle, so the task ends once the database is dropped,
+/// e.g. when its vault is locked. Dropping the returned handle leaves the
+/// task running.
+#[allow(clippy::must_use_candidate)]
+pub fn spawn_watchdog(
+    db: Weak<Database>,
+    max_idle: Duration,
+    check_interval: Duration,
+) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        let mut ticker = tokio::time::interval(check_interval);
+        loop {
+            ticker.tick().await;
+            let Some(db) = db.upgrade() else {
+                break;
+            };
+            if let Err(e) = reap_stalled_scans(&db, max_idle).await {
+                tracing::warn!("Scan watchdog check failed: {}", e);
+            }
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+
+    async fn setup_db_with_job(last_progress_mins_ago: i64) -> (Arc<Database>, String) {
+        let db = Database::new(":memory:", vec![0x42; 32])
+            .await
+            .expect("create db");
+        db.run_migrations().await.expect("run migrations");
+        sqlx::query(
+            "INSERT INTO profiles (id, data, nonce, created_at, updated_at)
+             VALUES ('profile-1', 'data', 'nonce', datetime('now'), datetime('now'))",
+        )
+        .execute(db.pool())
+        .await
+        .expect("create profile");
+
+        let job = scan_jobs::create_scan_job(db.pool(), "profile-1".to_string(), 3)
+            .await
+            .expect("create scan job");
+        let last_progress = chrono::Utc::now() - chrono::Duration::minutes(last_progress_mins_ago);
+        sqlx::query("UPDATE scan_jobs SET last_progress_at = ? WHERE id = ?")
+            .bind(last_progress.to_rfc3339())
+            .bind(&job.id)
+            .execute(db.pool())
+            .await
+            .expect("set last progress");
+
+        (Arc::new(db), job.id)
+    }
+
+    #[tokio::test]
+    async fn test_reap_announces_failed_jobs() {
+        let (db, job_id) = setup_db_with_job(90).await;
+        let mut changes = db.listen_for_changes();
+
+        let reaped = reap_stalled_scans(&db, Duration::from_secs(30 * 60))
+            .await
+            .expect("reap stalled scans");
+
+        assert_eq!(reaped, vec![job_id.clone()]);
+        assert_eq!(
+            changes.recv().await.expect("receive change"),
+            DbChange::ScanStatusChanged {
+                scan_job_id: job_id,
+                status: "Failed".to_string(),
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn test_watchdog_runs_on_start_and_stops_with_database() {
+        let (db, job_id) = setup_db_with_job(90).await;
+        let mut changes = db.listen_for_changes();
+
+        let handle = spawn_watchdog(
+            Arc::downgrade(&db),
+            Duration::from_secs(30 * 60),
+            Duration::from_millis(10),
+        );
+
+        // The first check happens straight away
+        assert_eq!(
+            changes.recv().await.expect("receive change"),
+            DbChange::ScanStatusChanged {
+                scan_job_id: job_id,
+                status: "Failed".to_string(),
+            }
+        );
+
+        drop(db);
+        tokio::time::timeout(Duration::from_secs(5), handle)
+            .await
+            .expect("watchdog stops")
+            .expect("watchdog task");
+    }
+}
@@ -0,0 +1,194 @@
+use async_trait::async_trait;
+use spectral_broker::definition::{
+    BrokerCategory, BrokerDefinition, BrokerMetadata, RemovalConfirmation, RemovalDifficulty,
+    RemovalMethod, SearchMethod,
+};
+use spectral_broker::BrokerRegistry;
+use spectral_core::{BrokerId, PiiField, ProfileId};
+use spectral_db::{broker_scans, scan_jobs, Database};
+use spectral_scanner::{PageFetcher, ScanOrchestrator, SkipReason};
+use spectral_vault::{EncryptedField, UserProfile};
+use std::collections::HashMap;
+use std::sync::Arc;
+use tokio::sync::Notify;
+use tokio_util::sync::CancellationToken;
+
+/// Fetcher whose requests never complete, like a broker site that hangs.
+struct HangingFetcher {
+    started: Arc<Notify>,
+}
+
+#[async_trait]
+impl PageFetcher for HangingFetcher {
+    async fn fetch(
+        &self,
+        _url: &str,
+        _headers: &HashMap<String, String>,
+    ) -> spectral_browser::Result<String> {
+        self.started.notify_one();
+        std::future::pending().await
+    }
+}
+
+fn url_template_broker(broker_id: &str) -> BrokerDefinition {
+    BrokerDefinition {
+        broker: BrokerMetadata {
+            id: BrokerId::new(broker_id).expect("valid broker ID"),
+            name: format!("Test Broker {broker_id}"),
+            url: format!("https://{broker_id}.example.com"),
+            domain: format!("{broker_id}.example.com"),
+            category: BrokerCategory::PeopleSearch,
+            difficulty: RemovalDifficulty::Easy,
+            typical_removal_days: 7,
+            recheck_interval_days: 30,
+            last_verified: chrono::NaiveDate::from_ymd_opt(2025, 1, 1).expect("valid date"),
+            scan_priority: spectral_broker::ScanPriority::OnRequest,
+            region_relevance: vec!["Global".to_string()],
+            countries: vec![],
+            requires_id_verification: false,
+            requires_account: false,
+            related_brokers: vec![],
+            request_headers: HashMap::new(),
+            legal_contact: None,
+        },
+        search: SearchMethod::UrlTemplate {
+            template: format!("https://{broker_id}.example.com/search?name={{first_name}}"),
+            requires_fields: vec![PiiField::FirstName],
+            result_selectors: None,
+        },
+        removal: RemovalMethod::Manual {
+            instructions: "Manual removal instructions".to_string(),
+        },
+        fixture: None,
+        confirmation: RemovalConfirmation::None,
+    }
+}
+
+/// Database holding a profile for John, with the key it is encrypted with.
+async fn setup_profile() -> (Arc<Database>, ProfileId, [u8; 32]) {
+    let key = [0x42; 32];
+    let db = Database::new(":memory:", key.to_vec())
+        .await
+        .expect("create db");
+    db.run_migrations().await.expect("run migrations");
+    let db = Arc::new(db);
+
+    let profile_id =
+        ProfileId::new("550e8400-e29b-41d4-a716-446655440000").expect("valid profile ID");
+    let mut profile = UserProfile::new(profile_id.clone());
+    profile.first_name =
+        Some(EncryptedField::encrypt(&"John".to_string(), &key).expect("encrypt first name"));
+    profile.save(&db, &key).await.expect("save profile");
+
+    (db, profile_id, key)
+}
+
+/// Orchestrator over `broker_ids` whose fetches hang, scanning one broker at
+/// a time.
+fn hanging_orchestrator(
+    db: &Arc<Database>,
+    broker_ids: &[&str],
+    cancel: CancellationToken,
+) -> (ScanOrchestrator, Arc<Notify>) {
+    let registry = BrokerRegistry::new();
+    for broker_id in broker_ids {
+        registry
+            .insert(url_template_broker(broker_id))
+            .expect("insert broker");
+    }
+    let started = Arc::new(Notify::new());
+    let fetcher = Arc::new(HangingFetcher {
+        started: Arc::clone(&started),
+    });
+    let orchestrator =
+        ScanOrchestrator::with_fetcher(Arc::new(registry), fetcher, false, db.clone())
+            .with_max_concurrent_scans(1)
+            .with_cancellation(cancel);
+    (orchestrator, started)
+}
+
+#[tokio::test]
+async fn test_cancelled_brokers_are_not_recorded_as_failed() {
+    let (db, profile_id, key) = setup_profile().await;
+    let cancel = CancellationToken::new();
+    let (orchestrator, started) =
+        hanging_orchestrator(&db, &["slow-broker", "queued-broker"], cancel.clone());
+
+    let job = scan_jobs::create_scan_job(db.pool(), profile_id.as_str().to_string(), 2)
+        .await
+        .expect("create scan job");
+
+    // Cancel once the first broker's fetch is in flight
+    let canceller = tokio::spawn(async move {
+        started.notified().await;
+        cancel.cancel();
+    });
+    let results = orchestrator
+        .execute_scan_job(
+            job.id.clone(),
+            vec![
+                BrokerId::new("slow-broker").expect("valid broker ID"),
+                BrokerId::new("queued-broker").expect("valid broker ID"),
+            ],
+            profile_id.as_str().to_string(),
+            &key,
+        )
+        .await
+        .expect("execute scan job");
+    canceller.await.expect("cancel scan");
+
+    assert_eq!(results.len(), 2);
+    for result in &results {
+        assert_eq!(result.error, None);
+        assert_eq!(result.skip_reason, Some(SkipReason::Cancelled));
+    }
+
+    let scans = broker_scans::get_by_scan_job(db.pool(), &job.id)
+        .await
+        .expect("get broker scans");
+    assert_eq!(scans.len(), 2);
+    assert!(scans.iter().all(|scan| scan.status == "Cancelled"));
+
+    // Nothing counts against the brokers' failure history
+    let failed: i64 =
+        sqlx::query_scalar("SELECT COUNT(*) FROM broker_scans WHERE status = 'Failed'")
+            .fetch_one(db.pool())
+            .await
+            .expect("count failed scans");
+    assert_eq!(failed, 0);
+    assert!(broker_scans::average_durations(db.pool(), 10)
+        .await
+        .expect("average durations")
+        .is_empty());
+}
+
+#[tokio::test]
+async fn test_cancelled_resume_leaves_job_cancelled() {
+    let (db, profile_id, key) = setup_profile().await;
+    let cancel = CancellationToken::new();
+    let (orchestrator, _) = hanging_orchestrator(&db, &["pending-broker"], cancel.clone());
+
+    let job = scan_jobs::create_scan_job(db.pool(), profile_id.as_str().to_string(), 1)
+        .await
+        .expect("create scan job");
+    broker_scans::create_broker_scan(db.pool(), job.id.clone(), "pending-broker".to_string())
+        .await
+        .expect("create broker scan");
+
+    cancel.cancel();
+    let results = orchestrator
+        .resume_scan(&job.id, &key)
+        .await
+        .expect("resume scan");
+    assert_eq!(results.len(), 1);
+    assert_eq!(results[0].skip_reason, Some(SkipReason::Cancelled));
+
+    let (status, completed_brokers): (String, i64) =
+        sqlx::query_as("SELECT status, completed_brokers FROM scan_jobs WHERE id = ?")
+            .bind(&job.id)
+            .fetch_one(db.pool())
+            .await
+            .expect("get scan job");
+    assert_eq!(status, "Cancelled");
+    assert_eq!(completed_brokers, 0);
+}
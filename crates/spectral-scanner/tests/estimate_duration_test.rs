@@ -0,0 +1,144 @@
+use chrono::{Duration as ChronoDuration, TimeZone, Utc};
+use spectral_broker::definition::{
+    BrokerCategory, BrokerDefinition, BrokerMetadata, RemovalConfirmation, RemovalDifficulty,
+    RemovalMethod, SearchMethod,
+};
+use spectral_broker::BrokerRegistry;
+use spectral_core::{BrokerId, PiiField};
+use spectral_db::Database;
+use spectral_scanner::{BrokerFilter, ScanOrchestrator};
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+
+fn broker(broker_id: &str) -> BrokerDefinition {
+    BrokerDefinition {
+        broker: BrokerMetadata {
+            id: BrokerId::new(broker_id).expect("valid broker ID"),
+            name: format!("Test Broker {broker_id}"),
+            url: format!("https://{broker_id}.example.com"),
+            domain: format!("{broker_id}.example.com"),
+            category: BrokerCategory::PeopleSearch,
+            difficulty: RemovalDifficulty::Easy,
+            typical_removal_days: 7,
+            recheck_interval_days: 30,
+            last_verified: chrono::NaiveDate::from_ymd_opt(2025, 1, 1).expect("valid date"),
+            scan_priority: spectral_broker::ScanPriority::OnRequest,
+            region_relevance: vec!["Global".to_string()],
+            countries: vec![],
+            requires_id_verification: false,
+            requires_account: false,
+            related_brokers: vec![],
+            request_headers: HashMap::new(),
+            legal_contact: None,
+        },
+        search: SearchMethod::UrlTemplate {
+            template: format!("https://{broker_id}.example.com/search?name={{first_name}}"),
+            requires_fields: vec![PiiField::FirstName],
+            result_selectors: None,
+        },
+        removal: RemovalMethod::Manual {
+            instructions: "Manual removal instructions".to_string(),
+        },
+        fixture: None,
+        confirmation: RemovalConfirmation::None,
+    }
+}
+
+async fn setup(broker_count: usize) -> (Arc<Database>, Arc<BrokerRegistry>) {
+    let db = Database::new(":memory:", vec![0x42; 32])
+        .await
+        .expect("create db");
+    db.run_migrations().await.expect("run migrations");
+
+    let now = Utc::now().to_rfc3339();
+    for sql in [
+        "INSERT INTO profiles (id, data, nonce, created_at, updated_at) VALUES ('p1', x'00', x'00', ?1, ?1)",
+        "INSERT INTO scan_jobs (id, profile_id, started_at, status, total_brokers, completed_brokers) VALUES ('j1', 'p1', ?1, 'Completed', 0, 0)",
+    ] {
+        sqlx::query(sql)
+            .bind(&now)
+            .execute(db.pool())
+            .await
+            .expect("seed row");
+    }
+
+    let registry = BrokerRegistry::new();
+    for i in 0..broker_count {
+        registry
+            .insert(broker(&format!("broker-{i}")))
+            .expect("insert broker");
+    }
+    (Arc::new(db), Arc::new(registry))
+}
+
+/// Record a finished scan of `broker_id` that took `seconds`.
+async fn seed_scan(db: &Database, broker_id: &str, seconds: i64) {
+    let started = Utc
+        .with_ymd_and_hms(2025, 1, 1, 0, 0, 0)
+        .single()
+        .expect("valid timestamp");
+    let completed = started + ChronoDuration::seconds(seconds);
+    sqlx::query(
+        "INSERT INTO broker_scans (id, scan_job_id, broker_id, status, started_at, completed_at)
+         VALUES (?, 'j1', ?, 'Success', ?, ?)",
+    )
+    .bind(uuid::Uuid::new_v4().to_string())
+    .bind(broker_id)
+    .bind(started.to_rfc3339())
+    .bind(completed.to_rfc3339())
+    .execute(db.pool())
+    .await
+    .expect("seed broker scan");
+}
+
+fn assert_about(actual: Duration, expected_secs: f64) {
+    assert!(
+        (actual.as_secs_f64() - expected_secs).abs() < 0.1,
+        "expected about {expected_secs}s, got {actual:?}"
+    );
+}
+
+#[tokio::test]
+async fn test_estimate_accounts_for_concurrency() {
+    let (db, registry) = setup(10).await;
+    for i in 0..10 {
+        seed_scan(&db, &format!("broker-{i}"), 3).await;
+    }
+
+    let orchestrator = ScanOrchestrator::without_browser(registry, db).with_max_concurrent_scans(5);
+    let estimate = orchestrator
+        .estimate_duration(&BrokerFilter::All)
+        .await
+        .expect("estimate duration");
+
+    assert_about(estimate, 6.0);
+}
+
+#[tokio::test]
+async fn test_estimate_uses_default_for_unscanned_brokers_and_improves_with_history() {
+    let (db, registry) = setup(2).await;
+    let orchestrator =
+        ScanOrchestrator::without_browser(registry, db.clone()).with_max_concurrent_scans(1);
+
+    let initial = orchestrator
+        .estimate_duration(&BrokerFilter::All)
+        .await
+        .expect("estimate duration");
+    assert_about(initial, 20.0);
+
+    seed_scan(&db, "broker-0", 2).await;
+    seed_scan(&db, "broker-0", 4).await;
+    let refined = orchestrator
+        .estimate_duration(&BrokerFilter::All)
+        .await
+        .expect("estimate duration");
+    // broker-0 averages 3s; broker-1 is still unscanned
+    assert_about(refined, 13.0);
+
+    let only_scanned = orchestrator
+        .estimate_duration(&BrokerFilter::Specific(vec!["broker-0".to_string()]))
+        .await
+        .expect("estimate duration");
+    assert_about(only_scanned, 3.0);
+}
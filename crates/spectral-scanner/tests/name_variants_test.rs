@@ -0,0 +1,172 @@
+use async_trait::async_trait;
+use spectral_broker::definition::{
+    BrokerCategory, BrokerDefinition, BrokerMetadata, RemovalConfirmation, RemovalDifficulty,
+    RemovalMethod, ResultSelectors, SearchMethod,
+};
+use spectral_broker::BrokerRegistry;
+use spectral_core::{BrokerId, PiiField, ProfileId};
+use spectral_db::{findings, scan_jobs, Database};
+use spectral_scanner::{PageFetcher, ScanOrchestrator};
+use spectral_vault::profile::Alias;
+use spectral_vault::{EncryptedField, UserProfile};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+/// Fetcher answering like a broker site: a listing for the name searched,
+/// plus one listing that matches every name.
+#[derive(Default)]
+struct NameSearchFetcher {
+    urls: Mutex<Vec<String>>,
+}
+
+#[async_trait]
+impl PageFetcher for NameSearchFetcher {
+    async fn fetch(
+        &self,
+        url: &str,
+        _headers: &HashMap<String, String>,
+    ) -> spectral_browser::Result<String> {
+        self.urls.lock().expect("url log").push(url.to_string());
+        let name = url
+            .rsplit("name=")
+            .next()
+            .unwrap_or_default()
+            .to_lowercase();
+        Ok(format!(
+            r#"<div class="search-results">
+                <div class="result-card">
+                    <a class="profile-link" href="https://broker.example.com/profile/{name}">View</a>
+                    <div class="name">{name}</div>
+                </div>
+                <div class="result-card">
+                    <a class="profile-link" href="https://broker.example.com/profile/shared">View</a>
+                    <div class="name">J. Doe-Smith</div>
+                </div>
+            </div>"#
+        ))
+    }
+}
+
+fn broker() -> BrokerDefinition {
+    BrokerDefinition {
+        broker: BrokerMetadata {
+            id: BrokerId::new("name-broker").expect("valid broker ID"),
+            name: "Name Broker".to_string(),
+            url: "https://broker.example.com".to_string(),
+            domain: "broker.example.com".to_string(),
+            category: BrokerCategory::PeopleSearch,
+            difficulty: RemovalDifficulty::Easy,
+            typical_removal_days: 7,
+            recheck_interval_days: 30,
+            last_verified: chrono::NaiveDate::from_ymd_opt(2025, 1, 1).expect("valid date"),
+            scan_priority: spectral_broker::ScanPriority::OnRequest,
+            region_relevance: vec!["Global".to_string()],
+            countries: vec![],
+            requires_id_verification: false,
+            requires_account: false,
+            related_brokers: vec![],
+            request_headers: HashMap::new(),
+            legal_contact: None,
+        },
+        search: SearchMethod::UrlTemplate {
+            template: "https://broker.example.com/search?name={first_name}-{last_name}".to_string(),
+            requires_fields: vec![PiiField::FirstName, PiiField::LastName],
+            result_selectors: Some(ResultSelectors {
+                results_container: ".search-results".to_string(),
+                result_item: ".result-card".to_string(),
+                listing_url: "a.profile-link".to_string(),
+                name: Some(".name".to_string()),
+                age: None,
+                location: None,
+                relatives: None,
+                phones: None,
+                emails: None,
+                match_indicator: None,
+                no_results_indicator: None,
+                captcha_required: None,
+                next_page: None,
+                max_pages: None,
+            }),
+        },
+        removal: RemovalMethod::Manual {
+            instructions: "Manual removal instructions".to_string(),
+        },
+        fixture: None,
+        confirmation: RemovalConfirmation::None,
+    }
+}
+
+#[tokio::test]
+async fn test_broker_is_searched_for_primary_name_and_alias() {
+    let key = [0x42; 32];
+    let db = Database::new(":memory:", key.to_vec())
+        .await
+        .expect("create db");
+    db.run_migrations().await.expect("run migrations");
+    let db = Arc::new(db);
+
+    let profile_id =
+        ProfileId::new("550e8400-e29b-41d4-a716-446655440000").expect("valid profile ID");
+    let mut profile = UserProfile::new(profile_id.clone());
+    profile.first_name =
+        Some(EncryptedField::encrypt(&"John".to_string(), &key).expect("encrypt first name"));
+    profile.last_name =
+        Some(EncryptedField::encrypt(&"Doe".to_string(), &key).expect("encrypt last name"));
+    profile.aliases = vec![Alias {
+        first_name: None,
+        middle_name: None,
+        last_name: Some(
+            EncryptedField::encrypt(&"Smith".to_string(), &key).expect("encrypt alias"),
+        ),
+        nickname: None,
+    }];
+    profile.save(&db, &key).await.expect("save profile");
+
+    let registry = BrokerRegistry::new();
+    registry.insert(broker()).expect("insert broker");
+    let fetcher = Arc::new(NameSearchFetcher::default());
+    let orchestrator =
+        ScanOrchestrator::with_fetcher(Arc::new(registry), fetcher.clone(), false, db.clone())
+            .with_max_name_variants(2);
+
+    let job = scan_jobs::create_scan_job(db.pool(), profile_id.as_str().to_string(), 1)
+        .await
+        .expect("create scan job");
+    let results = orchestrator
+        .execute_scan_job(
+            job.id.clone(),
+            vec![BrokerId::new("name-broker").expect("valid broker ID")],
+            profile_id.as_str().to_string(),
+            &key,
+        )
+        .await
+        .expect("execute scan job");
+
+    // One search per name, the primary name first
+    assert_eq!(
+        *fetcher.urls.lock().expect("url log"),
+        [
+            "https://broker.example.com/search?name=John-Doe",
+            "https://broker.example.com/search?name=John-Smith",
+        ]
+    );
+
+    // Both names' listings are kept; the one found under both is stored once
+    assert_eq!(results.len(), 1);
+    assert_eq!(results[0].error, None);
+    assert_eq!(results[0].findings_count, 3);
+
+    let stored = findings::get_by_scan_job(db.pool(), &job.id)
+        .await
+        .expect("get findings");
+    let mut urls: Vec<&str> = stored.iter().map(|f| f.listing_url.as_str()).collect();
+    urls.sort_unstable();
+    assert_eq!(
+        urls,
+        [
+            "https://broker.example.com/profile/john-doe",
+            "https://broker.example.com/profile/john-smith",
+            "https://broker.example.com/profile/shared",
+        ]
+    );
+}
@@ -0,0 +1,349 @@
+use spectral_broker::definition::{
+    BrokerCategory, BrokerDefinition, BrokerMetadata, RemovalConfirmation, RemovalDifficulty,
+    RemovalMethod, ResultSelectors, SearchMethod,
+};
+use spectral_broker::BrokerRegistry;
+use spectral_core::{BrokerId, PiiField, ProfileId};
+use spectral_db::Database;
+use spectral_scanner::{ScanOrchestrator, SkipReason};
+use spectral_vault::{EncryptedField, UserProfile};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpListener;
+
+const RESULTS_PAGE: &str = r#"
+    <div class="search-results">
+        <div class="result-card">
+            <a class="profile-link" href="/profile/john-doe-123">View Profile</a>
+            <div class="name">John Doe</div>
+        </div>
+    </div>
+"#;
+
+/// Serve `RESULTS_PAGE` for every request, standing in for a broker site.
+///
+/// Returns the port and the head of every request received, lowercased.
+async fn serve_results_page() -> (u16, Arc<Mutex<Vec<String>>>) {
+    let listener = TcpListener::bind("127.0.0.1:0").await.expect("bind");
+    let port = listener.local_addr().expect("local addr").port();
+    let requests = Arc::new(Mutex::new(Vec::new()));
+    let log = Arc::clone(&requests);
+
+    tokio::spawn(async move {
+        while let Ok((mut socket, _)) = listener.accept().await {
+            let mut request = [0u8; 4096];
+            let len = socket.read(&mut request).await.unwrap_or(0);
+            log.lock()
+                .expect("request log")
+                .push(String::from_utf8_lossy(&request[..len]).to_lowercase());
+            let response = format!(
+                "HTTP/1.1 200 OK\r\nContent-Type: text/html\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                RESULTS_PAGE.len(),
+                RESULTS_PAGE
+            );
+            let _ = socket.write_all(response.as_bytes()).await;
+        }
+    });
+
+    (port, requests)
+}
+
+fn metadata(broker_id: &str) -> BrokerMetadata {
+    BrokerMetadata {
+        id: BrokerId::new(broker_id).expect("valid broker ID"),
+        name: format!("Test Broker {broker_id}"),
+        url: format!("https://{broker_id}.example.com"),
+        domain: format!("{broker_id}.example.com"),
+        category: BrokerCategory::PeopleSearch,
+        difficulty: RemovalDifficulty::Easy,
+        typical_removal_days: 7,
+        recheck_interval_days: 30,
+        last_verified: chrono::NaiveDate::from_ymd_opt(2025, 1, 1).expect("valid date"),
+        scan_priority: spectral_broker::ScanPriority::OnRequest,
+        region_relevance: vec!["Global".to_string()],
+        countries: vec![],
+        requires_id_verification: false,
+        requires_account: false,
+        related_brokers: vec![],
+        request_headers: HashMap::new(),
+        legal_contact: None,
+    }
+}
+
+fn selectors() -> ResultSelectors {
+    ResultSelectors {
+        results_container: ".search-results".to_string(),
+        result_item: ".result-card".to_string(),
+        listing_url: "a.profile-link".to_string(),
+        name: Some(".name".to_string()),
+        age: None,
+        location: None,
+        relatives: None,
+        phones: None,
+        emails: None,
+        match_indicator: None,
+        no_results_indicator: None,
+        captcha_required: None,
+        next_page: None,
+        max_pages: None,
+    }
+}
+
+fn url_template_broker(broker_id: &str, port: u16) -> BrokerDefinition {
+    BrokerDefinition {
+        broker: metadata(broker_id),
+        search: SearchMethod::UrlTemplate {
+            template: format!(
+                "http://127.0.0.1:{port}/{broker_id}/search?name={{first_name}}-{{last_name}}"
+            ),
+            requires_fields: vec![PiiField::FirstName, PiiField::LastName],
+            result_selectors: Some(selectors()),
+        },
+        removal: RemovalMethod::Manual {
+            instructions: "Manual removal instructions".to_string(),
+        },
+        fixture: None,
+        confirmation: RemovalConfirmation::None,
+    }
+}
+
+fn web_form_broker() -> BrokerDefinition {
+    BrokerDefinition {
+        broker: metadata("form-broker"),
+        search: SearchMethod::WebForm {
+            url: "https://form-broker.example.com/search".to_string(),
+            fields: HashMap::from([
+                ("first".to_string(), "{first_name}".to_string()),
+                ("last".to_string(), "{last_name}".to_string()),
+            ]),
+            requires_fields: vec![PiiField::FirstName, PiiField::LastName],
+            result_selectors: Some(selectors()),
+        },
+        removal: RemovalMethod::Manual {
+            instructions: "Manual removal instructions".to_string(),
+        },
+        fixture: None,
+        confirmation: RemovalConfirmation::None,
+    }
+}
+
+/// Database holding a profile for John Doe, with the key it is encrypted with.
+async fn setup_profile() -> (Arc<Database>, ProfileId, [u8; 32]) {
+    let key = [0x42; 32];
+    let db = Database::new(":memory:", key.to_vec())
+        .await
+        .expect("create db");
+    db.run_migrations().await.expect("run migrations");
+    let db = Arc::new(db);
+
+    let profile_id =
+        ProfileId::new("550e8400-e29b-41d4-a716-446655440000").expect("valid profile ID");
+    let mut profile = UserProfile::new(profile_id.clone());
+    profile.first_name =
+        Some(EncryptedField::encrypt(&"John".to_string(), &key).expect("encrypt first name"));
+    profile.last_name =
+        Some(EncryptedField::encrypt(&"Doe".to_string(), &key).expect("encrypt last name"));
+    profile.save(&db, &key).await.expect("save profile");
+
+    (db, profile_id, key)
+}
+
+#[tokio::test]
+async fn test_http_brokers_scan_and_browser_brokers_skip_without_browser() {
+    let (db, profile_id, key) = setup_profile().await;
+
+    let (port, _) = serve_results_page().await;
+    let registry = BrokerRegistry::new();
+    registry
+        .insert(url_template_broker("http-broker", port))
+        .expect("insert http broker");
+    registry
+        .insert(web_form_broker())
+        .expect("insert form broker");
+
+    let orchestrator = ScanOrchestrator::without_browser(Arc::new(registry), db.clone());
+    let job =
+        spectral_db::scan_jobs::create_scan_job(db.pool(), profile_id.as_str().to_string(), 2)
+            .await
+            .expect("create scan job");
+
+    let http_broker = BrokerId::new("http-broker").expect("valid broker ID");
+    let form_broker = BrokerId::new("form-broker").expect("valid broker ID");
+    let results = orchestrator
+        .execute_scan_job(
+            job.id.clone(),
+            vec![http_broker.clone(), form_broker.clone()],
+            profile_id.as_str().to_string(),
+            &key,
+        )
+        .await
+        .expect("execute scan job");
+    assert_eq!(results.len(), 2);
+
+    let http_result = results
+        .iter()
+        .find(|r| r.broker_id == http_broker)
+        .expect("http broker result");
+    assert_eq!(http_result.error, None);
+    assert_eq!(http_result.skip_reason, None);
+    assert_eq!(http_result.findings_count, 1);
+
+    let form_result = results
+        .iter()
+        .find(|r| r.broker_id == form_broker)
+        .expect("form broker result");
+    assert_eq!(form_result.error, None);
+    assert_eq!(form_result.skip_reason, Some(SkipReason::NoBrowser));
+    assert_eq!(form_result.findings_count, 0);
+
+    let scans = spectral_db::broker_scans::get_by_scan_job(db.pool(), &job.id)
+        .await
+        .expect("get broker scans");
+    let status = |broker_id: &str| {
+        scans
+            .iter()
+            .find(|scan| scan.broker_id == broker_id)
+            .map(|scan| scan.status.clone())
+            .expect("broker scan recorded")
+    };
+    assert_eq!(status("http-broker"), "Success");
+    assert_eq!(status("form-broker"), "Skipped");
+}
+
+#[tokio::test]
+async fn test_broker_request_headers_are_sent_only_to_that_broker() {
+    let (db, profile_id, key) = setup_profile().await;
+
+    let (port, requests) = serve_results_page().await;
+    let mut with_headers = url_template_broker("referer-broker", port);
+    with_headers.broker.request_headers = HashMap::from([
+        (
+            "Referer".to_string(),
+            "https://referer-broker.example.com/search".to_string(),
+        ),
+        ("X-Requested-With".to_string(), "XMLHttpRequest".to_string()),
+    ]);
+    let registry = BrokerRegistry::new();
+    registry.insert(with_headers).expect("insert broker");
+    registry
+        .insert(url_template_broker("plain-broker", port))
+        .expect("insert broker");
+
+    let orchestrator = ScanOrchestrator::without_browser(Arc::new(registry), db.clone());
+    let job =
+        spectral_db::scan_jobs::create_scan_job(db.pool(), profile_id.as_str().to_string(), 2)
+            .await
+            .expect("create scan job");
+    let results = orchestrator
+        .execute_scan_job(
+            job.id.clone(),
+            vec![
+                BrokerId::new("referer-broker").expect("valid broker ID"),
+                BrokerId::new("plain-broker").expect("valid broker ID"),
+            ],
+            profile_id.as_str().to_string(),
+            &key,
+        )
+        .await
+        .expect("execute scan job");
+    assert!(results.iter().all(|r| r.error.is_none()));
+
+    let requests = requests.lock().expect("request log").clone();
+    let request_for = |path: &str| {
+        requests
+            .iter()
+            .find(|r| r.starts_with(&format!("get {path}")))
+            .cloned()
+            .expect("request received")
+    };
+
+    let referer = request_for("/referer-broker/search");
+    assert!(referer.contains("\r\nreferer: https://referer-broker.example.com/search\r\n"));
+    assert!(referer.contains("\r\nx-requested-with: xmlhttprequest\r\n"));
+
+    let plain = request_for("/plain-broker/search");
+    assert!(!plain.contains("\r\nreferer:"));
+    assert!(!plain.contains("\r\nx-requested-with:"));
+
+    // Both keep the default browser-like headers
+    for request in [&referer, &plain] {
+        assert!(request.contains("\r\naccept: text/html"));
+        assert!(request.contains("\r\naccept-language: en-us"));
+    }
+}
+
+#[tokio::test]
+async fn test_resume_scan_rescans_only_unfinished_brokers() {
+    use spectral_db::broker_scans;
+
+    let (db, profile_id, key) = setup_profile().await;
+
+    let (port, requests) = serve_results_page().await;
+    let registry = BrokerRegistry::new();
+    for broker_id in ["done-broker", "interrupted-broker", "pending-broker"] {
+        registry
+            .insert(url_template_broker(broker_id, port))
+            .expect("insert broker");
+    }
+    let orchestrator = ScanOrchestrator::without_browser(Arc::new(registry), db.clone());
+
+    // A job that crashed after finishing one broker and starting another
+    let job =
+        spectral_db::scan_jobs::create_scan_job(db.pool(), profile_id.as_str().to_string(), 3)
+            .await
+            .expect("create scan job");
+    let scan_for = |broker_id: &str| {
+        broker_scans::create_broker_scan(db.pool(), job.id.clone(), broker_id.to_string())
+    };
+    let done = scan_for("done-broker").await.expect("create broker scan");
+    broker_scans::update_status(db.pool(), &done.id, "Success", None)
+        .await
+        .expect("finish broker scan");
+    let interrupted = scan_for("interrupted-broker")
+        .await
+        .expect("create broker scan");
+    broker_scans::mark_started(db.pool(), &interrupted.id)
+        .await
+        .expect("start broker scan");
+    scan_for("pending-broker")
+        .await
+        .expect("create broker scan");
+
+    let results = orchestrator
+        .resume_scan(&job.id, &key)
+        .await
+        .expect("resume scan");
+
+    let mut resumed: Vec<&str> = results.iter().map(|r| r.broker_id.as_str()).collect();
+    resumed.sort_unstable();
+    assert_eq!(resumed, ["interrupted-broker", "pending-broker"]);
+
+    let requests = requests.lock().expect("request log").clone();
+    assert_eq!(requests.len(), 2);
+    assert!(!requests
+        .iter()
+        .any(|r| r.starts_with("get /done-broker/search")));
+
+    // The existing records were reused and all finished
+    let scans = broker_scans::get_by_scan_job(db.pool(), &job.id)
+        .await
+        .expect("get broker scans");
+    assert_eq!(scans.len(), 3);
+    assert!(scans.iter().all(|scan| scan.status == "Success"));
+
+    let (status, completed_brokers): (String, i64) =
+        sqlx::query_as("SELECT status, completed_brokers FROM scan_jobs WHERE id = ?")
+            .bind(&job.id)
+            .fetch_one(db.pool())
+            .await
+            .expect("get scan job");
+    assert_eq!(status, "Completed");
+    assert_eq!(completed_brokers, 3);
+
+    // A completed job cannot be resumed again
+    assert!(matches!(
+        orchestrator.resume_scan(&job.id, &key).await,
+        Err(spectral_scanner::ScanError::JobNotResumable(_))
+    ));
+}
@@ -1,12 +1,13 @@
 use spectral_broker::definition::{
-    BrokerCategory, BrokerDefinition, BrokerMetadata, RemovalDifficulty, RemovalMethod,
-    ResultSelectors, SearchMethod,
+    BrokerCategory, BrokerDefinition, BrokerMetadata, RemovalConfirmation, RemovalDifficulty,
+    RemovalMethod, ResultSelectors, SearchMethod,
 };
 use spectral_broker::BrokerRegistry;
 use spectral_browser::BrowserEngine;
 use spectral_core::{BrokerId, PiiField};
-use spectral_db::Database;
+use spectral_db::{Database, DbChange};
 use spectral_scanner::ScanOrchestrator;
+use std::collections::HashMap;
 use std::sync::Arc;
 
 /// Helper to create a test broker definition with result selectors
@@ -27,6 +28,12 @@ fn create_test_broker_with_selectors(
             last_verified: chrono::NaiveDate::from_ymd_opt(2025, 1, 1).expect("valid date"),
             scan_priority: spectral_broker::ScanPriority::OnRequest,
             region_relevance: vec!["Global".to_string()],
+            countries: vec![],
+            requires_id_verification: false,
+            requires_account: false,
+            related_brokers: vec![],
+            request_headers: HashMap::new(),
+            legal_contact: None,
         },
         search: SearchMethod::UrlTemplate {
             template: format!(
@@ -39,6 +46,8 @@ fn create_test_broker_with_selectors(
         removal: RemovalMethod::Manual {
             instructions: "Manual removal instructions".to_string(),
         },
+        fixture: None,
+        confirmation: RemovalConfirmation::None,
     }
 }
 
@@ -101,8 +110,11 @@ async fn test_parse_findings_with_valid_selectors() {
         relatives: None,
         phones: None,
         emails: None,
+        match_indicator: None,
         no_results_indicator: None,
         captcha_required: None,
+        next_page: None,
+        max_pages: None,
     };
     let broker_def = create_test_broker_with_selectors("test-broker", Some(selectors));
     broker_registry
@@ -194,8 +206,11 @@ async fn test_deduplication_prevents_duplicates() {
         relatives: None,
         phones: None,
         emails: None,
+        match_indicator: None,
         no_results_indicator: None,
         captcha_required: None,
+        next_page: None,
+        max_pages: None,
     };
     let broker_def = create_test_broker_with_selectors("test-broker", Some(selectors));
     broker_registry
@@ -311,8 +326,11 @@ async fn test_parse_failure_returns_ok_zero() {
         relatives: None,
         phones: None,
         emails: None,
+        match_indicator: None,
         no_results_indicator: None,
         captcha_required: None,
+        next_page: None,
+        max_pages: None,
     };
     let broker_def = create_test_broker_with_selectors("test-broker", Some(selectors));
     broker_registry
@@ -347,3 +365,69 @@ async fn test_parse_failure_returns_ok_zero() {
 
     assert_eq!(findings.len(), 0);
 }
+
+#[tokio::test]
+async fn test_stored_findings_are_announced() {
+    let db = Database::new(":memory:", vec![0x42; 32])
+        .await
+        .expect("create db");
+    db.run_migrations().await.expect("run migrations");
+    let db = Arc::new(db);
+
+    let broker_registry = BrokerRegistry::new();
+    let selectors = ResultSelectors {
+        results_container: ".search-results".to_string(),
+        result_item: ".result-card".to_string(),
+        listing_url: "a.profile-link".to_string(),
+        name: Some(".name".to_string()),
+        age: None,
+        location: None,
+        relatives: None,
+        phones: None,
+        emails: None,
+        match_indicator: None,
+        no_results_indicator: None,
+        captcha_required: None,
+        next_page: None,
+        max_pages: None,
+    };
+    broker_registry
+        .insert(create_test_broker_with_selectors(
+            "test-broker",
+            Some(selectors),
+        ))
+        .expect("insert broker");
+    let orchestrator = ScanOrchestrator::without_browser(Arc::new(broker_registry), db.clone());
+
+    let (scan_job_id, broker_scan_id, profile_id) = create_test_scan_context(&db).await;
+    let mut changes = db.listen_for_changes();
+
+    let html = r#"
+        <div class="search-results">
+            <div class="result-card">
+                <a class="profile-link" href="/profile/john-doe-123">View Profile</a>
+                <div class="name">John Doe</div>
+            </div>
+        </div>
+    "#;
+    let broker_id = BrokerId::new("test-broker").expect("valid broker ID");
+    orchestrator
+        .parse_and_store_findings(html, &broker_scan_id, &broker_id, &profile_id)
+        .await
+        .expect("parse and store findings");
+
+    let findings = spectral_db::findings::get_by_scan_job(db.pool(), &scan_job_id)
+        .await
+        .expect("get findings");
+    assert_eq!(findings.len(), 1);
+
+    let change = changes.try_recv().expect("finding change published");
+    assert_eq!(
+        change,
+        DbChange::FindingCreated {
+            scan_job_id,
+            finding_id: findings[0].id.clone(),
+            broker_id: "test-broker".to_string(),
+        }
+    );
+}
@@ -0,0 +1,121 @@
+//! Run conditions — quiet hours and connection metering.
+
+use chrono::{DateTime, Duration, NaiveTime, TimeZone};
+use serde::{Deserialize, Serialize};
+
+/// Daily window during which scheduled jobs must not start.
+///
+/// Times are wall-clock times in the user's local time zone. A window whose
+/// `start` is later than its `end` wraps past midnight (e.g. 22:00–07:00).
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub struct QuietHours {
+    pub start: NaiveTime,
+    pub end: NaiveTime,
+}
+
+impl QuietHours {
+    pub fn new(start: NaiveTime, end: NaiveTime) -> Self {
+        Self { start, end }
+    }
+
+    /// Returns true if `time` falls inside the window. Equal start and end
+    /// describe an empty window.
+    pub fn contains(&self, time: NaiveTime) -> bool {
+        if self.start <= self.end {
+            time >= self.start && time < self.end
+        } else {
+            time >= self.start || time < self.end
+        }
+    }
+
+    /// Returns the first moment after `now` at which the window ends.
+    pub fn end_after<Tz: TimeZone>(&self, now: &DateTime<Tz>) -> DateTime<Tz> {
+        let local_now = now.naive_local();
+        let mut end = local_now.date().and_time(self.end);
+        if end <= local_now {
+            end += Duration::days(1);
+        }
+
+        // If the end falls in a DST gap, resume at the first valid time after it
+        let tz = now.timezone();
+        tz.from_local_datetime(&end)
+            .earliest()
+            .or_else(|| {
+                tz.from_local_datetime(&(end + Duration::hours(1)))
+                    .earliest()
+            })
+            .unwrap_or_else(|| now.clone() + Duration::hours(1))
+    }
+}
+
+/// Platform hook reporting whether the current network connection is metered.
+///
+/// Implementations query the OS (e.g. NetworkManager, `NWPathMonitor`,
+/// `NetworkInformation`). Detection failures should report unmetered so that
+/// scheduled work is not blocked indefinitely.
+pub trait ConnectionMonitor: Send + Sync {
+    fn is_metered(&self) -> bool;
+}
+
+/// Default monitor for platforms without metering detection; always unmetered.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct UnmeteredConnection;
+
+impl ConnectionMonitor for UnmeteredConnection {
+    fn is_metered(&self) -> bool {
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+
+    fn time(h: u32, m: u32) -> NaiveTime {
+        NaiveTime::from_hms_opt(h, m, 0).expect("valid time")
+    }
+
+    #[test]
+    fn test_quiet_hours_wrapping_midnight() {
+        let quiet = QuietHours::new(time(22, 0), time(7, 0));
+        assert!(quiet.contains(time(23, 30)));
+        assert!(quiet.contains(time(3, 0)));
+        assert!(!quiet.contains(time(7, 0)));
+        assert!(!quiet.contains(time(12, 0)));
+    }
+
+    #[test]
+    fn test_quiet_hours_same_day() {
+        let quiet = QuietHours::new(time(9, 0), time(17, 0));
+        assert!(quiet.contains(time(9, 0)));
+        assert!(!quiet.contains(time(17, 0)));
+        assert!(!quiet.contains(time(20, 0)));
+        assert!(!QuietHours::new(time(9, 0), time(9, 0)).contains(time(9, 0)));
+    }
+
+    #[test]
+    fn test_end_after_rolls_to_next_day() {
+        let quiet = QuietHours::new(time(22, 0), time(7, 0));
+        let now = "2026-02-17T23:30:00Z"
+            .parse::<DateTime<Utc>>()
+            .expect("valid timestamp");
+        assert_eq!(
+            quiet.end_after(&now).to_rfc3339(),
+            "2026-02-18T07:00:00+00:00"
+        );
+
+        let early = "2026-02-18T03:00:00Z"
+            .parse::<DateTime<Utc>>()
+            .expect("valid timestamp");
+        assert_eq!(
+            quiet.end_after(&early).to_rfc3339(),
+            "2026-02-18T07:00:00+00:00"
+        );
+    }
+
+    #[test]
+    fn test_default_monitor_is_unmetered() {
+        assert!(!UnmeteredConnection.is_metered());
+    }
+}
@@ -1,5 +1,6 @@
 //! Job type definitions.
 
+use crate::conditions::QuietHours;
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -10,6 +11,33 @@ pub enum JobType {
     PollImap,
 }
 
+/// Why a due job was pushed back instead of run.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum DeferReason {
+    QuietHours,
+    MeteredConnection,
+}
+
+impl DeferReason {
+    /// Stable identifier used when persisting the deferral.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::QuietHours => "quiet_hours",
+            Self::MeteredConnection => "metered_connection",
+        }
+    }
+
+    /// Parse a persisted identifier produced by [`Self::as_str`].
+    pub fn parse(s: &str) -> Option<Self> {
+        match s {
+            "quiet_hours" => Some(Self::QuietHours),
+            "metered_connection" => Some(Self::MeteredConnection),
+            _ => None,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ScheduledJob {
     pub id: String,
@@ -18,4 +46,13 @@ pub struct ScheduledJob {
     pub next_run_at: String,
     pub last_run_at: Option<String>,
     pub enabled: bool,
+    /// Local time window during which the job must not start
+    #[serde(default)]
+    pub quiet_hours: Option<QuietHours>,
+    /// Defer the job while the connection is metered
+    #[serde(default)]
+    pub skip_on_metered: bool,
+    /// Reason the job's current `next_run_at` was deferred, if it was
+    #[serde(default)]
+    pub deferred_reason: Option<DeferReason>,
 }
@@ -1,6 +1,13 @@
+pub mod conditions;
 pub mod jobs;
+pub mod notify;
 pub mod scheduler;
 pub mod tray;
 
-pub use jobs::{JobType, ScheduledJob};
-pub use scheduler::{is_job_due, next_run_timestamp};
+pub use conditions::{ConnectionMonitor, QuietHours, UnmeteredConnection};
+pub use jobs::{DeferReason, JobType, ScheduledJob};
+pub use notify::{job_summary, JobNotifier, JobOutcome, NoopNotificationSink, NotificationSink};
+pub use scheduler::{
+    evaluate_job, evaluate_job_now, is_job_due, next_run_timestamp, next_run_timestamp_with,
+    JobDecision,
+};
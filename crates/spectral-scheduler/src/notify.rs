@@ -0,0 +1,196 @@
+//! Notifications for finished jobs.
+//!
+//! Summaries only carry counts and the job's cadence, never profile data or
+//! error details, since they are shown outside the unlocked app.
+
+use crate::jobs::{JobType, ScheduledJob};
+use std::sync::Arc;
+
+/// Destination for user-facing notifications, such as the system tray.
+pub trait NotificationSink: Send + Sync {
+    /// Show a notification with the given title and body.
+    fn notify(&self, title: &str, body: &str);
+}
+
+/// A sink that drops every notification.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct NoopNotificationSink;
+
+impl NotificationSink for NoopNotificationSink {
+    fn notify(&self, _title: &str, _body: &str) {}
+}
+
+/// How a job run ended.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum JobOutcome {
+    /// The job finished; `count` is what it produced (new listings, confirmed
+    /// removals, or processed emails, depending on the job type).
+    Completed { count: usize },
+    /// The job did not finish.
+    Failed,
+}
+
+/// Sends a notification when a job finishes, if notifications are enabled.
+#[derive(Clone)]
+pub struct JobNotifier {
+    sink: Arc<dyn NotificationSink>,
+    enabled: bool,
+}
+
+impl JobNotifier {
+    /// Create a notifier that sends to `sink` when `enabled` is true.
+    pub fn new(sink: Arc<dyn NotificationSink>, enabled: bool) -> Self {
+        Self { sink, enabled }
+    }
+
+    /// Whether finished jobs produce notifications.
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    /// Notify that `job` finished with `outcome`.
+    pub fn job_finished(&self, job: &ScheduledJob, outcome: JobOutcome) {
+        if !self.enabled {
+            return;
+        }
+        let (title, body) = job_summary(&job.job_type, job.interval_days, outcome);
+        self.sink.notify(&title, &body);
+    }
+}
+
+impl Default for JobNotifier {
+    fn default() -> Self {
+        Self::new(Arc::new(NoopNotificationSink), true)
+    }
+}
+
+impl std::fmt::Debug for JobNotifier {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("JobNotifier")
+            .field("enabled", &self.enabled)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Title and body of the notification for a finished job.
+pub fn job_summary(
+    job_type: &JobType,
+    interval_days: u32,
+    outcome: JobOutcome,
+) -> (String, String) {
+    let cadence = match interval_days {
+        1 => "Daily",
+        7 => "Weekly",
+        30 => "Monthly",
+        _ => "Scheduled",
+    };
+    let (name, title, verb, noun) = match job_type {
+        JobType::ScanAll => ("scan", "Scan", "found", "new listing"),
+        JobType::VerifyRemovals => ("removal check", "Removal check", "confirmed", "removal"),
+        JobType::PollImap => ("inbox check", "Inbox check", "processed", "email"),
+    };
+
+    match outcome {
+        JobOutcome::Completed { count } => {
+            let found = match count {
+                0 => format!("no {noun}s"),
+                1 => format!("1 {noun}"),
+                n => format!("{n} {noun}s"),
+            };
+            (
+                format!("{title} complete"),
+                format!("{cadence} {name} {verb} {found}"),
+            )
+        }
+        JobOutcome::Failed => (
+            format!("{title} failed"),
+            format!("{cadence} {name} did not finish. Open Spectral for details."),
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+
+    #[derive(Default)]
+    struct RecordingSink {
+        sent: Mutex<Vec<(String, String)>>,
+    }
+
+    impl NotificationSink for RecordingSink {
+        fn notify(&self, title: &str, body: &str) {
+            self.sent
+                .lock()
+                .expect("sink lock")
+                .push((title.to_string(), body.to_string()));
+        }
+    }
+
+    fn weekly_scan() -> ScheduledJob {
+        ScheduledJob {
+            id: "default-scan-all".to_string(),
+            job_type: JobType::ScanAll,
+            interval_days: 7,
+            next_run_at: "2026-02-17T11:00:00Z".to_string(),
+            last_run_at: None,
+            enabled: true,
+            quiet_hours: None,
+            skip_on_metered: false,
+            deferred_reason: None,
+        }
+    }
+
+    #[test]
+    fn test_completed_job_sends_summary() {
+        let sink = Arc::new(RecordingSink::default());
+        let notifier = JobNotifier::new(sink.clone(), true);
+
+        notifier.job_finished(&weekly_scan(), JobOutcome::Completed { count: 3 });
+
+        let sent = sink.sent.lock().expect("sink lock");
+        assert_eq!(
+            *sent,
+            [(
+                "Scan complete".to_string(),
+                "Weekly scan found 3 new listings".to_string()
+            )]
+        );
+    }
+
+    #[test]
+    fn test_disabled_notifier_sends_nothing() {
+        let sink = Arc::new(RecordingSink::default());
+        let notifier = JobNotifier::new(sink.clone(), false);
+
+        notifier.job_finished(&weekly_scan(), JobOutcome::Completed { count: 3 });
+        notifier.job_finished(&weekly_scan(), JobOutcome::Failed);
+
+        assert!(sink.sent.lock().expect("sink lock").is_empty());
+    }
+
+    #[test]
+    fn test_summaries_for_each_outcome() {
+        assert_eq!(
+            job_summary(&JobType::ScanAll, 1, JobOutcome::Completed { count: 1 }).1,
+            "Daily scan found 1 new listing"
+        );
+        assert_eq!(
+            job_summary(
+                &JobType::VerifyRemovals,
+                30,
+                JobOutcome::Completed { count: 0 }
+            )
+            .1,
+            "Monthly removal check confirmed no removals"
+        );
+        assert_eq!(
+            job_summary(&JobType::ScanAll, 10, JobOutcome::Failed),
+            (
+                "Scan failed".to_string(),
+                "Scheduled scan did not finish. Open Spectral for details.".to_string()
+            )
+        );
+    }
+}
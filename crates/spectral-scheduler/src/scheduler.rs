@@ -1,6 +1,26 @@
 //! Job scheduling — determines when queued jobs are due.
 
-use chrono::DateTime;
+use crate::conditions::ConnectionMonitor;
+use crate::jobs::{DeferReason, ScheduledJob};
+use chrono::{DateTime, Duration, Local, TimeZone, Utc};
+use spectral_core::{Clock, SystemClock};
+
+/// How long to wait before re-checking a job deferred for a metered connection.
+pub const METERED_RETRY_MINUTES: i64 = 30;
+
+/// What the scheduler should do with a job at a given moment.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum JobDecision {
+    /// The job is disabled or not yet due.
+    NotDue,
+    /// The job is due and its run conditions are met.
+    Run,
+    /// The job is due but must wait until `until`.
+    Defer {
+        until: DateTime<Utc>,
+        reason: DeferReason,
+    },
+}
 
 /// Returns true if `next_run_at` is in the past relative to `now`.
 pub fn is_job_due(next_run_at: &str, now: &str) -> bool {
@@ -12,17 +32,104 @@ pub fn is_job_due(next_run_at: &str, now: &str) -> bool {
     }
 }
 
+/// Decide whether a job should run at `now`, honouring its quiet hours and
+/// metered-connection preference.
+///
+/// `now` carries the user's local time zone, which quiet hours are expressed
+/// in. A due job that may not run yet is deferred rather than skipped: the
+/// caller should move its `next_run_at` to the returned time and record the
+/// reason.
+pub fn evaluate_job<Tz: TimeZone>(
+    job: &ScheduledJob,
+    now: &DateTime<Tz>,
+    connection: &dyn ConnectionMonitor,
+) -> JobDecision {
+    let due = DateTime::parse_from_rfc3339(&job.next_run_at)
+        .map(|next| next <= *now)
+        .unwrap_or(false);
+    if !job.enabled || !due {
+        return JobDecision::NotDue;
+    }
+
+    if let Some(quiet_hours) = &job.quiet_hours {
+        if quiet_hours.contains(now.time()) {
+            return JobDecision::Defer {
+                until: quiet_hours.end_after(now).with_timezone(&Utc),
+                reason: DeferReason::QuietHours,
+            };
+        }
+    }
+
+    if job.skip_on_metered && connection.is_metered() {
+        return JobDecision::Defer {
+            until: now.with_timezone(&Utc) + Duration::minutes(METERED_RETRY_MINUTES),
+            reason: DeferReason::MeteredConnection,
+        };
+    }
+
+    JobDecision::Run
+}
+
+/// Decide whether a job should run now, reading the time from `clock`.
+///
+/// Quiet hours are checked against the system's local time zone. See
+/// [`evaluate_job`].
+pub fn evaluate_job_now(
+    job: &ScheduledJob,
+    clock: &dyn Clock,
+    connection: &dyn ConnectionMonitor,
+) -> JobDecision {
+    evaluate_job(job, &clock.now().with_timezone(&Local), connection)
+}
+
 /// Return the ISO-8601 timestamp for `now + interval_days`.
 pub fn next_run_timestamp(interval_days: u32) -> String {
-    use chrono::Utc;
+    next_run_timestamp_with(&SystemClock, interval_days)
+}
+
+/// Return the ISO-8601 timestamp `interval_days` after the time on `clock`.
+pub fn next_run_timestamp_with(clock: &dyn Clock, interval_days: u32) -> String {
     // nosemgrep: llm-prompt-injection-risk - false positive, this is chrono date arithmetic
-    let next = Utc::now() + chrono::Duration::days(interval_days as i64);
+    let next = clock.now() + chrono::Duration::days(interval_days as i64);
     next.to_rfc3339()
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::conditions::{QuietHours, UnmeteredConnection};
+    use crate::jobs::JobType;
+    use chrono::{FixedOffset, NaiveTime};
+    use spectral_core::MockClock;
+
+    struct Metered;
+
+    impl ConnectionMonitor for Metered {
+        fn is_metered(&self) -> bool {
+            true
+        }
+    }
+
+    fn job(next_run_at: &str) -> ScheduledJob {
+        ScheduledJob {
+            id: "default-scan-all".to_string(),
+            job_type: JobType::ScanAll,
+            interval_days: 7,
+            next_run_at: next_run_at.to_string(),
+            last_run_at: None,
+            enabled: true,
+            quiet_hours: None,
+            skip_on_metered: false,
+            deferred_reason: None,
+        }
+    }
+
+    fn overnight_quiet_hours() -> QuietHours {
+        QuietHours::new(
+            NaiveTime::from_hms_opt(22, 0, 0).expect("valid time"),
+            NaiveTime::from_hms_opt(7, 0, 0).expect("valid time"),
+        )
+    }
 
     #[test]
     fn test_job_is_due_past_next_run() {
@@ -37,4 +144,106 @@ mod tests {
         let next_run = "2026-02-17T13:00:00Z".to_string();
         assert!(!is_job_due(&next_run, &now));
     }
+
+    #[test]
+    fn test_job_due_during_quiet_hours_is_deferred_until_they_end() {
+        let mut job = job("2026-02-17T20:00:00Z");
+        job.quiet_hours = Some(overnight_quiet_hours());
+
+        // 23:30 local time in UTC-5
+        let now = DateTime::parse_from_rfc3339("2026-02-17T23:30:00-05:00").expect("valid");
+        let decision = evaluate_job(&job, &now, &UnmeteredConnection);
+
+        assert_eq!(
+            decision,
+            JobDecision::Defer {
+                until: "2026-02-18T12:00:00Z".parse().expect("valid"), // 07:00 local
+                reason: DeferReason::QuietHours,
+            }
+        );
+    }
+
+    #[test]
+    fn test_job_runs_outside_quiet_hours() {
+        let mut job = job("2026-02-17T20:00:00Z");
+        job.quiet_hours = Some(overnight_quiet_hours());
+
+        let offset = FixedOffset::west_opt(5 * 3600).expect("valid offset");
+        let now = offset
+            .with_ymd_and_hms(2026, 2, 18, 9, 0, 0)
+            .single()
+            .expect("valid");
+        assert_eq!(
+            evaluate_job(&job, &now, &UnmeteredConnection),
+            JobDecision::Run
+        );
+    }
+
+    #[test]
+    fn test_job_deferred_on_metered_connection() {
+        let mut job = job("2026-02-17T11:00:00Z");
+        let now = "2026-02-17T12:00:00Z"
+            .parse::<DateTime<Utc>>()
+            .expect("valid");
+
+        // Metered connections only matter when the job opts in
+        assert_eq!(evaluate_job(&job, &now, &Metered), JobDecision::Run);
+
+        job.skip_on_metered = true;
+        assert_eq!(
+            evaluate_job(&job, &now, &Metered),
+            JobDecision::Defer {
+                until: "2026-02-17T12:30:00Z".parse().expect("valid"),
+                reason: DeferReason::MeteredConnection,
+            }
+        );
+        assert_eq!(
+            evaluate_job(&job, &now, &UnmeteredConnection),
+            JobDecision::Run
+        );
+    }
+
+    #[test]
+    fn test_job_not_due_is_not_deferred() {
+        let mut job = job("2026-02-18T12:00:00Z");
+        job.quiet_hours = Some(overnight_quiet_hours());
+        let now = "2026-02-17T23:30:00Z"
+            .parse::<DateTime<Utc>>()
+            .expect("valid");
+        assert_eq!(
+            evaluate_job(&job, &now, &UnmeteredConnection),
+            JobDecision::NotDue
+        );
+
+        job.next_run_at = "2026-02-17T11:00:00Z".to_string();
+        job.enabled = false;
+        assert_eq!(
+            evaluate_job(&job, &now, &UnmeteredConnection),
+            JobDecision::NotDue
+        );
+    }
+
+    #[test]
+    fn test_job_becomes_due_when_clock_reaches_next_run() {
+        let clock = MockClock::new("2026-02-17T12:00:00Z".parse().expect("valid"));
+        let job = job(&next_run_timestamp_with(&clock, 7));
+        assert_eq!(job.next_run_at, "2026-02-24T12:00:00+00:00");
+
+        assert_eq!(
+            evaluate_job_now(&job, &clock, &UnmeteredConnection),
+            JobDecision::NotDue
+        );
+
+        clock.advance(Duration::days(7) - Duration::seconds(1));
+        assert_eq!(
+            evaluate_job_now(&job, &clock, &UnmeteredConnection),
+            JobDecision::NotDue
+        );
+
+        clock.advance(Duration::seconds(1));
+        assert_eq!(
+            evaluate_job_now(&job, &clock, &UnmeteredConnection),
+            JobDecision::Run
+        );
+    }
 }
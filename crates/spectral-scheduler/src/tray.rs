@@ -26,6 +26,9 @@ pub fn is_tray_supported() -> bool {
     }
 }
 
+/// ID of the application's tray icon
+pub const TRAY_ID: &str = "spectral";
+
 /// Tray icon menu item IDs
 pub const MENU_OPEN: &str = "open";
 pub const MENU_SCAN: &str = "scan_now";
@@ -0,0 +1,334 @@
+//! Encrypted documents stored alongside the vault.
+//!
+//! Attachments hold files the user wants to keep with their removal records,
+//! such as a copy of the ID a broker asked for or the confirmation it sent
+//! back. The contents and label are encrypted with the vault key; only the
+//! MIME type, size and creation time are stored in the clear.
+
+use crate::cipher::{decrypt_blob, encrypt_blob, EncryptedBlob};
+use crate::error::{Result, VaultError};
+use serde::{Deserialize, Serialize};
+use spectral_core::types::Timestamp;
+use spectral_db::Database;
+use sqlx::SqliteConnection;
+use std::fmt;
+use zeroize::Zeroizing;
+
+/// Largest attachment that can be stored, in bytes.
+pub const MAX_ATTACHMENT_BYTES: usize = 10 * 1024 * 1024;
+
+/// MIME types accepted for attachments.
+pub const ALLOWED_MIME_TYPES: &[&str] = &[
+    "application/pdf",
+    "image/jpeg",
+    "image/png",
+    "image/webp",
+    "text/plain",
+    "message/rfc822",
+];
+
+/// Identifier of a stored attachment (a UUID v4).
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct AttachmentId(String);
+
+impl AttachmentId {
+    /// Create an `AttachmentId` from a string.
+    ///
+    /// # Errors
+    /// Returns `VaultError::InvalidData` if the ID is not a UUID.
+    pub fn new(id: impl Into<String>) -> Result<Self> {
+        let id = id.into();
+        uuid::Uuid::parse_str(&id)
+            .map_err(|_| VaultError::InvalidData("invalid attachment ID".to_string()))?;
+        Ok(Self(id))
+    }
+
+    /// Create a new random `AttachmentId`.
+    #[must_use]
+    pub fn generate() -> Self {
+        Self(uuid::Uuid::new_v4().to_string())
+    }
+
+    /// Get the inner string value.
+    #[must_use]
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl fmt::Display for AttachmentId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/// Metadata of a stored attachment, without its contents.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AttachmentInfo {
+    /// Attachment identifier
+    pub id: AttachmentId,
+    /// MIME type of the contents
+    pub mime_type: String,
+    /// Decrypted user-supplied label
+    pub label: String,
+    /// Size of the contents in bytes
+    pub size_bytes: u64,
+    /// When the attachment was stored
+    pub created_at: Timestamp,
+}
+
+/// Check that `bytes` is a non-empty, allowed-size file of type `mime_type`.
+///
+/// Binary formats must start with the signature of their declared type, so
+/// a mislabelled file is rejected rather than stored.
+pub(crate) fn validate(bytes: &[u8], mime_type: &str) -> Result<()> {
+    if bytes.is_empty() {
+        return Err(VaultError::InvalidAttachment(
+            "attachment is empty".to_string(),
+        ));
+    }
+    if bytes.len() > MAX_ATTACHMENT_BYTES {
+        return Err(VaultError::InvalidAttachment(format!(
+            "attachment is {} bytes, maximum is {MAX_ATTACHMENT_BYTES}",
+            bytes.len()
+        )));
+    }
+
+    let matches_type = match mime_type {
+        "application/pdf" => bytes.starts_with(b"%PDF-"),
+        "image/jpeg" => bytes.starts_with(&[0xFF, 0xD8, 0xFF]),
+        "image/png" => bytes.starts_with(b"\x89PNG\r\n\x1a\n"),
+        "image/webp" => bytes.len() >= 12 && &bytes[..4] == b"RIFF" && &bytes[8..12] == b"WEBP",
+        "text/plain" | "message/rfc822" => std::str::from_utf8(bytes).is_ok(),
+        _ => {
+            return Err(VaultError::InvalidAttachment(format!(
+                "unsupported attachment type: {mime_type}"
+            )))
+        }
+    };
+    if !matches_type {
+        return Err(VaultError::InvalidAttachment(format!(
+            "contents do not match type {mime_type}"
+        )));
+    }
+
+    Ok(())
+}
+
+/// Associated data binding encrypted contents to their row and MIME type.
+fn data_aad(id: &str, mime_type: &str) -> Vec<u8> {
+    format!("attachment:{id}:{mime_type}").into_bytes()
+}
+
+/// Associated data binding an encrypted label to its row.
+fn label_aad(id: &str) -> Vec<u8> {
+    format!("attachment-label:{id}").into_bytes()
+}
+
+/// Encrypt and store an attachment.
+pub(crate) async fn store(
+    db: &Database,
+    key: &[u8; 32],
+    bytes: &[u8],
+    mime_type: &str,
+    label: &str,
+) -> Result<AttachmentId> {
+    validate(bytes, mime_type)?;
+
+    let id = AttachmentId::generate();
+    let data = encrypt_blob(bytes, key, &data_aad(id.as_str(), mime_type))?;
+    let label = encrypt_blob(label.as_bytes(), key, &label_aad(id.as_str()))?;
+
+    sqlx::query(
+        "INSERT INTO attachments (id, mime_type, label, data, size_bytes, created_at)
+         VALUES (?, ?, ?, ?, ?, ?)",
+    )
+    .bind(id.as_str())
+    .bind(mime_type)
+    .bind(label.to_bytes())
+    .bind(data.to_bytes())
+    .bind(i64::try_from(bytes.len()).unwrap_or(i64::MAX))
+    .bind(Timestamp::now().to_rfc3339())
+    .execute(db.pool())
+    .await?;
+
+    Ok(id)
+}
+
+/// Load and decrypt an attachment's contents and MIME type.
+pub(crate) async fn load(
+    db: &Database,
+    key: &[u8; 32],
+    id: &AttachmentId,
+) -> Result<(Zeroizing<Vec<u8>>, String)> {
+    let (mime_type, data) = sqlx::query_as::<_, (String, Vec<u8>)>(
+        "SELECT mime_type, data FROM attachments WHERE id = ?",
+    )
+    .bind(id.as_str())
+    .fetch_optional(db.pool())
+    .await?
+    .ok_or_else(|| VaultError::NotFound(format!("attachment {id}")))?;
+
+    let bytes = decrypt_blob(
+        &EncryptedBlob::from_bytes(&data)?,
+        key,
+        &data_aad(id.as_str(), &mime_type),
+    )?;
+    Ok((bytes, mime_type))
+}
+
+/// List every attachment's metadata, oldest first.
+pub(crate) async fn list(db: &Database, key: &[u8; 32]) -> Result<Vec<AttachmentInfo>> {
+    let rows = sqlx::query_as::<_, (String, String, Vec<u8>, i64, String)>(
+        "SELECT id, mime_type, label, size_bytes, created_at FROM attachments
+         ORDER BY created_at, id",
+    )
+    .fetch_all(db.pool())
+    .await?;
+
+    rows.into_iter()
+        .map(|(id, mime_type, label, size_bytes, created_at)| {
+            let label = decrypt_blob(&EncryptedBlob::from_bytes(&label)?, key, &label_aad(&id))?;
+            let label = String::from_utf8(label.to_vec()).map_err(|_| {
+                VaultError::InvalidData("attachment label is not UTF-8".to_string())
+            })?;
+            let created_at = Timestamp::from_rfc3339(&created_at)
+                .map_err(|e| VaultError::InvalidData(e.to_string()))?;
+            Ok(AttachmentInfo {
+                id: AttachmentId::new(id)?,
+                mime_type,
+                label,
+                size_bytes: u64::try_from(size_bytes).unwrap_or_default(),
+                created_at,
+            })
+        })
+        .collect()
+}
+
+/// Delete an attachment.
+pub(crate) async fn delete(db: &Database, id: &AttachmentId) -> Result<()> {
+    sqlx::query("DELETE FROM attachments WHERE id = ?")
+        .bind(id.as_str())
+        .execute(db.pool())
+        .await?;
+
+    Ok(())
+}
+
+/// Check that every attachment decrypts under `key`, reading `page_size`
+/// rows at a time.
+///
+/// # Errors
+/// Returns `VaultError::Decryption` for the first attachment that does not
+/// decrypt.
+pub(crate) async fn check_all(
+    conn: &mut SqliteConnection,
+    key: &[u8; 32],
+    page_size: u32,
+) -> Result<()> {
+    let mut after = String::new();
+    loop {
+        let rows = fetch_page(conn, &after, page_size).await?;
+        let Some((last, _, _, _)) = rows.last() else {
+            return Ok(());
+        };
+        after.clone_from(last);
+
+        for (id, mime_type, label, data) in &rows {
+            decrypt_blob(&EncryptedBlob::from_bytes(label)?, key, &label_aad(id))?;
+            decrypt_blob(
+                &EncryptedBlob::from_bytes(data)?,
+                key,
+                &data_aad(id, mime_type),
+            )?;
+        }
+    }
+}
+
+/// Re-encrypt up to `limit` attachments whose IDs sort after `after`, in ID
+/// order.
+///
+/// Returns the ID of the last attachment re-encrypted, or `None` once no
+/// attachments are left.
+pub(crate) async fn reencrypt_page(
+    conn: &mut SqliteConnection,
+    old_key: &[u8; 32],
+    new_key: &[u8; 32],
+    after: Option<&str>,
+    limit: u32,
+) -> Result<Option<String>> {
+    let rows = fetch_page(conn, after.unwrap_or_default(), limit).await?;
+    let last = rows.last().map(|(id, _, _, _)| id.clone());
+
+    for (id, mime_type, label, data) in rows {
+        let label_aad = label_aad(&id);
+        let label = decrypt_blob(&EncryptedBlob::from_bytes(&label)?, old_key, &label_aad)?;
+        let label = encrypt_blob(&label, new_key, &label_aad)?;
+
+        let data_aad = data_aad(&id, &mime_type);
+        let data = decrypt_blob(&EncryptedBlob::from_bytes(&data)?, old_key, &data_aad)?;
+        let data = encrypt_blob(&data, new_key, &data_aad)?;
+
+        sqlx::query("UPDATE attachments SET label = ?, data = ? WHERE id = ?")
+            .bind(label.to_bytes())
+            .bind(data.to_bytes())
+            .bind(&id)
+            .execute(&mut *conn)
+            .await?;
+    }
+
+    Ok(last)
+}
+
+/// Fetch up to `limit` encrypted attachment rows with IDs after `after`.
+async fn fetch_page(
+    conn: &mut SqliteConnection,
+    after: &str,
+    limit: u32,
+) -> Result<Vec<(String, String, Vec<u8>, Vec<u8>)>> {
+    Ok(sqlx::query_as(
+        "SELECT id, mime_type, label, data FROM attachments
+         WHERE id > ? ORDER BY id LIMIT ?",
+    )
+    .bind(after)
+    .bind(limit)
+    .fetch_all(&mut *conn)
+    .await?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_checks_size_and_type() {
+        assert!(validate(b"%PDF-1.4 test", "application/pdf").is_ok());
+        assert!(validate(b"\x89PNG\r\n\x1a\n....", "image/png").is_ok());
+        assert!(validate(b"Thanks, your listing was removed.", "text/plain").is_ok());
+
+        assert!(matches!(
+            validate(b"", "application/pdf"),
+            Err(VaultError::InvalidAttachment(_))
+        ));
+        assert!(matches!(
+            validate(&vec![b'a'; MAX_ATTACHMENT_BYTES + 1], "text/plain"),
+            Err(VaultError::InvalidAttachment(_))
+        ));
+        assert!(matches!(
+            validate(b"MZ\x90\x00", "application/x-msdownload"),
+            Err(VaultError::InvalidAttachment(_))
+        ));
+        // Declared type must match the contents
+        assert!(matches!(
+            validate(b"\x89PNG\r\n\x1a\n....", "application/pdf"),
+            Err(VaultError::InvalidAttachment(_))
+        ));
+    }
+
+    #[test]
+    fn test_attachment_id_validation() {
+        let id = AttachmentId::generate();
+        assert_eq!(AttachmentId::new(id.as_str()).expect("valid ID"), id);
+        assert!(AttachmentId::new("../etc/passwd").is_err());
+    }
+}
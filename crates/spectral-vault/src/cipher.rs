@@ -12,13 +12,25 @@
 //!
 //! Each encrypted field includes its own nonce and authentication tag,
 //! allowing independent encryption/decryption of fields.
+//!
+//! [`EncryptedField::encrypt`] serializes values as JSON. Composite values
+//! such as a phone number with its type can instead be stored as one field
+//! with [`EncryptedField::encrypt_value`], which uses a compact binary
+//! encoding.
+//!
+//! Other data stored at rest under the vault key (cookies, evidence, tokens)
+//! uses [`encrypt_blob`] and [`decrypt_blob`], which take associated data
+//! (AAD) binding the ciphertext to its context, so a blob copied into a
+//! different record fails to decrypt.
 
 use crate::error::{Result, VaultError};
 use chacha20poly1305::{
-    aead::{Aead, AeadCore, KeyInit, OsRng},
+    aead::{Aead, KeyInit, Payload},
     ChaCha20Poly1305, Nonce,
 };
 use serde::{Deserialize, Serialize};
+use spectral_core::RngSource;
+use zeroize::{Zeroize, Zeroizing};
 
 /// Length of the nonce in bytes (96 bits for ChaCha20-Poly1305).
 pub const NONCE_LENGTH: usize = 12;
@@ -71,29 +83,28 @@ where
     /// Returns `VaultError::Encryption` if encryption or serialization fails.
     pub fn encrypt(value: &T, key: &[u8; 32]) -> Result<Self> {
         // Serialize the value to JSON
-        let plaintext = serde_json::to_vec(value)
-            .map_err(|e| VaultError::Encryption(format!("serialization failed: {e}")))?;
-
-        // Generate random nonce
-        let nonce_bytes = ChaCha20Poly1305::generate_nonce(&mut OsRng);
-        let nonce_array: [u8; NONCE_LENGTH] = nonce_bytes
-            .as_slice()
-            .try_into()
-            .expect("nonce has correct length");
+        let plaintext = Zeroizing::new(
+            serde_json::to_vec(value)
+                .map_err(|e| VaultError::Encryption(format!("serialization failed: {e}")))?,
+        );
 
-        // Create cipher
-        let cipher = ChaCha20Poly1305::new(key.into());
+        Self::seal(&plaintext, key, &RngSource::os())
+    }
 
-        // Encrypt
-        let ciphertext = cipher
-            .encrypt(&nonce_bytes, plaintext.as_ref())
-            .map_err(|e| VaultError::Encryption(format!("encryption failed: {e}")))?;
+    /// Encrypt a value, drawing the nonce from `rng`.
+    ///
+    /// Same as [`encrypt`](Self::encrypt), for tests that need a
+    /// reproducible nonce from a seeded [`RngSource`].
+    ///
+    /// # Errors
+    /// Returns `VaultError::Encryption` if encryption or serialization fails.
+    pub fn encrypt_with_rng(value: &T, key: &[u8; 32], rng: &RngSource) -> Result<Self> {
+        let plaintext = Zeroizing::new(
+            serde_json::to_vec(value)
+                .map_err(|e| VaultError::Encryption(format!("serialization failed: {e}")))?,
+        );
 
-        Ok(Self {
-            ciphertext,
-            nonce: nonce_array,
-            _phantom: std::marker::PhantomData,
-        })
+        Self::seal(&plaintext, key, rng)
     }
 
     /// Decrypt the field using the provided key.
@@ -110,6 +121,83 @@ where
     /// - The ciphertext has been tampered with
     /// - Deserialization fails
     pub fn decrypt(&self, key: &[u8; 32]) -> Result<T> {
+        let plaintext = self.open(key)?;
+
+        // Deserialize
+        let value = serde_json::from_slice(&plaintext)
+            .map_err(|e| VaultError::Decryption(format!("deserialization failed: {e}")))?;
+
+        Ok(value)
+    }
+
+    /// Encrypt a value using a compact binary encoding.
+    ///
+    /// Suited to structured values that would otherwise need one encrypted
+    /// field per member. The field must be decrypted with
+    /// [`EncryptedField::decrypt_value`], as nothing in the ciphertext
+    /// records which encoding was used. Serde attributes that depend on
+    /// self-describing formats, such as `untagged`, `flatten` and
+    /// `skip_serializing_if`, are not supported.
+    ///
+    /// # Errors
+    /// Returns `VaultError::Encryption` if encryption or serialization fails.
+    pub fn encrypt_value(value: &T, key: &[u8; 32]) -> Result<Self> {
+        let plaintext = Zeroizing::new(
+            bincode::serialize(value)
+                .map_err(|e| VaultError::Encryption(format!("serialization failed: {e}")))?,
+        );
+
+        Self::seal(&plaintext, key, &RngSource::os())
+    }
+
+    /// Decrypt a field encrypted with [`EncryptedField::encrypt_value`].
+    ///
+    /// # Errors
+    /// Returns `VaultError::Decryption` if the key is incorrect, the
+    /// ciphertext has been tampered with, or deserialization fails.
+    pub fn decrypt_value(&self, key: &[u8; 32]) -> Result<T> {
+        let plaintext = self.open(key)?;
+
+        bincode::deserialize(&plaintext)
+            .map_err(|e| VaultError::Decryption(format!("deserialization failed: {e}")))
+    }
+
+    /// Decrypt with `old_key` and encrypt the same value under `new_key`.
+    ///
+    /// Used when the master password changes. The serialized plaintext is
+    /// never deserialized into `T`; it lives only in a zeroizing buffer
+    /// between the two cipher operations. A fresh nonce is generated.
+    ///
+    /// # Errors
+    /// Returns `VaultError::Decryption` if `old_key` is wrong, or
+    /// `VaultError::Encryption` if re-encryption fails.
+    pub fn reencrypt(&self, old_key: &[u8; 32], new_key: &[u8; 32]) -> Result<Self> {
+        let plaintext = self.open(old_key)?;
+        Self::seal(&plaintext, new_key, &RngSource::os())
+    }
+
+    /// Encrypt serialized plaintext under a fresh random nonce.
+    fn seal(plaintext: &[u8], key: &[u8; 32], rng: &RngSource) -> Result<Self> {
+        // Generate random nonce
+        let nonce_array = generate_nonce(rng);
+
+        // Create cipher
+        let cipher = ChaCha20Poly1305::new(key.into());
+
+        // Encrypt
+        let ciphertext = cipher
+            .encrypt(Nonce::from_slice(&nonce_array), plaintext)
+            .map_err(|e| VaultError::Encryption(format!("encryption failed: {e}")))?;
+
+        Ok(Self {
+            ciphertext,
+            nonce: nonce_array,
+            _phantom: std::marker::PhantomData,
+        })
+    }
+
+    /// Decrypt to the serialized plaintext, zeroized on drop.
+    fn open(&self, key: &[u8; 32]) -> Result<Zeroizing<Vec<u8>>> {
         // Create cipher
         let cipher = ChaCha20Poly1305::new(key.into());
 
@@ -119,11 +207,7 @@ where
             .decrypt(nonce, self.ciphertext.as_ref())
             .map_err(|e| VaultError::Decryption(format!("decryption failed: {e}")))?;
 
-        // Deserialize
-        let value = serde_json::from_slice(&plaintext)
-            .map_err(|e| VaultError::Decryption(format!("deserialization failed: {e}")))?;
-
-        Ok(value)
+        Ok(Zeroizing::new(plaintext))
     }
 
     /// Get the size of the ciphertext in bytes.
@@ -145,6 +229,24 @@ where
     }
 }
 
+impl<T> EncryptedField<T>
+where
+    T: Serialize + for<'de> Deserialize<'de> + Zeroize,
+{
+    /// Decrypt the field into a buffer that is wiped when dropped.
+    ///
+    /// Prefer this over [`EncryptedField::decrypt`] for PII that is only
+    /// needed briefly, so the plaintext does not linger in freed memory.
+    /// The wrapper derefs to `T`.
+    ///
+    /// # Errors
+    /// Returns `VaultError::Decryption` under the same conditions as
+    /// [`EncryptedField::decrypt`].
+    pub fn decrypt_zeroizing(&self, key: &[u8; 32]) -> Result<Zeroizing<T>> {
+        self.decrypt(key).map(Zeroizing::new)
+    }
+}
+
 /// Encrypt a string value.
 ///
 /// Convenience function for encrypting strings without needing to specify the type.
@@ -159,6 +261,135 @@ pub fn decrypt_string(field: &EncryptedField<String>, key: &[u8; 32]) -> Result<
     field.decrypt(key)
 }
 
+/// Arbitrary bytes encrypted under the vault key with associated data.
+///
+/// The associated data is authenticated but not stored; the caller supplies
+/// the same value again to decrypt.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EncryptedBlob {
+    /// Ciphertext + authentication tag (16 bytes)
+    ciphertext: Vec<u8>,
+    /// Random nonce used for this encryption
+    nonce: [u8; NONCE_LENGTH],
+}
+
+impl EncryptedBlob {
+    /// Create an `EncryptedBlob` from raw ciphertext and nonce.
+    ///
+    /// This is used when loading encrypted data from storage.
+    #[must_use]
+    pub fn from_raw(ciphertext: Vec<u8>, nonce: [u8; NONCE_LENGTH]) -> Self {
+        Self { ciphertext, nonce }
+    }
+
+    /// Encode as `nonce || ciphertext` for storage in a single column.
+    #[must_use]
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(NONCE_LENGTH + self.ciphertext.len());
+        bytes.extend_from_slice(&self.nonce);
+        bytes.extend_from_slice(&self.ciphertext);
+        bytes
+    }
+
+    /// Decode bytes produced by [`EncryptedBlob::to_bytes`].
+    ///
+    /// # Errors
+    /// Returns `VaultError::InvalidData` if `bytes` is too short to hold a
+    /// nonce.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        if bytes.len() < NONCE_LENGTH {
+            return Err(VaultError::InvalidData(
+                "encrypted blob is shorter than its nonce".to_string(),
+            ));
+        }
+        let (nonce, ciphertext) = bytes.split_at(NONCE_LENGTH);
+        Ok(Self {
+            ciphertext: ciphertext.to_vec(),
+            nonce: nonce.try_into().expect("nonce has correct length"),
+        })
+    }
+
+    /// Get the nonce as a byte slice.
+    #[must_use]
+    pub fn nonce(&self) -> &[u8; NONCE_LENGTH] {
+        &self.nonce
+    }
+
+    /// Get the ciphertext as a byte slice.
+    #[must_use]
+    pub fn ciphertext(&self) -> &[u8] {
+        &self.ciphertext
+    }
+}
+
+/// Generate a fresh nonce from `rng`.
+fn generate_nonce(rng: &RngSource) -> [u8; NONCE_LENGTH] {
+    let mut nonce = [0u8; NONCE_LENGTH];
+    rng.fill_bytes(&mut nonce);
+    nonce
+}
+
+/// Encrypt arbitrary bytes, binding them to `aad`.
+///
+/// `aad` should identify what the blob is and where it belongs, e.g.
+/// `b"cookies:broker-id"`, so it cannot be swapped into another record.
+///
+/// # Errors
+/// Returns `VaultError::Encryption` if encryption fails.
+pub fn encrypt_blob(plaintext: &[u8], key: &[u8; 32], aad: &[u8]) -> Result<EncryptedBlob> {
+    encrypt_blob_with_rng(plaintext, key, aad, &RngSource::os())
+}
+
+/// Encrypt a blob as [`encrypt_blob`] does, drawing the nonce from `rng`.
+///
+/// # Errors
+/// Returns `VaultError::Encryption` if encryption fails.
+pub fn encrypt_blob_with_rng(
+    plaintext: &[u8],
+    key: &[u8; 32],
+    aad: &[u8],
+    rng: &RngSource,
+) -> Result<EncryptedBlob> {
+    let nonce = generate_nonce(rng);
+
+    let cipher = ChaCha20Poly1305::new(key.into());
+    let ciphertext = cipher
+        .encrypt(
+            Nonce::from_slice(&nonce),
+            Payload {
+                msg: plaintext,
+                aad,
+            },
+        )
+        .map_err(|e| VaultError::Encryption(format!("encryption failed: {e}")))?;
+
+    Ok(EncryptedBlob { ciphertext, nonce })
+}
+
+/// Decrypt a blob encrypted with [`encrypt_blob`], zeroized on drop.
+///
+/// # Errors
+/// Returns `VaultError::Decryption` if the key or `aad` does not match, or
+/// the blob has been tampered with.
+pub fn decrypt_blob(
+    blob: &EncryptedBlob,
+    key: &[u8; 32],
+    aad: &[u8],
+) -> Result<Zeroizing<Vec<u8>>> {
+    let cipher = ChaCha20Poly1305::new(key.into());
+    let plaintext = cipher
+        .decrypt(
+            Nonce::from_slice(&blob.nonce),
+            Payload {
+                msg: &blob.ciphertext,
+                aad,
+            },
+        )
+        .map_err(|e| VaultError::Decryption(format!("decryption failed: {e}")))?;
+
+    Ok(Zeroizing::new(plaintext))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -216,6 +447,29 @@ mod tests {
         assert_eq!(decrypt_string(&encrypted2, &key).expect("decrypt 2"), value);
     }
 
+    #[test]
+    fn test_seeded_nonces_are_reproducible() {
+        let key = test_key();
+
+        let encrypted1 =
+            EncryptedField::encrypt_with_rng(&"test".to_string(), &key, &RngSource::seeded(7))
+                .expect("encrypt 1");
+        let encrypted2 =
+            EncryptedField::encrypt_with_rng(&"test".to_string(), &key, &RngSource::seeded(7))
+                .expect("encrypt 2");
+
+        // Same seed, same nonce, so the ciphertexts match too
+        assert_eq!(encrypted1.nonce(), encrypted2.nonce());
+        assert_eq!(encrypted1.ciphertext(), encrypted2.ciphertext());
+        assert_eq!(encrypted1.decrypt(&key).expect("decrypt"), "test");
+
+        let blob1 = encrypt_blob_with_rng(b"token", &key, b"oauth:gmail", &RngSource::seeded(9))
+            .expect("encrypt blob 1");
+        let blob2 = encrypt_blob_with_rng(b"token", &key, b"oauth:gmail", &RngSource::seeded(9))
+            .expect("encrypt blob 2");
+        assert_eq!(blob1, blob2);
+    }
+
     #[test]
     fn test_wrong_key_fails() {
         let key1 = [0x42; 32];
@@ -232,6 +486,40 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_reencrypt() {
+        let old_key = [0x42; 32];
+        let new_key = [0x43; 32];
+
+        let encrypted = encrypt_string("secret", &old_key).expect("encrypt");
+        let reencrypted = encrypted.reencrypt(&old_key, &new_key).expect("reencrypt");
+
+        assert_eq!(
+            decrypt_string(&reencrypted, &new_key).expect("decrypt"),
+            "secret"
+        );
+        assert!(decrypt_string(&reencrypted, &old_key).is_err());
+        assert!(encrypted.reencrypt(&new_key, &old_key).is_err());
+    }
+
+    #[test]
+    fn test_decrypt_zeroizing() {
+        let key = test_key();
+
+        let encrypted = encrypt_string("john@example.com", &key).expect("encrypt");
+        let decrypted = encrypted.decrypt_zeroizing(&key).expect("decrypt");
+        assert_eq!(*decrypted, "john@example.com");
+        assert_eq!(decrypted.len(), "john@example.com".len());
+        assert!(decrypted.ends_with("example.com"));
+
+        let bytes = vec![0u8, 1, 2, 255];
+        let encrypted = EncryptedField::encrypt(&bytes, &key).expect("encrypt");
+        let decrypted = encrypted.decrypt_zeroizing(&key).expect("decrypt");
+        assert_eq!(decrypted.as_slice(), bytes.as_slice());
+
+        assert!(encrypted.decrypt_zeroizing(&[0x43; 32]).is_err());
+    }
+
     #[test]
     fn test_tampered_ciphertext_fails() {
         let key = test_key();
@@ -323,4 +611,91 @@ mod tests {
         // Ciphertext should be longer than plaintext due to authentication tag (16 bytes)
         assert!(encrypted.ciphertext_len() > value.len());
     }
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct TestPhone {
+        number: String,
+        normalized: Option<String>,
+        phone_type: crate::profile::PhoneType,
+    }
+
+    fn test_phone() -> TestPhone {
+        TestPhone {
+            number: "(555) 123-4567".to_string(),
+            normalized: Some("5551234567".to_string()),
+            phone_type: crate::profile::PhoneType::Mobile,
+        }
+    }
+
+    #[test]
+    fn test_encrypt_value_roundtrip() {
+        let key = test_key();
+        let original = test_phone();
+
+        let encrypted = EncryptedField::encrypt_value(&original, &key).expect("encrypt");
+        let decrypted: TestPhone = encrypted.decrypt_value(&key).expect("decrypt");
+        assert_eq!(decrypted, original);
+
+        // Survives storage as a single field and re-keying
+        let json = serde_json::to_string(&encrypted).expect("serialize");
+        let stored: EncryptedField<TestPhone> = serde_json::from_str(&json).expect("deserialize");
+        let new_key = [0x43; 32];
+        let rekeyed = stored.reencrypt(&key, &new_key).expect("reencrypt");
+        assert_eq!(rekeyed.decrypt_value(&new_key).expect("decrypt"), original);
+
+        assert!(matches!(
+            encrypted.decrypt_value(&new_key),
+            Err(VaultError::Decryption(_))
+        ));
+    }
+
+    #[test]
+    fn test_encrypt_value_is_more_compact_than_json() {
+        let key = test_key();
+        let original = test_phone();
+
+        let binary = EncryptedField::encrypt_value(&original, &key).expect("encrypt binary");
+        let json = EncryptedField::encrypt(&original, &key).expect("encrypt json");
+        assert!(binary.ciphertext_len() < json.ciphertext_len());
+
+        // The encodings are not interchangeable
+        assert!(binary.decrypt(&key).is_err());
+    }
+
+    #[test]
+    fn test_blob_roundtrip() {
+        let key = test_key();
+        let plaintext = b"session=abc123; path=/";
+
+        let blob = encrypt_blob(plaintext, &key, b"cookies:spokeo").expect("encrypt");
+        assert_ne!(blob.ciphertext(), plaintext);
+
+        let stored = EncryptedBlob::from_bytes(&blob.to_bytes()).expect("decode");
+        assert_eq!(stored, blob);
+
+        let decrypted = decrypt_blob(&stored, &key, b"cookies:spokeo").expect("decrypt");
+        assert_eq!(decrypted.as_slice(), plaintext);
+    }
+
+    #[test]
+    fn test_blob_aad_mismatch_fails() {
+        let key = test_key();
+        let blob = encrypt_blob(b"token", &key, b"oauth:gmail").expect("encrypt");
+
+        for aad in [&b"oauth:outlook"[..], b""] {
+            match decrypt_blob(&blob, &key, aad) {
+                Err(VaultError::Decryption(_)) => {}
+                _ => panic!("expected Decryption error"),
+            }
+        }
+        assert!(decrypt_blob(&blob, &[0x43; 32], b"oauth:gmail").is_err());
+    }
+
+    #[test]
+    fn test_blob_from_short_bytes_fails() {
+        assert!(matches!(
+            EncryptedBlob::from_bytes(&[0u8; NONCE_LENGTH - 1]),
+            Err(VaultError::InvalidData(_))
+        ));
+    }
 }
@@ -3,6 +3,31 @@
 use thiserror::Error;
 
 /// Errors that can occur during vault operations.
+///
+/// Errors from lower layers convert with `?`: database and `SQLx` errors
+/// become [`Database`](Self::Database), JSON errors become
+/// [`Serialization`](Self::Serialization) and malformed IDs become
+/// [`InvalidData`](Self::InvalidData). Callers decide what to do with an
+/// error by its class rather than matching variants:
+///
+/// - [`is_retryable`](Self::is_retryable): a transient database failure;
+///   the same operation may succeed if tried again.
+/// - [`is_user_error`](Self::is_user_error): caused by the user's input or
+///   the vault's state, e.g. a wrong password; show it to the user.
+/// - Anything else is a bug or corrupted data and should be logged.
+///
+/// ```
+/// use spectral_vault::VaultError;
+///
+/// fn open() -> Result<(), VaultError> {
+///     Err(sqlx::Error::PoolTimedOut)?
+/// }
+///
+/// let err = open().unwrap_err();
+/// assert!(err.is_retryable());
+/// assert!(!err.is_user_error());
+/// assert!(VaultError::InvalidPassword.is_user_error());
+/// ```
 #[derive(Debug, Error)]
 pub enum VaultError {
     /// Vault is locked and must be unlocked first.
@@ -13,6 +38,10 @@ pub enum VaultError {
     #[error("key derivation failed: {0}")]
     KeyDerivation(String),
 
+    /// Key derivation parameters are below the enforced minimum.
+    #[error("key derivation parameters too weak: {0}")]
+    WeakKdfParams(String),
+
     /// Encryption operation failed.
     #[error("encryption failed: {0}")]
     Encryption(String),
@@ -29,10 +58,18 @@ pub enum VaultError {
     #[error("invalid vault data: {0}")]
     InvalidData(String),
 
+    /// Attachment was rejected (too large, empty, or unsupported type).
+    #[error("invalid attachment: {0}")]
+    InvalidAttachment(String),
+
     /// Database operation failed.
     #[error("database error: {0}")]
     Database(#[from] spectral_db::DatabaseError),
 
+    /// Imported profile data could not be parsed.
+    #[error("could not import profile: {}", .0.join("; "))]
+    ImportValidation(Vec<String>),
+
     /// Serialization/deserialization error.
     #[error("serialization error: {0}")]
     Serialization(String),
@@ -46,5 +83,84 @@ pub enum VaultError {
     VaultNotFound(String),
 }
 
+impl VaultError {
+    /// Whether the operation may succeed if tried again unchanged.
+    #[must_use]
+    pub fn is_retryable(&self) -> bool {
+        matches!(self, Self::Database(e) if e.is_retryable())
+    }
+
+    /// Whether the error is caused by the user's input or the vault's state
+    /// and should be shown to them, rather than an internal failure.
+    #[must_use]
+    pub fn is_user_error(&self) -> bool {
+        matches!(
+            self,
+            Self::Locked
+                | Self::InvalidPassword
+                | Self::VaultNotFound(_)
+                | Self::NotFound(_)
+                | Self::InvalidAttachment(_)
+                | Self::ImportValidation(_)
+        )
+    }
+}
+
+impl From<sqlx::Error> for VaultError {
+    fn from(e: sqlx::Error) -> Self {
+        Self::Database(e.into())
+    }
+}
+
+impl From<serde_json::Error> for VaultError {
+    fn from(e: serde_json::Error) -> Self {
+        Self::Serialization(e.to_string())
+    }
+}
+
+impl From<spectral_core::IdError> for VaultError {
+    fn from(e: spectral_core::IdError) -> Self {
+        Self::InvalidData(e.to_string())
+    }
+}
+
 /// Result type for vault operations.
 pub type Result<T> = std::result::Result<T, VaultError>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_connection_error_is_retryable() {
+        let refused = std::io::Error::from(std::io::ErrorKind::ConnectionRefused);
+        let err = VaultError::from(sqlx::Error::Io(refused));
+
+        assert!(matches!(err, VaultError::Database(_)));
+        assert!(err.is_retryable());
+        assert!(!err.is_user_error());
+    }
+
+    #[test]
+    fn test_invalid_password_is_user_error() {
+        let err = VaultError::InvalidPassword;
+
+        assert!(err.is_user_error());
+        assert!(!err.is_retryable());
+    }
+
+    #[test]
+    fn test_internal_errors_are_neither() {
+        let errors = [
+            VaultError::from(sqlx::Error::RowNotFound),
+            VaultError::Decryption("tag mismatch".to_string()),
+            VaultError::from(serde_json::from_str::<u32>("x").unwrap_err()),
+            VaultError::from(spectral_core::ProfileId::new("").unwrap_err()),
+        ];
+
+        for err in errors {
+            assert!(!err.is_retryable(), "{err} should not be retryable");
+            assert!(!err.is_user_error(), "{err} should not be a user error");
+        }
+    }
+}
@@ -0,0 +1,848 @@
+//! Profile import from vCard and JSON.
+//!
+//! Lets users seed a profile from an exported contact card or a pasted JSON
+//! object instead of typing every field. Common fields (names, emails,
+//! phones, addresses, birthday, employer) are mapped onto encrypted profile
+//! fields; properties that have no profile equivalent are skipped.
+//!
+//! Values that are recognized but cannot be parsed are collected and reported
+//! together as [`VaultError::ImportValidation`]. Problems describe where the
+//! value came from (line number or JSON key), never the value itself.
+
+use crate::cipher::encrypt_string;
+use crate::error::{Result, VaultError};
+use crate::profile::{
+    Alias, EmailAddress, EmailType, PhoneNumber, PhoneType, PreviousAddress, UserProfile,
+};
+use chrono::NaiveDate;
+use serde_json::{Map, Value};
+use spectral_core::normalize_country;
+use spectral_core::types::ProfileId;
+
+/// Plaintext fields gathered from an import source before encryption.
+#[derive(Default)]
+struct ImportedFields {
+    full_name: Option<String>,
+    first_name: Option<String>,
+    middle_name: Option<String>,
+    last_name: Option<String>,
+    nicknames: Vec<String>,
+    emails: Vec<(String, EmailType)>,
+    phones: Vec<(String, PhoneType)>,
+    addresses: Vec<ImportedAddress>,
+    date_of_birth: Option<String>,
+    employer: Option<String>,
+    job_title: Option<String>,
+}
+
+#[derive(Default)]
+struct ImportedAddress {
+    line1: Option<String>,
+    line2: Option<String>,
+    city: Option<String>,
+    state: Option<String>,
+    zip_code: Option<String>,
+    country: Option<String>,
+}
+
+impl ImportedAddress {
+    fn is_empty(&self) -> bool {
+        self.line1.is_none()
+            && self.line2.is_none()
+            && self.city.is_none()
+            && self.state.is_none()
+            && self.zip_code.is_none()
+            && self.country.is_none()
+    }
+}
+
+impl UserProfile {
+    /// Build a new profile from a vCard (versions 2.1, 3.0 and 4.0).
+    ///
+    /// Reads `FN`, `N`, `NICKNAME`, `EMAIL`, `TEL`, `ADR`, `BDAY`, `ORG` and
+    /// `TITLE` from the first card in `input`. The first address becomes the
+    /// current address and any others become previous addresses.
+    ///
+    /// # Errors
+    /// Returns `VaultError::ImportValidation` listing every property that
+    /// could not be parsed, or if the card contains no profile fields.
+    pub fn from_vcard(input: &str, key: &[u8; 32]) -> Result<Self> {
+        let (fields, problems) = parse_vcard(input);
+        fields.into_profile(problems, key)
+    }
+
+    /// Build a new profile from a JSON object.
+    ///
+    /// Keys are accepted in `snake_case` or `camelCase` (e.g. `first_name`
+    /// or `firstName`). `emails`, `phones` and `addresses` may hold strings
+    /// or objects with a `type`.
+    ///
+    /// # Errors
+    /// Returns `VaultError::ImportValidation` if the input is not a JSON
+    /// object, lists every key whose value could not be parsed, or if the
+    /// object contains no profile fields.
+    pub fn from_json(input: &str, key: &[u8; 32]) -> Result<Self> {
+        let value: Value = serde_json::from_str(input).map_err(|e| {
+            VaultError::ImportValidation(vec![format!(
+                "invalid JSON at line {}, column {}",
+                e.line(),
+                e.column()
+            )])
+        })?;
+        let Value::Object(object) = value else {
+            return Err(VaultError::ImportValidation(vec![
+                "expected a JSON object".to_string()
+            ]));
+        };
+
+        let (fields, problems) = parse_json(&object);
+        fields.into_profile(problems, key)
+    }
+}
+
+impl ImportedFields {
+    fn is_empty(&self) -> bool {
+        self.full_name.is_none()
+            && self.first_name.is_none()
+            && self.last_name.is_none()
+            && self.emails.is_empty()
+            && self.phones.is_empty()
+            && self.addresses.is_empty()
+    }
+
+    /// Fill in whichever of the full name and its parts are missing.
+    fn complete_names(&mut self) {
+        if self.first_name.is_none() && self.last_name.is_none() {
+            if let Some(full_name) = &self.full_name {
+                let words: Vec<&str> = full_name.split_whitespace().collect();
+                if let [first, .., last] = words.as_slice() {
+                    self.first_name = Some((*first).to_string());
+                    self.last_name = Some((*last).to_string());
+                }
+            }
+        }
+        if self.full_name.is_none() {
+            let parts: Vec<&str> = [&self.first_name, &self.middle_name, &self.last_name]
+                .into_iter()
+                .filter_map(|part| part.as_deref())
+                .collect();
+            if !parts.is_empty() {
+                self.full_name = Some(parts.join(" "));
+            }
+        }
+    }
+
+    fn into_profile(mut self, mut problems: Vec<String>, key: &[u8; 32]) -> Result<UserProfile> {
+        if problems.is_empty() && self.is_empty() {
+            problems.push("no name, email, phone or address found".to_string());
+        }
+        self.complete_names();
+
+        let mut profile = UserProfile::new(ProfileId::generate());
+        let encrypt = |value: Option<String>| value.map(|v| encrypt_string(&v, key)).transpose();
+
+        profile.full_name = encrypt(self.full_name)?;
+        profile.first_name = encrypt(self.first_name)?;
+        profile.middle_name = encrypt(self.middle_name)?;
+        profile.last_name = encrypt(self.last_name)?;
+        profile.date_of_birth = encrypt(self.date_of_birth)?;
+        profile.employer = encrypt(self.employer)?;
+        profile.job_title = encrypt(self.job_title)?;
+
+        for nickname in self.nicknames {
+            profile.aliases.push(Alias {
+                first_name: None,
+                middle_name: None,
+                last_name: None,
+                nickname: Some(encrypt_string(&nickname, key)?),
+            });
+        }
+
+        for (index, (email, email_type)) in self.emails.into_iter().enumerate() {
+            match EmailAddress::new(email, email_type, key) {
+                Ok(email) => profile.email_addresses.push(email),
+                Err(VaultError::InvalidData(_)) => {
+                    problems.push(format!("email {}: invalid email address", index + 1));
+                }
+                Err(e) => return Err(e),
+            }
+        }
+
+        for (index, (number, phone_type)) in self.phones.into_iter().enumerate() {
+            match PhoneNumber::new(number, phone_type, key) {
+                Ok(phone) => profile.phone_numbers.push(phone),
+                Err(VaultError::InvalidData(_)) => {
+                    problems.push(format!(
+                        "phone {}: expected a 10-digit US phone number",
+                        index + 1
+                    ));
+                }
+                Err(e) => return Err(e),
+            }
+        }
+
+        let mut addresses = self.addresses.into_iter();
+        if let Some(current) = addresses.next() {
+            let street = match (current.line1, current.line2) {
+                (Some(line1), Some(line2)) => Some(format!("{line1}\n{line2}")),
+                (line1, line2) => line1.or(line2),
+            };
+            profile.address = encrypt(street)?;
+            profile.city = encrypt(current.city)?;
+            profile.state = encrypt(current.state)?;
+            profile.zip_code = encrypt(current.zip_code)?;
+            profile.country = encrypt(current.country.as_deref().map(normalize_country))?;
+        }
+        for previous in addresses {
+            let text = |value: Option<String>| encrypt_string(&value.unwrap_or_default(), key);
+            profile.previous_addresses_v2.push(PreviousAddress {
+                address_line1: text(previous.line1)?,
+                address_line2: encrypt(previous.line2)?,
+                city: text(previous.city)?,
+                state: text(previous.state)?,
+                zip_code: text(previous.zip_code)?,
+                lived_from: None,
+                lived_to: None,
+            });
+        }
+
+        if problems.is_empty() {
+            Ok(profile)
+        } else {
+            Err(VaultError::ImportValidation(problems))
+        }
+    }
+}
+
+/// Parse a birthday in `YYYY-MM-DD` or `YYYYMMDD` form into ISO 8601.
+///
+/// A trailing time component (as allowed by vCard) is ignored.
+fn parse_birthday(value: &str) -> Option<String> {
+    let date = value.trim().split('T').next().unwrap_or_default();
+    NaiveDate::parse_from_str(date, "%Y-%m-%d")
+        .or_else(|_| NaiveDate::parse_from_str(date, "%Y%m%d"))
+        .ok()
+        .map(|date| date.format("%Y-%m-%d").to_string())
+}
+
+/// Trim a value, mapping blank strings to `None`.
+fn non_empty(value: &str) -> Option<String> {
+    let trimmed = value.trim();
+    (!trimmed.is_empty()).then(|| trimmed.to_string())
+}
+
+// ---------------------------------------------------------------------------
+// vCard
+// ---------------------------------------------------------------------------
+
+fn parse_vcard(input: &str) -> (ImportedFields, Vec<String>) {
+    let mut fields = ImportedFields::default();
+    let mut problems = Vec::new();
+    let mut in_card = false;
+    let mut finished = false;
+
+    for (line_number, line) in unfold_lines(input) {
+        let Some((name, params, value)) = split_property(&line) else {
+            if in_card {
+                problems.push(format!("line {line_number}: expected NAME:value"));
+            }
+            continue;
+        };
+
+        match (in_card, name.as_str()) {
+            (false, "BEGIN") if value.eq_ignore_ascii_case("VCARD") => in_card = true,
+            (false, _) => {}
+            (true, "END") if value.eq_ignore_ascii_case("VCARD") => {
+                finished = true;
+                break;
+            }
+            (true, _) => {
+                if let Err(problem) = apply_vcard_property(&mut fields, &name, &params, &value) {
+                    problems.push(format!("line {line_number} ({name}): {problem}"));
+                }
+            }
+        }
+    }
+
+    if !in_card {
+        problems.insert(0, "missing BEGIN:VCARD".to_string());
+    } else if !finished {
+        problems.push("missing END:VCARD".to_string());
+    }
+    (fields, problems)
+}
+
+/// Join folded continuation lines, returning each logical line with the
+/// number of the physical line it started on.
+fn unfold_lines(input: &str) -> Vec<(usize, String)> {
+    let mut lines: Vec<(usize, String)> = Vec::new();
+    for (index, raw) in input.lines().enumerate() {
+        match (raw.strip_prefix([' ', '\t']), lines.last_mut()) {
+            (Some(continuation), Some((_, previous))) => previous.push_str(continuation),
+            _ if raw.trim().is_empty() => {}
+            _ => lines.push((index + 1, raw.to_string())),
+        }
+    }
+    lines
+}
+
+/// Split `group.NAME;PARAM=x;TYPE=a,b:value` into its upper-cased name, the
+/// lower-cased parameter values, and the raw value.
+fn split_property(line: &str) -> Option<(String, Vec<String>, String)> {
+    let (head, value) = line.split_once(':')?;
+    let mut parts = head.split(';');
+    let name = parts.next()?.rsplit('.').next()?.trim().to_uppercase();
+    if name.is_empty() {
+        return None;
+    }
+
+    let params = parts
+        .flat_map(|param| {
+            let (param_name, param_value) = param.split_once('=').unwrap_or(("TYPE", param));
+            let param_name = param_name.trim().to_uppercase();
+            param_value
+                .split(',')
+                .map(move |v| format!("{param_name}={}", v.trim().trim_matches('"')))
+        })
+        .map(|param| param.to_lowercase())
+        .collect();
+    Some((name, params, value.to_string()))
+}
+
+fn has_type(params: &[String], types: &[&str]) -> bool {
+    params.iter().any(|param| {
+        param
+            .strip_prefix("type=")
+            .is_some_and(|value| types.contains(&value))
+    })
+}
+
+fn apply_vcard_property(
+    fields: &mut ImportedFields,
+    name: &str,
+    params: &[String],
+    value: &str,
+) -> std::result::Result<(), &'static str> {
+    let known = matches!(
+        name,
+        "FN" | "N" | "NICKNAME" | "EMAIL" | "TEL" | "ADR" | "BDAY" | "ORG" | "TITLE"
+    );
+    if !known {
+        return Ok(());
+    }
+    if params
+        .iter()
+        .any(|param| param.starts_with("encoding=") && param != "encoding=8bit")
+    {
+        return Err("encoded values are not supported");
+    }
+
+    match name {
+        "FN" => fields.full_name = non_empty(&unescape(value)),
+        "N" => {
+            let parts = split_components(value, ';');
+            let part = |index: usize| parts.get(index).and_then(|p| non_empty(p));
+            fields.last_name = part(0);
+            fields.first_name = part(1);
+            fields.middle_name = part(2);
+        }
+        "NICKNAME" => fields.nicknames.extend(
+            split_components(value, ',')
+                .iter()
+                .filter_map(|nickname| non_empty(nickname)),
+        ),
+        "EMAIL" => {
+            let email = non_empty(&unescape(value)).ok_or("empty email address")?;
+            let email_type = if has_type(params, &["work"]) {
+                EmailType::Work
+            } else {
+                EmailType::Personal
+            };
+            fields.emails.push((email, email_type));
+        }
+        "TEL" => {
+            let value = unescape(value);
+            let number = non_empty(value.strip_prefix("tel:").unwrap_or(&value))
+                .ok_or("empty phone number")?;
+            let phone_type = if has_type(params, &["work"]) {
+                PhoneType::Work
+            } else if has_type(params, &["home"]) && !has_type(params, &["cell", "mobile"]) {
+                PhoneType::Home
+            } else {
+                PhoneType::Mobile
+            };
+            fields.phones.push((number, phone_type));
+        }
+        "ADR" => {
+            // PO box; extended address; street; locality; region; postal code; country
+            let parts = split_components(value, ';');
+            let part = |index: usize| parts.get(index).and_then(|p| non_empty(p));
+            let address = ImportedAddress {
+                line1: part(2),
+                line2: part(1),
+                city: part(3),
+                state: part(4),
+                zip_code: part(5),
+                country: part(6),
+            };
+            if address.is_empty() {
+                return Err("empty address");
+            }
+            fields.addresses.push(address);
+        }
+        "BDAY" => {
+            let date = parse_birthday(&unescape(value)).ok_or("expected a date with a year")?;
+            fields.date_of_birth = Some(date);
+        }
+        "ORG" => {
+            fields.employer = split_components(value, ';')
+                .first()
+                .and_then(|org| non_empty(org));
+        }
+        "TITLE" => fields.job_title = non_empty(&unescape(value)),
+        _ => {}
+    }
+    Ok(())
+}
+
+/// Split a structured value on unescaped `separator`, unescaping each part.
+fn split_components(value: &str, separator: char) -> Vec<String> {
+    let mut parts = vec![String::new()];
+    let mut chars = value.chars();
+    while let Some(c) = chars.next() {
+        let current = parts.last_mut().expect("parts is never empty");
+        match c {
+            '\\' => match chars.next() {
+                Some('n' | 'N') => current.push('\n'),
+                Some(escaped) => current.push(escaped),
+                None => {}
+            },
+            c if c == separator => parts.push(String::new()),
+            c => current.push(c),
+        }
+    }
+    parts
+}
+
+fn unescape(value: &str) -> String {
+    split_components(value, '\0').concat()
+}
+
+// ---------------------------------------------------------------------------
+// JSON
+// ---------------------------------------------------------------------------
+
+fn parse_json(object: &Map<String, Value>) -> (ImportedFields, Vec<String>) {
+    let mut problems = Vec::new();
+    let mut text = |keys: &[&str]| json_text(object, keys, &mut problems);
+
+    let mut fields = ImportedFields {
+        full_name: text(&["full_name", "fullName", "name"]),
+        first_name: text(&["first_name", "firstName", "given_name", "givenName"]),
+        middle_name: text(&["middle_name", "middleName"]),
+        last_name: text(&[
+            "last_name",
+            "lastName",
+            "family_name",
+            "familyName",
+            "surname",
+        ]),
+        date_of_birth: None,
+        employer: text(&["employer", "company", "organization"]),
+        job_title: text(&["job_title", "jobTitle", "title"]),
+        ..ImportedFields::default()
+    };
+    fields.nicknames.extend(text(&["nickname"]));
+
+    let birthday = text(&["date_of_birth", "dateOfBirth", "dob", "birthday"]);
+    if let Some(birthday) = birthday {
+        match parse_birthday(&birthday) {
+            Some(date) => fields.date_of_birth = Some(date),
+            None => problems.push("date_of_birth: expected YYYY-MM-DD".to_string()),
+        }
+    }
+
+    // Single values and lists are both accepted
+    for (key, entry) in json_entries(
+        object,
+        &["email", "emails", "email_addresses", "emailAddresses"],
+        &mut problems,
+    ) {
+        match json_contact(entry, &["email", "address", "value"]) {
+            Some((email, kind)) => {
+                let email_type = if kind.as_deref() == Some("work") {
+                    EmailType::Work
+                } else {
+                    EmailType::Personal
+                };
+                fields.emails.push((email, email_type));
+            }
+            None => problems.push(format!("{key}: expected an email address")),
+        }
+    }
+
+    for (key, entry) in json_entries(
+        object,
+        &["phone", "phones", "phone_numbers", "phoneNumbers"],
+        &mut problems,
+    ) {
+        match json_contact(entry, &["number", "phone", "value"]) {
+            Some((number, kind)) => {
+                let phone_type = match kind.as_deref() {
+                    Some("work") => PhoneType::Work,
+                    Some("home") => PhoneType::Home,
+                    _ => PhoneType::Mobile,
+                };
+                fields.phones.push((number, phone_type));
+            }
+            None => problems.push(format!("{key}: expected a phone number")),
+        }
+    }
+
+    for (key, entry) in json_entries(object, &["address", "addresses"], &mut problems) {
+        match entry {
+            Value::String(line) => fields.addresses.push(ImportedAddress {
+                line1: non_empty(line),
+                ..ImportedAddress::default()
+            }),
+            Value::Object(address) => {
+                let mut nested = Vec::new();
+                let address = json_address(address, &mut nested);
+                problems.extend(nested.into_iter().map(|p| format!("{key}.{p}")));
+                if !address.is_empty() {
+                    fields.addresses.push(address);
+                }
+            }
+            _ => problems.push(format!("{key}: expected a string or object")),
+        }
+    }
+
+    // Top-level address parts fill in the current address
+    let top_level = json_address(object, &mut problems);
+    if !top_level.is_empty() {
+        if fields.addresses.is_empty() {
+            fields.addresses.push(ImportedAddress::default());
+        }
+        let current = &mut fields.addresses[0];
+        current.city = current.city.take().or(top_level.city);
+        current.state = current.state.take().or(top_level.state);
+        current.zip_code = current.zip_code.take().or(top_level.zip_code);
+        current.country = current.country.take().or(top_level.country);
+    }
+
+    (fields, problems)
+}
+
+/// Read the first present key as text. Numbers are accepted (e.g. ZIP codes).
+fn json_text(
+    object: &Map<String, Value>,
+    keys: &[&str],
+    problems: &mut Vec<String>,
+) -> Option<String> {
+    let (key, value) = keys
+        .iter()
+        .find_map(|key| object.get(*key).map(|value| (*key, value)))?;
+    match value {
+        Value::String(text) => non_empty(text),
+        Value::Number(number) => Some(number.to_string()),
+        Value::Null => None,
+        _ => {
+            problems.push(format!("{key}: expected a string"));
+            None
+        }
+    }
+}
+
+/// Collect the values of every present key, flattening arrays.
+fn json_entries<'a>(
+    object: &'a Map<String, Value>,
+    keys: &[&'a str],
+    problems: &mut Vec<String>,
+) -> Vec<(String, &'a Value)> {
+    let mut entries = Vec::new();
+    for key in keys {
+        match object.get(*key) {
+            None | Some(Value::Null) => {}
+            Some(Value::Array(items)) => entries.extend(
+                items
+                    .iter()
+                    .enumerate()
+                    .map(|(index, item)| (format!("{key}[{index}]"), item)),
+            ),
+            Some(Value::Object(_) | Value::String(_)) => {
+                entries.push(((*key).to_string(), &object[*key]));
+            }
+            Some(_) => problems.push(format!("{key}: expected a string, object or array")),
+        }
+    }
+    entries
+}
+
+/// Read a contact value given as a string or as `{ <value key>, type }`.
+fn json_contact(value: &Value, value_keys: &[&str]) -> Option<(String, Option<String>)> {
+    match value {
+        Value::String(text) => non_empty(text).map(|text| (text, None)),
+        Value::Object(object) => {
+            let text = value_keys
+                .iter()
+                .find_map(|key| object.get(*key)?.as_str())
+                .and_then(non_empty)?;
+            let kind = object
+                .get("type")
+                .and_then(Value::as_str)
+                .map(str::to_lowercase);
+            Some((text, kind))
+        }
+        _ => None,
+    }
+}
+
+fn json_address(object: &Map<String, Value>, problems: &mut Vec<String>) -> ImportedAddress {
+    let mut text = |keys: &[&str]| json_text(object, keys, problems);
+    ImportedAddress {
+        line1: text(&["street", "line1", "address_line1", "addressLine1"]),
+        line2: text(&["line2", "address_line2", "addressLine2"]),
+        city: text(&["city", "locality"]),
+        state: text(&["state", "region", "province"]),
+        zip_code: text(&[
+            "zip_code",
+            "zipCode",
+            "zip",
+            "postal_code",
+            "postalCode",
+            "postcode",
+        ]),
+        country: text(&["country"]),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_key() -> [u8; 32] {
+        [0x42; 32]
+    }
+
+    fn decrypt(field: Option<&crate::EncryptedField<String>>) -> Option<String> {
+        field.map(|f| f.decrypt(&test_key()).expect("decrypt"))
+    }
+
+    const VCARD: &str = "BEGIN:VCARD\r\n\
+        VERSION:3.0\r\n\
+        PRODID:-//Example//Contacts//EN\r\n\
+        N:Doe;Jane;Q.;Dr.;\r\n\
+        FN:Jane Q. Doe\r\n\
+        NICKNAME:JD\r\n\
+        ORG:Example Corp;Research\r\n\
+        TITLE:Analyst\r\n\
+        item1.EMAIL;TYPE=INTERNET,HOME:jane.doe@example.com\r\n\
+        EMAIL;TYPE=WORK:jdoe@example.org\r\n\
+        TEL;TYPE=CELL:(555) 123-4567\r\n\
+        TEL;TYPE=WORK,VOICE:+1-555-987-6543\r\n\
+        ADR;TYPE=HOME:;Apt 4;123 Main St;Springfield;IL;62701;USA\r\n\
+        ADR;TYPE=WORK:;;1 Long\r\n  Road;Chicago;IL;60601;\r\n\
+        BDAY:1985-04-12\r\n\
+        PHOTO;ENCODING=b;TYPE=JPEG:MIICajCCAdOgAwIBAgICBEUwDQYJKoZIhvcNAQEEBQAw\r\n\
+        X-SOCIALPROFILE;TYPE=twitter:https://example.com/jane\r\n\
+        END:VCARD\r\n";
+
+    #[test]
+    #[allow(deprecated)]
+    fn test_import_vcard() {
+        let key = test_key();
+        let profile = UserProfile::from_vcard(VCARD, &key).expect("import vcard");
+
+        assert_eq!(
+            decrypt(profile.full_name.as_ref()).as_deref(),
+            Some("Jane Q. Doe")
+        );
+        assert_eq!(
+            decrypt(profile.first_name.as_ref()).as_deref(),
+            Some("Jane")
+        );
+        assert_eq!(decrypt(profile.middle_name.as_ref()).as_deref(), Some("Q."));
+        assert_eq!(decrypt(profile.last_name.as_ref()).as_deref(), Some("Doe"));
+        assert_eq!(
+            decrypt(profile.date_of_birth.as_ref()).as_deref(),
+            Some("1985-04-12")
+        );
+        assert_eq!(
+            decrypt(profile.employer.as_ref()).as_deref(),
+            Some("Example Corp")
+        );
+        assert_eq!(
+            decrypt(profile.job_title.as_ref()).as_deref(),
+            Some("Analyst")
+        );
+        assert_eq!(
+            decrypt(profile.aliases[0].nickname.as_ref()).as_deref(),
+            Some("JD")
+        );
+
+        let emails: Vec<_> = profile
+            .email_addresses
+            .iter()
+            .map(|e| (e.email.decrypt(&key).expect("decrypt"), e.email_type))
+            .collect();
+        assert_eq!(
+            emails,
+            [
+                ("jane.doe@example.com".to_string(), EmailType::Personal),
+                ("jdoe@example.org".to_string(), EmailType::Work),
+            ]
+        );
+
+        let phones: Vec<_> = profile
+            .phone_numbers
+            .iter()
+            .map(|p| {
+                let normalized = p.number_normalized.as_ref().expect("normalized");
+                (normalized.decrypt(&key).expect("decrypt"), p.phone_type)
+            })
+            .collect();
+        assert_eq!(
+            phones,
+            [
+                ("5551234567".to_string(), PhoneType::Mobile),
+                ("5559876543".to_string(), PhoneType::Work),
+            ]
+        );
+
+        assert_eq!(
+            decrypt(profile.address.as_ref()).as_deref(),
+            Some("123 Main St\nApt 4")
+        );
+        assert_eq!(
+            decrypt(profile.city.as_ref()).as_deref(),
+            Some("Springfield")
+        );
+        assert_eq!(decrypt(profile.state.as_ref()).as_deref(), Some("IL"));
+        assert_eq!(decrypt(profile.zip_code.as_ref()).as_deref(), Some("62701"));
+        assert_eq!(decrypt(profile.country.as_ref()).as_deref(), Some("US"));
+
+        assert_eq!(profile.previous_addresses_v2.len(), 1);
+        let previous = &profile.previous_addresses_v2[0];
+        assert_eq!(
+            previous.address_line1.decrypt(&key).expect("decrypt"),
+            "1 Long Road"
+        );
+        assert_eq!(previous.city.decrypt(&key).expect("decrypt"), "Chicago");
+    }
+
+    #[test]
+    fn test_import_vcard_splits_full_name_without_n() {
+        let card = "BEGIN:VCARD\nVERSION:4.0\nFN:John Smith\nEND:VCARD\n";
+        let profile = UserProfile::from_vcard(card, &test_key()).expect("import vcard");
+
+        assert_eq!(
+            decrypt(profile.first_name.as_ref()).as_deref(),
+            Some("John")
+        );
+        assert_eq!(
+            decrypt(profile.last_name.as_ref()).as_deref(),
+            Some("Smith")
+        );
+    }
+
+    #[test]
+    fn test_import_vcard_reports_every_problem() {
+        let card = "BEGIN:VCARD\n\
+            FN:Jane Doe\n\
+            BDAY:--0412\n\
+            TEL:12345\n\
+            garbage line\n\
+            EMAIL:not-an-email\n\
+            END:VCARD\n";
+        let Err(VaultError::ImportValidation(problems)) =
+            UserProfile::from_vcard(card, &test_key())
+        else {
+            panic!("expected validation error");
+        };
+
+        assert_eq!(
+            problems,
+            [
+                "line 3 (BDAY): expected a date with a year",
+                "line 5: expected NAME:value",
+                "email 1: invalid email address",
+                "phone 1: expected a 10-digit US phone number",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_import_vcard_rejects_non_vcard() {
+        for input in ["", "hello world", "BEGIN:VCARD\nFN:Jane Doe\n"] {
+            assert!(matches!(
+                UserProfile::from_vcard(input, &test_key()),
+                Err(VaultError::ImportValidation(_))
+            ));
+        }
+    }
+
+    #[test]
+    fn test_import_json() {
+        let key = test_key();
+        let json = r#"{
+            "firstName": "Jane",
+            "last_name": "Doe",
+            "emails": ["jane@example.com", {"email": "jane@work.example", "type": "Work"}],
+            "phone": "555-123-4567",
+            "address": {"street": "10 Downing St", "city": "London", "postcode": "SW1A 2AA"},
+            "country": "United Kingdom",
+            "dob": "1990-01-31",
+            "favourite_colour": "green"
+        }"#;
+        let profile = UserProfile::from_json(json, &key).expect("import json");
+
+        assert_eq!(
+            decrypt(profile.full_name.as_ref()).as_deref(),
+            Some("Jane Doe")
+        );
+        assert_eq!(profile.email_addresses.len(), 2);
+        assert_eq!(profile.email_addresses[1].email_type, EmailType::Work);
+        assert_eq!(profile.phone_numbers.len(), 1);
+        assert_eq!(decrypt(profile.city.as_ref()).as_deref(), Some("London"));
+        assert_eq!(
+            decrypt(profile.zip_code.as_ref()).as_deref(),
+            Some("SW1A 2AA")
+        );
+        assert_eq!(decrypt(profile.country.as_ref()).as_deref(), Some("GB"));
+        assert_eq!(
+            decrypt(profile.date_of_birth.as_ref()).as_deref(),
+            Some("1990-01-31")
+        );
+    }
+
+    #[test]
+    fn test_import_json_malformed() {
+        let key = test_key();
+        let Err(VaultError::ImportValidation(problems)) =
+            UserProfile::from_json("{\"first_name\": ", &key)
+        else {
+            panic!("expected validation error");
+        };
+        assert!(problems[0].starts_with("invalid JSON"));
+
+        assert!(matches!(
+            UserProfile::from_json("[1, 2]", &key),
+            Err(VaultError::ImportValidation(_))
+        ));
+
+        let Err(VaultError::ImportValidation(problems)) = UserProfile::from_json(
+            r#"{"first_name": ["Jane"], "emails": 42, "dob": "31/01/1990"}"#,
+            &key,
+        ) else {
+            panic!("expected validation error");
+        };
+        assert_eq!(
+            problems,
+            [
+                "first_name: expected a string",
+                "date_of_birth: expected YYYY-MM-DD",
+                "emails: expected a string, object or array",
+            ]
+        );
+    }
+}
@@ -12,10 +12,18 @@
 //! - Output: 32 bytes (256 bits)
 //!
 //! These parameters balance security and usability for desktop applications.
+//! Parameters weaker than [`MINIMUM_PARAMS`] are always refused. Each vault
+//! stores the parameters it was created with, so raising the defaults only
+//! affects new vaults until an old one is upgraded.
+//!
+//! [`estimate_strength`] gives advisory feedback on a master password. It
+//! never blocks a password: Argon2id slows guessing, but cannot save a
+//! password that is near the top of every guessing list.
 
 use crate::error::{Result, VaultError};
 use argon2::{Algorithm, Argon2, ParamsBuilder, Version};
-use rand::{rngs::OsRng, RngCore};
+use serde::{Deserialize, Serialize};
+use spectral_core::RngSource;
 use zeroize::Zeroizing;
 
 /// Length of the derived key in bytes (256 bits).
@@ -33,13 +41,135 @@ const TIME_COST: u32 = 2;
 /// Argon2id parallelism (threads).
 const PARALLELISM: u32 = 1;
 
+/// Argon2id cost parameters.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct KdfParams {
+    /// Memory cost in KB
+    pub memory_cost_kb: u32,
+    /// Time cost (iterations)
+    pub time_cost: u32,
+    /// Parallelism (threads)
+    pub parallelism: u32,
+}
+
+/// Length of [`KdfParams::to_bytes`] output.
+pub const PARAMS_LENGTH: usize = 12;
+
+/// Parameters of vaults whose salt file predates stored parameters.
+///
+/// These were the defaults when such vaults were created and must not change
+/// when [`KdfParams::default`] is raised.
+pub const LEGACY_PARAMS: KdfParams = KdfParams {
+    memory_cost_kb: 262_144,
+    time_cost: 2,
+    parallelism: 1,
+};
+
+impl KdfParams {
+    /// Encode as memory cost, time cost and parallelism, each a little-endian `u32`.
+    #[must_use]
+    pub fn to_bytes(&self) -> [u8; PARAMS_LENGTH] {
+        let mut bytes = [0u8; PARAMS_LENGTH];
+        bytes[0..4].copy_from_slice(&self.memory_cost_kb.to_le_bytes());
+        bytes[4..8].copy_from_slice(&self.time_cost.to_le_bytes());
+        bytes[8..12].copy_from_slice(&self.parallelism.to_le_bytes());
+        bytes
+    }
+
+    /// Decode parameters written by [`KdfParams::to_bytes`].
+    ///
+    /// # Errors
+    /// Returns `VaultError::InvalidData` if `bytes` has the wrong length.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        let bytes: [u8; PARAMS_LENGTH] = bytes.try_into().map_err(|_| {
+            VaultError::InvalidData(format!(
+                "invalid KDF parameters: expected {PARAMS_LENGTH} bytes, got {}",
+                bytes.len()
+            ))
+        })?;
+        let word =
+            |i: usize| u32::from_le_bytes([bytes[i], bytes[i + 1], bytes[i + 2], bytes[i + 3]]);
+        Ok(Self {
+            memory_cost_kb: word(0),
+            time_cost: word(4),
+            parallelism: word(8),
+        })
+    }
+
+    /// Whether any cost is lower than in `other`.
+    #[must_use]
+    pub fn is_weaker_than(&self, other: &Self) -> bool {
+        self.memory_cost_kb < other.memory_cost_kb
+            || self.time_cost < other.time_cost
+            || self.parallelism < other.parallelism
+    }
+}
+
+impl Default for KdfParams {
+    fn default() -> Self {
+        Self {
+            memory_cost_kb: MEMORY_COST_KB,
+            time_cost: TIME_COST,
+            parallelism: PARALLELISM,
+        }
+    }
+}
+
+/// Weakest parameters accepted for key derivation (64 MB, 2 iterations).
+///
+/// Anything below this floor makes offline brute-forcing cheap, so a
+/// tampered or misconfigured parameter set is refused rather than used.
+pub const MINIMUM_PARAMS: KdfParams = KdfParams {
+    memory_cost_kb: 65_536,
+    time_cost: 2,
+    parallelism: 1,
+};
+
+/// Check that `params` are at least as strong as [`MINIMUM_PARAMS`].
+///
+/// # Errors
+/// Returns `VaultError::WeakKdfParams` naming the first parameter below the floor.
+pub fn verify_params_meet_minimum(params: &KdfParams) -> Result<()> {
+    let checks = [
+        (
+            "memory cost",
+            params.memory_cost_kb,
+            MINIMUM_PARAMS.memory_cost_kb,
+        ),
+        ("time cost", params.time_cost, MINIMUM_PARAMS.time_cost),
+        (
+            "parallelism",
+            params.parallelism,
+            MINIMUM_PARAMS.parallelism,
+        ),
+    ];
+
+    for (name, value, minimum) in checks {
+        if value < minimum {
+            return Err(VaultError::WeakKdfParams(format!(
+                "{name} {value} is below the minimum of {minimum}"
+            )));
+        }
+    }
+
+    Ok(())
+}
+
 /// Generate a random salt for key derivation.
 ///
 /// Returns a cryptographically secure random 32-byte salt.
 #[must_use]
 pub fn generate_salt() -> [u8; SALT_LENGTH] {
+    generate_salt_with(&RngSource::os())
+}
+
+/// Generate a salt from the given random source.
+///
+/// Tests pass a seeded [`RngSource`] to get reproducible salts.
+#[must_use]
+pub fn generate_salt_with(rng: &RngSource) -> [u8; SALT_LENGTH] {
     let mut salt = [0u8; SALT_LENGTH];
-    OsRng.fill_bytes(&mut salt);
+    rng.fill_bytes(&mut salt);
     salt
 }
 
@@ -65,6 +195,21 @@ pub fn generate_salt() -> [u8; SALT_LENGTH] {
 /// // Key is automatically zeroized when dropped
 /// ```
 pub fn derive_key(password: &str, salt: &[u8]) -> Result<Zeroizing<[u8; KEY_LENGTH]>> {
+    derive_key_with_params(password, salt, &KdfParams::default())
+}
+
+/// Derive a 256-bit encryption key using explicit Argon2id parameters.
+///
+/// # Errors
+/// Returns `VaultError::WeakKdfParams` if `params` are below [`MINIMUM_PARAMS`],
+/// or `VaultError::KeyDerivation` if the derivation fails.
+pub fn derive_key_with_params(
+    password: &str,
+    salt: &[u8],
+    params: &KdfParams,
+) -> Result<Zeroizing<[u8; KEY_LENGTH]>> {
+    verify_params_meet_minimum(params)?;
+
     // Validate salt length
     if salt.len() != SALT_LENGTH {
         return Err(VaultError::KeyDerivation(format!(
@@ -75,9 +220,9 @@ pub fn derive_key(password: &str, salt: &[u8]) -> Result<Zeroizing<[u8; KEY_LENG
 
     // Build Argon2id parameters
     let params = ParamsBuilder::new()
-        .m_cost(MEMORY_COST_KB)
-        .t_cost(TIME_COST)
-        .p_cost(PARALLELISM)
+        .m_cost(params.memory_cost_kb)
+        .t_cost(params.time_cost)
+        .p_cost(params.parallelism)
         .output_len(KEY_LENGTH)
         .build()
         .map_err(|e| VaultError::KeyDerivation(format!("failed to build parameters: {e}")))?;
@@ -94,6 +239,220 @@ pub fn derive_key(password: &str, salt: &[u8]) -> Result<Zeroizing<[u8; KEY_LENG
     Ok(key)
 }
 
+/// Lowest [`PasswordStrength::score`] not reported as weak.
+pub const MIN_RECOMMENDED_SCORE: u8 = 3;
+
+/// Passwords shorter than this are reported as too short.
+const MIN_RECOMMENDED_LENGTH: usize = 12;
+
+/// Passwords of one kind of character shorter than this lack variety.
+const MIN_SINGLE_CLASS_LENGTH: usize = 16;
+
+/// Estimated bits of entropy needed for scores 1 to 4.
+const SCORE_THRESHOLDS_BITS: [f64; 4] = [28.0, 36.0, 60.0, 80.0];
+
+/// Passwords at the top of leaked-password lists, lowercased.
+///
+/// Matched after stripping trailing digits and symbols and undoing common
+/// letter substitutions, so `P@ssw0rd123!` matches `password`.
+const COMMON_PASSWORDS: &[&str] = &[
+    "password",
+    "passw",
+    "pass",
+    "qwerty",
+    "qwertyuiop",
+    "asdf",
+    "asdfgh",
+    "asdfghjkl",
+    "zxcvbn",
+    "letmein",
+    "welcome",
+    "admin",
+    "administrator",
+    "login",
+    "iloveyou",
+    "monkey",
+    "dragon",
+    "master",
+    "sunshine",
+    "princess",
+    "football",
+    "baseball",
+    "soccer",
+    "hockey",
+    "shadow",
+    "superman",
+    "batman",
+    "trustno",
+    "abc",
+    "abcdef",
+    "secret",
+    "changeme",
+    "default",
+    "hello",
+    "freedom",
+    "whatever",
+    "starwars",
+    "pokemon",
+    "michael",
+    "jennifer",
+    "jordan",
+    "charlie",
+    "ashley",
+    "nicole",
+    "daniel",
+    "computer",
+    "internet",
+    "access",
+    "mustang",
+    "killer",
+    "cheese",
+    "summer",
+    "winter",
+    "spring",
+    "autumn",
+    "flower",
+    "lovely",
+    "qazwsx",
+    "zaq",
+    "passpass",
+    "vault",
+    "spectral",
+    "privacy",
+];
+
+/// Advisory estimate of how hard a password is to guess.
+///
+/// Holds no part of the password, so it is safe to log or return to the UI.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PasswordStrength {
+    /// 0 (guessed almost at once) to 4 (very hard to guess)
+    pub score: u8,
+    /// Reasons the score is low, for showing to the user
+    pub warnings: Vec<String>,
+}
+
+impl PasswordStrength {
+    /// Whether the score is below [`MIN_RECOMMENDED_SCORE`].
+    #[must_use]
+    pub fn is_weak(&self) -> bool {
+        self.score < MIN_RECOMMENDED_SCORE
+    }
+}
+
+/// Estimate the strength of `password` from its length, character variety,
+/// repeats and sequences, and whether it is a common password.
+///
+/// This is a heuristic, not a guarantee. A long passphrase of several
+/// unrelated words scores high.
+#[must_use]
+pub fn estimate_strength(password: &str) -> PasswordStrength {
+    let chars: Vec<char> = password.chars().collect();
+    let mut warnings = Vec::new();
+
+    if is_common_password(password) {
+        return PasswordStrength {
+            score: 0,
+            warnings: vec!["This is one of the most commonly used passwords".to_string()],
+        };
+    }
+
+    let has_lower = chars.iter().any(char::is_ascii_lowercase);
+    let has_upper = chars.iter().any(char::is_ascii_uppercase);
+    let has_digit = chars.iter().any(char::is_ascii_digit);
+    let has_symbol = chars
+        .iter()
+        .any(|c| c.is_ascii() && !c.is_ascii_alphanumeric());
+    let has_other = chars.iter().any(|c| !c.is_ascii());
+    let pool: u32 = [
+        (has_lower, 26),
+        (has_upper, 26),
+        (has_digit, 10),
+        (has_symbol, 33),
+        (has_other, 100),
+    ]
+    .iter()
+    .filter(|(present, _)| *present)
+    .map(|(_, size)| size)
+    .sum();
+    let bits_per_char = f64::from(pool.max(1)).log2();
+
+    // A character repeating or continuing a run like "abc" or "321" adds
+    // almost nothing for a guesser
+    let mut predictable = 0usize;
+    let mut bits = 0.0;
+    for (i, &c) in chars.iter().enumerate() {
+        let follows_pattern = i > 0 && {
+            let step = i64::from(u32::from(c)) - i64::from(u32::from(chars[i - 1]));
+            step.abs() <= 1
+        };
+        if follows_pattern {
+            predictable += 1;
+            bits += 1.0;
+        } else {
+            bits += bits_per_char;
+        }
+    }
+
+    if chars.len() < MIN_RECOMMENDED_LENGTH {
+        warnings.push(format!(
+            "Use at least {MIN_RECOMMENDED_LENGTH} characters; a passphrase of several words is easiest to remember"
+        ));
+    }
+    let classes = [has_lower, has_upper, has_digit, has_symbol || has_other]
+        .iter()
+        .filter(|present| **present)
+        .count();
+    if classes < 2 && chars.len() < MIN_SINGLE_CLASS_LENGTH {
+        warnings.push("Mix in other kinds of characters, such as digits or symbols".to_string());
+    }
+    if predictable * 3 >= chars.len() && !chars.is_empty() {
+        warnings
+            .push("Avoid repeated characters and sequences like \"abc\" or \"123\"".to_string());
+    }
+
+    let score = SCORE_THRESHOLDS_BITS
+        .iter()
+        .take_while(|threshold| bits >= **threshold)
+        .count();
+    // A password with a warning is always reported as weak
+    let score = if warnings.is_empty() {
+        score
+    } else {
+        score.min(usize::from(MIN_RECOMMENDED_SCORE - 1))
+    };
+
+    PasswordStrength {
+        score: u8::try_from(score).unwrap_or(4),
+        warnings,
+    }
+}
+
+/// Whether `password` is a [`COMMON_PASSWORDS`] entry once decorations and
+/// letter substitutions are removed.
+fn is_common_password(password: &str) -> bool {
+    let normalized: String = password
+        .trim_end_matches(|c: char| c.is_ascii_digit() || c.is_ascii_punctuation())
+        .chars()
+        .map(|c| match c.to_ascii_lowercase() {
+            '@' | '4' => 'a',
+            '3' => 'e',
+            '1' | '!' => 'i',
+            '0' => 'o',
+            '$' | '5' => 's',
+            '7' => 't',
+            other => other,
+        })
+        .collect();
+
+    // Nothing but digits and symbols ("123456"), or one character repeated
+    if normalized.is_empty() {
+        return !password.is_empty();
+    }
+    normalized.chars().all(|c| normalized.starts_with(c))
+        || COMMON_PASSWORDS.contains(&normalized.as_str())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -109,6 +468,15 @@ mod tests {
         assert_eq!(salt2.len(), SALT_LENGTH);
     }
 
+    #[test]
+    fn test_seeded_salts_are_reproducible() {
+        let salt1 = generate_salt_with(&RngSource::seeded(7));
+        let salt2 = generate_salt_with(&RngSource::seeded(7));
+
+        assert_eq!(salt1, salt2);
+        assert_ne!(salt1, generate_salt_with(&RngSource::seeded(8)));
+    }
+
     #[test]
     fn test_derive_key_deterministic() {
         let salt = generate_salt();
@@ -175,4 +543,126 @@ mod tests {
         // Should still work, just not recommended
         assert_eq!(key.len(), KEY_LENGTH);
     }
+
+    #[test]
+    fn test_params_round_trip_and_compare() {
+        let params = KdfParams::default();
+        assert_eq!(
+            KdfParams::from_bytes(&params.to_bytes()).expect("decode params"),
+            params
+        );
+        assert!(KdfParams::from_bytes(&[0u8; 4]).is_err());
+
+        assert!(MINIMUM_PARAMS.is_weaker_than(&params));
+        assert!(!params.is_weaker_than(&MINIMUM_PARAMS));
+        assert!(!params.is_weaker_than(&params));
+    }
+
+    #[test]
+    fn test_default_params_meet_minimum() {
+        verify_params_meet_minimum(&KdfParams::default()).expect("defaults are strong");
+        verify_params_meet_minimum(&MINIMUM_PARAMS).expect("floor itself is accepted");
+    }
+
+    #[test]
+    fn test_params_above_minimum_pass() {
+        let params = KdfParams {
+            memory_cost_kb: MINIMUM_PARAMS.memory_cost_kb * 2,
+            time_cost: MINIMUM_PARAMS.time_cost + 1,
+            parallelism: 4,
+        };
+        assert!(verify_params_meet_minimum(&params).is_ok());
+    }
+
+    #[test]
+    fn test_params_below_minimum_rejected() {
+        let weak = [
+            KdfParams {
+                memory_cost_kb: MINIMUM_PARAMS.memory_cost_kb - 1,
+                ..MINIMUM_PARAMS
+            },
+            KdfParams {
+                time_cost: MINIMUM_PARAMS.time_cost - 1,
+                ..MINIMUM_PARAMS
+            },
+            KdfParams {
+                parallelism: 0,
+                ..MINIMUM_PARAMS
+            },
+        ];
+
+        for params in weak {
+            assert!(matches!(
+                verify_params_meet_minimum(&params),
+                Err(VaultError::WeakKdfParams(_))
+            ));
+        }
+    }
+
+    #[test]
+    fn test_derive_key_refuses_weak_params() {
+        let salt = generate_salt();
+        let weak = KdfParams {
+            memory_cost_kb: 8,
+            time_cost: 1,
+            parallelism: 1,
+        };
+
+        let result = derive_key_with_params("password", &salt, &weak);
+        match result {
+            Err(VaultError::WeakKdfParams(msg)) => assert!(msg.contains("memory cost")),
+            _ => panic!("expected WeakKdfParams error"),
+        }
+    }
+
+    #[test]
+    fn test_common_passwords_score_low_with_warning() {
+        for password in [
+            "password",
+            "Password1",
+            "P@ssw0rd!",
+            "123456",
+            "qwerty123",
+            "letmein",
+        ] {
+            let strength = estimate_strength(password);
+            assert_eq!(strength.score, 0, "{password} should score 0");
+            assert!(strength.is_weak());
+            assert!(
+                strength.warnings[0].contains("commonly used"),
+                "{password}: {:?}",
+                strength.warnings
+            );
+        }
+    }
+
+    #[test]
+    fn test_short_and_patterned_passwords_are_weak() {
+        let short = estimate_strength("Xk9#q");
+        assert!(short.is_weak());
+        assert!(short.warnings.iter().any(|w| w.contains("at least 12")));
+
+        let patterned = estimate_strength("abcdefghijklmnop");
+        assert!(patterned.is_weak());
+        assert!(patterned.warnings.iter().any(|w| w.contains("sequences")));
+    }
+
+    #[test]
+    fn test_strong_passphrase_scores_high() {
+        let strength = estimate_strength("correct horse battery staple");
+        assert_eq!(strength.score, 4);
+        assert!(strength.warnings.is_empty());
+        assert!(!strength.is_weak());
+
+        let mixed = estimate_strength("vN7#pQ2!xR9$mK4&");
+        assert_eq!(mixed.score, 4);
+    }
+
+    #[test]
+    fn test_strength_never_contains_password() {
+        let password = "hunter2hunter2";
+        let strength = estimate_strength(password);
+        let rendered = format!("{strength:?}");
+        assert!(!rendered.contains("hunter"));
+    }
 }
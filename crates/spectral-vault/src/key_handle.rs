@@ -0,0 +1,80 @@
+//! Scoped access to the vault's encryption key.
+//!
+//! Copying the key out of the vault with `to_vec()` or `*key` leaves a copy
+//! in memory that is never cleared. A [`KeyHandle`] instead lends the key for
+//! the length of a closure, and the one way to take it away,
+//! [`KeyHandle::to_owned_key`], returns a copy that is zeroized on drop.
+
+use std::fmt;
+use zeroize::Zeroizing;
+
+/// A loan of an unlocked vault's encryption key.
+///
+/// The handle borrows the vault, so it cannot outlive it or be held across a
+/// lock.
+pub struct KeyHandle<'a> {
+    key: &'a [u8; 32],
+}
+
+impl<'a> KeyHandle<'a> {
+    pub(crate) fn new(key: &'a [u8; 32]) -> Self {
+        Self { key }
+    }
+
+    /// Run `f` with the key.
+    ///
+    /// The key should not be copied out of the closure; use
+    /// [`to_owned_key`](Self::to_owned_key) when it has to outlive the handle.
+    pub fn with_key<R>(&self, f: impl FnOnce(&[u8; 32]) -> R) -> R {
+        f(self.key)
+    }
+
+    /// Copy the key for a task that outlives the handle, such as a
+    /// background scan. The copy is zeroized when it is dropped.
+    #[must_use]
+    pub fn to_owned_key(&self) -> Zeroizing<[u8; 32]> {
+        Zeroizing::new(*self.key)
+    }
+}
+
+impl fmt::Debug for KeyHandle<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("KeyHandle([REDACTED])")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use zeroize::Zeroize;
+
+    #[test]
+    fn test_key_is_lent_to_closure() {
+        let key = [7u8; 32];
+        let handle = KeyHandle::new(&key);
+        let first = handle.with_key(|k| k[0]);
+        assert_eq!(first, 7);
+        assert!(handle.with_key(|k| k == &key));
+    }
+
+    #[test]
+    fn test_owned_key_is_zeroizing() {
+        let key = [7u8; 32];
+        let handle = KeyHandle::new(&key);
+
+        let mut owned: Zeroizing<[u8; 32]> = handle.to_owned_key();
+        assert_eq!(*owned, key);
+
+        // What drop does to the copy; the vault's key is untouched
+        owned.zeroize();
+        assert_eq!(*owned, [0u8; 32]);
+        assert!(handle.with_key(|k| k == &key));
+    }
+
+    #[test]
+    fn test_debug_hides_key() {
+        let key = [7u8; 32];
+        let debug = format!("{:?}", KeyHandle::new(&key));
+        assert_eq!(debug, "KeyHandle([REDACTED])");
+    }
+}
@@ -35,23 +35,44 @@
 #![allow(clippy::missing_errors_doc)]
 #![allow(clippy::missing_panics_doc)]
 
+pub mod attachment;
 pub mod cipher;
 pub mod error;
+mod import;
 pub mod kdf;
+mod key_handle;
 pub mod profile;
+mod rekey;
+mod secret;
 
-pub use cipher::{encrypt_string, EncryptedField};
+pub use attachment::{AttachmentId, AttachmentInfo};
+pub use cipher::{decrypt_blob, encrypt_blob, encrypt_string, EncryptedBlob, EncryptedField};
 pub use error::{Result, VaultError};
-pub use profile::{CompletenessTier, ProfileCompleteness, UserProfile};
+pub use kdf::{KdfParams, PasswordStrength};
+pub use key_handle::KeyHandle;
+pub use profile::{CompletenessTier, ProfileCompleteness, ProfileStorage, UserProfile};
+pub use rekey::RekeyOptions;
 
+use chrono::{DateTime, Utc};
+use futures::Stream;
 use spectral_core::types::{ProfileId, Timestamp};
+use spectral_core::{SharedClock, SystemClock};
 use spectral_db::Database;
+use std::collections::VecDeque;
 use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use subtle::ConstantTimeEq;
 use zeroize::Zeroizing;
 
 /// Salt storage file name (stored alongside the vault database).
 const SALT_FILE_NAME: &str = ".vault_salt";
 
+/// Salt for a new password, staged while a password change is in progress.
+const PENDING_SALT_FILE_NAME: &str = ".vault_salt.pending";
+
+/// Number of profile IDs fetched per query by [`Vault::profiles_stream`].
+const PROFILE_PAGE_SIZE: u32 = 100;
+
 /// Verification token stored in database to verify password correctness.
 const VERIFICATION_TOKEN: &str = "SPECTRAL_VAULT_V1";
 
@@ -62,12 +83,33 @@ const VERIFICATION_TOKEN: &str = "SPECTRAL_VAULT_V1";
 /// - **Unlocked**: Encryption key derived and held in memory, database open
 #[derive(Debug)]
 pub struct Vault {
-    /// Database connection (None when locked)
-    db: Option<Database>,
+    /// Database connection (None when locked), shared with background tasks
+    db: Option<Arc<Database>>,
     /// Derived encryption key (zeroized on drop)
     key: Option<Zeroizing<[u8; 32]>>,
     /// Path to the vault database
     db_path: PathBuf,
+    /// Salt held in memory for vaults that have no salt file
+    memory_salt: Option<[u8; kdf::SALT_LENGTH]>,
+    /// Argon2id parameters the current key was derived with
+    kdf_params: kdf::KdfParams,
+    /// Batching for password changes and KDF upgrades
+    rekey_options: RekeyOptions,
+    /// Time source for auto-lock
+    clock: SharedClock,
+    /// When the database or key was last accessed
+    last_activity: Mutex<DateTime<Utc>>,
+    /// Strength of the password the vault was created with this session
+    password_strength: Option<PasswordStrength>,
+}
+
+/// Options for [`Vault::unlock_with_options`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct UnlockOptions {
+    /// After unlocking, move a vault whose key derivation parameters are
+    /// weaker than the current defaults onto the defaults. See
+    /// [`Vault::upgrade_kdf_params`].
+    pub upgrade_kdf: bool,
 }
 
 impl Vault {
@@ -79,6 +121,9 @@ impl Vault {
     /// 3. Creates encrypted database
     /// 4. Stores salt in a separate file
     ///
+    /// Weak passwords are accepted, with a warning logged. The estimate is
+    /// available from [`Vault::password_strength`] so the caller can show it.
+    ///
     /// # Arguments
     /// * `password` - Master password for the vault
     /// * `db_path` - Path where the vault database should be created
@@ -93,6 +138,23 @@ impl Vault {
     /// - Database creation fails
     /// - File system operations fail
     pub async fn create(password: &str, db_path: impl AsRef<Path>) -> Result<Self> {
+        Self::create_with_params(password, db_path, kdf::KdfParams::default()).await
+    }
+
+    /// Create a new vault whose key is derived with the given Argon2id
+    /// parameters.
+    ///
+    /// The parameters are stored in the salt file and used on every unlock.
+    /// See [`Vault::create`].
+    ///
+    /// # Errors
+    /// Returns `VaultError::WeakKdfParams` if `params` are below
+    /// [`kdf::MINIMUM_PARAMS`], or any error from [`Vault::create`].
+    pub async fn create_with_params(
+        password: &str,
+        db_path: impl AsRef<Path>,
+        params: kdf::KdfParams,
+    ) -> Result<Self> {
         let db_path = db_path.as_ref();
         let salt_path = get_salt_path(db_path);
 
@@ -106,11 +168,19 @@ impl Vault {
 
         tracing::info!("Creating new vault at {}", db_path.display());
 
+        let strength = kdf::estimate_strength(password);
+        if strength.is_weak() {
+            tracing::warn!(
+                "Vault password is weak (score {}/4); creating the vault anyway",
+                strength.score
+            );
+        }
+
         // Generate salt
         let salt = kdf::generate_salt();
 
         // Derive key
-        let key = kdf::derive_key(password, &salt)?;
+        let key = kdf::derive_key_with_params(password, &salt, &params)?;
 
         // Create database
         let db = Database::new(db_path, key.to_vec()).await?;
@@ -120,16 +190,22 @@ impl Vault {
         Self::store_verification_token(&db, &key).await?;
 
         // Store salt
-        tokio::fs::write(&salt_path, &salt)
+        tokio::fs::write(&salt_path, salt_file_contents(&salt, &params))
             .await
             .map_err(|e| VaultError::InvalidData(format!("failed to write salt file: {e}")))?;
 
         tracing::info!("Vault created successfully");
 
         Ok(Self {
-            db: Some(db),
+            db: Some(Arc::new(db)),
             key: Some(key),
             db_path: db_path.to_path_buf(),
+            memory_salt: None,
+            kdf_params: params,
+            rekey_options: RekeyOptions::default(),
+            clock: SystemClock::shared(),
+            last_activity: Mutex::new(Utc::now()),
+            password_strength: Some(strength),
         })
     }
 
@@ -155,6 +231,21 @@ impl Vault {
     /// - Key derivation fails
     /// - Database cannot be opened
     pub async fn unlock(password: &str, db_path: impl AsRef<Path>) -> Result<Self> {
+        Self::unlock_with_options(password, db_path, UnlockOptions::default()).await
+    }
+
+    /// Unlock an existing vault, then apply `options`.
+    ///
+    /// See [`Vault::unlock`]. A failed KDF upgrade is logged and leaves the
+    /// vault unlocked on its old parameters.
+    ///
+    /// # Errors
+    /// Returns the same errors as [`Vault::unlock`].
+    pub async fn unlock_with_options(
+        password: &str,
+        db_path: impl AsRef<Path>,
+        options: UnlockOptions,
+    ) -> Result<Self> {
         let db_path = db_path.as_ref();
         let salt_path = get_salt_path(db_path);
 
@@ -170,40 +261,143 @@ impl Vault {
         tracing::info!("Unlocking vault at {}", db_path.display());
 
         // Load salt
-        let salt = tokio::fs::read(&salt_path)
-            .await
-            .map_err(|e| VaultError::InvalidData(format!("failed to read salt file: {e}")))?;
-
-        if salt.len() != kdf::SALT_LENGTH {
-            return Err(VaultError::InvalidData(format!(
-                "invalid salt file: expected {} bytes, got {}",
-                kdf::SALT_LENGTH,
-                salt.len()
-            )));
-        }
+        let (salt, mut params) = read_salt(&salt_path).await?;
 
         // Derive key
-        let key = kdf::derive_key(password, &salt)?;
-
-        // Open database
-        let db = Database::new(db_path, key.to_vec()).await?;
+        let mut key = kdf::derive_key_with_params(password, &salt, &params)?;
+
+        // Open the database. A file that refuses the key has been
+        // re-encrypted by a password change that stopped before its staged
+        // salt was promoted.
+        let pending_salt_path = get_pending_salt_path(db_path);
+        let mut db = match open_database(db_path, &key).await {
+            Ok(db) => db,
+            Err(e) if pending_salt_path.exists() => {
+                let (pending_salt, pending_params) = read_salt(&pending_salt_path).await?;
+                let pending_key =
+                    kdf::derive_key_with_params(password, &pending_salt, &pending_params)?;
+                let db = open_database(db_path, &pending_key).await.map_err(|_| e)?;
+                Self::verify_password(&db, &pending_key)
+                    .await
+                    .map_err(|_| VaultError::InvalidPassword)?;
+
+                tracing::warn!("Completing interrupted password change");
+                promote_pending_salt(db_path).await?;
+                key = pending_key;
+                params = pending_params;
+                db
+            }
+            Err(e) => return Err(e),
+        };
 
         // Run migrations to ensure schema is up to date
         // This is critical for existing vaults that were created before new migrations were added
         db.run_migrations().await?;
 
-        // Verify password is correct by decrypting verification token
-        Self::verify_password(&db, &key).await.map_err(|_| {
-            tracing::warn!("Failed to verify vault key - incorrect password");
-            VaultError::InvalidPassword
-        })?;
+        // Verify password is correct by decrypting verification token.
+        // If a password change was interrupted after its final batch
+        // committed, the data is keyed to the staged salt instead.
+        if Self::verify_password(&db, &key).await.is_ok() {
+            if let Some(new_key) = rekey::pending_key(&db, &key).await? {
+                // A rekey stopped partway: finish moving the data to the new
+                // key, which becomes the only key that opens the vault.
+                tracing::warn!("Resuming interrupted vault re-encryption");
+                let (_, pending_params) = read_salt(&pending_salt_path).await?;
+                rekey::run(
+                    &db,
+                    &vault_id(db_path),
+                    &key,
+                    &new_key,
+                    RekeyOptions::default(),
+                    &mut |_, _| {},
+                )
+                .await?;
+                db = db.rekey(db_path, new_key.to_vec()).await?;
+                promote_pending_salt(db_path).await?;
+                key = new_key;
+                params = pending_params;
+            } else if pending_salt_path.exists() {
+                tracing::warn!("Discarding staged salt from an interrupted password change");
+                remove_pending_salt(db_path).await?;
+            }
+        } else {
+            let recovered = if pending_salt_path.exists() {
+                let (pending_salt, pending_params) = read_salt(&pending_salt_path).await?;
+                let pending_key =
+                    kdf::derive_key_with_params(password, &pending_salt, &pending_params)?;
+                Self::verify_password(&db, &pending_key)
+                    .await
+                    .is_ok()
+                    .then_some((pending_key, pending_params))
+            } else {
+                None
+            };
+
+            let Some((pending_key, pending_params)) = recovered else {
+                tracing::warn!("Failed to verify vault key - incorrect password");
+                return Err(VaultError::InvalidPassword);
+            };
+
+            // The data moved to the new key but the file did not
+            tracing::warn!("Completing interrupted password change");
+            db = db.rekey(db_path, pending_key.to_vec()).await?;
+            promote_pending_salt(db_path).await?;
+            key = pending_key;
+            params = pending_params;
+        }
 
         tracing::info!("Vault unlocked successfully");
 
-        Ok(Self {
-            db: Some(db),
+        let mut vault = Self {
+            db: Some(Arc::new(db)),
             key: Some(key),
             db_path: db_path.to_path_buf(),
+            memory_salt: None,
+            kdf_params: params,
+            rekey_options: RekeyOptions::default(),
+            clock: SystemClock::shared(),
+            last_activity: Mutex::new(Utc::now()),
+            password_strength: None,
+        };
+
+        if options.upgrade_kdf {
+            if let Err(e) = vault.upgrade_kdf_params(password).await {
+                tracing::warn!("Failed to upgrade vault key derivation parameters: {}", e);
+            }
+        }
+
+        Ok(vault)
+    }
+
+    /// Create a vault over an in-memory database, for tests.
+    ///
+    /// The salt is held in memory instead of being written to a salt file, so
+    /// nothing touches the file system and the vault disappears when dropped.
+    /// Otherwise it behaves like a vault from [`Vault::create`]: the schema is
+    /// migrated, the verification token is stored, and profile operations and
+    /// password changes work the same way.
+    ///
+    /// # Errors
+    /// Returns error if key derivation or database setup fails.
+    #[cfg(any(test, feature = "test-util"))]
+    pub async fn new_in_memory(password: &str) -> Result<Self> {
+        let salt = kdf::generate_salt();
+        let key = kdf::derive_key(password, &salt)?;
+
+        let db = Database::new(":memory:", key.to_vec()).await?;
+        db.run_migrations().await?;
+        Self::store_verification_token(&db, &key).await?;
+
+        Ok(Self {
+            db: Some(Arc::new(db)),
+            key: Some(key),
+            db_path: PathBuf::from(":memory:"),
+            memory_salt: Some(salt),
+            kdf_params: kdf::KdfParams::default(),
+            rekey_options: RekeyOptions::default(),
+            clock: SystemClock::shared(),
+            last_activity: Mutex::new(Utc::now()),
+            password_strength: None,
         })
     }
 
@@ -224,6 +418,15 @@ impl Vault {
         self.key.is_some() && self.db.is_some()
     }
 
+    /// Strength of the password this vault was created with.
+    ///
+    /// `None` for a vault that was unlocked rather than created, since the
+    /// estimate is only made when the password is chosen.
+    #[must_use]
+    pub fn password_strength(&self) -> Option<&PasswordStrength> {
+        self.password_strength.as_ref()
+    }
+
     /// Get the vault's database path.
     #[must_use]
     pub fn db_path(&self) -> &Path {
@@ -244,7 +447,7 @@ impl Vault {
         let profile = UserProfile::new(id.clone());
 
         profile
-            .save(self.db.as_ref().unwrap(), self.key.as_ref().unwrap())
+            .save(self.db.as_deref().unwrap(), self.key.as_ref().unwrap())
             .await?;
 
         tracing::info!("Created profile {id}");
@@ -258,7 +461,18 @@ impl Vault {
     pub async fn load_profile(&self, id: &ProfileId) -> Result<UserProfile> {
         self.require_unlocked()?;
 
-        UserProfile::load(self.db.as_ref().unwrap(), id, self.key.as_ref().unwrap()).await
+        UserProfile::load(self.db.as_deref().unwrap(), id, self.key.as_ref().unwrap()).await
+    }
+
+    /// Load a user profile by ID, also returning the names of any fields
+    /// that could not be decrypted and were left empty.
+    ///
+    /// # Errors
+    /// Returns error if vault is locked or profile not found.
+    pub async fn load_profile_lossy(&self, id: &ProfileId) -> Result<(UserProfile, Vec<String>)> {
+        self.require_unlocked()?;
+
+        UserProfile::load_lossy(self.db.as_deref().unwrap(), id, self.key.as_ref().unwrap()).await
     }
 
     /// Save a user profile.
@@ -269,7 +483,7 @@ impl Vault {
         self.require_unlocked()?;
 
         profile
-            .save(self.db.as_ref().unwrap(), self.key.as_ref().unwrap())
+            .save(self.db.as_deref().unwrap(), self.key.as_ref().unwrap())
             .await
     }
 
@@ -280,7 +494,7 @@ impl Vault {
     pub async fn delete_profile(&self, id: &ProfileId) -> Result<()> {
         self.require_unlocked()?;
 
-        UserProfile::delete(self.db.as_ref().unwrap(), id).await?;
+        UserProfile::delete(self.db.as_deref().unwrap(), id).await?;
         tracing::info!("Deleted profile {id}");
         Ok(())
     }
@@ -292,7 +506,442 @@ impl Vault {
     pub async fn list_profiles(&self) -> Result<Vec<ProfileId>> {
         self.require_unlocked()?;
 
-        UserProfile::list_ids(self.db.as_ref().unwrap()).await
+        UserProfile::list_ids(self.db.as_deref().unwrap()).await
+    }
+
+    /// Encrypt and store a document, such as an ID scan or a removal
+    /// confirmation, alongside the vault.
+    ///
+    /// `mime_type` must be one of [`attachment::ALLOWED_MIME_TYPES`] and match
+    /// the contents, and `bytes` may be at most
+    /// [`attachment::MAX_ATTACHMENT_BYTES`] long. The label is encrypted too.
+    ///
+    /// # Errors
+    /// Returns `VaultError::InvalidAttachment` if the document is rejected, or
+    /// an error if the vault is locked or the database operation fails.
+    pub async fn store_attachment(
+        &self,
+        bytes: &[u8],
+        mime_type: &str,
+        label: &str,
+    ) -> Result<AttachmentId> {
+        self.require_unlocked()?;
+
+        let id = attachment::store(
+            self.db.as_deref().unwrap(),
+            self.key.as_ref().unwrap(),
+            bytes,
+            mime_type,
+            label,
+        )
+        .await?;
+        tracing::info!("Stored attachment {id}");
+        Ok(id)
+    }
+
+    /// Load a stored document, returning its decrypted contents and MIME type.
+    ///
+    /// # Errors
+    /// Returns error if vault is locked, the attachment is not found, or
+    /// decryption fails.
+    pub async fn load_attachment(&self, id: &AttachmentId) -> Result<(Zeroizing<Vec<u8>>, String)> {
+        self.require_unlocked()?;
+
+        attachment::load(self.db.as_deref().unwrap(), self.key.as_ref().unwrap(), id).await
+    }
+
+    /// List the metadata of every stored document, oldest first.
+    ///
+    /// # Errors
+    /// Returns error if vault is locked, database operation fails, or a label
+    /// cannot be decrypted.
+    pub async fn list_attachments(&self) -> Result<Vec<AttachmentInfo>> {
+        self.require_unlocked()?;
+
+        attachment::list(self.db.as_deref().unwrap(), self.key.as_ref().unwrap()).await
+    }
+
+    /// Delete a stored document.
+    ///
+    /// # Errors
+    /// Returns error if vault is locked or database operation fails.
+    pub async fn delete_attachment(&self, id: &AttachmentId) -> Result<()> {
+        self.require_unlocked()?;
+
+        attachment::delete(self.db.as_deref().unwrap(), id).await?;
+        tracing::info!("Deleted attachment {id}");
+        Ok(())
+    }
+
+    /// Encrypt and store a small secret, such as a mail server password,
+    /// under `name`, replacing any secret already stored there.
+    ///
+    /// Secrets live in the settings table and move to the new key when the
+    /// password changes.
+    ///
+    /// # Errors
+    /// Returns error if vault is locked, encryption or database operation
+    /// fails.
+    pub async fn store_secret(&self, name: &str, secret: &str) -> Result<()> {
+        self.require_unlocked()?;
+
+        secret::store(
+            self.db.as_deref().unwrap(),
+            self.key.as_ref().unwrap(),
+            name,
+            secret,
+        )
+        .await
+    }
+
+    /// Load the secret stored under `name`, if any.
+    ///
+    /// # Errors
+    /// Returns error if vault is locked, database operation fails, or the
+    /// secret cannot be decrypted.
+    pub async fn load_secret(&self, name: &str) -> Result<Option<Zeroizing<String>>> {
+        self.require_unlocked()?;
+
+        secret::load(
+            self.db.as_deref().unwrap(),
+            self.key.as_ref().unwrap(),
+            name,
+        )
+        .await
+    }
+
+    /// Delete the secret stored under `name`, if any.
+    ///
+    /// # Errors
+    /// Returns error if vault is locked or database operation fails.
+    pub async fn delete_secret(&self, name: &str) -> Result<()> {
+        self.require_unlocked()?;
+
+        secret::delete(self.db.as_deref().unwrap(), name).await
+    }
+
+    /// Stream every profile in the vault, decrypted one at a time.
+    ///
+    /// Profile IDs are read from the database a page at a time, so memory use
+    /// stays bounded however many profiles the vault holds. A profile that
+    /// fails to load or decrypt is yielded as an `Err` item and the stream
+    /// continues with the next one. If the vault is locked, the stream yields
+    /// a single `VaultError::Locked`.
+    pub fn profiles_stream(&self) -> impl Stream<Item = Result<UserProfile>> + '_ {
+        self.profiles_stream_paged(PROFILE_PAGE_SIZE)
+    }
+
+    /// [`Vault::profiles_stream`] with a configurable page size.
+    fn profiles_stream_paged(
+        &self,
+        page_size: u32,
+    ) -> impl Stream<Item = Result<UserProfile>> + '_ {
+        struct Cursor {
+            after: Option<(String, String)>,
+            page: VecDeque<String>,
+            exhausted: bool,
+        }
+
+        let start = Cursor {
+            after: None,
+            page: VecDeque::new(),
+            exhausted: false,
+        };
+
+        futures::stream::unfold(start, move |mut cursor| async move {
+            loop {
+                if let Some(id) = cursor.page.pop_front() {
+                    let profile = match ProfileId::new(id) {
+                        Ok(id) => self.load_profile(&id).await,
+                        Err(e) => Err(VaultError::InvalidData(e.to_string())),
+                    };
+                    return Some((profile, cursor));
+                }
+                if cursor.exhausted {
+                    return None;
+                }
+
+                // Any error ends the stream after it is reported
+                let page = match self.database() {
+                    Ok(db) => UserProfile::list_id_page(db, cursor.after.as_ref(), page_size).await,
+                    Err(e) => Err(e),
+                };
+                match page {
+                    Ok(rows) => {
+                        cursor.exhausted = rows.len() < page_size as usize;
+                        cursor.after = rows.last().cloned();
+                        cursor.page = rows.into_iter().map(|(_, id)| id).collect();
+                    }
+                    Err(e) => {
+                        cursor.exhausted = true;
+                        return Some((Err(e), cursor));
+                    }
+                }
+            }
+        })
+    }
+
+    /// Change the master password, re-encrypting all profiles under the new key.
+    ///
+    /// See [`Vault::change_password_with_progress`].
+    ///
+    /// # Errors
+    /// Returns `VaultError::InvalidPassword` if `current_password` is wrong.
+    pub async fn change_password(
+        &mut self,
+        current_password: &str,
+        new_password: &str,
+    ) -> Result<()> {
+        self.change_password_with_progress(current_password, new_password, |_, _| {})
+            .await
+    }
+
+    /// Change the master password, reporting progress as profiles are re-encrypted.
+    ///
+    /// `on_progress(completed, total)` is called once per re-encrypted profile.
+    /// On success a `VaultPasswordChanged` event is written to the audit log.
+    ///
+    /// The change is crash-safe: the new salt is staged in a separate file,
+    /// profiles and attachments are re-encrypted in batches with their
+    /// progress recorded in the database, the verification token moves last,
+    /// the database file is re-encrypted with `PRAGMA rekey`, and only then
+    /// is the staged salt promoted. If the process is
+    /// interrupted at any point, the vault still unlocks with exactly one of
+    /// the two passwords, and [`Vault::unlock`] finishes or discards the
+    /// staged change. A change interrupted partway through the data is
+    /// finished by unlocking with the current password.
+    ///
+    /// # Errors
+    /// Returns error if:
+    /// - The vault is locked
+    /// - `current_password` is incorrect
+    /// - A profile cannot be decrypted or re-encrypted
+    /// - Database or file system operations fail
+    pub async fn change_password_with_progress<F>(
+        &mut self,
+        current_password: &str,
+        new_password: &str,
+        mut on_progress: F,
+    ) -> Result<()>
+    where
+        F: FnMut(usize, usize),
+    {
+        tracing::info!("Changing vault password");
+        self.rekey(
+            current_password,
+            new_password,
+            self.kdf_params,
+            "VaultPasswordChanged",
+            &mut on_progress,
+        )
+        .await?;
+        tracing::info!("Vault password changed");
+        Ok(())
+    }
+
+    /// Re-derive the key from the same password and a freshly generated salt.
+    ///
+    /// Use this when the salt file's integrity is in doubt. All profiles, the
+    /// verification token and the database file are re-encrypted under the
+    /// new key and the salt file is replaced, with the same crash-safety guarantees as
+    /// [`Vault::change_password_with_progress`]. On success a
+    /// `VaultSaltRotated` event is written to the audit log.
+    ///
+    /// # Errors
+    /// Returns error if:
+    /// - The vault is locked
+    /// - `password` is incorrect
+    /// - A profile cannot be decrypted or re-encrypted
+    /// - Database or file system operations fail
+    pub async fn rotate_salt(&mut self, password: &str) -> Result<()> {
+        tracing::info!("Rotating vault salt");
+        self.rekey(
+            password,
+            password,
+            self.kdf_params,
+            "VaultSaltRotated",
+            &mut |_, _| {},
+        )
+        .await?;
+        tracing::info!("Vault salt rotated");
+        Ok(())
+    }
+
+    /// Argon2id parameters the vault's key is derived with.
+    #[must_use]
+    pub fn kdf_params(&self) -> kdf::KdfParams {
+        self.kdf_params
+    }
+
+    /// Re-derive the key with the current default Argon2id parameters if the
+    /// vault's parameters are weaker.
+    ///
+    /// Vaults keep the parameters they were created with, so this is how old
+    /// vaults pick up stronger defaults. The password stays the same; the
+    /// salt is replaced and everything is re-encrypted with the same
+    /// crash-safety guarantees as [`Vault::change_password_with_progress`].
+    /// On upgrade a `VaultKdfUpgraded` event is written to the audit log.
+    ///
+    /// Returns whether the vault was upgraded.
+    ///
+    /// # Errors
+    /// Returns error if:
+    /// - The vault is locked
+    /// - `password` is incorrect
+    /// - A profile cannot be decrypted or re-encrypted
+    /// - Database or file system operations fail
+    pub async fn upgrade_kdf_params(&mut self, password: &str) -> Result<bool> {
+        let defaults = kdf::KdfParams::default();
+        if !self.kdf_params.is_weaker_than(&defaults) {
+            return Ok(false);
+        }
+
+        tracing::info!("Upgrading vault key derivation parameters");
+        self.rekey(
+            password,
+            password,
+            defaults,
+            "VaultKdfUpgraded",
+            &mut |_, _| {},
+        )
+        .await?;
+        tracing::info!("Vault key derivation parameters upgraded");
+        Ok(true)
+    }
+
+    /// Verify `current_password`, then move the vault to a new salt and a key
+    /// derived from `new_password` with `new_params`, recording `audit_event`
+    /// on success.
+    ///
+    /// Data is re-encrypted in batches (see [`RekeyOptions`]). If a batch
+    /// fails after earlier ones committed, the vault is locked; unlocking
+    /// with `current_password` finishes the rekey. If re-encrypting the file
+    /// or promoting the salt fails after the data has moved, the vault is
+    /// locked too, and unlocking with `new_password` finishes it.
+    async fn rekey(
+        &mut self,
+        current_password: &str,
+        new_password: &str,
+        new_params: kdf::KdfParams,
+        audit_event: &str,
+        on_progress: &mut impl FnMut(usize, usize),
+    ) -> Result<()> {
+        self.require_unlocked()?;
+        let db = self.db.as_deref().unwrap();
+
+        let (salt, params) = match self.memory_salt {
+            Some(salt) => (salt.to_vec(), self.kdf_params),
+            None => read_salt(&get_salt_path(&self.db_path)).await?,
+        };
+        let current_key = kdf::derive_key_with_params(current_password, &salt, &params)?;
+        Self::verify_password(db, &current_key)
+            .await
+            .map_err(|_| VaultError::InvalidPassword)?;
+
+        // Refuse to start on data the current key cannot read, so a rekey
+        // never stops halfway on a corrupt row
+        rekey::check_decryptable(db, &current_key, self.rekey_options.batch_size).await?;
+
+        let new_salt = kdf::generate_salt();
+        let new_key = kdf::derive_key_with_params(new_password, &new_salt, &new_params)?;
+
+        if self.memory_salt.is_none() {
+            stage_pending_salt(&self.db_path, &new_salt, &new_params).await?;
+        }
+
+        if let Err(e) = rekey::begin(db, &current_key, &new_key, audit_event).await {
+            if self.memory_salt.is_none() {
+                remove_pending_salt(&self.db_path).await?;
+            }
+            return Err(e);
+        }
+
+        if let Err(e) = rekey::run(
+            db,
+            &vault_id(&self.db_path),
+            &current_key,
+            &new_key,
+            self.rekey_options,
+            on_progress,
+        )
+        .await
+        {
+            // Some batches may already be on the new key; lock so that the
+            // next unlock resumes the rekey instead of reading mixed data.
+            tracing::error!("Vault re-encryption interrupted: {}", e);
+            self.db = None;
+            self.key = None;
+            return Err(e);
+        }
+
+        // The data is on the new key; the database file and the salt follow.
+        // Until both have, the vault stays locked on failure and the next
+        // unlock finishes the change.
+        self.key = Some(new_key);
+        self.kdf_params = new_params;
+        if let Err(e) = self.finish_rekey(new_salt).await {
+            tracing::error!("Vault rekey could not be completed: {}", e);
+            self.db = None;
+            self.key = None;
+            return Err(e);
+        }
+        Ok(())
+    }
+
+    /// Re-encrypt the database file under the vault's new key and make
+    /// `new_salt` the active salt.
+    async fn finish_rekey(&mut self, new_salt: [u8; kdf::SALT_LENGTH]) -> Result<()> {
+        if let Some(salt) = &mut self.memory_salt {
+            // An in-memory database has no file to re-encrypt
+            *salt = new_salt;
+            return Ok(());
+        }
+
+        let db = self.db.as_deref().ok_or(VaultError::Locked)?;
+        let key = self.key.as_ref().ok_or(VaultError::Locked)?;
+        let db = db.rekey(&self.db_path, key.to_vec()).await?;
+        self.db = Some(Arc::new(db));
+        promote_pending_salt(&self.db_path).await
+    }
+
+    /// Re-encrypt in batches of `options` during password changes and KDF
+    /// upgrades.
+    #[must_use]
+    pub fn with_rekey_options(mut self, options: RekeyOptions) -> Self {
+        self.rekey_options = options;
+        self
+    }
+
+    /// Read the time from `clock` for auto-lock, e.g. a mock clock in tests.
+    ///
+    /// Resets the idle time.
+    #[must_use]
+    pub fn with_clock(mut self, clock: SharedClock) -> Self {
+        self.clock = clock;
+        self.record_activity();
+        self
+    }
+
+    /// Note that the vault is in use, resetting the idle time.
+    ///
+    /// Accessing the database or encryption key records activity
+    /// automatically.
+    pub fn record_activity(&self) {
+        *self.last_activity.lock().expect("activity lock poisoned") = self.clock.now();
+    }
+
+    /// How long the vault has gone without activity.
+    #[must_use]
+    pub fn idle_for(&self) -> chrono::Duration {
+        self.clock.now() - *self.last_activity.lock().expect("activity lock poisoned")
+    }
+
+    /// Whether an unlocked vault has been idle for longer than `timeout`
+    /// and should be locked.
+    #[must_use]
+    pub fn should_auto_lock(&self, timeout: std::time::Duration) -> bool {
+        let timeout = chrono::Duration::from_std(timeout).unwrap_or(chrono::Duration::MAX);
+        self.is_unlocked() && self.idle_for() > timeout
     }
 
     /// Get a reference to the underlying database.
@@ -300,7 +949,22 @@ impl Vault {
     /// # Errors
     /// Returns `VaultError::Locked` if the vault is not unlocked.
     pub fn database(&self) -> Result<&Database> {
-        self.db.as_ref().ok_or(VaultError::Locked)
+        self.record_activity();
+        self.db.as_deref().ok_or(VaultError::Locked)
+    }
+
+    /// Get a shared handle to the underlying database for background tasks.
+    ///
+    /// Every call returns a clone of the same `Arc`, so tasks share the
+    /// vault's connection pool instead of wrapping the pool and key in a new
+    /// `Database`. Handles that are still held when the vault is locked keep
+    /// the pool open until they are dropped.
+    ///
+    /// # Errors
+    /// Returns `VaultError::Locked` if the vault is not unlocked.
+    pub fn shared_database(&self) -> Result<Arc<Database>> {
+        self.record_activity();
+        self.db.clone().ok_or(VaultError::Locked)
     }
 
     /// Get the encryption key for field-level encryption.
@@ -314,11 +978,30 @@ impl Vault {
     /// # Security Note
     /// The returned key should be used immediately and not stored.
     /// It's a reference to zeroized memory that will be cleared when the vault is locked.
+    /// Prefer [`session_key_handle`](Self::session_key_handle) or
+    /// [`with_key`](Self::with_key), which never hand out an unzeroized copy.
     pub fn encryption_key(&self) -> Result<&[u8; 32]> {
+        self.record_activity();
         // Zeroizing<[u8; 32]> derefs to &[u8; 32]
         self.key.as_ref().ok_or(VaultError::Locked).map(|k| &**k)
     }
 
+    /// Borrow the encryption key for the current session.
+    ///
+    /// # Errors
+    /// Returns `VaultError::Locked` if the vault is not unlocked.
+    pub fn session_key_handle(&self) -> Result<KeyHandle<'_>> {
+        self.encryption_key().map(KeyHandle::new)
+    }
+
+    /// Run `f` with the encryption key.
+    ///
+    /// # Errors
+    /// Returns `VaultError::Locked` if the vault is not unlocked.
+    pub fn with_key<R>(&self, f: impl FnOnce(&[u8; 32]) -> R) -> Result<R> {
+        Ok(self.session_key_handle()?.with_key(f))
+    }
+
     /// Require that the vault is unlocked.
     fn require_unlocked(&self) -> Result<()> {
         if !self.is_unlocked() {
@@ -343,8 +1026,7 @@ impl Vault {
         .bind(Timestamp::now().to_rfc3339())
         .bind(Timestamp::now().to_rfc3339())
         .execute(db.pool())
-        .await
-        .map_err(spectral_db::DatabaseError::from)?;
+        .await?;
 
         Ok(())
     }
@@ -355,8 +1037,7 @@ impl Vault {
             "SELECT data, nonce FROM profiles WHERE id = '__vault_verification__'",
         )
         .fetch_optional(db.pool())
-        .await
-        .map_err(spectral_db::DatabaseError::from)?
+        .await?
         .ok_or(VaultError::InvalidPassword)?;
 
         let nonce: [u8; 12] = row.1.try_into().map_err(|_| VaultError::InvalidPassword)?;
@@ -364,7 +1045,7 @@ impl Vault {
         let encrypted = EncryptedField::<String>::from_raw(row.0, nonce);
         let token = encrypted.decrypt(key)?;
 
-        if token != VERIFICATION_TOKEN {
+        if !token_matches(&token) {
             return Err(VaultError::InvalidPassword);
         }
 
@@ -372,6 +1053,11 @@ impl Vault {
     }
 }
 
+/// Compare a decrypted verification token against the expected value in constant time.
+fn token_matches(token: &str) -> bool {
+    token.as_bytes().ct_eq(VERIFICATION_TOKEN.as_bytes()).into()
+}
+
 impl Drop for Vault {
     fn drop(&mut self) {
         // Ensure database is closed and key is zeroized
@@ -388,6 +1074,100 @@ fn get_salt_path(db_path: &Path) -> PathBuf {
         .join(SALT_FILE_NAME)
 }
 
+/// Get the path to the staged salt file used during a password change.
+fn get_pending_salt_path(db_path: &Path) -> PathBuf {
+    get_salt_path(db_path).with_file_name(PENDING_SALT_FILE_NAME)
+}
+
+/// Identify the vault by the name of the directory containing its database.
+fn vault_id(db_path: &Path) -> String {
+    db_path.parent().and_then(Path::file_name).map_or_else(
+        || "default".to_string(),
+        |n| n.to_string_lossy().into_owned(),
+    )
+}
+
+/// Salt file contents: the salt followed by the encoded KDF parameters.
+///
+/// Keeping both in one file lets a rekey replace them with a single rename.
+fn salt_file_contents(salt: &[u8], params: &kdf::KdfParams) -> Vec<u8> {
+    [salt, &params.to_bytes()[..]].concat()
+}
+
+/// Read and validate a salt file, returning the salt and the KDF parameters
+/// stored with it.
+///
+/// Salt files without parameters belong to vaults created with
+/// [`kdf::LEGACY_PARAMS`].
+async fn read_salt(path: &Path) -> Result<(Vec<u8>, kdf::KdfParams)> {
+    let mut salt = tokio::fs::read(path)
+        .await
+        .map_err(|e| VaultError::InvalidData(format!("failed to read salt file: {e}")))?;
+
+    match salt.len() {
+        kdf::SALT_LENGTH => Ok((salt, kdf::LEGACY_PARAMS)),
+        len if len == kdf::SALT_LENGTH + kdf::PARAMS_LENGTH => {
+            let params = kdf::KdfParams::from_bytes(&salt[kdf::SALT_LENGTH..])?;
+            salt.truncate(kdf::SALT_LENGTH);
+            Ok((salt, params))
+        }
+        len => Err(VaultError::InvalidData(format!(
+            "invalid salt file: expected {} or {} bytes, got {len}",
+            kdf::SALT_LENGTH,
+            kdf::SALT_LENGTH + kdf::PARAMS_LENGTH
+        ))),
+    }
+}
+
+/// Open the vault database with `key`, checking that the key decrypts the
+/// file.
+///
+/// # Errors
+/// Returns `VaultError::InvalidPassword` if the file is encrypted under
+/// another key.
+async fn open_database(db_path: &Path, key: &[u8; 32]) -> Result<Database> {
+    let db = Database::new(db_path, key.to_vec()).await?;
+    if db.verify_key().await.is_err() {
+        db.close().await;
+        return Err(VaultError::InvalidPassword);
+    }
+    Ok(db)
+}
+
+/// Durably write the salt and KDF parameters for an in-progress rekey.
+async fn stage_pending_salt(db_path: &Path, salt: &[u8], params: &kdf::KdfParams) -> Result<()> {
+    let io_err = |e: std::io::Error| VaultError::InvalidData(format!("failed to stage salt: {e}"));
+
+    let path = get_pending_salt_path(db_path);
+    tokio::fs::write(&path, salt_file_contents(salt, params))
+        .await
+        .map_err(io_err)?;
+    tokio::fs::File::open(&path)
+        .await
+        .map_err(io_err)?
+        .sync_all()
+        .await
+        .map_err(io_err)
+}
+
+/// Atomically replace the active salt with the staged one.
+async fn promote_pending_salt(db_path: &Path) -> Result<()> {
+    tokio::fs::rename(get_pending_salt_path(db_path), get_salt_path(db_path))
+        .await
+        .map_err(|e| VaultError::InvalidData(format!("failed to replace salt file: {e}")))
+}
+
+/// Discard a staged salt whose password change never committed.
+async fn remove_pending_salt(db_path: &Path) -> Result<()> {
+    match tokio::fs::remove_file(get_pending_salt_path(db_path)).await {
+        Ok(()) => Ok(()),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(()),
+        Err(e) => Err(VaultError::InvalidData(format!(
+            "failed to remove staged salt: {e}"
+        ))),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -399,21 +1179,120 @@ mod tests {
         (temp_dir, db_path)
     }
 
+    #[test]
+    fn test_token_matches() {
+        assert!(token_matches(VERIFICATION_TOKEN));
+        assert!(!token_matches("SPECTRAL_VAULT_V2"));
+        assert!(!token_matches("SPECTRAL_VAULT_V"));
+        assert!(!token_matches(""));
+    }
+
     #[tokio::test]
-    async fn test_vault_create() {
-        let (_temp_dir, db_path) = test_vault_path();
-        let password = "test_password";
+    async fn test_verify_password_checks_token_contents() {
+        let key = [0x42; 32];
+        let db = Database::new(":memory:", key.to_vec())
+            .await
+            .expect("create db");
+        db.run_migrations().await.expect("run migrations");
 
-        let vault = Vault::create(password, &db_path)
+        Vault::store_verification_token(&db, &key)
             .await
-            .expect("create vault");
+            .expect("store token");
+        assert!(Vault::verify_password(&db, &key).await.is_ok());
+
+        // A token that decrypts under the right key but has the wrong contents
+        let forged = encrypt_string("SPECTRAL_VAULT_V0", &key).expect("encrypt");
+        sqlx::query("UPDATE profiles SET data = ?, nonce = ? WHERE id = '__vault_verification__'")
+            .bind(forged.ciphertext())
+            .bind(&forged.nonce()[..])
+            .execute(db.pool())
+            .await
+            .expect("replace token");
 
-        assert!(vault.is_unlocked());
-        assert_eq!(vault.db_path(), db_path);
-        assert!(db_path.exists());
+        assert!(matches!(
+            Vault::verify_password(&db, &key).await,
+            Err(VaultError::InvalidPassword)
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_auto_lock_after_idle_timeout() {
+        let clock = Arc::new(spectral_core::MockClock::default());
+        let vault = Vault::new_in_memory("password")
+            .await
+            .expect("create vault")
+            .with_clock(clock.clone());
+        let timeout = std::time::Duration::from_secs(15 * 60);
+
+        clock.advance(chrono::Duration::minutes(10));
+        assert!(!vault.should_auto_lock(timeout));
+
+        // Using the vault resets the idle time
+        vault.encryption_key().expect("unlocked");
+        clock.advance(chrono::Duration::minutes(10));
+        assert_eq!(vault.idle_for(), chrono::Duration::minutes(10));
+        assert!(!vault.should_auto_lock(timeout));
+
+        clock.advance(chrono::Duration::minutes(6));
+        assert!(vault.should_auto_lock(timeout));
+    }
+
+    #[tokio::test]
+    async fn test_key_handle_lends_session_key() {
+        let mut vault = Vault::new_in_memory("password")
+            .await
+            .expect("create vault");
+        let expected = *vault.encryption_key().expect("unlocked");
+
+        let matches = vault.with_key(|k| k == &expected).expect("unlocked");
+        assert!(matches);
+        let owned: Zeroizing<[u8; 32]> =
+            vault.session_key_handle().expect("unlocked").to_owned_key();
+        assert_eq!(*owned, expected);
+
+        // What lock() leaves behind
+        vault.key = None;
+        assert!(matches!(vault.with_key(|_| ()), Err(VaultError::Locked)));
+        assert!(matches!(
+            vault.session_key_handle(),
+            Err(VaultError::Locked)
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_vault_create() {
+        let (_temp_dir, db_path) = test_vault_path();
+        let password = "test_password";
+
+        let vault = Vault::create(password, &db_path)
+            .await
+            .expect("create vault");
+
+        assert!(vault.is_unlocked());
+        assert_eq!(vault.db_path(), db_path);
+        assert!(db_path.exists());
         assert!(get_salt_path(&db_path).exists());
     }
 
+    #[tokio::test]
+    async fn test_vault_create_warns_on_weak_password() {
+        let (_temp_dir, db_path) = test_vault_path();
+
+        // Weak passwords are accepted; the estimate is only advisory
+        let vault = Vault::create("password", &db_path)
+            .await
+            .expect("create vault");
+        let strength = vault.password_strength().expect("estimated at create");
+        assert!(strength.is_weak());
+        assert!(!strength.warnings.is_empty());
+        vault.lock();
+
+        let vault = Vault::unlock("password", &db_path)
+            .await
+            .expect("unlock vault");
+        assert!(vault.password_strength().is_none());
+    }
+
     #[tokio::test]
     async fn test_vault_unlock_correct_password() {
         let (_temp_dir, db_path) = test_vault_path();
@@ -642,4 +1521,748 @@ mod tests {
 
         assert_eq!(profile.id, profile_id);
     }
+
+    /// Smallest well-formed PDF header plus some body bytes.
+    const TEST_PDF: &[u8] = b"%PDF-1.4\n1 0 obj << /Type /Catalog >> endobj\n%%EOF\n";
+
+    #[tokio::test]
+    async fn test_attachment_persists_across_lock_unlock() {
+        let (_temp_dir, db_path) = test_vault_path();
+        let password = "test_password";
+
+        let vault = Vault::create(password, &db_path)
+            .await
+            .expect("create vault");
+        let id = vault
+            .store_attachment(TEST_PDF, "application/pdf", "Driver's license")
+            .await
+            .expect("store attachment");
+
+        // Contents are not stored in the clear
+        let stored = sqlx::query_scalar::<_, Vec<u8>>("SELECT data FROM attachments WHERE id = ?")
+            .bind(id.as_str())
+            .fetch_one(vault.database().expect("database").pool())
+            .await
+            .expect("query attachment");
+        assert!(!stored.windows(5).any(|w| w == b"%PDF-"));
+        vault.lock();
+
+        let vault = Vault::unlock(password, &db_path)
+            .await
+            .expect("unlock vault");
+        let (bytes, mime_type) = vault.load_attachment(&id).await.expect("load attachment");
+        assert_eq!(bytes.as_slice(), TEST_PDF);
+        assert_eq!(mime_type, "application/pdf");
+
+        let attachments = vault.list_attachments().await.expect("list attachments");
+        assert_eq!(attachments.len(), 1);
+        assert_eq!(attachments[0].id, id);
+        assert_eq!(attachments[0].label, "Driver's license");
+        assert_eq!(attachments[0].size_bytes, TEST_PDF.len() as u64);
+
+        vault
+            .delete_attachment(&id)
+            .await
+            .expect("delete attachment");
+        assert!(matches!(
+            vault.load_attachment(&id).await,
+            Err(VaultError::NotFound(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_store_attachment_rejects_invalid_documents() {
+        let (_temp_dir, db_path) = test_vault_path();
+        let vault = Vault::create("test_password", &db_path)
+            .await
+            .expect("create vault");
+
+        let oversized = vec![b'a'; attachment::MAX_ATTACHMENT_BYTES + 1];
+        for (bytes, mime_type) in [
+            (oversized.as_slice(), "text/plain"),
+            (TEST_PDF, "application/zip"),
+            (TEST_PDF, "image/png"),
+        ] {
+            assert!(matches!(
+                vault.store_attachment(bytes, mime_type, "label").await,
+                Err(VaultError::InvalidAttachment(_))
+            ));
+        }
+        assert!(vault
+            .list_attachments()
+            .await
+            .expect("list attachments")
+            .is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_change_password_reencrypts_attachments() {
+        let (_temp_dir, db_path) = test_vault_path();
+
+        let mut vault = Vault::create("old_password", &db_path)
+            .await
+            .expect("create vault");
+        let id = vault
+            .store_attachment(TEST_PDF, "application/pdf", "Confirmation")
+            .await
+            .expect("store attachment");
+        vault
+            .change_password("old_password", "new_password")
+            .await
+            .expect("change password");
+        vault.lock();
+
+        let vault = Vault::unlock("new_password", &db_path)
+            .await
+            .expect("unlock with new password");
+        let (bytes, _) = vault.load_attachment(&id).await.expect("load attachment");
+        assert_eq!(bytes.as_slice(), TEST_PDF);
+        let attachments = vault.list_attachments().await.expect("list attachments");
+        assert_eq!(attachments[0].label, "Confirmation");
+    }
+
+    #[tokio::test]
+    async fn test_secrets_are_encrypted_and_survive_password_change() {
+        let (_temp_dir, db_path) = test_vault_path();
+
+        let mut vault = Vault::create("old_password", &db_path)
+            .await
+            .expect("create vault");
+        assert!(vault
+            .load_secret("mail.smtp.password")
+            .await
+            .expect("load secret")
+            .is_none());
+        vault
+            .store_secret("mail.smtp.password", "app-password")
+            .await
+            .expect("store secret");
+
+        let stored = spectral_db::settings::get_setting(
+            vault.database().unwrap().pool(),
+            "secret.mail.smtp.password",
+        )
+        .await
+        .expect("read setting")
+        .expect("secret stored");
+        assert!(!stored.to_string().contains("app-password"));
+
+        vault
+            .change_password("old_password", "new_password")
+            .await
+            .expect("change password");
+        vault.lock();
+
+        let vault = Vault::unlock("new_password", &db_path)
+            .await
+            .expect("unlock with new password");
+        let secret = vault
+            .load_secret("mail.smtp.password")
+            .await
+            .expect("load secret")
+            .expect("secret stored");
+        assert_eq!(secret.as_str(), "app-password");
+
+        vault
+            .delete_secret("mail.smtp.password")
+            .await
+            .expect("delete secret");
+        assert!(vault
+            .load_secret("mail.smtp.password")
+            .await
+            .expect("load secret")
+            .is_none());
+    }
+
+    #[tokio::test]
+    async fn test_change_password_reports_progress_and_audits() {
+        let (_temp_dir, db_path) = test_vault_path();
+
+        let mut vault = Vault::create("old_password", &db_path)
+            .await
+            .expect("create vault");
+        let profile_id = vault.create_profile().await.expect("create profile 1");
+        vault.create_profile().await.expect("create profile 2");
+        vault.create_profile().await.expect("create profile 3");
+
+        let mut progress = Vec::new();
+        vault
+            .change_password_with_progress("old_password", "new_password", |done, total| {
+                progress.push((done, total));
+            })
+            .await
+            .expect("change password");
+
+        assert_eq!(progress, vec![(1, 3), (2, 3), (3, 3)]);
+        assert!(!get_pending_salt_path(&db_path).exists());
+
+        let events = sqlx::query_scalar::<_, String>(
+            "SELECT event_type FROM audit_log WHERE event_type = 'VaultPasswordChanged'",
+        )
+        .fetch_all(vault.database().expect("database").pool())
+        .await
+        .expect("query audit log");
+        assert_eq!(events.len(), 1);
+
+        // The in-memory key was swapped, so the open vault keeps working
+        vault.load_profile(&profile_id).await.expect("load profile");
+        vault.lock();
+
+        assert!(matches!(
+            Vault::unlock("old_password", &db_path).await,
+            Err(VaultError::InvalidPassword)
+        ));
+        let vault = Vault::unlock("new_password", &db_path)
+            .await
+            .expect("unlock with new password");
+        vault.load_profile(&profile_id).await.expect("load profile");
+    }
+
+    #[tokio::test]
+    async fn test_rotate_salt_keeps_password() {
+        let (_temp_dir, db_path) = test_vault_path();
+
+        let mut vault = Vault::create("password", &db_path)
+            .await
+            .expect("create vault");
+        let profile_id = vault.create_profile().await.expect("create profile");
+        let old_salt = read_salt(&get_salt_path(&db_path))
+            .await
+            .expect("read salt");
+        let old_key = *vault.encryption_key().expect("key");
+        let old_db = vault.shared_database().expect("database");
+
+        vault.rotate_salt("password").await.expect("rotate salt");
+
+        // The file was re-encrypted and reopened under the new key
+        assert!(old_db.pool().is_closed());
+        assert!(!Arc::ptr_eq(
+            &old_db,
+            &vault.shared_database().expect("database")
+        ));
+
+        let new_salt = read_salt(&get_salt_path(&db_path))
+            .await
+            .expect("read salt");
+        assert_ne!(old_salt, new_salt);
+        assert_ne!(&old_key, vault.encryption_key().expect("key"));
+        assert!(!get_pending_salt_path(&db_path).exists());
+
+        let events = sqlx::query_scalar::<_, String>(
+            "SELECT event_type FROM audit_log WHERE event_type = 'VaultSaltRotated'",
+        )
+        .fetch_all(vault.database().expect("database").pool())
+        .await
+        .expect("query audit log");
+        assert_eq!(events.len(), 1);
+
+        vault.lock();
+        let vault = Vault::unlock("password", &db_path)
+            .await
+            .expect("unlock with same password");
+        vault.load_profile(&profile_id).await.expect("load profile");
+    }
+
+    #[tokio::test]
+    async fn test_unlock_upgrades_weak_kdf_params() {
+        let (_temp_dir, db_path) = test_vault_path();
+
+        let vault = Vault::create_with_params("password", &db_path, kdf::MINIMUM_PARAMS)
+            .await
+            .expect("create vault");
+        let profile_id = vault.create_profile().await.expect("create profile");
+        vault.lock();
+
+        // Upgrading is opt-in
+        let vault = Vault::unlock("password", &db_path)
+            .await
+            .expect("unlock without upgrade");
+        assert_eq!(vault.kdf_params(), kdf::MINIMUM_PARAMS);
+        vault.lock();
+
+        let options = UnlockOptions { upgrade_kdf: true };
+        let vault = Vault::unlock_with_options("password", &db_path, options)
+            .await
+            .expect("unlock with upgrade");
+        assert_eq!(vault.kdf_params(), kdf::KdfParams::default());
+        let (_, stored) = read_salt(&get_salt_path(&db_path))
+            .await
+            .expect("read salt");
+        assert_eq!(stored, kdf::KdfParams::default());
+        assert!(!get_pending_salt_path(&db_path).exists());
+        vault.load_profile(&profile_id).await.expect("load profile");
+        vault.lock();
+
+        let mut vault = Vault::unlock("password", &db_path)
+            .await
+            .expect("unlock after upgrade");
+        vault.load_profile(&profile_id).await.expect("load profile");
+        assert!(!vault
+            .upgrade_kdf_params("password")
+            .await
+            .expect("upgrade is a no-op"));
+    }
+
+    #[tokio::test]
+    async fn test_legacy_salt_file_uses_legacy_params() {
+        let (_temp_dir, db_path) = test_vault_path();
+
+        let vault = Vault::create_with_params("password", &db_path, kdf::LEGACY_PARAMS)
+            .await
+            .expect("create vault");
+        vault.lock();
+
+        // Salt files written before parameters were stored hold only the salt
+        let salt_path = get_salt_path(&db_path);
+        let (salt, _) = read_salt(&salt_path).await.expect("read salt");
+        tokio::fs::write(&salt_path, &salt)
+            .await
+            .expect("write legacy salt file");
+
+        let vault = Vault::unlock("password", &db_path)
+            .await
+            .expect("unlock legacy vault");
+        assert_eq!(vault.kdf_params(), kdf::LEGACY_PARAMS);
+    }
+
+    #[tokio::test]
+    async fn test_rotate_salt_rejects_wrong_password() {
+        let (_temp_dir, db_path) = test_vault_path();
+
+        let mut vault = Vault::create("password", &db_path)
+            .await
+            .expect("create vault");
+        let old_salt = read_salt(&get_salt_path(&db_path))
+            .await
+            .expect("read salt");
+
+        let result = vault.rotate_salt("wrong_password").await;
+
+        assert!(matches!(result, Err(VaultError::InvalidPassword)));
+        assert_eq!(
+            read_salt(&get_salt_path(&db_path))
+                .await
+                .expect("read salt"),
+            old_salt
+        );
+        assert!(!get_pending_salt_path(&db_path).exists());
+    }
+
+    #[tokio::test]
+    async fn test_change_password_rejects_wrong_current_password() {
+        let (_temp_dir, db_path) = test_vault_path();
+
+        let mut vault = Vault::create("old_password", &db_path)
+            .await
+            .expect("create vault");
+
+        let result = vault
+            .change_password("wrong_password", "new_password")
+            .await;
+
+        assert!(matches!(result, Err(VaultError::InvalidPassword)));
+        assert!(!get_pending_salt_path(&db_path).exists());
+    }
+
+    #[tokio::test]
+    async fn test_change_password_interrupted_mid_reencryption() {
+        let (_temp_dir, db_path) = test_vault_path();
+
+        let mut vault = Vault::create("old_password", &db_path)
+            .await
+            .expect("create vault");
+        let profile_id = vault.create_profile().await.expect("create profile 1");
+        vault.create_profile().await.expect("create profile 2");
+
+        // Abort the change after the first profile is re-encrypted but
+        // before the first batch commits.
+        let result = tokio::spawn(async move {
+            vault
+                .change_password_with_progress("old_password", "new_password", |done, _| {
+                    assert!(done < 1, "simulated crash");
+                })
+                .await
+        })
+        .await;
+        assert!(result.is_err());
+        assert!(get_pending_salt_path(&db_path).exists());
+
+        // Only the old password opens the vault, and unlocking finishes the
+        // change
+        assert!(Vault::unlock("new_password", &db_path).await.is_err());
+        let vault = Vault::unlock("old_password", &db_path)
+            .await
+            .expect("unlock with old password");
+        vault.load_profile(&profile_id).await.expect("load profile");
+        assert!(!get_pending_salt_path(&db_path).exists());
+        vault.lock();
+
+        assert!(matches!(
+            Vault::unlock("old_password", &db_path).await,
+            Err(VaultError::InvalidPassword)
+        ));
+        Vault::unlock("new_password", &db_path)
+            .await
+            .expect("unlock with new password");
+    }
+
+    #[tokio::test]
+    async fn test_interrupted_rekey_resumes_from_last_batch() {
+        let (_temp_dir, db_path) = test_vault_path();
+
+        let mut vault = Vault::create("old_password", &db_path)
+            .await
+            .expect("create vault")
+            .with_rekey_options(RekeyOptions {
+                batch_size: 2,
+                batch_pause: std::time::Duration::ZERO,
+            });
+        let mut profile_ids = Vec::new();
+        for _ in 0..5 {
+            profile_ids.push(vault.create_profile().await.expect("create profile"));
+        }
+        let attachment_id = vault
+            .store_attachment(TEST_PDF, "application/pdf", "Confirmation")
+            .await
+            .expect("store attachment");
+
+        // Crash partway through the second batch: the first batch of two
+        // profiles has committed under the new key
+        let result = tokio::spawn(async move {
+            vault
+                .change_password_with_progress("old_password", "new_password", |done, _| {
+                    assert!(done < 3, "simulated crash");
+                })
+                .await
+        })
+        .await;
+        assert!(result.is_err());
+
+        let db = Database::new(&db_path, vec![0; 32]).await.expect("open db");
+        let (profiles_done,): (i64,) =
+            sqlx::query_as("SELECT profiles_done FROM rekey_progress WHERE id = 1")
+                .fetch_one(db.pool())
+                .await
+                .expect("progress row");
+        assert_eq!(profiles_done, 2);
+        drop(db);
+
+        assert!(matches!(
+            Vault::unlock("new_password", &db_path).await,
+            Err(VaultError::InvalidPassword)
+        ));
+        let vault = Vault::unlock("old_password", &db_path)
+            .await
+            .expect("unlock resumes rekey");
+        for id in &profile_ids {
+            vault.load_profile(id).await.expect("load profile");
+        }
+        let (data, _) = vault
+            .load_attachment(&attachment_id)
+            .await
+            .expect("load attachment");
+        assert_eq!(&data[..], TEST_PDF);
+        assert!(!get_pending_salt_path(&db_path).exists());
+
+        let (remaining,): (i64,) = sqlx::query_as("SELECT COUNT(*) FROM rekey_progress")
+            .fetch_one(vault.database().expect("database").pool())
+            .await
+            .expect("count progress rows");
+        assert_eq!(remaining, 0);
+        let (audited,): (i64,) = sqlx::query_as(
+            "SELECT COUNT(*) FROM audit_log WHERE event_type = 'VaultPasswordChanged'",
+        )
+        .fetch_one(vault.database().expect("database").pool())
+        .await
+        .expect("count audit events");
+        assert_eq!(audited, 1);
+        vault.lock();
+
+        // The vault is now entirely on the new key
+        assert!(matches!(
+            Vault::unlock("old_password", &db_path).await,
+            Err(VaultError::InvalidPassword)
+        ));
+        let vault = Vault::unlock("new_password", &db_path)
+            .await
+            .expect("unlock with new password");
+        for id in &profile_ids {
+            vault.load_profile(id).await.expect("load profile");
+        }
+        vault
+            .load_attachment(&attachment_id)
+            .await
+            .expect("load attachment");
+    }
+
+    #[tokio::test]
+    async fn test_change_password_interrupted_after_commit() {
+        let (_temp_dir, db_path) = test_vault_path();
+
+        let vault = Vault::create("old_password", &db_path)
+            .await
+            .expect("create vault");
+        let profile_id = vault.create_profile().await.expect("create profile");
+
+        // Run every step except promoting the staged salt.
+        let old_key = kdf::derive_key(
+            "old_password",
+            &read_salt(&get_salt_path(&db_path))
+                .await
+                .expect("read salt")
+                .0,
+        )
+        .expect("derive old key");
+        let new_salt = kdf::generate_salt();
+        let new_key = kdf::derive_key("new_password", &new_salt).expect("derive new key");
+        stage_pending_salt(&db_path, &new_salt, &kdf::KdfParams::default())
+            .await
+            .expect("stage salt");
+        let db = vault.database().expect("database");
+        rekey::begin(db, &old_key, &new_key, "VaultPasswordChanged")
+            .await
+            .expect("begin rekey");
+        rekey::run(
+            db,
+            "test",
+            &old_key,
+            &new_key,
+            RekeyOptions::default(),
+            &mut |_, _| {},
+        )
+        .await
+        .expect("reencrypt");
+        vault.lock();
+
+        assert!(Vault::unlock("old_password", &db_path).await.is_err());
+        let vault = Vault::unlock("new_password", &db_path)
+            .await
+            .expect("unlock with new password");
+        vault.load_profile(&profile_id).await.expect("load profile");
+        assert!(!get_pending_salt_path(&db_path).exists());
+        vault.lock();
+
+        Vault::unlock("new_password", &db_path)
+            .await
+            .expect("unlock after recovery");
+    }
+
+    #[tokio::test]
+    async fn test_change_password_interrupted_after_file_rekey() {
+        let (_temp_dir, db_path) = test_vault_path();
+
+        let vault = Vault::create("old_password", &db_path)
+            .await
+            .expect("create vault");
+        let profile_id = vault.create_profile().await.expect("create profile");
+
+        // Run every step, the file's PRAGMA rekey included, except promoting
+        // the staged salt.
+        let old_key = *vault.encryption_key().expect("key");
+        let new_salt = kdf::generate_salt();
+        let new_key = kdf::derive_key("new_password", &new_salt).expect("derive new key");
+        stage_pending_salt(&db_path, &new_salt, &kdf::KdfParams::default())
+            .await
+            .expect("stage salt");
+        let db = vault.database().expect("database");
+        rekey::begin(db, &old_key, &new_key, "VaultPasswordChanged")
+            .await
+            .expect("begin rekey");
+        rekey::run(
+            db,
+            "test",
+            &old_key,
+            &new_key,
+            RekeyOptions::default(),
+            &mut |_, _| {},
+        )
+        .await
+        .expect("reencrypt");
+        db.rekey(&db_path, new_key.to_vec())
+            .await
+            .expect("rekey file")
+            .close()
+            .await;
+        vault.lock();
+
+        assert!(matches!(
+            Vault::unlock("old_password", &db_path).await,
+            Err(VaultError::InvalidPassword)
+        ));
+        let vault = Vault::unlock("new_password", &db_path)
+            .await
+            .expect("unlock with new password");
+        vault.load_profile(&profile_id).await.expect("load profile");
+        assert!(!get_pending_salt_path(&db_path).exists());
+    }
+
+    #[tokio::test]
+    #[allow(deprecated)]
+    async fn test_in_memory_vault_profile_crud() {
+        let vault = Vault::new_in_memory("test_password")
+            .await
+            .expect("create in-memory vault");
+        assert!(vault.is_unlocked());
+
+        let id1 = vault.create_profile().await.expect("create profile 1");
+        let id2 = vault.create_profile().await.expect("create profile 2");
+
+        let key = vault.encryption_key().expect("key");
+        let mut profile = vault.load_profile(&id1).await.expect("load profile");
+        profile.email = Some(encrypt_string("test@example.com", key).expect("encrypt"));
+        vault.save_profile(&profile).await.expect("save profile");
+
+        let loaded = vault.load_profile(&id1).await.expect("load profile");
+        let email = loaded.email.expect("email").decrypt(key).expect("decrypt");
+        assert_eq!(email, "test@example.com");
+
+        let profiles = vault.list_profiles().await.expect("list profiles");
+        assert_eq!(profiles.len(), 2);
+        assert!(profiles.contains(&id1) && profiles.contains(&id2));
+
+        vault.delete_profile(&id1).await.expect("delete profile");
+        assert!(vault.load_profile(&id1).await.is_err());
+        assert_eq!(
+            vault.list_profiles().await.expect("list profiles"),
+            vec![id2]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_in_memory_vaults_are_isolated() {
+        let first = Vault::new_in_memory("password").await.expect("first vault");
+        let second = Vault::new_in_memory("password")
+            .await
+            .expect("second vault");
+
+        first.create_profile().await.expect("create profile");
+
+        assert_eq!(first.list_profiles().await.expect("list").len(), 1);
+        assert!(second.list_profiles().await.expect("list").is_empty());
+    }
+
+    #[tokio::test]
+    #[allow(deprecated)]
+    async fn test_in_memory_vault_change_password() {
+        let mut vault = Vault::new_in_memory("old_password")
+            .await
+            .expect("create in-memory vault");
+        let profile_id = vault.create_profile().await.expect("create profile");
+        let mut profile = vault.load_profile(&profile_id).await.expect("load profile");
+        profile.email = Some(
+            encrypt_string("test@example.com", vault.encryption_key().expect("key"))
+                .expect("encrypt"),
+        );
+        vault.save_profile(&profile).await.expect("save profile");
+
+        assert!(matches!(
+            vault
+                .change_password("wrong_password", "new_password")
+                .await,
+            Err(VaultError::InvalidPassword)
+        ));
+        vault
+            .change_password("old_password", "new_password")
+            .await
+            .expect("change password");
+        vault
+            .change_password("new_password", "newer_password")
+            .await
+            .expect("change password again");
+
+        let loaded = vault.load_profile(&profile_id).await.expect("load profile");
+        let email = loaded
+            .email
+            .expect("email")
+            .decrypt(vault.encryption_key().expect("key"))
+            .expect("decrypt");
+        assert_eq!(email, "test@example.com");
+    }
+
+    #[tokio::test]
+    async fn test_profiles_stream_pages_through_all_profiles() {
+        use futures::StreamExt;
+
+        let vault = Vault::new_in_memory("password")
+            .await
+            .expect("create in-memory vault");
+        let mut ids = Vec::new();
+        for _ in 0..5 {
+            ids.push(vault.create_profile().await.expect("create profile"));
+        }
+
+        let streamed: Vec<ProfileId> = vault
+            .profiles_stream_paged(2)
+            .map(|profile| profile.expect("load profile").id)
+            .collect()
+            .await;
+        assert_eq!(streamed.len(), 5);
+        for id in &ids {
+            assert!(streamed.contains(id));
+        }
+
+        assert_eq!(vault.profiles_stream().count().await, 5);
+    }
+
+    #[tokio::test]
+    async fn test_profiles_stream_yields_corrupt_profile_as_error() {
+        use futures::StreamExt;
+
+        let vault = Vault::new_in_memory("password")
+            .await
+            .expect("create in-memory vault");
+        vault.create_profile().await.expect("create profile");
+        vault.create_profile().await.expect("create profile");
+
+        let now = chrono::Utc::now().to_rfc3339();
+        sqlx::query(
+            "INSERT INTO profiles (id, data, nonce, created_at, updated_at) VALUES (?, ?, ?, ?, ?)",
+        )
+        .bind(ProfileId::generate().as_str())
+        .bind(&[0xAB_u8; 48][..])
+        .bind(&[0u8; 12][..])
+        .bind(&now)
+        .bind(&now)
+        .execute(vault.database().expect("database").pool())
+        .await
+        .expect("insert corrupt profile");
+
+        vault.create_profile().await.expect("create profile");
+        vault.create_profile().await.expect("create profile");
+
+        let results: Vec<Result<UserProfile>> = vault.profiles_stream_paged(2).collect().await;
+        assert_eq!(results.len(), 5);
+        assert!(results[2].is_err());
+        assert_eq!(results.iter().filter(|r| r.is_ok()).count(), 4);
+    }
+
+    #[tokio::test]
+    async fn test_shared_database_reuses_vault_pool() {
+        let vault = Vault::new_in_memory("password")
+            .await
+            .expect("create in-memory vault");
+
+        let first = vault.shared_database().expect("shared database");
+        let second = vault.shared_database().expect("shared database");
+        assert!(Arc::ptr_eq(&first, &second));
+        assert!(std::ptr::eq(
+            first.as_ref(),
+            vault.database().expect("database")
+        ));
+
+        // Writes through the vault are visible to a background task holding
+        // the shared handle, which an in-memory database only allows if the
+        // pool itself is shared.
+        let profile_id = vault.create_profile().await.expect("create profile");
+        let count = tokio::spawn(async move {
+            sqlx::query_scalar::<_, i64>("SELECT COUNT(*) FROM profiles WHERE id = ?")
+                .bind(profile_id.as_str())
+                .fetch_one(first.pool())
+                .await
+                .expect("count profiles")
+        })
+        .await
+        .expect("background task");
+        assert_eq!(count, 1);
+    }
 }
@@ -3,11 +3,14 @@
 //! Manages user profile data with field-level encryption. All PII is stored
 //! as encrypted fields that can only be decrypted when the vault is unlocked.
 
-use crate::cipher::EncryptedField;
+use crate::cipher::{decrypt_blob, encrypt_blob, EncryptedBlob, EncryptedField, NONCE_LENGTH};
 use crate::error::{Result, VaultError};
+use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
 use spectral_core::types::{ProfileId, Timestamp};
+use spectral_core::AddressFormat;
 use spectral_db::Database;
+use sqlx::SqliteConnection;
 
 /// User profile with encrypted PII fields.
 ///
@@ -77,6 +80,83 @@ pub struct UserProfile {
     pub updated_at: Timestamp,
 }
 
+/// How a profile is laid out in the database.
+///
+/// [`UserProfile::save`] keeps a stored profile's layout; use
+/// [`UserProfile::save_as`] to switch.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ProfileStorage {
+    /// The whole profile is one encrypted blob (storage version 1).
+    #[default]
+    Blob,
+    /// Each field is encrypted separately (storage version 2). A corrupt
+    /// field loses only that field, and single fields can be read with
+    /// [`UserProfile::load_field`] without decrypting the rest.
+    PerField,
+}
+
+impl ProfileStorage {
+    /// Value of the `profiles.storage_version` column for this layout.
+    fn version(self) -> i64 {
+        match self {
+            Self::Blob => 1,
+            Self::PerField => 2,
+        }
+    }
+
+    pub(crate) fn from_version(version: i64) -> Result<Self> {
+        match version {
+            1 => Ok(Self::Blob),
+            2 => Ok(Self::PerField),
+            other => Err(VaultError::InvalidData(format!(
+                "unknown profile storage version {other}"
+            ))),
+        }
+    }
+}
+
+/// Profile fields kept as `profiles` columns instead of encrypted rows in
+/// per-field storage. They hold no PII.
+const METADATA_FIELDS: [&str; 3] = ["id", "created_at", "updated_at"];
+
+/// Associated data binding an encrypted field to its profile and name, so
+/// rows cannot be swapped between fields or profiles.
+fn field_aad(profile_id: &str, field: &str) -> Vec<u8> {
+    format!("profile-field:{profile_id}:{field}").into_bytes()
+}
+
+/// Decrypt one row of `profile_fields`.
+fn decrypt_field(
+    profile_id: &str,
+    field: &str,
+    data: Vec<u8>,
+    nonce: Vec<u8>,
+    key: &[u8; 32],
+) -> Result<serde_json::Value> {
+    let nonce: [u8; NONCE_LENGTH] = nonce.try_into().map_err(|_| {
+        VaultError::InvalidData(format!("invalid nonce length for profile field {field}"))
+    })?;
+    let plaintext = decrypt_blob(
+        &EncryptedBlob::from_raw(data, nonce),
+        key,
+        &field_aad(profile_id, field),
+    )
+    .map_err(|_| VaultError::Decryption(format!("profile field {field} could not be decrypted")))?;
+    serde_json::from_slice(&plaintext).map_err(|e| {
+        VaultError::Serialization(format!("failed to deserialize profile field {field}: {e}"))
+    })
+}
+
+/// Storage layout of a stored profile, or `None` if it does not exist.
+async fn find_storage(conn: &mut SqliteConnection, id: &str) -> Result<Option<ProfileStorage>> {
+    sqlx::query_scalar::<_, i64>("SELECT storage_version FROM profiles WHERE id = ?")
+        .bind(id)
+        .fetch_optional(&mut *conn)
+        .await?
+        .map(ProfileStorage::from_version)
+        .transpose()
+}
+
 /// Type of email address.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "PascalCase")]
@@ -349,42 +429,125 @@ impl UserProfile {
 
     /// Save the profile to the database.
     ///
-    /// The entire profile is serialized and stored as an encrypted blob
-    /// in the profiles table.
+    /// A stored profile keeps its [`ProfileStorage`] layout; a new profile is
+    /// stored as one encrypted blob in the profiles table.
     ///
     /// # Arguments
     /// * `db` - Database connection
-    /// * `key` - Encryption key for the profile data blob
+    /// * `key` - Encryption key for the profile data
     ///
     /// # Errors
     /// Returns error if serialization or database operation fails.
     pub async fn save(&self, db: &Database, key: &[u8; 32]) -> Result<()> {
-        // Serialize the profile
-        let profile_json = serde_json::to_vec(self)
-            .map_err(|e| VaultError::Serialization(format!("failed to serialize profile: {e}")))?;
+        let mut tx = db.pool().begin().await?;
+        let storage = find_storage(&mut tx, self.id.as_str())
+            .await?
+            .unwrap_or_default();
+        self.write(&mut tx, key, storage).await?;
+        tx.commit().await?;
+        Ok(())
+    }
 
-        // Encrypt the entire profile blob
-        let encrypted = EncryptedField::<Vec<u8>>::encrypt(&profile_json, key)?;
+    /// Save the profile to the database in the given layout.
+    ///
+    /// Whatever was stored for the profile before is replaced, so this also
+    /// converts a stored profile between layouts.
+    ///
+    /// # Errors
+    /// Returns error if serialization or database operation fails.
+    pub async fn save_as(
+        &self,
+        db: &Database,
+        key: &[u8; 32],
+        storage: ProfileStorage,
+    ) -> Result<()> {
+        let mut tx = db.pool().begin().await?;
+        self.write(&mut tx, key, storage).await?;
+        tx.commit().await?;
+        Ok(())
+    }
+
+    /// Write the profile on `conn` in the given layout.
+    pub(crate) async fn write(
+        &self,
+        conn: &mut SqliteConnection,
+        key: &[u8; 32],
+        storage: ProfileStorage,
+    ) -> Result<()> {
+        let to_json = |e: serde_json::Error| {
+            VaultError::Serialization(format!("failed to serialize profile: {e}"))
+        };
+
+        // Per-field profiles leave the blob empty
+        let (data, nonce) = match storage {
+            ProfileStorage::Blob => {
+                let profile_json = serde_json::to_vec(self).map_err(to_json)?;
+                let encrypted = EncryptedField::<Vec<u8>>::encrypt(&profile_json, key)?;
+                (encrypted.ciphertext().to_vec(), encrypted.nonce().to_vec())
+            }
+            ProfileStorage::PerField => (Vec::new(), Vec::new()),
+        };
 
-        // Store in database
         sqlx::query(
-            "INSERT OR REPLACE INTO profiles (id, data, nonce, created_at, updated_at)
-             VALUES (?, ?, ?, ?, ?)",
+            "INSERT INTO profiles (id, data, nonce, created_at, updated_at, storage_version)
+             VALUES (?, ?, ?, ?, ?, ?)
+             ON CONFLICT(id) DO UPDATE SET
+                 data = excluded.data,
+                 nonce = excluded.nonce,
+                 updated_at = excluded.updated_at,
+                 storage_version = excluded.storage_version",
         )
         .bind(self.id.as_str())
-        .bind(encrypted.ciphertext())
-        .bind(&encrypted.nonce()[..])
+        .bind(data)
+        .bind(nonce)
         .bind(self.created_at.to_rfc3339())
         .bind(self.updated_at.to_rfc3339())
-        .execute(db.pool())
-        .await
-        .map_err(spectral_db::DatabaseError::from)?;
+        .bind(storage.version())
+        .execute(&mut *conn)
+        .await?;
+
+        sqlx::query("DELETE FROM profile_fields WHERE profile_id = ?")
+            .bind(self.id.as_str())
+            .execute(&mut *conn)
+            .await?;
+
+        if storage == ProfileStorage::PerField {
+            let serde_json::Value::Object(fields) = serde_json::to_value(self).map_err(to_json)?
+            else {
+                return Err(VaultError::Serialization(
+                    "profile did not serialize to an object".to_string(),
+                ));
+            };
+
+            for (field, value) in fields {
+                if value.is_null() || METADATA_FIELDS.contains(&field.as_str()) {
+                    continue;
+                }
+                let value_json = serde_json::to_vec(&value).map_err(to_json)?;
+                let encrypted =
+                    encrypt_blob(&value_json, key, &field_aad(self.id.as_str(), &field))?;
+
+                sqlx::query(
+                    "INSERT INTO profile_fields (profile_id, field, data, nonce)
+                     VALUES (?, ?, ?, ?)",
+                )
+                .bind(self.id.as_str())
+                .bind(&field)
+                .bind(encrypted.ciphertext())
+                .bind(&encrypted.nonce()[..])
+                .execute(&mut *conn)
+                .await?;
+            }
+        }
 
         Ok(())
     }
 
     /// Load a profile from the database.
     ///
+    /// With per-field storage, fields that cannot be decrypted are left
+    /// `None` and logged; see [`UserProfile::load_lossy`] to find out which.
+    ///
     /// # Arguments
     /// * `db` - Database connection
     /// * `id` - Profile ID to load
@@ -393,33 +556,172 @@ impl UserProfile {
     /// # Errors
     /// Returns error if profile not found, decryption fails, or deserialization fails.
     pub async fn load(db: &Database, id: &ProfileId, key: &[u8; 32]) -> Result<Self> {
-        // Query the database
-        let row = sqlx::query_as::<_, (Vec<u8>, Vec<u8>)>(
-            "SELECT data, nonce FROM profiles WHERE id = ?",
-        )
-        .bind(id.as_str())
-        .fetch_optional(db.pool())
-        .await
-        .map_err(spectral_db::DatabaseError::from)?
-        .ok_or_else(|| VaultError::NotFound(format!("profile {id}")))?;
+        Self::load_lossy(db, id, key)
+            .await
+            .map(|(profile, _)| profile)
+    }
+
+    /// Load a profile, skipping per-field rows that cannot be decrypted.
+    ///
+    /// Returns the profile along with the names of the fields that were
+    /// dropped, so callers can tell the user which values were lost.
+    ///
+    /// # Errors
+    /// Returns error if profile not found, a blob profile cannot be
+    /// decrypted, or deserialization fails.
+    pub async fn load_lossy(
+        db: &Database,
+        id: &ProfileId,
+        key: &[u8; 32],
+    ) -> Result<(Self, Vec<String>)> {
+        let mut conn = db.pool().acquire().await?;
+        let mut dropped = Vec::new();
+        let profile = Self::read_fields(&mut conn, id.as_str(), key, Some(&mut dropped)).await?;
+        if !dropped.is_empty() {
+            tracing::warn!(
+                "Profile {id} loaded without undecryptable fields: {}",
+                dropped.join(", ")
+            );
+        }
+        Ok((profile, dropped))
+    }
+
+    /// Read a profile on `conn`, whatever its layout, failing on any field
+    /// that cannot be decrypted.
+    pub(crate) async fn read(
+        conn: &mut SqliteConnection,
+        id: &str,
+        key: &[u8; 32],
+    ) -> Result<Self> {
+        Self::read_fields(conn, id, key, None).await
+    }
 
-        // Reconstruct encrypted field
-        let nonce: [u8; 12] = row
-            .1
-            .try_into()
-            .map_err(|_| VaultError::InvalidData("invalid nonce length".to_string()))?;
+    /// Read a profile on `conn`. When `dropped` is given, per-field rows that
+    /// fail to decrypt are skipped and their names pushed onto it.
+    async fn read_fields(
+        conn: &mut SqliteConnection,
+        id: &str,
+        key: &[u8; 32],
+        mut dropped: Option<&mut Vec<String>>,
+    ) -> Result<Self> {
+        let (data, nonce, storage_version, created_at, updated_at) =
+            sqlx::query_as::<_, (Vec<u8>, Vec<u8>, i64, String, String)>(
+                "SELECT data, nonce, storage_version, created_at, updated_at
+                 FROM profiles WHERE id = ?",
+            )
+            .bind(id)
+            .fetch_optional(&mut *conn)
+            .await?
+            .ok_or_else(|| VaultError::NotFound(format!("profile {id}")))?;
+
+        let profile = match ProfileStorage::from_version(storage_version)? {
+            ProfileStorage::Blob => {
+                // Reconstruct encrypted field
+                let nonce: [u8; NONCE_LENGTH] = nonce
+                    .try_into()
+                    .map_err(|_| VaultError::InvalidData("invalid nonce length".to_string()))?;
+                let encrypted = EncryptedField::<Vec<u8>>::from_raw(data, nonce);
+
+                // Decrypt
+                let profile_json = encrypted.decrypt(key)?;
+                serde_json::from_slice(&profile_json)
+            }
+            ProfileStorage::PerField => {
+                let rows = sqlx::query_as::<_, (String, Vec<u8>, Vec<u8>)>(
+                    "SELECT field, data, nonce FROM profile_fields WHERE profile_id = ?",
+                )
+                .bind(id)
+                .fetch_all(&mut *conn)
+                .await?;
+
+                let timestamp = |value: &str| {
+                    Timestamp::from_rfc3339(value)
+                        .map(|t| serde_json::Value::String(t.to_rfc3339()))
+                        .map_err(|e| VaultError::InvalidData(e.to_string()))
+                };
+                let mut fields = serde_json::Map::new();
+                fields.insert("id".to_string(), serde_json::Value::String(id.to_string()));
+                fields.insert("created_at".to_string(), timestamp(&created_at)?);
+                fields.insert("updated_at".to_string(), timestamp(&updated_at)?);
+                for (field, data, nonce) in rows {
+                    match decrypt_field(id, &field, data, nonce, key) {
+                        Ok(value) => {
+                            fields.insert(field, value);
+                        }
+                        Err(e) => match dropped.as_deref_mut() {
+                            Some(dropped) => dropped.push(field),
+                            None => return Err(e),
+                        },
+                    }
+                }
+                serde_json::from_value(serde_json::Value::Object(fields))
+            }
+        };
 
-        let encrypted = EncryptedField::<Vec<u8>>::from_raw(row.0, nonce);
+        profile
+            .map_err(|e| VaultError::Serialization(format!("failed to deserialize profile: {e}")))
+    }
 
-        // Decrypt
-        let profile_json = encrypted.decrypt(key)?;
+    /// Load a single field of a profile, such as `"first_name"` to show a
+    /// profile in a list.
+    ///
+    /// With per-field storage only that field is decrypted, so other fields
+    /// being corrupt does not matter. Returns `None` if the profile has no
+    /// value for the field.
+    ///
+    /// # Errors
+    /// Returns error if the profile is not found, or the field cannot be
+    /// decrypted or deserialized as `T`.
+    pub async fn load_field<T: DeserializeOwned>(
+        db: &Database,
+        id: &ProfileId,
+        key: &[u8; 32],
+        field: &str,
+    ) -> Result<Option<T>> {
+        let mut conn = db.pool().acquire().await?;
+        let storage = find_storage(&mut conn, id.as_str())
+            .await?
+            .ok_or_else(|| VaultError::NotFound(format!("profile {id}")))?;
+
+        let value = if storage == ProfileStorage::PerField && !METADATA_FIELDS.contains(&field) {
+            let row = sqlx::query_as::<_, (Vec<u8>, Vec<u8>)>(
+                "SELECT data, nonce FROM profile_fields WHERE profile_id = ? AND field = ?",
+            )
+            .bind(id.as_str())
+            .bind(field)
+            .fetch_optional(&mut *conn)
+            .await?;
+            match row {
+                Some((data, nonce)) => decrypt_field(id.as_str(), field, data, nonce, key)?,
+                None => serde_json::Value::Null,
+            }
+        } else {
+            let profile = Self::read(&mut conn, id.as_str(), key).await?;
+            serde_json::to_value(profile)
+                .map_err(|e| {
+                    VaultError::Serialization(format!("failed to serialize profile: {e}"))
+                })?
+                .get_mut(field)
+                .map_or(serde_json::Value::Null, serde_json::Value::take)
+        };
 
-        // Deserialize
-        let profile = serde_json::from_slice(&profile_json).map_err(|e| {
-            VaultError::Serialization(format!("failed to deserialize profile: {e}"))
-        })?;
+        if value.is_null() {
+            return Ok(None);
+        }
+        serde_json::from_value(value).map(Some).map_err(|e| {
+            VaultError::Serialization(format!("failed to deserialize profile field {field}: {e}"))
+        })
+    }
 
-        Ok(profile)
+    /// Get the storage layout of a stored profile.
+    ///
+    /// # Errors
+    /// Returns error if the profile is not found or the database query fails.
+    pub async fn storage(db: &Database, id: &ProfileId) -> Result<ProfileStorage> {
+        let mut conn = db.pool().acquire().await?;
+        find_storage(&mut conn, id.as_str())
+            .await?
+            .ok_or_else(|| VaultError::NotFound(format!("profile {id}")))
     }
 
     /// Delete a profile from the database.
@@ -434,8 +736,7 @@ impl UserProfile {
         sqlx::query("DELETE FROM profiles WHERE id = ?")
             .bind(id.as_str())
             .execute(db.pool())
-            .await
-            .map_err(spectral_db::DatabaseError::from)?;
+            .await?;
 
         Ok(())
     }
@@ -452,15 +753,148 @@ impl UserProfile {
             "SELECT id FROM profiles WHERE id != '__vault_verification__' ORDER BY created_at",
         )
         .fetch_all(db.pool())
+        .await?;
+
+        rows.into_iter().map(|id| Ok(ProfileId::new(id)?)).collect()
+    }
+
+    /// List one page of profile IDs in creation order.
+    ///
+    /// Pages are keyed on `(created_at, id)`: pass the last entry of the
+    /// previous page as `after` to get the next one. IDs are returned as
+    /// stored so that a malformed row can be reported by the caller.
+    pub(crate) async fn list_id_page(
+        db: &Database,
+        after: Option<&(String, String)>,
+        limit: u32,
+    ) -> Result<Vec<(String, String)>> {
+        let (after_created_at, after_id) = after.map_or((None, None), |(created_at, id)| {
+            (Some(created_at.as_str()), Some(id.as_str()))
+        });
+
+        sqlx::query_as::<_, (String, String)>(
+            "SELECT created_at, id FROM profiles
+             WHERE id != '__vault_verification__'
+               AND (?1 IS NULL OR (created_at, id) > (?1, ?2))
+             ORDER BY created_at, id
+             LIMIT ?3",
+        )
+        .bind(after_created_at)
+        .bind(after_id)
+        .bind(limit)
+        .fetch_all(db.pool())
         .await
-        .map_err(spectral_db::DatabaseError::from)?;
-
-        rows.into_iter()
-            .map(|id| {
-                ProfileId::new(id)
-                    .map_err(|e| VaultError::InvalidData(format!("invalid profile ID: {e}")))
-            })
-            .collect()
+        .map_err(VaultError::from)
+    }
+
+    /// Re-encrypt every PII field from `old_key` to `new_key`.
+    ///
+    /// Used when the master password changes. Non-PII metadata (ID,
+    /// timestamps, relationship and contact types) is copied unchanged.
+    ///
+    /// # Errors
+    /// Returns error if any field fails to decrypt with `old_key`.
+    #[allow(deprecated)]
+    pub fn reencrypt(&self, old_key: &[u8; 32], new_key: &[u8; 32]) -> Result<Self> {
+        fn opt<T>(
+            field: Option<&EncryptedField<T>>,
+            old_key: &[u8; 32],
+            new_key: &[u8; 32],
+        ) -> Result<Option<EncryptedField<T>>>
+        where
+            T: Serialize + for<'de> Deserialize<'de>,
+        {
+            field.map(|f| f.reencrypt(old_key, new_key)).transpose()
+        }
+
+        let re = |f: &EncryptedField<String>| f.reencrypt(old_key, new_key);
+        let re_opt = |f: Option<&EncryptedField<String>>| opt(f, old_key, new_key);
+
+        Ok(Self {
+            id: self.id.clone(),
+            full_name: re_opt(self.full_name.as_ref())?,
+            first_name: re_opt(self.first_name.as_ref())?,
+            middle_name: re_opt(self.middle_name.as_ref())?,
+            last_name: re_opt(self.last_name.as_ref())?,
+            email: re_opt(self.email.as_ref())?,
+            phone: re_opt(self.phone.as_ref())?,
+            address: re_opt(self.address.as_ref())?,
+            city: re_opt(self.city.as_ref())?,
+            state: re_opt(self.state.as_ref())?,
+            zip_code: re_opt(self.zip_code.as_ref())?,
+            country: re_opt(self.country.as_ref())?,
+            date_of_birth: re_opt(self.date_of_birth.as_ref())?,
+            ssn: re_opt(self.ssn.as_ref())?,
+            employer: re_opt(self.employer.as_ref())?,
+            job_title: re_opt(self.job_title.as_ref())?,
+            education: re_opt(self.education.as_ref())?,
+            social_media: opt(self.social_media.as_ref(), old_key, new_key)?,
+            previous_addresses_v1: opt(self.previous_addresses_v1.as_ref(), old_key, new_key)?,
+            phone_numbers: self
+                .phone_numbers
+                .iter()
+                .map(|p| {
+                    Ok(PhoneNumber {
+                        number: re(&p.number)?,
+                        number_normalized: re_opt(p.number_normalized.as_ref())?,
+                        phone_type: p.phone_type,
+                    })
+                })
+                .collect::<Result<_>>()?,
+            email_addresses: self
+                .email_addresses
+                .iter()
+                .map(|e| {
+                    Ok(EmailAddress {
+                        email: re(&e.email)?,
+                        email_normalized: re_opt(e.email_normalized.as_ref())?,
+                        email_type: e.email_type,
+                    })
+                })
+                .collect::<Result<_>>()?,
+            previous_addresses_v2: self
+                .previous_addresses_v2
+                .iter()
+                .map(|a| {
+                    Ok(PreviousAddress {
+                        address_line1: re(&a.address_line1)?,
+                        address_line2: re_opt(a.address_line2.as_ref())?,
+                        city: re(&a.city)?,
+                        state: re(&a.state)?,
+                        zip_code: re(&a.zip_code)?,
+                        lived_from: a.lived_from.clone(),
+                        lived_to: a.lived_to.clone(),
+                    })
+                })
+                .collect::<Result<_>>()?,
+            aliases: self
+                .aliases
+                .iter()
+                .map(|a| {
+                    Ok(Alias {
+                        first_name: re_opt(a.first_name.as_ref())?,
+                        middle_name: re_opt(a.middle_name.as_ref())?,
+                        last_name: re_opt(a.last_name.as_ref())?,
+                        nickname: re_opt(a.nickname.as_ref())?,
+                    })
+                })
+                .collect::<Result<_>>()?,
+            relatives: self
+                .relatives
+                .iter()
+                .map(|r| {
+                    Ok(Relative {
+                        first_name: re_opt(r.first_name.as_ref())?,
+                        middle_name: re_opt(r.middle_name.as_ref())?,
+                        last_name: re_opt(r.last_name.as_ref())?,
+                        maiden_name: re_opt(r.maiden_name.as_ref())?,
+                        relationship: r.relationship,
+                    })
+                })
+                .collect::<Result<_>>()?,
+            created_at: self.created_at,
+            updated_at: self.updated_at,
+        })
     }
 
     /// Update the profile's `updated_at` timestamp.
@@ -468,14 +902,27 @@ impl UserProfile {
         self.updated_at = Timestamp::now();
     }
 
-    /// Calculate profile completeness score.
+    /// Calculate profile completeness score assuming a US address.
+    ///
+    /// See [`Self::completeness_score_for_country`] for the scoring breakdown.
+    #[must_use]
+    pub fn completeness_score(&self) -> ProfileCompleteness {
+        self.completeness_score_for_country(None)
+    }
+
+    /// Calculate profile completeness score for the profile's country.
+    ///
+    /// `country` is the decrypted `country` field; `None` assumes the US.
     ///
     /// Scoring breakdown:
     /// - Core identity (40 points): `first_name` (15), `last_name` (15), email (10)
     /// - Current location (30 points): address (10), city (10), state+zip (10)
     /// - Enhanced matching (30 points): phones (10), `prev_addresses` (10), dob (5), aliases (3), relatives (2)
+    ///
+    /// For countries without states the postcode alone earns the state+zip points.
     #[must_use]
-    pub fn completeness_score(&self) -> ProfileCompleteness {
+    pub fn completeness_score_for_country(&self, country: Option<&str>) -> ProfileCompleteness {
+        let address_format = AddressFormat::for_country(country);
         let mut score = 0u32;
 
         // Core identity (40 points)
@@ -497,7 +944,8 @@ impl UserProfile {
         if self.city.is_some() {
             score += 10;
         }
-        if self.state.is_some() && self.zip_code.is_some() {
+        let has_region = self.state.is_some() || !address_format.has_states;
+        if has_region && self.zip_code.is_some() {
             score += 10;
         }
 
@@ -579,6 +1027,117 @@ mod tests {
         assert!(profile.email.is_none());
     }
 
+    #[test]
+    fn test_reencrypt_profile() {
+        let old_key = test_key();
+        let new_key = [0x24; 32];
+        let mut profile = UserProfile::new(ProfileId::generate());
+        profile.first_name = Some(encrypt_string("Jane", &old_key).expect("encrypt"));
+        profile.phone_numbers =
+            vec![PhoneNumber::new("555-123-4567", PhoneType::Mobile, &old_key).expect("create")];
+        profile.relatives = vec![Relative {
+            first_name: Some(encrypt_string("Sam", &old_key).expect("encrypt")),
+            middle_name: None,
+            last_name: None,
+            maiden_name: None,
+            relationship: RelationshipType::Sibling,
+        }];
+
+        let rekeyed = profile.reencrypt(&old_key, &new_key).expect("reencrypt");
+
+        assert_eq!(rekeyed.id, profile.id);
+        let first_name = rekeyed.first_name.as_ref().expect("first name");
+        assert_eq!(first_name.decrypt(&new_key).expect("decrypt"), "Jane");
+        assert!(first_name.decrypt(&old_key).is_err());
+        let phone = &rekeyed.phone_numbers[0];
+        assert_eq!(
+            phone.number.decrypt(&new_key).expect("decrypt"),
+            "555-123-4567"
+        );
+        assert_eq!(
+            phone
+                .number_normalized
+                .as_ref()
+                .expect("normalized")
+                .decrypt(&new_key)
+                .expect("decrypt"),
+            "5551234567"
+        );
+        assert_eq!(rekeyed.relatives[0].relationship, RelationshipType::Sibling);
+
+        assert!(profile.reencrypt(&new_key, &old_key).is_err());
+    }
+
+    #[test]
+    #[allow(deprecated)]
+    fn test_reencrypt_profile_phase2_collections() {
+        let old_key = test_key();
+        let new_key = [0x24; 32];
+        let enc = |value: &str| encrypt_string(value, &old_key).expect("encrypt");
+
+        let mut profile = UserProfile::new(ProfileId::generate());
+        profile.email = Some(enc("legacy@example.com"));
+        profile.social_media =
+            Some(EncryptedField::encrypt(&vec!["@jane".to_string()], &old_key).expect("encrypt"));
+        profile.email_addresses = vec![EmailAddress {
+            email: enc("Jane@Example.com"),
+            email_normalized: Some(enc("jane@example.com")),
+            email_type: EmailType::Personal,
+        }];
+        profile.previous_addresses_v2 = vec![PreviousAddress {
+            address_line1: enc("456 Oak"),
+            address_line2: Some(enc("Unit 2")),
+            city: enc("Seattle"),
+            state: enc("WA"),
+            zip_code: enc("98101"),
+            lived_from: Some("2020-01-01".to_string()),
+            lived_to: None,
+        }];
+        profile.aliases = vec![Alias {
+            first_name: None,
+            middle_name: None,
+            last_name: None,
+            nickname: Some(enc("JJ")),
+        }];
+
+        let rekeyed = profile.reencrypt(&old_key, &new_key).expect("reencrypt");
+
+        let email = &rekeyed.email_addresses[0];
+        assert_eq!(
+            email.email.decrypt(&new_key).expect("decrypt"),
+            "Jane@Example.com"
+        );
+        assert!(email.email.decrypt(&old_key).is_err());
+        assert_eq!(email.email_type, EmailType::Personal);
+
+        let address = &rekeyed.previous_addresses_v2[0];
+        assert_eq!(address.city.decrypt(&new_key).expect("decrypt"), "Seattle");
+        assert!(address.zip_code.decrypt(&old_key).is_err());
+        assert_eq!(
+            address
+                .address_line2
+                .as_ref()
+                .expect("line 2")
+                .decrypt(&new_key)
+                .expect("decrypt"),
+            "Unit 2"
+        );
+        assert_eq!(address.lived_from.as_deref(), Some("2020-01-01"));
+
+        let nickname = rekeyed.aliases[0].nickname.as_ref().expect("nickname");
+        assert_eq!(nickname.decrypt(&new_key).expect("decrypt"), "JJ");
+        assert!(nickname.decrypt(&old_key).is_err());
+
+        let social = rekeyed.social_media.as_ref().expect("social media");
+        assert_eq!(social.decrypt(&new_key).expect("decrypt"), vec!["@jane"]);
+        assert!(rekeyed
+            .email
+            .as_ref()
+            .expect("legacy email")
+            .decrypt(&old_key)
+            .is_err());
+    }
+
     #[test]
     #[allow(deprecated)]
     fn test_profile_with_encrypted_fields() {
@@ -740,6 +1299,146 @@ mod tests {
         }
     }
 
+    async fn per_field_profile(db: &Database, key: &[u8; 32]) -> UserProfile {
+        let mut profile = UserProfile::new(ProfileId::generate());
+        profile.first_name = Some(encrypt_string("Jane", key).expect("encrypt"));
+        profile.last_name = Some(encrypt_string("Doe", key).expect("encrypt"));
+        profile.city = Some(encrypt_string("Springfield", key).expect("encrypt"));
+        profile
+            .save_as(db, key, ProfileStorage::PerField)
+            .await
+            .expect("save profile");
+        profile
+    }
+
+    #[tokio::test]
+    async fn test_per_field_profile_round_trip() {
+        let key = test_key();
+        let db = Database::new(":memory:", key.to_vec())
+            .await
+            .expect("create database");
+        db.run_migrations().await.expect("run migrations");
+
+        let profile = per_field_profile(&db, &key).await;
+        assert_eq!(
+            UserProfile::storage(&db, &profile.id)
+                .await
+                .expect("storage"),
+            ProfileStorage::PerField
+        );
+
+        let loaded = UserProfile::load(&db, &profile.id, &key)
+            .await
+            .expect("load profile");
+        assert_eq!(loaded.id, profile.id);
+        assert_eq!(loaded.created_at, profile.created_at);
+        assert_eq!(loaded.updated_at, profile.updated_at);
+        let decrypt = |field: &Option<EncryptedField<String>>| {
+            field.as_ref().map(|f| f.decrypt(&key).expect("decrypt"))
+        };
+        assert_eq!(decrypt(&loaded.first_name).as_deref(), Some("Jane"));
+        assert_eq!(decrypt(&loaded.last_name).as_deref(), Some("Doe"));
+        assert_eq!(decrypt(&loaded.city).as_deref(), Some("Springfield"));
+        assert!(loaded.middle_name.is_none());
+
+        // A plain save keeps the per-field layout
+        loaded.save(&db, &key).await.expect("save profile");
+        assert_eq!(
+            UserProfile::storage(&db, &profile.id)
+                .await
+                .expect("storage"),
+            ProfileStorage::PerField
+        );
+    }
+
+    #[tokio::test]
+    async fn test_per_field_corruption_is_contained() {
+        let key = test_key();
+        let db = Database::new(":memory:", key.to_vec())
+            .await
+            .expect("create database");
+        db.run_migrations().await.expect("run migrations");
+
+        let profile = per_field_profile(&db, &key).await;
+        sqlx::query(
+            "UPDATE profile_fields SET data = zeroblob(length(data))
+             WHERE profile_id = ? AND field = 'last_name'",
+        )
+        .bind(profile.id.as_str())
+        .execute(db.pool())
+        .await
+        .expect("corrupt field");
+
+        let first_name: EncryptedField<String> =
+            UserProfile::load_field(&db, &profile.id, &key, "first_name")
+                .await
+                .expect("load first name")
+                .expect("first name present");
+        assert_eq!(first_name.decrypt(&key).expect("decrypt"), "Jane");
+
+        let missing: Option<EncryptedField<String>> =
+            UserProfile::load_field(&db, &profile.id, &key, "middle_name")
+                .await
+                .expect("load middle name");
+        assert!(missing.is_none());
+
+        let last_name =
+            UserProfile::load_field::<EncryptedField<String>>(&db, &profile.id, &key, "last_name")
+                .await;
+        assert!(matches!(last_name, Err(VaultError::Decryption(_))));
+
+        let (loaded, dropped) = UserProfile::load_lossy(&db, &profile.id, &key)
+            .await
+            .expect("load profile");
+        assert_eq!(dropped, vec!["last_name".to_string()]);
+        assert!(loaded.last_name.is_none());
+        assert_eq!(
+            loaded
+                .first_name
+                .expect("first name present")
+                .decrypt(&key)
+                .expect("decrypt"),
+            "Jane"
+        );
+
+        let loaded = UserProfile::load(&db, &profile.id, &key)
+            .await
+            .expect("load profile");
+        assert!(loaded.last_name.is_none());
+
+        let mut conn = db.pool().acquire().await.expect("acquire");
+        assert!(matches!(
+            UserProfile::read(&mut conn, profile.id.as_str(), &key).await,
+            Err(VaultError::Decryption(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_load_field_from_blob_profile() {
+        let key = test_key();
+        let db = Database::new(":memory:", key.to_vec())
+            .await
+            .expect("create database");
+        db.run_migrations().await.expect("run migrations");
+
+        let mut profile = UserProfile::new(ProfileId::generate());
+        profile.first_name = Some(encrypt_string("Jane", &key).expect("encrypt"));
+        profile.save(&db, &key).await.expect("save profile");
+
+        assert_eq!(
+            UserProfile::storage(&db, &profile.id)
+                .await
+                .expect("storage"),
+            ProfileStorage::Blob
+        );
+        let first_name: EncryptedField<String> =
+            UserProfile::load_field(&db, &profile.id, &key, "first_name")
+                .await
+                .expect("load first name")
+                .expect("first name present");
+        assert_eq!(first_name.decrypt(&key).expect("decrypt"), "Jane");
+    }
+
     #[test]
     fn test_touch_updates_timestamp() {
         let mut profile = UserProfile::new(ProfileId::generate());
@@ -910,6 +1609,29 @@ mod tests {
         assert_eq!(completeness.score, 40); // 15+15+10
     }
 
+    #[test]
+    #[allow(deprecated)]
+    fn test_completeness_uk_profile_not_penalized_for_missing_state() {
+        let key = test_key();
+        let mut profile = UserProfile::new(ProfileId::generate());
+
+        profile.first_name = Some(encrypt_string("Jane", &key).expect("encrypt"));
+        profile.last_name = Some(encrypt_string("Smith", &key).expect("encrypt"));
+        profile.email = Some(encrypt_string("jane@example.co.uk", &key).expect("encrypt"));
+        profile.address = Some(encrypt_string("10 Downing Street", &key).expect("encrypt"));
+        profile.city = Some(encrypt_string("London", &key).expect("encrypt"));
+        profile.zip_code = Some(encrypt_string("SW1A 2AA", &key).expect("encrypt"));
+        profile.country = Some(encrypt_string("GB", &key).expect("encrypt"));
+
+        let uk = profile.completeness_score_for_country(Some("GB"));
+        assert_eq!(uk.score, 70); // 15+15+10 + 10+10+10
+
+        // The same profile scored as a US address is missing its state
+        let us = profile.completeness_score_for_country(Some("US"));
+        assert_eq!(us.score, 60);
+        assert_eq!(profile.completeness_score().score, 60);
+    }
+
     #[test]
     #[allow(deprecated)]
     fn test_completeness_tier_excellent() {
@@ -0,0 +1,294 @@
+//! Batched, resumable re-encryption of the vault under a new key.
+//!
+//! Changing the password, rotating the salt or upgrading the KDF moves every
+//! profile and attachment to a new key. Large vaults are moved in batches,
+//! each in its own transaction, so a rekey does not hold one write
+//! transaction for minutes and can be paced with [`RekeyOptions`]. Progress
+//! is kept in the single `rekey_progress` row, together with the new key
+//! encrypted under the old one.
+//!
+//! Secrets kept in settings and the verification token move in the last
+//! transaction, which also deletes the progress row. Until then the vault
+//! unlocks with the old key only, and unlocking finishes the interrupted
+//! rekey from the stored cursors; after it, the vault unlocks with the new
+//! key only.
+
+use crate::attachment;
+use crate::cipher::{decrypt_blob, encrypt_blob, encrypt_string, EncryptedBlob};
+use crate::error::{Result, VaultError};
+use crate::profile::{ProfileStorage, UserProfile};
+use crate::secret;
+use crate::VERIFICATION_TOKEN;
+use spectral_core::types::Timestamp;
+use spectral_db::Database;
+use std::time::Duration;
+use zeroize::Zeroizing;
+
+/// Associated data binding the wrapped key to the `rekey_progress` row.
+const WRAPPED_KEY_AAD: &[u8] = b"rekey_progress:new_key";
+
+/// How a rekey paces its work.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RekeyOptions {
+    /// Profiles or attachments re-encrypted per transaction
+    pub batch_size: u32,
+    /// Pause between batches, leaving the database to other work
+    pub batch_pause: Duration,
+}
+
+impl Default for RekeyOptions {
+    fn default() -> Self {
+        Self {
+            batch_size: 100,
+            batch_pause: Duration::from_millis(10),
+        }
+    }
+}
+
+/// State of an unfinished rekey, as stored in `rekey_progress`.
+struct Progress {
+    audit_event: String,
+    profile_cursor: Option<String>,
+    attachment_cursor: Option<String>,
+    profiles_done: i64,
+    profiles_total: i64,
+}
+
+/// Check that every profile, attachment and secret decrypts under `key`.
+///
+/// Run before a rekey starts: a row that cannot be decrypted would otherwise
+/// fail a batch after earlier batches had committed, leaving a rekey that
+/// can never finish.
+pub(crate) async fn check_decryptable(db: &Database, key: &[u8; 32], page_size: u32) -> Result<()> {
+    let mut conn = db.pool().acquire().await?;
+
+    let mut after = String::new();
+    loop {
+        let ids = sqlx::query_scalar::<_, String>(
+            "SELECT id FROM profiles
+             WHERE id != '__vault_verification__' AND id > ?
+             ORDER BY id LIMIT ?",
+        )
+        .bind(&after)
+        .bind(page_size)
+        .fetch_all(&mut *conn)
+        .await?;
+        let Some(last) = ids.last() else {
+            break;
+        };
+        after.clone_from(last);
+
+        for id in &ids {
+            // Re-encrypting under the same key decrypts every field
+            UserProfile::read(&mut conn, id, key)
+                .await?
+                .reencrypt(key, key)?;
+        }
+    }
+
+    attachment::check_all(&mut conn, key, page_size).await?;
+    secret::check_all(&mut conn, key).await
+}
+
+/// Record the start of a rekey from `old_key` to `new_key`.
+///
+/// # Errors
+/// Returns `VaultError::InvalidData` if another rekey is already in progress.
+pub(crate) async fn begin(
+    db: &Database,
+    old_key: &[u8; 32],
+    new_key: &[u8; 32],
+    audit_event: &str,
+) -> Result<()> {
+    let wrapped = encrypt_blob(new_key, old_key, WRAPPED_KEY_AAD)?;
+    let (total,): (i64,) =
+        sqlx::query_as("SELECT COUNT(*) FROM profiles WHERE id != '__vault_verification__'")
+            .fetch_one(db.pool())
+            .await?;
+
+    sqlx::query(
+        "INSERT INTO rekey_progress (id, wrapped_key, audit_event, profiles_total, started_at)
+         VALUES (1, ?, ?, ?, ?)",
+    )
+    .bind(wrapped.to_bytes())
+    .bind(audit_event)
+    .bind(total)
+    .bind(Timestamp::now().to_rfc3339())
+    .execute(db.pool())
+    .await
+    .map_err(|e| match e {
+        sqlx::Error::Database(ref db_err) if db_err.is_unique_violation() => {
+            VaultError::InvalidData("a vault rekey is already in progress".to_string())
+        }
+        e => e.into(),
+    })?;
+
+    Ok(())
+}
+
+/// The new key of an unfinished rekey, unwrapped with `old_key`, or `None`
+/// if no rekey is in progress.
+pub(crate) async fn pending_key(
+    db: &Database,
+    old_key: &[u8; 32],
+) -> Result<Option<Zeroizing<[u8; 32]>>> {
+    let Some(wrapped) =
+        sqlx::query_scalar::<_, Vec<u8>>("SELECT wrapped_key FROM rekey_progress WHERE id = 1")
+            .fetch_optional(db.pool())
+            .await?
+    else {
+        return Ok(None);
+    };
+
+    let key = decrypt_blob(
+        &EncryptedBlob::from_bytes(&wrapped)?,
+        old_key,
+        WRAPPED_KEY_AAD,
+    )?;
+    let key: [u8; 32] = key
+        .as_slice()
+        .try_into()
+        .map_err(|_| VaultError::InvalidData("invalid rekey key length".to_string()))?;
+    Ok(Some(Zeroizing::new(key)))
+}
+
+/// Re-encrypt whatever the rekey in progress has not yet moved, then switch
+/// the secrets and the verification token to `new_key` and record the audit
+/// event.
+///
+/// Profiles and then attachments are moved `options.batch_size` at a time
+/// in ID order; each batch commits with its cursor, so an interrupted run
+/// picks up after the last committed batch. `on_progress` receives the
+/// number of profiles moved so far, including those moved by earlier runs,
+/// and the total.
+pub(crate) async fn run(
+    db: &Database,
+    vault_id: &str,
+    old_key: &[u8; 32],
+    new_key: &[u8; 32],
+    options: RekeyOptions,
+    on_progress: &mut impl FnMut(usize, usize),
+) -> Result<()> {
+    let mut progress = load_progress(db).await?;
+    let total = usize::try_from(progress.profiles_total).unwrap_or(0);
+    let batch_size = options.batch_size.max(1);
+
+    loop {
+        let mut tx = db.pool().begin().await?;
+        let rows = sqlx::query_as::<_, (String, i64)>(
+            "SELECT id, storage_version FROM profiles
+             WHERE id != '__vault_verification__' AND id > ?
+             ORDER BY id LIMIT ?",
+        )
+        .bind(progress.profile_cursor.as_deref().unwrap_or_default())
+        .bind(batch_size)
+        .fetch_all(&mut *tx)
+        .await?;
+        if rows.is_empty() {
+            break;
+        }
+
+        for (id, storage_version) in rows {
+            // Each profile keeps its storage layout under the new key
+            let storage = ProfileStorage::from_version(storage_version)?;
+            let profile = UserProfile::read(&mut tx, &id, old_key).await?;
+            profile
+                .reencrypt(old_key, new_key)?
+                .write(&mut tx, new_key, storage)
+                .await?;
+
+            progress.profiles_done += 1;
+            progress.profile_cursor = Some(id);
+            on_progress(usize::try_from(progress.profiles_done).unwrap_or(0), total);
+        }
+
+        sqlx::query("UPDATE rekey_progress SET profile_cursor = ?, profiles_done = ? WHERE id = 1")
+            .bind(&progress.profile_cursor)
+            .bind(progress.profiles_done)
+            .execute(&mut *tx)
+            .await?;
+        tx.commit().await?;
+        pause(options).await;
+    }
+
+    loop {
+        let mut tx = db.pool().begin().await?;
+        let Some(last) = attachment::reencrypt_page(
+            &mut tx,
+            old_key,
+            new_key,
+            progress.attachment_cursor.as_deref(),
+            batch_size,
+        )
+        .await?
+        else {
+            break;
+        };
+
+        sqlx::query("UPDATE rekey_progress SET attachment_cursor = ? WHERE id = 1")
+            .bind(&last)
+            .execute(&mut *tx)
+            .await?;
+        tx.commit().await?;
+        progress.attachment_cursor = Some(last);
+        pause(options).await;
+    }
+
+    let mut tx = db.pool().begin().await?;
+
+    secret::reencrypt_all(&mut tx, old_key, new_key).await?;
+
+    let token = encrypt_string(VERIFICATION_TOKEN, new_key)?;
+    sqlx::query(
+        "UPDATE profiles SET data = ?, nonce = ?, updated_at = ?
+         WHERE id = '__vault_verification__'",
+    )
+    .bind(token.ciphertext())
+    .bind(&token.nonce()[..])
+    .bind(Timestamp::now().to_rfc3339())
+    .execute(&mut *tx)
+    .await?;
+
+    sqlx::query(
+        "INSERT INTO audit_log (id, vault_id, timestamp, event_type, subject, data_destination, outcome)
+         VALUES (?, ?, ?, ?, 'vault', 'LocalOnly', 'Allowed')",
+    )
+    .bind(uuid::Uuid::new_v4().to_string())
+    .bind(vault_id)
+    .bind(Timestamp::now().to_rfc3339())
+    .bind(&progress.audit_event)
+    .execute(&mut *tx)
+    .await?;
+
+    sqlx::query("DELETE FROM rekey_progress WHERE id = 1")
+        .execute(&mut *tx)
+        .await?;
+
+    tx.commit().await?;
+    Ok(())
+}
+
+async fn load_progress(db: &Database) -> Result<Progress> {
+    let (audit_event, profile_cursor, attachment_cursor, profiles_done, profiles_total) =
+        sqlx::query_as::<_, (String, Option<String>, Option<String>, i64, i64)>(
+            "SELECT audit_event, profile_cursor, attachment_cursor, profiles_done, profiles_total
+             FROM rekey_progress WHERE id = 1",
+        )
+        .fetch_optional(db.pool())
+        .await?
+        .ok_or_else(|| VaultError::InvalidData("no vault rekey in progress".to_string()))?;
+
+    Ok(Progress {
+        audit_event,
+        profile_cursor,
+        attachment_cursor,
+        profiles_done,
+        profiles_total,
+    })
+}
+
+async fn pause(options: RekeyOptions) {
+    if !options.batch_pause.is_zero() {
+        tokio::time::sleep(options.batch_pause).await;
+    }
+}
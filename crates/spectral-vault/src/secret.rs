@@ -0,0 +1,103 @@
+//! Small secrets, such as mail server passwords, kept in the settings table.
+//!
+//! Settings are stored as plain JSON, so a secret is encrypted under the
+//! vault key before it is written, under its name prefixed with
+//! `secret.`. The prefix is how a rekey finds every secret to move it to the
+//! new key.
+
+use crate::cipher::{decrypt_blob, encrypt_blob, EncryptedBlob};
+use crate::error::{Result, VaultError};
+use spectral_db::Database;
+use sqlx::SqliteConnection;
+use zeroize::Zeroizing;
+
+/// Prefix of the settings keys holding secrets.
+const SETTINGS_PREFIX: &str = "secret.";
+
+/// Settings key holding the secret `name`.
+fn settings_key(name: &str) -> String {
+    format!("{SETTINGS_PREFIX}{name}")
+}
+
+/// Associated data binding an encrypted secret to its settings key.
+fn secret_aad(settings_key: &str) -> Vec<u8> {
+    format!("setting:{settings_key}").into_bytes()
+}
+
+/// Encrypt and store the secret `name`, replacing any previous value.
+pub(crate) async fn store(db: &Database, key: &[u8; 32], name: &str, secret: &str) -> Result<()> {
+    let settings_key = settings_key(name);
+    let blob = encrypt_blob(secret.as_bytes(), key, &secret_aad(&settings_key))?;
+    spectral_db::settings::set_setting(db.pool(), &settings_key, &serde_json::to_value(blob)?)
+        .await?;
+    Ok(())
+}
+
+/// Load and decrypt the secret `name`, if one is stored.
+pub(crate) async fn load(
+    db: &Database,
+    key: &[u8; 32],
+    name: &str,
+) -> Result<Option<Zeroizing<String>>> {
+    let settings_key = settings_key(name);
+    let Some(value) = spectral_db::settings::get_setting(db.pool(), &settings_key).await? else {
+        return Ok(None);
+    };
+    let blob: EncryptedBlob = serde_json::from_value(value)?;
+
+    let secret = decrypt_blob(&blob, key, &secret_aad(&settings_key))?;
+    let secret = String::from_utf8(secret.to_vec())
+        .map_err(|_| VaultError::InvalidData(format!("secret {name} is not UTF-8")))?;
+    Ok(Some(Zeroizing::new(secret)))
+}
+
+/// Delete the secret `name`, if one is stored.
+pub(crate) async fn delete(db: &Database, name: &str) -> Result<()> {
+    spectral_db::settings::delete_setting(db.pool(), &settings_key(name)).await?;
+    Ok(())
+}
+
+/// Every stored secret's settings key and encrypted value.
+async fn fetch_all(conn: &mut SqliteConnection) -> Result<Vec<(String, EncryptedBlob)>> {
+    let rows = sqlx::query_as::<_, (String, String)>(
+        "SELECT key, value FROM settings WHERE substr(key, 1, ?) = ? ORDER BY key",
+    )
+    .bind(i64::try_from(SETTINGS_PREFIX.len()).unwrap_or(i64::MAX))
+    .bind(SETTINGS_PREFIX)
+    .fetch_all(conn)
+    .await?;
+
+    rows.into_iter()
+        .map(|(settings_key, value)| Ok((settings_key, serde_json::from_str(&value)?)))
+        .collect()
+}
+
+/// Check that every stored secret decrypts under `key`.
+pub(crate) async fn check_all(conn: &mut SqliteConnection, key: &[u8; 32]) -> Result<()> {
+    for (settings_key, blob) in fetch_all(conn).await? {
+        decrypt_blob(&blob, key, &secret_aad(&settings_key))?;
+    }
+    Ok(())
+}
+
+/// Re-encrypt every stored secret from `old_key` to `new_key`.
+///
+/// Secrets are few and small, so they all move in the caller's transaction.
+pub(crate) async fn reencrypt_all(
+    conn: &mut SqliteConnection,
+    old_key: &[u8; 32],
+    new_key: &[u8; 32],
+) -> Result<()> {
+    for (settings_key, blob) in fetch_all(conn).await? {
+        let aad = secret_aad(&settings_key);
+        let secret = decrypt_blob(&blob, old_key, &aad)?;
+        let value = serde_json::to_string(&encrypt_blob(&secret, new_key, &aad)?)?;
+
+        sqlx::query("UPDATE settings SET value = ?, updated_at = datetime('now') WHERE key = ?")
+            .bind(value)
+            .bind(&settings_key)
+            .execute(&mut *conn)
+            .await?;
+    }
+    Ok(())
+}
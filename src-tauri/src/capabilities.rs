@@ -0,0 +1,142 @@
+//! Runtime capability probes.
+//!
+//! Registers the checks behind features that depend on the machine or the
+//! user's settings, so the UI can disable them and say why.
+
+use spectral_core::{AppConfig, CapabilityRegistry, FeatureId};
+use spectral_vault::Vault;
+use std::collections::HashMap;
+use std::sync::{Arc, RwLock};
+
+/// Vaults currently unlocked, shared with [`crate::state::AppState`].
+pub type UnlockedVaults = Arc<RwLock<HashMap<String, Arc<Vault>>>>;
+
+/// Build the capability registry with probes for Ollama, the browser binary
+/// and SMTP settings.
+pub fn build_registry(
+    config: Arc<RwLock<AppConfig>>,
+    vaults: UnlockedVaults,
+) -> CapabilityRegistry {
+    let mut registry = CapabilityRegistry::new();
+    registry.enable_feature(FeatureId::BrowserAutomation);
+    registry.enable_feature(FeatureId::EmailSending);
+    registry.enable_llm_features();
+
+    for feature in FeatureId::llm_features() {
+        let config = Arc::clone(&config);
+        registry.register_probe(*feature, move || {
+            let llm = config
+                .read()
+                .expect("RwLock poisoned: another thread panicked while holding the lock")
+                .llm
+                .clone();
+            check_llm(llm)
+        });
+    }
+    registry.register_probe(FeatureId::BrowserAutomation, || async {
+        spectral_browser::BrowserEngine::find_executable()
+            .map(|_| ())
+            .map_err(|e| e.to_string())
+    });
+    registry.register_probe(FeatureId::EmailSending, move || {
+        let vaults: Vec<Arc<Vault>> = vaults
+            .read()
+            .expect("RwLock poisoned: another thread panicked while holding the lock")
+            .values()
+            .cloned()
+            .collect();
+        async move { check_smtp(&vaults).await }
+    });
+
+    registry
+}
+
+/// LLM features need them turned on, and a running Ollama with a model
+/// installed when Ollama is the provider.
+async fn check_llm(llm: spectral_core::LlmConfig) -> Result<(), String> {
+    if !llm.enabled {
+        return Err("LLM features are turned off in settings".to_string());
+    }
+    if llm.default_provider != "ollama" {
+        return Ok(());
+    }
+
+    spectral_llm::OllamaProvider::discover_at(llm.ollama_url.clone())
+        .await
+        .map(|_| ())
+        .map_err(|e| format!("Ollama is not available at {}: {}", llm.ollama_url, e))
+}
+
+/// Removal emails are sent over SMTP only once an unlocked vault has SMTP
+/// settings; otherwise they open in the user's mail client.
+async fn check_smtp(vaults: &[Arc<Vault>]) -> Result<(), String> {
+    if vaults.is_empty() {
+        return Err("Unlock a vault to check its SMTP settings".to_string());
+    }
+
+    for vault in vaults {
+        let Ok(db) = vault.database() else {
+            continue;
+        };
+        if let Ok(Some(_)) = spectral_db::settings::get_setting(
+            db.pool(),
+            spectral_mail::delivery::SMTP_SETTINGS_KEY,
+        )
+        .await
+        {
+            return Ok(());
+        }
+    }
+
+    Err("No SMTP server is configured; removal emails open in your mail client".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use spectral_core::FeatureStatus;
+
+    #[tokio::test]
+    async fn test_unconfigured_smtp_reports_reason() {
+        let temp_dir = tempfile::tempdir().expect("create temp dir");
+        let vault = Vault::create("test-password-123", temp_dir.path().join("vault.db"))
+            .await
+            .expect("create vault");
+        let vaults: UnlockedVaults = Arc::new(RwLock::new(HashMap::from([(
+            "vault-1".to_string(),
+            Arc::new(vault),
+        )])));
+
+        let registry = build_registry(Arc::new(RwLock::new(AppConfig::default())), vaults);
+        let status = registry.check_feature(FeatureId::EmailSending).await;
+
+        assert!(!status.is_available());
+        assert_eq!(
+            status,
+            FeatureStatus::Unavailable {
+                reason: "No SMTP server is configured; removal emails open in your mail client"
+                    .to_string()
+            }
+        );
+        assert_eq!(
+            registry
+                .unavailable_reason(FeatureId::EmailSending)
+                .as_deref(),
+            status.reason()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_disabled_llm_reports_reason() {
+        let registry = build_registry(
+            Arc::new(RwLock::new(AppConfig::default())),
+            Arc::new(RwLock::new(HashMap::new())),
+        );
+
+        let status = registry.check_feature(FeatureId::LlmChat).await;
+        assert_eq!(
+            status.reason(),
+            Some("LLM features are turned off in settings")
+        );
+    }
+}
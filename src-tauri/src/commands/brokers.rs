@@ -116,8 +116,8 @@ pub async fn get_broker_detail(
 mod tests {
     use super::*;
     use spectral_broker::definition::{
-        BrokerCategory, BrokerMetadata, ConfirmationType, FormSelectors, RemovalDifficulty,
-        RemovalMethod,
+        BrokerCategory, BrokerMetadata, ConfirmationType, FormSelectors, RemovalConfirmation,
+        RemovalDifficulty, RemovalMethod,
     };
     use std::collections::HashMap;
 
@@ -137,6 +137,12 @@ mod tests {
                 last_verified: chrono::NaiveDate::from_ymd_opt(2025, 1, 1).expect("valid date"),
                 scan_priority: spectral_broker::ScanPriority::OnRequest,
                 region_relevance: vec!["Global".to_string()],
+                countries: vec![],
+                requires_id_verification: false,
+                requires_account: false,
+                related_brokers: vec![],
+                request_headers: HashMap::new(),
+                legal_contact: None,
             },
             search: spectral_broker::definition::SearchMethod::UrlTemplate {
                 template: "https://spokeo.com/{first}-{last}".to_string(),
@@ -158,6 +164,8 @@ mod tests {
                 confirmation: ConfirmationType::EmailVerification,
                 notes: String::new(),
             },
+            fixture: None,
+            confirmation: RemovalConfirmation::None,
         };
 
         let summary = BrokerSummary::from(&def);
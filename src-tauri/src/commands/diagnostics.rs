@@ -0,0 +1,90 @@
+//! Diagnostics commands for the operational visibility screen.
+
+use crate::error::CommandError;
+use crate::state::AppState;
+use serde::Serialize;
+use spectral_core::metrics::{self, MetricsSnapshot};
+use spectral_core::{FeatureId, FeatureStatus};
+use spectral_db::TableStat;
+use tauri::State;
+
+/// Get a snapshot of in-memory operational metrics.
+///
+/// Metrics are aggregate counts only and never contain PII.
+#[tauri::command]
+pub async fn get_metrics() -> Result<MetricsSnapshot, CommandError> {
+    Ok(metrics::global().snapshot())
+}
+
+/// Storage usage for a vault database.
+#[derive(Debug, Serialize)]
+pub struct StorageStats {
+    /// Row counts per table
+    pub tables: Vec<TableStat>,
+    /// Size of the database file in bytes
+    pub file_size_bytes: u64,
+}
+
+/// Get per-table row counts and the database size for a vault.
+///
+/// Only counts are returned; no row contents leave the database.
+#[tauri::command]
+pub async fn get_storage_stats(
+    state: State<'_, AppState>,
+    vault_id: String,
+) -> Result<StorageStats, CommandError> {
+    let vault = state
+        .get_vault(&vault_id)
+        .ok_or_else(|| CommandError::new("VAULT_LOCKED", "Vault is locked"))?;
+    let db = vault.database().map_err(|e| {
+        CommandError::new(
+            "VAULT_ERROR",
+            format!("Failed to access vault database: {e}"),
+        )
+    })?;
+
+    let tables = db.table_stats().await.map_err(|e| {
+        CommandError::new("DATABASE_ERROR", format!("Failed to read table stats: {e}"))
+    })?;
+    let file_size_bytes = db.file_size().await.map_err(|e| {
+        CommandError::new(
+            "DATABASE_ERROR",
+            format!("Failed to read database size: {e}"),
+        )
+    })?;
+
+    Ok(StorageStats {
+        tables,
+        file_size_bytes,
+    })
+}
+
+/// Whether a feature can be used right now.
+#[derive(Debug, Serialize)]
+pub struct FeatureAvailability {
+    pub feature: FeatureId,
+    pub name: &'static str,
+    /// `available`, or `unavailable` with the reason
+    #[serde(flatten)]
+    pub status: FeatureStatus,
+}
+
+/// Check which features can be used, so the UI can disable the rest.
+///
+/// Runs the capability probes whose cached results have expired.
+#[tauri::command]
+pub async fn get_capabilities(
+    state: State<'_, AppState>,
+) -> Result<Vec<FeatureAvailability>, CommandError> {
+    Ok(state
+        .capabilities
+        .check_all()
+        .await
+        .into_iter()
+        .map(|(feature, status)| FeatureAvailability {
+            feature,
+            name: feature.display_name(),
+            status,
+        })
+        .collect())
+}
@@ -1,6 +1,7 @@
 //! Tauri command handlers.
 
 pub mod brokers;
+pub mod diagnostics;
 pub mod discovery;
 pub mod llm;
 pub mod privacy;
@@ -318,6 +318,38 @@ pub async fn set_llm_api_key(
     Ok(())
 }
 
+/// Set or clear the API key for the external CAPTCHA solving service.
+///
+/// Stores the API key encrypted in the vault database. The service is only
+/// used when `captcha.external_solver_enabled` is also set in the config.
+#[tauri::command]
+pub async fn set_captcha_solver_api_key(
+    state: State<'_, AppState>,
+    vault_id: String,
+    api_key: Option<String>,
+) -> Result<(), CommandError> {
+    info!("Updating CAPTCHA solver API key in vault: {}", vault_id);
+
+    let pool = get_vault_pool(&state, &vault_id)?;
+    let key = crate::removal_worker::CAPTCHA_API_KEY_SETTING;
+
+    let result = match api_key.filter(|api_key| !api_key.is_empty()) {
+        Some(api_key) => {
+            spectral_db::settings::set_setting(&pool, key, &serde_json::Value::String(api_key))
+                .await
+        }
+        None => spectral_db::settings::delete_setting(&pool, key).await,
+    };
+    result.map_err(|e| {
+        CommandError::new(
+            "PRIVACY_ERROR",
+            format!("Failed to update CAPTCHA solver API key: {}", e),
+        )
+    })?;
+
+    Ok(())
+}
+
 /// Test connection to an LLM provider.
 ///
 /// Attempts to connect to the provider and make a simple test request.
@@ -341,8 +373,8 @@ pub async fn test_llm_provider(
     // Test the provider by attempting to create it and make a request
     match provider {
         LlmProvider::Ollama => {
-            // Try to create Ollama provider
-            let ollama = OllamaProvider::new().map_err(|e| {
+            // Discover installed models, failing clearly if there are none
+            let ollama = OllamaProvider::discover().await.map_err(|e| {
                 CommandError::new(
                     "PROVIDER_ERROR",
                     format!("Failed to create Ollama provider: {}", e),
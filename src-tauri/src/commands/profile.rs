@@ -3,6 +3,7 @@
 use crate::error::CommandError;
 use crate::state::AppState;
 use crate::types::profile::{ProfileInput, ProfileOutput, ProfileSummary};
+use spectral_core::normalize_country;
 use spectral_core::types::ProfileId;
 use spectral_vault::cipher::encrypt_string;
 use spectral_vault::UserProfile;
@@ -32,9 +33,6 @@ pub async fn profile_create(
         )
     })?;
 
-    // Get the encryption key for field-level encryption
-    let key = vault.encryption_key()?;
-
     // Create profile ID
     let profile_id = ProfileId::generate();
 
@@ -42,28 +40,7 @@ pub async fn profile_create(
     let mut profile = UserProfile::new(profile_id.clone());
 
     // Encrypt and store fields
-    profile.first_name = Some(encrypt_string(&input.first_name, key)?);
-    profile.middle_name = input
-        .middle_name
-        .as_ref()
-        .map(|s| encrypt_string(s, key))
-        .transpose()?;
-    profile.last_name = Some(encrypt_string(&input.last_name, key)?);
-    profile.email = Some(encrypt_string(&input.email, key)?);
-    profile.date_of_birth = input
-        .date_of_birth
-        .map(|d| encrypt_string(&d.to_string(), key))
-        .transpose()?;
-    // Combine address lines if address_line2 exists
-    let full_address = if let Some(ref line2) = input.address_line2 {
-        format!("{}\n{}", input.address_line1, line2)
-    } else {
-        input.address_line1.clone()
-    };
-    profile.address = Some(encrypt_string(&full_address, key)?);
-    profile.city = Some(encrypt_string(&input.city, key)?);
-    profile.state = Some(encrypt_string(&input.state, key)?);
-    profile.zip_code = Some(encrypt_string(&input.zip_code, key)?);
+    vault.with_key(|key| encrypt_input(&mut profile, &input, key))??;
 
     // Save profile
     vault.save_profile(&profile).await?;
@@ -83,8 +60,10 @@ pub async fn profile_create(
         city: input.city,
         state: input.state,
         zip_code: input.zip_code,
+        country: input.country.as_deref().map(normalize_country),
         created_at: profile.created_at.to_rfc3339(),
         updated_at: profile.updated_at.to_rfc3339(),
+        unavailable_fields: Vec::new(),
     })
 }
 
@@ -111,107 +90,28 @@ pub async fn profile_get(
     // Parse profile ID
     let id = ProfileId::new(profile_id.clone())?;
 
-    // Load profile
-    let profile = vault.load_profile(&id).await?;
-
-    // Get encryption key
-    let key = vault.encryption_key()?;
-
-    // Decrypt fields
-    let first_name = profile
-        .first_name
-        .as_ref()
-        .map(|f| f.decrypt(key))
-        .transpose()?
-        .unwrap_or_default();
-    let middle_name = profile
-        .middle_name
-        .as_ref()
-        .map(|f| f.decrypt(key))
-        .transpose()?;
-    let last_name = profile
-        .last_name
-        .as_ref()
-        .map(|f| f.decrypt(key))
-        .transpose()?
-        .unwrap_or_default();
-    let email = profile
-        .email
-        .as_ref()
-        .map(|f| f.decrypt(key))
-        .transpose()?
-        .unwrap_or_default();
-    let date_of_birth = profile
-        .date_of_birth
-        .as_ref()
-        .map(|f| f.decrypt(key))
-        .transpose()?
-        .and_then(|s: String| s.parse().ok());
-    // Decrypt and split address into two lines
-    let (address_line1, address_line2) = profile
-        .address
-        .as_ref()
-        .map(|f| f.decrypt(key))
-        .transpose()?
-        .map(|address_str: String| {
-            let address_parts: Vec<&str> = address_str.split('\n').collect();
-            let line1 = address_parts.first().unwrap_or(&"").to_string();
-            let line2 = address_parts.get(1).map(|s| s.to_string());
-            (line1, line2)
-        })
-        .unwrap_or((String::new(), None));
-    let city = profile
-        .city
-        .as_ref()
-        .map(|f| f.decrypt(key))
-        .transpose()?
-        .unwrap_or_default();
-    let state_code = profile
-        .state
-        .as_ref()
-        .map(|f| f.decrypt(key))
-        .transpose()?
-        .unwrap_or_default();
-    let zip_code = profile
-        .zip_code
-        .as_ref()
-        .map(|f| f.decrypt(key))
-        .transpose()?
-        .unwrap_or_default();
+    // Load profile, reporting any fields that could not be decrypted
+    let (profile, unavailable_fields) = vault.load_profile_lossy(&id).await?;
 
-    Ok(ProfileOutput {
-        id: profile_id,
-        first_name,
-        middle_name,
-        last_name,
-        email,
-        date_of_birth,
-        address_line1,
-        address_line2,
-        city,
-        state: state_code,
-        zip_code,
-        created_at: profile.created_at.to_rfc3339(),
-        updated_at: profile.updated_at.to_rfc3339(),
-    })
+    let mut output = vault.with_key(|key| profile_to_output(&profile, key))??;
+    output.unavailable_fields = unavailable_fields;
+    Ok(output)
 }
 
 /// Update an existing profile.
 ///
-/// Updates all fields of a profile with validated input.
+/// Updates all fields of a profile with validated input, except that a
+/// missing country leaves the stored country unchanged.
 #[allow(deprecated)]
 #[tauri::command]
 pub async fn profile_update(
     state: State<'_, AppState>,
     vault_id: String,
     profile_id: String,
-    input: ProfileInput,
+    mut input: ProfileInput,
 ) -> Result<ProfileOutput, CommandError> {
     info!("Updating profile {} in vault: {}", profile_id, vault_id);
 
-    // Validate input
-    input.validate()?;
-
     // Get vault
     let vault = state.get_vault(&vault_id).ok_or_else(|| {
         CommandError::new(
@@ -226,32 +126,18 @@ pub async fn profile_update(
     // Load existing profile
     let mut profile = vault.load_profile(&id).await?;
 
-    // Get encryption key
-    let key = vault.encryption_key()?;
+    // An update that doesn't name a country keeps the stored one, and its
+    // address is validated against that country's format
+    if input.country.is_none() {
+        input.country = vault
+            .with_key(|key| profile.country.as_ref().map(|f| f.decrypt(key)).transpose())??;
+    }
+
+    // Validate input
+    input.validate()?;
 
     // Update encrypted fields
-    profile.first_name = Some(encrypt_string(&input.first_name, key)?);
-    profile.middle_name = input
-        .middle_name
-        .as_ref()
-        .map(|s| encrypt_string(s, key))
-        .transpose()?;
-    profile.last_name = Some(encrypt_string(&input.last_name, key)?);
-    profile.email = Some(encrypt_string(&input.email, key)?);
-    profile.date_of_birth = input
-        .date_of_birth
-        .map(|d| encrypt_string(&d.to_string(), key))
-        .transpose()?;
-    // Combine address lines if address_line2 exists
-    let full_address = if let Some(ref line2) = input.address_line2 {
-        format!("{}\n{}", input.address_line1, line2)
-    } else {
-        input.address_line1.clone()
-    };
-    profile.address = Some(encrypt_string(&full_address, key)?);
-    profile.city = Some(encrypt_string(&input.city, key)?);
-    profile.state = Some(encrypt_string(&input.state, key)?);
-    profile.zip_code = Some(encrypt_string(&input.zip_code, key)?);
+    vault.with_key(|key| encrypt_input(&mut profile, &input, key))??;
 
     // Update timestamp
     profile.touch();
@@ -274,8 +160,10 @@ pub async fn profile_update(
         city: input.city,
         state: input.state,
         zip_code: input.zip_code,
+        country: input.country.as_deref().map(normalize_country),
         created_at: profile.created_at.to_rfc3339(),
         updated_at: profile.updated_at.to_rfc3339(),
+        unavailable_fields: Vec::new(),
     })
 }
 
@@ -301,44 +189,45 @@ pub async fn profile_list(
     // Get all profile IDs
     let profile_ids = vault.list_profiles().await?;
 
-    // Get encryption key
-    let key = vault.encryption_key()?;
-
     // Load and decrypt basic info for each profile
     let mut summaries = Vec::new();
 
     for id in profile_ids {
-        let profile = vault.load_profile(&id).await?;
-
-        // Decrypt first and last name for full name
-        let first_name = profile
-            .first_name
-            .as_ref()
-            .map(|f| f.decrypt(key))
-            .transpose()?
-            .unwrap_or_default();
-        let last_name = profile
-            .last_name
-            .as_ref()
-            .map(|f| f.decrypt(key))
-            .transpose()?
-            .unwrap_or_default();
-        let full_name = format!("{} {}", first_name, last_name).trim().to_string();
-
-        // Decrypt email
-        let email = profile
-            .email
-            .as_ref()
-            .map(|f| f.decrypt(key))
-            .transpose()?
-            .unwrap_or_default();
-
-        summaries.push(ProfileSummary {
-            id: id.to_string(),
-            full_name,
-            email,
-            created_at: profile.created_at.to_rfc3339(),
-        });
+        let (profile, unavailable_fields) = vault.load_profile_lossy(&id).await?;
+
+        let summary = vault.with_key(|key| -> Result<ProfileSummary, CommandError> {
+            // Decrypt first and last name for full name
+            let first_name = profile
+                .first_name
+                .as_ref()
+                .map(|f| f.decrypt(key))
+                .transpose()?
+                .unwrap_or_default();
+            let last_name = profile
+                .last_name
+                .as_ref()
+                .map(|f| f.decrypt(key))
+                .transpose()?
+                .unwrap_or_default();
+            let full_name = format!("{} {}", first_name, last_name).trim().to_string();
+
+            // Decrypt email
+            let email = profile
+                .email
+                .as_ref()
+                .map(|f| f.decrypt(key))
+                .transpose()?
+                .unwrap_or_default();
+
+            Ok(ProfileSummary {
+                id: id.to_string(),
+                full_name,
+                email,
+                created_at: profile.created_at.to_rfc3339(),
+                unavailable_fields,
+            })
+        })??;
+        summaries.push(summary);
     }
 
     info!("Found {} profiles", summaries.len());
@@ -374,8 +263,188 @@ pub async fn get_profile_completeness(
     // Load profile
     let profile = vault.load_profile(profile_id).await?;
 
+    // Score against the profile's country so non-US addresses aren't
+    // penalized for fields their country doesn't use
+    let country =
+        vault.with_key(|key| profile.country.as_ref().map(|f| f.decrypt(key)).transpose())??;
+
     // Calculate and return completeness
-    Ok(profile.completeness_score())
+    Ok(profile.completeness_score_for_country(country.as_deref()))
+}
+
+/// Encrypt the fields of `input` into `profile`.
+#[allow(deprecated)]
+fn encrypt_input(
+    profile: &mut UserProfile,
+    input: &ProfileInput,
+    key: &[u8; 32],
+) -> Result<(), CommandError> {
+    profile.first_name = Some(encrypt_string(&input.first_name, key)?);
+    profile.middle_name = input
+        .middle_name
+        .as_ref()
+        .map(|s| encrypt_string(s, key))
+        .transpose()?;
+    profile.last_name = Some(encrypt_string(&input.last_name, key)?);
+    profile.email = Some(encrypt_string(&input.email, key)?);
+    profile.date_of_birth = input
+        .date_of_birth
+        .map(|d| encrypt_string(&d.to_string(), key))
+        .transpose()?;
+    // Combine address lines if address_line2 exists
+    let full_address = if let Some(ref line2) = input.address_line2 {
+        format!("{}\n{}", input.address_line1, line2)
+    } else {
+        input.address_line1.clone()
+    };
+    profile.address = Some(encrypt_string(&full_address, key)?);
+    profile.city = Some(encrypt_string(&input.city, key)?);
+    profile.state = (!input.state.trim().is_empty())
+        .then(|| encrypt_string(&input.state, key))
+        .transpose()?;
+    profile.zip_code = Some(encrypt_string(&input.zip_code, key)?);
+    profile.country = input
+        .country
+        .as_deref()
+        .map(|c| encrypt_string(&normalize_country(c), key))
+        .transpose()?;
+    Ok(())
+}
+
+/// Decrypt a stored profile into the shape returned to the frontend.
+#[allow(deprecated)]
+fn profile_to_output(profile: &UserProfile, key: &[u8; 32]) -> Result<ProfileOutput, CommandError> {
+    // Decrypt fields
+    let first_name = profile
+        .first_name
+        .as_ref()
+        .map(|f| f.decrypt(key))
+        .transpose()?
+        .unwrap_or_default();
+    let middle_name = profile
+        .middle_name
+        .as_ref()
+        .map(|f| f.decrypt(key))
+        .transpose()?;
+    let last_name = profile
+        .last_name
+        .as_ref()
+        .map(|f| f.decrypt(key))
+        .transpose()?
+        .unwrap_or_default();
+    let email = profile
+        .email
+        .as_ref()
+        .map(|f| f.decrypt(key))
+        .transpose()?
+        .unwrap_or_default();
+    let date_of_birth = profile
+        .date_of_birth
+        .as_ref()
+        .map(|f| f.decrypt(key))
+        .transpose()?
+        .and_then(|s: String| s.parse().ok());
+    // Decrypt and split address into two lines
+    let (address_line1, address_line2) = profile
+        .address
+        .as_ref()
+        .map(|f| f.decrypt(key))
+        .transpose()?
+        .map(|address_str: String| {
+            let address_parts: Vec<&str> = address_str.split('\n').collect();
+            let line1 = address_parts.first().unwrap_or(&"").to_string();
+            let line2 = address_parts.get(1).map(|s| s.to_string());
+            (line1, line2)
+        })
+        .unwrap_or((String::new(), None));
+    let city = profile
+        .city
+        .as_ref()
+        .map(|f| f.decrypt(key))
+        .transpose()?
+        .unwrap_or_default();
+    let state_code = profile
+        .state
+        .as_ref()
+        .map(|f| f.decrypt(key))
+        .transpose()?
+        .unwrap_or_default();
+    let zip_code = profile
+        .zip_code
+        .as_ref()
+        .map(|f| f.decrypt(key))
+        .transpose()?
+        .unwrap_or_default();
+    let country = profile
+        .country
+        .as_ref()
+        .map(|f| f.decrypt(key))
+        .transpose()?;
+
+    Ok(ProfileOutput {
+        id: profile.id.to_string(),
+        first_name,
+        middle_name,
+        last_name,
+        email,
+        date_of_birth,
+        address_line1,
+        address_line2,
+        city,
+        state: state_code,
+        zip_code,
+        country,
+        created_at: profile.created_at.to_rfc3339(),
+        updated_at: profile.updated_at.to_rfc3339(),
+        unavailable_fields: Vec::new(),
+    })
+}
+
+/// Import a new profile from a vCard or JSON document.
+///
+/// `format` is `"vcard"` or `"json"`. Unknown properties are skipped;
+/// values that cannot be parsed are listed in the error details.
+#[allow(deprecated)]
+#[tauri::command]
+pub async fn profile_import(
+    state: State<'_, AppState>,
+    vault_id: String,
+    format: String,
+    data: String,
+) -> Result<ProfileOutput, CommandError> {
+    info!("Importing {} profile into vault: {}", format, vault_id);
+
+    // Get vault
+    let vault = state.get_vault(&vault_id).ok_or_else(|| {
+        CommandError::new(
+            "VAULT_NOT_UNLOCKED",
+            format!("Vault '{}' is not unlocked", vault_id),
+        )
+    })?;
+
+    let mut profile = vault.with_key(|key| -> Result<UserProfile, CommandError> {
+        match format.to_lowercase().as_str() {
+            "vcard" | "vcf" => Ok(UserProfile::from_vcard(&data, key)?),
+            "json" => Ok(UserProfile::from_json(&data, key)?),
+            other => Err(CommandError::new(
+                "UNSUPPORTED_FORMAT",
+                format!("Unsupported import format '{other}', expected 'vcard' or 'json'"),
+            )),
+        }
+    })??;
+
+    // The profile form still reads the single-email field
+    profile.email = profile
+        .email_addresses
+        .first()
+        .map(|email| email.email.clone());
+
+    // Save profile
+    vault.save_profile(&profile).await?;
+
+    info!("Profile imported: {}", profile.id);
+
+    vault.with_key(|key| profile_to_output(&profile, key))?
 }
 
 #[cfg(test)]
@@ -394,6 +463,7 @@ mod tests {
         let _get = profile_get;
         let _update = profile_update;
         let _list = profile_list;
+        let _import = profile_import;
     }
 
     #[test]
@@ -409,6 +479,7 @@ mod tests {
             city: "San Francisco".to_string(),
             state: "CA".to_string(),
             zip_code: "94102".to_string(),
+            country: None,
         };
 
         // Validation should fail
@@ -427,6 +498,7 @@ mod tests {
             city: "San Francisco".to_string(),
             state: "CA".to_string(),
             zip_code: "94102".to_string(),
+            country: None,
         };
 
         // Validation should pass
@@ -1,11 +1,27 @@
 //! Removal submission commands.
 
 use crate::error::CommandError;
+use crate::removal_worker::map_fields_for_submission;
 use crate::state::AppState;
+use serde::Serialize;
 use spectral_broker::removal::RemovalOutcome;
+use spectral_core::types::{BrokerId, ProfileId};
+use spectral_db::legal_requests::{LegalRequest, LegalRequestStatus};
+use spectral_vault::Vault;
 use tauri::{Emitter, State};
 use tracing::{info, warn};
 
+/// A legal request waiting for review, with its text rendered from the
+/// profile it was drafted for.
+#[derive(Debug, Serialize)]
+pub struct LegalRequestDraft {
+    #[serde(flatten)]
+    pub request: LegalRequest,
+    pub broker_name: String,
+    pub subject: String,
+    pub body: String,
+}
+
 /// Submit a removal request for a search result.
 ///
 /// Note: This is a legacy stub command. The new workflow uses
@@ -72,6 +88,11 @@ pub async fn mark_attempt_verified(
     .await
     .map_err(|e| format!("Failed to update status: {}", e))?;
 
+    db.notify(spectral_db::DbChange::RemovalStatusChanged {
+        removal_attempt_id: attempt_id.clone(),
+        status: spectral_db::removal_attempts::RemovalStatus::Completed,
+    });
+
     // Emit removal:verified event
     app_handle
         .emit(
@@ -86,3 +107,99 @@ pub async fn mark_attempt_verified(
     info!("Marked attempt {} as verified", attempt_id);
     Ok(())
 }
+
+/// Get the legal requests waiting for review, oldest first.
+///
+/// Only their metadata is stored, so each draft's text is rendered here from
+/// the profile of the removal attempt it was drafted for. Drafts that can no
+/// longer be rendered, e.g. because the profile was deleted, are left out.
+#[tauri::command]
+pub async fn get_legal_review_queue(
+    state: State<'_, AppState>,
+    vault_id: String,
+) -> Result<Vec<LegalRequestDraft>, String> {
+    info!("get_legal_review_queue: vault_id={}", vault_id);
+    let vault = state.get_vault(&vault_id).ok_or("Vault not unlocked")?;
+    let db = vault.database().map_err(|e| e.to_string())?;
+
+    let queue = spectral_db::legal_requests::get_review_queue(db.pool())
+        .await
+        .map_err(|e| format!("Failed to get legal review queue: {}", e))?;
+
+    let mut drafts = Vec::with_capacity(queue.len());
+    for request in queue {
+        match render_legal_draft(&state, &vault, request).await {
+            Ok(draft) => drafts.push(draft),
+            Err(e) => warn!("Skipping legal request that cannot be rendered: {}", e),
+        }
+    }
+    Ok(drafts)
+}
+
+/// Render a stored legal request from the profile it was drafted for.
+async fn render_legal_draft(
+    state: &AppState,
+    vault: &Vault,
+    request: LegalRequest,
+) -> Result<LegalRequestDraft, String> {
+    let db = vault.database().map_err(|e| e.to_string())?;
+
+    let attempt = spectral_db::removal_attempts::get_by_id(db.pool(), &request.attempt_id)
+        .await
+        .map_err(|e| e.to_string())?
+        .ok_or("Removal attempt not found")?;
+    let finding = spectral_db::findings::get_by_id(db.pool(), &attempt.finding_id)
+        .await
+        .map_err(|e| e.to_string())?
+        .ok_or("Finding not found")?;
+    let profile_id = ProfileId::new(&finding.profile_id).map_err(|e| e.to_string())?;
+    let profile = vault
+        .load_profile(&profile_id)
+        .await
+        .map_err(|e| format!("Failed to load profile: {}", e))?;
+
+    // A broker dropped from the registry is still named by its ID
+    let broker_name = BrokerId::new(&request.broker_id)
+        .ok()
+        .and_then(|id| state.broker_registry.get(&id).ok())
+        .map_or_else(
+            || request.broker_id.clone(),
+            |broker| broker.name().to_string(),
+        );
+
+    let fields = vault
+        .with_key(|key| map_fields_for_submission(&profile, &finding.listing_url, key))
+        .map_err(|e| format!("Failed to get vault key: {}", e))??;
+    let email = spectral_mail::legal::render_draft(&request, &broker_name, &fields)?;
+
+    Ok(LegalRequestDraft {
+        request,
+        broker_name,
+        subject: email.subject,
+        body: email.body,
+    })
+}
+
+/// Record that the user sent or discarded a legal request.
+#[tauri::command]
+pub async fn update_legal_request_status(
+    state: State<'_, AppState>,
+    vault_id: String,
+    request_id: String,
+    status: LegalRequestStatus,
+) -> Result<(), String> {
+    info!(
+        "update_legal_request_status: request_id={}, status={}",
+        request_id, status
+    );
+    if status == LegalRequestStatus::Draft {
+        return Err("A legal request can only be marked sent or discarded".to_string());
+    }
+
+    let vault = state.get_vault(&vault_id).ok_or("Vault not unlocked")?;
+    let db = vault.database().map_err(|e| e.to_string())?;
+
+    spectral_db::legal_requests::update_status(db.pool(), &request_id, status)
+        .await
+        .map_err(|e| format!("Failed to update legal request: {}", e))
+}
@@ -1,16 +1,17 @@
 use crate::removal_worker::submit_removal_task;
 use crate::state::AppState;
 use serde::{Deserialize, Serialize};
-use spectral_broker::{BrokerRegistry, RemovalMethod, ScanPriority};
-use spectral_browser::BrowserEngine;
+use spectral_broker::{BrokerRegistry, CategoryExposure, RemovalMethod};
+use spectral_browser::{BrowserEngine, BrowserError};
+use spectral_core::config::{ScanDepth, ScanTier as ConfigScanTier};
 use spectral_core::types::{BrokerId, ProfileId};
-use spectral_scanner::{BrokerFilter, ScanOrchestrator};
+use spectral_scanner::{BrokerFilter, ScanOrchestrator, ScanSettings};
 use std::collections::HashMap;
 use std::sync::Arc;
 use tauri::{Emitter, State};
 use tauri_plugin_shell::ShellExt;
 use tokio::sync::Semaphore;
-use tracing::info;
+use tracing::{info, warn};
 use uuid::Uuid;
 
 /// Scan tier for filtering brokers by priority
@@ -25,6 +26,8 @@ pub enum ScanTier {
     All,
     /// Custom broker selection (use broker_ids parameter)
     Custom,
+    /// A custom tier defined in the scanning settings, by name
+    Named(String),
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -150,6 +153,8 @@ pub async fn start_scan(
     _broker_filter: Option<String>, // Deprecated: use tier parameter instead
     tier: Option<ScanTier>,
     broker_ids: Option<Vec<String>>,
+    max_concurrent_scans: Option<usize>,
+    depth: Option<ScanDepth>,
 ) -> Result<ScanJobResponse, String> {
     // Get the unlocked vault
     let vault = state
@@ -157,96 +162,80 @@ pub async fn start_scan(
         .ok_or_else(|| format!("Vault '{}' is not unlocked", vault_id))?;
 
     // Get the profile from the vault
-    let profile_id =
-        ProfileId::new(&profile_id).map_err(|e| format!("Invalid profile ID: {}", e))?;
+    let profile_id = ProfileId::new(&profile_id).map_err(|e| e.to_string())?;
 
     let profile = vault
         .load_profile(&profile_id)
         .await
         .map_err(|e| format!("Failed to load profile: {}", e))?;
 
-    // Get the vault's encryption key
+    // Copy the vault's encryption key for the background scan
     let vault_key = vault
-        .encryption_key()
-        .map_err(|e| format!("Failed to get vault key: {}", e))?;
+        .session_key_handle()
+        .map_err(|e| format!("Failed to get vault key: {}", e))?
+        .to_owned_key();
 
-    // Get the vault's database
+    // Share the vault's database with the background scan
     let db = vault
-        .database()
+        .shared_database()
         .map_err(|e| format!("Failed to get vault database: {}", e))?;
 
+    // Defaults come from the scanning config; explicit arguments override them
+    let scanning = state.config().scanning;
+    let explicit_tier = match &tier {
+        Some(ScanTier::Tier1) => Some(ConfigScanTier::Tier1),
+        Some(ScanTier::Tier2) => Some(ConfigScanTier::Tier2),
+        Some(ScanTier::All) => Some(ConfigScanTier::All),
+        Some(ScanTier::Named(name)) => Some(ConfigScanTier::Named(name.clone())),
+        Some(ScanTier::Custom) | None => None,
+    };
+    let settings = ScanSettings::from_config(&scanning)
+        .with_overrides(explicit_tier, max_concurrent_scans, None)
+        .with_depth(depth.unwrap_or(scanning.depth));
+
     // Create orchestrator for this scan
-    // TODO: These should be cached/shared across scans
-    // Note: We can't clone EncryptedPool (it contains Zeroizing secrets),
-    // but Pool<Sqlite> itself is Arc-based and can be cloned.
-    // For now, we create a temporary EncryptedPool from the existing pool.
-    // In production, the orchestrator should be a singleton in AppState.
+    // TODO: The orchestrator should be a singleton in AppState.
     let broker_registry = state.broker_registry.clone();
-    let browser_engine = Arc::new(
-        BrowserEngine::new()
-            .await
-            .map_err(|e| format!("Failed to create browser engine: {}", e))?,
-    );
-
-    // Get the underlying Pool<Sqlite> which can be cloned
-    let pool = db.pool().clone();
-    let vault_key_vec = vault_key.to_vec();
-
-    // Create a new EncryptedPool with the same pool and key
-    // This is safe because both point to the same underlying connection pool
-    use spectral_db::{Database, EncryptedPool};
-    let encrypted_pool = EncryptedPool::from_pool(pool, vault_key_vec);
-    let database = Database::from_encrypted_pool(encrypted_pool);
-    let db = Arc::new(database);
+    let browser_engine = if settings.uses_browser() {
+        match BrowserEngine::new().await {
+            Ok(engine) => Some(Arc::new(engine)),
+            // Without a browser, URL-template brokers are still scanned over HTTP
+            Err(BrowserError::BrowserNotFound(_)) => {
+                warn!("No browser installed; browser-only brokers will be skipped");
+                None
+            }
+            Err(e) => return Err(format!("Failed to create browser engine: {}", e)),
+        }
+    } else {
+        // Quick scans stay on plain HTTP
+        None
+    };
 
-    let orchestrator = ScanOrchestrator::new(broker_registry.clone(), browser_engine, db)
-        .with_max_concurrent_scans(4);
+    let orchestrator = match browser_engine {
+        Some(engine) => ScanOrchestrator::new(broker_registry.clone(), engine, db),
+        None => ScanOrchestrator::without_browser(broker_registry.clone(), db),
+    }
+    .with_settings(&settings)
+    .with_rate_limiter(state.scan_rate_limiter.clone());
 
     // Filter brokers based on tier or custom IDs
     let all_brokers = broker_registry.get_all();
 
-    let selected_brokers: Vec<_> = match (&tier, &broker_ids) {
-        (_, Some(ids)) => {
-            // Custom broker selection takes precedence
-            all_brokers
-                .iter()
-                .filter(|b| ids.contains(&b.broker.id.to_string()))
-                .cloned()
-                .collect()
-        }
-        (Some(ScanTier::Tier1), _) => {
-            // Only Tier 1 brokers
-            all_brokers
-                .iter()
-                .filter(|b| b.broker.scan_priority == ScanPriority::AutoScanTier1)
-                .cloned()
-                .collect()
-        }
-        (Some(ScanTier::Tier2), _) => {
-            // Tier 1 and Tier 2 brokers
-            all_brokers
-                .iter()
-                .filter(|b| {
-                    matches!(
-                        b.broker.scan_priority,
-                        ScanPriority::AutoScanTier1 | ScanPriority::AutoScanTier2
-                    )
-                })
-                .cloned()
-                .collect()
-        }
-        _ => {
-            // All brokers except ManualOnly (default)
-            all_brokers
-                .iter()
-                .filter(|b| b.broker.scan_priority != ScanPriority::ManualOnly)
-                .cloned()
-                .collect()
-        }
+    let selected_brokers: Vec<_> = match &broker_ids {
+        // Custom broker selection takes precedence
+        Some(ids) => all_brokers
+            .iter()
+            .filter(|b| ids.contains(&b.broker.id.to_string()))
+            .cloned()
+            .collect(),
+        None => settings
+            .select_brokers(&all_brokers)
+            .map_err(|e| e.to_string())?,
     };
 
     // If tier or broker_ids filtering was applied but resulted in empty list, return error
-    if (tier.is_some() || broker_ids.is_some()) && selected_brokers.is_empty() {
+    let narrowed = tier.is_some() || broker_ids.is_some() || settings.tier != ConfigScanTier::All;
+    if narrowed && selected_brokers.is_empty() {
         return Err("No brokers matched the specified tier or IDs".to_string());
     }
 
@@ -265,7 +254,7 @@ pub async fn start_scan(
 
     // Start the scan with tier-based filter
     let job_id = orchestrator
-        .start_scan(&profile, filter, vault_key)
+        .start_scan(&profile, filter, &vault_key)
         .await
         .map_err(|e| format!("Failed to start scan: {}", e))?;
 
@@ -305,6 +294,60 @@ pub async fn get_scan_status(
     })
 }
 
+/// Forward a vault's database changes to the frontend as `db:change` events.
+///
+/// Call after unlocking the vault; forwarding stops when the vault is locked.
+/// Calling again replaces the vault's forwarder rather than adding a second
+/// one. Changes written before this call are not replayed.
+///
+/// # Events
+/// - `db:change`: `{ vault_id, change }` for each `DbChange`
+#[tauri::command]
+pub async fn listen_for_db_changes<R: tauri::Runtime>(
+    state: State<'_, AppState>,
+    app: tauri::AppHandle<R>,
+    vault_id: String,
+) -> Result<(), String> {
+    // Only the receiver is kept, so locking the vault closes the channel
+    let mut changes = {
+        let vault = state
+            .get_vault(&vault_id)
+            .ok_or_else(|| format!("Vault '{}' is not unlocked", vault_id))?;
+        let db = vault
+            .database()
+            .map_err(|e| format!("Failed to get vault database: {}", e))?;
+        db.listen_for_changes()
+    };
+
+    let forwarder_vault_id = vault_id.clone();
+    let forwarder = tokio::spawn(async move {
+        let vault_id = forwarder_vault_id;
+        loop {
+            match changes.recv().await {
+                Ok(change) => {
+                    let _ = app.emit(
+                        "db:change",
+                        serde_json::json!({
+                            "vault_id": vault_id,
+                            "change": change,
+                        }),
+                    );
+                }
+                Err(tokio::sync::broadcast::error::RecvError::Lagged(skipped)) => {
+                    warn!(
+                        "Dropped {} database changes for vault {}",
+                        skipped, vault_id
+                    );
+                }
+                Err(tokio::sync::broadcast::error::RecvError::Closed) => break,
+            }
+        }
+    });
+    state.replace_db_change_forwarder(&vault_id, forwarder.abort_handle());
+
+    Ok(())
+}
+
 /// Get findings for a scan job with optional verification status filter.
 #[tauri::command]
 pub async fn get_findings(
@@ -324,7 +367,9 @@ pub async fn get_findings(
         .map_err(|e| format!("Failed to get vault database: {}", e))?;
 
     // Get all findings for this scan job
-    let mut findings = spectral_db::findings::get_by_scan_job(db.pool(), &scan_job_id)
+    let mut findings = db
+        .read_only_view()
+        .findings_by_scan_job(&scan_job_id)
         .await
         .map_err(|e| format!("Failed to get findings: {}", e))?;
 
@@ -370,6 +415,35 @@ pub async fn verify_finding(
     Ok(())
 }
 
+/// Confirm or reject many findings at once.
+///
+/// Updates are applied atomically; IDs that don't exist are returned in
+/// `missing` rather than dropped.
+#[tauri::command]
+pub async fn bulk_verify_findings(
+    state: State<'_, AppState>,
+    vault_id: String,
+    finding_ids: Vec<String>,
+    is_match: bool,
+) -> Result<spectral_db::findings::BulkVerifyResult, String> {
+    let vault = state
+        .get_vault(&vault_id)
+        .ok_or_else(|| format!("Vault '{}' is not unlocked", vault_id))?;
+
+    let db = vault
+        .database()
+        .map_err(|e| format!("Failed to get vault database: {}", e))?;
+
+    spectral_db::findings::bulk_verify(
+        db.pool(),
+        &finding_ids,
+        is_match,
+        true, // verified_by_user = true
+    )
+    .await
+    .map_err(|e| format!("Failed to verify findings: {}", e))
+}
+
 /// Submit removal requests for confirmed findings
 #[tauri::command]
 pub async fn submit_removals_for_confirmed(
@@ -392,10 +466,11 @@ pub async fn submit_removals_for_confirmed(
         .await
         .map_err(|e| e.to_string())?;
 
-    // Filter to confirmed findings
+    // Filter to confirmed findings without a removal request, including
+    // reopened ones
     let confirmed_findings = findings
         .into_iter()
-        .filter(|f| f.verification_status == spectral_db::findings::VerificationStatus::Confirmed)
+        .filter(spectral_db::findings::Finding::is_eligible_for_removal)
         .collect::<Vec<_>>();
 
     // Create removal attempt for each confirmed finding
@@ -422,7 +497,8 @@ pub async fn submit_removals_for_confirmed(
 ///
 /// # Events
 /// - `removal:started`: When task begins processing
-/// - `removal:success`: When removal is submitted successfully
+/// - `removal:success`: When removal is submitted successfully; carries
+///   `related_brokers` to suggest for removal next
 /// - `removal:captcha`: When CAPTCHA is required
 /// - `removal:failed`: When removal fails
 #[tauri::command]
@@ -437,28 +513,16 @@ pub async fn process_removal_batch<R: tauri::Runtime>(
         .get_vault(&vault_id)
         .ok_or_else(|| "Vault not found or locked".to_string())?;
 
-    // Get database
+    // Share the vault's database with the worker tasks
     let db = vault
-        .database()
+        .shared_database()
         .map_err(|e| format!("Failed to get vault database: {}", e))?;
 
-    // Get the underlying Pool<Sqlite> which can be cloned
-    let pool = db.pool().clone();
-    let vault_key = vault
-        .encryption_key()
-        .map_err(|e| format!("Failed to get vault key: {}", e))?;
-    let vault_key_vec = vault_key.to_vec();
-
-    // Create a new EncryptedPool with the same pool and key
-    use spectral_db::{Database, EncryptedPool};
-    let encrypted_pool = EncryptedPool::from_pool(pool, vault_key_vec);
-    let database = Database::from_encrypted_pool(encrypted_pool);
-    let db = Arc::new(database);
-
     // Create shared resources
     let broker_registry = Arc::new(BrokerRegistry::new());
     let semaphore = Arc::new(Semaphore::new(3)); // Max 3 concurrent
     let browser_engine = state.browser_engine.clone();
+    let config = state.config();
 
     // Generate job_id
     let job_id = Uuid::new_v4().to_string();
@@ -474,6 +538,7 @@ pub async fn process_removal_batch<R: tauri::Runtime>(
         let broker_registry_clone = broker_registry.clone();
         let semaphore_clone = semaphore.clone();
         let browser_engine_clone = browser_engine.clone();
+        let config = config.clone();
         let job_id_clone = job_id.clone();
         let app_handle = app.clone();
         let attempt_id_clone = attempt_id.clone();
@@ -496,6 +561,7 @@ pub async fn process_removal_batch<R: tauri::Runtime>(
                 broker_registry_clone,
                 semaphore_clone,
                 browser_engine_clone,
+                config,
             )
             .await;
 
@@ -511,7 +577,8 @@ pub async fn process_removal_batch<R: tauri::Runtime>(
                             serde_json::json!({
                                 "job_id": job_id_clone,
                                 "attempt_id": attempt_id_clone,
-                                "outcome": format!("{:?}", worker_result.outcome)
+                                "outcome": format!("{:?}", worker_result.outcome),
+                                "related_brokers": worker_result.related_brokers
                             }),
                         );
                     }
@@ -525,8 +592,33 @@ pub async fn process_removal_batch<R: tauri::Runtime>(
                             }),
                         );
                     }
-                    spectral_broker::removal::RemovalOutcome::Failed { .. }
-                    | spectral_broker::removal::RemovalOutcome::RequiresAccountCreation => {
+                    spectral_broker::removal::RemovalOutcome::RequiresIdVerification {
+                        ref instructions_url,
+                    } => {
+                        let _ = app_handle.emit(
+                            "removal:user-action",
+                            serde_json::json!({
+                                "job_id": job_id_clone,
+                                "attempt_id": attempt_id_clone,
+                                "kind": "id_verification",
+                                "instructions_url": instructions_url
+                            }),
+                        );
+                    }
+                    spectral_broker::removal::RemovalOutcome::RequiresAccountCreation {
+                        ref signup_url,
+                    } => {
+                        let _ = app_handle.emit(
+                            "removal:user-action",
+                            serde_json::json!({
+                                "job_id": job_id_clone,
+                                "attempt_id": attempt_id_clone,
+                                "kind": "account",
+                                "instructions_url": signup_url
+                            }),
+                        );
+                    }
+                    spectral_broker::removal::RemovalOutcome::Failed { .. } => {
                         let _ = app_handle.emit(
                             "removal:failed",
                             serde_json::json!({
@@ -578,11 +670,38 @@ pub async fn get_captcha_queue(
         .map_err(|e| format!("Failed to get vault database: {}", e))?;
 
     // Get CAPTCHA queue
-    spectral_db::removal_attempts::get_captcha_queue(db.pool())
+    db.read_only_view()
+        .captcha_queue()
         .await
         .map_err(|e| format!("Failed to get CAPTCHA queue: {}", e))
 }
 
+/// Get all removal attempts the user must finish themselves.
+///
+/// Returns removal attempts such as brokers requiring ID verification,
+/// ordered oldest first.
+#[tauri::command]
+pub async fn get_user_action_queue(
+    state: State<'_, AppState>,
+    vault_id: String,
+) -> Result<Vec<spectral_db::removal_attempts::RemovalAttempt>, String> {
+    // Get unlocked vault
+    let vault = state
+        .get_vault(&vault_id)
+        .ok_or_else(|| format!("Vault '{}' is not unlocked", vault_id))?;
+
+    // Get database
+    let db = vault
+        .database()
+        .map_err(|e| format!("Failed to get vault database: {}", e))?;
+
+    // Get user-action queue
+    db.read_only_view()
+        .user_action_queue()
+        .await
+        .map_err(|e| format!("Failed to get user action queue: {}", e))
+}
+
 /// Get all removal attempts in the failed queue.
 ///
 /// Returns removal attempts that have failed, ordered newest first.
@@ -602,11 +721,56 @@ pub async fn get_failed_queue(
         .map_err(|e| format!("Failed to get vault database: {}", e))?;
 
     // Get failed queue
-    spectral_db::removal_attempts::get_failed_queue(db.pool())
+    db.read_only_view()
+        .failed_queue()
         .await
         .map_err(|e| format!("Failed to get failed queue: {}", e))
 }
 
+/// Get one page of removal attempts with the given status, oldest first.
+#[tauri::command]
+pub async fn get_removal_attempts_by_status(
+    state: State<'_, AppState>,
+    vault_id: String,
+    status: spectral_db::removal_attempts::RemovalStatus,
+    limit: u32,
+    offset: u32,
+) -> Result<Vec<spectral_db::removal_attempts::RemovalAttempt>, String> {
+    let vault = state
+        .get_vault(&vault_id)
+        .ok_or_else(|| format!("Vault '{}' is not unlocked", vault_id))?;
+
+    let db = vault
+        .database()
+        .map_err(|e| format!("Failed to get vault database: {}", e))?;
+
+    db.read_only_view()
+        .removal_attempts_by_status(status, limit, offset)
+        .await
+        .map_err(|e| format!("Failed to query removal attempts: {}", e))
+}
+
+/// Count removal attempts with the given status, for queue badges.
+#[tauri::command]
+pub async fn count_removal_attempts_by_status(
+    state: State<'_, AppState>,
+    vault_id: String,
+    status: spectral_db::removal_attempts::RemovalStatus,
+) -> Result<i64, String> {
+    let vault = state
+        .get_vault(&vault_id)
+        .ok_or_else(|| format!("Vault '{}' is not unlocked", vault_id))?;
+
+    let db = vault
+        .database()
+        .map_err(|e| format!("Failed to get vault database: {}", e))?;
+
+    db.read_only_view()
+        .count_removal_attempts(status)
+        .await
+        .map_err(|e| format!("Failed to count removal attempts: {}", e))
+}
+
 /// Get all removal attempts for a scan job.
 ///
 /// Returns all removal attempts for findings associated with the given scan job.
@@ -624,7 +788,8 @@ pub async fn get_removal_attempts_by_scan_job(
         .database()
         .map_err(|e| format!("Failed to access database: {}", e))?;
 
-    spectral_db::removal_attempts::get_by_scan_job_id(db.pool(), &scan_job_id)
+    db.read_only_view()
+        .removal_attempts_by_scan_job(&scan_job_id)
         .await
         .map_err(|e| format!("Failed to query removal attempts: {}", e))
 }
@@ -648,7 +813,8 @@ pub async fn get_removal_job_history(
         .map_err(|e| format!("Failed to get vault database: {}", e))?;
 
     // Get job history
-    spectral_db::removal_attempts::get_job_history(db.pool())
+    db.read_only_view()
+        .removal_job_history()
         .await
         .map_err(|e| format!("Failed to get job history: {}", e))
 }
@@ -660,7 +826,8 @@ pub async fn get_removal_job_history(
 ///
 /// # Events
 /// - `removal:retry`: When retry begins
-/// - `removal:success`: When removal is submitted successfully
+/// - `removal:success`: When removal is submitted successfully; carries
+///   `related_brokers` to suggest for removal next
 /// - `removal:captcha`: When CAPTCHA is required
 /// - `removal:failed`: When removal fails
 #[tauri::command]
@@ -692,24 +859,22 @@ pub async fn retry_removal<R: tauri::Runtime>(
     .await
     .map_err(|e| format!("Failed to reset removal attempt: {}", e))?;
 
-    // Get the underlying Pool<Sqlite> which can be cloned
-    let pool = db.pool().clone();
-    let vault_key = vault
-        .encryption_key()
-        .map_err(|e| format!("Failed to get vault key: {}", e))?;
-    let vault_key_vec = vault_key.to_vec();
+    db.notify(spectral_db::DbChange::RemovalStatusChanged {
+        removal_attempt_id: removal_attempt_id.clone(),
+        status: spectral_db::removal_attempts::RemovalStatus::Pending,
+    });
 
-    // Create a new EncryptedPool with the same pool and key
-    use spectral_db::{Database, EncryptedPool};
-    let encrypted_pool = EncryptedPool::from_pool(pool, vault_key_vec);
-    let database = Database::from_encrypted_pool(encrypted_pool);
-    let db = Arc::new(database);
+    // Share the vault's database with the worker task
+    let db = vault
+        .shared_database()
+        .map_err(|e| format!("Failed to get vault database: {}", e))?;
 
     // Create shared resources
     let broker_registry = Arc::new(BrokerRegistry::new());
     let semaphore = Arc::new(Semaphore::new(3)); // Max 3 concurrent
     let vault_clone = Arc::clone(&vault);
     let browser_engine = state.browser_engine.clone();
+    let config = state.config();
 
     // Spawn background worker task
     let attempt_id_clone = removal_attempt_id.clone();
@@ -730,6 +895,7 @@ pub async fn retry_removal<R: tauri::Runtime>(
             broker_registry,
             semaphore,
             browser_engine,
+            config,
         )
         .await;
 
@@ -742,7 +908,8 @@ pub async fn retry_removal<R: tauri::Runtime>(
                         "removal:success",
                         serde_json::json!({
                             "attempt_id": attempt_id_clone,
-                            "outcome": format!("{:?}", worker_result.outcome)
+                            "outcome": format!("{:?}", worker_result.outcome),
+                            "related_brokers": worker_result.related_brokers
                         }),
                     );
                 }
@@ -755,8 +922,31 @@ pub async fn retry_removal<R: tauri::Runtime>(
                         }),
                     );
                 }
-                spectral_broker::removal::RemovalOutcome::Failed { .. }
-                | spectral_broker::removal::RemovalOutcome::RequiresAccountCreation => {
+                spectral_broker::removal::RemovalOutcome::RequiresIdVerification {
+                    ref instructions_url,
+                } => {
+                    let _ = app.emit(
+                        "removal:user-action",
+                        serde_json::json!({
+                            "attempt_id": attempt_id_clone,
+                            "kind": "id_verification",
+                            "instructions_url": instructions_url
+                        }),
+                    );
+                }
+                spectral_broker::removal::RemovalOutcome::RequiresAccountCreation {
+                    ref signup_url,
+                } => {
+                    let _ = app.emit(
+                        "removal:user-action",
+                        serde_json::json!({
+                            "attempt_id": attempt_id_clone,
+                            "kind": "account",
+                            "instructions_url": signup_url
+                        }),
+                    );
+                }
+                spectral_broker::removal::RemovalOutcome::Failed { .. } => {
                     let _ = app.emit(
                         "removal:failed",
                         serde_json::json!({
@@ -832,49 +1022,54 @@ pub async fn get_dashboard_summary(
     let db = vault
         .database()
         .map_err(|e| format!("Failed to get vault database: {}", e))?;
-    let pool = db.pool();
+    let db = db.read_only_view();
 
     // Count distinct brokers with at least one finding.
-    let brokers_scanned: i64 = sqlx::query_scalar("SELECT COUNT(DISTINCT broker_id) FROM findings")
-        .fetch_one(pool)
+    let brokers_scanned: i64 = db
+        .fetch_scalar(sqlx::query_scalar(
+            "SELECT COUNT(DISTINCT broker_id) FROM findings",
+        ))
         .await
         .map_err(|e| format!("Failed to count brokers scanned: {}", e))?;
 
     // Timestamp of the most recently started scan job.
-    let last_scan_at: Option<String> = sqlx::query_scalar("SELECT MAX(started_at) FROM scan_jobs")
-        .fetch_one(pool)
+    let last_scan_at: Option<String> = db
+        .fetch_scalar(sqlx::query_scalar("SELECT MAX(started_at) FROM scan_jobs"))
         .await
         .map_err(|e| format!("Failed to get last scan timestamp: {}", e))?;
 
     // Removal counts by status.
-    let submitted: i64 =
-        sqlx::query_scalar("SELECT COUNT(*) FROM removal_attempts WHERE status = 'Submitted'")
-            .fetch_one(pool)
-            .await
-            .map_err(|e| format!("Failed to count submitted removals: {}", e))?;
+    let submitted: i64 = db
+        .fetch_scalar(sqlx::query_scalar(
+            "SELECT COUNT(*) FROM removal_attempts WHERE status = 'Submitted'",
+        ))
+        .await
+        .map_err(|e| format!("Failed to count submitted removals: {}", e))?;
 
-    let pending: i64 =
-        sqlx::query_scalar("SELECT COUNT(*) FROM removal_attempts WHERE status = 'Pending'")
-            .fetch_one(pool)
-            .await
-            .map_err(|e| format!("Failed to count pending removals: {}", e))?;
+    let pending: i64 = db
+        .fetch_scalar(sqlx::query_scalar(
+            "SELECT COUNT(*) FROM removal_attempts WHERE status = 'Pending'",
+        ))
+        .await
+        .map_err(|e| format!("Failed to count pending removals: {}", e))?;
 
-    let failed: i64 =
-        sqlx::query_scalar("SELECT COUNT(*) FROM removal_attempts WHERE status = 'Failed'")
-            .fetch_one(pool)
-            .await
-            .map_err(|e| format!("Failed to count failed removals: {}", e))?;
+    let failed: i64 = db
+        .fetch_scalar(sqlx::query_scalar(
+            "SELECT COUNT(*) FROM removal_attempts WHERE status = 'Failed'",
+        ))
+        .await
+        .map_err(|e| format!("Failed to count failed removals: {}", e))?;
 
     // Compute score only when there is something to base it on.
     let has_data = brokers_scanned > 0 || submitted > 0 || failed > 0;
     let privacy_score = if has_data {
         // Unresolved = confirmed findings with no removal yet.
-        let unresolved: i64 = sqlx::query_scalar(
-            "SELECT COUNT(*) FROM findings WHERE verification_status = 'Confirmed'",
-        )
-        .fetch_one(pool)
-        .await
-        .map_err(|e| format!("Failed to count confirmed findings: {}", e))?;
+        let unresolved: i64 = db
+            .fetch_scalar(sqlx::query_scalar(
+                "SELECT COUNT(*) FROM findings WHERE verification_status = 'Confirmed'",
+            ))
+            .await
+            .map_err(|e| format!("Failed to count confirmed findings: {}", e))?;
 
         Some(calculate_privacy_score(
             unresolved as u32,
@@ -887,12 +1082,12 @@ pub async fn get_dashboard_summary(
     };
 
     // Last 5 scan jobs as activity events.
-    let scan_rows: Vec<(String, String, String)> = sqlx::query_as(
-        "SELECT id, started_at, status FROM scan_jobs ORDER BY started_at DESC LIMIT 5",
-    )
-    .fetch_all(pool)
-    .await
-    .map_err(|e| format!("Failed to fetch recent scan jobs: {}", e))?;
+    let scan_rows: Vec<(String, String, String)> = db
+        .fetch_all(sqlx::query_as(
+            "SELECT id, started_at, status FROM scan_jobs ORDER BY started_at DESC LIMIT 5",
+        ))
+        .await
+        .map_err(|e| format!("Failed to fetch recent scan jobs: {}", e))?;
 
     let mut events: Vec<ActivityEvent> = scan_rows
         .into_iter()
@@ -905,10 +1100,9 @@ pub async fn get_dashboard_summary(
         .collect();
 
     // Last 5 removal attempts as activity events.
-    let removal_rows: Vec<(String, String, String, String)> = sqlx::query_as(
+    let removal_rows: Vec<(String, String, String, String)> = db.fetch_all(sqlx::query_as(
         "SELECT id, broker_id, created_at, status FROM removal_attempts ORDER BY created_at DESC LIMIT 5",
-    )
-    .fetch_all(pool)
+    ))
     .await
     .map_err(|e| format!("Failed to fetch recent removal attempts: {}", e))?;
 
@@ -1009,30 +1203,33 @@ pub async fn get_privacy_score(
     let db = vault
         .database()
         .map_err(|e| format!("Failed to get vault database: {}", e))?;
-    let pool = db.pool();
+    let db = db.read_only_view();
 
     // Count all confirmed findings. The penalty applies to all Confirmed findings
     // until the listing is verified removed (a future feature).
     // verification_status = 'Confirmed' means the user has verified this is them.
-    let unresolved: i64 =
-        sqlx::query_scalar("SELECT COUNT(*) FROM findings WHERE verification_status = 'Confirmed'")
-            .fetch_one(pool)
-            .await
-            .map_err(|e| format!("Failed to count unresolved findings: {}", e))?;
+    let unresolved: i64 = db
+        .fetch_scalar(sqlx::query_scalar(
+            "SELECT COUNT(*) FROM findings WHERE verification_status = 'Confirmed'",
+        ))
+        .await
+        .map_err(|e| format!("Failed to count unresolved findings: {}", e))?;
 
     // Count submitted removal attempts via JOIN (removal_attempts has no vault_id).
-    let confirmed: i64 =
-        sqlx::query_scalar("SELECT COUNT(*) FROM removal_attempts WHERE status = 'Submitted'")
-            .fetch_one(pool)
-            .await
-            .map_err(|e| format!("Failed to count submitted removals: {}", e))?;
+    let confirmed: i64 = db
+        .fetch_scalar(sqlx::query_scalar(
+            "SELECT COUNT(*) FROM removal_attempts WHERE status = 'Submitted'",
+        ))
+        .await
+        .map_err(|e| format!("Failed to count submitted removals: {}", e))?;
 
     // Count failed removal attempts.
-    let failed: i64 =
-        sqlx::query_scalar("SELECT COUNT(*) FROM removal_attempts WHERE status = 'Failed'")
-            .fetch_one(pool)
-            .await
-            .map_err(|e| format!("Failed to count failed removals: {}", e))?;
+    let failed: i64 = db
+        .fetch_scalar(sqlx::query_scalar(
+            "SELECT COUNT(*) FROM removal_attempts WHERE status = 'Failed'",
+        ))
+        .await
+        .map_err(|e| format!("Failed to count failed removals: {}", e))?;
 
     let score = calculate_privacy_score(
         unresolved as u32,
@@ -1050,6 +1247,31 @@ pub async fn get_privacy_score(
     })
 }
 
+/// Return confirmed findings grouped by broker category.
+///
+/// Each category carries its finding count, risk weight and share of the
+/// weighted exposure, so the dashboard can show where the score is lost
+/// (e.g. mostly to background-check sites).
+#[tauri::command]
+pub async fn get_exposure_breakdown(
+    state: State<'_, AppState>,
+    vault_id: String,
+) -> Result<Vec<CategoryExposure>, String> {
+    info!("get_exposure_breakdown: vault_id={}", vault_id);
+    let vault = state
+        .get_vault(&vault_id)
+        .ok_or_else(|| format!("Vault '{}' is not unlocked", vault_id))?;
+    let db = vault
+        .database()
+        .map_err(|e| format!("Failed to get vault database: {}", e))?;
+
+    let counts = spectral_db::findings::count_confirmed_by_broker(db.pool())
+        .await
+        .map_err(|e| format!("Failed to count findings by broker: {}", e))?;
+
+    Ok(state.broker_registry.exposure_breakdown(&counts))
+}
+
 /// Evidence record captured during browser-form removal submissions.
 #[derive(Debug, serde::Serialize)]
 pub struct RemovalEvidence {
@@ -1093,6 +1315,66 @@ pub async fn get_removal_evidence(
     }))
 }
 
+/// Get the history of the listing a finding belongs to, oldest first.
+#[tauri::command]
+pub async fn get_finding_timeline(
+    state: State<'_, AppState>,
+    vault_id: String,
+    finding_id: String,
+) -> Result<Vec<spectral_db::findings::TimelineEvent>, String> {
+    info!(
+        "get_finding_timeline: vault_id={}, finding_id={}",
+        vault_id, finding_id
+    );
+    let vault = state.get_vault(&vault_id).ok_or("Vault not unlocked")?;
+    let db = vault.database().map_err(|e| e.to_string())?;
+
+    spectral_db::findings::get_timeline(db.pool(), &finding_id)
+        .await
+        .map_err(|e| format!("Failed to get finding timeline: {}", e))
+}
+
+/// Move a confirmed finding back to active so it gets a new removal request.
+#[tauri::command]
+pub async fn reopen_finding(
+    state: State<'_, AppState>,
+    vault_id: String,
+    finding_id: String,
+    reason: spectral_db::findings::ReopenReason,
+) -> Result<spectral_db::findings::Reopening, String> {
+    info!(
+        "reopen_finding: vault_id={}, finding_id={}, reason={}",
+        vault_id,
+        finding_id,
+        reason.as_str()
+    );
+    let vault = state.get_vault(&vault_id).ok_or("Vault not unlocked")?;
+    let db = vault.database().map_err(|e| e.to_string())?;
+
+    spectral_db::findings::reopen(db.pool(), &finding_id, reason)
+        .await
+        .map_err(|e| format!("Failed to reopen finding: {}", e))
+}
+
+/// Get the findings of a scan job grouped by the person they describe.
+#[tauri::command]
+pub async fn get_identity_clusters(
+    state: State<'_, AppState>,
+    vault_id: String,
+    scan_job_id: String,
+) -> Result<Vec<spectral_db::findings::IdentityCluster>, String> {
+    info!(
+        "get_identity_clusters: vault_id={}, scan_job_id={}",
+        vault_id, scan_job_id
+    );
+    let vault = state.get_vault(&vault_id).ok_or("Vault not unlocked")?;
+    let db = vault.database().map_err(|e| e.to_string())?;
+
+    spectral_db::findings::cluster_by_identity(db.pool(), &scan_job_id)
+        .await
+        .map_err(|e| format!("Failed to group findings: {}", e))
+}
+
 /// Decrypt all profile fields into a HashMap for template rendering.
 fn decrypt_profile_fields(
     profile: &spectral_vault::UserProfile,
@@ -1141,6 +1423,7 @@ fn decrypt_profile_fields(
 
 /// Removal email context loaded from database.
 struct RemovalEmailContext {
+    broker_id: String,
     email_address: String,
     subject_template: String,
     body_template: String,
@@ -1198,6 +1481,7 @@ async fn load_removal_context(
         .map_err(|e| format!("Failed to load profile: {}", e))?;
 
     Ok(RemovalEmailContext {
+        broker_id: attempt.broker_id,
         email_address,
         subject_template,
         body_template,
@@ -1205,15 +1489,15 @@ async fn load_removal_context(
     })
 }
 
-/// Re-trigger email send for a pending email attempt.
+/// Send the removal email for a pending email attempt.
 ///
-/// This command is a stub for Task 16 (Email Verification Manual Tab).
-/// It will load the removal attempt, broker definition, and profile data,
-/// then regenerate and send the email.
+/// Sends over SMTP when an SMTP server is configured, retrying once, and
+/// otherwise opens the email in the user's default mail client. The method
+/// used is recorded in `email_removals`.
 #[tauri::command]
 pub async fn send_removal_email<R: tauri::Runtime>(
     state: State<'_, AppState>,
-    _app: tauri::AppHandle<R>,
+    app: tauri::AppHandle<R>,
     vault_id: String,
     attempt_id: String,
 ) -> Result<(), String> {
@@ -1225,38 +1509,66 @@ pub async fn send_removal_email<R: tauri::Runtime>(
     // Get unlocked vault
     let vault = state.get_vault(&vault_id).ok_or("Vault not unlocked")?;
 
-    // Get vault encryption key for profile decryption
-    let vault_key = vault
-        .encryption_key()
-        .map_err(|e| format!("Failed to get vault key: {}", e))?;
-
     // Load all removal context (attempt, finding, broker, profile)
     let context = load_removal_context(&state, &vault, &attempt_id).await?;
 
     // Decrypt profile fields for template rendering
-    let fields = decrypt_profile_fields(&context.profile, vault_key);
-
-    // Render email subject and body templates
-    let rendered_subject = render_email_template(&context.subject_template, &fields);
-    let rendered_body = render_email_template(&context.body_template, &fields);
-
-    // Construct mailto: URL
-    let mailto_url = format!(
-        "mailto:{}?subject={}&body={}",
-        urlencoding::encode(&context.email_address),
-        urlencoding::encode(&rendered_subject),
-        urlencoding::encode(&rendered_body)
-    );
+    let fields = vault
+        .with_key(|key| decrypt_profile_fields(&context.profile, key))
+        .map_err(|e| format!("Failed to get vault key: {}", e))?;
+
+    // Refuse to send an email that would still contain {{placeholders}}
+    let available: Vec<&str> = fields.keys().map(String::as_str).collect();
+    for template in [&context.subject_template, &context.body_template] {
+        spectral_mail::templates::validate_template(template, &available).map_err(
+            |unresolved| {
+                format!(
+                    "Email template for {} has unresolved placeholders: {}",
+                    context.broker_id,
+                    unresolved.join(", ")
+                )
+            },
+        )?;
+    }
+
+    let email = spectral_mail::EmailTemplate {
+        to: context.email_address.clone(),
+        subject: render_email_template(&context.subject_template, &fields),
+        body: render_email_template(&context.body_template, &fields),
+    };
 
-    // Open mailto: URL in default email client
-    #[allow(deprecated)]
-    _app.shell()
-        .open(&mailto_url, None)
-        .map_err(|e| format!("Failed to open email client: {}", e))?;
+    // SMTP needs the user's address as the sender
+    let db = vault.database().map_err(|e| e.to_string())?;
+    let from = fields.get("email").map(String::as_str).unwrap_or_default();
+    let smtp_config = if from.is_empty() {
+        None
+    } else {
+        spectral_mail::delivery::load_smtp_config(&vault).await?
+    };
+    let delivery = spectral_mail::delivery::deliver(&email, from, smtp_config.as_ref()).await;
+
+    if let spectral_mail::Delivery::Mailto { url } = &delivery {
+        // Open mailto: URL in default email client
+        #[allow(deprecated)]
+        app.shell()
+            .open(url, None)
+            .map_err(|e| format!("Failed to open email client: {}", e))?;
+    }
+
+    spectral_mail::delivery::record_delivery(
+        db.pool(),
+        &attempt_id,
+        &context.broker_id,
+        &email,
+        &delivery,
+    )
+    .await?;
 
     info!(
-        "Opened mailto: for attempt {} to {}",
-        attempt_id, context.email_address
+        "Sent removal email for attempt {} to {} via {}",
+        attempt_id,
+        context.email_address,
+        delivery.method()
     );
 
     Ok(())
@@ -1,15 +1,20 @@
 //! Scheduler command handlers.
 
 use crate::error::CommandError;
+use crate::notifications::job_notifier;
 use crate::state::AppState;
-use spectral_db::{Database, EncryptedPool};
-use spectral_scanner::{BrokerFilter, ScanOrchestrator};
-use spectral_scheduler::{next_run_timestamp, JobType, ScheduledJob};
+use chrono::NaiveTime;
+use spectral_browser::BrowserError;
+use spectral_db::scan_jobs::ScanJobStatus;
+use spectral_privacy::{Feature, PermissionResult, PrivacyEngine};
+use spectral_scanner::{BrokerFilter, ScanOrchestrator, ScanSettings};
+use spectral_scheduler::{JobNotifier, JobOutcome, JobType, QuietHours, ScheduledJob};
 use std::sync::Arc;
-use tracing::{error, info};
+use std::time::Duration;
+use tracing::{error, info, warn};
 
-/// Interval for disabled jobs (far future to prevent execution)
-const DISABLED_JOB_INTERVAL_DAYS: u32 = 365 * 10; // 10 years
+/// How often a running scheduled scan is checked for completion
+const SCAN_COMPLETION_POLL_INTERVAL: Duration = Duration::from_secs(5);
 
 #[tauri::command]
 pub async fn get_scheduled_jobs(
@@ -29,7 +34,7 @@ pub async fn get_scheduled_jobs(
         )
     })?;
 
-    db.get_scheduled_jobs().await.map_err(|e| {
+    db.read_only_view().scheduled_jobs().await.map_err(|e| {
         CommandError::new(
             "DATABASE_ERROR",
             format!("Failed to get scheduled jobs: {}", e),
@@ -63,30 +68,80 @@ pub async fn update_scheduled_job(
         )
     })?;
 
-    // Update interval and enabled status
-    let next_run = if enabled {
-        next_run_timestamp(interval_days)
-    } else {
-        // If disabled, set next_run far in future
-        next_run_timestamp(DISABLED_JOB_INTERVAL_DAYS)
-    };
+    // Enabling a job schedules it to run in the background
+    if enabled {
+        require_background_execution(db.pool()).await?;
+    }
 
-    sqlx::query(
-        "UPDATE scheduled_jobs SET interval_days = ?, enabled = ?, next_run_at = ? WHERE id = ?",
-    )
-    .bind(interval_days as i64)
-    .bind(if enabled { 1 } else { 0 })
-    .bind(&next_run)
-    .bind(&job_id)
-    .execute(db.pool())
-    .await
-    .map_err(|e| CommandError::new("DATABASE_ERROR", format!("Failed to update job: {}", e)))?;
+    db.set_job_interval(&job_id, interval_days)
+        .await
+        .map_err(|e| CommandError::new("DATABASE_ERROR", format!("Failed to update job: {}", e)))?;
+    db.set_job_enabled(&job_id, enabled)
+        .await
+        .map_err(|e| CommandError::new("DATABASE_ERROR", format!("Failed to update job: {}", e)))?;
 
     Ok(())
 }
 
+#[tauri::command]
+pub async fn update_job_conditions(
+    vault_id: String,
+    job_id: String,
+    quiet_hours_start: Option<String>,
+    quiet_hours_end: Option<String>,
+    skip_on_metered: bool,
+    state: tauri::State<'_, AppState>,
+) -> Result<(), CommandError> {
+    info!(
+        "Updating run conditions for job {} - quiet hours: {:?}-{:?}, skip on metered: {}",
+        job_id, quiet_hours_start, quiet_hours_end, skip_on_metered
+    );
+
+    let parse_time = |value: &str| {
+        NaiveTime::parse_from_str(value, "%H:%M").map_err(|e| {
+            CommandError::new(
+                "INVALID_QUIET_HOURS",
+                format!("Invalid quiet hours time '{}': {}", value, e),
+            )
+        })
+    };
+    let quiet_hours = match (quiet_hours_start.as_deref(), quiet_hours_end.as_deref()) {
+        (Some(start), Some(end)) => Some(QuietHours::new(parse_time(start)?, parse_time(end)?)),
+        (None, None) => None,
+        _ => {
+            return Err(CommandError::new(
+                "INVALID_QUIET_HOURS",
+                "Quiet hours need both a start and an end time",
+            ))
+        }
+    };
+
+    let vault = state.get_vault(&vault_id).ok_or_else(|| {
+        CommandError::new(
+            "VAULT_NOT_UNLOCKED",
+            format!("Vault {} not unlocked", vault_id),
+        )
+    })?;
+    let db = vault.database().map_err(|e| {
+        CommandError::new(
+            "DATABASE_ERROR",
+            format!("Failed to access database: {}", e),
+        )
+    })?;
+
+    db.update_job_conditions(&job_id, quiet_hours, skip_on_metered)
+        .await
+        .map_err(|e| {
+            CommandError::new(
+                "DATABASE_ERROR",
+                format!("Failed to update job conditions: {}", e),
+            )
+        })
+}
+
 #[tauri::command]
 pub async fn run_job_now(
+    app: tauri::AppHandle,
     vault_id: String,
     job_type: String,
     state: tauri::State<'_, AppState>,
@@ -110,18 +165,23 @@ pub async fn run_job_now(
         )
     })?;
 
-    // Get the vault's database
-    let db = vault.database().map_err(|e| {
-        CommandError::new(
-            "DATABASE_ERROR",
-            format!("Failed to get vault database: {}", e),
-        )
-    })?;
+    let pool = vault
+        .database()
+        .map_err(|e| {
+            CommandError::new(
+                "DATABASE_ERROR",
+                format!("Failed to access database: {}", e),
+            )
+        })?
+        .pool()
+        .clone();
+    require_background_execution(&pool).await?;
 
-    // Get the vault's encryption key
+    // Copy the vault's encryption key for the background scan
     let vault_key = vault
-        .encryption_key()
-        .map_err(|e| CommandError::new("VAULT_ERROR", format!("Failed to get vault key: {}", e)))?;
+        .session_key_handle()
+        .map_err(|e| CommandError::new("VAULT_ERROR", format!("Failed to get vault key: {}", e)))?
+        .to_owned_key();
 
     match job_type {
         JobType::ScanAll => {
@@ -148,24 +208,45 @@ pub async fn run_job_now(
                 CommandError::new("DATABASE_ERROR", format!("Failed to load profile: {}", e))
             })?;
 
-            // Get or initialize cached browser engine
-            let browser_engine = state.get_or_init_browser_engine().await.map_err(|e| {
+            // Get or initialize cached browser engine; without one, only
+            // brokers that can be fetched over plain HTTP are scanned
+            let browser_engine = match state.get_or_init_browser_engine().await {
+                Ok(engine) => Some(engine),
+                Err(e)
+                    if matches!(
+                        e.downcast_ref::<BrowserError>(),
+                        Some(BrowserError::BrowserNotFound(_))
+                    ) =>
+                {
+                    warn!("No browser installed; browser-only brokers will be skipped");
+                    None
+                }
+                Err(e) => {
+                    return Err(CommandError::new(
+                        "BROWSER_ERROR",
+                        format!("Failed to get browser engine: {}", e),
+                    ))
+                }
+            };
+
+            // Create orchestrator sharing the vault's database
+            let db_arc = vault.shared_database().map_err(|e| {
                 CommandError::new(
-                    "BROWSER_ERROR",
-                    format!("Failed to get browser engine: {}", e),
+                    "DATABASE_ERROR",
+                    format!("Failed to get vault database: {}", e),
                 )
             })?;
 
-            // Create orchestrator
-            let pool = db.pool().clone();
-            let vault_key_vec = vault_key.to_vec();
-            let encrypted_pool = EncryptedPool::from_pool(pool, vault_key_vec);
-            let database = Database::from_encrypted_pool(encrypted_pool);
-            let db_arc = Arc::new(database);
-
-            let orchestrator =
-                ScanOrchestrator::new(state.broker_registry.clone(), browser_engine, db_arc)
-                    .with_max_concurrent_scans(4);
+            let orchestrator = match browser_engine {
+                Some(engine) => {
+                    ScanOrchestrator::new(state.broker_registry.clone(), engine, db_arc.clone())
+                }
+                None => {
+                    ScanOrchestrator::without_browser(state.broker_registry.clone(), db_arc.clone())
+                }
+            }
+            .with_settings(&ScanSettings::from_config(&state.config().scanning))
+            .with_rate_limiter(state.scan_rate_limiter.clone());
 
             // Scan all brokers except ManualOnly
             let filter = BrokerFilter::All;
@@ -173,8 +254,8 @@ pub async fn run_job_now(
             info!("Starting scheduled scan with all auto-scan brokers");
 
             // Start the scan
-            let _job_id = orchestrator
-                .start_scan(&profile, filter, vault_key)
+            let scan_job_id = orchestrator
+                .start_scan(&profile, filter, &vault_key)
                 .await
                 .map_err(|e| {
                     error!("Scheduled scan failed: {}", e);
@@ -182,6 +263,18 @@ pub async fn run_job_now(
                 })?;
 
             info!("Scheduled scan started successfully");
+
+            // Notify once the scan finishes in the background
+            let notifier = job_notifier(&app);
+            if notifier.is_enabled() {
+                let job = scheduled_job_for(&vault, JobType::ScanAll).await;
+                tokio::spawn(notify_when_scan_finishes(
+                    db_arc,
+                    scan_job_id,
+                    job,
+                    notifier,
+                ));
+            }
             Ok(())
         }
         JobType::VerifyRemovals => {
@@ -192,11 +285,130 @@ pub async fn run_job_now(
             ))
         }
         JobType::PollImap => {
-            // Not yet implemented - requires IMAP poller (Feature 8)
-            Err(CommandError::new(
-                "NOT_IMPLEMENTED",
-                "PollImap job type not yet implemented. This feature requires IMAP configuration and the email verification monitoring system (Feature 8).".to_string(),
-            ))
+            info!("Executing PollImap job for vault {}", vault_id);
+
+            let config = spectral_mail::inbox::load_imap_config(&vault)
+                .await
+                .map_err(|e| CommandError::new("DATABASE_ERROR", e))?
+                .ok_or_else(|| {
+                    CommandError::new(
+                        "IMAP_NOT_CONFIGURED",
+                        "No IMAP server configured. Add one in settings to check for bounced removal emails.",
+                    )
+                })?;
+            let db = vault.database().map_err(|e| {
+                CommandError::new(
+                    "DATABASE_ERROR",
+                    format!("Failed to access database: {}", e),
+                )
+            })?;
+
+            // Bounced removal emails never reached the broker; fail their attempts
+            let failed = spectral_mail::inbox::check_inbox(db.pool(), &config)
+                .await
+                .map_err(|e| CommandError::new("IMAP_ERROR", e))?;
+            info!("Inbox check found {} bounced removal emails", failed.len());
+
+            let notifier = job_notifier(&app);
+            if notifier.is_enabled() {
+                let job = scheduled_job_for(&vault, JobType::PollImap).await;
+                notifier.job_finished(
+                    &job,
+                    JobOutcome::Completed {
+                        count: failed.len(),
+                    },
+                );
+            }
+            Ok(())
         }
     }
 }
+
+/// Refuse to run or enable a scheduled job unless the privacy settings allow
+/// background execution.
+async fn require_background_execution(pool: &sqlx::SqlitePool) -> Result<(), CommandError> {
+    let permission = PrivacyEngine::new(pool.clone())
+        .check_permission(Feature::BackgroundExecution)
+        .await
+        .map_err(|e| {
+            CommandError::new(
+                "DATABASE_ERROR",
+                format!("Failed to check permission: {}", e),
+            )
+        })?;
+
+    match permission {
+        PermissionResult::Allowed => Ok(()),
+        PermissionResult::Denied { reason } => Err(CommandError::new(
+            "PERMISSION_DENIED",
+            format!("Scheduled jobs are not permitted: {}", reason),
+        )),
+    }
+}
+
+/// The scheduled job of `job_type`, or a stand-in if it cannot be loaded.
+async fn scheduled_job_for(vault: &spectral_vault::Vault, job_type: JobType) -> ScheduledJob {
+    let jobs = match vault.database() {
+        Ok(db) => db
+            .read_only_view()
+            .scheduled_jobs()
+            .await
+            .unwrap_or_default(),
+        Err(_) => Vec::new(),
+    };
+    jobs.into_iter()
+        .find(|job| job.job_type == job_type)
+        .unwrap_or_else(|| ScheduledJob {
+            id: String::new(),
+            job_type,
+            interval_days: 0,
+            next_run_at: String::new(),
+            last_run_at: None,
+            enabled: true,
+            quiet_hours: None,
+            skip_on_metered: false,
+            deferred_reason: None,
+        })
+}
+
+/// Wait for a scheduled scan to finish, then notify with its new listings.
+async fn notify_when_scan_finishes(
+    db: Arc<spectral_db::Database>,
+    scan_job_id: String,
+    job: ScheduledJob,
+    notifier: JobNotifier,
+) {
+    let outcome = loop {
+        tokio::time::sleep(SCAN_COMPLETION_POLL_INTERVAL).await;
+
+        let status = sqlx::query_scalar::<_, String>("SELECT status FROM scan_jobs WHERE id = ?")
+            .bind(&scan_job_id)
+            .fetch_one(db.pool())
+            .await;
+        match status.as_deref() {
+            Ok(status) if status == ScanJobStatus::InProgress.to_string() => continue,
+            Ok(status) if status == ScanJobStatus::Completed.to_string() => {
+                match spectral_db::findings::get_by_scan_job(db.pool(), &scan_job_id).await {
+                    Ok(findings) => {
+                        break JobOutcome::Completed {
+                            count: findings.len(),
+                        }
+                    }
+                    Err(e) => {
+                        warn!("Failed to count findings for scan {}: {}", scan_job_id, e);
+                        return;
+                    }
+                }
+            }
+            // Cancelled scans were stopped by the user and need no notification
+            Ok(status) if status == ScanJobStatus::Cancelled.to_string() => return,
+            Ok(_) => break JobOutcome::Failed,
+            Err(e) => {
+                warn!("Failed to check status of scan {}: {}", scan_job_id, e);
+                return;
+            }
+        }
+    };
+
+    notifier.job_finished(&job, outcome);
+}
@@ -1,4 +1,5 @@
 use crate::error::CommandError;
+use crate::state::AppState;
 
 #[tauri::command]
 pub async fn test_smtp_connection(
@@ -41,3 +42,33 @@ pub async fn test_imap_connection(
 
     Ok(())
 }
+
+/// Save the IMAP server checked for bounced removal emails.
+///
+/// The password is kept encrypted in the vault.
+#[tauri::command]
+pub async fn save_imap_settings(
+    vault_id: String,
+    host: String,
+    port: u16,
+    username: String,
+    password: String,
+    state: tauri::State<'_, AppState>,
+) -> Result<(), CommandError> {
+    let vault = state.get_vault(&vault_id).ok_or_else(|| {
+        CommandError::new(
+            "VAULT_NOT_UNLOCKED",
+            format!("Vault {} not unlocked", vault_id),
+        )
+    })?;
+
+    let config = spectral_mail::ImapConfig {
+        host,
+        port,
+        username,
+        password,
+    };
+    spectral_mail::inbox::save_imap_config(&vault, &config)
+        .await
+        .map_err(|e| CommandError::new("DATABASE_ERROR", e))
+}
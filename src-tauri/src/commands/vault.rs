@@ -4,11 +4,42 @@ use crate::error::CommandError;
 use crate::metadata::VaultMetadata;
 use crate::state::AppState;
 use serde::Serialize;
-use spectral_vault::Vault;
+use spectral_scanner::watchdog::{spawn_watchdog, DEFAULT_CHECK_INTERVAL};
+use spectral_vault::{UnlockOptions, Vault};
 use std::sync::Arc;
-use tauri::State;
+use std::time::Duration;
+use tauri::{AppHandle, Emitter, Manager, Runtime, State};
 use tracing::{info, warn};
 
+/// How often unlocked vaults are checked for auto-lock.
+const AUTO_LOCK_CHECK_INTERVAL: Duration = Duration::from_secs(30);
+
+/// Lock vaults left idle for longer than the configured auto-lock timeout.
+///
+/// Runs for the life of the app and emits `vault:locked` for each vault it
+/// locks. The timeout is read from the config on every check so changes apply
+/// without a restart; a timeout of 0 turns auto-lock off.
+pub fn spawn_auto_lock<R: Runtime>(app: AppHandle<R>) {
+    tauri::async_runtime::spawn(async move {
+        let mut ticker = tokio::time::interval(AUTO_LOCK_CHECK_INTERVAL);
+        loop {
+            ticker.tick().await;
+
+            let state = app.state::<AppState>();
+            let minutes = state.config().vault.auto_lock_minutes;
+            if minutes == 0 {
+                continue;
+            }
+            let timeout = Duration::from_secs(u64::from(minutes) * 60);
+
+            for vault_id in state.lock_idle_vaults(timeout) {
+                info!("Vault auto-locked after being idle: {}", vault_id);
+                let _ = app.emit("vault:locked", serde_json::json!({ "vault_id": vault_id }));
+            }
+        }
+    });
+}
+
 /// Response for vault_status command.
 #[derive(Debug, Serialize)]
 pub struct VaultStatus {
@@ -72,6 +103,8 @@ pub async fn vault_create(
         return Err(e.into());
     }
 
+    start_scan_watchdog(&state, &vault);
+
     // Insert into unlocked vaults
     state.insert_vault(vault_id.clone(), Arc::new(vault));
 
@@ -79,6 +112,23 @@ pub async fn vault_create(
     Ok(())
 }
 
+/// Fail scan jobs left running by a crash, and any that stall from now on.
+///
+/// The watchdog stops on its own once the vault is locked.
+fn start_scan_watchdog(state: &AppState, vault: &Vault) {
+    match vault.shared_database() {
+        Ok(db) => {
+            let timeout_mins = u64::from(state.config().scanning.job_timeout_mins);
+            spawn_watchdog(
+                Arc::downgrade(&db),
+                Duration::from_secs(timeout_mins * 60),
+                DEFAULT_CHECK_INTERVAL,
+            );
+        }
+        Err(e) => warn!("Failed to start scan watchdog: {}", e),
+    }
+}
+
 /// Unlock an existing vault with password.
 ///
 /// Loads encrypted database and inserts into unlocked vaults.
@@ -106,9 +156,13 @@ pub async fn vault_unlock(
         return Ok(());
     }
 
-    // Unlock vault
+    // Unlock vault, strengthening old key derivation parameters if the user
+    // opted in
     let db_path = state.vault_db_path(&vault_id);
-    let vault = Vault::unlock(&password, &db_path).await?;
+    let options = UnlockOptions {
+        upgrade_kdf: state.config().vault.upgrade_kdf_on_unlock,
+    };
+    let vault = Vault::unlock_with_options(&password, &db_path, options).await?;
 
     // Update last_accessed in metadata
     let metadata_path = state.vault_metadata_path(&vault_id);
@@ -117,6 +171,8 @@ pub async fn vault_unlock(
         metadata.write_to_file(&metadata_path).ok();
     }
 
+    start_scan_watchdog(&state, &vault);
+
     // Insert into unlocked vaults
     state.insert_vault(vault_id.clone(), Arc::new(vault));
 
@@ -254,10 +310,11 @@ pub async fn rename_vault(
 
 /// Change the master password of a vault.
 ///
-/// The vault must be currently unlocked. This operation is not yet implemented
-/// because `spectral_vault::Vault` does not expose a `change_password` method.
+/// The vault must be currently unlocked. Profiles are re-encrypted under the
+/// new key, emitting a `vault:password-change-progress` event per profile.
 #[tauri::command]
 pub async fn change_vault_password(
+    app: tauri::AppHandle,
     state: State<'_, AppState>,
     vault_id: String,
     old_password: String,
@@ -279,128 +336,60 @@ pub async fn change_vault_password(
         ));
     }
 
-    // Get the vault before locking it
-    let vault = state.get_vault(&vault_id).ok_or_else(|| {
+    // Take exclusive ownership of the vault while its key changes
+    let vault = state.remove_vault(&vault_id).ok_or_else(|| {
         CommandError::new("VAULT_NOT_FOUND", format!("Vault '{}' not found", vault_id))
     })?;
+    let mut vault = match Arc::try_unwrap(vault) {
+        Ok(vault) => vault,
+        Err(vault) => {
+            state.insert_vault(vault_id.clone(), vault);
+            return Err(CommandError::new(
+                "VAULT_BUSY",
+                "Vault is in use by a background task, try again shortly",
+            ));
+        }
+    };
 
-    // Get database path
-    let db_path = state.vault_db_path(&vault_id);
-    let salt_path = db_path
-        .parent()
-        .unwrap_or_else(|| std::path::Path::new("."))
-        .join("salt");
-
-    // Read current salt
-    let current_salt = tokio::fs::read(&salt_path)
-        .await
-        .map_err(|e| CommandError::new("FILE_ERROR", format!("Failed to read salt file: {}", e)))?;
-
-    // Verify old password by deriving key and comparing to current vault key
-    use spectral_vault::kdf;
-    let old_key = kdf::derive_key(&old_password, &current_salt).map_err(|e| {
-        CommandError::new(
-            "KEY_DERIVATION_ERROR",
-            format!("Failed to derive key from old password: {}", e),
-        )
-    })?;
-
-    let vault_key = vault
-        .encryption_key()
-        .map_err(|e| CommandError::new("VAULT_ERROR", format!("Failed to get vault key: {}", e)))?;
-
-    if old_key.as_ref() != vault_key.as_ref() {
-        return Err(CommandError::new(
-            "INVALID_PASSWORD",
-            "Old password is incorrect",
-        ));
+    let result = vault
+        .change_password_with_progress(&old_password, &new_password, |completed, total| {
+            let _ = app.emit(
+                "vault:password-change-progress",
+                serde_json::json!({
+                    "vault_id": vault_id,
+                    "completed": completed,
+                    "total": total
+                }),
+            );
+        })
+        .await;
+
+    match result {
+        Ok(()) => {
+            state.insert_vault(vault_id.clone(), Arc::new(vault));
+            info!("Password changed successfully for vault: {vault_id}");
+            Ok(())
+        }
+        // Nothing was re-encrypted; the vault is still unlocked with the old key
+        Err(e) if vault.is_unlocked() => {
+            state.insert_vault(vault_id.clone(), Arc::new(vault));
+            warn!("Password change failed for vault {}: {}", vault_id, e);
+            Err(e.into())
+        }
+        // Part of the change committed, so the vault locked itself; it stays
+        // out of the unlocked map and the next unlock finishes the change
+        Err(e) => {
+            warn!(
+                "Password change interrupted for vault {}, vault locked: {}",
+                vault_id, e
+            );
+            let _ = app.emit("vault:locked", serde_json::json!({ "vault_id": vault_id }));
+            Err(CommandError::new(
+                "PASSWORD_CHANGE_INTERRUPTED",
+                "The password change was interrupted and the vault was locked. Unlock it to finish the change.",
+            ))
+        }
     }
-
-    // Lock the vault temporarily
-    state.remove_vault(&vault_id);
-    drop(vault); // Explicitly drop to release the Arc
-
-    // Generate new salt and derive new key
-    let new_salt = kdf::generate_salt();
-    let new_key = kdf::derive_key(&new_password, &new_salt).map_err(|e| {
-        CommandError::new(
-            "KEY_DERIVATION_ERROR",
-            format!("Failed to derive new key: {}", e),
-        )
-    })?;
-
-    // Connect to database with old key to perform rekey
-    let old_key_hex = format!("\"x'{}'\"", hex::encode(old_key.as_ref()));
-    let new_key_hex = format!("\"x'{}'\"", hex::encode(new_key.as_ref()));
-
-    let db_path_str = db_path
-        .to_str()
-        .ok_or_else(|| CommandError::new("FILE_ERROR", "Invalid database path: not valid UTF-8"))?;
-
-    use sqlx::sqlite::{SqliteConnectOptions, SqlitePoolOptions};
-    use std::str::FromStr;
-
-    let connect_options = SqliteConnectOptions::from_str(db_path_str)
-        .map_err(|e| {
-            CommandError::new(
-                "DATABASE_ERROR",
-                format!("Invalid connection string: {}", e),
-            )
-        })?
-        .pragma("key", old_key_hex)
-        .pragma("cipher_page_size", "4096")
-        .pragma("kdf_iter", "256000")
-        .pragma("cipher_hmac_algorithm", "HMAC_SHA512")
-        .pragma("cipher_kdf_algorithm", "PBKDF2_HMAC_SHA512");
-
-    let pool = SqlitePoolOptions::new()
-        .max_connections(1)
-        .connect_with(connect_options)
-        .await
-        .map_err(|e| {
-            CommandError::new(
-                "DATABASE_ERROR",
-                format!("Failed to connect to database: {}", e),
-            )
-        })?;
-
-    // Re-encrypt database with new key
-    sqlx::query(&format!("PRAGMA rekey = {}", new_key_hex))
-        .execute(&pool)
-        .await
-        .map_err(|e| {
-            CommandError::new(
-                "DATABASE_ERROR",
-                format!("Failed to re-encrypt database: {}", e),
-            )
-        })?;
-
-    // Close the pool
-    pool.close().await;
-
-    // Write new salt to file
-    tokio::fs::write(&salt_path, new_salt).await.map_err(|e| {
-        CommandError::new("FILE_ERROR", format!("Failed to write salt file: {}", e))
-    })?;
-
-    // Unlock vault with new password (this will verify the rekey worked)
-    let new_vault = Vault::unlock(&new_password, &db_path).await.map_err(|e| {
-        // Try to restore old state if unlock fails
-        warn!("Failed to unlock vault with new password: {}", e);
-        CommandError::new(
-            "VAULT_ERROR",
-            format!(
-                "Password change failed - could not unlock with new password: {}",
-                e
-            ),
-        )
-    })?;
-
-    // Add the re-unlocked vault back to state
-    state.insert_vault(vault_id.clone(), Arc::new(new_vault));
-
-    info!("Password changed successfully for vault: {vault_id}");
-    Ok(())
 }
 
 /// Delete a vault after verifying the password.
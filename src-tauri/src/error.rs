@@ -3,7 +3,7 @@
 #![allow(dead_code)] // Used by vault commands (implemented in later tasks)
 
 use serde::Serialize;
-use spectral_core::error::SpectralError;
+use spectral_core::error::{IdError, SpectralError};
 use spectral_vault::VaultError;
 
 /// Serializable error for Tauri IPC commands.
@@ -58,6 +58,10 @@ impl From<VaultError> for CommandError {
                 "KEY_DERIVATION_FAILED",
                 format!("Key derivation failed: {msg}"),
             ),
+            VaultError::WeakKdfParams(msg) => Self::new(
+                "WEAK_KDF_PARAMS",
+                format!("Key derivation parameters too weak: {msg}"),
+            ),
             VaultError::Encryption(msg) => {
                 Self::new("ENCRYPTION_FAILED", format!("Encryption failed: {msg}"))
             }
@@ -70,12 +74,20 @@ impl From<VaultError> for CommandError {
             VaultError::InvalidData(msg) => {
                 Self::new("INVALID_DATA", format!("Invalid vault data: {msg}"))
             }
+            VaultError::InvalidAttachment(msg) => {
+                Self::new("INVALID_ATTACHMENT", format!("Invalid attachment: {msg}"))
+            }
             VaultError::NotFound(field) => {
                 Self::new("FIELD_NOT_FOUND", format!("Field not found: {field}"))
             }
             VaultError::Serialization(msg) => {
                 Self::new("SERIALIZATION_ERROR", format!("Serialization error: {msg}"))
             }
+            VaultError::ImportValidation(problems) => Self::with_details(
+                "IMPORT_VALIDATION",
+                format!("Could not import profile: {}", problems.join("; ")),
+                serde_json::json!({ "problems": problems }),
+            ),
         }
     }
 }
@@ -87,6 +99,23 @@ impl From<std::io::Error> for CommandError {
     }
 }
 
+/// Convert IdError to CommandError.
+///
+/// The message says what was wrong with the ID without repeating it.
+impl From<IdError> for CommandError {
+    fn from(err: IdError) -> Self {
+        Self::with_details(
+            "INVALID_ID",
+            format!(
+                "Invalid {}: {}",
+                err.id_type,
+                err.kind.describe(err.input_len)
+            ),
+            serde_json::json!(err),
+        )
+    }
+}
+
 /// Convert SpectralError to CommandError.
 impl From<SpectralError> for CommandError {
     fn from(err: SpectralError) -> Self {
@@ -94,17 +123,23 @@ impl From<SpectralError> for CommandError {
             SpectralError::Config(config_err) => {
                 Self::new("CONFIG_ERROR", format!("Configuration error: {config_err}"))
             }
-            SpectralError::Vault(msg) => Self::new("VAULT_ERROR", format!("Vault error: {msg}")),
-            SpectralError::Database(msg) => {
-                Self::new("DATABASE_ERROR", format!("Database error: {msg}"))
+            SpectralError::Vault { message, .. } => {
+                Self::new("VAULT_ERROR", format!("Vault error: {message}"))
             }
-            SpectralError::Broker(msg) => Self::new("BROKER_ERROR", format!("Broker error: {msg}")),
-            SpectralError::Llm(msg) => Self::new("LLM_ERROR", format!("LLM error: {msg}")),
-            SpectralError::Browser(msg) => {
-                Self::new("BROWSER_ERROR", format!("Browser error: {msg}"))
+            SpectralError::Database { message, .. } => {
+                Self::new("DATABASE_ERROR", format!("Database error: {message}"))
             }
-            SpectralError::Network(msg) => {
-                Self::new("NETWORK_ERROR", format!("Network error: {msg}"))
+            SpectralError::Broker { message, .. } => {
+                Self::new("BROKER_ERROR", format!("Broker error: {message}"))
+            }
+            SpectralError::Llm { message, .. } => {
+                Self::new("LLM_ERROR", format!("LLM error: {message}"))
+            }
+            SpectralError::Browser { message, .. } => {
+                Self::new("BROWSER_ERROR", format!("Browser error: {message}"))
+            }
+            SpectralError::Network { message, .. } => {
+                Self::new("NETWORK_ERROR", format!("Network error: {message}"))
             }
             SpectralError::PermissionDenied(msg) => {
                 Self::new("PERMISSION_DENIED", format!("Permission denied: {msg}"))
@@ -112,9 +147,10 @@ impl From<SpectralError> for CommandError {
             SpectralError::Validation(msg) => {
                 Self::new("VALIDATION_ERROR", format!("Validation failed: {msg}"))
             }
+            SpectralError::InvalidId(id_err) => id_err.into(),
             SpectralError::Io(io_err) => Self::new("IO_ERROR", format!("I/O error: {io_err}")),
-            SpectralError::Internal(msg) => {
-                Self::new("INTERNAL_ERROR", format!("Internal error: {msg}"))
+            SpectralError::Internal { message, .. } => {
+                Self::new("INTERNAL_ERROR", format!("Internal error: {message}"))
             }
         }
     }
@@ -155,6 +191,27 @@ mod tests {
         assert_eq!(err.message, "Invalid password");
     }
 
+    #[test]
+    fn test_id_error_conversion() {
+        let raw_id = "not-a-profile-id";
+        let id_err = spectral_core::ProfileId::new(raw_id).expect_err("invalid profile ID");
+        let err: CommandError = SpectralError::from(id_err).into();
+        assert_eq!(err.code, "INVALID_ID");
+        assert_eq!(
+            err.message,
+            "Invalid profile ID: contains characters that are not allowed"
+        );
+        assert!(!err.message.contains(raw_id));
+        assert_eq!(
+            err.details,
+            Some(serde_json::json!({
+                "id_type": "profile",
+                "kind": "invalid_char",
+                "input_len": raw_id.len(),
+            }))
+        );
+    }
+
     #[test]
     fn test_vault_error_not_found_conversion() {
         let err: CommandError = VaultError::VaultNotFound("/path/to/vault".to_string()).into();
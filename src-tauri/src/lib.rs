@@ -3,9 +3,11 @@
 //! This is the thin application shell that registers commands and manages windows.
 //! Core business logic lives in the `crates/` directory.
 
+pub mod capabilities;
 pub mod commands;
 mod error;
 mod metadata;
+pub mod notifications;
 pub mod removal_worker;
 pub mod state;
 pub mod types;
@@ -60,6 +62,9 @@ pub fn run() {
                 }
             }
 
+            // Lock vaults left idle past the auto-lock timeout
+            commands::vault::spawn_auto_lock(app.handle().clone());
+
             // Set up system tray if supported
             if spectral_scheduler::tray::is_tray_supported() {
                 use spectral_scheduler::tray;
@@ -73,7 +78,7 @@ pub fn run() {
                     .items(&[&open_item, &quit_item])
                     .build()?;
 
-                TrayIconBuilder::new()
+                TrayIconBuilder::with_id(tray::TRAY_ID)
                     .menu(&menu)
                     .on_menu_event(|app, event| match event.id.as_ref() {
                         tray::MENU_OPEN => {
@@ -109,31 +114,48 @@ pub fn run() {
             commands::profile::profile_get,
             commands::profile::profile_update,
             commands::profile::profile_list,
+            commands::profile::profile_import,
             commands::profile::get_profile_completeness,
             commands::removal::submit_removal,
             commands::removal::mark_attempt_verified,
+            commands::removal::get_legal_review_queue,
+            commands::removal::update_legal_request_status,
             commands::scan::start_scan,
             commands::scan::get_scan_status,
+            commands::scan::listen_for_db_changes,
             commands::scan::get_findings,
             commands::scan::verify_finding,
+            commands::scan::bulk_verify_findings,
             commands::scan::submit_removals_for_confirmed,
             commands::scan::process_removal_batch,
             commands::scan::get_captcha_queue,
+            commands::scan::get_user_action_queue,
             commands::scan::get_failed_queue,
+            commands::scan::get_removal_attempts_by_status,
+            commands::scan::count_removal_attempts_by_status,
             commands::scan::retry_removal,
             commands::scan::get_removal_attempts_by_scan_job,
             commands::scan::get_removal_job_history,
             commands::scan::get_privacy_score,
+            commands::scan::get_exposure_breakdown,
             commands::scan::get_dashboard_summary,
             commands::scan::get_removal_evidence,
+            commands::scan::get_finding_timeline,
+            commands::scan::get_identity_clusters,
+            commands::scan::reopen_finding,
             commands::scan::send_removal_email,
             commands::settings::test_smtp_connection,
             commands::settings::test_imap_connection,
+            commands::settings::save_imap_settings,
             commands::scheduler::get_scheduled_jobs,
             commands::scheduler::update_scheduled_job,
+            commands::scheduler::update_job_conditions,
             commands::scheduler::run_job_now,
             commands::brokers::list_brokers,
             commands::brokers::get_broker_detail,
+            commands::diagnostics::get_metrics,
+            commands::diagnostics::get_storage_stats,
+            commands::diagnostics::get_capabilities,
             commands::discovery::start_discovery_scan,
             commands::discovery::get_discovery_findings,
             commands::discovery::mark_finding_remediated,
@@ -144,6 +166,7 @@ pub fn run() {
             commands::privacy::set_llm_primary_provider,
             commands::privacy::set_llm_task_provider,
             commands::privacy::set_llm_api_key,
+            commands::privacy::set_captcha_solver_api_key,
             commands::privacy::test_llm_provider,
             commands::llm::draft_email,
             commands::llm::fill_form,
@@ -0,0 +1,51 @@
+//! Desktop notifications for finished scheduled jobs.
+
+use crate::state::AppState;
+use spectral_scheduler::{tray, JobNotifier, NotificationSink};
+use std::sync::Arc;
+use tauri::{AppHandle, Emitter, Manager};
+
+/// Shows notifications on the tray icon and forwards them to the frontend.
+///
+/// The tray tooltip keeps the latest summary; the `scheduler:notification`
+/// event lets the frontend raise an OS notification.
+pub struct TrayNotificationSink {
+    app: AppHandle,
+}
+
+impl TrayNotificationSink {
+    pub fn new(app: AppHandle) -> Self {
+        Self { app }
+    }
+}
+
+impl NotificationSink for TrayNotificationSink {
+    fn notify(&self, title: &str, body: &str) {
+        if let Some(tray_icon) = self.app.tray_by_id(tray::TRAY_ID) {
+            if let Err(e) = tray_icon.set_tooltip(Some(format!("Spectral — {}", body))) {
+                tracing::debug!("Failed to update tray tooltip: {}", e);
+            }
+        }
+        let _ = self.app.emit(
+            "scheduler:notification",
+            serde_json::json!({
+                "title": title,
+                "body": body,
+            }),
+        );
+    }
+}
+
+/// Build a job notifier honouring the notification settings.
+///
+/// Notifications are sent only while notifications are enabled and the user
+/// wants to hear about completed scans. Whether the job may run in the
+/// background at all is checked before it starts, against the background
+/// execution permission.
+pub fn job_notifier(app: &AppHandle) -> JobNotifier {
+    let notifications = app.state::<AppState>().config().notifications;
+    JobNotifier::new(
+        Arc::new(TrayNotificationSink::new(app.clone())),
+        notifications.enabled && notifications.notify_scan_complete,
+    )
+}
@@ -4,35 +4,75 @@
 //! and database state management.
 
 use spectral_broker::definition::RemovalMethod;
-use spectral_broker::removal::{RemovalOutcome, WebFormSubmitter};
+use spectral_broker::removal::{
+    detect_account_wall, ApiRemovalSubmitter, CaptchaSolver, ExternalCaptchaSolver, RemovalOutcome,
+    WebFormSubmitter,
+};
 use spectral_broker::BrokerRegistry;
-use spectral_browser::{BrowserActions, BrowserEngine};
-use spectral_core::BrokerId;
+use spectral_browser::{BrowserActions, BrowserEngine, HumanizeConfig, HumanizedActions};
+use spectral_core::metrics::{self, Counter};
+use spectral_core::{AppConfig, BrokerId, CaptchaConfig};
 use spectral_db::removal_attempts::{self, RemovalStatus};
-use spectral_db::Database;
+use spectral_db::{Database, DbChange};
+use spectral_privacy::{Feature, PermissionResult, PrivacyEngine};
 use spectral_vault::UserProfile;
 use std::collections::HashMap;
+use std::ops::Deref;
 use std::sync::Arc;
 use std::time::Duration;
 use tokio::sync::{Mutex, Semaphore};
 use tracing::{error, info, warn};
+use zeroize::Zeroize;
 
 /// Result of a removal submission worker task.
 #[derive(Debug)]
 pub struct WorkerResult {
     pub removal_attempt_id: String,
     pub outcome: RemovalOutcome,
+    /// Brokers related to this one, suggested for removal after a successful
+    /// submission. Empty unless the submission succeeded.
+    pub related_brokers: Vec<BrokerId>,
+}
+
+/// Decrypted form field values, wiped from memory when dropped.
+///
+/// Derefs to the field map expected by the submitters.
+pub struct FieldValues(HashMap<String, String>);
+
+impl Deref for FieldValues {
+    type Target = HashMap<String, String>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl std::fmt::Debug for FieldValues {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        // Only the field names; the values are PII
+        f.debug_set().entries(self.0.keys()).finish()
+    }
+}
+
+impl Drop for FieldValues {
+    fn drop(&mut self) {
+        for value in self.0.values_mut() {
+            value.zeroize();
+        }
+    }
 }
 
 /// Map profile and finding data to form fields.
 ///
 /// Extracts required fields from profile and finding for form submission.
+/// Decrypted values are moved straight into the returned map, which zeroizes
+/// them on drop.
 #[allow(deprecated)]
 pub fn map_fields_for_submission(
     profile: &UserProfile,
     finding_listing_url: &str,
     key: &[u8; 32],
-) -> Result<HashMap<String, String>, String> {
+) -> Result<FieldValues, String> {
     let mut fields = HashMap::new();
 
     // listing_url from finding
@@ -65,7 +105,7 @@ pub fn map_fields_for_submission(
         .map_err(|e| format!("Failed to decrypt last_name: {}", e))?;
     fields.insert("last_name".to_string(), last_name);
 
-    Ok(fields)
+    Ok(FieldValues(fields))
 }
 
 /// Retry a task with exponential backoff.
@@ -120,11 +160,60 @@ where
     unreachable!("Loop should have returned via Ok or Err")
 }
 
+/// Settings key holding the API key for the external CAPTCHA solving service.
+pub const CAPTCHA_API_KEY_SETTING: &str = "captcha.solver.api_key";
+
+/// Build the external CAPTCHA solver if the user opted into one.
+///
+/// Returns `None`, leaving CAPTCHAs to the user, unless
+/// `captcha.external_solver_enabled` is set in the config and an API key is
+/// stored in the vault.
+pub async fn load_captcha_solver(
+    db: &Database,
+    config: &CaptchaConfig,
+) -> Option<ExternalCaptchaSolver> {
+    if !config.external_solver_enabled {
+        return None;
+    }
+    // Configs are loaded even with invalid values, so the API key is never
+    // sent to a service over plain HTTP
+    if !config.service_url.starts_with("https://") {
+        warn!("External CAPTCHA solver is enabled but its service URL is not https://");
+        return None;
+    }
+
+    let api_key = match spectral_db::settings::get_setting(db.pool(), CAPTCHA_API_KEY_SETTING).await
+    {
+        Ok(Some(serde_json::Value::String(key))) if !key.is_empty() => key,
+        Ok(_) => {
+            warn!("External CAPTCHA solver is enabled but no API key is set");
+            return None;
+        }
+        Err(e) => {
+            warn!("Failed to load CAPTCHA solver API key: {}", e);
+            return None;
+        }
+    };
+
+    match ExternalCaptchaSolver::new(config.service_url, api_key) {
+        Ok(solver) => Some(solver.with_timeout(Duration::from_secs(config.solve_timeout_secs))),
+        Err(e) => {
+            warn!("Failed to create CAPTCHA solver: {}", e);
+            None
+        }
+    }
+}
+
 /// Submit a removal using browser automation for JS-heavy opt-out flows.
 ///
 /// Initializes the browser engine on first call, navigates to the form URL,
 /// fills fields based on the BrowserForm configuration, clicks submit, and
-/// captures a screenshot as evidence stored in the database.
+/// captures a screenshot as evidence stored in the database. Typing and
+/// clicks use human-like timing unless `browser.humanize_input` is off.
+///
+/// A CAPTCHA on the form is passed to `captcha_solver` when one is given;
+/// if it is solved the form is submitted, otherwise the attempt is returned
+/// as needing the user.
 ///
 /// # Arguments
 /// * `broker_def` - Broker definition with BrowserForm removal config
@@ -132,12 +221,15 @@ where
 /// * `field_values` - Decrypted field values mapped from the user profile
 /// * `browser_engine_mutex` - Shared lazy-initialized browser engine
 /// * `db` - Database for storing screenshot evidence
+/// * `captcha_solver` - Opt-in automated CAPTCHA solver
 pub async fn submit_via_browser(
     broker_def: &spectral_broker::definition::BrokerDefinition,
     attempt_id: &str,
     field_values: &HashMap<String, String>,
     browser_engine_mutex: &Mutex<Option<Arc<BrowserEngine>>>,
     db: &Database,
+    captcha_solver: Option<&dyn CaptchaSolver>,
+    humanize_input: bool,
 ) -> Result<RemovalOutcome, String> {
     let RemovalMethod::BrowserForm {
         url,
@@ -159,10 +251,16 @@ pub async fn submit_via_browser(
         );
         *engine_guard = Some(engine);
     }
-    let engine = engine_guard
+    let humanize = if humanize_input {
+        HumanizeConfig::default()
+    } else {
+        HumanizeConfig::disabled()
+    };
+    let browser = engine_guard
         .as_ref()
         .expect("engine initialized above")
         .as_ref();
+    let engine = HumanizedActions::new(browser, humanize);
 
     info!(
         "submit_via_browser: navigating to {} for attempt {}",
@@ -175,6 +273,27 @@ pub async fn submit_via_browser(
         .await
         .map_err(|e| format!("Navigation failed: {}", e))?;
 
+    // Bail before filling anything if the form sits behind a login
+    let page = browser
+        .page_content()
+        .await
+        .map_err(|e| format!("Failed to read opt-out page: {}", e))?;
+    if let Some(outcome) = detect_account_wall(&page, form_selectors, url) {
+        warn!(
+            "Account wall detected on browser-form for attempt {}",
+            attempt_id
+        );
+        let screenshot = engine.screenshot().await.unwrap_or_else(|e| {
+            warn!(
+                "Screenshot capture failed for attempt {}: {}",
+                attempt_id, e
+            );
+            vec![]
+        });
+        store_screenshot_evidence(db, attempt_id, screenshot).await?;
+        return Ok(outcome);
+    }
+
     // Fill listing URL field if selector and value present
     if let (Some(selector), Some(value)) = (
         &form_selectors.listing_url_input,
@@ -233,10 +352,12 @@ pub async fn submit_via_browser(
     // Check for CAPTCHA before submitting
     if let Some(captcha_selector) = &form_selectors.captcha_frame {
         // If CAPTCHA element is present, we cannot proceed automatically
+        // unless the user opted into a solving service
         if engine
             .wait_for_selector(captcha_selector, 1000)
             .await
             .is_ok()
+            && !solve_captcha(captcha_solver, browser, captcha_selector, attempt_id).await
         {
             warn!(
                 "CAPTCHA detected on browser-form for attempt {}",
@@ -307,6 +428,31 @@ pub async fn submit_via_browser(
     Ok(RemovalOutcome::Submitted)
 }
 
+/// Try to solve a CAPTCHA on the current page, returning whether it was
+/// solved.
+async fn solve_captcha(
+    solver: Option<&dyn CaptchaSolver>,
+    browser: &BrowserEngine,
+    captcha_selector: &str,
+    attempt_id: &str,
+) -> bool {
+    let Some(solver) = solver else {
+        return false;
+    };
+
+    match solver.solve(browser, captcha_selector).await {
+        Ok(true) => {
+            info!("CAPTCHA solved for attempt {}", attempt_id);
+            true
+        }
+        Ok(false) => false,
+        Err(e) => {
+            warn!("CAPTCHA solving failed for attempt {}: {}", attempt_id, e);
+            false
+        }
+    }
+}
+
 /// Store screenshot evidence for a removal attempt.
 async fn store_screenshot_evidence(
     db: &Database,
@@ -370,6 +516,17 @@ pub async fn submit_via_email(
         .get("email")
         .ok_or("Missing required field: email")?;
 
+    // Refuse to send a body that would still contain {{placeholders}}
+    let available: Vec<&str> = field_values.keys().map(String::as_str).collect();
+    spectral_mail::templates::validate_template(body_template, &available).map_err(
+        |unresolved| {
+            format!(
+                "Email template has unresolved placeholders: {}",
+                unresolved.join(", ")
+            )
+        },
+    )?;
+
     info!(
         "submit_via_email: rendering template for attempt {}",
         attempt_id
@@ -393,15 +550,18 @@ pub async fn submit_via_email(
         "mailto"
     };
 
-    // Send via SMTP or log as ready for manual sending
-    if let Some(config) = smtp_config {
+    // Send via SMTP or log as ready for manual sending. SMTP sends carry a
+    // Message-ID we control so replies and bounces can be matched back to
+    // this attempt; mailto sends are matched by broker address instead.
+    let message_id = if let Some(config) = smtp_config {
         info!(
             "submit_via_email: sending via SMTP for attempt {}",
             attempt_id
         );
-        spectral_mail::sender::send_smtp(&email_template, user_email, config)
+        let message_id = spectral_mail::sender::send_smtp(&email_template, user_email, config)
             .await
             .map_err(|e| format!("SMTP send failed: {}", e))?;
+        Some(message_id)
     } else {
         info!(
             "submit_via_email: email ready for manual sending for attempt {}",
@@ -411,43 +571,185 @@ pub async fn submit_via_email(
         // the email_removals table. The frontend (Task 16) will provide a UI
         // to re-generate and send the email via mailto: URL.
         // For now, we mark this as submitted since the email is logged and ready.
-    }
+        None
+    };
 
     // Log to email_removals table
-    let email_removal_id = uuid::Uuid::new_v4().to_string();
-    let sent_at = chrono::Utc::now().to_rfc3339();
-
-    sqlx::query(
-        "INSERT INTO email_removals (id, attempt_id, broker_id, sent_at, method, recipient, subject, body_hash) VALUES (?, ?, ?, ?, ?, ?, ?, ?)"
+    let email_removal = spectral_db::email_removals::insert_email_removal(
+        db.pool(),
+        spectral_db::email_removals::CreateEmailRemoval {
+            attempt_id: attempt_id.to_string(),
+            broker_id: broker_def.broker.id.to_string(),
+            method: send_method.to_string(),
+            recipient: to_email.clone(),
+            subject: email_template.subject.clone(),
+            body_hash,
+            message_id,
+        },
     )
-    .bind(&email_removal_id)
-    .bind(attempt_id)
-    .bind(broker_def.broker.id.to_string())
-    .bind(&sent_at)
-    .bind(send_method)
-    .bind(to_email)
-    .bind(&email_template.subject)
-    .bind(&body_hash)
-    .execute(db.pool())
     .await
     .map_err(|e| format!("Failed to log email removal: {}", e))?;
 
     info!(
         "Logged email removal {} for attempt {}",
-        email_removal_id, attempt_id
+        email_removal.id, attempt_id
     );
 
     Ok(RemovalOutcome::Submitted)
 }
 
+/// Returns the outcome for a broker that requires ID verification, or
+/// `None` if the removal can be submitted automatically.
+pub fn id_verification_outcome(
+    broker_def: &spectral_broker::definition::BrokerDefinition,
+) -> Option<RemovalOutcome> {
+    broker_def
+        .id_verification_url()
+        .map(|url| RemovalOutcome::RequiresIdVerification {
+            instructions_url: url.to_string(),
+        })
+}
+
+/// Returns the outcome for a broker that only accepts removals from account
+/// holders, or `None` if the removal can be submitted automatically.
+pub fn account_required_outcome(
+    broker_def: &spectral_broker::definition::BrokerDefinition,
+) -> Option<RemovalOutcome> {
+    broker_def
+        .account_required_url()
+        .map(|url| RemovalOutcome::RequiresAccountCreation {
+            signup_url: url.to_string(),
+        })
+}
+
+/// Update a removal attempt's status to reflect the submission outcome.
+pub async fn record_outcome(
+    db: &Database,
+    removal_attempt_id: &str,
+    outcome: &RemovalOutcome,
+) -> Result<(), String> {
+    let status = match outcome {
+        RemovalOutcome::Submitted | RemovalOutcome::RequiresEmailVerification { .. } => {
+            let now = chrono::Utc::now();
+            removal_attempts::update_status(
+                db.pool(),
+                removal_attempt_id,
+                RemovalStatus::Submitted,
+                Some(now),
+                None,
+                None,
+            )
+            .await
+            .map_err(|e| format!("Failed to update status to Submitted: {}", e))?;
+
+            metrics::global().increment(Counter::RemovalsSubmitted);
+            info!("Removal submitted successfully: {}", removal_attempt_id);
+            RemovalStatus::Submitted
+        }
+        RemovalOutcome::RequiresCaptcha { captcha_url } => {
+            // Left for the user to solve; shown in the CAPTCHA queue
+            removal_attempts::update_status(
+                db.pool(),
+                removal_attempt_id,
+                RemovalStatus::NeedsUserAction,
+                None,
+                None,
+                Some(format!(
+                    "{}{}",
+                    removal_attempts::CAPTCHA_REQUIRED_PREFIX,
+                    captcha_url
+                )),
+            )
+            .await
+            .map_err(|e| format!("Failed to update for CAPTCHA: {}", e))?;
+
+            metrics::global().increment(Counter::RemovalsCaptchaRequired);
+            warn!("CAPTCHA required for removal: {}", removal_attempt_id);
+            RemovalStatus::NeedsUserAction
+        }
+        RemovalOutcome::Failed { reason, .. } => {
+            // Mark as failed with error message
+            removal_attempts::update_status(
+                db.pool(),
+                removal_attempt_id,
+                RemovalStatus::Failed,
+                None,
+                None,
+                Some(reason.clone()),
+            )
+            .await
+            .map_err(|e| format!("Failed to update status to Failed: {}", e))?;
+
+            metrics::global().increment(Counter::RemovalsFailed);
+            error!("Removal failed: {} - {}", removal_attempt_id, reason);
+            RemovalStatus::Failed
+        }
+        RemovalOutcome::RequiresAccountCreation { signup_url } => {
+            // Left for the user to sign in; shown in the user-action queue
+            removal_attempts::update_status(
+                db.pool(),
+                removal_attempt_id,
+                RemovalStatus::NeedsUserAction,
+                None,
+                None,
+                Some(format!(
+                    "{}{}",
+                    removal_attempts::ACCOUNT_REQUIRED_PREFIX,
+                    signup_url
+                )),
+            )
+            .await
+            .map_err(|e| format!("Failed to update for account creation: {}", e))?;
+
+            metrics::global().increment(Counter::RemovalsNeedsUserAction);
+            warn!("Account required for removal: {}", removal_attempt_id);
+            RemovalStatus::NeedsUserAction
+        }
+        RemovalOutcome::RequiresIdVerification { instructions_url } => {
+            // Left for the user to finish; shown in the user-action queue
+            removal_attempts::update_status(
+                db.pool(),
+                removal_attempt_id,
+                RemovalStatus::NeedsUserAction,
+                None,
+                None,
+                Some(format!(
+                    "{}{}",
+                    removal_attempts::ID_VERIFICATION_PREFIX,
+                    instructions_url
+                )),
+            )
+            .await
+            .map_err(|e| format!("Failed to update for ID verification: {}", e))?;
+
+            metrics::global().increment(Counter::RemovalsNeedsUserAction);
+            warn!(
+                "ID verification required for removal: {}",
+                removal_attempt_id
+            );
+            RemovalStatus::NeedsUserAction
+        }
+    };
+
+    db.notify(DbChange::RemovalStatusChanged {
+        removal_attempt_id: removal_attempt_id.to_string(),
+        status,
+    });
+
+    Ok(())
+}
+
 /// Submit a removal request for a single attempt.
 ///
 /// Worker task that:
-/// 1. Loads removal attempt, finding, and profile data
-/// 2. Maps fields for form submission
-/// 3. Routes to browser or HTTP form submission based on broker removal method
-/// 4. Updates database based on outcome
-/// 5. Returns result for event emission
+/// 1. Loads the removal attempt and broker definition
+/// 2. Hands brokers that require ID verification or an account to the user
+///    unsubmitted
+/// 3. Loads finding and profile data
+/// 4. Maps fields for form submission
+/// 5. Routes to browser or HTTP form submission based on broker removal method
+/// 6. Updates database based on outcome
+/// 7. Returns result for event emission
 ///
 /// # Arguments
 /// * `db` - Database connection
@@ -456,6 +758,7 @@ pub async fn submit_via_email(
 /// * `broker_registry` - Registry for broker definitions
 /// * `semaphore` - Concurrency limiter (max 3 concurrent)
 /// * `browser_engine` - Shared lazy-initialized browser engine for browser-form removals
+/// * `config` - App configuration at the time the removal was queued
 pub async fn submit_removal_task(
     db: Arc<Database>,
     vault: Arc<spectral_vault::Vault>,
@@ -463,6 +766,7 @@ pub async fn submit_removal_task(
     broker_registry: Arc<BrokerRegistry>,
     semaphore: Arc<Semaphore>,
     browser_engine: Arc<Mutex<Option<Arc<BrowserEngine>>>>,
+    config: AppConfig,
 ) -> Result<WorkerResult, String> {
     // Acquire semaphore permit (wait if 3 tasks active)
     let _permit = semaphore
@@ -481,6 +785,30 @@ pub async fn submit_removal_task(
         .map_err(|e| format!("Failed to load removal attempt: {}", e))?
         .ok_or_else(|| format!("Removal attempt not found: {}", removal_attempt_id))?;
 
+    // Load broker definition
+    let broker_id = BrokerId::new(&removal_attempt.broker_id).map_err(|e| e.to_string())?;
+
+    let broker_def = broker_registry
+        .get(&broker_id)
+        .map_err(|e| format!("Failed to get broker definition: {}", e))?;
+
+    // Brokers demanding a government ID or an account are handed to the
+    // user without spending a submission attempt
+    if let Some(outcome) =
+        id_verification_outcome(&broker_def).or_else(|| account_required_outcome(&broker_def))
+    {
+        info!(
+            "Removal attempt {} needs user action; skipping submission",
+            removal_attempt_id
+        );
+        record_outcome(&db, &removal_attempt_id, &outcome).await?;
+        return Ok(WorkerResult {
+            removal_attempt_id,
+            outcome,
+            related_brokers: Vec::new(),
+        });
+    }
+
     // Load associated finding
     let finding = spectral_db::findings::get_by_id(db.pool(), &removal_attempt.finding_id)
         .await
@@ -488,29 +816,18 @@ pub async fn submit_removal_task(
         .ok_or_else(|| format!("Finding not found: {}", removal_attempt.finding_id))?;
 
     // Load profile
-    let profile_id = spectral_core::types::ProfileId::new(&finding.profile_id)
-        .map_err(|e| format!("Invalid profile ID: {}", e))?;
+    let profile_id =
+        spectral_core::types::ProfileId::new(&finding.profile_id).map_err(|e| e.to_string())?;
 
     let profile = vault
         .load_profile(&profile_id)
         .await
         .map_err(|e| format!("Failed to load profile: {}", e))?;
 
-    // Get encryption key from vault
-    let key = vault
-        .encryption_key()
-        .map_err(|e| format!("Failed to get encryption key: {}", e))?;
-
-    // Map fields for submission
-    let field_values = map_fields_for_submission(&profile, &finding.listing_url, key)?;
-
-    // Load broker definition
-    let broker_id = BrokerId::new(&removal_attempt.broker_id)
-        .map_err(|e| format!("Invalid broker ID: {}", e))?;
-
-    let broker_def = broker_registry
-        .get(&broker_id)
-        .map_err(|e| format!("Failed to get broker definition: {}", e))?;
+    // Map fields for submission, decrypting with the vault's key
+    let field_values = vault
+        .with_key(|key| map_fields_for_submission(&profile, &finding.listing_url, key))
+        .map_err(|e| format!("Failed to get encryption key: {}", e))??;
 
     // Route submission based on broker removal method
     let outcome = match &broker_def.removal {
@@ -519,6 +836,7 @@ pub async fn submit_removal_task(
                 "Routing removal attempt {} via browser-form",
                 removal_attempt_id
             );
+            let captcha_solver = load_captcha_solver(&db, &config.captcha).await;
             retry_with_backoff(
                 || async {
                     submit_via_browser(
@@ -527,6 +845,10 @@ pub async fn submit_removal_task(
                         &field_values,
                         &browser_engine,
                         &db,
+                        captcha_solver
+                            .as_ref()
+                            .map(|solver| solver as &dyn CaptchaSolver),
+                        config.browser.humanize_input,
                     )
                     .await
                 },
@@ -553,15 +875,49 @@ pub async fn submit_removal_task(
             )
             .await?
         }
+        RemovalMethod::Api { .. } => {
+            info!("Routing removal attempt {} via API", removal_attempt_id);
+            // API submissions are automated like browser forms, so they share
+            // the browser-automation permission.
+            let permission = PrivacyEngine::new(db.pool().clone())
+                .check_permission(Feature::BrowserAutomation)
+                .await
+                .map_err(|e| format!("Failed to check permission: {}", e))?;
+
+            match permission {
+                PermissionResult::Denied { reason } => RemovalOutcome::Failed {
+                    reason: "Automated submission not permitted".to_string(),
+                    error_details: Some(reason),
+                },
+                PermissionResult::Allowed => {
+                    let submitter = ApiRemovalSubmitter::new()
+                        .map_err(|e| format!("Failed to create API submitter: {}", e))?;
+
+                    retry_with_backoff(
+                        || async {
+                            submitter
+                                .submit(&broker_def, &field_values)
+                                .await
+                                .map_err(|e| format!("API submission failed: {}", e))
+                        },
+                        3,
+                    )
+                    .await?
+                }
+            }
+        }
         _ => {
             info!(
                 "Routing removal attempt {} via HTTP form",
                 removal_attempt_id
             );
             // Create WebFormSubmitter (creates its own browser engine)
-            let submitter = WebFormSubmitter::new()
+            let mut submitter = WebFormSubmitter::new()
                 .await
                 .map_err(|e| format!("Failed to create submitter: {}", e))?;
+            if let Some(solver) = load_captcha_solver(&db, &config.captcha).await {
+                submitter = submitter.with_captcha_solver(Box::new(solver));
+            }
 
             retry_with_backoff(
                 || async {
@@ -576,77 +932,55 @@ pub async fn submit_removal_task(
         }
     };
 
-    // Update database based on outcome
-    match &outcome {
-        RemovalOutcome::Submitted | RemovalOutcome::RequiresEmailVerification { .. } => {
-            let now = chrono::Utc::now();
-            removal_attempts::update_status(
-                db.pool(),
-                &removal_attempt_id,
-                RemovalStatus::Submitted,
-                Some(now),
-                None,
-                None,
-            )
-            .await
-            .map_err(|e| format!("Failed to update status to Submitted: {}", e))?;
-
-            info!("Removal submitted successfully: {}", removal_attempt_id);
-        }
-        RemovalOutcome::RequiresCaptcha { captcha_url } => {
-            // Keep status as Pending but set error message for CAPTCHA queue
-            removal_attempts::update_status(
-                db.pool(),
-                &removal_attempt_id,
-                RemovalStatus::Pending,
-                None,
-                None,
-                Some(format!("CAPTCHA_REQUIRED:{}", captcha_url)),
-            )
-            .await
-            .map_err(|e| format!("Failed to update for CAPTCHA: {}", e))?;
-
-            warn!("CAPTCHA required for removal: {}", removal_attempt_id);
-        }
-        RemovalOutcome::Failed { reason, .. } => {
-            // Mark as failed with error message
-            removal_attempts::update_status(
-                db.pool(),
-                &removal_attempt_id,
-                RemovalStatus::Failed,
-                None,
-                None,
-                Some(reason.clone()),
-            )
-            .await
-            .map_err(|e| format!("Failed to update status to Failed: {}", e))?;
-
-            error!("Removal failed: {} - {}", removal_attempt_id, reason);
-        }
-        RemovalOutcome::RequiresAccountCreation => {
-            // Treat as failed - account creation not supported
-            removal_attempts::update_status(
-                db.pool(),
-                &removal_attempt_id,
-                RemovalStatus::Failed,
-                None,
-                None,
-                Some("Account creation required (not supported)".to_string()),
-            )
-            .await
-            .map_err(|e| format!("Failed to update for account creation: {}", e))?;
-
+    record_outcome(&db, &removal_attempt_id, &outcome).await?;
+
+    // After repeated failures, draft a formal deletion request for the user
+    // to review; it is never sent from here
+    if matches!(outcome, RemovalOutcome::Failed { .. }) {
+        let (country, state) = vault
+            .with_key(|key| {
+                let state = profile
+                    .state
+                    .as_ref()
+                    .and_then(|state| state.decrypt(key).ok());
+                (spectral_scanner::profile_country(&profile, key), state)
+            })
+            .unwrap_or_default();
+        if let Err(e) = spectral_mail::legal::escalate_failed_removal(
+            db.pool(),
+            &removal_attempt_id,
+            &broker_def,
+            &field_values,
+            country.as_deref(),
+            state.as_deref(),
+            spectral_mail::legal::ESCALATION_THRESHOLD,
+        )
+        .await
+        {
             warn!(
-                "Account creation required (unsupported): {}",
-                removal_attempt_id
+                "Failed to draft legal request for {}: {}",
+                removal_attempt_id, e
             );
         }
     }
 
+    // Suggest, never submit, removals on sibling brokers
+    let related_brokers = match outcome {
+        RemovalOutcome::Submitted | RemovalOutcome::RequiresEmailVerification { .. } => {
+            broker_registry
+                .related(&broker_id)
+                .iter()
+                .map(|def| def.id().clone())
+                .collect()
+        }
+        _ => Vec::new(),
+    };
+
     // Return result (permit is dropped here, releasing semaphore)
     Ok(WorkerResult {
         removal_attempt_id,
         outcome,
+        related_brokers,
     })
 }
 
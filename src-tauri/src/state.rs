@@ -1,10 +1,13 @@
 //! Application state management.
 
-use spectral_broker::{BrokerDefinition, BrokerLoader, BrokerRegistry};
+use crate::capabilities::{build_registry, UnlockedVaults};
+use spectral_broker::{BrokerDefinition, BrokerLoader, BrokerRegistry, RemovalMethod};
+use spectral_core::{AppConfig, CapabilityRegistry, ConfigWatcher};
+use spectral_scanner::RateLimiter;
 use spectral_vault::Vault;
 use std::collections::HashMap;
 use std::path::PathBuf;
-use std::sync::{Arc, RwLock};
+use std::sync::{Arc, Mutex, RwLock};
 
 /// Global application state shared across all Tauri commands.
 #[allow(dead_code)] // Used by vault commands in later tasks
@@ -13,8 +16,9 @@ pub struct AppState {
     pub vaults_dir: PathBuf,
 
     /// Currently unlocked vaults: vault_id -> Vault
-    /// RwLock allows concurrent reads (status checks)
-    pub unlocked_vaults: RwLock<HashMap<String, Arc<Vault>>>,
+    /// RwLock allows concurrent reads (status checks); shared with the
+    /// capability probes
+    pub unlocked_vaults: UnlockedVaults,
 
     /// Shared browser engine for browser-form removal submissions.
     ///
@@ -26,6 +30,26 @@ pub struct AppState {
     /// Broker registry loaded from broker-definitions/ directory.
     /// Cached on startup for fast access across all commands.
     pub broker_registry: Arc<BrokerRegistry>,
+
+    /// Global request budget shared by every scan orchestrator.
+    /// Built from `ScanningConfig` so concurrent scans cannot exceed it together.
+    pub scan_rate_limiter: Arc<RateLimiter>,
+
+    /// Tasks forwarding each vault's database changes to the frontend:
+    /// vault_id -> task. At most one per vault, so no change is sent twice.
+    pub db_change_forwarders: Mutex<HashMap<String, tokio::task::AbortHandle>>,
+
+    /// App configuration, loaded on startup and replaced whenever the
+    /// config file changes. Read through [`AppState::config`].
+    pub config: Arc<RwLock<AppConfig>>,
+
+    /// Watcher keeping `config` in sync with the config file. `None` if the
+    /// file could not be watched, in which case changes need a restart.
+    pub config_watcher: Option<ConfigWatcher>,
+
+    /// Which features can run on this machine, with the reason for any that
+    /// cannot. Built with runtime probes by [`build_registry`].
+    pub capabilities: CapabilityRegistry,
 }
 
 #[allow(dead_code)] // Used by vault commands in later tasks
@@ -48,36 +72,110 @@ impl AppState {
         // Load broker definitions
         let broker_registry = Self::load_broker_registry();
 
+        let config = AppConfig::load().unwrap_or_else(|e| {
+            tracing::warn!("Failed to load config, using defaults: {}", e);
+            AppConfig::default()
+        });
+        let scan_rate_limiter = Arc::new(RateLimiter::from_config(&config.scanning));
+        let config = Arc::new(RwLock::new(config));
+        let config_watcher = Self::watch_config(Arc::clone(&config));
+        let unlocked_vaults: UnlockedVaults = Arc::new(RwLock::new(HashMap::new()));
+        let capabilities = build_registry(Arc::clone(&config), Arc::clone(&unlocked_vaults));
+
         Self {
             vaults_dir,
-            unlocked_vaults: RwLock::new(HashMap::new()),
+            unlocked_vaults,
             browser_engine: Arc::new(tokio::sync::Mutex::new(None)),
             broker_registry: Arc::new(broker_registry),
+            scan_rate_limiter,
+            db_change_forwarders: Mutex::new(HashMap::new()),
+            config,
+            config_watcher,
+            capabilities,
         }
     }
 
-    /// Load broker registry from broker-definitions/ directory.
+    /// Keep `config` up to date with the config file.
+    fn watch_config(config: Arc<RwLock<AppConfig>>) -> Option<ConfigWatcher> {
+        let path = match AppConfig::config_path() {
+            Ok(path) => path,
+            Err(e) => {
+                tracing::warn!("Not watching config for changes: {}", e);
+                return None;
+            }
+        };
+        let watched = AppConfig::watch(path, move |updated| {
+            *config
+                .write()
+                .expect("RwLock poisoned: another thread panicked while holding the lock") =
+                updated;
+        });
+        watched
+            .map_err(|e| tracing::warn!("Not watching config for changes: {}", e))
+            .ok()
+    }
+
+    /// Current app configuration.
+    ///
+    /// Returns a copy of the cached config, so changes to the config file
+    /// apply to the next caller without a restart.
+    pub fn config(&self) -> AppConfig {
+        self.config
+            .read()
+            .expect("RwLock poisoned: another thread panicked while holding the lock")
+            .clone()
+    }
+
+    /// Load broker registry from the embedded definitions, overridden by the
+    /// broker-definitions/ directory when present.
     ///
     /// Falls back to empty registry if loading fails.
     fn load_broker_registry() -> BrokerRegistry {
-        match BrokerLoader::with_default_dir() {
-            Ok(loader) => match BrokerRegistry::load_from(&loader) {
-                Ok(registry) => {
-                    tracing::info!("Loaded broker definitions successfully");
-                    registry
+        let loaded = BrokerLoader::with_default_dir()
+            .and_then(|loader| BrokerRegistry::load_with_errors(&loader));
+        match loaded {
+            Ok((registry, errors)) => {
+                for error in &errors {
+                    tracing::warn!("Skipped broker definition {}", error);
                 }
-                Err(e) => {
-                    tracing::warn!("Failed to load broker definitions: {}", e);
-                    BrokerRegistry::new()
-                }
-            },
+                tracing::info!(
+                    "Loaded {} broker definitions ({} skipped)",
+                    registry.count(),
+                    errors.len()
+                );
+                Self::check_email_templates(&registry);
+                registry
+            }
             Err(e) => {
-                tracing::warn!("Failed to find broker-definitions directory: {}", e);
+                tracing::warn!("Failed to load broker definitions: {}", e);
                 BrokerRegistry::new()
             }
         }
     }
 
+    /// Report email removal templates that use placeholders no profile
+    /// field can fill, so a broken definition shows up at startup rather
+    /// than in a sent email.
+    fn check_email_templates(registry: &BrokerRegistry) {
+        for definition in registry.get_all() {
+            let RemovalMethod::Email { subject, body, .. } = &definition.removal else {
+                continue;
+            };
+            for template in [subject, body] {
+                if let Err(unresolved) = spectral_mail::templates::validate_template(
+                    template,
+                    spectral_mail::templates::TEMPLATE_FIELDS,
+                ) {
+                    tracing::error!(
+                        "Broker {} email template has unresolved placeholders: {}",
+                        definition.id(),
+                        unresolved.join(", ")
+                    );
+                }
+            }
+        }
+    }
+
     /// Get all broker definitions.
     pub fn broker_definitions(&self) -> Vec<BrokerDefinition> {
         self.broker_registry.get_all()
@@ -143,6 +241,37 @@ impl AppState {
             .remove(vault_id)
     }
 
+    /// Lock every unlocked vault that has been idle for longer than
+    /// `timeout`, returning their IDs.
+    pub fn lock_idle_vaults(&self, timeout: std::time::Duration) -> Vec<String> {
+        let mut vaults = self
+            .unlocked_vaults
+            .write()
+            .expect("RwLock poisoned: another thread panicked while holding the lock");
+        let idle: Vec<String> = vaults
+            .iter()
+            .filter(|(_, vault)| vault.should_auto_lock(timeout))
+            .map(|(vault_id, _)| vault_id.clone())
+            .collect();
+        for vault_id in &idle {
+            vaults.remove(vault_id);
+        }
+        idle
+    }
+
+    /// Make `forwarder` the vault's only database change forwarder, stopping
+    /// the one it replaces.
+    pub fn replace_db_change_forwarder(&self, vault_id: &str, forwarder: tokio::task::AbortHandle) {
+        if let Some(previous) = self
+            .db_change_forwarders
+            .lock()
+            .expect("Mutex poisoned: another thread panicked while holding the lock")
+            .insert(vault_id.to_string(), forwarder)
+        {
+            previous.abort();
+        }
+    }
+
     /// Get a reference to an unlocked vault.
     pub fn get_vault(&self, vault_id: &str) -> Option<Arc<Vault>> {
         self.unlocked_vaults
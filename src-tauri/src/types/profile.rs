@@ -2,6 +2,7 @@ use chrono::{Datelike, NaiveDate};
 use once_cell::sync::Lazy;
 use serde::{Deserialize, Serialize};
 use spectral_core::error::SpectralError;
+use spectral_core::{AddressFormat, DEFAULT_COUNTRY};
 
 /// Input type for creating/updating a profile
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -14,8 +15,9 @@ pub struct ProfileInput {
     pub address_line1: String,
     pub address_line2: Option<String>,
     pub city: String,
-    pub state: String, // US state code (e.g., "CA")
+    pub state: String, // US state code (e.g., "CA"), or province/region outside the US
     pub zip_code: String,
+    pub country: Option<String>, // Country name or ISO code; US when absent
 }
 
 impl ProfileInput {
@@ -32,8 +34,18 @@ impl ProfileInput {
         if let Some(dob) = self.date_of_birth {
             validate_date_of_birth(dob)?;
         }
-        validate_us_state(&self.state)?;
-        validate_zip_code(&self.zip_code)?;
+        let address_format = AddressFormat::for_country(self.country.as_deref());
+        if address_format.country_code == DEFAULT_COUNTRY {
+            validate_us_state(&self.state)?;
+            validate_zip_code(&self.zip_code)?;
+        } else {
+            if address_format.has_states && self.state.trim().is_empty() {
+                return Err(SpectralError::Validation(
+                    "State or province is required".to_string(),
+                ));
+            }
+            validate_postcode(&self.zip_code, &address_format)?;
+        }
         Ok(())
     }
 }
@@ -52,8 +64,12 @@ pub struct ProfileOutput {
     pub city: String,
     pub state: String,
     pub zip_code: String,
+    pub country: Option<String>,
     pub created_at: String,
     pub updated_at: String,
+    /// Fields that could not be decrypted and were left empty
+    #[serde(default)]
+    pub unavailable_fields: Vec<String>,
 }
 
 /// Summary type for profile listings
@@ -63,6 +79,9 @@ pub struct ProfileSummary {
     pub full_name: String,
     pub email: String,
     pub created_at: String,
+    /// Fields that could not be decrypted and were left empty
+    #[serde(default)]
+    pub unavailable_fields: Vec<String>,
 }
 
 // Validation functions
@@ -159,6 +178,19 @@ pub fn validate_zip_code(zip: &str) -> Result<(), SpectralError> {
     Ok(())
 }
 
+pub fn validate_postcode(
+    postcode: &str,
+    address_format: &AddressFormat,
+) -> Result<(), SpectralError> {
+    if !address_format.is_valid_postcode(postcode) {
+        return Err(SpectralError::Validation(
+            "Invalid postcode format for country".to_string(),
+        ));
+    }
+
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -227,6 +259,7 @@ mod tests {
             city: "San Francisco".to_string(),
             state: "CA".to_string(),
             zip_code: "94102".to_string(),
+            country: None,
         };
         assert!(valid_input.validate().is_ok());
 
@@ -236,4 +269,35 @@ mod tests {
         };
         assert!(invalid_email.validate().is_err());
     }
+
+    #[test]
+    fn test_profile_input_validation_uk() {
+        let uk_input = ProfileInput {
+            first_name: "Jane".to_string(),
+            middle_name: None,
+            last_name: "Smith".to_string(),
+            email: "jane@example.co.uk".to_string(),
+            date_of_birth: None,
+            address_line1: "10 Downing Street".to_string(),
+            address_line2: None,
+            city: "London".to_string(),
+            state: String::new(),
+            zip_code: "SW1A 2AA".to_string(),
+            country: Some("United Kingdom".to_string()),
+        };
+        assert!(uk_input.validate().is_ok());
+
+        let us_zip = ProfileInput {
+            zip_code: "94102".to_string(),
+            ..uk_input.clone()
+        };
+        assert!(us_zip.validate().is_err());
+
+        let missing_province = ProfileInput {
+            zip_code: "K1A 0B1".to_string(),
+            country: Some("CA".to_string()),
+            ..uk_input
+        };
+        assert!(missing_province.validate().is_err());
+    }
 }
@@ -3,11 +3,12 @@
 //! Tests the process_removal_batch command and queue query commands
 //! to validate task spawning, database state, and queue filtering logic.
 
-use spectral_app::commands::scan::{get_captcha_queue, get_failed_queue};
+use spectral_app::commands::scan::{get_captcha_queue, get_failed_queue, get_user_action_queue};
 use spectral_app::state::AppState;
 use spectral_db::findings::create_finding;
 use spectral_db::removal_attempts::{
-    create_removal_attempt, get_by_id, update_status, RemovalStatus,
+    create_removal_attempt, get_by_id, update_status, RemovalStatus, ACCOUNT_REQUIRED_PREFIX,
+    ID_VERIFICATION_PREFIX,
 };
 use spectral_vault::Vault;
 use std::sync::Arc;
@@ -23,9 +24,16 @@ fn create_test_app() -> (tauri::App<tauri::test::MockRuntime>, TempDir) {
 
     let app_state = AppState {
         vaults_dir,
-        unlocked_vaults: std::sync::RwLock::new(std::collections::HashMap::new()),
+        unlocked_vaults: std::sync::Arc::new(std::sync::RwLock::new(
+            std::collections::HashMap::new(),
+        )),
         browser_engine: std::sync::Arc::new(tokio::sync::Mutex::new(None)),
         broker_registry: std::sync::Arc::new(spectral_broker::BrokerRegistry::new()),
+        scan_rate_limiter: std::sync::Arc::new(spectral_scanner::RateLimiter::default()),
+        db_change_forwarders: std::sync::Mutex::new(std::collections::HashMap::new()),
+        config: std::sync::Arc::new(std::sync::RwLock::new(spectral_core::AppConfig::default())),
+        config_watcher: None,
+        capabilities: spectral_core::CapabilityRegistry::new(),
     };
 
     let app = tauri::test::mock_app();
@@ -206,11 +214,11 @@ async fn test_queue_queries_return_correct_attempts() {
     // Update removal attempts to different statuses
     let db = vault.database().expect("get database");
 
-    // Set first to Pending with CAPTCHA error message
+    // Set first to blocked by a CAPTCHA
     update_status(
         db.pool(),
         &removal_attempt_ids[0],
-        RemovalStatus::Pending,
+        RemovalStatus::NeedsUserAction,
         None,
         None,
         Some("CAPTCHA_REQUIRED: reCAPTCHA v2 detected".to_string()),
@@ -242,7 +250,7 @@ async fn test_queue_queries_return_correct_attempts() {
         captcha_attempts[0].id, removal_attempt_ids[0],
         "Should return the CAPTCHA attempt"
     );
-    assert_eq!(captcha_attempts[0].status, RemovalStatus::Pending);
+    assert_eq!(captcha_attempts[0].status, RemovalStatus::NeedsUserAction);
     assert!(captcha_attempts[0]
         .error_message
         .as_ref()
@@ -261,3 +269,156 @@ async fn test_queue_queries_return_correct_attempts() {
     );
     assert_eq!(failed_attempts[0].status, RemovalStatus::Failed);
 }
+
+/// Broker definition for "test-broker" that only accepts removals with an ID upload.
+fn id_required_broker() -> spectral_broker::BrokerDefinition {
+    use spectral_broker::definition::{
+        BrokerCategory, BrokerMetadata, RemovalConfirmation, RemovalDifficulty, RemovalMethod,
+        SearchMethod,
+    };
+    use std::collections::HashMap;
+
+    spectral_broker::BrokerDefinition {
+        broker: BrokerMetadata {
+            id: spectral_core::BrokerId::new("test-broker").expect("valid broker ID"),
+            name: "Test Broker".to_string(),
+            url: "https://broker.example.com".to_string(),
+            domain: "broker.example.com".to_string(),
+            category: BrokerCategory::PeopleSearch,
+            difficulty: RemovalDifficulty::Hard,
+            typical_removal_days: 30,
+            recheck_interval_days: 30,
+            last_verified: chrono::NaiveDate::from_ymd_opt(2025, 1, 1).expect("valid date"),
+            scan_priority: spectral_broker::ScanPriority::OnRequest,
+            region_relevance: vec!["Global".to_string()],
+            countries: vec![],
+            requires_id_verification: true,
+            requires_account: false,
+            related_brokers: vec![],
+            request_headers: HashMap::new(),
+            legal_contact: None,
+        },
+        search: SearchMethod::Manual {
+            url: "https://broker.example.com/search".to_string(),
+            instructions: "Search by name".to_string(),
+        },
+        removal: RemovalMethod::BrowserForm {
+            url: "https://broker.example.com/opt-out".to_string(),
+            fields: std::collections::HashMap::new(),
+            form_selectors: Default::default(),
+            confirmation: Default::default(),
+            notes: String::new(),
+        },
+        fixture: None,
+        confirmation: RemovalConfirmation::None,
+    }
+}
+
+/// Broker definition for "test-broker" that only accepts removals from account holders.
+fn account_required_broker() -> spectral_broker::BrokerDefinition {
+    let mut broker_def = id_required_broker();
+    broker_def.broker.requires_id_verification = false;
+    broker_def.broker.requires_account = true;
+    broker_def
+}
+
+#[tokio::test]
+async fn test_id_required_broker_needs_user_action_without_submission() {
+    let (app, _temp_dir) = create_test_app();
+    let state: State<AppState> = app.state();
+    let vault_id = Uuid::new_v4().to_string();
+
+    create_test_vault(&state, &vault_id).await;
+    let vault = state.get_vault(&vault_id).expect("get vault");
+    let removal_attempt_ids =
+        setup_test_removal_structure(&vault, "profile-123", "scan-job-456", "broker-scan-789", 1)
+            .await;
+
+    let registry = spectral_broker::BrokerRegistry::new();
+    registry
+        .insert(id_required_broker())
+        .expect("insert broker");
+    let browser_engine = Arc::new(tokio::sync::Mutex::new(None));
+
+    let result = spectral_app::removal_worker::submit_removal_task(
+        vault.shared_database().expect("get database"),
+        vault.clone(),
+        removal_attempt_ids[0].clone(),
+        Arc::new(registry),
+        Arc::new(tokio::sync::Semaphore::new(1)),
+        browser_engine.clone(),
+        spectral_core::AppConfig::default(),
+    )
+    .await
+    .expect("worker result");
+
+    assert_eq!(
+        result.outcome,
+        spectral_broker::removal::RemovalOutcome::RequiresIdVerification {
+            instructions_url: "https://broker.example.com/opt-out".to_string()
+        }
+    );
+    // No browser was started, so no submission was attempted
+    assert!(browser_engine.lock().await.is_none());
+
+    let queue = get_user_action_queue(state.clone(), vault_id.clone())
+        .await
+        .expect("user action queue");
+    assert_eq!(queue.len(), 1);
+    assert_eq!(queue[0].id, removal_attempt_ids[0]);
+    assert_eq!(queue[0].status, RemovalStatus::NeedsUserAction);
+    assert_eq!(
+        queue[0].error_message.as_deref(),
+        Some(format!("{ID_VERIFICATION_PREFIX}https://broker.example.com/opt-out").as_str())
+    );
+}
+
+#[tokio::test]
+async fn test_account_required_broker_needs_user_action_without_submission() {
+    let (app, _temp_dir) = create_test_app();
+    let state: State<AppState> = app.state();
+    let vault_id = Uuid::new_v4().to_string();
+
+    create_test_vault(&state, &vault_id).await;
+    let vault = state.get_vault(&vault_id).expect("get vault");
+    let removal_attempt_ids =
+        setup_test_removal_structure(&vault, "profile-123", "scan-job-456", "broker-scan-789", 1)
+            .await;
+
+    let registry = spectral_broker::BrokerRegistry::new();
+    registry
+        .insert(account_required_broker())
+        .expect("insert broker");
+    let browser_engine = Arc::new(tokio::sync::Mutex::new(None));
+
+    let result = spectral_app::removal_worker::submit_removal_task(
+        vault.shared_database().expect("get database"),
+        vault.clone(),
+        removal_attempt_ids[0].clone(),
+        Arc::new(registry),
+        Arc::new(tokio::sync::Semaphore::new(1)),
+        browser_engine.clone(),
+        spectral_core::AppConfig::default(),
+    )
+    .await
+    .expect("worker result");
+
+    assert_eq!(
+        result.outcome,
+        spectral_broker::removal::RemovalOutcome::RequiresAccountCreation {
+            signup_url: "https://broker.example.com/opt-out".to_string()
+        }
+    );
+    // No browser was started, so the opt-out page was never visited
+    assert!(browser_engine.lock().await.is_none());
+
+    let queue = get_user_action_queue(state.clone(), vault_id.clone())
+        .await
+        .expect("user action queue");
+    assert_eq!(queue.len(), 1);
+    assert_eq!(queue[0].status, RemovalStatus::NeedsUserAction);
+    assert_eq!(
+        queue[0].error_message.as_deref(),
+        Some(format!("{ACCOUNT_REQUIRED_PREFIX}https://broker.example.com/opt-out").as_str())
+    );
+}
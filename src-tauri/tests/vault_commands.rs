@@ -14,9 +14,16 @@ fn create_test_app() -> (tauri::App<tauri::test::MockRuntime>, TempDir) {
 
     let app_state = AppState {
         vaults_dir,
-        unlocked_vaults: std::sync::RwLock::new(std::collections::HashMap::new()),
+        unlocked_vaults: std::sync::Arc::new(std::sync::RwLock::new(
+            std::collections::HashMap::new(),
+        )),
         browser_engine: std::sync::Arc::new(tokio::sync::Mutex::new(None)),
         broker_registry: std::sync::Arc::new(spectral_broker::BrokerRegistry::new()),
+        scan_rate_limiter: std::sync::Arc::new(spectral_scanner::RateLimiter::default()),
+        db_change_forwarders: std::sync::Mutex::new(std::collections::HashMap::new()),
+        config: std::sync::Arc::new(std::sync::RwLock::new(spectral_core::AppConfig::default())),
+        config_watcher: None,
+        capabilities: spectral_core::CapabilityRegistry::new(),
     };
 
     let app = tauri::test::mock_app();
@@ -0,0 +1,36 @@
+import { invoke } from '@tauri-apps/api/core';
+
+export interface MetricsSnapshot {
+	counters: Record<string, number>;
+	gauges: Record<string, number>;
+	captured_at: string;
+}
+
+export async function getMetrics(): Promise<MetricsSnapshot> {
+	return await invoke<MetricsSnapshot>('get_metrics');
+}
+
+export interface TableStat {
+	name: string;
+	row_count: number;
+}
+
+export interface StorageStats {
+	tables: TableStat[];
+	file_size_bytes: number;
+}
+
+export async function getStorageStats(vaultId: string): Promise<StorageStats> {
+	return await invoke<StorageStats>('get_storage_stats', { vaultId });
+}
+
+export type FeatureStatus = { status: 'available' } | { status: 'unavailable'; reason: string };
+
+export type FeatureAvailability = FeatureStatus & {
+	feature: string;
+	name: string;
+};
+
+export async function getCapabilities(): Promise<FeatureAvailability[]> {
+	return await invoke<FeatureAvailability[]>('get_capabilities');
+}
@@ -22,6 +22,7 @@ export interface FeatureFlags {
 	allow_email_sending: boolean;
 	allow_imap_monitoring: boolean;
 	allow_pii_scanning: boolean;
+	allow_background_execution: boolean;
 }
 
 /**
@@ -146,6 +147,22 @@ export async function setLlmApiKey(
 	return invoke('set_llm_api_key', { vaultId, provider, apiKey });
 }
 
+/**
+ * Set or clear the API key for the external CAPTCHA solving service
+ *
+ * Stores the API key encrypted in the vault database. The service is only
+ * used when `captcha.external_solver_enabled` is set in the config file.
+ *
+ * @param vaultId - The ID of the vault to update
+ * @param apiKey - The API key to store, or null to remove it
+ */
+export async function setCaptchaSolverApiKey(
+	vaultId: string,
+	apiKey: string | null
+): Promise<void> {
+	return invoke('set_captcha_solver_api_key', { vaultId, apiKey });
+}
+
 /**
  * Test connection to an LLM provider
  *
@@ -83,8 +83,9 @@ export interface ProfileInput {
 	address_line1: string;
 	address_line2?: string;
 	city: string;
-	state: string; // US state code (e.g., "CA")
+	state: string; // US state code (e.g., "CA"), or province/region outside the US
 	zip_code: string;
+	country?: string; // Country name or ISO code; US when omitted
 
 	// Phase 2 fields
 	phone_numbers?: PhoneNumber[];
@@ -109,8 +110,10 @@ export interface ProfileOutput {
 	city: string;
 	state: string;
 	zip_code: string;
+	country?: string; // ISO 3166-1 alpha-2 code
 	created_at: string; // RFC3339 timestamp
 	updated_at: string; // RFC3339 timestamp
+	unavailable_fields: string[]; // fields that could not be decrypted
 }
 
 /**
@@ -121,6 +124,7 @@ export interface ProfileSummary {
 	full_name: string;
 	email: string;
 	created_at: string; // RFC3339 timestamp
+	unavailable_fields: string[]; // fields that could not be decrypted
 }
 
 /**
@@ -177,6 +181,19 @@ export const profileAPI = {
 	 */
 	async list(vaultId: string): Promise<ProfileSummary[]> {
 		return await invoke<ProfileSummary[]>('profile_list', { vaultId });
+	},
+
+	/**
+	 * Import a new profile from a vCard or JSON document
+	 *
+	 * @param vaultId - The vault ID to create the profile in
+	 * @param format - Format of `data`
+	 * @param data - vCard text or JSON object text
+	 * @returns {ProfileOutput} The imported profile
+	 * @throws {CommandError} IMPORT_VALIDATION with `details.problems` listing unparseable values
+	 */
+	async import(vaultId: string, format: 'vcard' | 'json', data: string): Promise<ProfileOutput> {
+		return await invoke<ProfileOutput>('profile_import', { vaultId, format, data });
 	}
 };
 
@@ -10,13 +10,33 @@ export interface RemovalAttempt {
 	id: string;
 	finding_id: string;
 	broker_id: string;
-	status: 'Pending' | 'Processing' | 'Submitted' | 'Completed' | 'Failed';
+	status:
+		| 'Pending'
+		| 'Processing'
+		| 'Submitted'
+		| 'Completed'
+		| 'Failed'
+		| 'NeedsUserAction'
+		| 'Cancelled';
 	created_at: string;
 	submitted_at: string | null;
 	completed_at: string | null;
 	error_message: string | null;
 }
 
+export interface LegalRequestDraft {
+	id: string;
+	attempt_id: string;
+	broker_id: string;
+	broker_name: string;
+	jurisdiction: 'ccpa' | 'gdpr' | 'generic';
+	recipient: string;
+	subject: string;
+	body: string;
+	status: 'Draft' | 'Sent' | 'Discarded';
+	created_at: string;
+}
+
 export const removalAPI = {
 	/**
 	 * Process a batch of removal attempts
@@ -35,6 +55,13 @@ export const removalAPI = {
 		return await invoke<RemovalAttempt[]>('get_captcha_queue', { vaultId });
 	},
 
+	/**
+	 * Get removals waiting on the user, e.g. brokers that require ID verification
+	 */
+	async getUserActionQueue(vaultId: string): Promise<RemovalAttempt[]> {
+		return await invoke<RemovalAttempt[]>('get_user_action_queue', { vaultId });
+	},
+
 	/**
 	 * Get failed queue
 	 */
@@ -42,6 +69,30 @@ export const removalAPI = {
 		return await invoke<RemovalAttempt[]>('get_failed_queue', { vaultId });
 	},
 
+	/**
+	 * Get one page of removal attempts with a status, oldest first
+	 */
+	async getByStatus(
+		vaultId: string,
+		status: RemovalAttempt['status'],
+		limit: number,
+		offset: number
+	): Promise<RemovalAttempt[]> {
+		return await invoke<RemovalAttempt[]>('get_removal_attempts_by_status', {
+			vaultId,
+			status,
+			limit,
+			offset
+		});
+	},
+
+	/**
+	 * Count removal attempts with a status
+	 */
+	async countByStatus(vaultId: string, status: RemovalAttempt['status']): Promise<number> {
+		return await invoke<number>('count_removal_attempts_by_status', { vaultId, status });
+	},
+
 	/**
 	 * Retry a failed removal
 	 */
@@ -64,6 +115,24 @@ export const removalAPI = {
 	 */
 	async getJobHistory(vaultId: string): Promise<RemovalJobSummary[]> {
 		return await invoke<RemovalJobSummary[]>('get_removal_job_history', { vaultId });
+	},
+
+	/**
+	 * Get the legal requests waiting for review, with their text, oldest first
+	 */
+	async getLegalReviewQueue(vaultId: string): Promise<LegalRequestDraft[]> {
+		return await invoke<LegalRequestDraft[]>('get_legal_review_queue', { vaultId });
+	},
+
+	/**
+	 * Record that a legal request was sent or discarded
+	 */
+	async updateLegalRequestStatus(
+		vaultId: string,
+		requestId: string,
+		status: 'Sent' | 'Discarded'
+	): Promise<void> {
+		return await invoke('update_legal_request_status', { vaultId, requestId, status });
 	}
 };
 
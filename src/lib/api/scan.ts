@@ -26,6 +26,21 @@ export interface ExtractedData {
 	emails: string[];
 }
 
+export interface BulkVerifyResult {
+	updated: number;
+	missing: string[];
+}
+
+export type DbChange =
+	| { kind: 'scan_status_changed'; scan_job_id: string; status: ScanJobStatus['status'] }
+	| { kind: 'finding_created'; scan_job_id: string; finding_id: string; broker_id: string }
+	| { kind: 'removal_status_changed'; removal_attempt_id: string; status: string };
+
+export interface DbChangeEvent {
+	vault_id: string;
+	change: DbChange;
+}
+
 export const scanAPI = {
 	/**
 	 * Start a new scan job
@@ -48,6 +63,13 @@ export const scanAPI = {
 		});
 	},
 
+	/**
+	 * Start forwarding the vault's database changes as `db:change` events
+	 */
+	async listenForChanges(vaultId: string): Promise<void> {
+		return await invoke('listen_for_db_changes', { vaultId });
+	},
+
 	/**
 	 * Get findings for a scan job
 	 */
@@ -74,6 +96,21 @@ export const scanAPI = {
 		});
 	},
 
+	/**
+	 * Confirm or reject many findings at once
+	 */
+	async bulkVerify(
+		vaultId: string,
+		findingIds: string[],
+		isMatch: boolean
+	): Promise<BulkVerifyResult> {
+		return await invoke<BulkVerifyResult>('bulk_verify_findings', {
+			vaultId,
+			findingIds,
+			isMatch
+		});
+	},
+
 	/**
 	 * Submit removal requests for all confirmed findings
 	 */
@@ -91,14 +128,21 @@ export const scanAPI = {
 export async function startScan(
 	vaultId: string,
 	profileId: string,
-	options: { tier?: 'Tier1' | 'Tier2' | 'All'; brokerIds?: string[] } = {}
+	options: {
+		tier?: 'Tier1' | 'Tier2' | 'All';
+		brokerIds?: string[];
+		maxConcurrentScans?: number;
+		depth?: 'Quick' | 'Thorough';
+	} = {}
 ): Promise<string> {
 	const result = await invoke<ScanJobStatus>('start_scan', {
 		vaultId,
 		profileId,
 		brokerFilter: null,
 		tier: options.tier ?? null,
-		brokerIds: options.brokerIds ?? null
+		brokerIds: options.brokerIds ?? null,
+		maxConcurrentScans: options.maxConcurrentScans ?? null,
+		depth: options.depth ?? null
 	});
 	return result.id;
 }
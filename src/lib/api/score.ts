@@ -11,3 +11,15 @@ export interface PrivacyScoreResult {
 export async function getPrivacyScore(vaultId: string): Promise<PrivacyScoreResult> {
 	return await invoke<PrivacyScoreResult>('get_privacy_score', { vaultId });
 }
+
+export interface CategoryExposure {
+	category: string;
+	finding_count: number;
+	risk_weight: number;
+	weighted_exposure: number;
+	share: number;
+}
+
+export async function getExposureBreakdown(vaultId: string): Promise<CategoryExposure[]> {
+	return await invoke<CategoryExposure[]>('get_exposure_breakdown', { vaultId });
+}
@@ -18,6 +18,16 @@ export async function testImapConnection(
 	return await invoke('test_imap_connection', { host, port, username, password });
 }
 
+export async function saveImapSettings(
+	vaultId: string,
+	host: string,
+	port: number,
+	username: string,
+	password: string
+): Promise<void> {
+	return invoke('save_imap_settings', { vaultId, host, port, username, password });
+}
+
 export interface ScheduledJob {
 	id: string;
 	job_type: 'ScanAll' | 'VerifyRemovals' | 'PollImap';
@@ -25,6 +35,9 @@ export interface ScheduledJob {
 	next_run_at: string;
 	last_run_at: string | null;
 	enabled: boolean;
+	quiet_hours: { start: string; end: string } | null; // local "HH:MM:SS" times
+	skip_on_metered: boolean;
+	deferred_reason: 'quiet_hours' | 'metered_connection' | null;
 }
 
 export async function getScheduledJobs(vaultId: string): Promise<ScheduledJob[]> {
@@ -40,6 +53,22 @@ export async function updateScheduledJob(
 	return invoke('update_scheduled_job', { vaultId, jobId, intervalDays, enabled });
 }
 
+export async function updateJobConditions(
+	vaultId: string,
+	jobId: string,
+	quietHoursStart: string | null, // "HH:MM"
+	quietHoursEnd: string | null, // "HH:MM"
+	skipOnMetered: boolean
+): Promise<void> {
+	return invoke('update_job_conditions', {
+		vaultId,
+		jobId,
+		quietHoursStart,
+		quietHoursEnd,
+		skipOnMetered
+	});
+}
+
 export async function runJobNow(vaultId: string, jobType: string): Promise<void> {
 	return invoke('run_job_now', { vaultId, jobType });
 }
@@ -4,7 +4,7 @@
  * Manages removal attempt state including:
  * - Removal attempts list
  * - Real-time event updates from Tauri
- * - Queue filtering (submitted, captcha, failed, in-progress)
+ * - Queue filtering (submitted, captcha, user action, failed, in-progress)
  * - Loading and error states
  *
  * Uses Svelte 5 runes for reactive state management.
@@ -32,6 +32,12 @@ interface RemovalCaptchaEvent {
 	outcome: string;
 }
 
+interface RemovalUserActionEvent {
+	attempt_id: string;
+	kind: 'id_verification' | 'account';
+	instructions_url: string;
+}
+
 interface RemovalFailedEvent {
 	attempt_id: string;
 	error: string;
@@ -93,7 +99,13 @@ function createRemovalStore() {
 
 	const captchaQueue = $derived(
 		state.removalAttempts.filter(
-			(r) => r.status === 'Pending' && r.error_message?.startsWith('CAPTCHA_REQUIRED')
+			(r) => r.status === 'NeedsUserAction' && r.error_message?.startsWith('CAPTCHA_REQUIRED')
+		)
+	);
+
+	const userActionQueue = $derived(
+		state.removalAttempts.filter(
+			(r) => r.status === 'NeedsUserAction' && !r.error_message?.startsWith('CAPTCHA_REQUIRED')
 		)
 	);
 
@@ -129,6 +141,9 @@ function createRemovalStore() {
 		get captchaQueue() {
 			return captchaQueue;
 		},
+		get userActionQueue() {
+			return userActionQueue;
+		},
 		get failedQueue() {
 			return failedQueue;
 		},
@@ -140,15 +155,13 @@ function createRemovalStore() {
 		},
 		get allComplete() {
 			// True when all terminal states reached: nothing in progress,
-			// nothing in CAPTCHA queue, and nothing pending without CAPTCHA
-			const pendingWithoutCaptcha = state.removalAttempts.filter(
-				(r) => r.status === 'Pending' && !r.error_message?.startsWith('CAPTCHA_REQUIRED')
-			);
+			// nothing in CAPTCHA queue, and nothing pending
+			const pending = state.removalAttempts.filter((r) => r.status === 'Pending');
 			return (
 				state.removalAttempts.length > 0 &&
 				this.inProgress.length === 0 &&
 				this.captchaQueue.length === 0 &&
-				pendingWithoutCaptcha.length === 0
+				pending.length === 0
 			);
 		},
 
@@ -211,7 +224,8 @@ function createRemovalStore() {
 
 		/**
 		 * Set up Tauri event listeners for real-time removal updates
-		 * Listens for: removal:started, removal:success, removal:captcha, removal:failed, removal:retry
+		 * Listens for: removal:started, removal:success, removal:captcha, removal:user-action,
+		 * removal:failed, removal:retry
 		 */
 		async setupEventListeners(): Promise<void> {
 			// Clean up any existing listeners
@@ -238,12 +252,26 @@ function createRemovalStore() {
 			const unlistenCaptcha = await listen<RemovalCaptchaEvent>('removal:captcha', (event) => {
 				const captchaUrl = extractCaptchaUrl(event.payload.outcome);
 				this.updateAttempt(event.payload.attempt_id, {
-					status: 'Pending',
+					status: 'NeedsUserAction',
 					error_message: captchaUrl ? `CAPTCHA_REQUIRED:${captchaUrl}` : 'CAPTCHA_REQUIRED'
 				});
 			});
 			unlisteners.push(unlistenCaptcha);
 
+			// removal:user-action
+			const unlistenUserAction = await listen<RemovalUserActionEvent>(
+				'removal:user-action',
+				(event) => {
+					this.updateAttempt(event.payload.attempt_id, {
+						status: 'NeedsUserAction',
+						error_message: `${
+							event.payload.kind === 'account' ? 'ACCOUNT_REQUIRED' : 'ID_VERIFICATION_REQUIRED'
+						}:${event.payload.instructions_url}`
+					});
+				}
+			);
+			unlisteners.push(unlistenUserAction);
+
 			// removal:failed
 			const unlistenFailed = await listen<RemovalFailedEvent>('removal:failed', (event) => {
 				this.updateAttempt(event.payload.attempt_id, {
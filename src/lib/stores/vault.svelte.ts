@@ -12,6 +12,7 @@
  * @module $lib/stores/vault
  */
 
+import { listen, type UnlistenFn } from '@tauri-apps/api/event';
 import { listVaults, unlockVault, lockVault, createVault } from '$lib/api/vault';
 import type { VaultInfo } from '$lib/api/vault';
 import { profileStore } from '$lib/stores/profile.svelte';
@@ -44,6 +45,18 @@ function createVaultStore() {
 		error: null
 	});
 
+	/**
+	 * Forget a vault that the backend has locked
+	 */
+	function markLocked(vaultId: string) {
+		const newUnlocked = new Set(state.unlockedVaultIds);
+		newUnlocked.delete(vaultId);
+		state.unlockedVaultIds = newUnlocked;
+		if (state.currentVaultId === vaultId) {
+			state.currentVaultId = null;
+		}
+	}
+
 	return {
 		// Getters for reactive access
 		get currentVaultId() {
@@ -141,12 +154,7 @@ function createVaultStore() {
 			state.error = null;
 			try {
 				await lockVault(vaultId);
-				const newUnlocked = new Set(state.unlockedVaultIds);
-				newUnlocked.delete(vaultId);
-				state.unlockedVaultIds = newUnlocked;
-				if (state.currentVaultId === vaultId) {
-					state.currentVaultId = null;
-				}
+				markLocked(vaultId);
 			} catch (err) {
 				state.error = err instanceof Error ? err.message : 'Failed to lock vault';
 			} finally {
@@ -154,6 +162,17 @@ function createVaultStore() {
 			}
 		},
 
+		/**
+		 * Track vaults the backend locks on its own, such as on auto-lock
+		 *
+		 * @returns Function that stops listening
+		 */
+		async listenForLocks(): Promise<UnlistenFn> {
+			return listen<{ vault_id: string }>('vault:locked', (event) => {
+				markLocked(event.payload.vault_id);
+			});
+		},
+
 		/**
 		 * Set the current active vault
 		 *